@@ -49,6 +49,23 @@ pub fn text(token_stream: TokenStream) -> TokenStream {
     macro_impl(token_stream.into()).into()
 }
 
+#[proc_macro]
+pub fn rich_text(token_stream: TokenStream) -> TokenStream {
+    create_component_macro!(korangar_interface::components::rich_text::RichText, {
+        text: !,
+        color: { korangar_interface::theme::theme().text().color() },
+        highlight_color: { korangar_interface::theme::theme().text().highlight_color() },
+        height: { korangar_interface::theme::theme().text().height() },
+        font_size: { korangar_interface::theme::theme().text().font_size() },
+        horizontal_alignment: { korangar_interface::theme::theme().text().horizontal_alignment() },
+        vertical_alignment: { korangar_interface::theme::theme().text().vertical_alignment() },
+        overflow_behavior: { korangar_interface::theme::theme().text().overflow_behavior() },
+        handler: { () },
+    });
+
+    macro_impl(token_stream.into()).into()
+}
+
 #[proc_macro]
 pub fn button(token_stream: TokenStream) -> TokenStream {
     create_component_macro!(korangar_interface::components::button::Button, {
@@ -194,6 +211,92 @@ pub fn field(token_stream: TokenStream) -> TokenStream {
     macro_impl(token_stream.into()).into()
 }
 
+#[proc_macro]
+pub fn number_input(token_stream: TokenStream) -> TokenStream {
+    create_component_macro!(korangar_interface::components::number_input::NumberInput, {
+        text: !,
+        tooltip: { "" },
+        state: !,
+        minimum: !,
+        maximum: !,
+        step: !,
+        foreground_color: { korangar_interface::theme::theme().number_input().foreground_color() },
+        background_color: { korangar_interface::theme::theme().number_input().background_color() },
+        highlight_color: { korangar_interface::theme::theme().number_input().highlight_color() },
+        hovered_background_color: { korangar_interface::theme::theme().number_input().hovered_background_color() },
+        button_foreground_color: { korangar_interface::theme::theme().number_input().button_foreground_color() },
+        button_background_color: { korangar_interface::theme::theme().number_input().button_background_color() },
+        hovered_button_background_color: { korangar_interface::theme::theme().number_input().hovered_button_background_color() },
+        shadow_color: { korangar_interface::theme::theme().number_input().shadow_color() },
+        shadow_padding: { korangar_interface::theme::theme().number_input().shadow_padding() },
+        height: { korangar_interface::theme::theme().number_input().height() },
+        corner_diameter: { korangar_interface::theme::theme().number_input().corner_diameter() },
+        font_size: { korangar_interface::theme::theme().number_input().font_size() },
+        horizontal_alignment: { korangar_interface::theme::theme().number_input().horizontal_alignment() },
+        vertical_alignment: { korangar_interface::theme::theme().number_input().vertical_alignment() },
+        overflow_behavior: { korangar_interface::theme::theme().number_input().overflow_behavior() },
+    });
+
+    macro_impl(token_stream.into()).into()
+}
+
+#[proc_macro]
+pub fn progress_bar(token_stream: TokenStream) -> TokenStream {
+    create_component_macro!(korangar_interface::components::progress_bar::ProgressBar, {
+        text: { "" },
+        tooltip: { "" },
+        value: !,
+        maximum: !,
+        foreground_color: { korangar_interface::theme::theme().progress_bar().foreground_color() },
+        background_color: { korangar_interface::theme::theme().progress_bar().background_color() },
+        highlight_color: { korangar_interface::theme::theme().progress_bar().highlight_color() },
+        bar_color: { korangar_interface::theme::theme().progress_bar().bar_color() },
+        shadow_color: { korangar_interface::theme::theme().progress_bar().shadow_color() },
+        shadow_padding: { korangar_interface::theme::theme().progress_bar().shadow_padding() },
+        height: { korangar_interface::theme::theme().progress_bar().height() },
+        corner_diameter: { korangar_interface::theme::theme().progress_bar().corner_diameter() },
+        border: { korangar_interface::theme::theme().progress_bar().border() },
+        font_size: { korangar_interface::theme::theme().progress_bar().font_size() },
+        horizontal_alignment: { korangar_interface::theme::theme().progress_bar().horizontal_alignment() },
+        vertical_alignment: { korangar_interface::theme::theme().progress_bar().vertical_alignment() },
+        overflow_behavior: { korangar_interface::theme::theme().progress_bar().overflow_behavior() },
+    });
+
+    macro_impl(token_stream.into()).into()
+}
+
+#[proc_macro]
+pub fn slider(token_stream: TokenStream) -> TokenStream {
+    create_component_macro!(korangar_interface::components::slider::Slider, {
+        text: !,
+        tooltip: { "" },
+        state: !,
+        minimum: !,
+        maximum: !,
+        step: !,
+        foreground_color: { korangar_interface::theme::theme().slider().foreground_color() },
+        background_color: { korangar_interface::theme::theme().slider().background_color() },
+        highlight_color: { korangar_interface::theme::theme().slider().highlight_color() },
+        hovered_background_color: { korangar_interface::theme::theme().slider().hovered_background_color() },
+        rail_color: { korangar_interface::theme::theme().slider().rail_color() },
+        filled_rail_color: { korangar_interface::theme::theme().slider().filled_rail_color() },
+        knob_color: { korangar_interface::theme::theme().slider().knob_color() },
+        hovered_knob_color: { korangar_interface::theme::theme().slider().hovered_knob_color() },
+        shadow_color: { korangar_interface::theme::theme().slider().shadow_color() },
+        shadow_padding: { korangar_interface::theme::theme().slider().shadow_padding() },
+        height: { korangar_interface::theme::theme().slider().height() },
+        corner_diameter: { korangar_interface::theme::theme().slider().corner_diameter() },
+        rail_height: { korangar_interface::theme::theme().slider().rail_height() },
+        knob_width: { korangar_interface::theme::theme().slider().knob_width() },
+        font_size: { korangar_interface::theme::theme().slider().font_size() },
+        horizontal_alignment: { korangar_interface::theme::theme().slider().horizontal_alignment() },
+        vertical_alignment: { korangar_interface::theme::theme().slider().vertical_alignment() },
+        overflow_behavior: { korangar_interface::theme::theme().slider().overflow_behavior() },
+    });
+
+    macro_impl(token_stream.into()).into()
+}
+
 #[proc_macro]
 pub fn split(token_stream: TokenStream) -> TokenStream {
     create_component_macro!(korangar_interface::components::split::Split, {
@@ -231,6 +334,8 @@ pub fn text_box(token_stream: TokenStream) -> TokenStream {
         ghost_foreground_color: { korangar_interface::theme::theme().text_box().ghost_foreground_color() },
         hide_icon_color: { korangar_interface::theme::theme().text_box().hide_icon_color() },
         hovered_hide_icon_color: { korangar_interface::theme::theme().text_box().hovered_hide_icon_color() },
+        caret_color: { korangar_interface::theme::theme().text_box().caret_color() },
+        selection_color: { korangar_interface::theme::theme().text_box().selection_color() },
         shadow_color: { korangar_interface::theme::theme().text_box().shadow_color() },
         shadow_padding: { korangar_interface::theme::theme().text_box().shadow_padding() },
         height: { korangar_interface::theme::theme().text_box().height() },
@@ -255,6 +360,39 @@ pub fn tabs(token_stream: TokenStream) -> TokenStream {
     macro_impl(token_stream.into()).into()
 }
 
+#[proc_macro]
+pub fn table(token_stream: TokenStream) -> TokenStream {
+    create_component_macro!(korangar_interface::components::table::Table, {
+        rows: !,
+        columns: !,
+        foreground_color: { korangar_interface::theme::theme().table().foreground_color() },
+        background_color: { korangar_interface::theme::theme().table().background_color() },
+        highlight_color: { korangar_interface::theme::theme().table().highlight_color() },
+        header_foreground_color: { korangar_interface::theme::theme().table().header_foreground_color() },
+        header_background_color: { korangar_interface::theme::theme().table().header_background_color() },
+        hovered_header_background_color: { korangar_interface::theme::theme().table().hovered_header_background_color() },
+        row_background_color: { korangar_interface::theme::theme().table().row_background_color() },
+        alternate_row_background_color: { korangar_interface::theme::theme().table().alternate_row_background_color() },
+        hovered_row_background_color: { korangar_interface::theme::theme().table().hovered_row_background_color() },
+        separator_color: { korangar_interface::theme::theme().table().separator_color() },
+        icon_color: { korangar_interface::theme::theme().table().icon_color() },
+        shadow_color: { korangar_interface::theme::theme().table().shadow_color() },
+        shadow_padding: { korangar_interface::theme::theme().table().shadow_padding() },
+        corner_diameter: { korangar_interface::theme::theme().table().corner_diameter() },
+        header_height: { korangar_interface::theme::theme().table().header_height() },
+        row_height: { korangar_interface::theme::theme().table().row_height() },
+        maximum_height: { korangar_interface::theme::theme().table().maximum_height() },
+        minimum_column_width: { korangar_interface::theme::theme().table().minimum_column_width() },
+        icon_size: { korangar_interface::theme::theme().table().icon_size() },
+        font_size: { korangar_interface::theme::theme().table().font_size() },
+        horizontal_alignment: { korangar_interface::theme::theme().table().horizontal_alignment() },
+        vertical_alignment: { korangar_interface::theme::theme().table().vertical_alignment() },
+        overflow_behavior: { korangar_interface::theme::theme().table().overflow_behavior() },
+    });
+
+    macro_impl(token_stream.into()).into()
+}
+
 #[proc_macro]
 pub fn either(token_stream: TokenStream) -> TokenStream {
     create_component_macro!(korangar_interface::components::either::Either, {
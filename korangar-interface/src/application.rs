@@ -1,4 +1,5 @@
 use crate::layout::Icon;
+use crate::markup::InlineArea;
 use crate::theme::ThemePathGetter;
 use crate::window::Anchor;
 
@@ -64,6 +65,12 @@ pub trait Application: Sized + 'static {
     /// where the cache is saved to and loaded from a file.
     type Cache: WindowCache<Self>;
 
+    /// Clipboard used by text boxes to copy, cut, and paste text.
+    ///
+    /// This can be backed by the clipboard of the operating system or kept
+    /// inside the application.
+    type Clipboard: Clipboard;
+
     /// Type to specify the theme when creating a window.
     ///
     /// Typically this would be an enum with each variant being one of the
@@ -82,7 +89,7 @@ pub trait Application: Sized + 'static {
     ///
     /// Ideally this should be the same type that the application renderer uses
     /// to represent color.
-    type Color: Copy + serde_gate::Bound + element_gate::Bound<Self>;
+    type Color: Color + serde_gate::Bound + element_gate::Bound<Self>;
 
     /// Application corner diameter type.
     ///
@@ -199,6 +206,34 @@ where
 
     /// Update the size of a registered window.
     fn update_size(&mut self, window_class: App::WindowClass, size: App::Size);
+
+    /// Attempt to get the class of the window that a given window is docked
+    /// with. Windows are put back into the same tab group when they are
+    /// opened while the other window is open.
+    fn get_tab_group(&self, _window_class: App::WindowClass) -> Option<App::WindowClass> {
+        None
+    }
+
+    /// Update the class of the window that a registered window is docked
+    /// with. [`None`] if the window is no longer docked.
+    fn update_tab_group(&mut self, _window_class: App::WindowClass, _docked_with: Option<App::WindowClass>) {}
+}
+
+/// A type for exchanging text with the clipboard.
+///
+/// [`korangar_interface`] does not depend on any specific behavior of the
+/// clipboard, therefore the actual logic of this is up to the implementer.
+/// The implementer may choose to use the clipboard of the operating system or
+/// to only share text inside the application.
+pub trait Clipboard {
+    /// Create a new instance of the clipboard.
+    fn create() -> Self;
+
+    /// Get the current text of the clipboard, if there is any.
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Replace the contents of the clipboard with the given text.
+    fn set_text(&mut self, text: String);
 }
 
 /// Glue between [`korangar_interface`] and the renderer of the application.
@@ -260,6 +295,36 @@ pub trait TextLayouter<App: Application>: Clone {
         available_width: f32,
         overflow_behavior: App::OverflowBehavior,
     ) -> (App::Size, App::FontSize);
+
+    /// Calculate the areas of the links and icons in a text with
+    /// [`markup`](crate::markup), relative to the top left corner of the text.
+    /// Takes the same arguments as
+    /// [`get_text_dimensions`](TextLayouter::get_text_dimensions).
+    ///
+    /// The default implementation returns no areas, so links can't be clicked
+    /// and icons are not drawn.
+    fn get_inline_areas(
+        &self,
+        _text: &str,
+        _default_color: App::Color,
+        _highlight_color: App::Color,
+        _font_size: App::FontSize,
+        _available_width: f32,
+        _overflow_behavior: App::OverflowBehavior,
+    ) -> Vec<InlineArea> {
+        Vec::new()
+    }
+}
+
+/// Color used to render rectangles, text, and icons.
+pub trait Color: Copy {
+    /// Multiply the alpha channel of the color. Used to fade elements in and
+    /// out.
+    fn multiply_alpha(&self, factor: f32) -> Self;
+
+    /// Linearly interpolate between this color and another color. An amount of
+    /// `0.0` yields this color and an amount of `1.0` yields the other color.
+    fn interpolate(&self, other: Self, amount: f32) -> Self;
 }
 
 /// Size for text elements.
@@ -1,14 +1,17 @@
+use std::cell::Cell;
 use std::marker::PhantomData;
 
 use rust_state::{RustState, Selector, State};
 
-use crate::application::{Application, Size};
+use crate::application::{Application, Color, Size};
 use crate::element::Element;
-use crate::element::store::{ElementStore, ElementStoreMut};
+use crate::element::store::{ElementStore, ElementStoreMut, Persistent, PersistentExt};
 use crate::event::ClickHandler;
 use crate::layout::alignment::{HorizontalAlignment, VerticalAlignment};
 use crate::layout::tooltip::TooltipExt;
 use crate::layout::{MouseButton, Resolvers, WindowLayout, with_single_resolver};
+use crate::theme::{ThemePathGetter, theme};
+use crate::tween::{AnimationThemePathExt, Tween, animation_duration};
 
 #[derive(RustState)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -33,6 +36,25 @@ where
     pub overflow_behavior: App::OverflowBehavior,
 }
 
+pub struct ButtonData {
+    /// Progress of the transition to the hovered colors.
+    hover: Cell<Tween<f32>>,
+}
+
+impl Default for ButtonData {
+    fn default() -> Self {
+        Self {
+            hover: Cell::new(Tween::new(0.0)),
+        }
+    }
+}
+
+impl<Text, Tooltip, DisabledTooltip, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T> Persistent
+    for Button<Text, Tooltip, DisabledTooltip, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T>
+{
+    type Data = ButtonData;
+}
+
 pub struct Button<Text, Tooltip, DisabledTooltip, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T> {
     text_marker: PhantomData<(Text, Tooltip, DisabledTooltip)>,
     text: A,
@@ -168,13 +190,17 @@ where
     fn lay_out<'a>(
         &'a self,
         state: &'a State<App>,
-        _: ElementStore<'a>,
+        store: ElementStore<'a>,
         layout_info: &'a Self::LayoutInfo,
         layout: &mut WindowLayout<'a, App>,
     ) {
         let is_hoverered = layout_info.area.check().run(layout);
         let is_disabled = *state.get(&self.disabled);
 
+        if !is_disabled {
+            layout.register_focusable(store.get_element_id(), layout_info.area, Some(&self.event));
+        }
+
         if is_hoverered {
             struct ButtonTooltip;
 
@@ -193,10 +219,28 @@ where
             }
         }
 
-        let background_color = match is_hoverered {
-            _ if is_disabled => *state.get(&self.disabled_background_color),
-            true => *state.get(&self.hovered_background_color),
-            false => *state.get(&self.background_color),
+        let hover_progress = {
+            let persistent = self.get_persistent_data(&store, ());
+            let easing = *state.get(&theme().animation().easing());
+            let duration = animation_duration(*state.get(&theme().animation().hover_duration()));
+
+            let target = match is_hoverered {
+                true => 1.0,
+                false => 0.0,
+            };
+
+            let mut hover = persistent.hover.get();
+            hover.set(target, duration, easing);
+            persistent.hover.set(hover);
+
+            hover.value()
+        };
+
+        let background_color = match is_disabled {
+            true => *state.get(&self.disabled_background_color),
+            false => state
+                .get(&self.background_color)
+                .interpolate(*state.get(&self.hovered_background_color), hover_progress),
         };
 
         layout.add_rectangle(
@@ -207,10 +251,11 @@ where
             *state.get(&self.shadow_padding),
         );
 
-        let foreground_color = match is_hoverered {
-            _ if is_disabled => *state.get(&self.disabled_foreground_color),
-            true => *state.get(&self.hovered_foreground_color),
-            false => *state.get(&self.foreground_color),
+        let foreground_color = match is_disabled {
+            true => *state.get(&self.disabled_foreground_color),
+            false => state
+                .get(&self.foreground_color)
+                .interpolate(*state.get(&self.hovered_foreground_color), hover_progress),
         };
 
         layout.add_text(
@@ -12,6 +12,8 @@ use crate::layout::area::Area;
 use crate::layout::tooltip::TooltipExt;
 use crate::layout::{Icon, MouseButton, Resolver, Resolvers, WindowLayout, with_single_resolver};
 use crate::prelude::EventQueue;
+use crate::theme::{ThemePathGetter, theme};
+use crate::tween::{AnimationThemePathExt, Tween, animation_duration};
 
 const CHILDREN_STORE_ID: u64 = 0;
 const EXTRA_STORE_ID: u64 = 1;
@@ -43,14 +45,23 @@ where
 
 pub struct CollapsibleData {
     expanded: Cell<bool>,
+    /// Progress of expanding the collapsible. Goes from `0.0` when collapsed
+    /// to `1.0` when expanded.
+    expansion: Cell<Tween<f32>>,
 }
 
 impl PersistentData for CollapsibleData {
     type Inputs = bool;
 
     fn from_inputs(inputs: Self::Inputs) -> Self {
+        let expansion = match inputs {
+            true => 1.0,
+            false => 0.0,
+        };
+
         Self {
             expanded: Cell::new(inputs),
+            expansion: Cell::new(Tween::new(expansion)),
         }
     }
 }
@@ -78,6 +89,9 @@ where
     area: Area,
     title_height: f32,
     expanded: bool,
+    /// Set while the collapsible is expanding or collapsing, in which case the
+    /// children are clipped to the area.
+    is_animating: bool,
     font_size: App::FontSize,
     children: Option<C>,
     extra_elements: E,
@@ -228,8 +242,25 @@ where
 
             let title_height = state.get(&self.title_height).max(size.height());
 
-            let (area, children) = match expanded && self.children.get_element_count(state) > 0 {
-                true => resolver.with_derived_borderless(*state.get(&self.gaps), *state.get(&self.border), 0.0, |resolver| {
+            let easing = *state.get(&theme().animation().easing());
+            let duration = animation_duration(*state.get(&theme().animation().collapsible_duration()));
+            let target = match expanded {
+                true => 1.0,
+                false => 0.0,
+            };
+
+            let mut expansion = persistent.expansion.get();
+            expansion.set(target, duration, easing);
+            persistent.expansion.set(expansion);
+
+            let progress = expansion.value();
+
+            // The children are laid out into a copy of the resolver first, so we know their
+            // height before committing the animated height. While collapsing, the children
+            // stay visible until the animation is finished.
+            let mut children_resolver = resolver.clone();
+            let (children_area, children) = match progress > 0.0 && self.children.get_element_count(state) > 0 {
+                true => children_resolver.with_derived_borderless(*state.get(&self.gaps), *state.get(&self.border), 0.0, |resolver| {
                     resolver.push_top(title_height);
 
                     // We need to create a separate store so that the children and the extra
@@ -239,9 +270,16 @@ where
 
                     Some(self.children.create_layout_info(state, children_store, resolver))
                 }),
-                false => (resolver.with_height(title_height), None),
+                false => (children_resolver.with_height(title_height), None),
             };
 
+            let is_animating = progress < 1.0 && children.is_some();
+            let height = match is_animating {
+                true => title_height + (children_area.height - title_height) * progress,
+                false => children_area.height,
+            };
+            let area = resolver.with_height(height);
+
             // TODO: Figure out a better way to space the elements from the right.
             let extra_space = 40.0;
             let extra_area = Area {
@@ -262,6 +300,7 @@ where
                 area,
                 title_height,
                 expanded,
+                is_animating,
                 font_size,
                 children,
                 extra_elements,
@@ -277,10 +316,16 @@ where
         layout: &mut WindowLayout<'a, App>,
     ) {
         let use_secondary_color = layout.with_secondary_background(|layout| {
-            if let Some(layout_info) = &layout_info.children {
-                layout.with_layer(|layout| {
-                    let children_store = store.child_store(CHILDREN_STORE_ID);
-                    self.children.lay_out(state, children_store, layout_info, layout);
+            if let Some(children_layout_info) = &layout_info.children {
+                layout.with_layer(|layout| match layout_info.is_animating {
+                    true => layout.with_clip(layout_info.area, |layout| {
+                        let children_store = store.child_store(CHILDREN_STORE_ID);
+                        self.children.lay_out(state, children_store, children_layout_info, layout);
+                    }),
+                    false => {
+                        let children_store = store.child_store(CHILDREN_STORE_ID);
+                        self.children.lay_out(state, children_store, children_layout_info, layout);
+                    }
                 });
             }
         });
@@ -328,6 +373,9 @@ where
             self.extra_elements.lay_out(state, extra_store, &layout_info.extra_elements, layout);
         });
 
+        let persistent = self.get_persistent_data(&store, *state.get(&self.initially_expanded));
+        layout.register_focusable(store.get_element_id(), title_area, Some(persistent));
+
         let is_title_hovered = title_area.check().run(layout);
 
         if is_title_hovered {
@@ -338,7 +386,6 @@ where
                 layout.add_tooltip(tooltip, CollapsibleTooltip.tooltip_id());
             }
 
-            let persistent = self.get_persistent_data(&store, *state.get(&self.initially_expanded));
             layout.register_click_handler(MouseButton::Left, persistent);
         }
 
@@ -0,0 +1,409 @@
+//! Context menus that open at the mouse position. Any element can open one
+//! by registering a click handler that queues
+//! [`Event::OpenContextMenu`](crate::event::Event::OpenContextMenu), usually
+//! for [`MouseButton::Right`]. Menus for things outside of the interface, like
+//! entities in the world, are opened with
+//! [`InterfaceFrame::open_context_menu`](crate::InterfaceFrame::open_context_menu).
+
+use std::cell::Cell;
+
+use rust_state::{RustState, State};
+
+use crate::application::{Application, CornerDiameter, ShadowPadding, Size};
+use crate::element::Element;
+use crate::element::store::{ElementStore, ElementStoreMut};
+use crate::event::{ClickHandler, Event, EventQueue};
+use crate::layout::alignment::{HorizontalAlignment, VerticalAlignment};
+use crate::layout::area::Area;
+use crate::layout::{Icon, MouseButton, Resolvers, WindowLayout, with_single_resolver};
+use crate::theme::{ThemePathGetter, theme};
+
+#[derive(RustState)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ContextMenuTheme<App>
+where
+    App: Application,
+{
+    pub foreground_color: App::Color,
+    pub disabled_foreground_color: App::Color,
+    pub highlight_color: App::Color,
+    pub background_color: App::Color,
+    pub hovered_background_color: App::Color,
+    pub separator_color: App::Color,
+    pub shadow_color: App::Color,
+    pub shadow_padding: App::ShadowPadding,
+    pub corner_diameter: App::CornerDiameter,
+    pub item_corner_diameter: App::CornerDiameter,
+    pub width: f32,
+    pub border: f32,
+    pub item_height: f32,
+    pub separator_height: f32,
+    pub icon_size: f32,
+    pub font_size: App::FontSize,
+    pub horizontal_alignment: HorizontalAlignment,
+    pub vertical_alignment: VerticalAlignment,
+    pub overflow_behavior: App::OverflowBehavior,
+}
+
+/// Runs the action of an item and closes the menu.
+struct ActionHandler<App: Application> {
+    action: Box<dyn ClickHandler<App>>,
+}
+
+impl<App> ClickHandler<App> for ActionHandler<App>
+where
+    App: Application,
+{
+    fn handle_click(&self, state: &State<App>, queue: &mut EventQueue<App>) {
+        self.action.handle_click(state, queue);
+        queue.queue(Event::CloseOverlay);
+    }
+}
+
+/// Opens a submenu when its item is clicked or activated.
+#[derive(Default)]
+struct SubmenuHandler {
+    requested: Cell<bool>,
+}
+
+impl<App> ClickHandler<App> for SubmenuHandler
+where
+    App: Application,
+{
+    fn handle_click(&self, _: &State<App>, _: &mut EventQueue<App>) {
+        self.requested.set(true);
+    }
+}
+
+enum ContextMenuItem<App: Application> {
+    Action {
+        text: String,
+        /// `None` if the item is disabled.
+        handler: Option<ActionHandler<App>>,
+    },
+    Submenu {
+        text: String,
+        menu: ContextMenu<App>,
+        handler: SubmenuHandler,
+    },
+    Separator,
+}
+
+/// Menu with a list of actions, separators and nested submenus.
+///
+/// ```ignore
+/// let menu = ContextMenu::new()
+///     .item("Equip", equip_action)
+///     .disabled_item("Drop")
+///     .separator()
+///     .submenu("Move to", ContextMenu::new().item("Cart", move_to_cart_action));
+///
+/// queue.queue(Event::OpenContextMenu { menu });
+/// ```
+pub struct ContextMenu<App: Application> {
+    items: Vec<ContextMenuItem<App>>,
+    /// Index of the submenu that is currently open.
+    open_submenu: Cell<Option<usize>>,
+}
+
+impl<App> Default for ContextMenu<App>
+where
+    App: Application,
+{
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            open_submenu: Cell::new(None),
+        }
+    }
+}
+
+impl<App> ContextMenu<App>
+where
+    App: Application,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item that runs the action when clicked. The menu is closed
+    /// afterwards.
+    pub fn item(mut self, text: impl Into<String>, action: impl ClickHandler<App> + 'static) -> Self {
+        self.items.push(ContextMenuItem::Action {
+            text: text.into(),
+            handler: Some(ActionHandler { action: Box::new(action) }),
+        });
+        self
+    }
+
+    /// Add an item that is displayed but can't be clicked.
+    pub fn disabled_item(mut self, text: impl Into<String>) -> Self {
+        self.items.push(ContextMenuItem::Action {
+            text: text.into(),
+            handler: None,
+        });
+        self
+    }
+
+    /// Add an item that opens another menu next to this one.
+    pub fn submenu(mut self, text: impl Into<String>, menu: ContextMenu<App>) -> Self {
+        self.items.push(ContextMenuItem::Submenu {
+            text: text.into(),
+            menu,
+            handler: SubmenuHandler::default(),
+        });
+        self
+    }
+
+    /// Add a line separating groups of items.
+    pub fn separator(mut self) -> Self {
+        self.items.push(ContextMenuItem::Separator);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn item_height(item: &ContextMenuItem<App>, state: &State<App>) -> f32 {
+        match item {
+            ContextMenuItem::Separator => *state.get(&theme().context_menu().separator_height()),
+            _ => *state.get(&theme().context_menu().item_height()),
+        }
+    }
+
+    /// Get the space needed to display the menu with any of its submenus open.
+    pub(crate) fn size(&self, state: &State<App>) -> App::Size {
+        let width = *state.get(&theme().context_menu().width());
+        let border = *state.get(&theme().context_menu().border());
+
+        let mut total_width = width;
+        let mut total_height = border * 2.0;
+        let mut item_top = border;
+
+        for item in &self.items {
+            if let ContextMenuItem::Submenu { menu, .. } = item {
+                // Submenus are aligned with the top of the panel around their items.
+                let submenu_size = menu.size(state);
+                total_width = total_width.max(width + submenu_size.width());
+                total_height = total_height.max(item_top - border + submenu_size.height());
+            }
+
+            let item_height = Self::item_height(item, state);
+            item_top += item_height;
+            total_height = total_height.max(item_top + border);
+        }
+
+        App::Size::new(total_width, total_height)
+    }
+
+    fn create_layout_info(&self, state: &State<App>, mut store: ElementStoreMut, left: f32, top: f32) -> MenuLayoutInfo {
+        let width = *state.get(&theme().context_menu().width());
+        let border = *state.get(&theme().context_menu().border());
+
+        let mut item_top = top + border;
+        let mut item_areas = Vec::with_capacity(self.items.len());
+        let mut submenus = Vec::with_capacity(self.items.len());
+
+        for (index, item) in self.items.iter().enumerate() {
+            let item_height = Self::item_height(item, state);
+            let item_store = store.child_store(index as u64);
+
+            let submenu = match item {
+                ContextMenuItem::Submenu { menu, .. } => Some(menu.create_layout_info(state, item_store, left + width, item_top - border)),
+                _ => None,
+            };
+
+            item_areas.push(Area {
+                left: left + border,
+                top: item_top,
+                width: width - border * 2.0,
+                height: item_height,
+            });
+            submenus.push(submenu);
+
+            item_top += item_height;
+        }
+
+        MenuLayoutInfo {
+            area: Area {
+                left,
+                top,
+                width,
+                height: item_top + border - top,
+            },
+            item_areas,
+            submenus,
+        }
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<App>,
+        store: ElementStore<'a>,
+        layout_info: &'a MenuLayoutInfo,
+        layout: &mut WindowLayout<'a, App>,
+    ) {
+        let foreground_color = *state.get(&theme().context_menu().foreground_color());
+        let disabled_foreground_color = *state.get(&theme().context_menu().disabled_foreground_color());
+        let highlight_color = *state.get(&theme().context_menu().highlight_color());
+        let hovered_background_color = *state.get(&theme().context_menu().hovered_background_color());
+        let shadow_color = *state.get(&theme().context_menu().shadow_color());
+        let item_corner_diameter = *state.get(&theme().context_menu().item_corner_diameter());
+        let icon_size = *state.get(&theme().context_menu().icon_size());
+        let font_size = *state.get(&theme().context_menu().font_size());
+        let horizontal_alignment = *state.get(&theme().context_menu().horizontal_alignment());
+        let vertical_alignment = *state.get(&theme().context_menu().vertical_alignment());
+        let overflow_behavior = *state.get(&theme().context_menu().overflow_behavior());
+
+        layout.add_rectangle(
+            layout_info.area,
+            *state.get(&theme().context_menu().corner_diameter()),
+            *state.get(&theme().context_menu().background_color()),
+            shadow_color,
+            *state.get(&theme().context_menu().shadow_padding()),
+        );
+
+        for (index, item) in self.items.iter().enumerate() {
+            let area = layout_info.item_areas[index];
+
+            let (text, handler, is_submenu) = match item {
+                ContextMenuItem::Action { text, handler } => {
+                    (text, handler.as_ref().map(|handler| handler as &dyn ClickHandler<App>), false)
+                }
+                ContextMenuItem::Submenu { text, handler, .. } => {
+                    if handler.requested.take() {
+                        self.open_submenu.set(Some(index));
+                    }
+
+                    (text, Some(handler as &dyn ClickHandler<App>), true)
+                }
+                ContextMenuItem::Separator => {
+                    let separator_color = *state.get(&theme().context_menu().separator_color());
+                    let separator_area = Area {
+                        top: area.top + (area.height - 1.0) / 2.0,
+                        height: 1.0,
+                        ..area
+                    };
+
+                    layout.add_rectangle(
+                        separator_area,
+                        App::CornerDiameter::new(0.0, 0.0, 0.0, 0.0),
+                        separator_color,
+                        separator_color,
+                        App::ShadowPadding::none(),
+                    );
+                    continue;
+                }
+            };
+
+            let element_id = store.child_store(index as u64).get_element_id();
+            let is_hovered = area.check().run(layout);
+            let is_focused = layout.is_element_focused(element_id);
+
+            // Moving to an item with the mouse or keyboard opens its submenu and
+            // closes any other one.
+            if is_hovered || is_focused {
+                self.open_submenu.set(is_submenu.then_some(index));
+            }
+
+            if let Some(handler) = handler {
+                layout.register_focusable(element_id, area, Some(handler));
+
+                if is_hovered {
+                    layout.register_click_handler(MouseButton::Left, handler);
+                }
+            }
+
+            let is_open = self.open_submenu.get() == Some(index);
+
+            if handler.is_some() && (is_hovered || is_focused || is_open) {
+                layout.add_rectangle(
+                    area,
+                    item_corner_diameter,
+                    hovered_background_color,
+                    shadow_color,
+                    App::ShadowPadding::none(),
+                );
+            }
+
+            let text_color = match handler.is_some() {
+                true => foreground_color,
+                false => disabled_foreground_color,
+            };
+
+            layout.add_text(
+                area,
+                text,
+                font_size,
+                text_color,
+                highlight_color,
+                horizontal_alignment,
+                vertical_alignment,
+                overflow_behavior,
+            );
+
+            if is_submenu {
+                let icon_area = Area {
+                    left: area.left + area.width - icon_size - (area.height - icon_size) / 2.0,
+                    top: area.top + (area.height - icon_size) / 2.0,
+                    width: icon_size,
+                    height: icon_size,
+                };
+
+                layout.add_icon(icon_area, Icon::ArrowRight, text_color);
+            }
+        }
+
+        if let Some(index) = self.open_submenu.get()
+            && let ContextMenuItem::Submenu { menu, .. } = &self.items[index]
+            && let Some(submenu_layout_info) = &layout_info.submenus[index]
+        {
+            menu.lay_out(state, store.child_store(index as u64), submenu_layout_info, layout);
+        }
+    }
+}
+
+pub(crate) struct MenuLayoutInfo {
+    area: Area,
+    item_areas: Vec<Area>,
+    submenus: Vec<Option<MenuLayoutInfo>>,
+}
+
+/// Element displaying a [`ContextMenu`] inside of an overlay.
+pub(crate) struct ContextMenuElement<App: Application> {
+    menu: ContextMenu<App>,
+}
+
+impl<App> ContextMenuElement<App>
+where
+    App: Application,
+{
+    pub fn new(menu: ContextMenu<App>) -> Self {
+        Self { menu }
+    }
+}
+
+impl<App> Element<App> for ContextMenuElement<App>
+where
+    App: Application,
+{
+    type LayoutInfo = MenuLayoutInfo;
+
+    fn create_layout_info(&mut self, state: &State<App>, store: ElementStoreMut, resolvers: &mut dyn Resolvers<App>) -> Self::LayoutInfo {
+        with_single_resolver(resolvers, |resolver| {
+            let available_area = resolver.push_available_area();
+            self.menu.create_layout_info(state, store, available_area.left, available_area.top)
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<App>,
+        store: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, App>,
+    ) {
+        self.menu.lay_out(state, store, layout_info, layout);
+    }
+}
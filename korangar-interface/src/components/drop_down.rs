@@ -125,10 +125,12 @@ where
     fn lay_out<'a>(
         &'a self,
         state: &'a State<App>,
-        _: ElementStore<'a>,
+        store: ElementStore<'a>,
         layout_info: &'a Self::LayoutInfo,
         layout: &mut WindowLayout<'a, App>,
     ) {
+        layout.register_focusable(store.get_element_id(), layout_info.area, Some(&self.event));
+
         let is_hoverered = layout_info.area.check().run(layout);
 
         if is_hoverered {
@@ -532,10 +534,16 @@ where
     fn lay_out<'a>(
         &'a self,
         state: &'a State<App>,
-        _: ElementStore<'a>,
+        store: ElementStore<'a>,
         layout_info: &'a Self::LayoutInfo,
         layout: &mut WindowLayout<'a, App>,
     ) {
+        layout.register_focusable(
+            store.get_element_id(),
+            layout_info.area,
+            Some(&self.click_handler.overlay_element),
+        );
+
         let is_hoverered = layout_info.area.check().run(layout);
 
         if is_hoverered {
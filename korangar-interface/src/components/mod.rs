@@ -1,12 +1,19 @@
 pub mod button;
 pub mod collapsible;
+pub mod context_menu;
 pub mod drop_down;
 pub mod either;
 pub mod field;
 pub mod fragment;
+pub mod number_input;
+pub mod progress_bar;
+pub mod range;
+pub mod rich_text;
 pub mod scroll_view;
+pub mod slider;
 pub mod split;
 pub mod state_button;
+pub mod table;
 pub mod tabs;
 pub mod text;
 pub mod text_box;
@@ -0,0 +1,448 @@
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+
+use rust_state::{Path, RustState, Selector, State};
+
+use super::range::{DragClick, RangeBinding, RangeValue};
+use crate::application::{Application, Position, Size};
+use crate::element::Element;
+use crate::element::store::{ElementStore, ElementStoreMut};
+use crate::event::{ClickHandler, Event, EventQueue, InputHandler, KeyInput, NavigationInput, ScrollHandler};
+use crate::layout::alignment::{HorizontalAlignment, VerticalAlignment};
+use crate::layout::area::Area;
+use crate::layout::tooltip::TooltipExt;
+use crate::layout::{MouseButton, Resolvers, WindowLayout, with_single_resolver};
+
+/// Number of pixels the mouse needs to be dragged to change the value by one
+/// step.
+const DRAG_DISTANCE_PER_STEP: f32 = 8.0;
+
+#[derive(RustState)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NumberInputTheme<App>
+where
+    App: Application,
+{
+    pub foreground_color: App::Color,
+    pub background_color: App::Color,
+    pub highlight_color: App::Color,
+    pub hovered_background_color: App::Color,
+    pub button_foreground_color: App::Color,
+    pub button_background_color: App::Color,
+    pub hovered_button_background_color: App::Color,
+    pub shadow_color: App::Color,
+    pub shadow_padding: App::ShadowPadding,
+    pub height: f32,
+    pub corner_diameter: App::CornerDiameter,
+    pub font_size: App::FontSize,
+    pub horizontal_alignment: HorizontalAlignment,
+    pub vertical_alignment: VerticalAlignment,
+    pub overflow_behavior: App::OverflowBehavior,
+}
+
+pub struct NumberInputLayoutInfo<App>
+where
+    App: Application,
+{
+    area: Area,
+    font_size: App::FontSize,
+    value_text: String,
+}
+
+/// Handles typing a value into the number input.
+struct NumberInputHandler<Value, C, D, E, F> {
+    binding: RangeBinding<Value, C, D, E, F>,
+    /// Text typed by the user that is not committed yet.
+    typed_text: RefCell<Option<String>>,
+}
+
+impl<App, Value, C, D, E, F> InputHandler<App> for NumberInputHandler<Value, C, D, E, F>
+where
+    App: Application,
+    Value: RangeValue,
+    C: Path<App, Value>,
+    D: Selector<App, Value>,
+    E: Selector<App, Value>,
+    F: Selector<App, Value>,
+{
+    fn handle_character(&self, state: &State<App>, queue: &mut EventQueue<App>, character: char) {
+        let mut typed_text = self.typed_text.borrow_mut();
+
+        if character == '\x0d' {
+            // On enter
+            if let Some(value) = typed_text.take().and_then(|text| text.parse::<Value>().ok()) {
+                self.binding.set(state, value.to_f64());
+            }
+        } else if character == '\x1b' {
+            // On escape, discard the typed text or unfocus if there is none.
+            if typed_text.take().is_none() {
+                queue.queue(Event::Unfocus);
+            }
+        } else if character == '\x08' {
+            typed_text.get_or_insert_with(|| self.binding.get(state).to_string()).pop();
+        } else if character.is_ascii_digit() || character == '-' || character == '.' {
+            typed_text.get_or_insert_with(String::new).push(character);
+        }
+    }
+
+    fn captures_navigation(&self, input: NavigationInput) -> bool {
+        // The arrow keys step the value, so only tab moves the focus.
+        !matches!(input, NavigationInput::Move(direction) if direction.is_sequential())
+    }
+
+    fn handle_key(&self, state: &State<App>, _: &mut EventQueue<App>, _: &mut App::Clipboard, key: KeyInput) {
+        if self.binding.apply_key(state, key) {
+            *self.typed_text.borrow_mut() = None;
+        }
+    }
+}
+
+impl<App, Value, C, D, E, F> ScrollHandler<App> for NumberInputHandler<Value, C, D, E, F>
+where
+    App: Application,
+    Value: RangeValue,
+    C: Path<App, Value>,
+    D: Selector<App, Value>,
+    E: Selector<App, Value>,
+    F: Selector<App, Value>,
+{
+    fn handle_scroll(&self, state: &State<App>, queue: &mut EventQueue<App>, delta: f32) -> bool {
+        self.binding.handle_scroll(state, queue, delta)
+    }
+}
+
+/// Changes the value by a fixed number of steps when clicked.
+struct StepClick<Value, C, D, E, F> {
+    binding: RangeBinding<Value, C, D, E, F>,
+    steps: f64,
+}
+
+impl<App, Value, C, D, E, F> ClickHandler<App> for StepClick<Value, C, D, E, F>
+where
+    App: Application,
+    Value: RangeValue,
+    C: Path<App, Value>,
+    D: Selector<App, Value>,
+    E: Selector<App, Value>,
+    F: Selector<App, Value>,
+{
+    fn handle_click(&self, state: &State<App>, _: &mut EventQueue<App>) {
+        self.binding.step_by(state, self.steps);
+    }
+}
+
+pub struct NumberInput<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U> {
+    text_marker: PhantomData<(Text, Tooltip)>,
+    text: A,
+    tooltip: B,
+    handler: NumberInputHandler<Value, C, D, E, F>,
+    decrease: StepClick<Value, C, D, E, F>,
+    increase: StepClick<Value, C, D, E, F>,
+    drag_click: DragClick,
+    /// Mouse position and value when the current drag started.
+    drag_start: Cell<Option<(f32, f64)>>,
+    foreground_color: G,
+    background_color: H,
+    highlight_color: I,
+    hovered_background_color: J,
+    button_foreground_color: K,
+    button_background_color: L,
+    hovered_button_background_color: M,
+    shadow_color: N,
+    shadow_padding: O,
+    height: P,
+    corner_diameter: Q,
+    font_size: R,
+    horizontal_alignment: S,
+    vertical_alignment: T,
+    overflow_behavior: U,
+}
+
+impl<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U>
+    NumberInput<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U>
+where
+    C: Clone,
+    D: Clone,
+    E: Clone,
+    F: Clone,
+{
+    /// This function is supposed to be called from a component macro and not
+    /// intended to be called manually.
+    #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn component_new(
+        text: A,
+        tooltip: B,
+        state: C,
+        minimum: D,
+        maximum: E,
+        step: F,
+        foreground_color: G,
+        background_color: H,
+        highlight_color: I,
+        hovered_background_color: J,
+        button_foreground_color: K,
+        button_background_color: L,
+        hovered_button_background_color: M,
+        shadow_color: N,
+        shadow_padding: O,
+        height: P,
+        corner_diameter: Q,
+        font_size: R,
+        horizontal_alignment: S,
+        vertical_alignment: T,
+        overflow_behavior: U,
+    ) -> Self {
+        let binding = RangeBinding::new(state, minimum, maximum, step);
+
+        Self {
+            text_marker: PhantomData,
+            text,
+            tooltip,
+            decrease: StepClick {
+                binding: binding.clone(),
+                steps: -1.0,
+            },
+            increase: StepClick {
+                binding: binding.clone(),
+                steps: 1.0,
+            },
+            handler: NumberInputHandler {
+                binding,
+                typed_text: RefCell::default(),
+            },
+            drag_click: DragClick::default(),
+            drag_start: Cell::default(),
+            foreground_color,
+            background_color,
+            highlight_color,
+            hovered_background_color,
+            button_foreground_color,
+            button_background_color,
+            hovered_button_background_color,
+            shadow_color,
+            shadow_padding,
+            height,
+            corner_diameter,
+            font_size,
+            horizontal_alignment,
+            vertical_alignment,
+            overflow_behavior,
+        }
+    }
+}
+
+impl<App, Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U> Element<App>
+    for NumberInput<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U>
+where
+    App: Application,
+    Text: AsRef<str> + 'static,
+    Tooltip: AsRef<str> + 'static,
+    Value: RangeValue,
+    A: Selector<App, Text>,
+    B: Selector<App, Tooltip>,
+    C: Path<App, Value>,
+    D: Selector<App, Value>,
+    E: Selector<App, Value>,
+    F: Selector<App, Value>,
+    G: Selector<App, App::Color>,
+    H: Selector<App, App::Color>,
+    I: Selector<App, App::Color>,
+    J: Selector<App, App::Color>,
+    K: Selector<App, App::Color>,
+    L: Selector<App, App::Color>,
+    M: Selector<App, App::Color>,
+    N: Selector<App, App::Color>,
+    O: Selector<App, App::ShadowPadding>,
+    P: Selector<App, f32>,
+    Q: Selector<App, App::CornerDiameter>,
+    R: Selector<App, App::FontSize>,
+    S: Selector<App, HorizontalAlignment>,
+    T: Selector<App, VerticalAlignment>,
+    U: Selector<App, App::OverflowBehavior>,
+{
+    type LayoutInfo = NumberInputLayoutInfo<App>;
+
+    fn create_layout_info(&mut self, state: &State<App>, store: ElementStoreMut, resolvers: &mut dyn Resolvers<App>) -> Self::LayoutInfo {
+        self.drag_click.update(store.get_element_id());
+
+        with_single_resolver(resolvers, |resolver| {
+            let height = *state.get(&self.height);
+
+            let text = state.get(&self.text).as_ref();
+            let foreground_color = *state.get(&self.foreground_color);
+            let highlight_color = *state.get(&self.highlight_color);
+            let font_size = *state.get(&self.font_size);
+            let horizontal_alignment = *state.get(&self.horizontal_alignment);
+            let overflow_behavior = *state.get(&self.overflow_behavior);
+
+            let (size, font_size) = resolver.get_text_dimensions(
+                text,
+                foreground_color,
+                highlight_color,
+                font_size,
+                horizontal_alignment,
+                overflow_behavior,
+            );
+
+            let area = resolver.with_height(height.max(size.height()));
+            let value_text = match self.handler.typed_text.borrow().as_ref() {
+                Some(typed_text) => typed_text.clone(),
+                None => self.handler.binding.get(state).to_string(),
+            };
+
+            Self::LayoutInfo {
+                area,
+                font_size,
+                value_text,
+            }
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<App>,
+        store: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, App>,
+    ) {
+        let area = layout_info.area;
+        let is_focused = layout.is_element_focused(store.get_element_id());
+        let is_dragging = self.drag_click.is_dragging(layout.get_mouse_mode());
+
+        let button_size = area.height;
+        let decrease_area = Area {
+            left: area.left + area.width / 2.0,
+            width: button_size,
+            ..area
+        };
+        let increase_area = Area {
+            left: area.left + area.width - button_size,
+            width: button_size,
+            ..area
+        };
+        let value_area = Area {
+            left: decrease_area.left + button_size,
+            width: (area.width / 2.0 - button_size * 2.0).max(0.0),
+            ..area
+        };
+
+        // The buttons are inside the area of the number input, so they are checked
+        // without marking the hover first.
+        let is_decrease_hovered = decrease_area.check().dont_mark().run(layout);
+        let is_increase_hovered = increase_area.check().dont_mark().run(layout);
+        let is_hovered = area.check().run(layout);
+
+        if is_hovered {
+            struct NumberInputTooltip;
+
+            let tooltip = state.get(&self.tooltip).as_ref();
+            if !tooltip.is_empty() {
+                layout.add_tooltip(tooltip, NumberInputTooltip.tooltip_id());
+            }
+
+            match (is_decrease_hovered, is_increase_hovered) {
+                (true, _) => layout.register_click_handler(MouseButton::Left, &self.decrease),
+                (_, true) => layout.register_click_handler(MouseButton::Left, &self.increase),
+                _ => layout.register_click_handler(MouseButton::Left, &self.drag_click),
+            }
+
+            layout.register_scroll_handler(&self.handler);
+        }
+
+        layout.register_focusable(store.get_element_id(), area, None);
+
+        if is_focused {
+            layout.register_input_handler(&self.handler);
+        } else {
+            // Text that wasn't committed with enter is discarded when losing focus.
+            self.handler.typed_text.borrow_mut().take();
+        }
+
+        if is_dragging {
+            let mouse_left = layout.get_mouse_position().left();
+
+            if self.drag_click.take_drag_start() {
+                let value = self.handler.binding.get(state).to_f64();
+                self.drag_start.set(Some((mouse_left, value)));
+            } else if let Some((start_left, start_value)) = self.drag_start.get() {
+                let steps = ((mouse_left - start_left) / DRAG_DISTANCE_PER_STEP).trunc() as f64;
+                let step = self.handler.binding.step(state).to_f64();
+
+                if self.handler.binding.set(state, start_value + steps * step) {
+                    self.handler.typed_text.borrow_mut().take();
+                }
+            }
+        }
+
+        let background_color = match is_hovered || is_focused || is_dragging {
+            true => *state.get(&self.hovered_background_color),
+            false => *state.get(&self.background_color),
+        };
+        let corner_diameter = *state.get(&self.corner_diameter);
+        let shadow_color = *state.get(&self.shadow_color);
+        let shadow_padding = *state.get(&self.shadow_padding);
+
+        layout.add_rectangle(area, corner_diameter, background_color, shadow_color, shadow_padding);
+
+        let foreground_color = *state.get(&self.foreground_color);
+        let highlight_color = *state.get(&self.highlight_color);
+        let vertical_alignment = *state.get(&self.vertical_alignment);
+        let overflow_behavior = *state.get(&self.overflow_behavior);
+
+        layout.add_text(
+            Area {
+                width: area.width / 2.0,
+                ..area
+            },
+            state.get(&self.text).as_ref(),
+            layout_info.font_size,
+            foreground_color,
+            highlight_color,
+            *state.get(&self.horizontal_alignment),
+            vertical_alignment,
+            overflow_behavior,
+        );
+
+        layout.add_text(
+            value_area,
+            &layout_info.value_text,
+            layout_info.font_size,
+            foreground_color,
+            highlight_color,
+            HorizontalAlignment::Center { offset: 0.0, border: 0.0 },
+            vertical_alignment,
+            overflow_behavior,
+        );
+
+        let button_foreground_color = *state.get(&self.button_foreground_color);
+
+        for (button_area, is_button_hovered, button_text) in
+            [(decrease_area, is_decrease_hovered, "-"), (increase_area, is_increase_hovered, "+")]
+        {
+            let button_background_color = match is_button_hovered {
+                true => *state.get(&self.hovered_button_background_color),
+                false => *state.get(&self.button_background_color),
+            };
+
+            layout.add_rectangle(
+                button_area,
+                corner_diameter,
+                button_background_color,
+                shadow_color,
+                shadow_padding,
+            );
+
+            layout.add_text(
+                button_area,
+                button_text,
+                layout_info.font_size,
+                button_foreground_color,
+                highlight_color,
+                HorizontalAlignment::Center { offset: 0.0, border: 0.0 },
+                vertical_alignment,
+                overflow_behavior,
+            );
+        }
+    }
+}
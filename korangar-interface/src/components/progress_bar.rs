@@ -0,0 +1,221 @@
+use std::marker::PhantomData;
+
+use rust_state::{RustState, Selector, State};
+
+use super::range::{RangeValue, ratio_of};
+use crate::application::{Application, ShadowPadding, Size};
+use crate::element::Element;
+use crate::element::store::{ElementStore, ElementStoreMut};
+use crate::layout::alignment::{HorizontalAlignment, VerticalAlignment};
+use crate::layout::area::Area;
+use crate::layout::tooltip::TooltipExt;
+use crate::layout::{Resolvers, WindowLayout, with_single_resolver};
+
+#[derive(RustState)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ProgressBarTheme<App>
+where
+    App: Application,
+{
+    pub foreground_color: App::Color,
+    pub background_color: App::Color,
+    pub highlight_color: App::Color,
+    pub bar_color: App::Color,
+    pub shadow_color: App::Color,
+    pub shadow_padding: App::ShadowPadding,
+    pub height: f32,
+    pub corner_diameter: App::CornerDiameter,
+    pub border: f32,
+    pub font_size: App::FontSize,
+    pub horizontal_alignment: HorizontalAlignment,
+    pub vertical_alignment: VerticalAlignment,
+    pub overflow_behavior: App::OverflowBehavior,
+}
+
+pub struct ProgressBar<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q> {
+    text_marker: PhantomData<(Text, Tooltip, Value)>,
+    text: A,
+    tooltip: B,
+    value: C,
+    maximum: D,
+    foreground_color: E,
+    background_color: F,
+    highlight_color: G,
+    bar_color: H,
+    shadow_color: I,
+    shadow_padding: J,
+    height: K,
+    corner_diameter: L,
+    border: M,
+    font_size: N,
+    horizontal_alignment: O,
+    vertical_alignment: P,
+    overflow_behavior: Q,
+}
+
+impl<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q>
+    ProgressBar<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q>
+{
+    /// This function is supposed to be called from a component macro and not
+    /// intended to be called manually.
+    #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn component_new(
+        text: A,
+        tooltip: B,
+        value: C,
+        maximum: D,
+        foreground_color: E,
+        background_color: F,
+        highlight_color: G,
+        bar_color: H,
+        shadow_color: I,
+        shadow_padding: J,
+        height: K,
+        corner_diameter: L,
+        border: M,
+        font_size: N,
+        horizontal_alignment: O,
+        vertical_alignment: P,
+        overflow_behavior: Q,
+    ) -> Self {
+        Self {
+            text_marker: PhantomData,
+            text,
+            tooltip,
+            value,
+            maximum,
+            foreground_color,
+            background_color,
+            highlight_color,
+            bar_color,
+            shadow_color,
+            shadow_padding,
+            height,
+            corner_diameter,
+            border,
+            font_size,
+            horizontal_alignment,
+            vertical_alignment,
+            overflow_behavior,
+        }
+    }
+}
+
+impl<App, Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q> Element<App>
+    for ProgressBar<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q>
+where
+    App: Application,
+    Text: AsRef<str> + 'static,
+    Tooltip: AsRef<str> + 'static,
+    Value: RangeValue,
+    A: Selector<App, Text>,
+    B: Selector<App, Tooltip>,
+    C: Selector<App, Value>,
+    D: Selector<App, Value>,
+    E: Selector<App, App::Color>,
+    F: Selector<App, App::Color>,
+    G: Selector<App, App::Color>,
+    H: Selector<App, App::Color>,
+    I: Selector<App, App::Color>,
+    J: Selector<App, App::ShadowPadding>,
+    K: Selector<App, f32>,
+    L: Selector<App, App::CornerDiameter>,
+    M: Selector<App, f32>,
+    N: Selector<App, App::FontSize>,
+    O: Selector<App, HorizontalAlignment>,
+    P: Selector<App, VerticalAlignment>,
+    Q: Selector<App, App::OverflowBehavior>,
+{
+    fn create_layout_info(&mut self, state: &State<App>, _: ElementStoreMut, resolvers: &mut dyn Resolvers<App>) -> Self::LayoutInfo {
+        with_single_resolver(resolvers, |resolver| {
+            let height = *state.get(&self.height);
+
+            let text = state.get(&self.text).as_ref();
+            let foreground_color = *state.get(&self.foreground_color);
+            let highlight_color = *state.get(&self.highlight_color);
+            let font_size = *state.get(&self.font_size);
+            let horizontal_alignment = *state.get(&self.horizontal_alignment);
+            let overflow_behavior = *state.get(&self.overflow_behavior);
+
+            let (size, font_size) = resolver.get_text_dimensions(
+                text,
+                foreground_color,
+                highlight_color,
+                font_size,
+                horizontal_alignment,
+                overflow_behavior,
+            );
+
+            let area = resolver.with_height(height.max(size.height()));
+
+            Self::LayoutInfo { area, font_size }
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<App>,
+        _: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, App>,
+    ) {
+        let area = layout_info.area;
+
+        if area.check().run(layout) {
+            struct ProgressBarTooltip;
+
+            let tooltip = state.get(&self.tooltip).as_ref();
+            if !tooltip.is_empty() {
+                layout.add_tooltip(tooltip, ProgressBarTooltip.tooltip_id());
+            }
+        }
+
+        let corner_diameter = *state.get(&self.corner_diameter);
+        let shadow_color = *state.get(&self.shadow_color);
+        let shadow_padding = *state.get(&self.shadow_padding);
+
+        layout.add_rectangle(
+            area,
+            corner_diameter,
+            *state.get(&self.background_color),
+            shadow_color,
+            shadow_padding,
+        );
+
+        let ratio = ratio_of(*state.get(&self.value), Value::from_f64(0.0), *state.get(&self.maximum));
+
+        if ratio > 0.0 {
+            let border = *state.get(&self.border);
+            let bar_color = *state.get(&self.bar_color);
+
+            layout.add_rectangle(
+                Area {
+                    left: area.left + border,
+                    top: area.top + border,
+                    width: (area.width - border * 2.0) * ratio,
+                    height: area.height - border * 2.0,
+                },
+                corner_diameter,
+                bar_color,
+                bar_color,
+                App::ShadowPadding::none(),
+            );
+        }
+
+        let text = state.get(&self.text).as_ref();
+
+        if !text.is_empty() {
+            layout.add_text(
+                area,
+                text,
+                layout_info.font_size,
+                *state.get(&self.foreground_color),
+                *state.get(&self.highlight_color),
+                *state.get(&self.horizontal_alignment),
+                *state.get(&self.vertical_alignment),
+                *state.get(&self.overflow_behavior),
+            );
+        }
+    }
+}
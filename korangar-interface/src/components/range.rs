@@ -0,0 +1,361 @@
+//! Shared logic of the components that pick a value from a range, such as
+//! `slider` and `number_input`.
+
+use std::cell::Cell;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use rust_state::{Path, Selector, State};
+
+use crate::MouseMode;
+use crate::application::Application;
+use crate::element::id::ElementId;
+use crate::event::{ClickHandler, EditKey, Event, EventQueue, FocusDirection, InputHandler, KeyInput, NavigationInput, ScrollHandler};
+
+/// A numeric value that can be picked from a range.
+pub trait RangeValue: Copy + PartialOrd + Display + FromStr + 'static {
+    fn to_f64(self) -> f64;
+
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! implement_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl RangeValue for $ty {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn from_f64(value: f64) -> Self {
+                    value.round() as $ty
+                }
+            }
+        )*
+    };
+}
+
+implement_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl RangeValue for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl RangeValue for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+/// Clamp a value to the range and snap it to the closest step, counting from
+/// the minimum. A step of zero disables snapping.
+fn snap<T: RangeValue>(value: f64, minimum: T, maximum: T, step: T) -> T {
+    let minimum = minimum.to_f64();
+    let maximum = maximum.to_f64().max(minimum);
+    let step = step.to_f64();
+
+    let mut value = value.clamp(minimum, maximum);
+
+    if step > 0.0 {
+        value = (minimum + ((value - minimum) / step).round() * step).min(maximum);
+    }
+
+    T::from_f64(value)
+}
+
+/// Get the position of the value in the range, where `0.0` is the minimum and
+/// `1.0` is the maximum.
+pub(crate) fn ratio_of<T: RangeValue>(value: T, minimum: T, maximum: T) -> f32 {
+    let minimum = minimum.to_f64();
+    let maximum = maximum.to_f64();
+
+    if maximum <= minimum {
+        return 0.0;
+    }
+
+    ((value.to_f64() - minimum) / (maximum - minimum)).clamp(0.0, 1.0) as f32
+}
+
+/// Value bound to a path in the state together with the range it is allowed
+/// to be in.
+pub(crate) struct RangeBinding<T, A, B, C, D> {
+    value_marker: PhantomData<T>,
+    value: A,
+    minimum: B,
+    maximum: C,
+    step: D,
+}
+
+impl<T, A, B, C, D> RangeBinding<T, A, B, C, D> {
+    pub fn new(value: A, minimum: B, maximum: C, step: D) -> Self {
+        Self {
+            value_marker: PhantomData,
+            value,
+            minimum,
+            maximum,
+            step,
+        }
+    }
+}
+
+impl<T, A, B, C, D> Clone for RangeBinding<T, A, B, C, D>
+where
+    A: Clone,
+    B: Clone,
+    C: Clone,
+    D: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(
+            self.value.clone(),
+            self.minimum.clone(),
+            self.maximum.clone(),
+            self.step.clone(),
+        )
+    }
+}
+
+impl<T, A, B, C, D> RangeBinding<T, A, B, C, D>
+where
+    T: RangeValue,
+{
+    pub fn get<App>(&self, state: &State<App>) -> T
+    where
+        App: Application,
+        A: Path<App, T>,
+    {
+        *state.get(&self.value)
+    }
+
+    pub fn step<App>(&self, state: &State<App>) -> T
+    where
+        App: Application,
+        D: Selector<App, T>,
+    {
+        *state.get(&self.step)
+    }
+
+    /// Get the position of the current value in the range.
+    pub fn ratio<App>(&self, state: &State<App>) -> f32
+    where
+        App: Application,
+        A: Path<App, T>,
+        B: Selector<App, T>,
+        C: Selector<App, T>,
+    {
+        ratio_of(self.get(state), *state.get(&self.minimum), *state.get(&self.maximum))
+    }
+
+    /// Set the value, clamped to the range and snapped to the step. Returns
+    /// `true` if the value changed.
+    pub fn set<App>(&self, state: &State<App>, value: f64) -> bool
+    where
+        App: Application,
+        A: Path<App, T>,
+        B: Selector<App, T>,
+        C: Selector<App, T>,
+        D: Selector<App, T>,
+    {
+        let value = snap(
+            value,
+            *state.get(&self.minimum),
+            *state.get(&self.maximum),
+            *state.get(&self.step),
+        );
+
+        if value == self.get(state) {
+            return false;
+        }
+
+        state.update_value(self.value, value);
+        true
+    }
+
+    /// Set the value from a position in the range, where `0.0` is the minimum
+    /// and `1.0` is the maximum.
+    pub fn set_ratio<App>(&self, state: &State<App>, ratio: f32) -> bool
+    where
+        App: Application,
+        A: Path<App, T>,
+        B: Selector<App, T>,
+        C: Selector<App, T>,
+        D: Selector<App, T>,
+    {
+        let minimum = state.get(&self.minimum).to_f64();
+        let maximum = state.get(&self.maximum).to_f64();
+
+        self.set(state, minimum + (maximum - minimum) * ratio.clamp(0.0, 1.0) as f64)
+    }
+
+    /// Move the value by a number of steps.
+    pub fn step_by<App>(&self, state: &State<App>, steps: f64) -> bool
+    where
+        App: Application,
+        A: Path<App, T>,
+        B: Selector<App, T>,
+        C: Selector<App, T>,
+        D: Selector<App, T>,
+    {
+        let value = self.get(state).to_f64() + steps * self.step(state).to_f64();
+        self.set(state, value)
+    }
+
+    /// Apply keyboard navigation to the value. Returns `true` if the key is
+    /// used by range components.
+    pub fn apply_key<App>(&self, state: &State<App>, key: KeyInput) -> bool
+    where
+        App: Application,
+        A: Path<App, T>,
+        B: Selector<App, T>,
+        C: Selector<App, T>,
+        D: Selector<App, T>,
+    {
+        match key.key {
+            EditKey::Left | EditKey::Down => self.step_by(state, -1.0),
+            EditKey::Right | EditKey::Up => self.step_by(state, 1.0),
+            EditKey::Home => self.set(state, state.get(&self.minimum).to_f64()),
+            EditKey::End => self.set(state, state.get(&self.maximum).to_f64()),
+            _ => return false,
+        };
+
+        true
+    }
+}
+
+impl<App, T, A, B, C, D> ScrollHandler<App> for RangeBinding<T, A, B, C, D>
+where
+    App: Application,
+    T: RangeValue,
+    A: Path<App, T>,
+    B: Selector<App, T>,
+    C: Selector<App, T>,
+    D: Selector<App, T>,
+{
+    fn handle_scroll(&self, state: &State<App>, _: &mut EventQueue<App>, delta: f32) -> bool {
+        if delta == 0.0 {
+            return false;
+        }
+
+        // Only consume the scroll if the value changed, so scrolling past the end
+        // of the range scrolls the surrounding view instead.
+        self.step_by(state, delta.signum() as f64)
+    }
+}
+
+impl<App, T, A, B, C, D> InputHandler<App> for RangeBinding<T, A, B, C, D>
+where
+    App: Application,
+    T: RangeValue,
+    A: Path<App, T>,
+    B: Selector<App, T>,
+    C: Selector<App, T>,
+    D: Selector<App, T>,
+{
+    fn handle_character(&self, _: &State<App>, queue: &mut EventQueue<App>, character: char) {
+        if character == '\x1b' || character == '\x0d' {
+            // On escape or enter
+            queue.queue(Event::Unfocus);
+        }
+    }
+
+    fn captures_navigation(&self, input: NavigationInput) -> bool {
+        !matches!(
+            input,
+            NavigationInput::Move(FocusDirection::Next | FocusDirection::Previous | FocusDirection::Up | FocusDirection::Down)
+        )
+    }
+
+    fn handle_key(&self, state: &State<App>, _: &mut EventQueue<App>, _: &mut App::Clipboard, key: KeyInput) {
+        // Up and down move the focus between sliders instead.
+        if !matches!(key.key, EditKey::Up | EditKey::Down) {
+            self.apply_key(state, key);
+        }
+    }
+}
+
+/// Focuses the element and starts dragging it with the mouse.
+#[derive(Default)]
+pub(crate) struct DragClick {
+    element_id: Option<ElementId>,
+    /// Set when clicked so the element can remember where the drag started
+    /// on the next frame.
+    start_drag: Cell<bool>,
+}
+
+impl DragClick {
+    pub fn update(&mut self, element_id: ElementId) {
+        self.element_id = Some(element_id);
+    }
+
+    /// Check if the element is currently being dragged.
+    pub fn is_dragging<App>(&self, mouse_mode: &MouseMode<App>) -> bool
+    where
+        App: Application,
+    {
+        matches!(
+            mouse_mode,
+            MouseMode::DraggingElement { element_id } if self.element_id == Some(*element_id)
+        )
+    }
+
+    /// Returns `true` once for every click.
+    pub fn take_drag_start(&self) -> bool {
+        self.start_drag.replace(false)
+    }
+}
+
+impl<App> ClickHandler<App> for DragClick
+where
+    App: Application,
+{
+    fn handle_click(&self, _: &State<App>, queue: &mut EventQueue<App>) {
+        let element_id = *self.element_id.as_ref().unwrap();
+        queue.queue(Event::FocusElementPost { element_id });
+        queue.queue(Event::SetMouseMode {
+            mouse_mode: MouseMode::DraggingElement { element_id },
+        });
+
+        self.start_drag.set(true);
+    }
+}
+
+#[cfg(test)]
+mod snapping {
+    use super::{ratio_of, snap};
+
+    #[test]
+    fn clamp_to_range() {
+        assert_eq!(snap(-5.0, 0u8, 10, 1), 0);
+        assert_eq!(snap(15.0, 0u8, 10, 1), 10);
+        assert_eq!(snap(0.5, 0.0f32, 1.0, 0.0), 0.5);
+    }
+
+    #[test]
+    fn snap_to_step() {
+        assert_eq!(snap(12.0, 0i32, 100, 5), 10);
+        assert_eq!(snap(13.0, 0i32, 100, 5), 15);
+        assert_eq!(snap(0.32, 0.0f32, 1.0, 0.05), 0.3);
+        // Steps count from the minimum.
+        assert_eq!(snap(4.0, 1i32, 10, 3), 4);
+        // The maximum stays reachable even if it isn't a multiple of the step.
+        assert_eq!(snap(10.0, 0i32, 10, 4), 10);
+    }
+
+    #[test]
+    fn ratio() {
+        assert_eq!(ratio_of(5u8, 0, 10), 0.5);
+        assert_eq!(ratio_of(20u8, 0, 10), 1.0);
+        assert_eq!(ratio_of(5u8, 5, 5), 0.0);
+    }
+}
@@ -0,0 +1,246 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+use rust_state::{Selector, State};
+
+use crate::application::{Application, Size, TextLayouter};
+use crate::element::Element;
+use crate::element::store::{ElementStore, ElementStoreMut};
+use crate::event::{ClickHandler, EventQueue};
+use crate::layout::alignment::{HorizontalAlignment, VerticalAlignment};
+use crate::layout::area::Area;
+use crate::layout::{MouseButton, Resolvers, WindowLayout, with_single_resolver};
+use crate::markup::{InlineArea, Link, MarkupIterator, MarkupSpan, inline_span};
+
+/// Handler for the links and icons of a text with [`markup`](crate::markup).
+pub trait MarkupHandler<App: Application> {
+    /// Called when a link in the text is clicked.
+    fn handle_link(&self, _state: &State<App>, _queue: &mut EventQueue<App>, _link: Link<'_>) {}
+
+    /// Add the instructions to draw an icon of the text. The area is reserved
+    /// by the [`TextLayouter`], so icons can be drawn in any way the
+    /// application likes.
+    fn lay_out_icon<'a>(&'a self, _state: &'a State<App>, _source: &str, _area: Area, _layout: &mut WindowLayout<'a, App>) {}
+}
+
+/// Handler that ignores all links and icons.
+impl<App: Application> MarkupHandler<App> for () {}
+
+/// Get the areas of all links and icons of a text that is rendered inside of
+/// the given area.
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_inline_areas<App: Application>(
+    text_layouter: &App::TextLayouter,
+    area: Area,
+    text: &str,
+    color: App::Color,
+    highlight_color: App::Color,
+    font_size: App::FontSize,
+    horizontal_alignment: HorizontalAlignment,
+    vertical_alignment: VerticalAlignment,
+    overflow_behavior: App::OverflowBehavior,
+) -> Vec<InlineArea> {
+    // Same as the available width when rendering the text.
+    let available_width = match horizontal_alignment {
+        HorizontalAlignment::Left { offset, border } => area.width - offset - border,
+        HorizontalAlignment::Center { border, .. } => area.width - border * 2.0,
+        HorizontalAlignment::Right { offset, border } => area.width - offset - border,
+    };
+
+    let (text_size, _) = text_layouter.get_text_dimensions(text, color, highlight_color, font_size, available_width, overflow_behavior);
+    let text_area = area.interior(text_size.width(), text_size.height(), horizontal_alignment, vertical_alignment);
+
+    let mut inline_areas = text_layouter.get_inline_areas(text, color, highlight_color, font_size, available_width, overflow_behavior);
+
+    inline_areas.iter_mut().for_each(|inline_area| {
+        inline_area.area.left += text_area.left;
+        inline_area.area.top += text_area.top;
+    });
+
+    inline_areas
+}
+
+/// Add the icons of a text to the layout. Returns the index of the link that
+/// is hovered, if any.
+pub fn lay_out_inline<'a, App: Application>(
+    state: &'a State<App>,
+    text: &str,
+    inline_areas: &[InlineArea],
+    handler: &'a impl MarkupHandler<App>,
+    layout: &mut WindowLayout<'a, App>,
+) -> Option<usize> {
+    let mut hovered_link = None;
+    let mut inline_areas = inline_areas.iter().peekable();
+
+    for (index, span) in MarkupIterator::new(text).filter(MarkupSpan::is_inline).enumerate() {
+        while let Some(inline_area) = inline_areas.next_if(|inline_area| inline_area.index == index) {
+            match span {
+                MarkupSpan::Icon { source } => handler.lay_out_icon(state, source, inline_area.area, layout),
+                MarkupSpan::Text { .. } => {
+                    if inline_area.area.check().run(layout) {
+                        hovered_link = Some(index);
+                    }
+                }
+            }
+        }
+    }
+
+    hovered_link
+}
+
+pub struct RichTextLayoutInfo<App: Application> {
+    pub area: Area,
+    pub font_size: App::FontSize,
+    pub inline_areas: Vec<InlineArea>,
+}
+
+/// Text with [`markup`](crate::markup). Links and icons are passed to the
+/// [`MarkupHandler`].
+pub struct RichText<T, A, B, C, D, E, F, G, H, I> {
+    text_marker: PhantomData<T>,
+    text: A,
+    color: B,
+    highlight_color: C,
+    height: D,
+    font_size: E,
+    horizontal_alignment: F,
+    vertical_alignment: G,
+    overflow_behavior: H,
+    handler: I,
+    /// Index of the link that was hovered during the last layout.
+    hovered_link: Cell<Option<usize>>,
+}
+
+impl<T, A, B, C, D, E, F, G, H, I> RichText<T, A, B, C, D, E, F, G, H, I> {
+    /// This function is supposed to be called from a component macro and not
+    /// intended to be called manually.
+    #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn component_new(
+        text: A,
+        color: B,
+        highlight_color: C,
+        height: D,
+        font_size: E,
+        horizontal_alignment: F,
+        vertical_alignment: G,
+        overflow_behavior: H,
+        handler: I,
+    ) -> Self {
+        Self {
+            text_marker: PhantomData,
+            text,
+            color,
+            highlight_color,
+            height,
+            font_size,
+            horizontal_alignment,
+            vertical_alignment,
+            overflow_behavior,
+            handler,
+            hovered_link: Cell::new(None),
+        }
+    }
+}
+
+impl<App, T, A, B, C, D, E, F, G, H, I> ClickHandler<App> for RichText<T, A, B, C, D, E, F, G, H, I>
+where
+    App: Application,
+    T: AsRef<str> + 'static,
+    A: Selector<App, T>,
+    I: MarkupHandler<App>,
+{
+    fn handle_click(&self, state: &State<App>, queue: &mut EventQueue<App>) {
+        let Some(index) = self.hovered_link.get() else {
+            return;
+        };
+
+        if let Some(MarkupSpan::Text { link: Some(link), .. }) = inline_span(state.get(&self.text).as_ref(), index) {
+            self.handler.handle_link(state, queue, link);
+        }
+    }
+}
+
+impl<App, T, A, B, C, D, E, F, G, H, I> Element<App> for RichText<T, A, B, C, D, E, F, G, H, I>
+where
+    App: Application,
+    T: AsRef<str> + 'static,
+    A: Selector<App, T>,
+    B: Selector<App, App::Color>,
+    C: Selector<App, App::Color>,
+    D: Selector<App, f32>,
+    E: Selector<App, App::FontSize>,
+    F: Selector<App, HorizontalAlignment>,
+    G: Selector<App, VerticalAlignment>,
+    H: Selector<App, App::OverflowBehavior>,
+    I: MarkupHandler<App>,
+{
+    type LayoutInfo = RichTextLayoutInfo<App>;
+
+    fn create_layout_info(&mut self, state: &State<App>, _: ElementStoreMut, resolvers: &mut dyn Resolvers<App>) -> Self::LayoutInfo {
+        with_single_resolver(resolvers, |resolver| {
+            let text = state.get(&self.text).as_ref();
+            let color = *state.get(&self.color);
+            let highlight_color = *state.get(&self.highlight_color);
+            let horizontal_alignment = *state.get(&self.horizontal_alignment);
+            let overflow_behavior = *state.get(&self.overflow_behavior);
+            let height = *state.get(&self.height);
+
+            let (size, font_size) = resolver.get_text_dimensions(
+                text,
+                color,
+                highlight_color,
+                *state.get(&self.font_size),
+                horizontal_alignment,
+                overflow_behavior,
+            );
+            let area = resolver.with_height(height.max(size.height()));
+
+            let inline_areas = resolve_inline_areas::<App>(
+                resolver.get_text_layouter(),
+                area,
+                text,
+                color,
+                highlight_color,
+                font_size,
+                horizontal_alignment,
+                *state.get(&self.vertical_alignment),
+                overflow_behavior,
+            );
+
+            Self::LayoutInfo {
+                area,
+                font_size,
+                inline_areas,
+            }
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<App>,
+        _: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, App>,
+    ) {
+        let text = state.get(&self.text).as_ref();
+
+        layout.add_text(
+            layout_info.area,
+            text,
+            layout_info.font_size,
+            *state.get(&self.color),
+            *state.get(&self.highlight_color),
+            *state.get(&self.horizontal_alignment),
+            *state.get(&self.vertical_alignment),
+            *state.get(&self.overflow_behavior),
+        );
+
+        let hovered_link = lay_out_inline(state, text, &layout_info.inline_areas, &self.handler, layout);
+        self.hovered_link.set(hovered_link);
+
+        if hovered_link.is_some() {
+            layout.register_click_handler(MouseButton::Left, self);
+        }
+    }
+}
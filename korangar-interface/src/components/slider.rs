@@ -0,0 +1,344 @@
+use std::marker::PhantomData;
+
+use rust_state::{Path, RustState, Selector, State};
+
+use super::range::{DragClick, RangeBinding, RangeValue};
+use crate::application::{Application, CornerDiameter, Position, ShadowPadding, Size};
+use crate::element::Element;
+use crate::element::store::{ElementStore, ElementStoreMut};
+use crate::layout::alignment::{HorizontalAlignment, VerticalAlignment};
+use crate::layout::area::Area;
+use crate::layout::tooltip::TooltipExt;
+use crate::layout::{MouseButton, Resolvers, WindowLayout, with_single_resolver};
+
+/// Space between the rail and the edges of the right half of the slider.
+const RAIL_PADDING: f32 = 10.0;
+
+#[derive(RustState)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SliderTheme<App>
+where
+    App: Application,
+{
+    pub foreground_color: App::Color,
+    pub background_color: App::Color,
+    pub highlight_color: App::Color,
+    pub hovered_background_color: App::Color,
+    pub rail_color: App::Color,
+    pub filled_rail_color: App::Color,
+    pub knob_color: App::Color,
+    pub hovered_knob_color: App::Color,
+    pub shadow_color: App::Color,
+    pub shadow_padding: App::ShadowPadding,
+    pub height: f32,
+    pub corner_diameter: App::CornerDiameter,
+    pub rail_height: f32,
+    pub knob_width: f32,
+    pub font_size: App::FontSize,
+    pub horizontal_alignment: HorizontalAlignment,
+    pub vertical_alignment: VerticalAlignment,
+    pub overflow_behavior: App::OverflowBehavior,
+}
+
+pub struct SliderLayoutInfo<App>
+where
+    App: Application,
+{
+    area: Area,
+    font_size: App::FontSize,
+    value_text: String,
+}
+
+pub struct Slider<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X> {
+    text_marker: PhantomData<(Text, Tooltip)>,
+    text: A,
+    tooltip: B,
+    binding: RangeBinding<Value, C, D, E, F>,
+    drag_click: DragClick,
+    foreground_color: G,
+    background_color: H,
+    highlight_color: I,
+    hovered_background_color: J,
+    rail_color: K,
+    filled_rail_color: L,
+    knob_color: M,
+    hovered_knob_color: N,
+    shadow_color: O,
+    shadow_padding: P,
+    height: Q,
+    corner_diameter: R,
+    rail_height: S,
+    knob_width: T,
+    font_size: U,
+    horizontal_alignment: V,
+    vertical_alignment: W,
+    overflow_behavior: X,
+}
+
+impl<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>
+    Slider<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>
+{
+    /// This function is supposed to be called from a component macro and not
+    /// intended to be called manually.
+    #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn component_new(
+        text: A,
+        tooltip: B,
+        state: C,
+        minimum: D,
+        maximum: E,
+        step: F,
+        foreground_color: G,
+        background_color: H,
+        highlight_color: I,
+        hovered_background_color: J,
+        rail_color: K,
+        filled_rail_color: L,
+        knob_color: M,
+        hovered_knob_color: N,
+        shadow_color: O,
+        shadow_padding: P,
+        height: Q,
+        corner_diameter: R,
+        rail_height: S,
+        knob_width: T,
+        font_size: U,
+        horizontal_alignment: V,
+        vertical_alignment: W,
+        overflow_behavior: X,
+    ) -> Self {
+        Self {
+            text_marker: PhantomData,
+            text,
+            tooltip,
+            binding: RangeBinding::new(state, minimum, maximum, step),
+            drag_click: DragClick::default(),
+            foreground_color,
+            background_color,
+            highlight_color,
+            hovered_background_color,
+            rail_color,
+            filled_rail_color,
+            knob_color,
+            hovered_knob_color,
+            shadow_color,
+            shadow_padding,
+            height,
+            corner_diameter,
+            rail_height,
+            knob_width,
+            font_size,
+            horizontal_alignment,
+            vertical_alignment,
+            overflow_behavior,
+        }
+    }
+}
+
+impl<App, Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X> Element<App>
+    for Slider<Text, Tooltip, Value, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>
+where
+    App: Application,
+    Text: AsRef<str> + 'static,
+    Tooltip: AsRef<str> + 'static,
+    Value: RangeValue,
+    A: Selector<App, Text>,
+    B: Selector<App, Tooltip>,
+    C: Path<App, Value>,
+    D: Selector<App, Value>,
+    E: Selector<App, Value>,
+    F: Selector<App, Value>,
+    G: Selector<App, App::Color>,
+    H: Selector<App, App::Color>,
+    I: Selector<App, App::Color>,
+    J: Selector<App, App::Color>,
+    K: Selector<App, App::Color>,
+    L: Selector<App, App::Color>,
+    M: Selector<App, App::Color>,
+    N: Selector<App, App::Color>,
+    O: Selector<App, App::Color>,
+    P: Selector<App, App::ShadowPadding>,
+    Q: Selector<App, f32>,
+    R: Selector<App, App::CornerDiameter>,
+    S: Selector<App, f32>,
+    T: Selector<App, f32>,
+    U: Selector<App, App::FontSize>,
+    V: Selector<App, HorizontalAlignment>,
+    W: Selector<App, VerticalAlignment>,
+    X: Selector<App, App::OverflowBehavior>,
+{
+    type LayoutInfo = SliderLayoutInfo<App>;
+
+    fn create_layout_info(&mut self, state: &State<App>, store: ElementStoreMut, resolvers: &mut dyn Resolvers<App>) -> Self::LayoutInfo {
+        self.drag_click.update(store.get_element_id());
+
+        with_single_resolver(resolvers, |resolver| {
+            let height = *state.get(&self.height);
+
+            let text = state.get(&self.text).as_ref();
+            let foreground_color = *state.get(&self.foreground_color);
+            let highlight_color = *state.get(&self.highlight_color);
+            let font_size = *state.get(&self.font_size);
+            let horizontal_alignment = *state.get(&self.horizontal_alignment);
+            let overflow_behavior = *state.get(&self.overflow_behavior);
+
+            let (size, font_size) = resolver.get_text_dimensions(
+                text,
+                foreground_color,
+                highlight_color,
+                font_size,
+                horizontal_alignment,
+                overflow_behavior,
+            );
+
+            let area = resolver.with_height(height.max(size.height()));
+            let value_text = self.binding.get(state).to_string();
+
+            Self::LayoutInfo {
+                area,
+                font_size,
+                value_text,
+            }
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<App>,
+        store: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, App>,
+    ) {
+        let area = layout_info.area;
+        let is_focused = layout.is_element_focused(store.get_element_id());
+        let is_dragging = self.drag_click.is_dragging(layout.get_mouse_mode());
+        let is_hovered = area.check().run(layout);
+
+        if is_hovered {
+            struct SliderTooltip;
+
+            let tooltip = state.get(&self.tooltip).as_ref();
+            if !tooltip.is_empty() {
+                layout.add_tooltip(tooltip, SliderTooltip.tooltip_id());
+            }
+
+            layout.register_click_handler(MouseButton::Left, &self.drag_click);
+            layout.register_scroll_handler(&self.binding);
+        }
+
+        layout.register_focusable(store.get_element_id(), area, None);
+
+        if is_focused {
+            layout.register_input_handler(&self.binding);
+        }
+
+        let knob_width = *state.get(&self.knob_width);
+        let rail_height = *state.get(&self.rail_height);
+
+        // The rail is inset by half the knob width so the knob stays inside the
+        // slider at the minimum and maximum.
+        let rail_area = Area {
+            left: area.left + area.width / 2.0 + RAIL_PADDING + knob_width / 2.0,
+            top: area.top + (area.height - rail_height) / 2.0,
+            width: (area.width / 2.0 - RAIL_PADDING * 2.0 - knob_width).max(0.0),
+            height: rail_height,
+        };
+
+        if is_dragging && rail_area.width > 0.0 {
+            let ratio = (layout.get_mouse_position().left() - rail_area.left) / rail_area.width;
+            self.binding.set_ratio(state, ratio);
+        }
+
+        let is_highlighted = is_hovered || is_focused || is_dragging;
+
+        let background_color = match is_highlighted {
+            true => *state.get(&self.hovered_background_color),
+            false => *state.get(&self.background_color),
+        };
+
+        layout.add_rectangle(
+            area,
+            *state.get(&self.corner_diameter),
+            background_color,
+            *state.get(&self.shadow_color),
+            *state.get(&self.shadow_padding),
+        );
+
+        let text_area = Area {
+            width: area.width / 2.0,
+            ..area
+        };
+        let foreground_color = *state.get(&self.foreground_color);
+        let highlight_color = *state.get(&self.highlight_color);
+        let vertical_alignment = *state.get(&self.vertical_alignment);
+        let overflow_behavior = *state.get(&self.overflow_behavior);
+
+        layout.add_text(
+            text_area,
+            state.get(&self.text).as_ref(),
+            layout_info.font_size,
+            foreground_color,
+            highlight_color,
+            *state.get(&self.horizontal_alignment),
+            vertical_alignment,
+            overflow_behavior,
+        );
+
+        layout.add_text(
+            text_area,
+            &layout_info.value_text,
+            layout_info.font_size,
+            foreground_color,
+            highlight_color,
+            HorizontalAlignment::Right {
+                offset: RAIL_PADDING,
+                border: RAIL_PADDING,
+            },
+            vertical_alignment,
+            overflow_behavior,
+        );
+
+        let rail_corner_diameter = App::CornerDiameter::new(rail_height, rail_height, rail_height, rail_height);
+        let ratio = self.binding.ratio(state);
+        let rail_color = *state.get(&self.rail_color);
+        let filled_rail_color = *state.get(&self.filled_rail_color);
+
+        layout.add_rectangle(
+            rail_area,
+            rail_corner_diameter,
+            rail_color,
+            rail_color,
+            App::ShadowPadding::none(),
+        );
+
+        layout.add_rectangle(
+            Area {
+                width: rail_area.width * ratio,
+                ..rail_area
+            },
+            rail_corner_diameter,
+            filled_rail_color,
+            filled_rail_color,
+            App::ShadowPadding::none(),
+        );
+
+        let knob_color = match is_highlighted {
+            true => *state.get(&self.hovered_knob_color),
+            false => *state.get(&self.knob_color),
+        };
+
+        layout.add_rectangle(
+            Area {
+                left: rail_area.left + rail_area.width * ratio - knob_width / 2.0,
+                top: area.top + 3.0,
+                width: knob_width,
+                height: area.height - 6.0,
+            },
+            App::CornerDiameter::new(knob_width, knob_width, knob_width, knob_width),
+            knob_color,
+            *state.get(&self.shadow_color),
+            *state.get(&self.shadow_padding),
+        );
+    }
+}
@@ -189,13 +189,17 @@ where
     fn lay_out<'a>(
         &'a self,
         state: &'a State<App>,
-        _: ElementStore<'a>,
+        store: ElementStore<'a>,
         layout_info: &'a Self::LayoutInfo,
         layout: &mut WindowLayout<'a, App>,
     ) {
         let is_hoverered = layout_info.area.check().run(layout);
         let is_disabled = *state.get(&self.disabled);
 
+        if !is_disabled {
+            layout.register_focusable(store.get_element_id(), layout_info.area, Some(&self.event));
+        }
+
         if is_hoverered {
             struct StateButtonTooltip;
 
@@ -0,0 +1,634 @@
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use num::Signed;
+use rust_state::{RustState, Selector, State};
+
+use crate::MouseMode;
+use crate::application::{Application, CornerDiameter, Position, ShadowPadding};
+use crate::element::Element;
+use crate::element::id::ElementId;
+use crate::element::store::{ElementStore, ElementStoreMut, Persistent, PersistentExt};
+use crate::event::{ClickHandler, Event, EventQueue, ScrollHandler};
+use crate::layout::alignment::{HorizontalAlignment, VerticalAlignment};
+use crate::layout::area::Area;
+use crate::layout::{Icon, MouseButton, Resolvers, WindowLayout, with_single_resolver};
+
+/// Width of the area around the right edge of a column header that can be
+/// dragged to resize the column.
+const RESIZE_HANDLE_WIDTH: f32 = 8.0;
+
+/// Width of the lines separating the columns.
+const SEPARATOR_WIDTH: f32 = 1.0;
+
+#[derive(RustState)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TableTheme<App>
+where
+    App: Application,
+{
+    pub foreground_color: App::Color,
+    pub background_color: App::Color,
+    pub highlight_color: App::Color,
+    pub header_foreground_color: App::Color,
+    pub header_background_color: App::Color,
+    pub hovered_header_background_color: App::Color,
+    pub row_background_color: App::Color,
+    pub alternate_row_background_color: App::Color,
+    pub hovered_row_background_color: App::Color,
+    pub separator_color: App::Color,
+    pub icon_color: App::Color,
+    pub shadow_color: App::Color,
+    pub shadow_padding: App::ShadowPadding,
+    pub corner_diameter: App::CornerDiameter,
+    pub header_height: f32,
+    pub row_height: f32,
+    pub maximum_height: f32,
+    pub minimum_column_width: f32,
+    pub icon_size: f32,
+    pub font_size: App::FontSize,
+    pub horizontal_alignment: HorizontalAlignment,
+    pub vertical_alignment: VerticalAlignment,
+    pub overflow_behavior: App::OverflowBehavior,
+}
+
+/// Column of a [`Table`].
+#[derive(Clone, Copy)]
+pub struct TableColumn {
+    pub title: &'static str,
+    /// Initial width of the column. The last column is stretched to fill the
+    /// remaining width of the table.
+    pub width: f32,
+}
+
+impl TableColumn {
+    pub fn new(title: &'static str, width: f32) -> Self {
+        Self { title, width }
+    }
+}
+
+/// Row of a [`Table`].
+pub trait TableRow<App: Application> {
+    /// Text displayed in the given column.
+    fn cell(&self, column: usize) -> Cow<'_, str>;
+
+    /// Order of two rows when sorting by the given column. Compares the text
+    /// of the cells by default.
+    fn compare(&self, other: &Self, column: usize) -> Ordering {
+        self.cell(column).cmp(&other.cell(column))
+    }
+
+    /// Handler that is called when the row is clicked.
+    fn click_handler(&self) -> Option<&dyn ClickHandler<App>> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+#[derive(Clone, Copy)]
+enum HeaderAction {
+    Sort { column: usize },
+    Resize { column: usize },
+}
+
+#[derive(Default)]
+struct TableDataInner {
+    scroll: f32,
+    maximum_scroll: f32,
+    column_widths: Vec<f32>,
+    sorting: Option<(usize, SortDirection)>,
+    /// Indices of the rows in the order they are displayed in.
+    order: Vec<usize>,
+    /// Number of rows and sorting that the order was created for.
+    ///
+    /// The order is recreated when one of them changes or when rows that
+    /// changed in place are no longer in sorted order.
+    order_key: Option<(usize, Option<(usize, SortDirection)>)>,
+}
+
+/// Order of two rows for the given sorting.
+fn compare_rows<App, Row>(rows: &[Row], left: usize, right: usize, sorting: Option<(usize, SortDirection)>) -> Ordering
+where
+    App: Application,
+    Row: TableRow<App>,
+{
+    match sorting {
+        Some((column, SortDirection::Ascending)) => rows[left].compare(&rows[right], column),
+        Some((column, SortDirection::Descending)) => rows[left].compare(&rows[right], column).reverse(),
+        None => left.cmp(&right),
+    }
+}
+
+#[derive(Default)]
+pub struct TableData {
+    inner: RefCell<TableDataInner>,
+    element_id: Cell<Option<ElementId>>,
+    hovered_header: Cell<Option<HeaderAction>>,
+    resizing_column: Cell<Option<usize>>,
+}
+
+impl<App> ClickHandler<App> for TableData
+where
+    App: Application,
+{
+    fn handle_click(&self, _: &State<App>, queue: &mut EventQueue<App>) {
+        match self.hovered_header.get() {
+            Some(HeaderAction::Sort { column }) => {
+                let mut inner = self.inner.borrow_mut();
+
+                // Clicking the same header cycles through ascending, descending and unsorted.
+                inner.sorting = match inner.sorting {
+                    Some((sorted_column, SortDirection::Ascending)) if sorted_column == column => Some((column, SortDirection::Descending)),
+                    Some((sorted_column, SortDirection::Descending)) if sorted_column == column => None,
+                    _ => Some((column, SortDirection::Ascending)),
+                };
+            }
+            Some(HeaderAction::Resize { column }) => {
+                if let Some(element_id) = self.element_id.get() {
+                    self.resizing_column.set(Some(column));
+                    queue.queue(Event::SetMouseMode {
+                        mouse_mode: MouseMode::DraggingElement { element_id },
+                    });
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+impl<App> ScrollHandler<App> for TableData
+where
+    App: Application,
+{
+    fn handle_scroll(&self, _: &State<App>, _: &mut EventQueue<App>, delta: f32) -> bool {
+        let mut inner = self.inner.borrow_mut();
+
+        // Don't try to scroll if its already at the minimum or maximum scroll value.
+        if delta.is_negative() && inner.scroll >= inner.maximum_scroll || delta.is_positive() && inner.scroll <= 0.0 {
+            return false;
+        }
+
+        inner.scroll = (inner.scroll - delta).max(0.0).min(inner.maximum_scroll);
+
+        true
+    }
+}
+
+struct VisibleRow {
+    index: usize,
+    /// Position of the row in the displayed order.
+    position: usize,
+    cells: Vec<String>,
+}
+
+pub struct TableLayoutInfo {
+    area: Area,
+    column_widths: Vec<f32>,
+    sorting: Option<(usize, SortDirection)>,
+    scroll: f32,
+    rows: Vec<VisibleRow>,
+}
+
+pub struct Table<Row, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y> {
+    row_marker: PhantomData<Row>,
+    rows: A,
+    columns: B,
+    foreground_color: C,
+    background_color: D,
+    highlight_color: E,
+    header_foreground_color: F,
+    header_background_color: G,
+    hovered_header_background_color: H,
+    row_background_color: I,
+    alternate_row_background_color: J,
+    hovered_row_background_color: K,
+    separator_color: L,
+    icon_color: M,
+    shadow_color: N,
+    shadow_padding: O,
+    corner_diameter: P,
+    header_height: Q,
+    row_height: R,
+    maximum_height: S,
+    minimum_column_width: T,
+    icon_size: U,
+    font_size: V,
+    horizontal_alignment: W,
+    vertical_alignment: X,
+    overflow_behavior: Y,
+}
+
+impl<Row, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>
+    Table<Row, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>
+{
+    /// This function is supposed to be called from a component macro and not
+    /// intended to be called manually.
+    #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn component_new(
+        rows: A,
+        columns: B,
+        foreground_color: C,
+        background_color: D,
+        highlight_color: E,
+        header_foreground_color: F,
+        header_background_color: G,
+        hovered_header_background_color: H,
+        row_background_color: I,
+        alternate_row_background_color: J,
+        hovered_row_background_color: K,
+        separator_color: L,
+        icon_color: M,
+        shadow_color: N,
+        shadow_padding: O,
+        corner_diameter: P,
+        header_height: Q,
+        row_height: R,
+        maximum_height: S,
+        minimum_column_width: T,
+        icon_size: U,
+        font_size: V,
+        horizontal_alignment: W,
+        vertical_alignment: X,
+        overflow_behavior: Y,
+    ) -> Self {
+        Self {
+            row_marker: PhantomData,
+            rows,
+            columns,
+            foreground_color,
+            background_color,
+            highlight_color,
+            header_foreground_color,
+            header_background_color,
+            hovered_header_background_color,
+            row_background_color,
+            alternate_row_background_color,
+            hovered_row_background_color,
+            separator_color,
+            icon_color,
+            shadow_color,
+            shadow_padding,
+            corner_diameter,
+            header_height,
+            row_height,
+            maximum_height,
+            minimum_column_width,
+            icon_size,
+            font_size,
+            horizontal_alignment,
+            vertical_alignment,
+            overflow_behavior,
+        }
+    }
+}
+
+impl<Row, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y> Persistent
+    for Table<Row, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>
+{
+    type Data = TableData;
+}
+
+impl<App, Row, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y> Element<App>
+    for Table<Row, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>
+where
+    App: Application,
+    Row: TableRow<App> + 'static,
+    A: Selector<App, Vec<Row>>,
+    B: Selector<App, Vec<TableColumn>>,
+    C: Selector<App, App::Color>,
+    D: Selector<App, App::Color>,
+    E: Selector<App, App::Color>,
+    F: Selector<App, App::Color>,
+    G: Selector<App, App::Color>,
+    H: Selector<App, App::Color>,
+    I: Selector<App, App::Color>,
+    J: Selector<App, App::Color>,
+    K: Selector<App, App::Color>,
+    L: Selector<App, App::Color>,
+    M: Selector<App, App::Color>,
+    N: Selector<App, App::Color>,
+    O: Selector<App, App::ShadowPadding>,
+    P: Selector<App, App::CornerDiameter>,
+    Q: Selector<App, f32>,
+    R: Selector<App, f32>,
+    S: Selector<App, f32>,
+    T: Selector<App, f32>,
+    U: Selector<App, f32>,
+    V: Selector<App, App::FontSize>,
+    W: Selector<App, HorizontalAlignment>,
+    X: Selector<App, VerticalAlignment>,
+    Y: Selector<App, App::OverflowBehavior>,
+{
+    type LayoutInfo = TableLayoutInfo;
+
+    fn create_layout_info(&mut self, state: &State<App>, store: ElementStoreMut, resolvers: &mut dyn Resolvers<App>) -> Self::LayoutInfo {
+        with_single_resolver(resolvers, |resolver| {
+            let rows = state.get(&self.rows);
+            let columns = state.get(&self.columns);
+            let header_height = *state.get(&self.header_height);
+            let row_height = *state.get(&self.row_height);
+            let maximum_height = *state.get(&self.maximum_height);
+
+            let content_height = rows.len() as f32 * row_height;
+            let area = resolver.with_height((header_height + content_height).min(maximum_height));
+            let body_height = area.height - header_height;
+
+            let persistent = self.get_persistent_data(&store, ());
+            let mut inner = persistent.inner.borrow_mut();
+
+            if inner.column_widths.len() != columns.len() {
+                inner.column_widths = columns.iter().map(|column| column.width).collect();
+                inner.sorting = None;
+            }
+
+            let sorting = inner.sorting;
+            let order_key = (rows.len(), sorting);
+
+            // Checking the order is linear, so this is a lot cheaper than sorting every
+            // frame. Rows with equal keys keep their position.
+            let is_outdated = inner.order_key != Some(order_key)
+                || !inner
+                    .order
+                    .is_sorted_by(|&left, &right| compare_rows(rows, left, right, sorting).is_le());
+
+            if is_outdated {
+                let order = &mut inner.order;
+
+                order.clear();
+                order.extend(0..rows.len());
+
+                if sorting.is_some() {
+                    order.sort_by(|&left, &right| compare_rows(rows, left, right, sorting));
+                }
+
+                inner.order_key = Some(order_key);
+            }
+
+            inner.maximum_scroll = (content_height - body_height).max(0.0);
+            inner.scroll = inner.scroll.clamp(0.0, inner.maximum_scroll);
+
+            // The last column fills the remaining width of the table.
+            let mut column_widths = inner.column_widths.clone();
+            if let Some((last_width, other_widths)) = column_widths.split_last_mut() {
+                let used_width: f32 = other_widths.iter().sum();
+                *last_width = last_width.max(area.width - used_width);
+            }
+
+            // Only the rows that are inside the visible part of the table are laid out.
+            let first_visible = (inner.scroll / row_height).floor() as usize;
+            let last_visible = (((inner.scroll + body_height) / row_height).ceil() as usize).min(rows.len());
+
+            let visible_rows = (first_visible..last_visible)
+                .map(|position| {
+                    let index = inner.order[position];
+                    let cells = (0..columns.len()).map(|column| rows[index].cell(column).into_owned()).collect();
+
+                    VisibleRow { index, position, cells }
+                })
+                .collect();
+
+            Self::LayoutInfo {
+                area,
+                column_widths,
+                sorting: inner.sorting,
+                scroll: inner.scroll,
+                rows: visible_rows,
+            }
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<App>,
+        store: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, App>,
+    ) {
+        let persistent = self.get_persistent_data(&store, ());
+        let element_id = store.get_element_id();
+        persistent.element_id.set(Some(element_id));
+
+        let area = layout_info.area;
+        let header_height = *state.get(&self.header_height);
+        let row_height = *state.get(&self.row_height);
+
+        if let Some(column) = persistent.resizing_column.get() {
+            let is_resizing = matches!(
+                layout.get_mouse_mode(),
+                MouseMode::DraggingElement { element_id: dragged_element_id } if *dragged_element_id == element_id
+            );
+
+            match is_resizing {
+                true => {
+                    let column_left = area.left + layout_info.column_widths[..column].iter().sum::<f32>();
+                    let minimum_column_width = *state.get(&self.minimum_column_width);
+                    let width = (layout.get_mouse_position().left() - column_left).max(minimum_column_width);
+
+                    if let Some(column_width) = persistent.inner.borrow_mut().column_widths.get_mut(column) {
+                        *column_width = width;
+                    }
+                }
+                false => persistent.resizing_column.set(None),
+            }
+        }
+
+        if area.check().dont_mark().run(layout) {
+            layout.register_scroll_handler(persistent);
+        }
+
+        let shadow_color = *state.get(&self.shadow_color);
+        let shadow_padding = *state.get(&self.shadow_padding);
+
+        layout.add_rectangle(
+            area,
+            *state.get(&self.corner_diameter),
+            *state.get(&self.background_color),
+            shadow_color,
+            shadow_padding,
+        );
+
+        let columns = state.get(&self.columns);
+        let font_size = *state.get(&self.font_size);
+        let highlight_color = *state.get(&self.highlight_color);
+        let horizontal_alignment = *state.get(&self.horizontal_alignment);
+        let vertical_alignment = *state.get(&self.vertical_alignment);
+        let overflow_behavior = *state.get(&self.overflow_behavior);
+        let separator_color = *state.get(&self.separator_color);
+        let no_corners = App::CornerDiameter::new(0.0, 0.0, 0.0, 0.0);
+
+        // Header
+        let mut hovered_header = None;
+        let mut column_left = area.left;
+
+        for (column, (table_column, column_width)) in columns.iter().zip(&layout_info.column_widths).enumerate() {
+            let header_area = Area {
+                left: column_left,
+                top: area.top,
+                width: *column_width,
+                height: header_height,
+            };
+            let handle_area = Area {
+                left: column_left + column_width - RESIZE_HANDLE_WIDTH / 2.0,
+                width: RESIZE_HANDLE_WIDTH,
+                ..header_area
+            };
+
+            let is_last = column + 1 == columns.len();
+
+            if !is_last && handle_area.check().dont_mark().run(layout) {
+                hovered_header = Some(HeaderAction::Resize { column });
+            } else if hovered_header.is_none() && header_area.check().dont_mark().run(layout) {
+                hovered_header = Some(HeaderAction::Sort { column });
+            }
+
+            let background_color = match hovered_header {
+                Some(HeaderAction::Sort { column: hovered_column }) if hovered_column == column => {
+                    *state.get(&self.hovered_header_background_color)
+                }
+                _ => *state.get(&self.header_background_color),
+            };
+
+            layout.add_rectangle(
+                header_area,
+                no_corners,
+                background_color,
+                background_color,
+                App::ShadowPadding::none(),
+            );
+
+            layout.with_clip(header_area, |layout| {
+                layout.add_text(
+                    header_area,
+                    table_column.title,
+                    font_size,
+                    *state.get(&self.header_foreground_color),
+                    highlight_color,
+                    horizontal_alignment,
+                    vertical_alignment,
+                    overflow_behavior,
+                );
+            });
+
+            if let Some((sorted_column, direction)) = layout_info.sorting
+                && sorted_column == column
+            {
+                let icon_size = *state.get(&self.icon_size);
+
+                // Descending order is shown with the arrow pointing down.
+                layout.add_icon(
+                    Area {
+                        left: header_area.left + header_area.width - icon_size - (header_height - icon_size) / 2.0,
+                        top: header_area.top + (header_height - icon_size) / 2.0,
+                        width: icon_size,
+                        height: icon_size,
+                    },
+                    Icon::ExpandArrow {
+                        expanded: direction == SortDirection::Descending,
+                    },
+                    *state.get(&self.icon_color),
+                );
+            }
+
+            if !is_last {
+                layout.add_rectangle(
+                    Area {
+                        left: column_left + column_width - SEPARATOR_WIDTH / 2.0,
+                        top: area.top,
+                        width: SEPARATOR_WIDTH,
+                        height: area.height,
+                    },
+                    no_corners,
+                    separator_color,
+                    separator_color,
+                    App::ShadowPadding::none(),
+                );
+            }
+
+            column_left += column_width;
+        }
+
+        persistent.hovered_header.set(hovered_header);
+
+        if hovered_header.is_some() {
+            layout.register_click_handler(MouseButton::Left, persistent);
+        }
+
+        // Rows
+        let body_area = Area {
+            top: area.top + header_height,
+            height: area.height - header_height,
+            ..area
+        };
+        let rows = state.get(&self.rows);
+        let foreground_color = *state.get(&self.foreground_color);
+
+        layout.with_clip(body_area, |layout| {
+            for visible_row in &layout_info.rows {
+                let row = &rows[visible_row.index];
+                let row_area = Area {
+                    top: body_area.top + visible_row.position as f32 * row_height - layout_info.scroll,
+                    height: row_height,
+                    ..body_area
+                };
+
+                let click_handler = row.click_handler();
+                let is_hovered = click_handler.is_some() && row_area.check().dont_mark().run(layout);
+
+                if is_hovered && let Some(click_handler) = click_handler {
+                    layout.register_click_handler(MouseButton::Left, click_handler);
+                }
+
+                let background_color = match is_hovered {
+                    true => *state.get(&self.hovered_row_background_color),
+                    false if visible_row.position % 2 == 1 => *state.get(&self.alternate_row_background_color),
+                    false => *state.get(&self.row_background_color),
+                };
+
+                layout.add_rectangle(
+                    row_area,
+                    no_corners,
+                    background_color,
+                    background_color,
+                    App::ShadowPadding::none(),
+                );
+
+                let mut cell_left = row_area.left;
+
+                for (cell, column_width) in visible_row.cells.iter().zip(&layout_info.column_widths) {
+                    let cell_area = Area {
+                        left: cell_left,
+                        width: *column_width,
+                        ..row_area
+                    };
+
+                    layout.with_clip(cell_area, |layout| {
+                        layout.add_text(
+                            cell_area,
+                            cell,
+                            font_size,
+                            foreground_color,
+                            highlight_color,
+                            horizontal_alignment,
+                            vertical_alignment,
+                            overflow_behavior,
+                        );
+                    });
+
+                    cell_left += column_width;
+                }
+            }
+        });
+
+        // All checks above leave the hover unmarked so the individual parts of the
+        // table don't shadow each other, so mark it once at the end.
+        area.check().run(layout);
+    }
+}
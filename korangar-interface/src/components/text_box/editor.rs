@@ -0,0 +1,566 @@
+use std::ops::Range;
+
+/// Maximum number of undo steps kept per text box.
+const UNDO_LIMIT: usize = 100;
+
+/// Movement of the caret inside the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaretMovement {
+    Left,
+    Right,
+    WordLeft,
+    WordRight,
+    Start,
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Insert,
+    Delete,
+}
+
+/// Text that is currently being composed with an input method (IME) but was
+/// not committed yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Composition {
+    pub text: String,
+    /// Byte range of the cursor inside the composed text, as reported by the
+    /// input method.
+    pub cursor: Option<Range<usize>>,
+}
+
+struct Snapshot {
+    text: String,
+    caret: usize,
+    anchor: usize,
+}
+
+fn floor_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+
+    index
+}
+
+fn previous_boundary(text: &str, index: usize) -> usize {
+    text[..index].char_indices().next_back().map(|(index, _)| index).unwrap_or(0)
+}
+
+fn next_boundary(text: &str, index: usize) -> usize {
+    text[index..]
+        .chars()
+        .next()
+        .map(|character| index + character.len_utf8())
+        .unwrap_or(index)
+}
+
+/// Start of the word before `index`, skipping any whitespace in between.
+fn previous_word_boundary(text: &str, index: usize) -> usize {
+    let trimmed = text[..index].trim_end();
+
+    trimmed
+        .char_indices()
+        .rfind(|(_, character)| character.is_whitespace())
+        .map(|(index, character)| index + character.len_utf8())
+        .unwrap_or(0)
+}
+
+/// Start of the word after `index`.
+fn next_word_boundary(text: &str, index: usize) -> usize {
+    let remaining = &text[index..];
+    let word_end = remaining.find(char::is_whitespace).unwrap_or(remaining.len());
+    let whitespace = &remaining[word_end..];
+    let next_word = whitespace
+        .find(|character: char| !character.is_whitespace())
+        .unwrap_or(whitespace.len());
+
+    index + word_end + next_word
+}
+
+/// Caret, selection, and undo history of an editable text.
+///
+/// The text itself is owned by the application, so every operation takes it as
+/// an argument. All positions are byte offsets on character boundaries. The
+/// selection spans from the anchor to the caret, so it is empty if both are
+/// the same.
+#[derive(Default)]
+pub struct TextEditor {
+    caret: usize,
+    anchor: usize,
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    last_edit: Option<EditKind>,
+    working_text: Option<String>,
+    composition: Option<Composition>,
+}
+
+impl TextEditor {
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    pub fn selection(&self) -> Option<Range<usize>> {
+        (self.caret != self.anchor).then(|| self.caret.min(self.anchor)..self.caret.max(self.anchor))
+    }
+
+    pub fn selected_text<'a>(&self, text: &'a str) -> &'a str {
+        self.selection().map(|selection| &text[selection]).unwrap_or_default()
+    }
+
+    /// Changes to the application state are only applied at the end of the
+    /// frame, so multiple edits in one frame need to build on the text of the
+    /// previous edit rather than on the state.
+    pub fn working_text(&self) -> Option<&str> {
+        self.working_text.as_deref()
+    }
+
+    pub fn set_working_text(&mut self, text: String) {
+        self.working_text = Some(text);
+    }
+
+    pub fn composition(&self) -> Option<&Composition> {
+        self.composition.as_ref()
+    }
+
+    /// Update the text that is being composed. An empty text ends the
+    /// composition without committing anything.
+    pub fn set_composition(&mut self, text: String, cursor: Option<Range<usize>>) {
+        self.composition = (!text.is_empty()).then_some(Composition { text, cursor });
+    }
+
+    pub fn clear_composition(&mut self) {
+        self.composition = None;
+    }
+
+    /// End the composition and insert the committed text, replacing the
+    /// selection. Works the same as [`paste`](Self::paste).
+    pub fn commit_composition(&mut self, text: &mut String, committed_text: &str, maximum_length: usize) -> bool {
+        self.composition = None;
+        self.paste(text, committed_text, maximum_length)
+    }
+
+    /// Text as it should be displayed while composing, with the composition
+    /// replacing the selection. Also returns the range of the composition and
+    /// the caret position inside the displayed text.
+    pub fn composed_text(&self, text: &str) -> Option<(String, Range<usize>, usize)> {
+        let composition = self.composition.as_ref()?;
+        let replaced = self.selection().unwrap_or(self.caret..self.caret);
+
+        let mut composed_text = String::with_capacity(text.len() + composition.text.len());
+        composed_text.push_str(&text[..replaced.start]);
+        composed_text.push_str(&composition.text);
+        composed_text.push_str(&text[replaced.end..]);
+
+        let composition_range = replaced.start..replaced.start + composition.text.len();
+        let cursor = composition
+            .cursor
+            .as_ref()
+            .map(|cursor| floor_boundary(&composition.text, cursor.end))
+            .unwrap_or(composition.text.len());
+
+        Some((composed_text, composition_range.clone(), composition_range.start + cursor))
+    }
+
+    /// Keep the caret and selection inside the text, which the application
+    /// might have changed, and discard the working text. Should be called
+    /// once per frame with the current text.
+    pub fn synchronize(&mut self, text: &str) {
+        self.caret = floor_boundary(text, self.caret);
+        self.anchor = floor_boundary(text, self.anchor);
+        self.working_text = None;
+    }
+
+    pub fn move_caret(&mut self, text: &str, movement: CaretMovement, select: bool) {
+        let position = match (movement, self.selection()) {
+            // Moving without selecting collapses the selection to the side of the
+            // movement.
+            (CaretMovement::Left, Some(selection)) if !select => selection.start,
+            (CaretMovement::Right, Some(selection)) if !select => selection.end,
+            (CaretMovement::Left, _) => previous_boundary(text, self.caret),
+            (CaretMovement::Right, _) => next_boundary(text, self.caret),
+            (CaretMovement::WordLeft, _) => previous_word_boundary(text, self.caret),
+            (CaretMovement::WordRight, _) => next_word_boundary(text, self.caret),
+            (CaretMovement::Start, _) => 0,
+            (CaretMovement::End, _) => text.len(),
+        };
+
+        self.set_caret(text, position, select);
+    }
+
+    /// Place the caret at `position`. If `select` is set, the selection is
+    /// extended to the new position.
+    pub fn set_caret(&mut self, text: &str, position: usize, select: bool) {
+        self.caret = floor_boundary(text, position);
+
+        if !select {
+            self.anchor = self.caret;
+        }
+
+        self.last_edit = None;
+    }
+
+    pub fn select_all(&mut self, text: &str) {
+        self.anchor = 0;
+        self.caret = text.len();
+        self.last_edit = None;
+    }
+
+    /// Replace the selection with `insertion`, shortening it so the text never
+    /// exceeds `maximum_length` bytes. Returns `false` if the text didn't
+    /// change.
+    pub fn insert(&mut self, text: &mut String, insertion: &str, maximum_length: usize) -> bool {
+        let selection = self.selection().unwrap_or(self.caret..self.caret);
+        let available_length = maximum_length.saturating_sub(text.len() - selection.len());
+        let insertion = &insertion[..floor_boundary(insertion, available_length)];
+
+        if insertion.is_empty() {
+            return false;
+        }
+
+        self.record(text, EditKind::Insert);
+
+        text.replace_range(selection.clone(), insertion);
+        self.caret = selection.start + insertion.len();
+        self.anchor = self.caret;
+
+        // Start a new undo step after every word.
+        if insertion.contains(char::is_whitespace) {
+            self.last_edit = None;
+        }
+
+        true
+    }
+
+    /// Same as [`insert`](Self::insert), but always creates a separate undo
+    /// step.
+    pub fn paste(&mut self, text: &mut String, insertion: &str, maximum_length: usize) -> bool {
+        self.last_edit = None;
+        let changed = self.insert(text, insertion, maximum_length);
+        self.last_edit = None;
+
+        changed
+    }
+
+    /// Delete the selection or, if nothing is selected, the character or word
+    /// before the caret.
+    pub fn delete_backward(&mut self, text: &mut String, word: bool) -> bool {
+        let range = match self.selection() {
+            Some(selection) => selection,
+            None if word => previous_word_boundary(text, self.caret)..self.caret,
+            None => previous_boundary(text, self.caret)..self.caret,
+        };
+
+        self.delete(text, range)
+    }
+
+    /// Delete the selection or, if nothing is selected, the character or word
+    /// after the caret.
+    pub fn delete_forward(&mut self, text: &mut String, word: bool) -> bool {
+        let range = match self.selection() {
+            Some(selection) => selection,
+            None if word => self.caret..next_word_boundary(text, self.caret),
+            None => self.caret..next_boundary(text, self.caret),
+        };
+
+        self.delete(text, range)
+    }
+
+    /// Remove the selected text and return it.
+    pub fn cut(&mut self, text: &mut String) -> Option<String> {
+        let selection = self.selection()?;
+        let cut_text = text[selection.clone()].to_owned();
+
+        self.last_edit = None;
+        self.delete(text, selection);
+        self.last_edit = None;
+
+        Some(cut_text)
+    }
+
+    pub fn undo(&mut self, text: &mut String) -> bool {
+        let Some(snapshot) = self.undo_stack.pop() else {
+            return false;
+        };
+
+        self.redo_stack.push(self.snapshot(text));
+        self.restore(text, snapshot);
+
+        true
+    }
+
+    pub fn redo(&mut self, text: &mut String) -> bool {
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return false;
+        };
+
+        self.undo_stack.push(self.snapshot(text));
+        self.restore(text, snapshot);
+
+        true
+    }
+
+    fn delete(&mut self, text: &mut String, range: Range<usize>) -> bool {
+        if range.is_empty() {
+            return false;
+        }
+
+        self.record(text, EditKind::Delete);
+
+        text.replace_range(range.clone(), "");
+        self.caret = range.start;
+        self.anchor = range.start;
+
+        true
+    }
+
+    /// Save the text before an edit. Consecutive edits of the same kind are
+    /// merged into one undo step.
+    fn record(&mut self, text: &str, kind: EditKind) {
+        if self.last_edit != Some(kind) {
+            if self.undo_stack.len() == UNDO_LIMIT {
+                self.undo_stack.remove(0);
+            }
+
+            let snapshot = self.snapshot(text);
+            self.undo_stack.push(snapshot);
+        }
+
+        self.redo_stack.clear();
+        self.last_edit = Some(kind);
+    }
+
+    fn snapshot(&self, text: &str) -> Snapshot {
+        Snapshot {
+            text: text.to_owned(),
+            caret: self.caret,
+            anchor: self.anchor,
+        }
+    }
+
+    fn restore(&mut self, text: &mut String, snapshot: Snapshot) {
+        *text = snapshot.text;
+        self.caret = snapshot.caret;
+        self.anchor = snapshot.anchor;
+        self.last_edit = None;
+    }
+}
+
+#[cfg(test)]
+mod editing {
+    use super::{CaretMovement, TextEditor};
+
+    const MAXIMUM_LENGTH: usize = 64;
+
+    fn type_text(editor: &mut TextEditor, text: &mut String, typed: &str) {
+        for character in typed.chars() {
+            editor.insert(text, character.encode_utf8(&mut [0; 4]), MAXIMUM_LENGTH);
+        }
+    }
+
+    #[test]
+    fn insert_in_the_middle() {
+        let mut editor = TextEditor::default();
+        let mut text = "helo".to_owned();
+
+        editor.set_caret(&text, 3, false);
+        type_text(&mut editor, &mut text, "l");
+
+        assert_eq!(text, "hello");
+        assert_eq!(editor.caret(), 4);
+    }
+
+    #[test]
+    fn replace_selection() {
+        let mut editor = TextEditor::default();
+        let mut text = "hello world".to_owned();
+
+        editor.set_caret(&text, 6, false);
+        editor.move_caret(&text, CaretMovement::End, true);
+
+        assert_eq!(editor.selected_text(&text), "world");
+
+        type_text(&mut editor, &mut text, "there");
+
+        assert_eq!(text, "hello there");
+        assert_eq!(editor.selection(), None);
+    }
+
+    #[test]
+    fn maximum_length() {
+        let mut editor = TextEditor::default();
+        let mut text = "ab".to_owned();
+
+        editor.move_caret(&text, CaretMovement::End, false);
+
+        // 'ä' takes two bytes, so only one fits.
+        assert!(editor.paste(&mut text, "ää", 5));
+        assert_eq!(text, "abä");
+        assert!(!editor.insert(&mut text, "c", 4));
+    }
+
+    #[test]
+    fn multi_byte_characters() {
+        let mut editor = TextEditor::default();
+        let mut text = "äöü".to_owned();
+
+        editor.move_caret(&text, CaretMovement::End, false);
+        editor.move_caret(&text, CaretMovement::Left, false);
+        editor.delete_backward(&mut text, false);
+
+        assert_eq!(text, "äü");
+        assert_eq!(editor.caret(), 2);
+    }
+
+    #[test]
+    fn word_movement() {
+        let mut editor = TextEditor::default();
+        let text = "one two  three";
+
+        editor.move_caret(text, CaretMovement::WordRight, false);
+        assert_eq!(editor.caret(), 4);
+
+        editor.move_caret(text, CaretMovement::WordRight, false);
+        assert_eq!(editor.caret(), 9);
+
+        editor.move_caret(text, CaretMovement::End, false);
+        editor.move_caret(text, CaretMovement::WordLeft, false);
+        assert_eq!(editor.caret(), 9);
+
+        editor.move_caret(text, CaretMovement::WordLeft, false);
+        assert_eq!(editor.caret(), 4);
+    }
+
+    #[test]
+    fn delete_words() {
+        let mut editor = TextEditor::default();
+        let mut text = "one two three".to_owned();
+
+        editor.move_caret(&text, CaretMovement::End, false);
+        editor.delete_backward(&mut text, true);
+        assert_eq!(text, "one two ");
+
+        editor.move_caret(&text, CaretMovement::Start, false);
+        editor.delete_forward(&mut text, true);
+        assert_eq!(text, "two ");
+    }
+
+    #[test]
+    fn collapse_selection() {
+        let mut editor = TextEditor::default();
+        let text = "hello";
+
+        editor.set_caret(text, 1, false);
+        editor.set_caret(text, 4, true);
+        editor.move_caret(text, CaretMovement::Left, false);
+
+        assert_eq!(editor.caret(), 1);
+        assert_eq!(editor.selection(), None);
+    }
+
+    #[test]
+    fn cut() {
+        let mut editor = TextEditor::default();
+        let mut text = "hello world".to_owned();
+
+        assert_eq!(editor.cut(&mut text), None);
+
+        editor.set_caret(&text, 5, false);
+        editor.move_caret(&text, CaretMovement::End, true);
+
+        assert_eq!(editor.cut(&mut text).as_deref(), Some(" world"));
+        assert_eq!(text, "hello");
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut editor = TextEditor::default();
+        let mut text = String::new();
+
+        type_text(&mut editor, &mut text, "hello world");
+        editor.delete_backward(&mut text, false);
+
+        assert!(editor.undo(&mut text));
+        assert_eq!(text, "hello world");
+
+        assert!(editor.undo(&mut text));
+        assert_eq!(text, "hello ");
+
+        assert!(editor.undo(&mut text));
+        assert_eq!(text, "");
+        assert!(!editor.undo(&mut text));
+
+        assert!(editor.redo(&mut text));
+        assert_eq!(text, "hello ");
+        assert_eq!(editor.caret(), 6);
+
+        // Editing discards everything that could be redone.
+        type_text(&mut editor, &mut text, "!");
+        assert!(!editor.redo(&mut text));
+    }
+
+    #[test]
+    fn compose_and_commit() {
+        let mut editor = TextEditor::default();
+        let mut text = "ab".to_owned();
+
+        editor.set_caret(&text, 1, false);
+        editor.set_composition("ㅎ".to_owned(), Some(3..3));
+        editor.set_composition("하".to_owned(), Some(3..3));
+
+        let (composed_text, composition_range, caret) = editor.composed_text(&text).unwrap();
+        assert_eq!(composed_text, "a하b");
+        assert_eq!(composition_range, 1..4);
+        assert_eq!(caret, 4);
+
+        // The composition is only displayed and not part of the text.
+        assert_eq!(text, "ab");
+
+        assert!(editor.commit_composition(&mut text, "한", MAXIMUM_LENGTH));
+        assert_eq!(text, "a한b");
+        assert_eq!(editor.caret(), 4);
+        assert_eq!(editor.composition(), None);
+
+        assert!(editor.undo(&mut text));
+        assert_eq!(text, "ab");
+    }
+
+    #[test]
+    fn compose_over_selection() {
+        let mut editor = TextEditor::default();
+        let mut text = "hello".to_owned();
+
+        editor.select_all(&text);
+        editor.set_composition("こ".to_owned(), None);
+
+        let (composed_text, composition_range, caret) = editor.composed_text(&text).unwrap();
+        assert_eq!(composed_text, "こ");
+        assert_eq!(composition_range, 0..3);
+        assert_eq!(caret, 3);
+
+        // An empty composition cancels without changing the text.
+        editor.set_composition(String::new(), None);
+        assert_eq!(editor.composed_text(&text), None);
+
+        editor.set_composition("こ".to_owned(), None);
+        assert!(editor.commit_composition(&mut text, "こんにちは", 9));
+        assert_eq!(text, "こんに");
+    }
+
+    #[test]
+    fn synchronize_with_changed_text() {
+        let mut editor = TextEditor::default();
+        let text = "hello";
+
+        editor.select_all(text);
+        editor.set_working_text(text.to_owned());
+        editor.synchronize("");
+
+        assert_eq!(editor.caret(), 0);
+        assert_eq!(editor.selection(), None);
+        assert_eq!(editor.working_text(), None);
+    }
+}
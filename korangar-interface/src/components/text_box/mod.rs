@@ -0,0 +1,790 @@
+mod editor;
+
+use std::any::Any;
+use std::cell::{Cell, RefCell, UnsafeCell};
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use rust_state::{Path, RustState, Selector, State};
+
+pub use self::editor::{CaretMovement, TextEditor};
+use crate::MouseMode;
+use crate::application::{Application, Clipboard, CornerDiameter, Position, ShadowPadding, Size, TextLayouter};
+use crate::element::Element;
+use crate::element::id::{ElementId, FocusIdExt};
+use crate::element::store::{ElementStore, ElementStoreMut, Persistent, PersistentData, PersistentExt};
+use crate::event::{ClickHandler, CompositionEvent, EditKey, Event, EventQueue, FocusDirection, InputHandler, KeyInput, NavigationInput};
+use crate::layout::alignment::{HorizontalAlignment, VerticalAlignment};
+use crate::layout::area::Area;
+use crate::layout::{Icon, MouseButton, Resolvers, WindowLayout, with_single_resolver};
+
+#[derive(RustState)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TextBoxTheme<App>
+where
+    App: Application,
+{
+    pub foreground_color: App::Color,
+    pub background_color: App::Color,
+    pub highlight_color: App::Color,
+    pub hovered_foreground_color: App::Color,
+    pub hovered_background_color: App::Color,
+    pub focused_foreground_color: App::Color,
+    pub focused_background_color: App::Color,
+    pub ghost_foreground_color: App::Color,
+    pub hide_icon_color: App::Color,
+    pub hovered_hide_icon_color: App::Color,
+    pub caret_color: App::Color,
+    pub selection_color: App::Color,
+    pub shadow_color: App::Color,
+    pub shadow_padding: App::ShadowPadding,
+    pub height: f32,
+    pub corner_diameter: App::CornerDiameter,
+    pub font_size: App::FontSize,
+    pub horizontal_alignment: HorizontalAlignment,
+    pub vertical_alignment: VerticalAlignment,
+    pub overflow_behavior: App::OverflowBehavior,
+}
+
+/// Width of the caret in pixels.
+const CARET_WIDTH: f32 = 2.0;
+/// Height of the line below text that is being composed with an input method.
+const COMPOSITION_UNDERLINE_HEIGHT: f32 = 2.0;
+
+/// Horizontal position of every caret position in the displayed text.
+#[derive(Default)]
+struct CaretOffsets {
+    /// The text the offsets were measured for.
+    text: String,
+    /// Byte index and horizontal offset from the start of the text. The last
+    /// offset is the width of the entire text.
+    offsets: Vec<(usize, f32)>,
+    text_height: f32,
+    /// Byte range of the composition in the displayed text and the caret
+    /// position inside of it.
+    composition: Option<(Range<usize>, usize)>,
+}
+
+impl CaretOffsets {
+    fn offset(&self, index: usize) -> f32 {
+        self.offsets
+            .iter()
+            .find(|(caret_index, _)| *caret_index >= index)
+            .or(self.offsets.last())
+            .map(|(_, offset)| *offset)
+            .unwrap_or_default()
+    }
+
+    fn text_width(&self) -> f32 {
+        self.offsets.last().map(|(_, offset)| *offset).unwrap_or_default()
+    }
+
+    fn closest_index(&self, offset: f32) -> usize {
+        self.offsets
+            .iter()
+            .min_by(|(_, left), (_, right)| (left - offset).abs().total_cmp(&(right - offset).abs()))
+            .map(|(index, _)| *index)
+            .unwrap_or_default()
+    }
+}
+
+pub struct TextBoxData {
+    is_hidden: Cell<bool>,
+    hidden_text: UnsafeCell<String>,
+    composed_text: UnsafeCell<String>,
+    caret_offsets: RefCell<CaretOffsets>,
+}
+
+impl PersistentData for TextBoxData {
+    type Inputs = bool;
+
+    fn from_inputs(inputs: Self::Inputs) -> Self {
+        Self {
+            is_hidden: Cell::new(inputs),
+            hidden_text: UnsafeCell::new(String::new()),
+            composed_text: UnsafeCell::new(String::new()),
+            caret_offsets: RefCell::default(),
+        }
+    }
+}
+
+impl<App> ClickHandler<App> for TextBoxData
+where
+    App: Application,
+{
+    fn handle_click(&self, _: &State<App>, _: &mut EventQueue<App>) {
+        let is_hidden = self.is_hidden.get();
+        self.is_hidden.set(!is_hidden);
+    }
+}
+
+#[derive(Default)]
+struct FocusClick {
+    element_id: Option<ElementId>,
+    /// Set when clicked so the caret is placed at the mouse position instead
+    /// of extending the selection on the next frame.
+    start_selection: Cell<bool>,
+}
+
+impl FocusClick {
+    fn update(&mut self, element_id: ElementId) {
+        self.element_id = Some(element_id);
+    }
+}
+
+impl<App> ClickHandler<App> for FocusClick
+where
+    App: Application,
+{
+    fn handle_click(&self, _: &State<App>, queue: &mut EventQueue<App>) {
+        let element_id = *self.element_id.as_ref().unwrap();
+        queue.queue(Event::FocusElementPost { element_id });
+        queue.queue(Event::SetMouseMode {
+            mouse_mode: MouseMode::SelectingText { element_id },
+        });
+
+        self.start_selection.set(true);
+    }
+}
+
+pub struct TextBox<Text, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Id> {
+    text_marker: PhantomData<Text>,
+    ghost_text: A,
+    state: B,
+    input_handler: C,
+    hidable: D,
+    foreground_color: E,
+    background_color: F,
+    highlight_color: G,
+    hovered_foreground_color: H,
+    hovered_background_color: I,
+    focused_foreground_color: J,
+    focused_background_color: K,
+    ghost_foreground_color: L,
+    hide_icon_color: M,
+    hovered_hide_icon_color: N,
+    caret_color: O,
+    selection_color: P,
+    shadow_color: Q,
+    shadow_padding: R,
+    height: S,
+    corner_diameter: T,
+    font_size: U,
+    horizontal_alignment: V,
+    vertical_alignment: W,
+    overflow_behavior: X,
+    focus_id: Id,
+    focus_click: FocusClick,
+}
+
+impl<Text, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Id>
+    TextBox<Text, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Id>
+{
+    /// This function is supposed to be called from a component macro and not
+    /// intended to be called manually.
+    #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn component_new(
+        ghost_text: A,
+        state: B,
+        input_handler: C,
+        hidable: D,
+        foreground_color: E,
+        background_color: F,
+        highlight_color: G,
+        hovered_foreground_color: H,
+        hovered_background_color: I,
+        focused_foreground_color: J,
+        focused_background_color: K,
+        ghost_foreground_color: L,
+        hide_icon_color: M,
+        hovered_hide_icon_color: N,
+        caret_color: O,
+        selection_color: P,
+        shadow_color: Q,
+        shadow_padding: R,
+        height: S,
+        corner_diameter: T,
+        font_size: U,
+        horizontal_alignment: V,
+        vertical_alignment: W,
+        overflow_behavior: X,
+        focus_id: Id,
+    ) -> Self {
+        Self {
+            text_marker: PhantomData,
+            ghost_text,
+            state,
+            input_handler,
+            hidable,
+            foreground_color,
+            background_color,
+            highlight_color,
+            hovered_foreground_color,
+            hovered_background_color,
+            focused_foreground_color,
+            focused_background_color,
+            ghost_foreground_color,
+            hide_icon_color,
+            hovered_hide_icon_color,
+            caret_color,
+            selection_color,
+            shadow_color,
+            shadow_padding,
+            height,
+            corner_diameter,
+            font_size,
+            horizontal_alignment,
+            vertical_alignment,
+            overflow_behavior,
+            focus_id,
+            focus_click: FocusClick::default(),
+        }
+    }
+}
+
+impl<Text, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Id> Persistent
+    for TextBox<Text, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Id>
+{
+    type Data = TextBoxData;
+}
+
+impl<App, Text, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Id> Element<App>
+    for TextBox<Text, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Id>
+where
+    App: Application,
+    Text: AsRef<str> + 'static,
+    A: Selector<App, Text>,
+    B: Selector<App, String>,
+    C: InputHandler<App> + 'static,
+    D: Selector<App, bool>,
+    E: Selector<App, App::Color>,
+    F: Selector<App, App::Color>,
+    G: Selector<App, App::Color>,
+    H: Selector<App, App::Color>,
+    I: Selector<App, App::Color>,
+    J: Selector<App, App::Color>,
+    K: Selector<App, App::Color>,
+    L: Selector<App, App::Color>,
+    M: Selector<App, App::Color>,
+    N: Selector<App, App::Color>,
+    O: Selector<App, App::Color>,
+    P: Selector<App, App::Color>,
+    Q: Selector<App, App::Color>,
+    R: Selector<App, App::ShadowPadding>,
+    S: Selector<App, f32>,
+    T: Selector<App, App::CornerDiameter>,
+    U: Selector<App, App::FontSize>,
+    V: Selector<App, HorizontalAlignment>,
+    W: Selector<App, VerticalAlignment>,
+    X: Selector<App, App::OverflowBehavior>,
+    Id: Any,
+{
+    fn create_layout_info(&mut self, state: &State<App>, store: ElementStoreMut, resolvers: &mut dyn Resolvers<App>) -> Self::LayoutInfo {
+        with_single_resolver(resolvers, |resolver| {
+            let height = *state.get(&self.height);
+
+            let mut display_text = state.get(&self.state).as_str();
+            let mut is_hidden = false;
+
+            self.focus_click.update(store.get_element_id());
+
+            let text_editor = self.input_handler.text_editor();
+
+            if let Some(editor) = text_editor {
+                editor.borrow_mut().synchronize(display_text);
+            }
+
+            if *state.get(&self.hidable) {
+                let persistent_data = self.get_persistent_data(&store, true);
+                is_hidden = persistent_data.is_hidden.get();
+
+                if is_hidden {
+                    // SAFETY:
+                    //
+                    // This is only used here to create a string with all '*' characters, so this
+                    // should be perfectly safe.
+                    let hidden_text = unsafe { &mut *persistent_data.hidden_text.get() };
+
+                    let display_text_length = display_text.len();
+                    if hidden_text.len() != display_text_length {
+                        *hidden_text = "*".repeat(display_text_length);
+                    }
+
+                    display_text = hidden_text;
+                }
+            }
+
+            // Text that is being composed with an input method is displayed in place of the
+            // selection, but never in hidden text boxes.
+            let composition = match is_hidden {
+                true => None,
+                false => text_editor.and_then(|editor| editor.borrow().composed_text(display_text)),
+            };
+
+            if let Some((composed_text, ..)) = &composition {
+                let persistent_data = self.get_persistent_data(&store, *state.get(&self.hidable));
+
+                // SAFETY:
+                //
+                // The composed text is only written here, before the text box is laid out for
+                // this frame, so there are no references to it at this point.
+                let stored_composed_text = unsafe { &mut *persistent_data.composed_text.get() };
+                stored_composed_text.clone_from(composed_text);
+
+                display_text = stored_composed_text;
+            }
+
+            let edited_text = display_text;
+
+            if display_text.is_empty() {
+                display_text = state.get(&self.ghost_text).as_ref();
+            }
+
+            let foreground_color = *state.get(&self.foreground_color);
+            let highlight_color = *state.get(&self.highlight_color);
+            let overflow_behavior = *state.get(&self.overflow_behavior);
+
+            let (size, font_size) = resolver.get_text_dimensions(
+                display_text,
+                foreground_color,
+                highlight_color,
+                *state.get(&self.font_size),
+                *state.get(&self.horizontal_alignment),
+                overflow_behavior,
+            );
+
+            if text_editor.is_some() {
+                let persistent_data = self.get_persistent_data(&store, *state.get(&self.hidable));
+                let mut caret_offsets = persistent_data.caret_offsets.borrow_mut();
+
+                caret_offsets.composition = composition.map(|(_, composition_range, caret)| (composition_range, caret));
+
+                // Measuring every caret position is fairly expensive, so we only do it when
+                // the text or font size changes. This assumes that the text is displayed in a
+                // single line.
+                if caret_offsets.offsets.is_empty() || caret_offsets.text != edited_text || caret_offsets.text_height != size.height() {
+                    let text_layouter = resolver.get_text_layouter();
+
+                    caret_offsets.offsets = edited_text
+                        .char_indices()
+                        .map(|(index, _)| index)
+                        .chain(std::iter::once(edited_text.len()))
+                        .map(|index| {
+                            let (prefix_size, _) = text_layouter.get_text_dimensions(
+                                &edited_text[..index],
+                                foreground_color,
+                                highlight_color,
+                                font_size,
+                                f32::MAX,
+                                overflow_behavior,
+                            );
+
+                            (index, prefix_size.width())
+                        })
+                        .collect();
+                    caret_offsets.text = edited_text.to_owned();
+                    caret_offsets.text_height = size.height();
+                }
+            }
+
+            Self::LayoutInfo {
+                area: resolver.with_height(height.max(size.height())),
+                font_size,
+            }
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<App>,
+        store: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, App>,
+    ) {
+        let element_id = store.get_element_id();
+        let is_focused = layout.is_element_focused(element_id);
+
+        let hide_button = state.get(&self.hidable).then(|| {
+            let button_area = Area {
+                left: layout_info.area.left + layout_info.area.width - layout_info.area.height - layout_info.area.height / 2.0,
+                top: layout_info.area.top,
+                width: layout_info.area.height + layout_info.area.height / 2.0,
+                height: layout_info.area.height,
+            };
+
+            let is_hoverered = button_area.check().run(layout);
+            let persistent_data = self.get_persistent_data(&store, true);
+
+            if is_hoverered {
+                layout.register_click_handler(MouseButton::Left, persistent_data);
+
+                // If the text field is already focused, we don't want to unfocus it when
+                // clicking the hide button. So we add another click area to
+                // re-focus if the button is clicked.
+                if is_focused {
+                    layout.register_click_handler(MouseButton::Left, &self.focus_click);
+                }
+            }
+
+            (button_area, is_hoverered, persistent_data)
+        });
+
+        let is_hovered = layout_info.area.check().run(layout);
+
+        if is_hovered {
+            layout.register_click_handler(MouseButton::Left, &self.focus_click);
+        }
+
+        if is_focused {
+            layout.register_input_handler(&self.input_handler);
+        }
+
+        let horizontal_alignment = *state.get(&self.horizontal_alignment);
+        let vertical_alignment = *state.get(&self.vertical_alignment);
+
+        let background_color = match is_hovered {
+            _ if is_focused => *state.get(&self.focused_background_color),
+            true => *state.get(&self.hovered_background_color),
+            false => *state.get(&self.background_color),
+        };
+
+        let corner_diameter = *state.get(&self.corner_diameter);
+
+        layout.add_rectangle(
+            layout_info.area,
+            corner_diameter,
+            background_color,
+            *state.get(&self.shadow_color),
+            *state.get(&self.shadow_padding),
+        );
+
+        let mut display_text = state.get(&self.state).as_str();
+
+        if let Some((button_area, is_hovered, persistent_data)) = hide_button {
+            let is_hidden = persistent_data.is_hidden.get();
+
+            if is_hidden {
+                // SAFETY:
+                //
+                // This is only used here to create a string with all '*' characters, so this
+                // should be perfectly safe.
+                let hidden_text = unsafe { &mut *persistent_data.hidden_text.get() };
+
+                let display_text_length = display_text.len();
+                if hidden_text.len() != display_text_length {
+                    *hidden_text = "*".repeat(display_text_length);
+                }
+
+                display_text = hidden_text;
+            }
+
+            let icon_area = Area {
+                left: button_area.left + 4.0 + layout_info.area.height / 4.0,
+                top: button_area.top + 4.0,
+                width: button_area.height - 8.0,
+                height: button_area.height - 8.0,
+            };
+            let icon_color = match is_hovered {
+                true => *state.get(&self.hovered_hide_icon_color),
+                false => *state.get(&self.hide_icon_color),
+            };
+
+            layout.add_icon(icon_area, Icon::Eye { open: is_hidden }, icon_color);
+        }
+
+        if self.input_handler.text_editor().is_some() {
+            let persistent_data = self.get_persistent_data(&store, *state.get(&self.hidable));
+
+            if persistent_data.caret_offsets.borrow().composition.is_some() {
+                // SAFETY:
+                //
+                // The composed text is only written in `create_layout_info`, so it can't change
+                // while the layout is referencing it.
+                display_text = unsafe { &*persistent_data.composed_text.get() };
+            }
+        }
+
+        let show_ghost_text = display_text.is_empty() && !is_focused;
+
+        if show_ghost_text {
+            display_text = state.get(&self.ghost_text).as_ref();
+        }
+
+        let foreground_color = match is_hovered {
+            _ if show_ghost_text => *state.get(&self.ghost_foreground_color),
+            _ if is_focused => *state.get(&self.focused_foreground_color),
+            true => *state.get(&self.hovered_foreground_color),
+            false => *state.get(&self.foreground_color),
+        };
+
+        if let Some(editor) = self.input_handler.text_editor() {
+            let persistent_data = self.get_persistent_data(&store, *state.get(&self.hidable));
+            let caret_offsets = persistent_data.caret_offsets.borrow();
+            let area = layout_info.area;
+
+            let text_left = area.left
+                + match horizontal_alignment {
+                    HorizontalAlignment::Left { offset, .. } => offset,
+                    HorizontalAlignment::Center { offset, .. } => (area.width - caret_offsets.text_width()) / 2.0 + offset,
+                    HorizontalAlignment::Right { offset, .. } => area.width - caret_offsets.text_width() - offset,
+                };
+
+            let text_top = area.top
+                + match vertical_alignment {
+                    VerticalAlignment::Top { offset } => offset,
+                    VerticalAlignment::Center { offset } => (area.height - caret_offsets.text_height) / 2.0 + offset,
+                    VerticalAlignment::Bottom { offset } => area.height - caret_offsets.text_height - offset,
+                };
+
+            let is_selecting = matches!(
+                layout.get_mouse_mode(),
+                MouseMode::SelectingText { element_id: selecting_element_id } if *selecting_element_id == element_id
+            );
+
+            // Selecting is disabled while composing since the displayed text doesn't match
+            // the actual text.
+            if is_selecting && caret_offsets.composition.is_none() {
+                let caret_index = caret_offsets.closest_index(layout.get_mouse_position().left() - text_left);
+                // The first frame after clicking places the caret, every following frame
+                // extends the selection.
+                let extend_selection = !self.focus_click.start_selection.replace(false);
+
+                editor.borrow_mut().set_caret(state.get(&self.state), caret_index, extend_selection);
+            }
+
+            if is_focused {
+                let editor = editor.borrow();
+                let corner_diameter = App::CornerDiameter::new(0.0, 0.0, 0.0, 0.0);
+
+                let caret = match &caret_offsets.composition {
+                    Some((composition_range, caret)) => {
+                        let underline_left = text_left + caret_offsets.offset(composition_range.start);
+                        let underline_right = text_left + caret_offsets.offset(composition_range.end);
+
+                        layout.add_rectangle(
+                            Area {
+                                left: underline_left,
+                                top: text_top + caret_offsets.text_height - COMPOSITION_UNDERLINE_HEIGHT,
+                                width: underline_right - underline_left,
+                                height: COMPOSITION_UNDERLINE_HEIGHT,
+                            },
+                            corner_diameter,
+                            foreground_color,
+                            foreground_color,
+                            App::ShadowPadding::none(),
+                        );
+
+                        *caret
+                    }
+                    None => {
+                        if let Some(selection) = editor.selection() {
+                            let selection_color = *state.get(&self.selection_color);
+                            let selection_left = text_left + caret_offsets.offset(selection.start);
+                            let selection_right = text_left + caret_offsets.offset(selection.end);
+
+                            layout.add_rectangle(
+                                Area {
+                                    left: selection_left,
+                                    top: text_top,
+                                    width: selection_right - selection_left,
+                                    height: caret_offsets.text_height,
+                                },
+                                corner_diameter,
+                                selection_color,
+                                selection_color,
+                                App::ShadowPadding::none(),
+                            );
+                        }
+
+                        editor.caret()
+                    }
+                };
+
+                let caret_color = *state.get(&self.caret_color);
+                let caret_area = Area {
+                    left: text_left + caret_offsets.offset(caret) - CARET_WIDTH / 2.0,
+                    top: text_top,
+                    width: CARET_WIDTH,
+                    height: caret_offsets.text_height,
+                };
+
+                layout.add_rectangle(
+                    caret_area,
+                    corner_diameter,
+                    caret_color,
+                    caret_color,
+                    App::ShadowPadding::none(),
+                );
+
+                // Place the candidate window of the input method next to the caret.
+                layout.set_ime_cursor_area(caret_area);
+            } else if caret_offsets.composition.is_some() {
+                // Composing can't continue once the text box lost focus.
+                editor.borrow_mut().clear_composition();
+            }
+        }
+
+        layout.add_text(
+            layout_info.area,
+            display_text,
+            layout_info.font_size,
+            foreground_color,
+            *state.get(&self.highlight_color),
+            horizontal_alignment,
+            vertical_alignment,
+            *state.get(&self.overflow_behavior),
+        );
+
+        layout.register_focus_id(self.focus_id.focus_id(), element_id);
+        layout.register_focusable(element_id, layout_info.area, None);
+    }
+}
+
+pub struct DefaultHandler<P, A, const INPUT_LENGTH: usize> {
+    path: P,
+    action: A,
+    editor: RefCell<TextEditor>,
+}
+
+impl<P, A, const INPUT_LENGTH: usize> DefaultHandler<P, A, INPUT_LENGTH> {
+    pub fn new(path: P, action: A) -> Self {
+        Self {
+            path,
+            action,
+            editor: RefCell::default(),
+        }
+    }
+
+    fn current_text<App>(&self, editor: &TextEditor, state: &State<App>) -> String
+    where
+        App: Application,
+        P: Path<App, String>,
+    {
+        editor
+            .working_text()
+            .map(str::to_owned)
+            .unwrap_or_else(|| state.get(&self.path).clone())
+    }
+
+    /// Apply an edit to the current text and update the state if the edit
+    /// changed anything.
+    fn edit<App>(&self, state: &State<App>, edit: impl FnOnce(&mut TextEditor, &mut String) -> bool)
+    where
+        App: Application,
+        P: Path<App, String>,
+    {
+        let mut editor = self.editor.borrow_mut();
+        let mut text = self.current_text(&editor, state);
+
+        if edit(&mut editor, &mut text) {
+            editor.set_working_text(text.clone());
+            state.update_value(self.path, text);
+        }
+    }
+
+    fn move_caret<App>(&self, state: &State<App>, movement: CaretMovement, select: bool)
+    where
+        App: Application,
+        P: Path<App, String>,
+    {
+        let mut editor = self.editor.borrow_mut();
+        let text = self.current_text(&editor, state);
+
+        editor.move_caret(&text, movement, select);
+    }
+}
+
+impl<App, P, A, const INPUT_LENGTH: usize> InputHandler<App> for DefaultHandler<P, A, INPUT_LENGTH>
+where
+    App: Application,
+    P: Path<App, String>,
+    A: ClickHandler<App>,
+{
+    fn handle_character(&self, state: &State<App>, queue: &mut EventQueue<App>, character: char) {
+        if character == '\x09' || character == '\x0d' {
+            // On tab or enter
+            self.action.handle_click(state, queue);
+        } else if character == '\x1b' {
+            // On escape
+            queue.queue(Event::Unfocus);
+        } else if character == '\x08' {
+            self.edit(state, |editor, text| editor.delete_backward(text, false));
+        } else if !character.is_control() {
+            self.edit(state, |editor, text| {
+                editor.insert(text, character.encode_utf8(&mut [0; 4]), INPUT_LENGTH)
+            });
+        }
+    }
+
+    fn captures_navigation(&self, input: NavigationInput) -> bool {
+        // Up and down have no use in a single line of text, so they move the focus
+        // instead. Tab runs the action of the text box.
+        !matches!(
+            input,
+            NavigationInput::Move(FocusDirection::Up) | NavigationInput::Move(FocusDirection::Down)
+        )
+    }
+
+    fn handle_key(&self, state: &State<App>, _: &mut EventQueue<App>, clipboard: &mut App::Clipboard, key: KeyInput) {
+        match key.key {
+            EditKey::Left if key.control => self.move_caret(state, CaretMovement::WordLeft, key.shift),
+            EditKey::Left => self.move_caret(state, CaretMovement::Left, key.shift),
+            EditKey::Right if key.control => self.move_caret(state, CaretMovement::WordRight, key.shift),
+            EditKey::Right => self.move_caret(state, CaretMovement::Right, key.shift),
+            EditKey::Up | EditKey::Down => {}
+            EditKey::Home => self.move_caret(state, CaretMovement::Start, key.shift),
+            EditKey::End => self.move_caret(state, CaretMovement::End, key.shift),
+            EditKey::Delete => self.edit(state, |editor, text| editor.delete_forward(text, key.control)),
+            EditKey::SelectAll => {
+                let mut editor = self.editor.borrow_mut();
+                let text = self.current_text(&editor, state);
+
+                editor.select_all(&text);
+            }
+            EditKey::Copy => {
+                let editor = self.editor.borrow();
+                let text = self.current_text(&editor, state);
+                let selected_text = editor.selected_text(&text);
+
+                if !selected_text.is_empty() {
+                    clipboard.set_text(selected_text.to_owned());
+                }
+            }
+            EditKey::Cut => self.edit(state, |editor, text| match editor.cut(text) {
+                Some(cut_text) => {
+                    clipboard.set_text(cut_text);
+                    true
+                }
+                None => false,
+            }),
+            EditKey::Paste => {
+                if let Some(pasted_text) = clipboard.get_text() {
+                    // Line breaks and other control characters can't be displayed in a text box.
+                    let pasted_text: String = pasted_text.chars().filter(|character| !character.is_control()).collect();
+
+                    self.edit(state, |editor, text| editor.paste(text, &pasted_text, INPUT_LENGTH));
+                }
+            }
+            EditKey::Undo => self.edit(state, |editor, text| editor.undo(text)),
+            EditKey::Redo => self.edit(state, |editor, text| editor.redo(text)),
+        }
+    }
+
+    fn handle_composition(&self, state: &State<App>, _: &mut EventQueue<App>, event: &CompositionEvent) {
+        match event {
+            CompositionEvent::Preedit { text, cursor } => self.editor.borrow_mut().set_composition(text.clone(), cursor.clone()),
+            CompositionEvent::Commit { text: committed_text } => {
+                let committed_text: String = committed_text.chars().filter(|character| !character.is_control()).collect();
+
+                self.edit(state, |editor, text| {
+                    editor.commit_composition(text, &committed_text, INPUT_LENGTH)
+                });
+            }
+        }
+    }
+
+    fn text_editor(&self) -> Option<&RefCell<TextEditor>> {
+        Some(&self.editor)
+    }
+}
@@ -0,0 +1,12 @@
+use std::ops::Range;
+
+/// Input from an input method editor (IME), which is used to enter text that
+/// can't be typed directly, such as Korean, Japanese, or Chinese.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompositionEvent {
+    /// The text being composed changed. An empty text ends the composition
+    /// without committing anything.
+    Preedit { text: String, cursor: Option<Range<usize>> },
+    /// The composition is finished and the text should be inserted.
+    Commit { text: String },
+}
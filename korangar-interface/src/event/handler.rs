@@ -1,8 +1,11 @@
+use std::cell::RefCell;
+
 use rust_state::{Path, State};
 
-use super::EventQueue;
+use super::{CompositionEvent, EventQueue, KeyInput, NavigationInput};
 use crate::MouseMode;
 use crate::application::Application;
+use crate::components::text_box::TextEditor;
 
 /// Handler for mouse clicks.
 pub trait ClickHandler<App: Application> {
@@ -70,4 +73,23 @@ pub trait ScrollHandler<App: Application> {
 /// Handler for receiving keyboard input.
 pub trait InputHandler<App: Application> {
     fn handle_character(&self, state: &State<App>, queue: &mut EventQueue<App>, character: char);
+
+    /// Handle keys that don't produce a character, such as the arrow keys or
+    /// keyboard shortcuts.
+    fn handle_key(&self, _state: &State<App>, _queue: &mut EventQueue<App>, _clipboard: &mut App::Clipboard, _key: KeyInput) {}
+
+    /// Handle text composed with an input method.
+    fn handle_composition(&self, _state: &State<App>, _queue: &mut EventQueue<App>, _event: &CompositionEvent) {}
+
+    /// Editor holding the caret and selection of the text, if the handler
+    /// edits text.
+    fn text_editor(&self) -> Option<&RefCell<TextEditor>> {
+        None
+    }
+
+    /// Whether the handler uses the keys behind the navigation input itself,
+    /// in which case the focus stays on the element.
+    fn captures_navigation(&self, _input: NavigationInput) -> bool {
+        false
+    }
 }
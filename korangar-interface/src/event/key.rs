@@ -0,0 +1,25 @@
+/// Keys used for editing text and values that don't produce a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditKey {
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    Delete,
+    SelectAll,
+    Copy,
+    Cut,
+    Paste,
+    Undo,
+    Redo,
+}
+
+/// A key press together with the relevant modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyInput {
+    pub key: EditKey,
+    pub shift: bool,
+    pub control: bool,
+}
@@ -1,5 +1,11 @@
+mod composition;
 mod handler;
+mod key;
+mod navigation;
 mod queue;
 
+pub use self::composition::CompositionEvent;
 pub use self::handler::{ClickHandler, DropHandler, InputHandler, ScrollHandler, SetToFalse, SetToTrue, Toggle};
+pub use self::key::{EditKey, KeyInput};
+pub use self::navigation::{FocusDirection, NavigationInput};
 pub use self::queue::{Event, EventQueue};
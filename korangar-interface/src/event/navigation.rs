@@ -0,0 +1,31 @@
+/// Direction in which the focus is moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusDirection {
+    /// Next element in reading order.
+    Next,
+    /// Previous element in reading order.
+    Previous,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl FocusDirection {
+    pub fn is_sequential(self) -> bool {
+        matches!(self, Self::Next | Self::Previous)
+    }
+}
+
+/// Input for navigating the interface without a mouse. The application
+/// translates keyboard and gamepad input into these, so both drive the same
+/// navigation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationInput {
+    /// Move the focus to another element.
+    Move(FocusDirection),
+    /// Activate the focused element, as if it was clicked.
+    Activate,
+    /// Remove the focus from the focused element.
+    Cancel,
+}
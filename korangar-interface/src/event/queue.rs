@@ -5,6 +5,7 @@ use rust_state::State;
 use super::ClickHandler;
 use crate::MouseMode;
 use crate::application::Application;
+use crate::components::context_menu::ContextMenu;
 use crate::element::ElementBox;
 use crate::element::id::{ElementId, FocusId};
 
@@ -40,6 +41,13 @@ pub enum Event<App: Application> {
         window_id: u64,
     },
     CloseOverlay,
+    /// Open a context menu at the mouse position. This is converted to an
+    /// [`OpenOverlay`](Event::OpenOverlay) by the interface after the click
+    /// handlers ran, so it only has an effect when queued from a click
+    /// handler.
+    OpenContextMenu {
+        menu: ContextMenu<App>,
+    },
 }
 
 impl<App: Application> Clone for Event<App> {
@@ -58,6 +66,8 @@ impl<App: Application> Clone for Event<App> {
             Self::MoveWindowToTop { window_id } => Self::MoveWindowToTop { window_id: *window_id },
             Self::CloseWindow { window_id } => Self::CloseWindow { window_id: *window_id },
             Self::CloseOverlay => Self::CloseOverlay,
+            // TODO: Find a better solution for this. Ideally Event wouldn't need to be clone.
+            Self::OpenContextMenu { .. } => unimplemented!(),
         }
     }
 }
@@ -85,6 +95,10 @@ impl<App: Application> EventQueue<App> {
         self.events.push(event.into());
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &Event<App>> {
+        self.events.iter()
+    }
+
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Event<App>> {
         self.events.iter_mut()
     }
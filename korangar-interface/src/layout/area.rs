@@ -1,7 +1,7 @@
 use super::HoverCheck;
 use crate::prelude::{HorizontalAlignment, VerticalAlignment};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Area {
     pub left: f32,
     pub top: f32,
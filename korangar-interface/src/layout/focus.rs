@@ -0,0 +1,136 @@
+//! Focus graph used for navigating the interface with a keyboard or gamepad.
+
+use std::cmp::Ordering;
+
+use super::ClipId;
+use super::area::Area;
+use crate::application::Application;
+use crate::element::id::ElementId;
+use crate::event::{ClickHandler, FocusDirection};
+
+/// How much more the distance perpendicular to the direction of movement
+/// weighs when picking the closest element. This favors elements that are in
+/// line with the focused element over elements that are closer but offset.
+const PERPENDICULAR_WEIGHT: f32 = 2.0;
+
+/// Element that can be focused by navigating the interface.
+pub(crate) struct FocusNode<'a, App: Application> {
+    pub element_id: ElementId,
+    pub area: Area,
+    pub clip_id: ClipId,
+    /// Whether any part of the element is inside its clip.
+    pub is_visible: bool,
+    pub activate: Option<&'a dyn ClickHandler<App>>,
+}
+
+fn center(area: &Area) -> (f32, f32) {
+    (area.left + area.width / 2.0, area.top + area.height / 2.0)
+}
+
+/// Distance between two ranges on the same axis, zero if they overlap.
+fn gap(start: f32, size: f32, other_start: f32, other_size: f32) -> f32 {
+    (other_start - (start + size)).max(start - (other_start + other_size)).max(0.0)
+}
+
+fn reading_order(left: &Area, right: &Area) -> Ordering {
+    left.top.total_cmp(&right.top).then(left.left.total_cmp(&right.left))
+}
+
+/// Find the index of the area that the focus should move to from `current`.
+/// Without a current area, the first area in the direction of movement is
+/// picked.
+pub(crate) fn find_target(areas: &[Area], current: Option<usize>, direction: FocusDirection) -> Option<usize> {
+    if areas.is_empty() {
+        return None;
+    }
+
+    let mut order: Vec<usize> = (0..areas.len()).collect();
+    order.sort_by(|&left, &right| reading_order(&areas[left], &areas[right]));
+
+    let Some(current) = current else {
+        return match direction {
+            FocusDirection::Previous | FocusDirection::Up | FocusDirection::Left => order.last().copied(),
+            FocusDirection::Next | FocusDirection::Down | FocusDirection::Right => order.first().copied(),
+        };
+    };
+
+    let position = order.iter().position(|&index| index == current)?;
+
+    match direction {
+        FocusDirection::Next => Some(order[(position + 1) % order.len()]),
+        FocusDirection::Previous => Some(order[(position + order.len() - 1) % order.len()]),
+        FocusDirection::Up | FocusDirection::Down | FocusDirection::Left | FocusDirection::Right => {
+            let area = &areas[current];
+            let (center_x, center_y) = center(area);
+
+            areas
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != current)
+                .filter_map(|(index, other)| {
+                    let (other_x, other_y) = center(other);
+
+                    let (distance, perpendicular_gap) = match direction {
+                        FocusDirection::Up => (center_y - other_y, gap(area.left, area.width, other.left, other.width)),
+                        FocusDirection::Down => (other_y - center_y, gap(area.left, area.width, other.left, other.width)),
+                        FocusDirection::Left => (center_x - other_x, gap(area.top, area.height, other.top, other.height)),
+                        _ => (other_x - center_x, gap(area.top, area.height, other.top, other.height)),
+                    };
+
+                    (distance > 0.5).then_some((index, distance + perpendicular_gap * PERPENDICULAR_WEIGHT))
+                })
+                .min_by(|(_, left), (_, right)| left.total_cmp(right))
+                .map(|(index, _)| index)
+        }
+    }
+}
+
+#[cfg(test)]
+mod navigation {
+    use super::find_target;
+    use crate::event::FocusDirection;
+    use crate::layout::area::Area;
+
+    fn area(left: f32, top: f32) -> Area {
+        Area {
+            left,
+            top,
+            width: 100.0,
+            height: 20.0,
+        }
+    }
+
+    #[test]
+    fn sequential_wraps_around() {
+        // Registered out of reading order on purpose.
+        let areas = [area(0.0, 50.0), area(0.0, 0.0), area(0.0, 25.0)];
+
+        assert_eq!(find_target(&areas, None, FocusDirection::Next), Some(1));
+        assert_eq!(find_target(&areas, Some(1), FocusDirection::Next), Some(2));
+        assert_eq!(find_target(&areas, Some(0), FocusDirection::Next), Some(1));
+        assert_eq!(find_target(&areas, Some(1), FocusDirection::Previous), Some(0));
+        assert_eq!(find_target(&areas, None, FocusDirection::Previous), Some(0));
+    }
+
+    #[test]
+    fn directional_prefers_aligned_elements() {
+        // Two rows of two elements each.
+        let areas = [area(0.0, 0.0), area(110.0, 0.0), area(0.0, 25.0), area(110.0, 25.0)];
+
+        assert_eq!(find_target(&areas, Some(0), FocusDirection::Right), Some(1));
+        assert_eq!(find_target(&areas, Some(0), FocusDirection::Down), Some(2));
+        assert_eq!(find_target(&areas, Some(2), FocusDirection::Right), Some(3));
+        assert_eq!(find_target(&areas, Some(3), FocusDirection::Up), Some(1));
+        assert_eq!(find_target(&areas, Some(1), FocusDirection::Left), Some(0));
+    }
+
+    #[test]
+    fn directional_stops_at_edge() {
+        let areas = [area(0.0, 0.0), area(0.0, 25.0)];
+
+        assert_eq!(find_target(&areas, Some(0), FocusDirection::Up), None);
+        assert_eq!(find_target(&areas, Some(1), FocusDirection::Down), None);
+        assert_eq!(find_target(&areas, Some(0), FocusDirection::Left), None);
+        assert_eq!(find_target(&[], None, FocusDirection::Next), None);
+    }
+}
@@ -1,3 +1,4 @@
+mod focus;
 mod resolver;
 
 pub mod alignment;
@@ -12,11 +13,14 @@ use area::Area;
 use rust_state::State;
 use tooltip::{Tooltip, TooltipId};
 
+use self::focus::{FocusNode, find_target};
 pub use self::resolver::{Resolver, Resolvers, with_nth_resolver, with_single_resolver};
 use crate::MouseMode;
-use crate::application::{Application, Clip, CornerDiameter, FontSize, Position, RenderLayer, ShadowPadding, Size, TextLayouter};
+use crate::application::{Application, Clip, Color, CornerDiameter, FontSize, Position, RenderLayer, ShadowPadding, Size, TextLayouter};
 use crate::element::id::{ElementId, FocusId};
-use crate::event::{ClickHandler, DropHandler, EventQueue, InputHandler, ScrollHandler};
+use crate::event::{
+    ClickHandler, CompositionEvent, DropHandler, EventQueue, FocusDirection, InputHandler, KeyInput, NavigationInput, ScrollHandler,
+};
 
 // Rename this to ButtonPress or something.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -222,11 +226,19 @@ pub struct WindowLayout<'a, App: Application> {
     tooltip_timers: BTreeMap<TooltipId, Instant>,
 
     focus_id_lookup: BTreeMap<FocusId, ElementId>,
+    focus_nodes: Vec<FocusNode<'a, App>>,
 
     window_position: App::Position,
     interface_scaling: f32,
 
+    /// Opacity that all colors are multiplied with when rendering.
+    opacity: f32,
+    /// Offset in pixels that everything is moved down by when rendering.
+    vertical_offset: f32,
+
     mouse_mode: Option<&'a MouseMode<App>>,
+
+    ime_cursor_area: Option<Area>,
 }
 
 impl<App: Application> Default for WindowLayout<'_, App> {
@@ -249,11 +261,17 @@ impl<App: Application> Default for WindowLayout<'_, App> {
             tooltip_timers: BTreeMap::new(),
 
             focus_id_lookup: BTreeMap::new(),
+            focus_nodes: Vec::new(),
 
             window_position: App::Position::new(0.0, 0.0),
             interface_scaling: 1.0,
 
+            opacity: 1.0,
+            vertical_offset: 0.0,
+
             mouse_mode: None,
+
+            ime_cursor_area: None,
         }
     }
 }
@@ -274,6 +292,7 @@ impl<'a, App: Application> WindowLayout<'a, App> {
 
         self.tooltips.clear();
         self.focus_id_lookup.clear();
+        self.focus_nodes.clear();
         self.mouse_mode = None;
     }
 
@@ -296,6 +315,14 @@ impl<'a, App: Application> WindowLayout<'a, App> {
         self.is_hovered = false;
         self.can_be_hovered = can_be_hovered;
         self.mouse_mode = Some(mouse_mode);
+        self.ime_cursor_area = None;
+    }
+
+    /// Set the opacity and vertical offset of the window. This is used to
+    /// animate windows and only affects rendering, not the hover checks.
+    pub fn set_transition(&mut self, opacity: f32, vertical_offset: f32) {
+        self.opacity = opacity;
+        self.vertical_offset = vertical_offset;
     }
 
     pub fn get_interface_scaling(&self) -> f32 {
@@ -476,6 +503,16 @@ impl<'a, App: Application> WindowLayout<'a, App> {
         self.tooltip_timers.entry(id).or_insert_with(Instant::now);
     }
 
+    /// Set the area of the text cursor, which is used to position the
+    /// candidate window of the input method.
+    pub fn set_ime_cursor_area(&mut self, area: Area) {
+        self.ime_cursor_area = Some(self.scale_area(area));
+    }
+
+    pub fn get_ime_cursor_area(&self) -> Option<Area> {
+        self.ime_cursor_area
+    }
+
     pub fn register_focus_id(&mut self, focus_id: FocusId, element_id: ElementId) {
         self.focus_id_lookup.insert(focus_id, element_id);
     }
@@ -484,15 +521,102 @@ impl<'a, App: Application> WindowLayout<'a, App> {
         self.focus_id_lookup.get(&focus_id).copied()
     }
 
+    /// Register an element that can be focused by navigating the interface
+    /// with a keyboard or gamepad. The `activate` handler is called when the
+    /// element is activated while focused.
+    pub fn register_focusable(&mut self, element_id: ElementId, area: Area, activate: Option<&'a dyn ClickHandler<App>>) {
+        let clip_id = self.get_active_clip_id();
+        let clip = self.clips[clip_id.0];
+
+        let is_visible = area.left < clip.right()
+            && area.left + area.width > clip.left()
+            && area.top < clip.bottom()
+            && area.top + area.height > clip.top();
+
+        self.focus_nodes.push(FocusNode {
+            element_id,
+            area,
+            clip_id,
+            is_visible,
+            activate,
+        });
+    }
+
+    pub fn has_focusable(&self, element_id: ElementId) -> bool {
+        self.focus_nodes.iter().any(|node| node.element_id == element_id)
+    }
+
+    pub fn has_visible_focusables(&self) -> bool {
+        self.focus_nodes.iter().any(|node| node.is_visible)
+    }
+
+    /// Find the element that the focus should move to. Elements that are
+    /// scrolled out of view are skipped.
+    pub fn find_focus_target(&self, focused_element: Option<ElementId>, direction: FocusDirection) -> Option<ElementId> {
+        let candidates: Vec<&FocusNode<'a, App>> = self
+            .focus_nodes
+            .iter()
+            .filter(|node| node.is_visible || Some(node.element_id) == focused_element)
+            .collect();
+
+        let areas: Vec<Area> = candidates.iter().map(|node| node.area).collect();
+        let current = focused_element.and_then(|element_id| candidates.iter().position(|node| node.element_id == element_id));
+
+        find_target(&areas, current, direction).map(|index| candidates[index].element_id)
+    }
+
+    /// Activate the focused element. Returns `true` if the element has an
+    /// activation handler.
+    pub fn activate_focusable(&self, state: &State<App>, queue: &mut EventQueue<App>, element_id: ElementId) -> bool {
+        let activate = self
+            .focus_nodes
+            .iter()
+            .find(|node| node.element_id == element_id)
+            .and_then(|node| node.activate);
+
+        match activate {
+            Some(handler) => {
+                handler.handle_click(state, queue);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if the focused element uses the input itself. Only the focused
+    /// element registers an input handler, so any registered handler counts.
+    pub fn captures_navigation(&self, input: NavigationInput) -> bool {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.input_handlers.iter())
+            .any(|input_handler| input_handler.captures_navigation(input))
+    }
+
+    /// Get the screen area and clip of the focused element. Since the clips
+    /// are scaled while rendering, this is only valid after
+    /// [`render`](Self::render) was called.
+    pub fn get_focused_area(&self) -> Option<(Area, App::Clip)> {
+        let element_id = self.focused_element?;
+        let node = self.focus_nodes.iter().find(|node| node.element_id == element_id)?;
+
+        node.is_visible.then(|| {
+            let mut area = self.scale_area(node.area);
+            area.top += self.vertical_offset;
+
+            (area, self.clips[node.clip_id.0])
+        })
+    }
+
     /// Update tooltips and collect those that have been registered for some
-    /// time. Those are the tooltips that will be rendered to the screen.
-    pub fn update_tooltips(&mut self, tooltips: &mut Vec<&'a str>) {
+    /// time. Those are the tooltips that will be rendered to the screen,
+    /// together with the time they have been visible for.
+    pub fn update_tooltips(&mut self, tooltips: &mut Vec<(&'a str, Duration)>) {
         self.tooltip_timers.retain(|id, timer| {
             let mut found = false;
 
             self.tooltips.iter().filter(|tooltip| tooltip.id == *id).for_each(|tooltip| {
-                if timer.elapsed() > Duration::from_secs(1) {
-                    tooltips.push(tooltip.text);
+                if let Some(visible_for) = timer.elapsed().checked_sub(Duration::from_secs(1)) {
+                    tooltips.push((tooltip.text, visible_for));
                 }
 
                 found = true;
@@ -510,12 +634,19 @@ impl<'a, App: Application> WindowLayout<'a, App> {
         for clip in &mut self.clips {
             *clip = App::Clip::new(
                 (clip.left() - self.window_position.left()) * self.interface_scaling + self.window_position.left(),
-                (clip.top() - self.window_position.top()) * self.interface_scaling + self.window_position.top(),
+                (clip.top() - self.window_position.top()) * self.interface_scaling + self.window_position.top() + self.vertical_offset,
                 (clip.right() - self.window_position.left()) * self.interface_scaling + self.window_position.left(),
-                (clip.bottom() - self.window_position.top()) * self.interface_scaling + self.window_position.top(),
+                (clip.bottom() - self.window_position.top()) * self.interface_scaling + self.window_position.top() + self.vertical_offset,
             );
         }
 
+        let opacity = self.opacity;
+        let vertical_offset = self.vertical_offset;
+        let fade = |color: App::Color| match opacity < 1.0 {
+            true => color.multiply_alpha(opacity),
+            false => color,
+        };
+
         for layer in self.layers.iter_mut() {
             #[cfg(feature = "debug")]
             korangar_debug::profile_block!("render layer");
@@ -535,12 +666,12 @@ impl<'a, App: Application> WindowLayout<'a, App> {
                     let clip = self.clips[clip_id.0];
 
                     renderer.render_rectangle(
-                        App::Position::new(area.left, area.top),
+                        App::Position::new(area.left, area.top + vertical_offset),
                         App::Size::new(area.width, area.height),
                         clip,
                         corner_diameter,
-                        color,
-                        shadow_color,
+                        fade(color),
+                        fade(shadow_color),
                         shadow_padding,
                     );
                 },
@@ -559,11 +690,11 @@ impl<'a, App: Application> WindowLayout<'a, App> {
                     let clip = self.clips[clip_id.0];
 
                     renderer.render_icon(
-                        App::Position::new(area.left, area.top),
+                        App::Position::new(area.left, area.top + vertical_offset),
                         App::Size::new(area.width, area.height),
                         clip,
                         icon,
-                        color,
+                        fade(color),
                     );
                 },
             );
@@ -627,11 +758,11 @@ impl<'a, App: Application> WindowLayout<'a, App> {
 
                     renderer.render_text(
                         text,
-                        App::Position::new(area.left + left_offset, area.top + top_offset),
+                        App::Position::new(area.left + left_offset, area.top + top_offset + vertical_offset),
                         available_width,
                         clip,
-                        color,
-                        highlight_color,
+                        fade(color),
+                        fade(highlight_color),
                         font_size,
                     );
                 },
@@ -691,4 +822,30 @@ impl<'a, App: Application> WindowLayout<'a, App> {
 
         input_handled
     }
+
+    pub fn handle_key(&self, state: &State<App>, queue: &mut EventQueue<App>, clipboard: &mut App::Clipboard, key: KeyInput) -> bool {
+        let mut input_handled = false;
+
+        for layer in &self.layers {
+            for input_handler in &layer.input_handlers {
+                input_handler.handle_key(state, queue, clipboard, key);
+                input_handled = true;
+            }
+        }
+
+        input_handled
+    }
+
+    pub fn handle_composition(&self, state: &State<App>, queue: &mut EventQueue<App>, event: &CompositionEvent) -> bool {
+        let mut input_handled = false;
+
+        for layer in &self.layers {
+            for input_handler in &layer.input_handlers {
+                input_handler.handle_composition(state, queue, event);
+                input_handled = true;
+            }
+        }
+
+        input_handled
+    }
 }
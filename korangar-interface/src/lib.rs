@@ -14,7 +14,9 @@ pub mod components;
 pub mod element;
 pub mod event;
 pub mod layout;
+pub mod markup;
 pub mod theme;
+pub mod tween;
 pub mod window;
 
 // Re-export self as korangar_interface so we can use proc macros in this crate.
@@ -23,19 +25,23 @@ extern crate self as korangar_interface;
 use std::any::Any;
 use std::collections::BTreeMap;
 
-use application::{Application, Clip, CornerDiameter, FontSize, Position, RenderLayer, Size, TextLayouter, WindowCache};
-use element::ElementBox;
+use application::{Application, Clip, Clipboard, Color, CornerDiameter, FontSize, Position, RenderLayer, Size, TextLayouter, WindowCache};
+use components::context_menu::{ContextMenu, ContextMenuElement};
 use element::id::{ElementId, ElementIdGenerator};
 use element::store::{ElementStore, ElementStoreMut, InternalElementStore};
-use event::{Event, EventQueue};
+use element::{ElementBox, ErasedElement};
+use event::{CompositionEvent, Event, EventQueue, KeyInput, NavigationInput};
 use layout::area::Area;
 use layout::tooltip::TooltipTheme;
 use layout::{MouseButton, ResizeMode, Resolver, WindowLayout};
 use option_ext::OptionExt;
 use rust_state::State;
 use theme::ThemePathGetter;
+use tween::{AnimationThemePathExt, Tween};
 use window::store::WindowStore;
-use window::{Anchor, CustomWindow, DisplayInformation, StateWindow, Window, WindowData, WindowThemePathExt};
+use window::{
+    Anchor, CustomWindow, DisplayInformation, StateWindow, TabClickHandler, Window, WindowData, WindowTab, WindowThemePathExt, snap_area,
+};
 
 use crate::application::ShadowPadding;
 use crate::element::id::FocusIdExt;
@@ -50,10 +56,15 @@ pub mod prelude {
 
     pub use crate::components::button::ButtonThemePathExt;
     pub use crate::components::collapsible::CollapsibleThemePathExt;
+    pub use crate::components::context_menu::{ContextMenu, ContextMenuThemePathExt};
     pub use crate::components::drop_down::DropDownThemePathExt;
     pub use crate::components::either::Either;
     pub use crate::components::field::FieldThemePathExt;
+    pub use crate::components::number_input::NumberInputThemePathExt;
+    pub use crate::components::progress_bar::ProgressBarThemePathExt;
+    pub use crate::components::slider::SliderThemePathExt;
     pub use crate::components::state_button::StateButtonThemePathExt;
+    pub use crate::components::table::{TableColumn, TableRow, TableThemePathExt};
     pub use crate::components::tabs::Tabs;
     pub use crate::components::text::TextThemePathExt;
     pub use crate::components::text_box::TextBoxThemePathExt;
@@ -63,6 +74,7 @@ pub mod prelude {
     pub use crate::layout::tooltip::TooltipThemePathExt;
     pub use crate::selector_helpers::*;
     pub use crate::theme::{ThemePathGetter, theme};
+    pub use crate::tween::AnimationThemePathExt;
     pub use crate::window::WindowThemePathExt;
 }
 
@@ -190,7 +202,10 @@ where
 {
     Default,
     MovingWindow { window_id: u64 },
+    DraggingTab { window_id: u64 },
     ResizingWindow { resize_mode: ResizeMode, window_id: u64 },
+    SelectingText { element_id: ElementId },
+    DraggingElement { element_id: ElementId },
     Custom { mode: App::CustomMouseMode },
 }
 
@@ -212,10 +227,13 @@ where
         match self {
             Self::Default => Self::Default,
             Self::MovingWindow { window_id } => Self::MovingWindow { window_id: *window_id },
+            Self::DraggingTab { window_id } => Self::DraggingTab { window_id: *window_id },
             Self::ResizingWindow { resize_mode, window_id } => Self::ResizingWindow {
                 resize_mode: *resize_mode,
                 window_id: *window_id,
             },
+            Self::SelectingText { element_id } => Self::SelectingText { element_id: *element_id },
+            Self::DraggingElement { element_id } => Self::DraggingElement { element_id: *element_id },
             Self::Custom { mode } => Self::Custom { mode: mode.clone() },
         }
     }
@@ -228,8 +246,22 @@ where
     window: Box<dyn Window<App>>,
     data: WindowData<App>,
     display_information: DisplayInformation,
+    /// Tab group that the window is docked into. Windows in the same tab
+    /// group share their position and size, and only the top most one is
+    /// visible. A group with a single window is treated like no group.
+    tab_group: Option<u64>,
+    /// Transition of the window after opening it. Goes from `0.0` to `1.0`.
+    transition: Tween<f32>,
 }
 
+fn is_same_tab_group(tab_group: Option<u64>, other_tab_group: Option<u64>) -> bool {
+    tab_group.is_some() && tab_group == other_tab_group
+}
+
+/// Window id of overlays that are opened at a position on the screen instead of
+/// inside of a window, like the context menus of entities in the world.
+const SCREEN_OVERLAY_WINDOW_ID: u64 = u64::MAX;
+
 struct OverlayElement<App>
 where
     App: Application,
@@ -247,12 +279,19 @@ where
 {
     windows: Vec<WindowWrapper<App>>,
     window_cache: App::Cache,
+    clipboard: App::Clipboard,
     window_size: App::Size,
 
     generator: ElementIdGenerator,
     window_store: WindowStore,
     focused_element: Option<ElementId>,
+    /// Set when the focus was moved by navigating the interface, in which
+    /// case the focused element is highlighted.
+    focus_visible: bool,
     mouse_mode: MouseMode<App>,
+    /// Position of the window that is being moved before snapping it. This
+    /// allows pulling a window away from an edge it snapped to.
+    drag_position: Option<App::Position>,
     event_queue: EventQueue<App>,
     overlay_element: Option<OverlayElement<App>>,
 
@@ -266,6 +305,10 @@ where
     overlay_layout: Option<WindowLayout<'a, App>>,
 
     next_window_id: u64,
+    next_tab_group_id: u64,
+
+    /// Disables all animations if set.
+    reduce_motion: bool,
 
     text_layouter: App::TextLayouter,
 }
@@ -276,16 +319,20 @@ where
 {
     pub fn new(text_layouter: App::TextLayouter, available_space: App::Size) -> Self {
         let window_cache = App::Cache::create();
+        let clipboard = App::Clipboard::create();
 
         Self {
             windows: Vec::new(),
             window_cache,
+            clipboard,
             window_size: available_space,
 
             generator: ElementIdGenerator::new(),
             window_store: WindowStore::default(),
             focused_element: None,
+            focus_visible: false,
             mouse_mode: MouseMode::Default,
+            drag_position: None,
             event_queue: EventQueue::default(),
             overlay_element: None,
 
@@ -293,6 +340,8 @@ where
             overlay_layout: None,
 
             next_window_id: 0,
+            next_tab_group_id: 0,
+            reduce_motion: false,
             text_layouter,
         }
     }
@@ -301,28 +350,73 @@ where
         self.window_size = screen_size;
     }
 
+    /// Disable all animations. Values that would otherwise be animated change
+    /// immediately.
+    pub fn set_reduce_motion(&mut self, reduce_motion: bool) {
+        self.reduce_motion = reduce_motion;
+    }
+
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
     pub fn handle_drag(&mut self, delta: App::Size, interface_scaling: f32) {
+        // Pulling a tab out of its tab group undocks the window, after that it is
+        // moved like any other window.
+        if let MouseMode::DraggingTab { window_id } = self.mouse_mode {
+            self.undock_window(window_id);
+            self.mouse_mode = MouseMode::MovingWindow { window_id };
+        }
+
         match self.mouse_mode {
             MouseMode::Default => {}
             MouseMode::MovingWindow { window_id } => {
+                let tab_group = self.get_tab_group(window_id);
+
+                let other_areas: Vec<Area> = self
+                    .windows
+                    .iter()
+                    .filter(|wrapper| wrapper.data.id != window_id && !is_same_tab_group(wrapper.tab_group, tab_group))
+                    .map(|wrapper| Area {
+                        left: wrapper.display_information.real_area.left,
+                        top: wrapper.display_information.real_area.top,
+                        width: wrapper.display_information.real_area.width * interface_scaling,
+                        height: wrapper.display_information.display_height * interface_scaling,
+                    })
+                    .collect();
+
                 if let Some(wrapper) = self.windows.iter_mut().find(|window| window.data.id == window_id) {
-                    let new_position = App::Position::new(
-                        wrapper.display_information.real_area.left + delta.width(),
-                        wrapper.display_information.real_area.top + delta.height(),
-                    );
+                    let drag_position = self.drag_position.get_or_insert(App::Position::new(
+                        wrapper.display_information.real_area.left,
+                        wrapper.display_information.real_area.top,
+                    ));
+                    *drag_position = App::Position::new(drag_position.left() + delta.width(), drag_position.top() + delta.height());
 
                     let scaled_size = App::Size::new(
                         wrapper.display_information.real_area.width * interface_scaling,
                         wrapper.display_information.display_height * interface_scaling,
                     );
 
+                    let dragged_area = Area {
+                        left: drag_position.left(),
+                        top: drag_position.top(),
+                        width: scaled_size.width(),
+                        height: scaled_size.height(),
+                    };
+                    let (left, top) = snap_area(
+                        dragged_area,
+                        self.window_size.width(),
+                        self.window_size.height(),
+                        &other_areas,
+                        wrapper.display_information.snap_distance * interface_scaling,
+                    );
+                    let new_position = App::Position::new(left, top);
+
                     wrapper.data.anchor.update(self.window_size, new_position, scaled_size);
 
                     if let Some(window_class) = wrapper.window.get_class() {
                         self.window_cache.update_anchor(window_class, wrapper.data.anchor);
                     }
                 }
+
+                self.synchronize_tab_group(window_id);
             }
             MouseMode::ResizingWindow { window_id, resize_mode } => {
                 if let Some(wrapper) = self.windows.iter_mut().find(|window| window.data.id == window_id) {
@@ -341,7 +435,12 @@ where
                         self.window_cache.update_size(window_class, wrapper.data.size);
                     }
                 }
+
+                self.synchronize_tab_group(window_id);
             }
+            MouseMode::DraggingTab { .. } => {}
+            MouseMode::SelectingText { .. } => {}
+            MouseMode::DraggingElement { .. } => {}
             MouseMode::Custom { .. } => {}
         }
     }
@@ -352,30 +451,302 @@ where
             .any(|wrapper| wrapper.window.get_class().is_some_and(|class| class == window_class))
     }
 
-    fn open_new_window(&mut self, window: impl Window<App> + 'static) {
-        let id = self.next_window_id;
-        // TODO: Actual logic to wrap around and adjust all window ids.
-        self.next_window_id = self.next_window_id.wrapping_add(1);
-
-        let window_class = window.get_class();
-
-        let (anchor, size) = match window_class.and_then(|window_class| self.window_cache.get_window_state(window_class)) {
+    /// Get the anchor and size of a window from the window cache. Windows
+    /// that are not in the cache yet are registered with a default state.
+    fn initial_window_state(window_cache: &mut App::Cache, window_class: Option<App::WindowClass>) -> (Anchor<App>, App::Size) {
+        match window_class.and_then(|window_class| window_cache.get_window_state(window_class)) {
             Some(saved_state) => saved_state,
             None => {
                 let anchor = Anchor::default();
                 let size = App::Size::new(0.0, f32::MAX);
 
                 if let Some(window_class) = window_class {
-                    self.window_cache.register_window(window_class, anchor, size);
+                    window_cache.register_window(window_class, anchor, size);
                 }
 
                 (anchor, size)
             }
+        }
+    }
+
+    /// Get mutable access to the window cache, for example to switch between
+    /// different window layouts. Call
+    /// [`reload_window_states`](Self::reload_window_states) afterwards to
+    /// apply the changes to the open windows.
+    pub fn get_window_cache_mut(&mut self) -> &mut App::Cache {
+        &mut self.window_cache
+    }
+
+    /// Reload the anchor, size and tab group of all open windows from the
+    /// window cache.
+    pub fn reload_window_states(&mut self) {
+        for wrapper in &mut self.windows {
+            let window_class = wrapper.window.get_class();
+
+            if window_class.is_some() {
+                let (anchor, size) = Self::initial_window_state(&mut self.window_cache, window_class);
+
+                wrapper.data.anchor = anchor;
+                wrapper.data.size = size;
+                wrapper.tab_group = None;
+            }
+        }
+
+        let window_ids: Vec<u64> = self.windows.iter().map(|wrapper| wrapper.data.id).collect();
+
+        for window_id in window_ids {
+            self.restore_tab_group(window_id);
+        }
+    }
+
+    fn get_tab_group(&self, window_id: u64) -> Option<u64> {
+        self.windows
+            .iter()
+            .find(|wrapper| wrapper.data.id == window_id)
+            .and_then(|wrapper| wrapper.tab_group)
+    }
+
+    /// Add a window to the tab group of the target window, creating a new tab
+    /// group if the target is not docked yet. The window takes over the
+    /// position and size of the tab group.
+    fn join_tab_group(&mut self, window_id: u64, target_window_id: u64) -> bool {
+        let Some(target) = self.windows.iter_mut().find(|wrapper| wrapper.data.id == target_window_id) else {
+            return false;
+        };
+
+        let tab_group = *target.tab_group.get_or_insert_with(|| {
+            let tab_group = self.next_tab_group_id;
+            self.next_tab_group_id = self.next_tab_group_id.wrapping_add(1);
+            tab_group
+        });
+        let anchor = target.data.anchor;
+        let size = target.data.size;
+
+        let Some(wrapper) = self.windows.iter_mut().find(|wrapper| wrapper.data.id == window_id) else {
+            return false;
+        };
+
+        wrapper.tab_group = Some(tab_group);
+        wrapper.data.anchor = anchor;
+        wrapper.data.size = size;
+
+        true
+    }
+
+    /// Dock a window into the tab group of the target window and save it in
+    /// the window cache.
+    fn dock_window(&mut self, window_id: u64, target_window_id: u64) {
+        let target_was_docked = self.get_tab_group(target_window_id).is_some();
+
+        if !self.join_tab_group(window_id, target_window_id) {
+            return;
+        }
+
+        let class_of = |window_id: u64| {
+            self.windows
+                .iter()
+                .find(|wrapper| wrapper.data.id == window_id)
+                .and_then(|wrapper| wrapper.window.get_class())
         };
+        let window_class = class_of(window_id);
+        let target_class = class_of(target_window_id);
+
+        if let Some(window_class) = window_class {
+            let (anchor, size) = self
+                .windows
+                .iter()
+                .find(|wrapper| wrapper.data.id == window_id)
+                .map(|wrapper| (wrapper.data.anchor, wrapper.data.size))
+                .unwrap();
+
+            self.window_cache.update_anchor(window_class, anchor);
+            self.window_cache.update_size(window_class, size);
+            self.window_cache.update_tab_group(window_class, target_class);
+        }
+
+        if !target_was_docked && let Some(target_class) = target_class {
+            self.window_cache.update_tab_group(target_class, window_class);
+        }
+    }
+
+    /// Remove a window from its tab group and save it in the window cache.
+    fn undock_window(&mut self, window_id: u64) {
+        let Some(wrapper) = self.windows.iter_mut().find(|wrapper| wrapper.data.id == window_id) else {
+            return;
+        };
+
+        let Some(tab_group) = wrapper.tab_group.take() else {
+            return;
+        };
+
+        let Some(window_class) = wrapper.window.get_class() else {
+            return;
+        };
+
+        self.window_cache.update_tab_group(window_class, None);
+
+        // Windows that were docked with this window are docked with another window
+        // of the group instead, so the group can be restored when opening the
+        // windows again.
+        let remaining_classes: Vec<App::WindowClass> = self
+            .windows
+            .iter()
+            .filter(|wrapper| wrapper.tab_group == Some(tab_group))
+            .filter_map(|wrapper| wrapper.window.get_class())
+            .collect();
+
+        for class in &remaining_classes {
+            if self.window_cache.get_tab_group(*class) == Some(window_class) {
+                let other_class = remaining_classes.iter().find(|other_class| *other_class != class).copied();
+                self.window_cache.update_tab_group(*class, other_class);
+            }
+        }
+    }
+
+    /// Put a newly opened window back into the tab group it was docked into
+    /// when it was last open.
+    fn restore_tab_group(&mut self, window_id: u64) {
+        let Some(window_class) = self
+            .windows
+            .iter()
+            .find(|wrapper| wrapper.data.id == window_id)
+            .and_then(|wrapper| wrapper.window.get_class())
+        else {
+            return;
+        };
+
+        let find_open_window = |class: App::WindowClass| {
+            self.windows
+                .iter()
+                .find(|wrapper| wrapper.data.id != window_id && wrapper.window.get_class() == Some(class))
+                .map(|wrapper| wrapper.data.id)
+        };
+
+        if let Some(target_window_id) = self.window_cache.get_tab_group(window_class).and_then(find_open_window) {
+            self.join_tab_group(window_id, target_window_id);
+            return;
+        }
+
+        // The window this window was docked with is not open, but other windows might
+        // have been docked with this window.
+        let docked_window_ids: Vec<u64> = self
+            .windows
+            .iter()
+            .filter(|wrapper| wrapper.data.id != window_id && wrapper.tab_group.is_none())
+            .filter(|wrapper| {
+                wrapper
+                    .window
+                    .get_class()
+                    .is_some_and(|class| self.window_cache.get_tab_group(class) == Some(window_class))
+            })
+            .map(|wrapper| wrapper.data.id)
+            .collect();
+
+        for docked_window_id in docked_window_ids {
+            self.join_tab_group(docked_window_id, window_id);
+        }
+    }
+
+    /// Apply the anchor and size of a window to all other windows in its tab
+    /// group.
+    fn synchronize_tab_group(&mut self, window_id: u64) {
+        let Some((tab_group, anchor, size)) = self
+            .windows
+            .iter()
+            .find(|wrapper| wrapper.data.id == window_id)
+            .and_then(|wrapper| Some((wrapper.tab_group?, wrapper.data.anchor, wrapper.data.size)))
+        else {
+            return;
+        };
+
+        for wrapper in &mut self.windows {
+            if wrapper.data.id != window_id && wrapper.tab_group == Some(tab_group) {
+                wrapper.data.anchor = anchor;
+                wrapper.data.size = size;
+
+                if let Some(window_class) = wrapper.window.get_class() {
+                    self.window_cache.update_anchor(window_class, anchor);
+                    self.window_cache.update_size(window_class, size);
+                }
+            }
+        }
+    }
+
+    /// Dock a window that was just dropped if its top left corner lines up
+    /// with the top left corner of another window.
+    fn dock_dropped_window(&mut self, window_id: u64) {
+        const DOCKING_TOLERANCE: f32 = 0.5;
+
+        let Some(wrapper) = self.windows.iter().find(|wrapper| wrapper.data.id == window_id) else {
+            return;
+        };
+
+        let area = wrapper.display_information.real_area;
+        let tab_group = wrapper.tab_group;
+
+        let target_window_id = self
+            .windows
+            .iter()
+            .rev()
+            .filter(|other| other.data.id != window_id && !is_same_tab_group(other.tab_group, tab_group))
+            .find(|other| {
+                let other_area = other.display_information.real_area;
+
+                (other_area.left - area.left).abs() < DOCKING_TOLERANCE && (other_area.top - area.top).abs() < DOCKING_TOLERANCE
+            })
+            .map(|other| other.data.id);
+
+        if let Some(target_window_id) = target_window_id {
+            self.undock_window(window_id);
+            self.dock_window(window_id, target_window_id);
+        }
+    }
+
+    /// Update the tabs of all docked windows.
+    fn update_window_tabs(&mut self, state: &State<App>) {
+        for index in 0..self.windows.len() {
+            let mut tabs = std::mem::take(&mut self.windows[index].data.tabs);
+            tabs.clear();
+
+            if let Some(tab_group) = self.windows[index].tab_group {
+                let mut members: Vec<&WindowWrapper<App>> =
+                    self.windows.iter().filter(|wrapper| wrapper.tab_group == Some(tab_group)).collect();
+
+                if members.len() > 1 {
+                    // The top most window of the group is the active one.
+                    let active_window_id = members.last().unwrap().data.id;
+
+                    // Order the tabs by the time their window was opened so they don't change
+                    // position when switching between them.
+                    members.sort_by_key(|wrapper| wrapper.data.id);
+
+                    tabs.extend(members.into_iter().map(|wrapper| WindowTab {
+                        title: wrapper.window.get_title(state).to_owned(),
+                        is_active: wrapper.data.id == active_window_id,
+                        click_action: TabClickHandler::new(wrapper.data.id),
+                    }));
+                }
+            }
+
+            self.windows[index].data.tabs = tabs;
+        }
+    }
+
+    fn open_new_window(&mut self, window: impl Window<App> + 'static) {
+        let id = self.next_window_id;
+        // TODO: Actual logic to wrap around and adjust all window ids.
+        self.next_window_id = self.next_window_id.wrapping_add(1);
+
+        let (anchor, size) = Self::initial_window_state(&mut self.window_cache, window.get_class());
 
         self.windows.push(WindowWrapper {
             window: Box::new(window),
-            data: WindowData { id, anchor, size },
+            data: WindowData {
+                id,
+                anchor,
+                size,
+                tabs: Vec::new(),
+            },
             display_information: DisplayInformation {
                 real_area: Area {
                     left: 0.0,
@@ -384,8 +755,13 @@ where
                     height: size.height(),
                 },
                 display_height: 0.0,
+                snap_distance: 0.0,
             },
+            tab_group: None,
+            transition: Tween::new(0.0),
         });
+
+        self.restore_tab_group(id);
     }
 
     pub fn get_mouse_mode(&self) -> &MouseMode<App> {
@@ -480,14 +856,28 @@ where
 
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
     pub fn process_events(&mut self, custom_events: &mut Vec<App::CustomEvent>) {
+        let mut dropped_window = None;
+
         for event in self.event_queue.drain() {
             match event {
                 // This case should never be hit. FocusElement needs to be converted to
                 // FocusElementPost in the event queue while the layout is still alive.
                 Event::FocusElement { .. } => {}
+                // Context menus are converted to overlays when clicking. If the event is
+                // queued anywhere else it is ignored.
+                Event::OpenContextMenu { .. } => {}
                 Event::FocusElementPost { element_id } => self.focused_element = Some(element_id),
                 Event::Unfocus => self.focused_element = None,
-                Event::SetMouseMode { mouse_mode } => self.mouse_mode = mouse_mode,
+                Event::SetMouseMode { mouse_mode } => {
+                    if let MouseMode::MovingWindow { window_id } = self.mouse_mode
+                        && !matches!(mouse_mode, MouseMode::MovingWindow { .. })
+                    {
+                        dropped_window = Some(window_id);
+                    }
+
+                    self.mouse_mode = mouse_mode;
+                    self.drag_position = None;
+                }
                 Event::Application { custom_event } => custom_events.push(custom_event),
                 Event::OpenOverlay {
                     element,
@@ -520,6 +910,10 @@ where
                 }
             }
         }
+
+        if let Some(window_id) = dropped_window {
+            self.dock_dropped_window(window_id);
+        }
     }
 
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
@@ -529,9 +923,20 @@ where
         interface_scaling: f32,
         mouse_position: App::Position,
     ) -> InterfaceFrame<'a, App> {
+        tween::set_reduce_motion(self.reduce_motion);
+
         if let Some(overlay_element) = &mut self.overlay_element {
-            match self.windows.iter().find(|wrapper| wrapper.data.id == overlay_element.window_id) {
-                Some(wrapper) => {
+            let theme_type = match overlay_element.window_id {
+                SCREEN_OVERLAY_WINDOW_ID => Some(App::ThemeType::default()),
+                window_id => self
+                    .windows
+                    .iter()
+                    .find(|wrapper| wrapper.data.id == window_id)
+                    .map(|wrapper| wrapper.window.get_theme_type()),
+            };
+
+            match theme_type {
+                Some(theme_type) => {
                     #[cfg(feature = "debug")]
                     korangar_debug::profile_block!("create overlay element layout info");
 
@@ -545,7 +950,7 @@ where
                     let store = ElementStoreMut::new(&mut overlay_element.store, &mut self.generator, overlay_element.window_id);
                     let mut resolver = Resolver::new(available_area, 0.0, &self.text_layouter);
 
-                    App::set_current_theme_type(wrapper.window.get_theme_type());
+                    App::set_current_theme_type(theme_type);
 
                     overlay_element.element.create_layout_info(state, store, &mut resolver);
                 }
@@ -561,6 +966,8 @@ where
         // from the struct. If drop does not clear the layout or the clear
         // implementation of drop is incorrect we will end up with dangling
         // references.
+        self.update_window_tabs(state);
+
         let this = unsafe { std::mem::transmute::<&'a mut Interface<'static, App>, &'a mut Interface<'a, App>>(self) };
 
         this.windows.iter_mut().for_each(|wrapper| {
@@ -574,27 +981,38 @@ where
                 &mut this.generator,
                 &this.text_layouter,
                 this.window_size,
+                interface_scaling,
             );
+
+            // Windows fade in after opening. Since the tween doesn't restart if the
+            // target is unchanged, this only has an effect on the first frame.
+            let easing = *state.get(&theme::theme().animation().easing());
+            let duration = tween::animation_duration(*state.get(&theme::theme().animation().window_duration()));
+            wrapper.transition.set(1.0, duration, easing);
         });
 
         let mut hovered_window = None;
+        let mut is_overlay_hovered = false;
 
         if let Some(overlay_element) = &this.overlay_element {
             #[cfg(feature = "debug")]
             korangar_debug::profile_block!("lay out overlay element");
 
             // Window is guaranteed to exist since we check for that when creating the
-            // layout info for the overlay.
-            let wrapper = this
-                .windows
-                .iter()
-                .find(|wrapper| wrapper.data.id == overlay_element.window_id)
-                .unwrap();
+            // layout info for the overlay. Overlays on the screen are positioned relative
+            // to the top left corner of the screen.
+            let (position, theme_type) = match overlay_element.window_id {
+                SCREEN_OVERLAY_WINDOW_ID => (App::Position::new(0.0, 0.0), App::ThemeType::default()),
+                window_id => {
+                    let wrapper = this.windows.iter().find(|wrapper| wrapper.data.id == window_id).unwrap();
+                    let position = App::Position::new(
+                        wrapper.display_information.real_area.left,
+                        wrapper.display_information.real_area.top,
+                    );
 
-            let position = App::Position::new(
-                wrapper.display_information.real_area.left,
-                wrapper.display_information.real_area.top,
-            );
+                    (position, wrapper.window.get_theme_type())
+                }
+            };
 
             let layout = this.overlay_layout.get_or_insert_default();
             layout.update(
@@ -608,7 +1026,7 @@ where
 
             let store = ElementStore::new(&overlay_element.store, overlay_element.window_id);
 
-            App::set_current_theme_type(wrapper.window.get_theme_type());
+            App::set_current_theme_type(theme_type);
 
             let overlay_area = Area {
                 left: overlay_element.position.left(),
@@ -618,16 +1036,35 @@ where
             };
 
             if overlay_area.check().dont_mark().run(layout) {
-                hovered_window = Some(overlay_element.window_id);
+                // Overlays on the screen don't have a window layout that could handle the
+                // input, so only the overlay is marked as hovered.
+                if overlay_element.window_id != SCREEN_OVERLAY_WINDOW_ID {
+                    hovered_window = Some(overlay_element.window_id);
+                }
+
+                is_overlay_hovered = true;
             }
 
             overlay_element.element.lay_out(state, store, &(), layout);
         }
 
+        let mut visible_tab_groups = Vec::new();
+
         this.windows.iter().rev().for_each(|wrapper| {
             #[cfg(feature = "debug")]
             korangar_debug::profile_block!("lay out window");
 
+            // Only the top most window of a tab group is visible. The layout of the
+            // other windows stays empty.
+            let is_hidden = match wrapper.tab_group {
+                Some(tab_group) if visible_tab_groups.contains(&tab_group) => true,
+                Some(tab_group) => {
+                    visible_tab_groups.push(tab_group);
+                    false
+                }
+                None => false,
+            };
+
             let position = App::Position::new(
                 wrapper.display_information.real_area.left,
                 wrapper.display_information.real_area.top,
@@ -639,10 +1076,20 @@ where
                 position,
                 mouse_position,
                 this.focused_element,
-                hovered_window.is_none(),
+                hovered_window.is_none() && !is_overlay_hovered,
                 &this.mouse_mode,
             );
 
+            if is_hidden {
+                return;
+            }
+
+            App::set_current_theme_type(wrapper.window.get_theme_type());
+
+            let transition = wrapper.transition.value();
+            let window_offset = *state.get(&theme::theme().animation().window_offset());
+            layout.set_transition(transition, (1.0 - transition) * window_offset * interface_scaling);
+
             wrapper.window.lay_out(state, &this.window_store, &wrapper.data, layout);
 
             if hovered_window.is_none() && layout.is_hovered() {
@@ -654,10 +1101,15 @@ where
             windows: &this.windows,
             window_layouts: &mut this.window_layouts,
             overlay_layout: &mut this.overlay_layout,
+            overlay_window_id: this.overlay_element.as_ref().map(|overlay_element| overlay_element.window_id),
+            focused_element: this.focused_element,
+            focus_visible: &mut this.focus_visible,
             event_queue: &mut this.event_queue,
+            clipboard: &mut this.clipboard,
             window_size: this.window_size,
             mouse_mode: &this.mouse_mode,
             hovered_window,
+            is_overlay_hovered,
             interface_scaling,
             text_layouter: &this.text_layouter,
         }
@@ -668,17 +1120,22 @@ pub struct InterfaceFrame<'a, App: Application> {
     windows: &'a [WindowWrapper<App>],
     window_layouts: &'a mut BTreeMap<u64, WindowLayout<'a, App>>,
     overlay_layout: &'a mut Option<WindowLayout<'a, App>>,
+    overlay_window_id: Option<u64>,
+    focused_element: Option<ElementId>,
+    focus_visible: &'a mut bool,
     event_queue: &'a mut EventQueue<App>,
+    clipboard: &'a mut App::Clipboard,
     mouse_mode: &'a MouseMode<App>,
     window_size: App::Size,
     hovered_window: Option<u64>,
+    is_overlay_hovered: bool,
     interface_scaling: f32,
     text_layouter: &'a App::TextLayouter,
 }
 
 impl<App: Application> InterfaceFrame<'_, App> {
     pub fn is_interface_hovered(&self) -> bool {
-        self.hovered_window.is_some()
+        self.hovered_window.is_some() || self.is_overlay_hovered
     }
 
     #[cfg_attr(feature = "debug", korangar_debug::profile("render user interface"))]
@@ -696,11 +1153,35 @@ impl<App: Application> InterfaceFrame<'_, App> {
             let layout = self.window_layouts.get_mut(&wrapper.data.id).unwrap();
             layout.render(renderer, self.text_layouter);
             layout.update_tooltips(&mut tooltips);
+
+            // The focus ring is rendered right after the window so windows above it
+            // still cover it.
+            if *self.focus_visible
+                && let Some((area, clip)) = layout.get_focused_area()
+            {
+                App::set_current_theme_type(wrapper.window.get_theme_type());
+                Self::render_focus_ring(state, renderer, self.interface_scaling, area, clip);
+            }
         });
 
         if let Some(layout) = &mut self.overlay_layout {
             layout.render(renderer, self.text_layouter);
             layout.update_tooltips(&mut tooltips);
+
+            if *self.focus_visible
+                && let Some((area, clip)) = layout.get_focused_area()
+            {
+                let theme_type = self
+                    .windows
+                    .iter()
+                    .find(|wrapper| Some(wrapper.data.id) == self.overlay_window_id)
+                    .map(|wrapper| wrapper.window.get_theme_type())
+                    .unwrap_or_default();
+
+                App::set_current_theme_type(theme_type);
+
+                Self::render_focus_ring(state, renderer, self.interface_scaling, area, clip);
+            }
         }
 
         if let MouseMode::MovingWindow { window_id } = self.mouse_mode {
@@ -708,10 +1189,52 @@ impl<App: Application> InterfaceFrame<'_, App> {
         }
 
         if !tooltips.is_empty() {
+            let easing = *state.get(&theme::theme().animation().easing());
+            let duration = tween::animation_duration(*state.get(&theme::theme().animation().tooltip_duration()));
+            let tooltips: Vec<(&str, f32)> = tooltips
+                .into_iter()
+                .map(|(text, visible_for)| (text, easing.progress(visible_for, duration)))
+                .collect();
+
             self.render_tooltips(renderer, tooltip_theme, &tooltips, mouse_position);
         }
     }
 
+    #[cfg_attr(feature = "debug", korangar_debug::profile)]
+    fn render_focus_ring(state: &State<App>, renderer: &App::Renderer, interface_scaling: f32, area: Area, clip: App::Clip) {
+        let color = *state.get(&theme::theme().window().focus_ring_color());
+        let width = (*state.get(&theme::theme().window().focus_ring_width()) * interface_scaling)
+            .min(area.width / 2.0)
+            .min(area.height / 2.0);
+        let corner_diameter = App::CornerDiameter::new(0.0, 0.0, 0.0, 0.0);
+
+        // The ring is drawn on the inside of the element so it isn't cut off by the
+        // clip of the element.
+        let edges = [
+            (area.left, area.top, area.width, width),
+            (area.left, area.top + area.height - width, area.width, width),
+            (area.left, area.top + width, width, area.height - width * 2.0),
+            (
+                area.left + area.width - width,
+                area.top + width,
+                width,
+                area.height - width * 2.0,
+            ),
+        ];
+
+        for (left, top, width, height) in edges {
+            renderer.render_rectangle(
+                App::Position::new(left, top),
+                App::Size::new(width, height),
+                clip,
+                corner_diameter,
+                color,
+                color,
+                App::ShadowPadding::none(),
+            );
+        }
+    }
+
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
     fn render_window_anchors(&self, state: &State<App>, renderer: &App::Renderer, window_id: u64) {
         if let Some(wrapper) = self.windows.iter().find(|wrapper| wrapper.data.id == window_id) {
@@ -760,7 +1283,7 @@ impl<App: Application> InterfaceFrame<'_, App> {
         &self,
         renderer: &App::Renderer,
         tooltip_theme: &TooltipTheme<App>,
-        tooltips: &[&str],
+        tooltips: &[(&str, f32)],
         mouse_position: App::Position,
     ) {
         let background_color = tooltip_theme.background_color;
@@ -785,12 +1308,12 @@ impl<App: Application> InterfaceFrame<'_, App> {
         let mut forwards_iterator = tooltips.iter();
         let mut backwards_iterator = tooltips.iter().rev();
 
-        let iterator: &mut dyn Iterator<Item = &&str> = match mouse_position.top() > half_window_size.height() {
+        let iterator: &mut dyn Iterator<Item = &(&str, f32)> = match mouse_position.top() > half_window_size.height() {
             true => &mut backwards_iterator,
             false => &mut forwards_iterator,
         };
 
-        for tooltip in iterator {
+        for (tooltip, opacity) in iterator {
             let (text_dimensions, font_size) = self.text_layouter.get_text_dimensions(
                 tooltip,
                 foreground_color,
@@ -819,8 +1342,8 @@ impl<App: Application> InterfaceFrame<'_, App> {
                 App::Size::new(text_dimensions.width() + border * 2.0, text_dimensions.height() + border * 2.0),
                 App::Clip::unbound(),
                 corner_diameter,
-                background_color,
-                shadow_color,
+                background_color.multiply_alpha(*opacity),
+                shadow_color.multiply_alpha(*opacity),
                 shadow_padding,
             );
 
@@ -829,8 +1352,8 @@ impl<App: Application> InterfaceFrame<'_, App> {
                 App::Position::new(tooltip_left + border, tooltip_top + border),
                 available_width,
                 App::Clip::unbound(),
-                foreground_color,
-                highlight_color,
+                foreground_color.multiply_alpha(*opacity),
+                highlight_color.multiply_alpha(*opacity),
                 font_size,
             );
         }
@@ -838,8 +1361,15 @@ impl<App: Application> InterfaceFrame<'_, App> {
 
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
     pub fn click(&mut self, state: &State<App>, mouse_button: MouseButton) {
+        *self.focus_visible = false;
+
         self.event_queue.queue(Event::Unfocus);
-        self.event_queue.queue(Event::CloseOverlay);
+
+        // Clicks inside of the overlay leave it open, so elements like submenus can
+        // be used. Elements inside the overlay close it themselves.
+        if !self.is_overlay_hovered {
+            self.event_queue.queue(Event::CloseOverlay);
+        }
 
         if let Some(hovered_window) = self.hovered_window {
             self.event_queue.queue(Event::MoveWindowToTop { window_id: hovered_window });
@@ -864,6 +1394,43 @@ impl<App: Application> InterfaceFrame<'_, App> {
 
             layout.handle_click(state, self.event_queue, mouse_button);
         }
+
+        self.open_context_menus(state);
+    }
+
+    /// Turn the context menus opened by click handlers into overlays at the
+    /// mouse position.
+    fn open_context_menus(&mut self, state: &State<App>) {
+        let Some(window_id) = self.hovered_window else {
+            return;
+        };
+
+        let Some(wrapper) = self.windows.iter().find(|wrapper| wrapper.data.id == window_id) else {
+            return;
+        };
+
+        // The overlay is laid out at the same position as its window, so the mouse
+        // position is the same in both layouts.
+        let position = self.window_layouts.get(&window_id).unwrap().get_mouse_position();
+
+        App::set_current_theme_type(wrapper.window.get_theme_type());
+
+        self.event_queue.iter_mut().for_each(|event| {
+            if !matches!(event, Event::OpenContextMenu { .. }) {
+                return;
+            }
+
+            if let Event::OpenContextMenu { menu } = std::mem::replace(event, Event::CloseOverlay) {
+                let size = menu.size(state);
+
+                *event = Event::OpenOverlay {
+                    element: ErasedElement::new(ContextMenuElement::new(menu)),
+                    position,
+                    size,
+                    window_id,
+                };
+            }
+        });
     }
 
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
@@ -917,6 +1484,159 @@ impl<App: Application> InterfaceFrame<'_, App> {
         input_handled
     }
 
+    #[cfg_attr(feature = "debug", korangar_debug::profile)]
+    pub fn input_keys(&mut self, state: &State<App>, keys: &[KeyInput]) -> bool {
+        let mut input_handled = false;
+
+        if let Some(layout) = &self.overlay_layout {
+            for key in keys {
+                input_handled |= layout.handle_key(state, self.event_queue, self.clipboard, *key);
+            }
+        }
+
+        for wrapper in self.windows {
+            let layout = self.window_layouts.get(&wrapper.data.id).unwrap();
+
+            for key in keys {
+                input_handled |= layout.handle_key(state, self.event_queue, self.clipboard, *key);
+            }
+        }
+
+        input_handled
+    }
+
+    #[cfg_attr(feature = "debug", korangar_debug::profile)]
+    pub fn input_composition(&mut self, state: &State<App>, events: &[CompositionEvent]) -> bool {
+        let mut input_handled = false;
+
+        if let Some(layout) = &self.overlay_layout {
+            for event in events {
+                input_handled |= layout.handle_composition(state, self.event_queue, event);
+            }
+        }
+
+        for wrapper in self.windows {
+            let layout = self.window_layouts.get(&wrapper.data.id).unwrap();
+
+            for event in events {
+                input_handled |= layout.handle_composition(state, self.event_queue, event);
+            }
+        }
+
+        input_handled
+    }
+
+    /// Navigate the interface with a keyboard or gamepad. Returns `true` if the
+    /// input was used.
+    ///
+    /// Directional movement and activation only apply while an element is
+    /// focused, so the interface has to be entered with
+    /// [`FocusDirection::Next`](event::FocusDirection::Next) or
+    /// [`FocusDirection::Previous`](event::FocusDirection::Previous).
+    #[cfg_attr(feature = "debug", korangar_debug::profile)]
+    pub fn navigate(&mut self, state: &State<App>, input: NavigationInput) -> bool {
+        let windows = self.windows;
+        let window_layouts = &*self.window_layouts;
+        let overlay_layout = self.overlay_layout.as_ref();
+
+        // Overlay first, then the windows from top to bottom. Layouts of the
+        // overlay don't have a window id.
+        let layouts = || {
+            overlay_layout.map(|layout| (None, layout)).into_iter().chain(
+                windows
+                    .iter()
+                    .rev()
+                    .filter_map(|wrapper| window_layouts.get(&wrapper.data.id).map(|layout| (Some(wrapper.data.id), layout))),
+            )
+        };
+
+        let focused_layout = self
+            .focused_element
+            .and_then(|element_id| layouts().find(|(_, layout)| layout.has_focusable(element_id)));
+
+        if let Some((_, layout)) = focused_layout
+            && layout.captures_navigation(input)
+        {
+            return false;
+        }
+
+        match input {
+            NavigationInput::Cancel => {
+                // Close an open overlay before removing the focus from the window.
+                if self.overlay_window_id.is_some() {
+                    self.event_queue.queue(Event::CloseOverlay);
+
+                    if let Some((None, _)) = focused_layout {
+                        self.focused_element = None;
+                        self.event_queue.queue(Event::Unfocus);
+                    }
+
+                    return true;
+                }
+
+                if self.focused_element.take().is_none() {
+                    return false;
+                }
+
+                self.event_queue.queue(Event::Unfocus);
+                true
+            }
+            NavigationInput::Activate => {
+                let (Some(element_id), Some((_, layout))) = (self.focused_element, focused_layout) else {
+                    return false;
+                };
+
+                layout.activate_focusable(state, self.event_queue, element_id)
+            }
+            NavigationInput::Move(direction) => {
+                // Without focus only tab enters the interface, unless an overlay like a
+                // context menu is open.
+                if self.focused_element.is_none()
+                    && !direction.is_sequential()
+                    && !overlay_layout.is_some_and(|layout| layout.has_visible_focusables())
+                {
+                    return false;
+                }
+
+                // An open overlay takes the focus, otherwise the focus stays in the window
+                // of the focused element or starts in the top window.
+                let target_layout = overlay_layout
+                    .filter(|layout| layout.has_visible_focusables())
+                    .map(|layout| (None, layout))
+                    .or(focused_layout)
+                    .or_else(|| layouts().find(|(_, layout)| layout.has_visible_focusables()));
+
+                let Some((window_id, layout)) = target_layout else {
+                    return false;
+                };
+
+                let Some(element_id) = layout.find_focus_target(self.focused_element, direction) else {
+                    return false;
+                };
+
+                self.focused_element = Some(element_id);
+                *self.focus_visible = true;
+                self.event_queue.queue(Event::FocusElementPost { element_id });
+
+                if let Some(window_id) = window_id {
+                    self.event_queue.queue(Event::MoveWindowToTop { window_id });
+                }
+
+                true
+            }
+        }
+    }
+
+    /// Get the screen area of the text cursor of the focused element, if it
+    /// has one.
+    pub fn get_ime_cursor_area(&self) -> Option<(App::Position, App::Size)> {
+        self.overlay_layout
+            .iter()
+            .chain(self.windows.iter().filter_map(|wrapper| self.window_layouts.get(&wrapper.data.id)))
+            .find_map(WindowLayout::get_ime_cursor_area)
+            .map(|area| (App::Position::new(area.left, area.top), App::Size::new(area.width, area.height)))
+    }
+
     pub fn set_mouse_mode(&mut self, mouse_mode: impl Into<MouseMode<App>>) {
         self.event_queue.queue(Event::SetMouseMode {
             mouse_mode: mouse_mode.into(),
@@ -932,6 +1652,29 @@ impl<App: Application> InterfaceFrame<'_, App> {
     pub fn unfocus(&mut self) {
         self.event_queue.queue(Event::Unfocus);
     }
+
+    /// Open a context menu at the mouse position for something that is not
+    /// part of the interface, for example an entity in the world.
+    pub fn open_context_menu(&mut self, state: &State<App>, menu: ContextMenu<App>, mouse_position: App::Position) {
+        App::set_current_theme_type(App::ThemeType::default());
+
+        let size = menu.size(state);
+        let position = App::Position::new(
+            mouse_position.left() / self.interface_scaling,
+            mouse_position.top() / self.interface_scaling,
+        );
+
+        self.event_queue.queue(Event::OpenOverlay {
+            element: ErasedElement::new(ContextMenuElement::new(menu)),
+            position,
+            size,
+            window_id: SCREEN_OVERLAY_WINDOW_ID,
+        });
+    }
+
+    pub fn close_overlay(&mut self) {
+        self.event_queue.queue(Event::CloseOverlay);
+    }
 }
 
 impl<App: Application> Drop for InterfaceFrame<'_, App> {
@@ -950,6 +1693,25 @@ impl<App: Application> Drop for InterfaceFrame<'_, App> {
             }
         });
 
+        // Unfocus the focused element if it wasn't laid out this frame, for example
+        // because its window was closed. Otherwise the interface would keep the
+        // keyboard input forever.
+        if let Some(element_id) = self.focused_element
+            && !self.event_queue.iter().any(|event| {
+                matches!(
+                    event,
+                    Event::FocusElement { .. } | Event::FocusElementPost { .. } | Event::Unfocus
+                )
+            })
+            && !self
+                .overlay_layout
+                .iter()
+                .chain(self.window_layouts.values())
+                .any(|layout| layout.has_focusable(element_id))
+        {
+            self.event_queue.queue(Event::Unfocus);
+        }
+
         self.window_layouts.values_mut().for_each(|layout| layout.clear());
 
         if let Some(layout) = self.overlay_layout {
@@ -0,0 +1,339 @@
+//! Markup for styled text, links and inline icons.
+//!
+//! The tags follow the format of the messages that are sent by the server, so
+//! chat and dialog messages can be displayed without converting them first.
+//!
+//! - `<B>text</B>`: bold text
+//! - `<I>text</I>`: italic text
+//! - `<ITEM>label<INFO>item id</INFO></ITEM>`: link to an item
+//! - `<NAVI>label<INFO>location</INFO></NAVI>`: link to a location on a map
+//! - `<URL>label<INFO>address</INFO></URL>`: link to a website
+//! - `<ICON>source</ICON>`: inline icon
+//!
+//! Tags that are not closed or not known are displayed as regular text.
+//! Color codes (`^RRGGBB`) are not part of the markup, they are left in the
+//! text and handled by the [`TextLayouter`](crate::application::TextLayouter).
+
+use crate::layout::area::Area;
+
+const BOLD_START: &str = "<B>";
+const BOLD_END: &str = "</B>";
+const ITALIC_START: &str = "<I>";
+const ITALIC_END: &str = "</I>";
+const INFO_START: &str = "<INFO>";
+const INFO_END: &str = "</INFO>";
+const ICON_START: &str = "<ICON>";
+const ICON_END: &str = "</ICON>";
+
+const LINK_TAGS: [(LinkKind, &str, &str); 3] = [
+    (LinkKind::Item, "<ITEM>", "</ITEM>"),
+    (LinkKind::Navigation, "<NAVI>", "</NAVI>"),
+    (LinkKind::Url, "<URL>", "</URL>"),
+];
+
+/// Style of a span of text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TextStyle {
+    pub bold: bool,
+    pub italic: bool,
+}
+
+/// The kind of a [`Link`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    Item,
+    Navigation,
+    Url,
+}
+
+/// A clickable link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Link<'a> {
+    pub kind: LinkKind,
+    /// Content of the `<INFO>` tag, e.g. the item id of an item link.
+    pub target: &'a str,
+}
+
+/// A span of text with markup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkupSpan<'a> {
+    Text {
+        text: &'a str,
+        style: TextStyle,
+        link: Option<Link<'a>>,
+    },
+    Icon {
+        source: &'a str,
+    },
+}
+
+impl MarkupSpan<'_> {
+    /// Returns `true` for links and icons. These spans have an area inside of
+    /// the text, see [`InlineArea`].
+    pub fn is_inline(&self) -> bool {
+        matches!(self, MarkupSpan::Text { link: Some(..), .. } | MarkupSpan::Icon { .. })
+    }
+}
+
+/// Area of a link or icon inside of a text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InlineArea {
+    /// Index of the link or icon, counting only the spans that are
+    /// [`inline`](MarkupSpan::is_inline). A link that wraps around to the next
+    /// line has one area per line, all with the same index.
+    pub index: usize,
+    pub area: Area,
+}
+
+enum Tag<'a> {
+    Style { bold: Option<bool>, italic: Option<bool> },
+    Link { label: &'a str, link: Link<'a> },
+    Icon { source: &'a str },
+}
+
+/// Iterator over the [`MarkupSpan`]s of a text.
+pub struct MarkupIterator<'a> {
+    text: &'a str,
+    style: TextStyle,
+    position: usize,
+}
+
+impl<'a> MarkupIterator<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            style: TextStyle::default(),
+            position: 0,
+        }
+    }
+
+    /// Parse the tag at the start of the text. Returns the tag and its length
+    /// in bytes.
+    fn parse_tag(text: &'a str) -> Option<(Tag<'a>, usize)> {
+        let style_tags = [
+            (BOLD_START, Some(true), None),
+            (BOLD_END, Some(false), None),
+            (ITALIC_START, None, Some(true)),
+            (ITALIC_END, None, Some(false)),
+        ];
+
+        for (tag, bold, italic) in style_tags {
+            if text.starts_with(tag) {
+                return Some((Tag::Style { bold, italic }, tag.len()));
+            }
+        }
+
+        for (kind, start_tag, end_tag) in LINK_TAGS {
+            if let Some(remaining) = text.strip_prefix(start_tag) {
+                let content_length = remaining.find(end_tag)?;
+                let content = &remaining[..content_length];
+
+                let info_start = content.find(INFO_START)?;
+                let info = &content[info_start + INFO_START.len()..];
+                let info_length = info.find(INFO_END)?;
+
+                let target = &info[..info_length];
+                let label = match &content[..info_start] {
+                    "" => target,
+                    label => label,
+                };
+
+                let link = Link { kind, target };
+                let length = start_tag.len() + content_length + end_tag.len();
+
+                return Some((Tag::Link { label, link }, length));
+            }
+        }
+
+        if let Some(remaining) = text.strip_prefix(ICON_START) {
+            let source_length = remaining.find(ICON_END)?;
+            let source = &remaining[..source_length];
+            let length = ICON_START.len() + source_length + ICON_END.len();
+
+            return Some((Tag::Icon { source }, length));
+        }
+
+        None
+    }
+}
+
+impl<'a> Iterator for MarkupIterator<'a> {
+    type Item = MarkupSpan<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = &self.text[self.position..];
+
+        if remaining.is_empty() {
+            return None;
+        }
+
+        let mut search_position = 0;
+
+        while let Some(offset) = remaining[search_position..].find('<') {
+            let tag_position = search_position + offset;
+
+            // Invalid tag - continue searching.
+            let Some((tag, tag_length)) = Self::parse_tag(&remaining[tag_position..]) else {
+                search_position = tag_position + 1;
+                continue;
+            };
+
+            // Return the text before the tag first.
+            if tag_position > 0 {
+                self.position += tag_position;
+                return Some(MarkupSpan::Text {
+                    text: &remaining[..tag_position],
+                    style: self.style,
+                    link: None,
+                });
+            }
+
+            self.position += tag_length;
+
+            match tag {
+                Tag::Style { bold, italic } => {
+                    self.style.bold = bold.unwrap_or(self.style.bold);
+                    self.style.italic = italic.unwrap_or(self.style.italic);
+                    return self.next();
+                }
+                Tag::Link { label, link } => {
+                    return Some(MarkupSpan::Text {
+                        text: label,
+                        style: self.style,
+                        link: Some(link),
+                    });
+                }
+                Tag::Icon { source } => return Some(MarkupSpan::Icon { source }),
+            }
+        }
+
+        // Return remaining text.
+        self.position = self.text.len();
+        Some(MarkupSpan::Text {
+            text: remaining,
+            style: self.style,
+            link: None,
+        })
+    }
+}
+
+/// Get the link or icon with the given index, see [`InlineArea::index`].
+pub fn inline_span(text: &str, index: usize) -> Option<MarkupSpan<'_>> {
+    MarkupIterator::new(text).filter(MarkupSpan::is_inline).nth(index)
+}
+
+#[cfg(test)]
+mod parsing {
+    use super::{Link, LinkKind, MarkupIterator, MarkupSpan, TextStyle, inline_span};
+
+    const REGULAR: TextStyle = TextStyle {
+        bold: false,
+        italic: false,
+    };
+    const BOLD: TextStyle = TextStyle { bold: true, italic: false };
+    const BOLD_ITALIC: TextStyle = TextStyle { bold: true, italic: true };
+
+    fn text(text: &str, style: TextStyle) -> MarkupSpan<'_> {
+        MarkupSpan::Text { text, style, link: None }
+    }
+
+    #[test]
+    fn plain_text() {
+        let spans: Vec<_> = MarkupIterator::new("Hello ^FF0000World").collect();
+
+        assert_eq!(spans, [text("Hello ^FF0000World", REGULAR)]);
+    }
+
+    #[test]
+    fn empty_text() {
+        assert_eq!(MarkupIterator::new("").count(), 0);
+        assert_eq!(MarkupIterator::new("<B></B>").count(), 0);
+    }
+
+    #[test]
+    fn nested_styles() {
+        let spans: Vec<_> = MarkupIterator::new("a <B>b <I>c</B> d</I> e").collect();
+
+        assert_eq!(spans, [
+            text("a ", REGULAR),
+            text("b ", BOLD),
+            text("c", BOLD_ITALIC),
+            text(" d", TextStyle { bold: false, italic: true }),
+            text(" e", REGULAR),
+        ]);
+    }
+
+    #[test]
+    fn item_link() {
+        let spans: Vec<_> = MarkupIterator::new("Buying <B><ITEM>[Red Potion]<INFO>501</INFO></ITEM></B>!").collect();
+
+        assert_eq!(spans, [
+            text("Buying ", REGULAR),
+            MarkupSpan::Text {
+                text: "[Red Potion]",
+                style: BOLD,
+                link: Some(Link {
+                    kind: LinkKind::Item,
+                    target: "501",
+                }),
+            },
+            text("!", REGULAR),
+        ]);
+        assert!(spans[1].is_inline());
+    }
+
+    #[test]
+    fn link_without_label_uses_target() {
+        let spans: Vec<_> = MarkupIterator::new("<URL><INFO>https://example.com</INFO></URL>").collect();
+
+        assert_eq!(spans, [MarkupSpan::Text {
+            text: "https://example.com",
+            style: REGULAR,
+            link: Some(Link {
+                kind: LinkKind::Url,
+                target: "https://example.com",
+            }),
+        }]);
+    }
+
+    #[test]
+    fn icon() {
+        let spans: Vec<_> = MarkupIterator::new("<ICON>501</ICON> Red Potion").collect();
+
+        assert_eq!(spans, [MarkupSpan::Icon { source: "501" }, text(" Red Potion", REGULAR)]);
+        assert!(spans[0].is_inline());
+    }
+
+    #[test]
+    fn inline_spans_are_indexed_in_order() {
+        let text = "<ICON>501</ICON> <B>a</B> <NAVI>b<INFO>prontera</INFO></NAVI>";
+
+        assert_eq!(inline_span(text, 0), Some(MarkupSpan::Icon { source: "501" }));
+        assert_eq!(
+            inline_span(text, 1),
+            Some(MarkupSpan::Text {
+                text: "b",
+                style: REGULAR,
+                link: Some(Link {
+                    kind: LinkKind::Navigation,
+                    target: "prontera",
+                }),
+            })
+        );
+        assert_eq!(inline_span(text, 2), None);
+    }
+
+    #[test]
+    fn invalid_tags_are_text() {
+        let spans: Vec<_> = MarkupIterator::new("1 < 2 <ITEM>[Apple]</ITEM> <NAVI>a<INFO>b <ICON>").collect();
+
+        assert_eq!(spans, [text("1 < 2 <ITEM>[Apple]</ITEM> <NAVI>a<INFO>b <ICON>", REGULAR)]);
+    }
+
+    #[test]
+    fn multi_byte_characters() {
+        let spans: Vec<_> = MarkupIterator::new("빨간 <B>포션</B>").collect();
+
+        assert_eq!(spans, [text("빨간 ", REGULAR), text("포션", BOLD)]);
+    }
+}
@@ -3,12 +3,18 @@ use rust_state::Path;
 use crate::application::Application;
 use crate::components::button::ButtonTheme;
 use crate::components::collapsible::CollapsibleTheme;
+use crate::components::context_menu::ContextMenuTheme;
 use crate::components::drop_down::DropDownTheme;
 use crate::components::field::FieldTheme;
+use crate::components::number_input::NumberInputTheme;
+use crate::components::progress_bar::ProgressBarTheme;
+use crate::components::slider::SliderTheme;
 use crate::components::state_button::StateButtonTheme;
+use crate::components::table::TableTheme;
 use crate::components::text::TextTheme;
 use crate::components::text_box::TextBoxTheme;
 use crate::layout::tooltip::TooltipTheme;
+use crate::tween::AnimationTheme;
 use crate::window::WindowTheme;
 
 /// Glue between [`korangar_interface`] and the final application. This trait
@@ -43,8 +49,26 @@ pub trait ThemePathGetter<App: Application>: Copy {
     /// Path to the field theme.
     fn field(self) -> impl Path<App, FieldTheme<App>>;
 
+    /// Path to the slider theme.
+    fn slider(self) -> impl Path<App, SliderTheme<App>>;
+
+    /// Path to the progress bar theme.
+    fn progress_bar(self) -> impl Path<App, ProgressBarTheme<App>>;
+
+    /// Path to the number input theme.
+    fn number_input(self) -> impl Path<App, NumberInputTheme<App>>;
+
+    /// Path to the table theme.
+    fn table(self) -> impl Path<App, TableTheme<App>>;
+
+    /// Path to the context menu theme.
+    fn context_menu(self) -> impl Path<App, ContextMenuTheme<App>>;
+
     /// Path to the tooltip theme.
     fn tooltip(self) -> impl Path<App, TooltipTheme<App>>;
+
+    /// Path to the animation theme.
+    fn animation(self) -> impl Path<App, AnimationTheme>;
 }
 
 /// Path to the theme of the window.
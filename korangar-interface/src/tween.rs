@@ -0,0 +1,274 @@
+//! Smooth transitions between values over time.
+//!
+//! Unlike the parameters of the audio engine, tweens are not updated every
+//! frame. The interface is only laid out when needed, so the current value is
+//! sampled from the time that passed since the transition started.
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use rust_state::RustState;
+
+use crate::layout::area::Area;
+
+/// Durations and easing of all animations in the interface. All durations are
+/// in seconds, a duration of zero disables the animation.
+#[derive(RustState)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AnimationTheme {
+    pub easing: Easing,
+    /// Time it takes a window to fade in after opening it.
+    pub window_duration: f32,
+    /// Distance in pixels that a window moves up while fading in.
+    pub window_offset: f32,
+    /// Time it takes a collapsible to expand or collapse.
+    pub collapsible_duration: f32,
+    /// Time it takes a tooltip to fade in after it appeared.
+    pub tooltip_duration: f32,
+    /// Time it takes an element to change to its hovered colors and back.
+    pub hover_duration: f32,
+}
+
+/// A trait for types that can be smoothly interpolated.
+pub trait Tweenable: Copy + PartialEq {
+    /// Returns a linearly interpolated value between `a` and `b`.
+    ///
+    /// An amount of `0.0` should yield `a`, an amount of `1.0` should
+    /// yield `b`, and an amount of `0.5` should yield a value halfway
+    /// between `a` and `b`.
+    #[must_use]
+    fn interpolate(a: Self, b: Self, amount: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn interpolate(a: Self, b: Self, amount: f32) -> Self {
+        a + (b - a) * amount
+    }
+}
+
+impl Tweenable for Area {
+    fn interpolate(a: Self, b: Self, amount: f32) -> Self {
+        Self {
+            left: f32::interpolate(a.left, b.left, amount),
+            top: f32::interpolate(a.top, b.top, amount),
+            width: f32::interpolate(a.width, b.width, amount),
+            height: f32::interpolate(a.height, b.height, amount),
+        }
+    }
+}
+
+/// Curve that maps the progress of a transition to the amount that the value
+/// changed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    #[default]
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Apply the easing to a linear progress between `0.0` and `1.0`.
+    #[must_use]
+    pub fn apply(self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => progress,
+            Easing::EaseIn => progress.powi(3),
+            Easing::EaseOut => 1.0 - (1.0 - progress).powi(3),
+            Easing::EaseInOut => match progress < 0.5 {
+                true => 4.0 * progress.powi(3),
+                false => 1.0 - (-2.0 * progress + 2.0).powi(3) / 2.0,
+            },
+        }
+    }
+
+    /// Get the eased progress of a transition that started `elapsed` ago.
+    #[must_use]
+    pub fn progress(self, elapsed: Duration, duration: Duration) -> f32 {
+        match duration.is_zero() {
+            true => 1.0,
+            false => self.apply(elapsed.as_secs_f32() / duration.as_secs_f32()),
+        }
+    }
+}
+
+thread_local! {
+    static REDUCE_MOTION: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Set whether or not animations should be disabled. This is set by the
+/// [`Interface`](crate::Interface) before laying out the windows.
+pub(crate) fn set_reduce_motion(reduce_motion: bool) {
+    REDUCE_MOTION.set(reduce_motion);
+}
+
+/// Convert a duration in seconds from the [`AnimationTheme`] to a
+/// [`Duration`]. If reduced motion is enabled, the duration is always zero so
+/// all values change immediately.
+#[must_use]
+pub fn animation_duration(seconds: f32) -> Duration {
+    match REDUCE_MOTION.get() {
+        true => Duration::ZERO,
+        false => Duration::from_secs_f32(seconds.max(0.0)),
+    }
+}
+
+/// A value that can be smoothly transitioned to a new target.
+#[derive(Clone, Copy)]
+pub struct Tween<T: Tweenable> {
+    state: State<T>,
+}
+
+impl<T: Tweenable> Tween<T> {
+    /// Creates a new [`Tween`] with an initial value.
+    #[must_use]
+    pub fn new(initial_value: T) -> Self {
+        Self {
+            state: State::Idle { value: initial_value },
+        }
+    }
+
+    /// Returns the value that the tween is transitioning to.
+    #[must_use]
+    pub fn target(&self) -> T {
+        match self.state {
+            State::Idle { value } => value,
+            State::Tweening { target, .. } => target,
+        }
+    }
+
+    /// Returns the current value of the tween.
+    #[must_use]
+    pub fn value(&self) -> T {
+        self.value_at(Instant::now())
+    }
+
+    /// Returns the value of the tween at a given point in time.
+    #[must_use]
+    pub fn value_at(&self, time: Instant) -> T {
+        match self.state {
+            State::Idle { value } => value,
+            State::Tweening {
+                start,
+                target,
+                start_time,
+                duration,
+                easing,
+            } => T::interpolate(
+                start,
+                target,
+                easing.progress(time.saturating_duration_since(start_time), duration),
+            ),
+        }
+    }
+
+    /// Returns `true` if the tween reached its target.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        match self.state {
+            State::Idle { .. } => true,
+            State::Tweening { start_time, duration, .. } => start_time.elapsed() >= duration,
+        }
+    }
+
+    /// Starts a transition from the current value to the target value. If the
+    /// tween is already transitioning to the target, it continues unchanged.
+    pub fn set(&mut self, target: T, duration: Duration, easing: Easing) {
+        self.set_at(target, duration, easing, Instant::now());
+    }
+
+    /// Same as [`set`](Self::set) but with an explicit start time.
+    pub fn set_at(&mut self, target: T, duration: Duration, easing: Easing, time: Instant) {
+        if self.target() == target {
+            return;
+        }
+
+        self.state = match duration.is_zero() {
+            true => State::Idle { value: target },
+            false => State::Tweening {
+                start: self.value_at(time),
+                target,
+                start_time: time,
+                duration,
+                easing,
+            },
+        };
+    }
+}
+
+#[derive(Clone, Copy)]
+enum State<T: Tweenable> {
+    Idle {
+        value: T,
+    },
+    Tweening {
+        start: T,
+        target: T,
+        start_time: Instant,
+        duration: Duration,
+        easing: Easing,
+    },
+}
+
+#[cfg(test)]
+mod tweening {
+    use std::time::{Duration, Instant};
+
+    use super::{Easing, Tween};
+
+    #[test]
+    fn easing_starts_and_ends_at_bounds() {
+        for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(easing.apply(2.0), 1.0);
+        }
+
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+    }
+
+    #[test]
+    fn tween_interpolates_over_time() {
+        let start_time = Instant::now();
+        let mut tween = Tween::new(0.0);
+        tween.set_at(10.0, Duration::from_secs(2), Easing::Linear, start_time);
+
+        assert_eq!(tween.value_at(start_time), 0.0);
+        assert_eq!(tween.value_at(start_time + Duration::from_secs(1)), 5.0);
+        assert_eq!(tween.value_at(start_time + Duration::from_secs(3)), 10.0);
+    }
+
+    #[test]
+    fn retargeting_starts_from_current_value() {
+        let start_time = Instant::now();
+        let mut tween = Tween::new(0.0);
+        tween.set_at(10.0, Duration::from_secs(2), Easing::Linear, start_time);
+
+        // Setting the same target again doesn't restart the transition.
+        tween.set_at(
+            10.0,
+            Duration::from_secs(2),
+            Easing::Linear,
+            start_time + Duration::from_secs(1),
+        );
+        assert_eq!(tween.value_at(start_time + Duration::from_secs(1)), 5.0);
+
+        tween.set_at(0.0, Duration::from_secs(2), Easing::Linear, start_time + Duration::from_secs(1));
+        assert_eq!(tween.value_at(start_time + Duration::from_secs(2)), 2.5);
+    }
+
+    #[test]
+    fn zero_duration_is_immediate() {
+        let mut tween = Tween::new(0.0);
+        tween.set(1.0, Duration::ZERO, Easing::EaseOut);
+
+        assert!(tween.is_finished());
+        assert_eq!(tween.value(), 1.0);
+    }
+}
@@ -1,4 +1,5 @@
 mod anchor;
+mod snap;
 pub mod store;
 
 use std::marker::PhantomData;
@@ -6,6 +7,7 @@ use std::marker::PhantomData;
 pub use anchor::{Anchor, AnchorPoint};
 pub use interface_macros::StateWindow;
 use rust_state::{Path, RustState, Selector, State};
+pub(crate) use snap::snap_area;
 use store::WindowStore;
 
 use crate::MouseMode;
@@ -38,6 +40,9 @@ pub trait Window<App: Application>: private::Sealed {
     /// Returns if the window is closable or not.
     fn is_closable(&self, state: &State<App>) -> bool;
 
+    /// Get the title of the window.
+    fn get_title<'a>(&'a self, state: &'a State<App>) -> &'a str;
+
     /// Create the layout info for the window.
     #[allow(private_interfaces)]
     fn create_layout_info(
@@ -48,6 +53,7 @@ pub trait Window<App: Application>: private::Sealed {
         generator: &mut ElementIdGenerator,
         text_layouter: &App::TextLayouter,
         window_size: App::Size,
+        interface_scaling: f32,
     ) -> DisplayInformation;
 
     /// Lay out the window.
@@ -122,6 +128,13 @@ where
     pub overflow_behavior: App::OverflowBehavior,
     pub anchor_color: App::Color,
     pub closest_anchor_color: App::Color,
+    pub focus_ring_color: App::Color,
+    pub focus_ring_width: f32,
+    /// Distance in pixels at which a window that is being moved snaps to the
+    /// screen edges and other windows. Zero disables snapping.
+    pub snap_distance: f32,
+    /// Background color of the selected tab in a tab group.
+    pub active_tab_color: App::Color,
 }
 
 pub struct WindowData<App>
@@ -131,16 +144,28 @@ where
     pub id: u64,
     pub anchor: Anchor<App>,
     pub size: App::Size,
+    /// Tabs of all windows in the same tab group, including this one. Empty
+    /// if the window is not docked.
+    pub(crate) tabs: Vec<WindowTab>,
+}
+
+/// A tab in the tab bar of a docked window.
+pub(crate) struct WindowTab {
+    pub title: String,
+    pub is_active: bool,
+    pub click_action: TabClickHandler,
 }
 
 pub(crate) struct DisplayInformation {
     pub real_area: Area,
     pub display_height: f32,
+    pub snap_distance: f32,
 }
 
 pub struct WindowLayoutInfoSet<T> {
     area: Area,
     title_area: Area,
+    tab_area: Option<Area>,
     children: T,
 }
 
@@ -205,6 +230,29 @@ impl<App: Application> ClickHandler<App> for CloseClickHandler {
     }
 }
 
+pub(crate) struct TabClickHandler {
+    window_id: u64,
+}
+
+impl TabClickHandler {
+    pub fn new(window_id: u64) -> Self {
+        Self { window_id }
+    }
+}
+
+impl<App: Application> ClickHandler<App> for TabClickHandler {
+    fn handle_click(&self, _: &State<App>, queue: &mut EventQueue<App>) {
+        let window_id = self.window_id;
+
+        // Selecting the tab brings its window to the front of the tab group.
+        // Dragging the tab afterwards pulls the window out of the group.
+        queue.queue(Event::MoveWindowToTop { window_id });
+        queue.queue(Event::SetMouseMode {
+            mouse_mode: MouseMode::DraggingTab { window_id },
+        });
+    }
+}
+
 pub struct WindowInternal<App, Title, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, Elements>
 where
     App: Application,
@@ -363,6 +411,10 @@ where
         *state.get(&self.closable)
     }
 
+    fn get_title<'a>(&'a self, state: &'a State<App>) -> &'a str {
+        state.get(&self.title).as_ref()
+    }
+
     #[allow(private_interfaces)]
     fn create_layout_info(
         &mut self,
@@ -372,6 +424,7 @@ where
         generator: &mut ElementIdGenerator,
         text_layouter: &App::TextLayouter,
         window_size: App::Size,
+        interface_scaling: f32,
     ) -> DisplayInformation {
         self.close_click_action.update(data.id);
         self.move_click_action.update(data.id);
@@ -396,18 +449,28 @@ where
             data.size.height().min(maximum_height).max(minimum_height),
         );
 
+        // The window position is in screen space while the size is not, so we
+        // need the scaled size to place the window correctly.
+        let scaled_size = App::Size::new(
+            adjusted_size.width() * interface_scaling,
+            adjusted_size.height() * interface_scaling,
+        );
+
         if data.anchor.is_initializing() {
-            data.anchor.initialize(window_size, adjusted_size);
+            data.anchor.initialize(window_size, scaled_size);
         }
 
         // Adjust position
         let real_position = {
             let anchor_position = data.anchor.to_position(window_size);
-            let half_width = adjusted_size.width() / 2.0;
+            let half_width = scaled_size.width() / 2.0;
 
             App::Position::new(
                 anchor_position.left().max(-half_width).min(window_size.width() - half_width),
-                anchor_position.top().max(0.0).min(window_size.height() - title_height),
+                anchor_position
+                    .top()
+                    .max(0.0)
+                    .min(window_size.height() - title_height * interface_scaling),
             )
         };
 
@@ -421,6 +484,7 @@ where
         let mut resolver = Resolver::new(available_area, 0.0, text_layouter);
 
         let title_area = resolver.with_height(title_height);
+        let tab_area = (!data.tabs.is_empty()).then(|| resolver.with_height(title_height));
 
         let (area, children) = resolver.with_derived_borderless(*state.get(&self.gaps), *state.get(&self.border), title_gap, |resolver| {
             self.elements.create_layout_info(state, store, resolver)
@@ -436,6 +500,7 @@ where
         self.layout_info = Some(WindowLayoutInfoSet {
             area,
             title_area,
+            tab_area,
             children,
         });
 
@@ -444,6 +509,7 @@ where
         DisplayInformation {
             real_area: area,
             display_height,
+            snap_distance: *state.get(&theme().window().snap_distance()),
         }
     }
 
@@ -490,6 +556,52 @@ where
             layout.register_click_handler(MouseButton::Left, &self.move_click_action);
         }
 
+        if let Some(tab_area) = layout_info.tab_area {
+            let tab_width = tab_area.width / data.tabs.len() as f32;
+
+            for (index, tab) in data.tabs.iter().enumerate() {
+                let area = Area {
+                    left: tab_area.left + tab_width * index as f32,
+                    top: tab_area.top,
+                    width: tab_width,
+                    height: tab_area.height,
+                };
+
+                let is_tab_hovered = area.check().run(layout);
+
+                if is_tab_hovered {
+                    layout.register_click_handler(MouseButton::Left, &tab.click_action);
+                }
+
+                let tab_color = match is_tab_hovered {
+                    true => *state.get(&self.hovered_title_color),
+                    false => *state.get(&self.title_color),
+                };
+
+                layout.add_text(
+                    area,
+                    &tab.title,
+                    *state.get(&self.font_size),
+                    tab_color,
+                    *state.get(&self.highlight_color),
+                    HorizontalAlignment::Center { offset: 0.0, border: 0.0 },
+                    *state.get(&theme().window().vertical_alignment()),
+                    *state.get(&theme().window().overflow_behavior()),
+                );
+
+                if tab.is_active {
+                    layout.add_rectangle(
+                        area,
+                        *state.get(&self.close_button_corner_diameter),
+                        *state.get(&theme().window().active_tab_color()),
+                        // TODO: Properly theme
+                        *state.get(&theme().window().active_tab_color()),
+                        App::ShadowPadding::none(),
+                    );
+                }
+            }
+        }
+
         let corner_diameter = *state.get(&self.corner_diameter);
 
         let horizontal_resize_area = Area {
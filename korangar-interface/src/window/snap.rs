@@ -0,0 +1,111 @@
+//! Snapping of windows to the screen edges and to each other while they are
+//! being moved.
+
+use crate::layout::area::Area;
+
+/// Distance between two ranges on the same axis, zero if they overlap.
+fn gap(start: f32, size: f32, other_start: f32, other_size: f32) -> f32 {
+    (other_start - (start + size)).max(start - (other_start + other_size)).max(0.0)
+}
+
+/// Pick the candidate closest to `current` if it is within `snap_distance`.
+fn closest(current: f32, candidates: impl Iterator<Item = f32>, snap_distance: f32) -> f32 {
+    candidates
+        .map(|candidate| (candidate, (candidate - current).abs()))
+        .filter(|(_, distance)| *distance <= snap_distance)
+        .min_by(|(_, left), (_, right)| left.total_cmp(right))
+        .map(|(candidate, _)| candidate)
+        .unwrap_or(current)
+}
+
+/// Snap the area of a window that is being moved to the edges of the screen
+/// and the edges of the `other` windows. Other windows are only considered on
+/// an axis if they are within `snap_distance` on the opposite axis, so windows
+/// on the other side of the screen don't affect each other.
+///
+/// All values are in screen space. Returns the new top left corner of the
+/// area.
+pub(crate) fn snap_area(area: Area, screen_width: f32, screen_height: f32, other: &[Area], snap_distance: f32) -> (f32, f32) {
+    if snap_distance <= 0.0 {
+        return (area.left, area.top);
+    }
+
+    let horizontal_neighbors = other
+        .iter()
+        .filter(|other| gap(area.top, area.height, other.top, other.height) <= snap_distance);
+    let vertical_neighbors = other
+        .iter()
+        .filter(|other| gap(area.left, area.width, other.left, other.width) <= snap_distance);
+
+    let left_candidates = [0.0, screen_width - area.width]
+        .into_iter()
+        .chain(horizontal_neighbors.flat_map(|other| {
+            [
+                other.left + other.width,
+                other.left - area.width,
+                other.left,
+                other.left + other.width - area.width,
+            ]
+        }));
+    let top_candidates = [0.0, screen_height - area.height]
+        .into_iter()
+        .chain(vertical_neighbors.flat_map(|other| {
+            [
+                other.top + other.height,
+                other.top - area.height,
+                other.top,
+                other.top + other.height - area.height,
+            ]
+        }));
+
+    (
+        closest(area.left, left_candidates, snap_distance),
+        closest(area.top, top_candidates, snap_distance),
+    )
+}
+
+#[cfg(test)]
+mod snapping {
+    use super::snap_area;
+    use crate::layout::area::Area;
+
+    fn area(left: f32, top: f32) -> Area {
+        Area {
+            left,
+            top,
+            width: 100.0,
+            height: 50.0,
+        }
+    }
+
+    #[test]
+    fn snaps_to_screen_edges() {
+        assert_eq!(snap_area(area(6.0, 400.0), 1000.0, 800.0, &[], 10.0), (0.0, 400.0));
+        assert_eq!(snap_area(area(895.0, 744.0), 1000.0, 800.0, &[], 10.0), (900.0, 750.0));
+        assert_eq!(snap_area(area(500.0, 400.0), 1000.0, 800.0, &[], 10.0), (500.0, 400.0));
+    }
+
+    #[test]
+    fn snaps_to_other_windows() {
+        let other = [area(300.0, 300.0)];
+
+        // Right next to the other window and aligned with its top edge.
+        assert_eq!(snap_area(area(405.0, 296.0), 1000.0, 800.0, &other, 10.0), (400.0, 300.0));
+        // Right below the other window and aligned with its left edge.
+        assert_eq!(snap_area(area(303.0, 358.0), 1000.0, 800.0, &other, 10.0), (300.0, 350.0));
+    }
+
+    #[test]
+    fn ignores_distant_windows() {
+        // The other window is far away vertically, so its horizontal edges are
+        // not considered.
+        let other = [area(300.0, 600.0)];
+
+        assert_eq!(snap_area(area(405.0, 100.0), 1000.0, 800.0, &other, 10.0), (405.0, 100.0));
+    }
+
+    #[test]
+    fn disabled_without_distance() {
+        assert_eq!(snap_area(area(3.0, 3.0), 1000.0, 800.0, &[], 0.0), (3.0, 3.0));
+    }
+}
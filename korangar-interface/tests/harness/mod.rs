@@ -0,0 +1,1322 @@
+//! Headless [`Application`] for testing the interface without a window or a
+//! GPU.
+//!
+//! The harness lays out and renders the interface once per simulated frame,
+//! the same way the client does. Instead of drawing anything, the renderer
+//! records every instruction so tests can assert on the resulting layout or
+//! compare it against a snapshot.
+
+#![allow(dead_code)]
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use korangar_interface::application::{self, Application, Clipboard, RenderLayer, TextLayouter, WindowCache};
+use korangar_interface::components::button::ButtonTheme;
+use korangar_interface::components::collapsible::CollapsibleTheme;
+use korangar_interface::components::context_menu::ContextMenuTheme;
+use korangar_interface::components::drop_down::DropDownTheme;
+use korangar_interface::components::field::FieldTheme;
+use korangar_interface::components::number_input::NumberInputTheme;
+use korangar_interface::components::progress_bar::ProgressBarTheme;
+use korangar_interface::components::slider::SliderTheme;
+use korangar_interface::components::state_button::StateButtonTheme;
+use korangar_interface::components::table::{TableRow, TableTheme};
+use korangar_interface::components::tabs::TabsChildren;
+use korangar_interface::components::text::TextTheme;
+use korangar_interface::components::text_box::TextBoxTheme;
+use korangar_interface::element::{Element, ElementDisplay};
+use korangar_interface::event::{ClickHandler, Event, EventQueue, KeyInput, NavigationInput};
+use korangar_interface::layout::alignment::{HorizontalAlignment, VerticalAlignment};
+use korangar_interface::layout::area::Area;
+use korangar_interface::layout::tooltip::TooltipTheme;
+use korangar_interface::layout::{Icon, MouseButton};
+use korangar_interface::markup::{InlineArea, MarkupIterator, MarkupSpan};
+use korangar_interface::theme::ThemePathGetter;
+use korangar_interface::tween::{AnimationTheme, Easing};
+use korangar_interface::window::{Anchor, CustomWindow, WindowTheme};
+use korangar_interface::{Interface, InterfaceFrame};
+use rust_state::{Path, RustState, State};
+
+/// Size of the simulated screen.
+pub const SCREEN_SIZE: Size = Size {
+    width: 1280.0,
+    height: 720.0,
+};
+
+/// Width of every character relative to the font size.
+const CHARACTER_WIDTH: f32 = 0.5;
+
+/// Number of characters that an inline icon takes up.
+const ICON_CHARACTERS: usize = 2;
+
+pub const WINDOW_BACKGROUND_COLOR: Color = Color::monochrome(50);
+pub const BUTTON_BACKGROUND_COLOR: Color = Color::monochrome(120);
+pub const BUTTON_HOVERED_BACKGROUND_COLOR: Color = Color::monochrome(150);
+pub const TEXT_BOX_FOCUSED_BACKGROUND_COLOR: Color = Color::monochrome(110);
+pub const DROP_DOWN_ITEM_BACKGROUND_COLOR: Color = Color::monochrome(80);
+pub const CONTEXT_MENU_DISABLED_FOREGROUND_COLOR: Color = Color::monochrome(130);
+pub const CONTEXT_MENU_HOVERED_BACKGROUND_COLOR: Color = Color::monochrome(105);
+pub const CONTEXT_MENU_SEPARATOR_COLOR: Color = Color::monochrome(90);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Color(pub u32);
+
+impl Color {
+    pub const fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self(u32::from_be_bytes([red, green, blue, alpha]))
+    }
+
+    pub const fn monochrome(brightness: u8) -> Self {
+        Self::rgba(brightness, brightness, brightness, 255)
+    }
+}
+
+impl application::Color for Color {
+    fn multiply_alpha(&self, factor: f32) -> Self {
+        let [red, green, blue, alpha] = self.0.to_be_bytes();
+        Self::rgba(red, green, blue, (alpha as f32 * factor).round() as u8)
+    }
+
+    fn interpolate(&self, other: Self, amount: f32) -> Self {
+        let channels = self.0.to_be_bytes();
+        let other_channels = other.0.to_be_bytes();
+        let [red, green, blue, alpha] = std::array::from_fn(|index| {
+            (channels[index] as f32 + (other_channels[index] as f32 - channels[index] as f32) * amount).round() as u8
+        });
+
+        Self::rgba(red, green, blue, alpha)
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:08x}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CornerDiameter([f32; 4]);
+
+impl CornerDiameter {
+    pub const fn uniform(diameter: f32) -> Self {
+        Self([diameter; 4])
+    }
+}
+
+impl application::CornerDiameter for CornerDiameter {
+    fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        Self([top_left, top_right, bottom_right, bottom_left])
+    }
+
+    fn scaled(&self, scaling: f32) -> Self {
+        Self(self.0.map(|diameter| diameter * scaling))
+    }
+
+    fn top_left(&self) -> f32 {
+        self.0[0]
+    }
+
+    fn top_right(&self) -> f32 {
+        self.0[1]
+    }
+
+    fn bottom_right(&self) -> f32 {
+        self.0[2]
+    }
+
+    fn bottom_left(&self) -> f32 {
+        self.0[3]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontSize(pub f32);
+
+impl application::FontSize for FontSize {
+    fn scaled(&self, scaling: f32) -> Self {
+        Self(self.0 * scaling)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub left: f32,
+    pub top: f32,
+}
+
+impl application::Position for Position {
+    fn new(left: f32, top: f32) -> Self {
+        Self { left, top }
+    }
+
+    fn left(&self) -> f32 {
+        self.left
+    }
+
+    fn top(&self) -> f32 {
+        self.top
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({:.1}, {:.1})", self.left, self.top)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl application::Size for Size {
+    fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+
+    fn width(&self) -> f32 {
+        self.width
+    }
+
+    fn height(&self) -> f32 {
+        self.height
+    }
+}
+
+impl Display for Size {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1}x{:.1}", self.width, self.height)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clip {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl application::Clip for Clip {
+    fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self { left, top, right, bottom }
+    }
+
+    fn unbound() -> Self {
+        Self {
+            left: f32::MIN,
+            top: f32::MIN,
+            right: f32::MAX,
+            bottom: f32::MAX,
+        }
+    }
+
+    fn left(&self) -> f32 {
+        self.left
+    }
+
+    fn right(&self) -> f32 {
+        self.right
+    }
+
+    fn top(&self) -> f32 {
+        self.top
+    }
+
+    fn bottom(&self) -> f32 {
+        self.bottom
+    }
+}
+
+impl Display for Clip {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if *self == <Self as application::Clip>::unbound() {
+            return write!(f, "unbound");
+        }
+
+        write!(f, "[{:.1}, {:.1}, {:.1}, {:.1}]", self.left, self.top, self.right, self.bottom)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShadowPadding(pub f32);
+
+impl application::ShadowPadding for ShadowPadding {
+    fn none() -> Self {
+        Self(0.0)
+    }
+
+    fn scaled(&self, scaling: f32) -> Self {
+        Self(self.0 * scaling)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum OverflowBehavior {
+    Shrink,
+    LineBreak,
+}
+
+// The `theme-element` feature requires all theme values to be displayable in
+// the state inspector.
+
+impl ElementDisplay for Color {
+    fn element_display(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ElementDisplay for CornerDiameter {
+    fn element_display(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+impl ElementDisplay for FontSize {
+    fn element_display(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl ElementDisplay for Size {
+    fn element_display(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ElementDisplay for OverflowBehavior {
+    fn element_display(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+/// Event emitted by the test windows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestEvent {
+    Pressed(&'static str),
+    LinkClicked(String),
+}
+
+impl From<TestEvent> for Event<TestApp> {
+    fn from(custom_event: TestEvent) -> Self {
+        Event::Application { custom_event }
+    }
+}
+
+impl ClickHandler<TestApp> for TestEvent {
+    fn handle_click(&self, _: &State<TestApp>, queue: &mut EventQueue<TestApp>) {
+        queue.queue(self.clone());
+    }
+}
+
+/// Window cache that doesn't remember anything, so every test starts with the
+/// same window positions.
+pub struct VolatileWindowCache;
+
+impl WindowCache<TestApp> for VolatileWindowCache {
+    fn create() -> Self {
+        Self
+    }
+
+    fn get_window_state(&self, _: ()) -> Option<(Anchor<TestApp>, Size)> {
+        None
+    }
+
+    fn register_window(&mut self, _: (), _: Anchor<TestApp>, _: Size) {}
+
+    fn update_anchor(&mut self, _: (), _: Anchor<TestApp>) {}
+
+    fn update_size(&mut self, _: (), _: Size) {}
+}
+
+#[derive(Default)]
+pub struct TestClipboard {
+    text: Option<String>,
+}
+
+impl Clipboard for TestClipboard {
+    fn create() -> Self {
+        Self::default()
+    }
+
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = Some(text);
+    }
+}
+
+/// Text layouter where every character has the same width, so text
+/// dimensions only depend on the number of characters.
+#[derive(Clone)]
+pub struct FixedWidthTextLayouter;
+
+impl FixedWidthTextLayouter {
+    fn character_count(span: &MarkupSpan) -> usize {
+        match span {
+            MarkupSpan::Text { text, .. } => text.chars().count(),
+            MarkupSpan::Icon { .. } => ICON_CHARACTERS,
+        }
+    }
+}
+
+impl TextLayouter<TestApp> for FixedWidthTextLayouter {
+    fn get_text_dimensions(
+        &self,
+        text: &str,
+        _default_color: Color,
+        _highlight_color: Color,
+        font_size: FontSize,
+        available_width: f32,
+        overflow_behavior: OverflowBehavior,
+    ) -> (Size, FontSize) {
+        let character_width = font_size.0 * CHARACTER_WIDTH;
+        let character_count = MarkupIterator::new(text).map(|span| Self::character_count(&span)).sum::<usize>() as f32;
+        let width = character_count * character_width;
+
+        if width <= available_width || available_width <= 0.0 {
+            return (
+                Size {
+                    width,
+                    height: font_size.0,
+                },
+                font_size,
+            );
+        }
+
+        match overflow_behavior {
+            OverflowBehavior::Shrink => {
+                let font_size = FontSize(font_size.0 * available_width / width);
+                let size = Size {
+                    width: available_width,
+                    height: font_size.0,
+                };
+
+                (size, font_size)
+            }
+            OverflowBehavior::LineBreak => {
+                let characters_per_line = (available_width / character_width).floor().max(1.0);
+                let size = Size {
+                    width: characters_per_line * character_width,
+                    height: (character_count / characters_per_line).ceil() * font_size.0,
+                };
+
+                (size, font_size)
+            }
+        }
+    }
+
+    /// Only supports text that fits on a single line.
+    fn get_inline_areas(
+        &self,
+        text: &str,
+        default_color: Color,
+        highlight_color: Color,
+        font_size: FontSize,
+        available_width: f32,
+        overflow_behavior: OverflowBehavior,
+    ) -> Vec<InlineArea> {
+        let (_, font_size) = self.get_text_dimensions(
+            text,
+            default_color,
+            highlight_color,
+            font_size,
+            available_width,
+            overflow_behavior,
+        );
+        let character_width = font_size.0 * CHARACTER_WIDTH;
+
+        let mut inline_areas = Vec::new();
+        let mut left = 0.0;
+
+        for span in MarkupIterator::new(text) {
+            let width = Self::character_count(&span) as f32 * character_width;
+
+            if span.is_inline() {
+                inline_areas.push(InlineArea {
+                    index: inline_areas.len(),
+                    area: Area {
+                        left,
+                        top: 0.0,
+                        width,
+                        height: font_size.0,
+                    },
+                });
+            }
+
+            left += width;
+        }
+
+        inline_areas
+    }
+}
+
+/// A single call to the [`RecordingRenderer`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instruction {
+    Rectangle {
+        position: Position,
+        size: Size,
+        clip: Clip,
+        color: Color,
+    },
+    Text {
+        text: String,
+        position: Position,
+        clip: Clip,
+        color: Color,
+        font_size: FontSize,
+    },
+    Icon {
+        icon: String,
+        position: Position,
+        size: Size,
+        clip: Clip,
+        color: Color,
+    },
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Rectangle {
+                position,
+                size,
+                clip,
+                color,
+            } => write!(f, "rectangle {position} {size} {color} clip {clip}"),
+            Instruction::Text {
+                text,
+                position,
+                clip,
+                color,
+                font_size,
+            } => write!(f, "text {text:?} {position} {:.1}pt {color} clip {clip}", font_size.0),
+            Instruction::Icon {
+                icon,
+                position,
+                size,
+                clip,
+                color,
+            } => write!(f, "icon {icon} {position} {size} {color} clip {clip}"),
+        }
+    }
+}
+
+/// Renderer that records all instructions instead of drawing them.
+#[derive(Default)]
+pub struct RecordingRenderer {
+    instructions: RefCell<Vec<Instruction>>,
+}
+
+impl RecordingRenderer {
+    fn clear(&self) {
+        self.instructions.borrow_mut().clear();
+    }
+}
+
+impl RenderLayer<TestApp> for RecordingRenderer {
+    type CustomIcon = ();
+    type CustomInstruction<'a> = ();
+
+    fn render_rectangle(
+        &self,
+        position: Position,
+        size: Size,
+        clip: Clip,
+        _corner_diameter: CornerDiameter,
+        color: Color,
+        _shadow_color: Color,
+        _shadow_padding: ShadowPadding,
+    ) {
+        self.instructions.borrow_mut().push(Instruction::Rectangle {
+            position,
+            size,
+            clip,
+            color,
+        });
+    }
+
+    fn render_text(
+        &self,
+        text: &str,
+        position: Position,
+        _available_width: f32,
+        clip: Clip,
+        color: Color,
+        _highlight_color: Color,
+        font_size: FontSize,
+    ) {
+        self.instructions.borrow_mut().push(Instruction::Text {
+            text: text.to_owned(),
+            position,
+            clip,
+            color,
+            font_size,
+        });
+    }
+
+    fn render_icon(&self, position: Position, size: Size, clip: Clip, icon: Icon<TestApp>, color: Color) {
+        let icon = match icon {
+            Icon::ArrowLeft => "arrow-left".to_owned(),
+            Icon::ArrowRight => "arrow-right".to_owned(),
+            Icon::ExpandArrow { expanded } => format!("expand-arrow(expanded: {expanded})"),
+            Icon::Checkbox { checked } => format!("checkbox(checked: {checked})"),
+            Icon::Eye { open } => format!("eye(open: {open})"),
+            Icon::TrashCan => "trash-can".to_owned(),
+            Icon::Custom { .. } => "custom".to_owned(),
+        };
+
+        self.instructions.borrow_mut().push(Instruction::Icon {
+            icon,
+            position,
+            size,
+            clip,
+            color,
+        });
+    }
+
+    fn render_custom(&self, _: (), _: &[Clip]) {}
+}
+
+#[derive(RustState)]
+pub struct TestTheme {
+    pub window: WindowTheme<TestApp>,
+    pub text: TextTheme<TestApp>,
+    pub button: ButtonTheme<TestApp>,
+    pub state_button: StateButtonTheme<TestApp>,
+    pub text_box: TextBoxTheme<TestApp>,
+    pub collapsible: CollapsibleTheme<TestApp>,
+    pub drop_down: DropDownTheme<TestApp>,
+    pub field: FieldTheme<TestApp>,
+    pub slider: SliderTheme<TestApp>,
+    pub progress_bar: ProgressBarTheme<TestApp>,
+    pub number_input: NumberInputTheme<TestApp>,
+    pub table: TableTheme<TestApp>,
+    pub context_menu: ContextMenuTheme<TestApp>,
+    pub tooltip: TooltipTheme<TestApp>,
+    pub animation: AnimationTheme,
+}
+
+impl Default for TestTheme {
+    fn default() -> Self {
+        // Mostly the same values as the in-game theme of the client, but without
+        // shadows.
+        let shadow_color = Color::rgba(0, 0, 0, 0);
+        let shadow_padding = ShadowPadding(0.0);
+        let highlight_color = Color::rgba(255, 160, 60, 255);
+
+        Self {
+            window: WindowTheme {
+                title_color: Color::rgba(185, 155, 155, 255),
+                hovered_title_color: Color::rgba(240, 155, 155, 255),
+                background_color: WINDOW_BACKGROUND_COLOR,
+                highlight_color,
+                shadow_color,
+                shadow_padding,
+                gaps: 8.0,
+                border: 10.0,
+                corner_diameter: CornerDiameter::uniform(14.0),
+                close_button_size: Size { width: 40.0, height: 18.0 },
+                close_button_corner_diameter: CornerDiameter::uniform(12.0),
+                minimum_width: 300.0,
+                maximum_width: 600.0,
+                minimum_height: 40.0,
+                maximum_height: 700.0,
+                title_height: 25.0,
+                title_gap: 2.0,
+                font_size: FontSize(15.0),
+                horizontal_alignment: HorizontalAlignment::Center { offset: 0.0, border: 5.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+                anchor_color: Color::rgba(140, 105, 130, 255),
+                closest_anchor_color: Color::rgba(255, 175, 30, 255),
+                focus_ring_color: Color::rgba(255, 175, 30, 255),
+                focus_ring_width: 1.5,
+                snap_distance: 10.0,
+                active_tab_color: Color::rgba(255, 175, 30, 60),
+            },
+            text: TextTheme {
+                color: Color::monochrome(220),
+                highlight_color,
+                height: 14.0,
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 3.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::LineBreak,
+            },
+            button: ButtonTheme {
+                foreground_color: Color::monochrome(220),
+                background_color: BUTTON_BACKGROUND_COLOR,
+                highlight_color,
+                hovered_foreground_color: Color::monochrome(250),
+                hovered_background_color: BUTTON_HOVERED_BACKGROUND_COLOR,
+                disabled_foreground_color: Color::monochrome(160),
+                disabled_background_color: Color::monochrome(100),
+                shadow_color,
+                shadow_padding,
+                height: 20.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Center { offset: 0.0, border: 5.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            state_button: StateButtonTheme {
+                foreground_color: Color::monochrome(220),
+                background_color: Color::monochrome(120),
+                highlight_color,
+                hovered_foreground_color: Color::monochrome(250),
+                hovered_background_color: Color::monochrome(150),
+                disabled_foreground_color: Color::monochrome(120),
+                disabled_background_color: Color::monochrome(80),
+                checkbox_color: Color::monochrome(200),
+                hovered_checkbox_color: Color::monochrome(250),
+                disabled_checkbox_color: Color::monochrome(120),
+                shadow_color,
+                shadow_padding,
+                height: 20.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 30.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            text_box: TextBoxTheme {
+                foreground_color: Color::monochrome(220),
+                background_color: Color::monochrome(40),
+                highlight_color,
+                hovered_foreground_color: Color::monochrome(250),
+                hovered_background_color: Color::monochrome(60),
+                focused_foreground_color: Color::monochrome(255),
+                focused_background_color: TEXT_BOX_FOCUSED_BACKGROUND_COLOR,
+                ghost_foreground_color: Color::monochrome(130),
+                hide_icon_color: Color::monochrome(180),
+                hovered_hide_icon_color: Color::rgba(250, 200, 200, 255),
+                caret_color: Color::monochrome(255),
+                selection_color: Color::rgba(255, 160, 60, 100),
+                shadow_color,
+                shadow_padding,
+                height: 20.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 15.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::LineBreak,
+            },
+            collapsible: CollapsibleTheme {
+                foreground_color: Color::monochrome(170),
+                highlight_color,
+                hovered_foreground_color: Color::rgba(250, 200, 200, 255),
+                background_color: Color::monochrome(75),
+                secondary_background_color: Color::monochrome(55),
+                icon_color: Color::monochrome(120),
+                icon_size: 10.0,
+                shadow_color,
+                shadow_padding,
+                gaps: 4.0,
+                border: 5.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                title_height: 20.0,
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 0.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            drop_down: DropDownTheme {
+                item_foreground_color: Color::monochrome(180),
+                item_background_color: DROP_DOWN_ITEM_BACKGROUND_COLOR,
+                item_highlight_color: highlight_color,
+                item_hovered_foreground_color: Color::monochrome(220),
+                item_hovered_background_color: Color::monochrome(120),
+                item_shadow_color: shadow_color,
+                item_shadow_padding: shadow_padding,
+                item_height: 20.0,
+                item_corner_diameter: CornerDiameter::uniform(10.0),
+                item_font_size: FontSize(14.0),
+                item_horizontal_alignment: HorizontalAlignment::Center { offset: 0.0, border: 5.0 },
+                item_vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                item_overflow_behavior: OverflowBehavior::Shrink,
+                list_corner_diameter: CornerDiameter::uniform(8.0),
+                list_background_color: Color::monochrome(40),
+                list_shadow_color: shadow_color,
+                list_shadow_padding: shadow_padding,
+                list_gaps: 4.0,
+                list_border: 4.0,
+                // Low enough for the list of the drop down tests to scroll.
+                list_maximum_height: 100.0,
+                button_foreground_color: Color::monochrome(220),
+                button_background_color: Color::monochrome(120),
+                button_highlight_color: highlight_color,
+                button_hovered_foreground_color: Color::monochrome(250),
+                button_hovered_background_color: Color::monochrome(150),
+                button_shadow_color: shadow_color,
+                button_shadow_padding: shadow_padding,
+                button_height: 20.0,
+                button_corner_diameter: CornerDiameter::uniform(10.0),
+                button_font_size: FontSize(14.0),
+                button_horizontal_alignment: HorizontalAlignment::Center { offset: 0.0, border: 5.0 },
+                button_vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                button_overflow_behavior: OverflowBehavior::Shrink,
+            },
+            field: FieldTheme {
+                foreground_color: Color::monochrome(220),
+                background_color: Color::monochrome(100),
+                highlight_color,
+                shadow_color,
+                shadow_padding,
+                height: 20.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 10.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::LineBreak,
+            },
+            slider: SliderTheme {
+                foreground_color: Color::monochrome(220),
+                background_color: Color::monochrome(100),
+                highlight_color,
+                hovered_background_color: Color::monochrome(130),
+                rail_color: Color::monochrome(60),
+                filled_rail_color: highlight_color,
+                knob_color: Color::monochrome(200),
+                hovered_knob_color: Color::monochrome(250),
+                shadow_color,
+                shadow_padding,
+                height: 20.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                rail_height: 4.0,
+                knob_width: 10.0,
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 10.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            progress_bar: ProgressBarTheme {
+                foreground_color: Color::monochrome(220),
+                background_color: Color::monochrome(60),
+                highlight_color,
+                bar_color: highlight_color,
+                shadow_color,
+                shadow_padding,
+                height: 20.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                border: 3.0,
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Center { offset: 0.0, border: 5.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            number_input: NumberInputTheme {
+                foreground_color: Color::monochrome(220),
+                background_color: Color::monochrome(100),
+                highlight_color,
+                hovered_background_color: Color::monochrome(130),
+                button_foreground_color: Color::monochrome(220),
+                button_background_color: Color::monochrome(120),
+                hovered_button_background_color: Color::monochrome(150),
+                shadow_color,
+                shadow_padding,
+                height: 20.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 10.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            table: TableTheme {
+                foreground_color: Color::monochrome(220),
+                background_color: Color::monochrome(60),
+                highlight_color,
+                header_foreground_color: Color::monochrome(240),
+                header_background_color: Color::monochrome(100),
+                hovered_header_background_color: Color::monochrome(130),
+                row_background_color: Color::monochrome(70),
+                alternate_row_background_color: Color::monochrome(80),
+                hovered_row_background_color: Color::monochrome(120),
+                separator_color: Color::monochrome(50),
+                icon_color: Color::monochrome(240),
+                shadow_color,
+                shadow_padding,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                header_height: 20.0,
+                row_height: 18.0,
+                maximum_height: 400.0,
+                minimum_column_width: 30.0,
+                icon_size: 10.0,
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 5.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            context_menu: ContextMenuTheme {
+                foreground_color: Color::monochrome(220),
+                disabled_foreground_color: CONTEXT_MENU_DISABLED_FOREGROUND_COLOR,
+                highlight_color,
+                background_color: Color::monochrome(60),
+                hovered_background_color: CONTEXT_MENU_HOVERED_BACKGROUND_COLOR,
+                separator_color: CONTEXT_MENU_SEPARATOR_COLOR,
+                shadow_color,
+                shadow_padding,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                item_corner_diameter: CornerDiameter::uniform(8.0),
+                width: 150.0,
+                border: 4.0,
+                item_height: 20.0,
+                separator_height: 7.0,
+                icon_size: 10.0,
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 5.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: 0.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            tooltip: TooltipTheme {
+                background_color: Color::rgba(15, 15, 15, 200),
+                foreground_color: Color::monochrome(235),
+                highlight_color,
+                shadow_color,
+                shadow_padding,
+                font_size: FontSize(14.0),
+                overflow_behavior: OverflowBehavior::LineBreak,
+                corner_diameter: CornerDiameter::uniform(5.0),
+                border: 4.0,
+                gap: 3.0,
+                mouse_offset: 16.0,
+            },
+            // Animations are disabled so every frame is deterministic. Tests that
+            // cover animations enable them explicitly.
+            animation: AnimationTheme {
+                easing: Easing::Linear,
+                window_duration: 0.0,
+                window_offset: 0.0,
+                collapsible_duration: 0.0,
+                tooltip_duration: 0.0,
+                hover_duration: 0.0,
+            },
+        }
+    }
+}
+
+/// Number of rows in the test table, enough for most of them to be outside
+/// of the visible part of the table.
+pub const TABLE_ROW_COUNT: usize = 1000;
+
+/// Row of the test table.
+#[derive(Clone)]
+pub struct TestRow {
+    pub name: String,
+    pub value: u32,
+}
+
+impl TableRow<TestApp> for TestRow {
+    fn cell(&self, column: usize) -> Cow<'_, str> {
+        match column {
+            0 => Cow::Borrowed(&self.name),
+            _ => Cow::Owned(self.value.to_string()),
+        }
+    }
+
+    fn compare(&self, other: &Self, column: usize) -> Ordering {
+        match column {
+            0 => self.name.cmp(&other.name),
+            _ => self.value.cmp(&other.value),
+        }
+    }
+}
+
+/// Root of the test state. Besides the theme, this contains a few values for
+/// the test windows to display and modify.
+#[derive(RustState)]
+#[state_root]
+pub struct TestApp {
+    pub theme: TestTheme,
+    pub selected_tab: usize,
+    pub selected_option: String,
+    pub options: Vec<String>,
+    pub text: String,
+    pub table_rows: Vec<TestRow>,
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        let options: Vec<String> = ["Apple", "Banana", "Cherry", "Date", "Elderberry", "Fig", "Grape"]
+            .into_iter()
+            .map(str::to_owned)
+            .collect();
+
+        Self {
+            theme: TestTheme::default(),
+            selected_tab: 0,
+            selected_option: options[0].clone(),
+            options,
+            text: String::new(),
+            // Shuffle the values so the rows are in a different order when sorted by
+            // value.
+            table_rows: (0..TABLE_ROW_COUNT as u32)
+                .map(|index| TestRow {
+                    name: format!("Row {index:04}"),
+                    value: index * 7919 % TABLE_ROW_COUNT as u32,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct TestThemeGetter;
+
+impl ThemePathGetter<TestApp> for TestThemeGetter {
+    fn new() -> Self {
+        Self
+    }
+
+    fn window(self) -> impl Path<TestApp, WindowTheme<TestApp>> {
+        TestApp::path().theme().window()
+    }
+
+    fn text(self) -> impl Path<TestApp, TextTheme<TestApp>> {
+        TestApp::path().theme().text()
+    }
+
+    fn button(self) -> impl Path<TestApp, ButtonTheme<TestApp>> {
+        TestApp::path().theme().button()
+    }
+
+    fn state_button(self) -> impl Path<TestApp, StateButtonTheme<TestApp>> {
+        TestApp::path().theme().state_button()
+    }
+
+    fn text_box(self) -> impl Path<TestApp, TextBoxTheme<TestApp>> {
+        TestApp::path().theme().text_box()
+    }
+
+    fn collapsible(self) -> impl Path<TestApp, CollapsibleTheme<TestApp>> {
+        TestApp::path().theme().collapsible()
+    }
+
+    fn drop_down(self) -> impl Path<TestApp, DropDownTheme<TestApp>> {
+        TestApp::path().theme().drop_down()
+    }
+
+    fn field(self) -> impl Path<TestApp, FieldTheme<TestApp>> {
+        TestApp::path().theme().field()
+    }
+
+    fn slider(self) -> impl Path<TestApp, SliderTheme<TestApp>> {
+        TestApp::path().theme().slider()
+    }
+
+    fn progress_bar(self) -> impl Path<TestApp, ProgressBarTheme<TestApp>> {
+        TestApp::path().theme().progress_bar()
+    }
+
+    fn number_input(self) -> impl Path<TestApp, NumberInputTheme<TestApp>> {
+        TestApp::path().theme().number_input()
+    }
+
+    fn table(self) -> impl Path<TestApp, TableTheme<TestApp>> {
+        TestApp::path().theme().table()
+    }
+
+    fn context_menu(self) -> impl Path<TestApp, ContextMenuTheme<TestApp>> {
+        TestApp::path().theme().context_menu()
+    }
+
+    fn tooltip(self) -> impl Path<TestApp, TooltipTheme<TestApp>> {
+        TestApp::path().theme().tooltip()
+    }
+
+    fn animation(self) -> impl Path<TestApp, AnimationTheme> {
+        TestApp::path().theme().animation()
+    }
+}
+
+impl Application for TestApp {
+    type Cache = VolatileWindowCache;
+    type Clip = Clip;
+    type Clipboard = TestClipboard;
+    type Color = Color;
+    type CornerDiameter = CornerDiameter;
+    type CustomEvent = TestEvent;
+    type CustomMouseMode = ();
+    type FontSize = FontSize;
+    type OverflowBehavior = OverflowBehavior;
+    type Position = Position;
+    type Renderer = RecordingRenderer;
+    type ShadowPadding = ShadowPadding;
+    type Size = Size;
+    type TextLayouter = FixedWidthTextLayouter;
+    type ThemeGetter = TestThemeGetter;
+    type ThemeType = ();
+    type WindowClass = ();
+
+    fn set_current_theme_type(_: ()) {}
+}
+
+/// Static list of tabs that all have the same element type.
+pub struct TabList<T> {
+    tabs: Vec<T>,
+}
+
+impl<T> TabList<T> {
+    pub fn new(tabs: Vec<T>) -> Self {
+        Self { tabs }
+    }
+}
+
+impl<T> TabsChildren<TestApp> for TabList<T>
+where
+    T: Element<TestApp>,
+{
+    type Key = usize;
+    type LayoutInfo = T::LayoutInfo;
+
+    fn get_tab(&self, key: &usize) -> &impl Element<TestApp, LayoutInfo = Self::LayoutInfo> {
+        &self.tabs[*key]
+    }
+
+    fn get_tab_mut(&mut self, key: &usize) -> &mut impl Element<TestApp, LayoutInfo = Self::LayoutInfo> {
+        &mut self.tabs[*key]
+    }
+}
+
+/// Drives an [`Interface`] the same way the client does, one frame at a
+/// time.
+pub struct Harness {
+    pub state: State<TestApp>,
+    interface: Interface<'static, TestApp>,
+    renderer: RecordingRenderer,
+    mouse_position: Position,
+    events: Vec<TestEvent>,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        Self {
+            state: State::new(TestApp::default()),
+            interface: Interface::new(FixedWidthTextLayouter, SCREEN_SIZE),
+            renderer: RecordingRenderer::default(),
+            // Start outside of any window so nothing is hovered.
+            mouse_position: Position { left: 0.0, top: 0.0 },
+            events: Vec::new(),
+        }
+    }
+
+    /// Open a window and render the first frame.
+    pub fn open_window(&mut self, window: impl CustomWindow<TestApp> + 'static) {
+        self.interface.open_window(window);
+        self.idle();
+    }
+
+    /// Lay out, run `input`, and render a single frame. Events and state
+    /// updates are processed at the end of the frame.
+    pub fn frame(&mut self, input: impl FnOnce(&mut InterfaceFrame<'_, TestApp>, &State<TestApp>)) {
+        self.renderer.clear();
+
+        let mut interface_frame = self.interface.lay_out_windows(&self.state, 1.0, self.mouse_position);
+
+        input(&mut interface_frame, &self.state);
+
+        let tooltip_theme = self.state.follow(TestApp::path().theme().tooltip());
+        interface_frame.render(&self.state, &self.renderer, tooltip_theme, self.mouse_position);
+
+        drop(interface_frame);
+
+        self.interface.process_events(&mut self.events);
+
+        assert!(self.state.apply().is_ok(), "failed to apply state updates");
+    }
+
+    /// Render a frame without any input.
+    pub fn idle(&mut self) {
+        self.frame(|_, _| {});
+    }
+
+    pub fn move_mouse(&mut self, position: Position) {
+        self.mouse_position = position;
+        self.idle();
+    }
+
+    pub fn click(&mut self, mouse_button: MouseButton) {
+        self.frame(|interface_frame, state| interface_frame.click(state, mouse_button));
+    }
+
+    /// Move the mouse onto the last rendered occurrence of `text` and click
+    /// it.
+    pub fn click_text(&mut self, text: &str) {
+        let position = self
+            .find_text(text)
+            .unwrap_or_else(|| panic!("no text {text:?} was rendered in the last frame"));
+
+        self.move_mouse(Position {
+            left: position.left + 1.0,
+            top: position.top + 1.0,
+        });
+        self.click(MouseButton::Left);
+    }
+
+    /// Press the left mouse button, move the mouse by `delta` and release
+    /// the button again, like the client does when dragging windows.
+    pub fn drag(&mut self, delta: Size) {
+        self.click(MouseButton::Left);
+        self.interface.handle_drag(delta, 1.0);
+        self.mouse_position = Position {
+            left: self.mouse_position.left + delta.width,
+            top: self.mouse_position.top + delta.height,
+        };
+        self.frame(|interface_frame, state| interface_frame.drop(state));
+    }
+
+    pub fn set_reduce_motion(&mut self, reduce_motion: bool) {
+        self.interface.set_reduce_motion(reduce_motion);
+    }
+
+    /// Change the size of the screen and render a frame.
+    pub fn resize_screen(&mut self, screen_size: Size) {
+        self.interface.update_window_size(screen_size);
+        self.idle();
+    }
+
+    pub fn scroll(&mut self, delta: f32) {
+        self.frame(|interface_frame, state| interface_frame.scroll(state, delta));
+    }
+
+    pub fn input_characters(&mut self, characters: &[char]) {
+        self.frame(|interface_frame, state| {
+            interface_frame.input_characters(state, characters);
+        });
+    }
+
+    pub fn input_keys(&mut self, keys: &[KeyInput]) {
+        self.frame(|interface_frame, state| {
+            interface_frame.input_keys(state, keys);
+        });
+    }
+
+    pub fn navigate(&mut self, input: NavigationInput) {
+        self.frame(|interface_frame, state| {
+            interface_frame.navigate(state, input);
+        });
+    }
+
+    /// Application events emitted since the last call.
+    pub fn take_events(&mut self) -> Vec<TestEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Instructions recorded in the last frame.
+    pub fn instructions(&self) -> Vec<Instruction> {
+        self.renderer.instructions.borrow().clone()
+    }
+
+    /// Position of the last rendered occurrence of `text`. Overlays are
+    /// rendered after windows, so this prefers text inside overlays.
+    pub fn find_text(&self, text: &str) -> Option<Position> {
+        self.renderer
+            .instructions
+            .borrow()
+            .iter()
+            .rev()
+            .find_map(|instruction| match instruction {
+                Instruction::Text {
+                    text: rendered, position, ..
+                } if rendered == text => Some(*position),
+                _ => None,
+            })
+    }
+
+    /// Position and size of all rectangles with the given color in the last
+    /// frame, in the order they were rendered.
+    pub fn rectangles_with_color(&self, color: Color) -> Vec<(Position, Size)> {
+        self.renderer
+            .instructions
+            .borrow()
+            .iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::Rectangle {
+                    position,
+                    size,
+                    color: rectangle_color,
+                    ..
+                } if *rectangle_color == color => Some((*position, *size)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Textual representation of the last frame, one instruction per line.
+    pub fn snapshot(&self) -> String {
+        self.renderer
+            .instructions
+            .borrow()
+            .iter()
+            .map(|instruction| format!("{instruction}\n"))
+            .collect()
+    }
+}
+
+/// Compare the last frame of the harness against the snapshot file with the
+/// given name.
+///
+/// Snapshots are stored in `tests/snapshots` and are only written when the
+/// `UPDATE_SNAPSHOTS` environment variable is set. Otherwise a missing
+/// snapshot fails the test, so that a clean checkout can't silently accept
+/// whatever is rendered. New or changed snapshots should always be reviewed
+/// before committing them.
+pub fn assert_snapshot(harness: &Harness, name: &str) {
+    let snapshot = harness.snapshot();
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{name}.snap"));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).expect("failed to create snapshot directory");
+        std::fs::write(&path, snapshot).expect("failed to write snapshot");
+        return;
+    }
+
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        panic!(
+            "snapshot {name} is missing at {}, run with UPDATE_SNAPSHOTS=1 to record it",
+            path.display()
+        );
+    };
+
+    if let Some((line, (expected, actual))) = expected
+        .lines()
+        .zip(snapshot.lines())
+        .enumerate()
+        .find(|(_, (expected, actual))| expected != actual)
+    {
+        panic!(
+            "snapshot {name} differs in line {}\nexpected: {expected}\n  actual: {actual}\nrun with UPDATE_SNAPSHOTS=1 to accept the \
+             changes",
+            line + 1,
+        );
+    }
+
+    assert_eq!(
+        expected.lines().count(),
+        snapshot.lines().count(),
+        "snapshot {name} has a different number of instructions, run with UPDATE_SNAPSHOTS=1 to accept the changes",
+    );
+}
@@ -0,0 +1,753 @@
+//! Layout and interaction tests of the components, run against the headless
+//! [`harness`].
+
+mod harness;
+
+use harness::{
+    BUTTON_BACKGROUND_COLOR, BUTTON_HOVERED_BACKGROUND_COLOR, CONTEXT_MENU_DISABLED_FOREGROUND_COLOR,
+    CONTEXT_MENU_HOVERED_BACKGROUND_COLOR, CONTEXT_MENU_SEPARATOR_COLOR, DROP_DOWN_ITEM_BACKGROUND_COLOR, Harness, Instruction, Position,
+    SCREEN_SIZE, Size, TABLE_ROW_COUNT, TEXT_BOX_FOCUSED_BACKGROUND_COLOR, TabList, TestApp, TestAppPathExt, TestEvent,
+    WINDOW_BACKGROUND_COLOR, assert_snapshot,
+};
+use korangar_interface::components::context_menu::ContextMenu;
+use korangar_interface::components::rich_text::MarkupHandler;
+use korangar_interface::components::table::TableColumn;
+use korangar_interface::event::{EditKey, Event, EventQueue, FocusDirection, KeyInput, NavigationInput};
+use korangar_interface::layout::area::Area;
+use korangar_interface::layout::{Icon, MouseButton, WindowLayout};
+use korangar_interface::markup::Link;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::State;
+
+struct SplitWindow;
+
+impl CustomWindow<TestApp> for SplitWindow {
+    fn to_window<'a>(self) -> impl Window<TestApp> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Split",
+            theme: (),
+            elements: (
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "Left",
+                            event: TestEvent::Pressed("Left"),
+                        },
+                        button! {
+                            text: "Middle",
+                            event: TestEvent::Pressed("Middle"),
+                        },
+                        button! {
+                            text: "Right",
+                            event: TestEvent::Pressed("Right"),
+                        },
+                    ),
+                },
+            ),
+        }
+    }
+}
+
+struct TabsWindow;
+
+impl CustomWindow<TestApp> for TabsWindow {
+    fn to_window<'a>(self) -> impl Window<TestApp> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Tabs",
+            theme: (),
+            elements: (
+                button! {
+                    text: "Next tab",
+                    event: |state: &State<TestApp>, _: &mut EventQueue<TestApp>| {
+                        let next_tab = (*state.get(&TestApp::path().selected_tab()) + 1) % 2;
+                        state.update_value(TestApp::path().selected_tab(), next_tab);
+                    },
+                },
+                tabs! {
+                    selected_tab: TestApp::path().selected_tab(),
+                    children: TabList::new(vec![
+                        text! {
+                            text: "Content of the first tab",
+                        },
+                        text! {
+                            text: "Content of the second tab",
+                        },
+                    ]),
+                },
+            ),
+        }
+    }
+}
+
+struct DropDownWindow;
+
+impl CustomWindow<TestApp> for DropDownWindow {
+    fn to_window<'a>(self) -> impl Window<TestApp> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Drop down",
+            theme: (),
+            elements: (
+                drop_down! {
+                    selected: TestApp::path().selected_option(),
+                    options: TestApp::path().options(),
+                },
+            ),
+        }
+    }
+}
+
+struct TextBoxWindow;
+
+impl CustomWindow<TestApp> for TextBoxWindow {
+    fn to_window<'a>(self) -> impl Window<TestApp> + 'a {
+        use korangar_interface::components::text_box::DefaultHandler;
+        use korangar_interface::prelude::*;
+
+        struct NameTextBox;
+
+        window! {
+            title: "Text box",
+            theme: (),
+            elements: (
+                text_box! {
+                    ghost_text: "Name",
+                    state: TestApp::path().text(),
+                    input_handler: DefaultHandler::<_, _, 16>::new(TestApp::path().text(), TestEvent::Pressed("Submit")),
+                    focus_id: NameTextBox,
+                },
+            ),
+        }
+    }
+}
+
+fn item_menu() -> ContextMenu<TestApp> {
+    ContextMenu::new()
+        .item("Equip", TestEvent::Pressed("Equip"))
+        .disabled_item("Drop")
+        .separator()
+        .submenu(
+            "Move to",
+            ContextMenu::new()
+                .item("Cart", TestEvent::Pressed("Cart"))
+                .item("Storage", TestEvent::Pressed("Storage")),
+        )
+}
+
+struct ContextMenuWindow;
+
+impl CustomWindow<TestApp> for ContextMenuWindow {
+    fn to_window<'a>(self) -> impl Window<TestApp> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Context menu",
+            theme: (),
+            elements: (
+                button! {
+                    text: "Options",
+                    event: |_: &State<TestApp>, queue: &mut EventQueue<TestApp>| {
+                        queue.queue(Event::OpenContextMenu { menu: item_menu() });
+                    },
+                },
+            ),
+        }
+    }
+}
+
+struct TableWindow;
+
+impl CustomWindow<TestApp> for TableWindow {
+    fn to_window<'a>(self) -> impl Window<TestApp> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Table",
+            theme: (),
+            elements: (
+                table! {
+                    rows: TestApp::path().table_rows(),
+                    columns: vec![TableColumn::new("Name", 100.0), TableColumn::new("Value", 60.0)],
+                },
+            ),
+        }
+    }
+}
+
+const RICH_TEXT: &str = "Buy <ITEM>[Apple]<INFO>512</INFO></ITEM> now <ICON>512</ICON>";
+
+/// Emits an event for every clicked link and draws all icons as custom icons.
+struct TestMarkupHandler;
+
+impl MarkupHandler<TestApp> for TestMarkupHandler {
+    fn handle_link(&self, _: &State<TestApp>, queue: &mut EventQueue<TestApp>, link: Link<'_>) {
+        queue.queue(TestEvent::LinkClicked(link.target.to_owned()));
+    }
+
+    fn lay_out_icon<'a>(&'a self, _: &'a State<TestApp>, _: &str, area: Area, layout: &mut WindowLayout<'a, TestApp>) {
+        layout.add_icon(area, Icon::Custom { icon: () }, WINDOW_BACKGROUND_COLOR);
+    }
+}
+
+struct RichTextWindow;
+
+impl CustomWindow<TestApp> for RichTextWindow {
+    fn to_window<'a>(self) -> impl Window<TestApp> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Rich text",
+            theme: (),
+            elements: (
+                rich_text! {
+                    text: RICH_TEXT,
+                    handler: TestMarkupHandler,
+                },
+            ),
+        }
+    }
+}
+
+#[test]
+fn window_renders_background() {
+    let mut harness = Harness::new();
+    harness.open_window(SplitWindow);
+
+    let backgrounds = harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR);
+
+    assert_eq!(backgrounds.len(), 1);
+    assert!(harness.find_text("Split").is_some());
+}
+
+#[test]
+fn split_divides_width_evenly() {
+    let mut harness = Harness::new();
+    harness.open_window(SplitWindow);
+
+    let buttons = harness.rectangles_with_color(BUTTON_BACKGROUND_COLOR);
+
+    assert_eq!(buttons.len(), 3);
+    assert!(buttons.iter().all(|(_, size)| size.width == buttons[0].1.width));
+    assert!(buttons.windows(2).all(|pair| pair[0].0.left + pair[0].1.width < pair[1].0.left));
+    assert!(buttons.iter().all(|(position, _)| position.top == buttons[0].0.top));
+
+    assert_snapshot(&harness, "split");
+}
+
+#[test]
+fn button_hover_and_click() {
+    let mut harness = Harness::new();
+    harness.open_window(SplitWindow);
+
+    let middle = harness.find_text("Middle").unwrap();
+    harness.move_mouse(Position {
+        left: middle.left + 1.0,
+        top: middle.top + 1.0,
+    });
+
+    assert_eq!(harness.rectangles_with_color(BUTTON_HOVERED_BACKGROUND_COLOR).len(), 1);
+    assert_snapshot(&harness, "split_hovered");
+
+    harness.click(MouseButton::Left);
+
+    assert_eq!(harness.take_events(), vec![TestEvent::Pressed("Middle")]);
+}
+
+#[test]
+fn navigation_activates_focused_button() {
+    let mut harness = Harness::new();
+    harness.open_window(SplitWindow);
+
+    harness.navigate(NavigationInput::Move(FocusDirection::Next));
+    harness.navigate(NavigationInput::Move(FocusDirection::Right));
+    assert_snapshot(&harness, "split_focused");
+
+    harness.navigate(NavigationInput::Activate);
+
+    assert_eq!(harness.take_events(), vec![TestEvent::Pressed("Middle")]);
+}
+
+#[test]
+fn tabs_only_show_selected_tab() {
+    let mut harness = Harness::new();
+    harness.open_window(TabsWindow);
+
+    assert!(harness.find_text("Content of the first tab").is_some());
+    assert!(harness.find_text("Content of the second tab").is_none());
+    assert_snapshot(&harness, "tabs_first");
+
+    harness.click_text("Next tab");
+    harness.idle();
+
+    assert_eq!(*harness.state.get(&TestApp::path().selected_tab()), 1);
+    assert!(harness.find_text("Content of the first tab").is_none());
+    assert!(harness.find_text("Content of the second tab").is_some());
+    assert_snapshot(&harness, "tabs_second");
+}
+
+#[test]
+fn drop_down_opens_list_and_selects_option() {
+    let mut harness = Harness::new();
+    harness.open_window(DropDownWindow);
+
+    assert!(harness.find_text("Cherry").is_none());
+    assert_snapshot(&harness, "drop_down_closed");
+
+    harness.click_text("Apple");
+    harness.idle();
+
+    assert!(!harness.rectangles_with_color(DROP_DOWN_ITEM_BACKGROUND_COLOR).is_empty());
+    assert!(harness.find_text("Cherry").is_some());
+    assert_snapshot(&harness, "drop_down_open");
+
+    harness.click_text("Cherry");
+    harness.idle();
+
+    assert_eq!(harness.state.get(&TestApp::path().selected_option()), "Cherry");
+    assert!(harness.find_text("Apple").is_none());
+    assert_snapshot(&harness, "drop_down_selected");
+}
+
+#[test]
+fn drop_down_list_scrolls() {
+    let mut harness = Harness::new();
+    harness.open_window(DropDownWindow);
+
+    harness.click_text("Apple");
+    harness.idle();
+
+    let banana = harness.find_text("Banana").unwrap();
+    harness.move_mouse(Position {
+        left: banana.left + 1.0,
+        top: banana.top + 1.0,
+    });
+    harness.scroll(-20.0);
+    harness.idle();
+
+    let scrolled_banana = harness.find_text("Banana").unwrap();
+
+    assert!(scrolled_banana.top < banana.top);
+    assert_snapshot(&harness, "drop_down_scrolled");
+}
+
+#[test]
+fn text_box_edits_state() {
+    let mut harness = Harness::new();
+    harness.open_window(TextBoxWindow);
+
+    harness.click_text("Name");
+    harness.idle();
+
+    assert_eq!(harness.rectangles_with_color(TEXT_BOX_FOCUSED_BACKGROUND_COLOR).len(), 1);
+
+    harness.input_characters(&['P', 'o', 'r', 'i', 'n', 'g']);
+    harness.input_keys(&[KeyInput {
+        key: EditKey::Home,
+        shift: false,
+        control: false,
+    }]);
+    harness.input_characters(&['x']);
+    harness.idle();
+
+    assert_eq!(harness.state.get(&TestApp::path().text()), "xPoring");
+    assert_snapshot(&harness, "text_box_edited");
+
+    harness.input_characters(&['\r']);
+
+    assert_eq!(harness.take_events(), vec![TestEvent::Pressed("Submit")]);
+}
+
+/// Grab the window by its title and drag it by `delta`.
+fn drag_window(harness: &mut Harness, title: &str, delta: Size) {
+    let title = harness.find_text(title).unwrap();
+    harness.move_mouse(Position {
+        left: title.left + 1.0,
+        top: title.top + 1.0,
+    });
+    harness.drag(delta);
+}
+
+#[test]
+fn window_snaps_to_screen_edge() {
+    let mut harness = Harness::new();
+    harness.open_window(SplitWindow);
+
+    let (position, _) = harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR)[0];
+    drag_window(&mut harness, "Split", Size {
+        width: 4.0 - position.left,
+        height: 0.0,
+    });
+
+    let (snapped_position, _) = harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR)[0];
+
+    assert_eq!(snapped_position.left, 0.0);
+    assert_eq!(snapped_position.top, position.top);
+}
+
+#[test]
+fn windows_snap_to_each_other() {
+    let mut harness = Harness::new();
+    harness.open_window(SplitWindow);
+    harness.open_window(TabsWindow);
+
+    // Both windows open at the same position, so moving one of them by a bit
+    // more than its width should snap it to the right edge of the other.
+    let (position, size) = harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR)[0];
+    drag_window(&mut harness, "Tabs", Size {
+        width: size.width + 3.0,
+        height: 0.0,
+    });
+
+    let mut lefts: Vec<f32> = harness
+        .rectangles_with_color(WINDOW_BACKGROUND_COLOR)
+        .into_iter()
+        .map(|(position, _)| position.left)
+        .collect();
+    lefts.sort_by(f32::total_cmp);
+
+    assert_eq!(lefts, vec![position.left, position.left + size.width]);
+}
+
+#[test]
+fn snapped_window_follows_resolution_change() {
+    let mut harness = Harness::new();
+    harness.open_window(SplitWindow);
+
+    let (position, size) = harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR)[0];
+    drag_window(&mut harness, "Split", Size {
+        width: SCREEN_SIZE.width - size.width - position.left - 3.0,
+        height: 0.0,
+    });
+
+    let (snapped_position, _) = harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR)[0];
+    assert_eq!(snapped_position.left + size.width, SCREEN_SIZE.width);
+
+    harness.resize_screen(Size {
+        width: 1024.0,
+        height: 768.0,
+    });
+
+    let (resized_position, _) = harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR)[0];
+    assert_eq!(resized_position.left + size.width, 1024.0);
+}
+
+#[test]
+fn windows_dock_into_tab_group() {
+    let mut harness = Harness::new();
+    harness.open_window(SplitWindow);
+    harness.open_window(TabsWindow);
+
+    // Move one window next to the other and back onto it, so its top left corner
+    // snaps to the top left corner of the other window.
+    let (_, size) = harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR)[0];
+    drag_window(&mut harness, "Tabs", Size {
+        width: size.width + 3.0,
+        height: 0.0,
+    });
+    drag_window(&mut harness, "Tabs", Size {
+        width: -size.width - 3.0,
+        height: 0.0,
+    });
+
+    // Only the selected window of the tab group is visible.
+    assert_eq!(harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR).len(), 1);
+    assert!(harness.find_text("Next tab").is_some());
+    assert!(harness.find_text("Left").is_none());
+
+    harness.click_text("Split");
+
+    assert_eq!(harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR).len(), 1);
+    assert!(harness.find_text("Next tab").is_none());
+    assert!(harness.find_text("Left").is_some());
+
+    // Pulling the tab out of the tab group undocks the window again.
+    drag_window(&mut harness, "Tabs", Size { width: 0.0, height: 150.0 });
+
+    assert_eq!(harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR).len(), 2);
+}
+
+#[test]
+fn reduce_motion_disables_window_transition() {
+    use harness::TestThemePathExt;
+    use korangar_interface::prelude::AnimationThemePathExt;
+
+    let mut harness = Harness::new();
+    harness
+        .state
+        .update_value(TestApp::path().theme().animation().window_duration(), 60.0);
+    harness.idle();
+
+    // The window only just started fading in, so its background isn't fully
+    // opaque yet.
+    harness.open_window(SplitWindow);
+    assert!(harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR).is_empty());
+
+    let mut harness = Harness::new();
+    harness
+        .state
+        .update_value(TestApp::path().theme().animation().window_duration(), 60.0);
+    harness.set_reduce_motion(true);
+    harness.idle();
+
+    harness.open_window(SplitWindow);
+    assert_eq!(harness.rectangles_with_color(WINDOW_BACKGROUND_COLOR).len(), 1);
+}
+
+#[test]
+fn rich_text_draws_icons_and_handles_links() {
+    let mut harness = Harness::new();
+    harness.open_window(RichTextWindow);
+
+    let instructions = harness.instructions();
+    let (text_position, font_size) = instructions
+        .iter()
+        .find_map(|instruction| match instruction {
+            Instruction::Text {
+                text, position, font_size, ..
+            } if text == RICH_TEXT => Some((*position, *font_size)),
+            _ => None,
+        })
+        .unwrap();
+    let icon_positions: Vec<_> = instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            Instruction::Icon { icon, position, .. } if icon == "custom" => Some(*position),
+            _ => None,
+        })
+        .collect();
+
+    // The icon comes after "Buy [Apple] now ".
+    let character_width = font_size.0 / 2.0;
+    assert_eq!(icon_positions, vec![Position {
+        left: text_position.left + 16.0 * character_width,
+        top: text_position.top,
+    }]);
+
+    // Clicking outside of the link does nothing.
+    harness.move_mouse(Position {
+        left: text_position.left + 1.0,
+        top: text_position.top + 1.0,
+    });
+    harness.click(MouseButton::Left);
+    assert!(harness.take_events().is_empty());
+
+    harness.move_mouse(Position {
+        left: text_position.left + 6.0 * character_width,
+        top: text_position.top + 1.0,
+    });
+    harness.click(MouseButton::Left);
+    assert_eq!(harness.take_events(), vec![TestEvent::LinkClicked("512".to_owned())]);
+}
+
+fn open_item_menu(harness: &mut Harness) {
+    harness.click_text("Options");
+    harness.idle();
+
+    assert!(harness.find_text("Equip").is_some());
+}
+
+#[test]
+fn context_menu_runs_action_and_closes() {
+    let mut harness = Harness::new();
+    harness.open_window(ContextMenuWindow);
+
+    open_item_menu(&mut harness);
+
+    // The submenu is only shown once its item is hovered or focused.
+    assert!(harness.find_text("Cart").is_none());
+    assert_snapshot(&harness, "context_menu_open");
+
+    harness.click_text("Equip");
+    harness.idle();
+
+    assert_eq!(harness.take_events(), vec![TestEvent::Pressed("Equip")]);
+    assert!(harness.find_text("Equip").is_none());
+}
+
+#[test]
+fn context_menu_disabled_item_and_separator() {
+    let mut harness = Harness::new();
+    harness.open_window(ContextMenuWindow);
+
+    open_item_menu(&mut harness);
+
+    assert_eq!(harness.rectangles_with_color(CONTEXT_MENU_SEPARATOR_COLOR).len(), 1);
+
+    let drop_color = harness.instructions().iter().find_map(|instruction| match instruction {
+        Instruction::Text { text, color, .. } if text == "Drop" => Some(*color),
+        _ => None,
+    });
+    assert_eq!(drop_color, Some(CONTEXT_MENU_DISABLED_FOREGROUND_COLOR));
+
+    // Disabled items are not highlighted and clicking them keeps the menu open.
+    harness.click_text("Drop");
+    harness.idle();
+
+    assert!(harness.rectangles_with_color(CONTEXT_MENU_HOVERED_BACKGROUND_COLOR).is_empty());
+    assert!(harness.take_events().is_empty());
+    assert!(harness.find_text("Equip").is_some());
+}
+
+#[test]
+fn context_menu_opens_submenu() {
+    let mut harness = Harness::new();
+    harness.open_window(ContextMenuWindow);
+
+    open_item_menu(&mut harness);
+
+    let move_to = harness.find_text("Move to").unwrap();
+    harness.move_mouse(Position {
+        left: move_to.left + 1.0,
+        top: move_to.top + 1.0,
+    });
+
+    let cart = harness.find_text("Cart").unwrap();
+    assert!(cart.left > move_to.left);
+    assert_snapshot(&harness, "context_menu_submenu");
+
+    // Moving the mouse into the submenu keeps it open.
+    harness.click_text("Storage");
+    harness.idle();
+
+    assert_eq!(harness.take_events(), vec![TestEvent::Pressed("Storage")]);
+    assert!(harness.find_text("Move to").is_none());
+}
+
+#[test]
+fn context_menu_keyboard_navigation() {
+    let mut harness = Harness::new();
+    harness.open_window(ContextMenuWindow);
+
+    open_item_menu(&mut harness);
+
+    // Disabled items can't be focused, so the second step skips "Drop".
+    harness.navigate(NavigationInput::Move(FocusDirection::Down));
+    harness.navigate(NavigationInput::Move(FocusDirection::Down));
+
+    assert!(harness.find_text("Cart").is_some());
+
+    harness.navigate(NavigationInput::Move(FocusDirection::Right));
+    harness.navigate(NavigationInput::Activate);
+    harness.idle();
+
+    assert_eq!(harness.take_events(), vec![TestEvent::Pressed("Cart")]);
+    assert!(harness.find_text("Equip").is_none());
+
+    // Cancelling closes the menu without running any action.
+    open_item_menu(&mut harness);
+
+    harness.navigate(NavigationInput::Move(FocusDirection::Down));
+    harness.navigate(NavigationInput::Cancel);
+    harness.idle();
+
+    assert!(harness.take_events().is_empty());
+    assert!(harness.find_text("Equip").is_none());
+}
+
+#[test]
+fn context_menu_opens_on_screen() {
+    let mut harness = Harness::new();
+    let position = Position { left: 600.0, top: 300.0 };
+
+    harness.frame(|interface_frame, state| interface_frame.open_context_menu(state, item_menu(), position));
+    harness.idle();
+
+    let equip = harness.find_text("Equip").unwrap();
+    assert!(equip.left > position.left && equip.top > position.top);
+
+    harness.click_text("Equip");
+    harness.idle();
+
+    assert_eq!(harness.take_events(), vec![TestEvent::Pressed("Equip")]);
+    assert!(harness.find_text("Equip").is_none());
+
+    // Clicking anywhere outside of the menu closes it.
+    harness.frame(|interface_frame, state| interface_frame.open_context_menu(state, item_menu(), position));
+    harness.move_mouse(Position { left: 10.0, top: 10.0 });
+    harness.click(MouseButton::Left);
+    harness.idle();
+
+    assert!(harness.take_events().is_empty());
+    assert!(harness.find_text("Equip").is_none());
+}
+
+/// Values of the table rows rendered in the last frame, from top to bottom.
+fn rendered_table_values(harness: &Harness) -> Vec<u32> {
+    harness
+        .instructions()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            Instruction::Text { text, .. } => text.parse().ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn table_only_lays_out_visible_rows() {
+    let mut harness = Harness::new();
+    harness.open_window(TableWindow);
+
+    let values = rendered_table_values(&harness);
+    assert!(!values.is_empty() && values.len() < TABLE_ROW_COUNT / 10);
+    assert!(harness.find_text("Row 0000").is_some());
+    assert!(harness.find_text("Row 0999").is_none());
+
+    let name = harness.find_text("Row 0000").unwrap();
+    harness.move_mouse(Position {
+        left: name.left + 1.0,
+        top: name.top + 1.0,
+    });
+    harness.scroll(-1_000_000.0);
+    harness.idle();
+
+    let values = rendered_table_values(&harness);
+    assert!(!values.is_empty() && values.len() < TABLE_ROW_COUNT / 10);
+    assert!(harness.find_text("Row 0000").is_none());
+    assert!(harness.find_text("Row 0999").is_some());
+}
+
+#[test]
+fn table_sorts_and_updates_changed_rows() {
+    let mut harness = Harness::new();
+    harness.open_window(TableWindow);
+
+    harness.click_text("Value");
+    harness.idle();
+
+    assert!(rendered_table_values(&harness).starts_with(&[0, 1, 2, 3]));
+
+    harness.click_text("Value");
+    harness.idle();
+
+    assert!(rendered_table_values(&harness).starts_with(&[999, 998, 997]));
+
+    // Changing the value of the top row in place moves it to the bottom. The
+    // update is applied at the end of the next frame.
+    harness.state.update_value_with(TestApp::path().table_rows(), |rows| {
+        rows.iter_mut().find(|row| row.value == 999).unwrap().value = 0;
+    });
+    harness.idle();
+    harness.idle();
+
+    assert!(rendered_table_values(&harness).starts_with(&[998, 997, 996]));
+
+    harness.scroll(-1_000_000.0);
+    harness.idle();
+
+    assert!(rendered_table_values(&harness).ends_with(&[2, 1, 0, 0]));
+
+    // Clicking a third time restores the original order.
+    harness.scroll(1_000_000.0);
+    harness.click_text("Value");
+    harness.idle();
+
+    assert!(harness.find_text("Row 0000").is_some());
+    assert_snapshot(&harness, "table_unsorted");
+}
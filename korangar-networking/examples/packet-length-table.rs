@@ -0,0 +1,5 @@
+use korangar_networking::SupportedPacketVersion;
+
+fn main() {
+    print!("{}", SupportedPacketVersion::_20220406.packet_length_table());
+}
@@ -0,0 +1,633 @@
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use korangar_pathing::{MAX_WALK_PATH_SIZE, PathFinder, Traversable};
+use ragnarok_packets::handler::{NoPacketCallback, PacketCallback};
+use ragnarok_packets::*;
+use tokio::time::Instant;
+
+use crate::{
+    DisconnectReason, InventoryItem, InventoryItemDetails, LoginServerLoginData, NetworkEvent, NetworkEventBuffer, NetworkingSystem,
+    NoMetadata, NotConnectedError, SupportedPacketVersion, UnifiedCharacterSelectionFailedReason, UnifiedLoginFailedReason,
+};
+
+/// Time between two checks for new events.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Default time to wait for the server to respond.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The character a [`Bot`] should play.
+#[derive(Debug, Clone)]
+pub enum CharacterSelection {
+    /// The character in the given slot.
+    Slot(usize),
+    /// The character with the given name.
+    Name(String),
+}
+
+impl CharacterSelection {
+    fn matches(&self, character: &CharacterInformation) -> bool {
+        match self {
+            CharacterSelection::Slot(slot) => character.character_number as usize == *slot,
+            CharacterSelection::Name(name) => character.name == *name,
+        }
+    }
+}
+
+/// Everything a [`Bot`] needs to log in and enter the map.
+#[derive(Debug, Clone)]
+pub struct BotLogin {
+    pub packet_version: SupportedPacketVersion,
+    pub login_server: SocketAddr,
+    pub username: String,
+    pub password: String,
+    pub character: CharacterSelection,
+}
+
+/// Errors returned by the [`Bot`].
+#[derive(Debug)]
+pub enum BotError {
+    /// The server didn't send the expected event in time.
+    Timeout,
+    /// The connection to the server was lost.
+    Disconnected,
+    /// The bot is not connected to the map server.
+    NotConnected,
+    LoginFailed {
+        reason: UnifiedLoginFailedReason,
+        message: &'static str,
+    },
+    CharacterServerConnectionFailed {
+        reason: LoginFailedReason,
+        message: &'static str,
+    },
+    MapServerConnectionFailed {
+        reason: LoginFailedReason,
+        message: &'static str,
+    },
+    /// None of the characters of the account match the
+    /// [`CharacterSelection`].
+    CharacterNotFound,
+    CharacterSelectionFailed {
+        reason: UnifiedCharacterSelectionFailedReason,
+        message: &'static str,
+    },
+    /// There is no walkable path to the destination.
+    NoPath,
+    /// The server moved the player to a different position than requested.
+    MovementRejected {
+        position: TilePosition,
+    },
+    /// The dialog option can't be sent to the server, since options are sent
+    /// as an `i8`.
+    InvalidDialogOption {
+        index: usize,
+    },
+}
+
+impl From<NotConnectedError> for BotError {
+    fn from(_: NotConnectedError) -> Self {
+        BotError::NotConnected
+    }
+}
+
+/// An entity that is visible to the bot.
+#[derive(Debug, Clone)]
+pub struct TrackedEntity {
+    pub entity_id: EntityId,
+    pub job_id: JobId,
+    /// Only known after requesting it with
+    /// [`NetworkingSystem::entity_details`].
+    pub name: Option<String>,
+    /// The position the entity is standing at or walking to.
+    pub position: TilePosition,
+    pub health_points: i32,
+    pub maximum_health_points: i32,
+}
+
+/// Everything the bot knows about the world, kept up to date with the events
+/// sent by the server.
+#[derive(Debug, Clone)]
+pub struct BotState {
+    pub account_id: AccountId,
+    pub character: CharacterInformation,
+    pub map_name: String,
+    /// The position the player is standing at or walking to.
+    pub position: TilePosition,
+    pub movement_speed: u32,
+    pub entities: HashMap<EntityId, TrackedEntity>,
+    pub inventory: Vec<InventoryItem<NoMetadata>>,
+}
+
+impl BotState {
+    fn update(&mut self, event: &NetworkEvent) {
+        match event {
+            NetworkEvent::ChangeMap { map_name, position } => {
+                self.map_name = map_name.clone();
+                self.position = *position;
+                self.entities.clear();
+            }
+            NetworkEvent::PlayerMove { destination, .. } => {
+                self.position = destination.tile_position();
+            }
+            NetworkEvent::AddEntity { entity_data } => {
+                let entity = TrackedEntity {
+                    entity_id: entity_data.entity_id,
+                    job_id: entity_data.job_id,
+                    name: None,
+                    position: entity_data.destination.unwrap_or(entity_data.position).tile_position(),
+                    health_points: entity_data.health_points,
+                    maximum_health_points: entity_data.maximum_health_points,
+                };
+
+                self.entities.insert(entity.entity_id, entity);
+            }
+            NetworkEvent::RemoveEntity { entity_id, .. } => {
+                self.entities.remove(entity_id);
+            }
+            NetworkEvent::EntityMove {
+                entity_id, destination, ..
+            } => {
+                if let Some(entity) = self.entities.get_mut(entity_id) {
+                    entity.position = destination.tile_position();
+                }
+            }
+            NetworkEvent::UpdateEntityDetails { entity_id, name } => {
+                if let Some(entity) = self.entities.get_mut(entity_id) {
+                    entity.name = Some(name.clone());
+                }
+            }
+            NetworkEvent::UpdateEntityHealth {
+                entity_id,
+                health_points,
+                maximum_health_points,
+            } => {
+                if let Some(entity) = self.entities.get_mut(entity_id) {
+                    entity.health_points = *health_points as i32;
+                    entity.maximum_health_points = *maximum_health_points as i32;
+                }
+            }
+            NetworkEvent::UpdateStat {
+                stat_type: StatType::MovementSpeed(movement_speed),
+            } => {
+                self.movement_speed = *movement_speed;
+            }
+            NetworkEvent::SetInventory { items } => {
+                self.inventory = items.clone();
+            }
+            NetworkEvent::IventoryItemAdded { item } => self.add_item(item.clone()),
+            NetworkEvent::InventoryItemRemoved { index, amount, .. } => self.remove_item(*index, *amount),
+            _ => {}
+        }
+    }
+
+    fn add_item(&mut self, item: InventoryItem<NoMetadata>) {
+        let found_item = self.inventory.iter_mut().find(|inventory_item| inventory_item.index == item.index);
+
+        match (found_item, &item.details) {
+            (
+                Some(InventoryItem {
+                    details: InventoryItemDetails::Regular { amount, .. },
+                    ..
+                }),
+                InventoryItemDetails::Regular { amount: added_amount, .. },
+            ) => *amount += added_amount,
+            (Some(found_item), _) => *found_item = item,
+            (None, _) => self.inventory.push(item),
+        }
+    }
+
+    fn remove_item(&mut self, index: InventoryIndex, remove_amount: u16) {
+        let Some(position) = self.inventory.iter().position(|item| item.index == index) else {
+            return;
+        };
+
+        if let InventoryItemDetails::Regular { amount, .. } = &mut self.inventory[position].details
+            && *amount > remove_amount
+        {
+            *amount -= remove_amount;
+            return;
+        }
+
+        self.inventory.remove(position);
+    }
+
+    /// Time it takes the player to walk along `path`.
+    fn walk_duration(&self, path: &[TilePosition]) -> Duration {
+        let milliseconds: u32 = path
+            .windows(2)
+            .map(|step| match step[0].x != step[1].x && step[0].y != step[1].y {
+                // Same factor as the server uses for diagonal steps.
+                true => self.movement_speed * 14 / 10,
+                false => self.movement_speed,
+            })
+            .sum();
+
+        Duration::from_millis(milliseconds as u64)
+    }
+}
+
+/// Split `path` into segments that are short enough to be accepted by the
+/// server. Every segment starts where the previous one ended.
+fn split_path(path: &[TilePosition]) -> impl Iterator<Item = &[TilePosition]> {
+    let step_count = MAX_WALK_PATH_SIZE - 1;
+
+    (0..path.len().saturating_sub(1))
+        .step_by(step_count)
+        .map(move |start| &path[start..(start + step_count + 1).min(path.len())])
+}
+
+/// Polls the [`NetworkingSystem`] for events.
+struct EventSource<Callback> {
+    networking_system: NetworkingSystem<Callback>,
+    event_buffer: NetworkEventBuffer,
+    pending_events: VecDeque<NetworkEvent>,
+}
+
+impl<Callback> EventSource<Callback>
+where
+    Callback: PacketCallback + Send,
+{
+    /// Get the next event, or `None` if there was no event before the
+    /// deadline.
+    async fn next_event(&mut self, deadline: Instant) -> Option<NetworkEvent> {
+        loop {
+            if let Some(event) = self.pending_events.pop_front() {
+                return Some(event);
+            }
+
+            self.networking_system.get_events(&mut self.event_buffer);
+            self.pending_events.extend(self.event_buffer.drain());
+
+            if self.pending_events.is_empty() {
+                if Instant::now() >= deadline {
+                    return None;
+                }
+
+                tokio::time::sleep_until((Instant::now() + POLL_INTERVAL).min(deadline)).await;
+            }
+        }
+    }
+
+    /// Wait for the first event accepted by `matcher` during the login,
+    /// returning an error if the server rejects the login instead.
+    async fn wait_for_login_event<T>(
+        &mut self,
+        timeout: Duration,
+        mut matcher: impl FnMut(NetworkEvent) -> Option<T>,
+    ) -> Result<T, BotError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let event = self.next_event(deadline).await.ok_or(BotError::Timeout)?;
+
+            match event {
+                NetworkEvent::LoginServerConnectionFailed { reason, message } => return Err(BotError::LoginFailed { reason, message }),
+                NetworkEvent::CharacterServerConnectionFailed { reason, message } => {
+                    return Err(BotError::CharacterServerConnectionFailed { reason, message });
+                }
+                NetworkEvent::CharacterSelectionFailed { reason, message } => {
+                    return Err(BotError::CharacterSelectionFailed { reason, message });
+                }
+                NetworkEvent::MapServerConnectionFailed { reason, message } => {
+                    return Err(BotError::MapServerConnectionFailed { reason, message });
+                }
+                NetworkEvent::LoginServerDisconnected {
+                    reason: DisconnectReason::ConnectionError,
+                }
+                | NetworkEvent::CharacterServerDisconnected {
+                    reason: DisconnectReason::ConnectionError,
+                }
+                | NetworkEvent::MapServerDisconnected { .. } => return Err(BotError::Disconnected),
+                event => {
+                    if let Some(result) = matcher(event) {
+                        return Ok(result);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A headless client that plays a single character.
+///
+/// The bot tracks the position of the player, the visible entities, and the
+/// inventory, and provides helpers for common actions like walking and
+/// talking to NPCs. Everything else can be done through the
+/// [`NetworkingSystem`].
+///
+/// Events are only processed while the bot is waiting for something, so the
+/// state is updated by calling any of the async functions.
+pub struct Bot<Callback = NoPacketCallback> {
+    event_source: EventSource<Callback>,
+    state: BotState,
+    path_finder: PathFinder,
+    timeout: Duration,
+}
+
+impl Bot<NoPacketCallback> {
+    /// Log in and enter the map with the selected character.
+    pub async fn log_in(login: BotLogin) -> Result<Self, BotError> {
+        Self::log_in_with_callback(login, NoPacketCallback).await
+    }
+}
+
+impl<Callback> Bot<Callback>
+where
+    Callback: PacketCallback + Send,
+{
+    /// Log in and enter the map with the selected character, monitoring all
+    /// packets with `packet_callback`.
+    pub async fn log_in_with_callback(login: BotLogin, packet_callback: Callback) -> Result<Self, BotError> {
+        let BotLogin {
+            packet_version,
+            login_server,
+            username,
+            password,
+            character,
+        } = login;
+
+        let (networking_system, event_buffer) = NetworkingSystem::spawn_with_callback(packet_callback);
+        let mut event_source = EventSource {
+            networking_system,
+            event_buffer,
+            pending_events: VecDeque::new(),
+        };
+
+        event_source
+            .networking_system
+            .connect_to_login_server(packet_version, login_server, username, password);
+
+        let (character_servers, login_data): (Vec<CharacterServerInformation>, LoginServerLoginData) = event_source
+            .wait_for_login_event(DEFAULT_TIMEOUT, |event| match event {
+                NetworkEvent::LoginServerConnected {
+                    character_servers,
+                    login_data,
+                } => Some((character_servers, login_data)),
+                _ => None,
+            })
+            .await?;
+
+        let character_server = character_servers.into_iter().next().ok_or(BotError::Disconnected)?;
+
+        event_source.networking_system.disconnect_from_login_server();
+        event_source
+            .networking_system
+            .connect_to_character_server(packet_version, &login_data, character_server);
+
+        event_source
+            .wait_for_login_event(DEFAULT_TIMEOUT, |event| {
+                matches!(event, NetworkEvent::CharacterServerConnected { .. }).then_some(())
+            })
+            .await?;
+
+        event_source.networking_system.request_character_list()?;
+
+        let characters = event_source
+            .wait_for_login_event(DEFAULT_TIMEOUT, |event| match event {
+                NetworkEvent::CharacterList { characters } => Some(characters),
+                _ => None,
+            })
+            .await?;
+
+        let character_information = characters
+            .into_iter()
+            .find(|information| character.matches(information))
+            .ok_or(BotError::CharacterNotFound)?;
+
+        event_source
+            .networking_system
+            .select_character(character_information.character_number as usize)?;
+
+        let character_login_data = event_source
+            .wait_for_login_event(DEFAULT_TIMEOUT, |event| match event {
+                NetworkEvent::CharacterSelected { login_data } => Some(login_data),
+                _ => None,
+            })
+            .await?;
+
+        event_source.networking_system.disconnect_from_character_server();
+        event_source
+            .networking_system
+            .connect_to_map_server(packet_version, &login_data, character_login_data);
+
+        let position = event_source
+            .wait_for_login_event(DEFAULT_TIMEOUT, |event| match event {
+                NetworkEvent::MapServerConnected { position } => Some(position.tile_position()),
+                _ => None,
+            })
+            .await?;
+
+        event_source.networking_system.map_loaded()?;
+
+        let state = BotState {
+            account_id: login_data.account_id,
+            map_name: character_information.map_name.replace(".gat", ""),
+            movement_speed: character_information.movement_speed as u32,
+            character: character_information,
+            position,
+            entities: HashMap::new(),
+            inventory: Vec::new(),
+        };
+
+        Ok(Self {
+            event_source,
+            state,
+            path_finder: PathFinder::default(),
+            timeout: DEFAULT_TIMEOUT,
+        })
+    }
+
+    /// Everything the bot knows about the world.
+    pub fn state(&self) -> &BotState {
+        &self.state
+    }
+
+    /// The networking system used by the bot, for actions that don't have a
+    /// helper.
+    pub fn networking_system(&mut self) -> &mut NetworkingSystem<Callback> {
+        &mut self.event_source.networking_system
+    }
+
+    /// Set how long to wait for the server to respond before returning
+    /// [`BotError::Timeout`].
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    async fn next_event_before(&mut self, deadline: Instant) -> Option<NetworkEvent> {
+        let event = self.event_source.next_event(deadline).await?;
+        self.state.update(&event);
+        Some(event)
+    }
+
+    /// Get the next event from the server.
+    pub async fn next_event(&mut self) -> Result<NetworkEvent, BotError> {
+        let deadline = Instant::now() + self.timeout;
+        self.next_event_before(deadline).await.ok_or(BotError::Timeout)
+    }
+
+    /// Wait for the first event accepted by `matcher`. All events before it
+    /// still update the state, but are discarded otherwise.
+    pub async fn wait_for_event<T>(&mut self, mut matcher: impl FnMut(&NetworkEvent) -> Option<T>) -> Result<T, BotError> {
+        let deadline = Instant::now() + self.timeout;
+
+        loop {
+            let event = self.next_event_before(deadline).await.ok_or(BotError::Timeout)?;
+
+            if let Some(result) = matcher(&event) {
+                return Ok(result);
+            }
+
+            if let NetworkEvent::MapServerDisconnected { .. } = event {
+                return Err(BotError::Disconnected);
+            }
+        }
+    }
+
+    /// Keep processing events for `duration`.
+    pub async fn idle(&mut self, duration: Duration) -> Result<(), BotError> {
+        let deadline = Instant::now() + duration;
+
+        while let Some(event) = self.next_event_before(deadline).await {
+            if let NetworkEvent::MapServerDisconnected { .. } = event {
+                return Err(BotError::Disconnected);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Walk to `destination` and wait until the player arrived.
+    ///
+    /// The path is found the same way the server finds it, so paths longer
+    /// than the server accepts are split into multiple steps.
+    pub async fn walk_to(&mut self, map: &impl Traversable, destination: TilePosition) -> Result<(), BotError> {
+        let path = self
+            .path_finder
+            .find_long_walkable_path(map, self.state.position, destination)
+            .ok_or(BotError::NoPath)?
+            .to_vec();
+
+        for segment in split_path(&path) {
+            let target = *segment.last().unwrap();
+
+            self.networking_system().player_move(WorldPosition {
+                x: target.x,
+                y: target.y,
+                direction: Direction::North,
+            })?;
+
+            let position = self
+                .wait_for_event(|event| match event {
+                    NetworkEvent::PlayerMove { destination, .. } => Some(destination.tile_position()),
+                    _ => None,
+                })
+                .await?;
+
+            if position != target {
+                return Err(BotError::MovementRejected { position });
+            }
+
+            self.idle(self.state.walk_duration(segment)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Talk to an NPC until the dialog is closed, returning all the text the
+    /// NPC said.
+    ///
+    /// Whenever the NPC offers multiple options, `choose` is called with the
+    /// options and has to return the index of the option to pick. Returning
+    /// `None` cancels the dialog.
+    pub async fn talk_to_npc(
+        &mut self,
+        npc_id: EntityId,
+        mut choose: impl FnMut(&[String]) -> Option<usize>,
+    ) -> Result<Vec<String>, BotError> {
+        enum DialogStep {
+            Text(String),
+            Next,
+            Choice(Vec<String>),
+            Close,
+        }
+
+        self.networking_system().start_dialog(npc_id)?;
+
+        let mut text = Vec::new();
+
+        loop {
+            let step = self
+                .wait_for_event(|event| match event {
+                    NetworkEvent::OpenDialog { text, npc_id: id } if *id == npc_id => Some(DialogStep::Text(text.clone())),
+                    NetworkEvent::AddNextButton { npc_id: id } if *id == npc_id => Some(DialogStep::Next),
+                    NetworkEvent::AddChoiceButtons { choices, npc_id: id } if *id == npc_id => Some(DialogStep::Choice(choices.clone())),
+                    NetworkEvent::AddCloseButton { npc_id: id } if *id == npc_id => Some(DialogStep::Close),
+                    _ => None,
+                })
+                .await?;
+
+            match step {
+                DialogStep::Text(line) => text.push(line),
+                DialogStep::Next => self.networking_system().next_dialog(npc_id)?,
+                DialogStep::Choice(choices) => match choose(&choices) {
+                    // Options start at 1.
+                    Some(index) => {
+                        let option = index
+                            .checked_add(1)
+                            .and_then(|option| i8::try_from(option).ok())
+                            .ok_or(BotError::InvalidDialogOption { index })?;
+                        self.networking_system().choose_dialog_option(npc_id, option)?
+                    }
+                    None => {
+                        self.networking_system().choose_dialog_option(npc_id, -1)?;
+                        return Ok(text);
+                    }
+                },
+                DialogStep::Close => {
+                    self.networking_system().close_dialog(npc_id)?;
+                    return Ok(text);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod path_segments {
+    use korangar_pathing::MAX_WALK_PATH_SIZE;
+    use ragnarok_packets::TilePosition;
+
+    use super::split_path;
+
+    fn straight_path(length: u16) -> Vec<TilePosition> {
+        (0..length).map(|x| TilePosition { x, y: 0 }).collect()
+    }
+
+    #[test]
+    fn already_there() {
+        assert_eq!(split_path(&straight_path(1)).count(), 0);
+    }
+
+    #[test]
+    fn single_segment() {
+        let path = straight_path(MAX_WALK_PATH_SIZE as u16);
+        let segments: Vec<_> = split_path(&path).collect();
+
+        assert_eq!(segments, [path.as_slice()]);
+    }
+
+    #[test]
+    fn multiple_segments() {
+        let path = straight_path(70);
+        let segments: Vec<_> = split_path(&path).collect();
+
+        assert_eq!(segments.len(), 3);
+        assert!(segments.iter().all(|segment| segment.len() <= MAX_WALK_PATH_SIZE));
+        assert!(segments.windows(2).all(|pair| pair[0].last() == pair[1].first()));
+        assert_eq!(segments[0].first(), path.first());
+        assert_eq!(segments[2].last(), path.last());
+    }
+}
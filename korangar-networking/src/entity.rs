@@ -1,5 +1,17 @@
 use ragnarok_packets::*;
 
+/// Option flags that mark an entity as pushing a cart. The position of the flag
+/// determines the type of the cart.
+const CART_OPTION_FLAGS: [u32; 5] = [0x0008, 0x0080, 0x0100, 0x0200, 0x0400];
+
+/// Get the type of cart an entity is pushing from its effect state.
+pub(crate) fn cart_type_from_effect_state(effect_state: u32) -> Option<usize> {
+    CART_OPTION_FLAGS
+        .iter()
+        .position(|flag| effect_state & flag != 0)
+        .map(|index| index + 1)
+}
+
 #[derive(Debug)]
 pub struct EntityData {
     pub entity_id: EntityId,
@@ -12,6 +24,7 @@ pub struct EntityData {
     pub maximum_health_points: i32,
     pub head_direction: usize,
     pub sex: Sex,
+    pub cart_type: Option<usize>,
 }
 
 impl EntityData {
@@ -27,6 +40,7 @@ impl EntityData {
             maximum_health_points: character_information.maximum_health_points as i32,
             head_direction: 0, // TODO: get correct rotation
             sex: character_information.sex,
+            cart_type: cart_type_from_effect_state(character_information.effect_state as u32),
         }
     }
 }
@@ -44,6 +58,7 @@ impl From<EntityAppearPacket> for EntityData {
             maximum_health_points: packet.maximum_health_points,
             head_direction: packet.head_direction as usize,
             sex: packet.sex,
+            cart_type: cart_type_from_effect_state(packet.effect_state),
         }
     }
 }
@@ -61,6 +76,7 @@ impl From<EntityAppear2Packet> for EntityData {
             maximum_health_points: packet.maximum_health_points,
             head_direction: packet.head_direction as usize,
             sex: packet.sex,
+            cart_type: cart_type_from_effect_state(packet.effect_state),
         }
     }
 }
@@ -80,6 +96,7 @@ impl From<MovingEntityAppearPacket> for EntityData {
             maximum_health_points: packet.maximum_health_points,
             head_direction: packet.head_direction as usize,
             sex: packet.sex,
+            cart_type: cart_type_from_effect_state(packet.effect_state),
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use ragnarok_packets::*;
 
@@ -33,6 +33,18 @@ pub enum NetworkEvent {
     CharacterServerDisconnected {
         reason: DisconnectReason,
     },
+    /// The connection to the character server was lost and the session will
+    /// be resumed after `delay`.
+    CharacterServerReconnecting {
+        attempt: u32,
+        maximum_attempts: u32,
+        delay: Duration,
+    },
+    /// The character server accepted the login after reconnecting. Sent
+    /// instead of [`CharacterServerConnected`](Self::CharacterServerConnected).
+    CharacterServerReconnected {
+        normal_slot_count: usize,
+    },
     AccountId {
         account_id: AccountId,
     },
@@ -58,6 +70,17 @@ pub enum NetworkEvent {
         reason: CharacterDeletionFailedReason,
         message: &'static str,
     },
+    /// The map server accepted the login. Provides the position that the
+    /// player spawns at.
+    MapServerConnected {
+        position: WorldPosition,
+    },
+    /// The map server refused the login, for example because it doesn't know
+    /// the session anymore.
+    MapServerConnectionFailed {
+        reason: LoginFailedReason,
+        message: &'static str,
+    },
     MapServerDisconnected {
         reason: DisconnectReason,
     },
@@ -275,6 +298,181 @@ pub enum NetworkEvent {
     RemoveSkill {
         skill_id: SkillId,
     },
+    /// A skill of the player can't be used until the cooldown is over.
+    SkillCooldown {
+        skill_id: SkillId,
+        /// Duration of the cooldown in milliseconds.
+        duration: u32,
+    },
+    /// An entity started casting a skill.
+    SkillCastStarted {
+        source_entity_id: EntityId,
+        target_entity_id: EntityId,
+        /// Target position of skills that are cast on the ground.
+        target_position: Option<TilePosition>,
+        skill_id: SkillId,
+        /// Cast time in milliseconds.
+        cast_time: u32,
+    },
+    /// An entity stopped casting before the cast time was over.
+    SkillCastCancelled {
+        entity_id: EntityId,
+    },
+    /// Replace the content of the merchant cart.
+    SetCart {
+        items: Vec<InventoryItem<NoMetadata>>,
+    },
+    CartItemAdded {
+        item: InventoryItem<NoMetadata>,
+    },
+    CartItemRemoved {
+        index: InventoryIndex,
+        amount: u32,
+    },
+    /// Update the number of items and the weight of the cart.
+    UpdateCartInformation {
+        item_count: u16,
+        maximum_item_count: u16,
+        weight: u32,
+        maximum_weight: u32,
+    },
+    MoveItemToCartFailed {
+        result: MoveItemToCartResult,
+    },
+    CartRemoved,
+    /// Update the cart that is displayed next to an entity.
+    UpdateEntityCart {
+        entity_id: EntityId,
+        cart_type: Option<usize>,
+    },
+    /// Sent when logging in and whenever a new mail arrives.
+    NewMailStatus {
+        has_new_mail: bool,
+    },
+    /// A page of mails from the mailbox.
+    MailList {
+        mails: Vec<MailInformation>,
+        is_last_page: bool,
+    },
+    MailContent {
+        open_type: MailOpenType,
+        mail_id: MailId,
+        text: String,
+        zeny: u64,
+        items: Vec<InventoryItem<NoMetadata>>,
+    },
+    MailDeleted {
+        mail_id: MailId,
+    },
+    MailZenyClaimed {
+        mail_id: MailId,
+        result: ClaimMailAttachmentResult,
+    },
+    MailItemsClaimed {
+        mail_id: MailId,
+        result: ClaimMailAttachmentResult,
+    },
+    /// The server accepted or rejected writing a new mail.
+    WriteMailOpened {
+        recipient_name: String,
+        success: bool,
+    },
+    /// Result of checking a mail recipient. If no character with the given
+    /// name exists, the character id is `None`.
+    MailRecipientChecked {
+        character_id: Option<CharacterId>,
+    },
+    MailItemAttached {
+        result: AddMailItemResult,
+        index: InventoryIndex,
+        amount: u16,
+    },
+    MailItemDetached {
+        success: bool,
+        index: InventoryIndex,
+        amount: u16,
+    },
+    MailSent {
+        result: SendMailResult,
+    },
+    /// The player used a taming item and should select a monster to capture.
+    StartPetCapture,
+    PetCaptureResult {
+        success: bool,
+    },
+    /// The player used a pet incubator and can select an egg to hatch.
+    PetEggList {
+        indices: Vec<InventoryIndex>,
+    },
+    UpdatePetInformation {
+        name: String,
+        level: u16,
+        hunger: u16,
+        intimacy: u16,
+        accessory_id: ItemId,
+        job_id: JobId,
+    },
+    UpdatePetState {
+        entity_id: EntityId,
+        state_type: PetStateType,
+        value: i32,
+    },
+    PetFed {
+        success: bool,
+        item_id: ItemId,
+    },
+    UpdateHomunculusInformation {
+        name: String,
+        level: u16,
+        hunger: u16,
+        intimacy: u16,
+        stats: HomunculusStats,
+    },
+    UpdateHomunculusState {
+        entity_id: EntityId,
+        state_type: HomunculusStateType,
+        value: i32,
+    },
+    HomunculusSkillTree {
+        skill_information: Vec<SkillInformation>,
+    },
+    HomunculusFed {
+        success: bool,
+        item_id: ItemId,
+    },
+    ChatRoomCreated {
+        result: CreateChatRoomResult,
+    },
+    /// A chat room appeared on the map or its settings changed.
+    UpdateChatRoom {
+        owner_entity_id: EntityId,
+        chat_room_id: ChatRoomId,
+        limit: u16,
+        user_count: u16,
+        room_type: ChatRoomType,
+        title: String,
+    },
+    ChatRoomDestroyed {
+        chat_room_id: ChatRoomId,
+    },
+    ChatRoomJoined {
+        chat_room_id: ChatRoomId,
+        members: Vec<ChatRoomMember>,
+    },
+    JoinChatRoomFailed {
+        reason: JoinChatRoomFailedReason,
+    },
+    ChatRoomMemberJoined {
+        name: String,
+    },
+    ChatRoomMemberLeft {
+        name: String,
+        was_kicked: bool,
+    },
+    ChatRoomRoleChanged {
+        name: String,
+        role: ChatRoomRole,
+    },
 }
 
 /// New-type so we can implement some `From` traits. This will help when
@@ -41,6 +41,12 @@ impl<Meta> InventoryItem<Meta> {
             InventoryItemDetails::Equippable { flags, .. } => flags.contains(EquippableItemFlags::IDENTIFIED),
         }
     }
+
+    /// Whether the item can be used from the inventory, based on the item
+    /// type (healing, usable, delayed consume and cash items).
+    pub fn is_usable(&self) -> bool {
+        matches!(self.item_type, 0 | 2 | 11 | 18)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
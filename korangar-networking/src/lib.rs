@@ -1,42 +1,62 @@
 #![cfg_attr(feature = "interface", feature(impl_trait_in_assoc_type))]
 #![cfg_attr(feature = "interface", feature(negative_impls))]
 
+mod bot;
 mod entity;
 mod event;
 mod hotkey;
 mod items;
 mod message;
 mod packet_versions;
+mod reconnect;
+mod recording;
+mod replay;
 mod server;
+#[cfg(feature = "debug")]
+mod simulation;
+#[cfg(feature = "debug")]
+mod statistics;
 
 use std::net::{IpAddr, SocketAddr};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use event::{
-    CharacterServerDisconnectedEvent, DisconnectedEvent, LoginServerDisconnectedEvent, MapServerDisconnectedEvent, NetworkEventList,
-};
+use event::{DisconnectedEvent, LoginServerDisconnectedEvent, MapServerDisconnectedEvent, NetworkEventList};
 use ragnarok_bytes::encoding::UTF_8;
 use ragnarok_bytes::{ByteReader, ByteWriter, FromBytes};
 use ragnarok_packets::handler::{DuplicateHandlerError, HandlerResult, NoPacketCallback, PacketCallback, PacketHandler};
+use ragnarok_packets::obfuscation::{PacketObfuscation, PacketObfuscationKeys};
 use ragnarok_packets::*;
-use server::{ServerConnectCommand, ServerConnection};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use server::{ConnectionTarget, ServerConnectCommand, ServerConnection};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio::sync::mpsc::error::TryRecvError;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::task::JoinHandle;
 
+pub use self::bot::{Bot, BotError, BotLogin, BotState, CharacterSelection, TrackedEntity};
 pub use self::entity::EntityData;
 pub use self::event::{DisconnectReason, NetworkEvent};
 pub use self::hotkey::HotkeyState;
 pub use self::items::{InventoryItem, InventoryItemDetails, ItemQuantity, NoMetadata, SellItem, ShopItem};
 pub use self::message::MessageColor;
 pub use self::packet_versions::SupportedPacketVersion;
+pub use self::reconnect::ReconnectPolicy;
+pub use self::recording::{PacketDirection, PacketRecording, RecordedPacket, RecordingCallback};
 pub use self::server::{
     CharacterServerLoginData, LoginServerLoginData, NotConnectedError, UnifiedCharacterSelectionFailedReason, UnifiedLoginFailedReason,
 };
+#[cfg(feature = "debug")]
+pub use self::simulation::NetworkConditions;
+#[cfg(feature = "debug")]
+pub use self::statistics::{HISTORY_LENGTH, NetworkStatistics, ServerStatistics, ServerType, TrafficSample, TrafficStatistics};
+use crate::reconnect::{CharacterServerSession, ReconnectState, ResumableSession, Session};
+use crate::replay::{ReplayServer, ReplaySource};
 use crate::server::NetworkTaskError;
+#[cfg(feature = "debug")]
+use crate::simulation::NetworkSimulation;
+#[cfg(feature = "debug")]
+use crate::statistics::StatisticsRecorder;
 
 /// Buffer for networking events. This struct exists to reduce heap allocations
 /// and is purely an optimization.
@@ -48,6 +68,11 @@ impl NetworkEventBuffer {
     }
 }
 
+/// Stream used to communicate with a server.
+trait ServerStream: AsyncRead + AsyncWrite + Unpin {}
+
+impl<T> ServerStream for T where T: AsyncRead + AsyncWrite + Unpin {}
+
 /// Simple time synchronization using the Cristian's algorithm.
 struct TimeSynchronization {
     request_send: Instant,
@@ -73,6 +98,18 @@ impl TimeSynchronization {
         (self.client_tick + (elapsed * 1000.0)) as u32
     }
 
+    /// Returns the client tick predicted for `request_received` from the last
+    /// synchronization.
+    #[cfg(feature = "debug")]
+    fn predicted_client_tick(&self, request_received: Instant) -> f64 {
+        self.client_tick + request_received.duration_since(self.request_received).as_secs_f64() * 1000.0
+    }
+
+    #[cfg(feature = "debug")]
+    fn round_trip_time(&self) -> Duration {
+        self.request_received.duration_since(self.request_send)
+    }
+
     /// Returns the estimated client tick using the Cristian's algorithm.
     fn estimated_client_tick(&mut self, server_tick: u32, request_received: Instant) -> u32 {
         self.request_received = request_received;
@@ -89,13 +126,25 @@ pub struct NetworkingSystem<Callback> {
     login_server_connection: ServerConnection,
     character_server_connection: ServerConnection,
     map_server_connection: ServerConnection,
+    reconnect_policy: Option<ReconnectPolicy>,
+    packet_obfuscation_keys: Option<PacketObfuscationKeys>,
+    character_server_session: Option<ResumableSession<CharacterServerSession>>,
+    #[cfg(feature = "debug")]
+    network_simulation: NetworkSimulation,
+    #[cfg(feature = "debug")]
+    statistics_recorder: StatisticsRecorder,
     packet_callback: Callback,
 }
 
 impl NetworkingSystem<NoPacketCallback> {
     pub fn spawn() -> (Self, NetworkEventBuffer) {
-        let (command_sender, time_synchronization) = Self::spawn_networking_thread(NoPacketCallback);
-        Self::inner_new(command_sender, time_synchronization, NoPacketCallback)
+        Self::inner_new(NoPacketCallback, None)
+    }
+
+    /// Spawn a networking system that replays `recording` instead of
+    /// connecting to any servers.
+    pub fn spawn_replay(recording: &PacketRecording) -> (Self, NetworkEventBuffer) {
+        Self::spawn_replay_with_callback(recording, NoPacketCallback)
     }
 }
 
@@ -103,17 +152,34 @@ impl<Callback> NetworkingSystem<Callback>
 where
     Callback: PacketCallback + Send,
 {
-    fn inner_new(
-        command_sender: UnboundedSender<ServerConnectCommand>,
-        time_synchronization: Arc<Mutex<TimeSynchronization>>,
-        packet_callback: Callback,
-    ) -> (Self, NetworkEventBuffer) {
+    fn inner_new(packet_callback: Callback, replay_source: Option<ReplaySource>) -> (Self, NetworkEventBuffer) {
+        #[cfg(feature = "debug")]
+        let network_simulation = NetworkSimulation::default();
+        #[cfg(feature = "debug")]
+        let statistics_recorder = StatisticsRecorder::default();
+
+        let (command_sender, time_synchronization) = Self::spawn_networking_thread(
+            packet_callback.clone(),
+            replay_source,
+            #[cfg(feature = "debug")]
+            network_simulation.clone(),
+            #[cfg(feature = "debug")]
+            statistics_recorder.clone(),
+        );
+
         let networking_system = Self {
             command_sender,
             time_synchronization,
             login_server_connection: ServerConnection::Disconnected,
             character_server_connection: ServerConnection::Disconnected,
             map_server_connection: ServerConnection::Disconnected,
+            reconnect_policy: None,
+            packet_obfuscation_keys: None,
+            character_server_session: None,
+            #[cfg(feature = "debug")]
+            network_simulation,
+            #[cfg(feature = "debug")]
+            statistics_recorder,
             packet_callback,
         };
         let event_buffer = NetworkEventBuffer(Vec::new());
@@ -122,11 +188,25 @@ where
     }
 
     pub fn spawn_with_callback(packet_callback: Callback) -> (Self, NetworkEventBuffer) {
-        let (command_sender, time_synchronization) = Self::spawn_networking_thread(packet_callback.clone());
-        Self::inner_new(command_sender, time_synchronization, packet_callback)
+        Self::inner_new(packet_callback, None)
     }
 
-    fn spawn_networking_thread(packet_callback: Callback) -> (UnboundedSender<ServerConnectCommand>, Arc<Mutex<TimeSynchronization>>) {
+    /// Spawn a networking system that replays `recording` instead of
+    /// connecting to any servers.
+    ///
+    /// Connecting to a server replays the next recorded connection to the
+    /// same type of server, regardless of the address. Packets sent by the
+    /// client are discarded.
+    pub fn spawn_replay_with_callback(recording: &PacketRecording, packet_callback: Callback) -> (Self, NetworkEventBuffer) {
+        Self::inner_new(packet_callback, Some(ReplaySource::new(recording)))
+    }
+
+    fn spawn_networking_thread(
+        packet_callback: Callback,
+        mut replay_source: Option<ReplaySource>,
+        #[cfg(feature = "debug")] network_simulation: NetworkSimulation,
+        #[cfg(feature = "debug")] statistics_recorder: StatisticsRecorder,
+    ) -> (UnboundedSender<ServerConnectCommand>, Arc<Mutex<TimeSynchronization>>) {
         let (command_sender, mut command_receiver) = tokio::sync::mpsc::unbounded_channel::<ServerConnectCommand>();
         let time_synchronization = Arc::new(Mutex::new(TimeSynchronization::new()));
         let thread_time_synchronization = Arc::clone(&time_synchronization);
@@ -157,14 +237,19 @@ where
 
                             let packet_handler = Self::create_login_server_packet_handler(packet_callback.clone(), packet_version).unwrap();
                             let handle = local_set.spawn_local(Self::handle_server_connection(
-                                address,
+                                Self::connection_target(&mut replay_source, address, ReplayServer::Login),
                                 action_receiver,
                                 event_sender,
                                 packet_handler,
                                 |_| LoginServerKeepalivePacket::new(),
                                 Duration::from_secs(58),
                                 false,
+                                None,
                                 thread_time_synchronization.clone(),
+                                #[cfg(feature = "debug")]
+                                network_simulation.clone(),
+                                #[cfg(feature = "debug")]
+                                (statistics_recorder.clone(), ServerType::Login),
                             ));
 
                             login_server_task_handle = Some(handle);
@@ -183,14 +268,19 @@ where
                             let packet_handler =
                                 Self::create_character_server_packet_handler(packet_callback.clone(), packet_version).unwrap();
                             let handle = local_set.spawn_local(Self::handle_server_connection(
-                                address,
+                                Self::connection_target(&mut replay_source, address, ReplayServer::Character),
                                 action_receiver,
                                 event_sender,
                                 packet_handler,
                                 |_| CharacterServerKeepalivePacket::new(),
                                 Duration::from_secs(10),
                                 true,
+                                None,
                                 thread_time_synchronization.clone(),
+                                #[cfg(feature = "debug")]
+                                network_simulation.clone(),
+                                #[cfg(feature = "debug")]
+                                (statistics_recorder.clone(), ServerType::Character),
                             ));
 
                             character_server_task_handle = Some(handle);
@@ -200,6 +290,7 @@ where
                             action_receiver,
                             event_sender,
                             packet_version,
+                            packet_obfuscation_keys,
                         } => {
                             if let Some(handle) = map_server_task_handle.take() {
                                 // TODO: Maybe add a timeout here? Maybe handle Result?
@@ -208,7 +299,7 @@ where
 
                             let packet_handler = Self::create_map_server_packet_handler(packet_callback.clone(), packet_version).unwrap();
                             let handle = local_set.spawn_local(Self::handle_server_connection(
-                                address,
+                                Self::connection_target(&mut replay_source, address, ReplayServer::Map),
                                 action_receiver,
                                 event_sender,
                                 packet_handler,
@@ -221,7 +312,12 @@ where
                                 },
                                 Duration::from_secs(10),
                                 false,
+                                packet_obfuscation_keys.map(PacketObfuscation::new),
                                 thread_time_synchronization.clone(),
+                                #[cfg(feature = "debug")]
+                                network_simulation.clone(),
+                                #[cfg(feature = "debug")]
+                                (statistics_recorder.clone(), ServerType::Map),
                             ));
 
                             map_server_task_handle = Some(handle);
@@ -234,10 +330,9 @@ where
         (command_sender, time_synchronization)
     }
 
-    fn handle_connection<Event>(connection: &mut ServerConnection, event_buffer: &mut NetworkEventBuffer)
-    where
-        Event: DisconnectedEvent,
-    {
+    /// Move all received events to the `event_buffer`. Returns the reason if
+    /// the connection was closed.
+    fn handle_connection(connection: &mut ServerConnection, event_buffer: &mut NetworkEventBuffer) -> Option<DisconnectReason> {
         match connection.take() {
             ServerConnection::Connected {
                 action_sender,
@@ -254,32 +349,150 @@ where
                             event_receiver,
                             packet_version,
                         };
-                        break;
+                        break None;
                     }
                     Err(..) => {
-                        event_buffer.0.push(Event::create_event(DisconnectReason::ConnectionError));
                         *connection = ServerConnection::Disconnected;
-                        break;
+                        break Some(DisconnectReason::ConnectionError);
                     }
                 }
             },
             ServerConnection::ClosingManually => {
-                event_buffer.0.push(Event::create_event(DisconnectReason::ClosedByClient));
                 *connection = ServerConnection::Disconnected;
+                Some(DisconnectReason::ClosedByClient)
             }
-            _ => (),
-        };
+            _ => None,
+        }
+    }
+
+    /// Like [`handle_connection`](Self::handle_connection), but schedules an
+    /// attempt to resume the `session` if the connection was lost.
+    fn handle_resumable_connection<S>(
+        connection: &mut ServerConnection,
+        session: &mut Option<ResumableSession<S>>,
+        reconnect_policy: Option<ReconnectPolicy>,
+        event_buffer: &mut NetworkEventBuffer,
+    ) where
+        S: Session,
+    {
+        let first_new_event = event_buffer.0.len();
+        let disconnect_reason = Self::handle_connection(connection, event_buffer);
+        let connection_lost = disconnect_reason == Some(DisconnectReason::ConnectionError);
+
+        if let Some(resumable_session) = session
+            && !resumable_session.update(reconnect_policy, &mut event_buffer.0, first_new_event, connection_lost)
+        {
+            *session = None;
+        }
+
+        let is_reconnecting = session
+            .as_ref()
+            .is_some_and(|session| matches!(session.state, ReconnectState::Waiting { .. }));
+
+        if let Some(reason) = disconnect_reason
+            && !is_reconnecting
+        {
+            event_buffer.0.push(S::DisconnectedEvent::create_event(reason));
+        }
     }
 
     pub fn get_events(&mut self, events: &mut NetworkEventBuffer) {
-        Self::handle_connection::<LoginServerDisconnectedEvent>(&mut self.login_server_connection, events);
-        Self::handle_connection::<CharacterServerDisconnectedEvent>(&mut self.character_server_connection, events);
-        Self::handle_connection::<MapServerDisconnectedEvent>(&mut self.map_server_connection, events);
+        if let Some(reason) = Self::handle_connection(&mut self.login_server_connection, events) {
+            events.0.push(LoginServerDisconnectedEvent::create_event(reason));
+        }
+
+        Self::handle_resumable_connection(
+            &mut self.character_server_connection,
+            &mut self.character_server_session,
+            self.reconnect_policy,
+            events,
+        );
+        if let Some(reason) = Self::handle_connection(&mut self.map_server_connection, events) {
+            events.0.push(MapServerDisconnectedEvent::create_event(reason));
+        }
+
+        self.resume_sessions();
+    }
+
+    /// Set the policy for resuming the session with the character server after
+    /// losing the connection. Without a policy, losing the connection
+    /// immediately emits a
+    /// [`CharacterServerDisconnected`](NetworkEvent::CharacterServerDisconnected)
+    /// event. The map server session is never resumed.
+    pub fn set_reconnect_policy(&mut self, reconnect_policy: Option<ReconnectPolicy>) {
+        self.reconnect_policy = reconnect_policy;
+    }
+
+    /// Override the keys used to obfuscate the headers of packets sent to the
+    /// map server, for servers that are configured with custom packet keys.
+    /// Without an override, the keys of the packet version are used. Only
+    /// affects connections opened after the call.
+    pub fn set_packet_obfuscation_keys(&mut self, packet_obfuscation_keys: Option<PacketObfuscationKeys>) {
+        self.packet_obfuscation_keys = packet_obfuscation_keys;
+    }
+
+    /// Simulate bad network conditions on all connections, including the ones
+    /// that are already open.
+    #[cfg(feature = "debug")]
+    pub fn set_network_conditions(&mut self, conditions: NetworkConditions) {
+        self.network_simulation.set_conditions(conditions);
+    }
+
+    /// Traffic of all server connections since the networking system was
+    /// spawned.
+    #[cfg(feature = "debug")]
+    pub fn statistics(&self) -> NetworkStatistics {
+        self.statistics_recorder.snapshot()
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn network_conditions(&self) -> NetworkConditions {
+        self.network_simulation.conditions()
+    }
+
+    /// Close all open connections as if the network failed. Unlike
+    /// disconnecting manually, this results in a
+    /// [`DisconnectReason::ConnectionError`].
+    #[cfg(feature = "debug")]
+    pub fn simulate_disconnect(&mut self) {
+        self.network_simulation.disconnect_all();
+    }
+
+    /// Returns `true` while waiting to resume the session with the character
+    /// server.
+    pub fn is_reconnecting(&self) -> bool {
+        self.character_server_session
+            .as_ref()
+            .is_some_and(|session| session.state != ReconnectState::Connected)
+    }
+
+    fn resume_sessions(&mut self) {
+        if let Some(session) = &self.character_server_session
+            && let Some(attempt) = session.due_attempt()
+        {
+            let CharacterServerSession {
+                packet_version,
+                login_data,
+                ref server,
+            } = session.session;
+            self.open_character_server_connection(packet_version, &login_data, server.clone());
+
+            if let Some(session) = &mut self.character_server_session {
+                session.state = ReconnectState::Resuming { attempt };
+            }
+        }
+    }
+
+    fn connection_target(replay_source: &mut Option<ReplaySource>, address: SocketAddr, server: ReplayServer) -> ConnectionTarget {
+        match replay_source {
+            Some(replay_source) => ConnectionTarget::Replay(replay_source.connect(server)),
+            None => ConnectionTarget::Address(address),
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
     async fn handle_server_connection<PingPacket>(
-        address: SocketAddr,
+        connection_target: ConnectionTarget,
         mut action_receiver: UnboundedReceiver<Vec<u8>>,
         event_sender: UnboundedSender<NetworkEvent>,
         mut packet_handler: PacketHandler<NetworkEventList, Callback>,
@@ -289,13 +502,28 @@ where
         // Since our packet handler has no way of working with this, we need to add some special
         // logic.
         mut read_account_id: bool,
+        // Newer clients obfuscate the header of every packet sent to the map server, so it needs
+        // to be applied right before writing to the stream to keep the order intact.
+        mut packet_obfuscation: Option<PacketObfuscation>,
         time_synchronization: Arc<Mutex<TimeSynchronization>>,
+        #[cfg(feature = "debug")] network_simulation: NetworkSimulation,
+        #[cfg(feature = "debug")] (statistics_recorder, server_type): (StatisticsRecorder, ServerType),
     ) -> Result<(), NetworkTaskError>
     where
         PingPacket: Packet + ClientPacket,
         Callback: PacketCallback,
     {
-        let mut stream = TcpStream::connect(address).await.map_err(|_| NetworkTaskError::FailedToConnect)?;
+        let stream: Box<dyn ServerStream> = match connection_target {
+            ConnectionTarget::Address(address) => {
+                Box::new(TcpStream::connect(address).await.map_err(|_| NetworkTaskError::FailedToConnect)?)
+            }
+            ConnectionTarget::Replay(stream) => Box::new(stream.ok_or(NetworkTaskError::FailedToConnect)?),
+        };
+        // Route everything through the simulated network, even without any simulated
+        // conditions, so that they can be changed at any time.
+        #[cfg(feature = "debug")]
+        let stream = network_simulation.wrap(stream);
+        let mut stream = stream;
         let mut interval = tokio::time::interval(ping_frequency);
         let mut buffer = [0u8; 8192];
         let mut cut_off_buffer_base = 0;
@@ -304,13 +532,25 @@ where
 
         loop {
             tokio::select! {
+                // Make sure that the login packet is sent before the first keep-alive packet.
+                biased;
+
                 // Send a packet to the server.
                 action = action_receiver.recv() => {
-                    let Some(action) = action else {
+                    let Some(mut action) = action else {
                         // Channel was closed by the main thread.
                         break Ok(());
                     };
 
+                    packet_handler.packet_callback().outgoing_bytes(&action);
+
+                    #[cfg(feature = "debug")]
+                    statistics_recorder.outgoing_packet(server_type, &action);
+
+                    if let Some(packet_obfuscation) = &mut packet_obfuscation {
+                        packet_obfuscation.apply_to_packet(&mut action);
+                    }
+
                     stream.write_all(&action).await.map_err(|_| NetworkTaskError::ConnectionClosed)?;
                 }
                 // Receive some packets from the server.
@@ -332,21 +572,40 @@ where
 
                     if read_account_id {
                         let account_id = AccountId::from_bytes(&mut byte_reader).unwrap();
+                        packet_handler.packet_callback().incoming_bytes(&data[..byte_reader.get_offset()]);
                         events.push(NetworkEvent::AccountId { account_id });
                         read_account_id = false;
                     }
 
                     while !byte_reader.is_empty() {
-                        match packet_handler.process_one(&mut byte_reader) {
+                        let packet_start = byte_reader.get_offset();
+                        let result = packet_handler.process_one(&mut byte_reader);
+
+                        // Unhandled packets and errors stop the stream, so everything after them is
+                        // consumed as well. The same goes for packets that don't fit into the buffer.
+                        let consumed_end = match result {
+                            HandlerResult::Ok(..) => Some(byte_reader.get_offset()),
+                            HandlerResult::PacketCutOff if packet_start != 0 => None,
+                            HandlerResult::PacketCutOff | HandlerResult::UnhandledPacket | HandlerResult::InternalError(..) => Some(data.len()),
+                        };
+
+                        if let Some(consumed_end) = consumed_end {
+                            let frame = &data[packet_start..consumed_end];
+
+                            packet_handler.packet_callback().incoming_bytes(frame);
+
+                            #[cfg(feature = "debug")]
+                            statistics_recorder.incoming_packet(server_type, frame);
+                        }
+
+                        match result {
                             HandlerResult::Ok(packet_events) => events.extend(packet_events.0.into_iter()),
                             HandlerResult::PacketCutOff => {
-                                let packet_start = byte_reader.get_offset();
                                 let packet_end = cut_off_buffer_base + received_bytes;
 
                                 if packet_start == 0 {
                                     // If the packet_start is 0, that means the packet is allegidly bigger than the MTU of a TCP packet.
                                     // We limit the size of a packet to the MTU, to avoid getting stuck on packets that are parsed incorrectly.
-                                    // TODO: Call the packet callback?
                                     cut_off_buffer_base = 0;
                                     break;
                                 }
@@ -370,7 +629,16 @@ where
 
                     for event in events.drain(..) {
                         if let NetworkEvent::UpdateClientTick {client_tick,received_at} = &event && let Ok(mut time_synchronization) = time_synchronization.lock() {
+                            #[cfg(feature = "debug")]
+                            let predicted_client_tick = time_synchronization.predicted_client_tick(*received_at);
+
                             time_synchronization.estimated_client_tick(client_tick.0, *received_at);
+
+                            #[cfg(feature = "debug")]
+                            statistics_recorder.time_synchronization(
+                                time_synchronization.round_trip_time(),
+                                time_synchronization.client_tick - predicted_client_tick,
+                            );
                         }
 
                         event_sender.send(event).map_err(|_| NetworkTaskError::ConnectionClosed)?;
@@ -379,6 +647,16 @@ where
                 // Send a keep-alive packet to the server.
                 _ = interval.tick() => {
                     ping_factory(&time_synchronization).packet_to_bytes(&mut byte_writer).unwrap();
+
+                    packet_handler.packet_callback().outgoing_bytes(byte_writer.as_slice());
+
+                    #[cfg(feature = "debug")]
+                    statistics_recorder.outgoing_packet(server_type, byte_writer.as_slice());
+
+                    if let Some(packet_obfuscation) = &mut packet_obfuscation {
+                        packet_obfuscation.apply_to_packet(byte_writer.as_mut_slice());
+                    }
+
                     stream.write_all(byte_writer.as_slice()).await.map_err(|_| NetworkTaskError::ConnectionClosed)?;
                     byte_writer.clear();
                 }
@@ -436,6 +714,20 @@ where
             return;
         }
 
+        self.character_server_session = Some(ResumableSession::new(CharacterServerSession {
+            packet_version,
+            login_data: *login_data,
+            server: server.clone(),
+        }));
+        self.open_character_server_connection(packet_version, login_data, server);
+    }
+
+    fn open_character_server_connection(
+        &mut self,
+        packet_version: SupportedPacketVersion,
+        login_data: &LoginServerLoginData,
+        server: CharacterServerInformation,
+    ) {
         let (action_sender, action_receiver) = tokio::sync::mpsc::unbounded_channel();
         let (event_sender, event_receiver) = tokio::sync::mpsc::unbounded_channel();
 
@@ -482,6 +774,15 @@ where
             return;
         }
 
+        self.open_map_server_connection(packet_version, login_server_login_data, character_server_login_data);
+    }
+
+    fn open_map_server_connection(
+        &mut self,
+        packet_version: SupportedPacketVersion,
+        login_server_login_data: &LoginServerLoginData,
+        character_server_login_data: CharacterServerLoginData,
+    ) {
         let (action_sender, action_receiver) = tokio::sync::mpsc::unbounded_channel();
         let (event_sender, event_receiver) = tokio::sync::mpsc::unbounded_channel();
 
@@ -493,6 +794,7 @@ where
                 action_receiver,
                 event_sender,
                 packet_version,
+                packet_obfuscation_keys: self.packet_obfuscation_keys.or(packet_version.packet_obfuscation_keys()),
             })
             .expect("network thread dropped");
 
@@ -526,6 +828,7 @@ where
     }
 
     pub fn disconnect_from_character_server(&mut self) {
+        self.character_server_session = None;
         self.character_server_connection = ServerConnection::ClosingManually;
     }
 
@@ -593,6 +896,10 @@ where
     ) -> Result<PacketHandler<NetworkEventList, Callback>, DuplicateHandlerError> {
         let mut packet_handler = PacketHandler::<NetworkEventList, Callback>::with_callback(packet_callback);
 
+        // Add the lengths first, so the lengths of the registered packets take
+        // precedence.
+        packet_handler.add_packet_lengths(&packet_version.packet_length_table());
+
         match packet_version {
             SupportedPacketVersion::_20220406 => packet_versions::version_20220406::register_login_server_packets(&mut packet_handler)?,
         }
@@ -606,6 +913,10 @@ where
     ) -> Result<PacketHandler<NetworkEventList, Callback>, DuplicateHandlerError> {
         let mut packet_handler = PacketHandler::<NetworkEventList, Callback>::with_callback(packet_callback);
 
+        // Add the lengths first, so the lengths of the registered packets take
+        // precedence.
+        packet_handler.add_packet_lengths(&packet_version.packet_length_table());
+
         match packet_version {
             SupportedPacketVersion::_20220406 => packet_versions::version_20220406::register_character_server_packets(&mut packet_handler)?,
         }
@@ -619,6 +930,10 @@ where
     ) -> Result<PacketHandler<NetworkEventList, Callback>, DuplicateHandlerError> {
         let mut packet_handler = PacketHandler::<NetworkEventList, Callback>::with_callback(packet_callback);
 
+        // Add the lengths first, so the lengths of the registered packets take
+        // precedence.
+        packet_handler.add_packet_lengths(&packet_version.packet_length_table());
+
         match packet_version {
             SupportedPacketVersion::_20220406 => packet_versions::version_20220406::register_map_server_packets(&mut packet_handler)?,
         }
@@ -732,6 +1047,18 @@ where
         }
     }
 
+    pub fn use_item(&mut self, item_index: InventoryIndex, account_id: AccountId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(UseItemPacket::new(item_index, account_id)),
+        }
+    }
+
+    pub fn drop_item(&mut self, item_index: InventoryIndex, amount: u16) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(DropItemPacket::new(item_index, amount)),
+        }
+    }
+
     pub fn send_chat_message(&mut self, player_name: &str, text: &str) -> Result<(), NotConnectedError> {
         let message = format!("{} : {}", player_name, text);
 
@@ -818,6 +1145,24 @@ where
         }
     }
 
+    pub fn send_whisper(&mut self, recipient_name: String, text: String) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(WhisperPacket::new(recipient_name, text)),
+        }
+    }
+
+    pub fn request_trade(&mut self, entity_id: EntityId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestTradePacket::new(entity_id)),
+        }
+    }
+
+    pub fn invite_to_party(&mut self, name: String) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(InviteToPartyPacket::new(name)),
+        }
+    }
+
     pub fn remove_friend(&mut self, account_id: AccountId, character_id: CharacterId) -> Result<(), NotConnectedError> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RemoveFriendPacket::new(account_id, character_id)),
@@ -893,11 +1238,222 @@ where
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(LevelUpSkillPacket::new(skill_id)),
         }
     }
+
+    pub fn move_item_to_cart(&mut self, inventory_index: InventoryIndex, amount: u32) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(MoveItemToCartPacket::new(inventory_index, amount)),
+        }
+    }
+
+    pub fn move_item_from_cart(&mut self, cart_index: InventoryIndex, amount: u32) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(MoveItemFromCartPacket::new(cart_index, amount)),
+        }
+    }
+
+    pub fn remove_cart(&mut self) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RemoveCartPacket::new()),
+        }
+    }
+
+    pub fn open_mailbox(&mut self, open_type: MailOpenType) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(OpenMailboxPacket::new(open_type, MailId(0))),
+        }
+    }
+
+    pub fn request_next_mail_page(&mut self, open_type: MailOpenType, last_mail_id: MailId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestNextMailPagePacket::new(open_type, last_mail_id)),
+        }
+    }
+
+    pub fn close_mailbox(&mut self) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(CloseMailboxPacket::new()),
+        }
+    }
+
+    pub fn read_mail(&mut self, open_type: MailOpenType, mail_id: MailId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(ReadMailPacket::new(open_type, mail_id)),
+        }
+    }
+
+    pub fn delete_mail(&mut self, open_type: MailOpenType, mail_id: MailId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(DeleteMailPacket::new(open_type, mail_id)),
+        }
+    }
+
+    pub fn claim_mail_zeny(&mut self, open_type: MailOpenType, mail_id: MailId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(ClaimMailZenyPacket::new(mail_id, open_type)),
+        }
+    }
+
+    pub fn claim_mail_items(&mut self, open_type: MailOpenType, mail_id: MailId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(ClaimMailItemsPacket::new(mail_id, open_type)),
+        }
+    }
+
+    pub fn open_write_mail(&mut self, recipient_name: String) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(OpenWriteMailPacket::new(recipient_name)),
+        }
+    }
+
+    pub fn cancel_write_mail(&mut self) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(CancelWriteMailPacket::new()),
+        }
+    }
+
+    pub fn add_mail_item(&mut self, inventory_index: InventoryIndex, amount: u16) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(AddMailItemPacket::new(inventory_index, amount)),
+        }
+    }
+
+    pub fn remove_mail_item(&mut self, inventory_index: InventoryIndex, amount: u16) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RemoveMailItemPacket::new(inventory_index, amount)),
+        }
+    }
+
+    pub fn check_mail_recipient(&mut self, name: String) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(CheckMailRecipientPacket::new(name)),
+        }
+    }
+
+    pub fn send_mail(
+        &mut self,
+        recipient_name: String,
+        recipient_character_id: CharacterId,
+        sender_name: String,
+        title: String,
+        text: String,
+        zeny: u64,
+    ) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => {
+                // The lengths include the null terminator.
+                let title_length = title.len() as u16 + 1;
+                let text_length = text.len() as u16 + 1;
+
+                self.send_map_server_packet(SendMailPacket {
+                    recipient_name,
+                    sender_name,
+                    zeny,
+                    title_length,
+                    text_length,
+                    recipient_character_id,
+                    title,
+                    text,
+                })
+            }
+        }
+    }
+
+    pub fn try_capture_pet(&mut self, entity_id: EntityId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(TryCapturePetPacket::new(entity_id)),
+        }
+    }
+
+    pub fn select_pet_egg(&mut self, index: InventoryIndex) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(SelectPetEggPacket::new(index)),
+        }
+    }
+
+    pub fn pet_command(&mut self, command: PetCommand) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(PetCommandPacket::new(command)),
+        }
+    }
+
+    pub fn homunculus_command(&mut self, command: HomunculusCommand) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(HomunculusCommandPacket::new(command)),
+        }
+    }
+
+    pub fn homunculus_move(&mut self, entity_id: EntityId, position: WorldPosition) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestHomunculusMovePacket::new(entity_id, position)),
+        }
+    }
+
+    pub fn homunculus_move_to_owner(&mut self, entity_id: EntityId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestHomunculusMoveToOwnerPacket::new(entity_id)),
+        }
+    }
+
+    pub fn homunculus_attack(&mut self, entity_id: EntityId, target_entity_id: EntityId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => {
+                self.send_map_server_packet(RequestHomunculusAttackPacket::new(entity_id, target_entity_id, 1))
+            }
+        }
+    }
+
+    pub fn level_up_homunculus_skill(&mut self, skill_id: SkillId) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(LevelUpHomunculusSkillPacket::new(skill_id)),
+        }
+    }
+
+    pub fn create_chat_room(&mut self, title: String, limit: u16, is_public: bool, password: String) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => {
+                self.send_map_server_packet(CreateChatRoomPacket::new(limit, is_public as u8, password, title))
+            }
+        }
+    }
+
+    pub fn join_chat_room(&mut self, chat_room_id: ChatRoomId, password: String) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(JoinChatRoomPacket::new(chat_room_id, password)),
+        }
+    }
+
+    pub fn change_chat_room(&mut self, title: String, limit: u16, is_public: bool, password: String) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => {
+                self.send_map_server_packet(ChangeChatRoomPacket::new(limit, is_public as u8, password, title))
+            }
+        }
+    }
+
+    pub fn change_chat_room_owner(&mut self, name: String) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(ChangeChatRoomOwnerPacket::new(name)),
+        }
+    }
+
+    pub fn kick_chat_room_member(&mut self, name: String) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(KickChatRoomMemberPacket::new(name)),
+        }
+    }
+
+    pub fn leave_chat_room(&mut self) -> Result<(), NotConnectedError> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(LeaveChatRoomPacket::new()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod packet_handlers {
-    use ragnarok_packets::handler::NoPacketCallback;
+    use ragnarok_bytes::ByteReader;
+    use ragnarok_packets::handler::{HandlerResult, NoPacketCallback};
+    use ragnarok_packets::{Packet, PacketHeader, PacketLength, ServerMessagePacket, ServerTickPacket};
 
     use crate::{NetworkingSystem, SupportedPacketVersion};
 
@@ -918,4 +1474,54 @@ mod packet_handlers {
         let result = NetworkingSystem::create_map_server_packet_handler(NoPacketCallback, SupportedPacketVersion::_20220406);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn packet_length_table() {
+        let packet_lengths = SupportedPacketVersion::_20220406.packet_length_table();
+
+        assert_eq!(packet_lengths.get(ServerTickPacket::HEADER), Some(PacketLength::Fixed(6)));
+        assert_eq!(packet_lengths.get(ServerMessagePacket::HEADER), Some(PacketLength::Variable));
+    }
+
+    #[test]
+    fn all_handled_packets_in_length_table() {
+        let packet_version = SupportedPacketVersion::_20220406;
+        let packet_lengths = packet_version.packet_length_table();
+        let packet_handlers = [
+            NetworkingSystem::create_login_server_packet_handler(NoPacketCallback, packet_version).unwrap(),
+            NetworkingSystem::create_character_server_packet_handler(NoPacketCallback, packet_version).unwrap(),
+            NetworkingSystem::create_map_server_packet_handler(NoPacketCallback, packet_version).unwrap(),
+        ];
+
+        for packet_handler in &packet_handlers {
+            for (header, length) in packet_handler.packet_length_table().iter() {
+                assert_eq!(
+                    packet_lengths.get(header),
+                    Some(length),
+                    "length of packet 0x{:04X} doesn't match",
+                    header.0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn skip_packet_without_type() {
+        // The number of online players, which the client has no packet type for.
+        const USER_COUNT_HEADER: PacketHeader = PacketHeader(0x00C2);
+
+        let packet_version = SupportedPacketVersion::_20220406;
+        let mut packet_handler = NetworkingSystem::create_map_server_packet_handler(NoPacketCallback, packet_version).unwrap();
+        let bytes = [0xC2, 0x00, 0x2A, 0x00, 0x00, 0x00, 0x7F, 0x00, 0x01, 0x00, 0x00, 0x00];
+        let mut byte_reader = ByteReader::without_metadata(&bytes);
+
+        assert_eq!(
+            packet_version.packet_length_table().get(USER_COUNT_HEADER),
+            Some(PacketLength::Fixed(6))
+        );
+        assert!(matches!(packet_handler.process_one(&mut byte_reader), HandlerResult::Ok(events) if events.0.is_empty()));
+        assert_eq!(byte_reader.get_offset(), 6);
+        assert!(matches!(packet_handler.process_one(&mut byte_reader), HandlerResult::Ok(events) if !events.0.is_empty()));
+        assert!(byte_reader.is_empty());
+    }
 }
@@ -1,7 +1,28 @@
 pub mod version_20220406;
 
+use ragnarok_packets::length_table::PacketLengthTable;
+use ragnarok_packets::obfuscation::PacketObfuscationKeys;
+
 /// All supported packet versions.
 #[derive(Debug, Clone, Copy)]
 pub enum SupportedPacketVersion {
     _20220406,
 }
+
+impl SupportedPacketVersion {
+    /// Keys used to obfuscate the headers of packets sent to the map server.
+    /// `None` if the client date doesn't obfuscate packet headers.
+    pub fn packet_obfuscation_keys(self) -> Option<PacketObfuscationKeys> {
+        match self {
+            SupportedPacketVersion::_20220406 => Some(version_20220406::PACKET_OBFUSCATION_KEYS),
+        }
+    }
+
+    /// Lengths of all packets that the servers can send. Used to skip packets
+    /// that the client doesn't handle.
+    pub fn packet_length_table(self) -> PacketLengthTable {
+        match self {
+            SupportedPacketVersion::_20220406 => version_20220406::packet_length_table(),
+        }
+    }
+}
@@ -4,8 +4,11 @@ use std::rc::Rc;
 use std::time::Instant;
 
 use ragnarok_packets::handler::{DuplicateHandlerError, PacketCallback, PacketHandler};
+use ragnarok_packets::length_table::PacketLengthTable;
+use ragnarok_packets::obfuscation::PacketObfuscationKeys;
 use ragnarok_packets::*;
 
+use crate::entity::cart_type_from_effect_state;
 use crate::event::{NetworkEventList, NoNetworkEvents};
 use crate::items::ItemQuantity;
 use crate::{
@@ -13,6 +16,25 @@ use crate::{
     NoMetadata, ShopItem, UnifiedCharacterSelectionFailedReason, UnifiedLoginFailedReason,
 };
 
+/// Packet keys of the 2022-04-06 client. The client ships with all keys set to
+/// zero, so the headers are sent unchanged.
+pub const PACKET_OBFUSCATION_KEYS: PacketObfuscationKeys = PacketObfuscationKeys {
+    key1: 0x00000000,
+    key2: 0x00000000,
+    key3: 0x00000000,
+};
+
+/// Inventory type that the server uses for the merchant cart.
+const CART_INVENTORY_TYPE: u8 = 1;
+
+/// Lengths of all packets that the servers can send, including the ones that
+/// are not handled by the client.
+pub fn packet_length_table() -> PacketLengthTable {
+    include_str!("packet_lengths_20220406.txt")
+        .parse()
+        .expect("invalid packet length table")
+}
+
 pub fn register_login_server_packets<Callback>(
     packet_handler: &mut PacketHandler<NetworkEventList, Callback>,
 ) -> Result<(), DuplicateHandlerError>
@@ -36,6 +58,7 @@ where
                 "Someone has already logged in with this id",
             ),
             LoginFailedReason::AlreadyOnline => (UnifiedLoginFailedReason::AlreadyOnline, "Already online"),
+            LoginFailedReason::AuthenticationFailed => (UnifiedLoginFailedReason::RejectedFromServer, "Rejected from server"),
         };
 
         NetworkEvent::LoginServerConnectionFailed { reason, message }
@@ -74,6 +97,7 @@ where
             LoginFailedReason::ServerClosed => "Server closed",
             LoginFailedReason::AlreadyLoggedIn => "Someone has already logged in with this id",
             LoginFailedReason::AlreadyOnline => "Already online",
+            LoginFailedReason::AuthenticationFailed => "Rejected from server",
         };
 
         NetworkEvent::CharacterServerConnectionFailed { reason, message }
@@ -162,6 +186,18 @@ where
     // This variable provides some transient storage shared by all the inventory
     // handlers.
     let inventory_items: Rc<RefCell<Option<Vec<InventoryItem<NoMetadata>>>>> = Rc::new(RefCell::new(None));
+    // The cart uses the same packets as the inventory, just with a different
+    // inventory type.
+    let cart_items: Rc<RefCell<Option<Vec<InventoryItem<NoMetadata>>>>> = Rc::new(RefCell::new(None));
+    let select_items = {
+        let inventory_items = inventory_items.clone();
+        let cart_items = cart_items.clone();
+
+        move |inventory_type: u8| match inventory_type {
+            CART_INVENTORY_TYPE => cart_items.clone(),
+            _ => inventory_items.clone(),
+        }
+    };
 
     packet_handler.register(|_: MapServerPingPacket| NoNetworkEvents)?;
     packet_handler.register(|packet: BroadcastMessagePacket| NetworkEvent::ChatMessage {
@@ -315,7 +351,9 @@ where
         NetworkEvent::UpdateStat { stat_type }
     })?;
     packet_handler.register_noop::<UpdateAttackRangePacket>()?;
-    packet_handler.register_noop::<NewMailStatusPacket>()?;
+    packet_handler.register(|packet: NewMailStatusPacket| NetworkEvent::NewMailStatus {
+        has_new_mail: packet.new_available != 0,
+    })?;
     packet_handler.register_noop::<AchievementUpdatePacket>()?;
     packet_handler.register_noop::<AchievementListPacket>()?;
     packet_handler.register_noop::<CriticalWeightUpdatePacket>()?;
@@ -331,18 +369,18 @@ where
         _ => None,
     })?;
     packet_handler.register({
-        let inventory_items = inventory_items.clone();
+        let select_items = select_items.clone();
 
-        move |_: InventoyStartPacket| {
-            *inventory_items.borrow_mut() = Some(Vec::new());
+        move |packet: InventoyStartPacket| {
+            *select_items(packet.inventory_type).borrow_mut() = Some(Vec::new());
             NoNetworkEvents
         }
     })?;
     packet_handler.register({
-        let inventory_items = inventory_items.clone();
+        let select_items = select_items.clone();
 
         move |packet: RegularItemListPacket| {
-            inventory_items
+            select_items(packet.inventory_type)
                 .borrow_mut()
                 .as_mut()
                 .expect("Unexpected inventory packet")
@@ -376,10 +414,10 @@ where
         }
     })?;
     packet_handler.register({
-        let inventory_items = inventory_items.clone();
+        let select_items = select_items.clone();
 
         move |packet: EquippableItemListPacket| {
-            inventory_items
+            select_items(packet.inventory_type)
                 .borrow_mut()
                 .as_mut()
                 .expect("Unexpected inventory packet")
@@ -424,12 +462,15 @@ where
             NoNetworkEvents
         }
     })?;
-    packet_handler.register({
-        let inventory_items = inventory_items.clone();
+    packet_handler.register(move |packet: InventoyEndPacket| {
+        let items = select_items(packet.inventory_type)
+            .borrow_mut()
+            .take()
+            .expect("Unexpected inventory end packet");
 
-        move |_: InventoyEndPacket| {
-            let items = inventory_items.borrow_mut().take().expect("Unexpected inventory end packet");
-            NetworkEvent::SetInventory { items }
+        match packet.inventory_type {
+            CART_INVENTORY_TYPE => NetworkEvent::SetCart { items },
+            _ => NetworkEvent::SetInventory { items },
         }
     })?;
     packet_handler.register_noop::<EquippableSwitchItemListPacket>()?;
@@ -492,7 +533,10 @@ where
         NetworkEvent::AddChoiceButtons { choices, npc_id }
     })?;
     packet_handler.register_noop::<DisplaySpecialEffectPacket>()?;
-    packet_handler.register_noop::<DisplaySkillCooldownPacket>()?;
+    packet_handler.register(|packet: DisplaySkillCooldownPacket| NetworkEvent::SkillCooldown {
+        skill_id: packet.skill_id,
+        duration: packet.duration,
+    })?;
     packet_handler.register_noop::<DisplaySkillEffectAndDamagePacket>()?;
     packet_handler.register(|packet: DisplaySkillEffectNoDamagePacket| NetworkEvent::HealEffect {
         entity_id: packet.destination_entity_id,
@@ -525,7 +569,10 @@ where
     })?;
     packet_handler.register_noop::<DisplayGainedExperiencePacket>()?;
     packet_handler.register_noop::<DisplayImagePacket>()?;
-    packet_handler.register_noop::<StateChangePacket>()?;
+    packet_handler.register(|packet: StateChangePacket| NetworkEvent::UpdateEntityCart {
+        entity_id: packet.entity_id,
+        cart_type: cart_type_from_effect_state(packet.effect_state),
+    })?;
 
     packet_handler.register(|packet: QuestEffectPacket| match packet.effect {
         QuestEffect::None => NetworkEvent::RemoveQuestEffect {
@@ -615,6 +662,257 @@ where
         index: packet.index,
         amount: packet.amount,
     })?;
+    packet_handler.register(|packet: CartInformationPacket| NetworkEvent::UpdateCartInformation {
+        item_count: packet.item_count,
+        maximum_item_count: packet.maximum_item_count,
+        weight: packet.weight,
+        maximum_weight: packet.maximum_weight,
+    })?;
+    packet_handler.register(|packet: CartItemAddedPacket| {
+        let CartItemAddedPacket {
+            index,
+            amount,
+            item_id,
+            item_type,
+            is_identified,
+            is_broken,
+            refinement_level,
+            cards,
+            option_data,
+            enchantment_level,
+        } = packet;
+
+        // The packet doesn't contain the equip position, so we have to rely on the
+        // item type to know if the item is equippable.
+        let is_equippable = matches!(item_type, 4 | 5 | 8 | 12);
+
+        let details = match is_equippable {
+            true => InventoryItemDetails::Equippable {
+                equip_position: EquipPosition::empty(),
+                equipped_position: EquipPosition::empty(),
+                bind_on_equip_type: 0,
+                w_item_sprite_number: 0,
+                option_count: option_data.len() as u8,
+                option_data,
+                refinement_level,
+                enchantment_level,
+                flags: {
+                    let mut flags = EquippableItemFlags::empty();
+                    flags.set(EquippableItemFlags::IDENTIFIED, is_identified != 0);
+                    flags.set(EquippableItemFlags::IS_BROKEN, is_broken != 0);
+                    flags
+                },
+            },
+            false => InventoryItemDetails::Regular {
+                // Stacks can't hold more than `u16::MAX` items.
+                amount: u16::try_from(amount).unwrap_or(u16::MAX),
+                equipped_position: EquipPosition::empty(),
+                flags: {
+                    let mut flags = RegularItemFlags::empty();
+                    flags.set(RegularItemFlags::IDENTIFIED, is_identified != 0);
+                    flags
+                },
+            },
+        };
+
+        let item = InventoryItem {
+            metadata: NoMetadata,
+            index,
+            item_id,
+            item_type,
+            slot: cards,
+            hire_expiration_date: 0,
+            details,
+        };
+
+        NetworkEvent::CartItemAdded { item }
+    })?;
+    packet_handler.register(|packet: CartItemRemovedPacket| NetworkEvent::CartItemRemoved {
+        index: packet.index,
+        amount: packet.amount,
+    })?;
+    packet_handler.register(|packet: MoveItemToCartFailedPacket| NetworkEvent::MoveItemToCartFailed { result: packet.result })?;
+    packet_handler.register(|_: CartRemovedPacket| NetworkEvent::CartRemoved)?;
+    packet_handler.register(|packet: MailListPacket| NetworkEvent::MailList {
+        mails: packet.mails,
+        is_last_page: packet.is_end != 0,
+    })?;
+    packet_handler.register(|packet: MailContentPacket| {
+        let items = packet
+            .items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let MailItemInformation {
+                    amount,
+                    item_id,
+                    is_identified,
+                    is_broken,
+                    refinement_level,
+                    cards,
+                    equip_position,
+                    item_type,
+                    view_sprite,
+                    bind_on_equip_type,
+                    option_data,
+                    enchantment_level,
+                } = item;
+
+                let details = match equip_position.is_empty() {
+                    true => InventoryItemDetails::Regular {
+                        amount,
+                        equipped_position: EquipPosition::empty(),
+                        flags: {
+                            let mut flags = RegularItemFlags::empty();
+                            flags.set(RegularItemFlags::IDENTIFIED, is_identified != 0);
+                            flags
+                        },
+                    },
+                    false => InventoryItemDetails::Equippable {
+                        equip_position,
+                        equipped_position: EquipPosition::empty(),
+                        bind_on_equip_type,
+                        w_item_sprite_number: view_sprite,
+                        option_count: option_data.len() as u8,
+                        option_data,
+                        refinement_level,
+                        enchantment_level,
+                        flags: {
+                            let mut flags = EquippableItemFlags::empty();
+                            flags.set(EquippableItemFlags::IDENTIFIED, is_identified != 0);
+                            flags.set(EquippableItemFlags::IS_BROKEN, is_broken != 0);
+                            flags
+                        },
+                    },
+                };
+
+                // Attachments don't have an inventory index, so we use the position in the
+                // mail instead.
+                InventoryItem {
+                    metadata: NoMetadata,
+                    index: InventoryIndex(index as u16),
+                    item_id,
+                    item_type,
+                    slot: cards,
+                    hire_expiration_date: 0,
+                    details,
+                }
+            })
+            .collect();
+
+        NetworkEvent::MailContent {
+            open_type: packet.open_type,
+            mail_id: packet.mail_id,
+            text: packet.text,
+            zeny: packet.zeny,
+            items,
+        }
+    })?;
+    packet_handler.register(|packet: MailDeletedPacket| NetworkEvent::MailDeleted { mail_id: packet.mail_id })?;
+    packet_handler.register(|packet: ClaimMailZenyResultPacket| NetworkEvent::MailZenyClaimed {
+        mail_id: packet.mail_id,
+        result: packet.result,
+    })?;
+    packet_handler.register(|packet: ClaimMailItemsResultPacket| NetworkEvent::MailItemsClaimed {
+        mail_id: packet.mail_id,
+        result: packet.result,
+    })?;
+    packet_handler.register(|packet: OpenWriteMailResultPacket| NetworkEvent::WriteMailOpened {
+        recipient_name: packet.recipient_name,
+        success: packet.success != 0,
+    })?;
+    packet_handler.register(|packet: CheckMailRecipientResultPacket| NetworkEvent::MailRecipientChecked {
+        character_id: (packet.character_id != CharacterId(0)).then_some(packet.character_id),
+    })?;
+    packet_handler.register(|packet: AddMailItemResultPacket| NetworkEvent::MailItemAttached {
+        result: packet.result,
+        index: packet.inventory_index,
+        amount: packet.amount,
+    })?;
+    packet_handler.register(|packet: RemoveMailItemResultPacket| NetworkEvent::MailItemDetached {
+        success: packet.success != 0,
+        index: packet.inventory_index,
+        amount: packet.amount,
+    })?;
+    packet_handler.register(|packet: SendMailResultPacket| NetworkEvent::MailSent { result: packet.result })?;
+    packet_handler.register(|_: StartPetCapturePacket| NetworkEvent::StartPetCapture)?;
+    packet_handler.register(|packet: CapturePetResultPacket| NetworkEvent::PetCaptureResult {
+        success: packet.success != 0,
+    })?;
+    packet_handler.register(|packet: PetEggListPacket| NetworkEvent::PetEggList { indices: packet.indices })?;
+    packet_handler.register(|packet: PetInformationPacket| NetworkEvent::UpdatePetInformation {
+        name: packet.name,
+        level: packet.level,
+        hunger: packet.hunger,
+        intimacy: packet.intimacy,
+        accessory_id: packet.accessory_id,
+        job_id: packet.job_id,
+    })?;
+    packet_handler.register(|packet: PetStateChangePacket| NetworkEvent::UpdatePetState {
+        entity_id: packet.entity_id,
+        state_type: packet.state_type,
+        value: packet.value,
+    })?;
+    packet_handler.register(|packet: FeedPetResultPacket| NetworkEvent::PetFed {
+        success: packet.success != 0,
+        item_id: packet.item_id,
+    })?;
+    packet_handler.register(
+        |packet: HomunculusInformationPacket| NetworkEvent::UpdateHomunculusInformation {
+            name: packet.name,
+            level: packet.level,
+            hunger: packet.hunger,
+            intimacy: packet.intimacy,
+            stats: packet.stats,
+        },
+    )?;
+    packet_handler.register(|packet: HomunculusStateChangePacket| NetworkEvent::UpdateHomunculusState {
+        entity_id: packet.entity_id,
+        state_type: packet.state_type,
+        value: packet.value,
+    })?;
+    packet_handler.register(|packet: HomunculusSkillListPacket| {
+        let HomunculusSkillListPacket { skill_information } = packet;
+        NetworkEvent::HomunculusSkillTree { skill_information }
+    })?;
+    packet_handler.register(|packet: FeedHomunculusResultPacket| NetworkEvent::HomunculusFed {
+        success: packet.success != 0,
+        item_id: packet.item_id,
+    })?;
+    packet_handler.register(|packet: CreateChatRoomResultPacket| NetworkEvent::ChatRoomCreated { result: packet.result })?;
+    packet_handler.register(|packet: ChatRoomAppearedPacket| NetworkEvent::UpdateChatRoom {
+        owner_entity_id: packet.owner_entity_id,
+        chat_room_id: packet.chat_room_id,
+        limit: packet.limit,
+        user_count: packet.user_count,
+        room_type: packet.room_type,
+        title: packet.title,
+    })?;
+    packet_handler.register(|packet: ChatRoomChangedPacket| NetworkEvent::UpdateChatRoom {
+        owner_entity_id: packet.owner_entity_id,
+        chat_room_id: packet.chat_room_id,
+        limit: packet.limit,
+        user_count: packet.user_count,
+        room_type: packet.room_type,
+        title: packet.title,
+    })?;
+    packet_handler.register(|packet: ChatRoomDestroyedPacket| NetworkEvent::ChatRoomDestroyed {
+        chat_room_id: packet.chat_room_id,
+    })?;
+    packet_handler.register(|packet: ChatRoomJoinedPacket| NetworkEvent::ChatRoomJoined {
+        chat_room_id: packet.chat_room_id,
+        members: packet.members,
+    })?;
+    packet_handler.register(|packet: JoinChatRoomFailedPacket| NetworkEvent::JoinChatRoomFailed { reason: packet.reason })?;
+    packet_handler.register(|packet: ChatRoomMemberJoinedPacket| NetworkEvent::ChatRoomMemberJoined { name: packet.name })?;
+    packet_handler.register(|packet: ChatRoomMemberLeftPacket| NetworkEvent::ChatRoomMemberLeft {
+        name: packet.name,
+        was_kicked: packet.was_kicked != 0,
+    })?;
+    packet_handler.register(|packet: ChatRoomRoleChangedPacket| NetworkEvent::ChatRoomRoleChanged {
+        name: packet.name,
+        role: packet.role,
+    })?;
     packet_handler.register(|packet: ServerTickPacket| NetworkEvent::UpdateClientTick {
         client_tick: packet.client_tick,
         received_at: Instant::now(),
@@ -725,9 +1023,25 @@ where
     packet_handler.register_noop::<Packet8302>()?;
     packet_handler.register_noop::<Packet0b18>()?;
     packet_handler.register_noop::<ConnectionRefusedPacket>()?;
-    packet_handler.register(|packet: MapServerLoginSuccessPacket| NetworkEvent::UpdateClientTick {
-        client_tick: packet.client_tick,
-        received_at: Instant::now(),
+    packet_handler.register(|packet: LoginFailedPacket| {
+        let reason = packet.reason;
+        let message = match reason {
+            LoginFailedReason::ServerClosed => "Server closed",
+            LoginFailedReason::AlreadyLoggedIn => "Someone has already logged in with this id",
+            LoginFailedReason::AlreadyOnline => "Already online",
+            LoginFailedReason::AuthenticationFailed => "Rejected from server",
+        };
+
+        NetworkEvent::MapServerConnectionFailed { reason, message }
+    })?;
+    packet_handler.register(|packet: MapServerLoginSuccessPacket| {
+        vec![
+            NetworkEvent::UpdateClientTick {
+                client_tick: packet.client_tick,
+                received_at: Instant::now(),
+            },
+            NetworkEvent::MapServerConnected { position: packet.position },
+        ]
     })?;
     packet_handler.register(|packet: RestartResponsePacket| match packet.result {
         RestartResponseStatus::Ok => NetworkEvent::LoggedOut,
@@ -743,8 +1057,31 @@ where
             color: MessageColor::Error,
         },
     })?;
-    packet_handler.register_noop::<UseSkillSuccessPacket>()?;
+    packet_handler.register(|packet: UseSkillSuccessPacket| {
+        let UseSkillSuccessPacket {
+            source_entity,
+            destination_entity,
+            position,
+            skill_id,
+            delay_time,
+            ..
+        } = packet;
+
+        // Skills that are cast on the ground don't have a target entity.
+        let target_position = (destination_entity == EntityId(0)).then_some(position);
+
+        NetworkEvent::SkillCastStarted {
+            source_entity_id: source_entity,
+            target_entity_id: destination_entity,
+            target_position,
+            skill_id,
+            cast_time: delay_time,
+        }
+    })?;
     packet_handler.register_noop::<ToUseSkillSuccessPacket>()?;
+    packet_handler.register(|packet: CastCancelPacket| NetworkEvent::SkillCastCancelled {
+        entity_id: packet.entity_id,
+    })?;
     packet_handler.register(|packet: NotifySkillUnitPacket| {
         let NotifySkillUnitPacket {
             entity_id,
@@ -0,0 +1,205 @@
+use std::time::{Duration, Instant};
+
+use ragnarok_packets::CharacterServerInformation;
+
+use crate::event::{CharacterServerDisconnectedEvent, DisconnectedEvent};
+use crate::{LoginServerLoginData, NetworkEvent, SupportedPacketVersion};
+
+/// Controls how the networking system tries to resume a session with the
+/// character server after losing the connection.
+///
+/// The delay before each attempt doubles, starting at `initial_delay` and
+/// never exceeding `maximum_delay`.
+///
+/// Only the character server session can be resumed. The login data for the
+/// map server is consumed by the first login, so a lost map server connection
+/// is always reported right away and the character has to be selected again
+/// through the character server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    pub maximum_attempts: u32,
+    pub initial_delay: Duration,
+    pub maximum_delay: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            maximum_attempts: 5,
+            initial_delay: Duration::from_secs(1),
+            maximum_delay: Duration::from_secs(16),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before the given attempt, starting at 1.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay.saturating_mul(factor).min(self.maximum_delay)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReconnectState {
+    /// The session is established, or the initial login is still in progress.
+    Connected,
+    /// The connection was lost and the next attempt starts at `next_attempt`.
+    Waiting { attempt: u32, next_attempt: Instant },
+    /// Reconnected, but the server didn't accept the login yet.
+    Resuming { attempt: u32 },
+}
+
+/// Everything needed to log in to a server again.
+pub(crate) trait Session {
+    type DisconnectedEvent: DisconnectedEvent;
+
+    fn reconnecting_event(attempt: u32, maximum_attempts: u32, delay: Duration) -> NetworkEvent;
+
+    /// Replaces the event sent when the server accepts the login with the
+    /// event for a resumed session. Returns `true` if the event was replaced.
+    fn resume_event(event: &mut NetworkEvent) -> bool;
+
+    /// Returns `true` if the event is sent when the server refuses the login.
+    fn is_rejection(event: &NetworkEvent) -> bool;
+}
+
+pub(crate) struct CharacterServerSession {
+    pub packet_version: SupportedPacketVersion,
+    pub login_data: LoginServerLoginData,
+    pub server: CharacterServerInformation,
+}
+
+impl Session for CharacterServerSession {
+    type DisconnectedEvent = CharacterServerDisconnectedEvent;
+
+    fn reconnecting_event(attempt: u32, maximum_attempts: u32, delay: Duration) -> NetworkEvent {
+        NetworkEvent::CharacterServerReconnecting {
+            attempt,
+            maximum_attempts,
+            delay,
+        }
+    }
+
+    fn resume_event(event: &mut NetworkEvent) -> bool {
+        match *event {
+            NetworkEvent::CharacterServerConnected { normal_slot_count } => {
+                *event = NetworkEvent::CharacterServerReconnected { normal_slot_count };
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn is_rejection(event: &NetworkEvent) -> bool {
+        matches!(event, NetworkEvent::CharacterServerConnectionFailed { .. })
+    }
+}
+
+/// A session that can be resumed after the connection is lost.
+pub(crate) struct ResumableSession<S> {
+    pub session: S,
+    pub state: ReconnectState,
+}
+
+impl<S> ResumableSession<S>
+where
+    S: Session,
+{
+    pub fn new(session: S) -> Self {
+        Self {
+            session,
+            state: ReconnectState::Connected,
+        }
+    }
+
+    /// Process the events received since `first_new_event`. If the
+    /// connection was lost, either schedules the next attempt or gives up,
+    /// depending on the `policy`. Retrying is pointless once the server
+    /// refused to resume the session, so it is dropped right away.
+    ///
+    /// Returns `false` if the session should be dropped.
+    pub fn update(
+        &mut self,
+        policy: Option<ReconnectPolicy>,
+        events: &mut Vec<NetworkEvent>,
+        first_new_event: usize,
+        connection_lost: bool,
+    ) -> bool {
+        if let ReconnectState::Resuming { .. } = self.state {
+            if events[first_new_event..].iter().any(S::is_rejection) {
+                return false;
+            }
+
+            if events[first_new_event..].iter_mut().any(S::resume_event) {
+                self.state = ReconnectState::Connected;
+            }
+        }
+
+        if !connection_lost {
+            return true;
+        }
+
+        let attempt = match self.state {
+            ReconnectState::Connected => 1,
+            ReconnectState::Waiting { attempt, .. } | ReconnectState::Resuming { attempt } => attempt + 1,
+        };
+
+        match policy.filter(|policy| attempt <= policy.maximum_attempts) {
+            Some(policy) => {
+                let delay = policy.delay(attempt);
+
+                self.state = ReconnectState::Waiting {
+                    attempt,
+                    next_attempt: Instant::now() + delay,
+                };
+                events.push(S::reconnecting_event(attempt, policy.maximum_attempts, delay));
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the attempt if it's time to reconnect.
+    pub fn due_attempt(&self) -> Option<u32> {
+        match self.state {
+            ReconnectState::Waiting { attempt, next_attempt } if Instant::now() >= next_attempt => Some(attempt),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod backoff {
+    use std::time::Duration;
+
+    use super::ReconnectPolicy;
+
+    #[test]
+    fn doubles_until_maximum() {
+        let policy = ReconnectPolicy {
+            maximum_attempts: 10,
+            initial_delay: Duration::from_millis(500),
+            maximum_delay: Duration::from_secs(3),
+        };
+
+        let delays: Vec<_> = (1..=5).map(|attempt| policy.delay(attempt)).collect();
+
+        assert_eq!(delays, [
+            Duration::from_millis(500),
+            Duration::from_secs(1),
+            Duration::from_secs(2),
+            Duration::from_secs(3),
+            Duration::from_secs(3),
+        ]);
+    }
+
+    #[test]
+    fn no_overflow() {
+        assert_eq!(
+            ReconnectPolicy::default().delay(u32::MAX),
+            ReconnectPolicy::default().maximum_delay
+        );
+    }
+}
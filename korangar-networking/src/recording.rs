@@ -0,0 +1,260 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ragnarok_bytes::{ByteReader, ByteWriter, ConversionError, ConversionResult, FromBytes, ToBytes};
+use ragnarok_packets::handler::PacketCallback;
+
+/// Magic bytes at the start of every recording.
+const MAGIC: [u8; 4] = *b"KRPR";
+/// Version of the recording format.
+const FORMAT_VERSION: u16 = 2;
+
+/// Direction of a [`RecordedPacket`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketDirection {
+    /// Sent by the server to the client.
+    Incoming,
+    /// Sent by the client to the server.
+    Outgoing,
+}
+
+/// A single packet in a [`PacketRecording`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedPacket {
+    /// Time since the recording was started.
+    pub timestamp: Duration,
+    pub direction: PacketDirection,
+    /// The raw bytes of the packet, *including* the header, exactly as they
+    /// were received or sent (before header obfuscation). Packets that could
+    /// not be handled by the client are recorded together with everything that
+    /// was received after them.
+    pub bytes: Vec<u8>,
+}
+
+impl RecordedPacket {
+    fn to_bytes(&self, byte_writer: &mut ByteWriter) -> ConversionResult<()> {
+        let direction: u8 = match self.direction {
+            PacketDirection::Incoming => 0,
+            PacketDirection::Outgoing => 1,
+        };
+
+        direction.to_bytes(byte_writer)?;
+        (self.timestamp.as_micros() as u64).to_bytes(byte_writer)?;
+        (self.bytes.len() as u32).to_bytes(byte_writer)?;
+        byte_writer.extend_from_slice(&self.bytes);
+
+        Ok(())
+    }
+
+    fn from_bytes(byte_reader: &mut ByteReader) -> ConversionResult<Self> {
+        let direction = match u8::from_bytes(byte_reader)? {
+            0 => PacketDirection::Incoming,
+            1 => PacketDirection::Outgoing,
+            other => return Err(ConversionError::from_message(format!("invalid packet direction {other}"))),
+        };
+        let timestamp = Duration::from_micros(u64::from_bytes(byte_reader)?);
+        let length = u32::from_bytes(byte_reader)? as usize;
+        let bytes = byte_reader.slice::<Self>(length)?.to_vec();
+
+        Ok(Self {
+            timestamp,
+            direction,
+            bytes,
+        })
+    }
+}
+
+/// Packets sent and received during a client session, recorded with a
+/// [`RecordingCallback`].
+///
+/// A recording can be replayed with
+/// [`NetworkingSystem::spawn_replay`](crate::NetworkingSystem::spawn_replay)
+/// to reproduce the session without a server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PacketRecording {
+    pub packets: Vec<RecordedPacket>,
+}
+
+impl PacketRecording {
+    /// Parse a recording. A recording that ends in the middle of a packet (for
+    /// example because the client crashed) is cut off before that packet.
+    pub fn from_bytes(bytes: &[u8]) -> ConversionResult<Self> {
+        let mut byte_reader = ByteReader::without_metadata(bytes);
+
+        if byte_reader.bytes::<Self, 4>()? != MAGIC {
+            return Err(ConversionError::from_message("not a packet recording"));
+        }
+
+        let format_version = u16::from_bytes(&mut byte_reader)?;
+
+        if format_version != FORMAT_VERSION {
+            return Err(ConversionError::from_message(format!(
+                "unsupported recording format version {format_version}"
+            )));
+        }
+
+        let mut packets = Vec::new();
+
+        while !byte_reader.is_empty() {
+            match RecordedPacket::from_bytes(&mut byte_reader) {
+                Ok(packet) => packets.push(packet),
+                Err(error) if error.is_byte_reader_too_short() => break,
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(Self { packets })
+    }
+
+    /// Convert the recording to the format written by the
+    /// [`RecordingCallback`].
+    pub fn to_bytes(&self) -> ConversionResult<Vec<u8>> {
+        let mut byte_writer = ByteWriter::new();
+
+        write_header(&mut byte_writer)?;
+
+        for packet in &self.packets {
+            packet.to_bytes(&mut byte_writer)?;
+        }
+
+        Ok(byte_writer.into_inner())
+    }
+
+    /// Load a recording from a file.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::from_bytes(&bytes).map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{error:?}")))
+    }
+}
+
+fn write_header(byte_writer: &mut ByteWriter) -> ConversionResult<()> {
+    MAGIC.to_bytes(byte_writer)?;
+    FORMAT_VERSION.to_bytes(byte_writer)?;
+    Ok(())
+}
+
+struct Recorder {
+    writer: Box<dyn Write + Send>,
+    start: Instant,
+    byte_writer: ByteWriter,
+}
+
+impl Recorder {
+    fn record(&mut self, direction: PacketDirection, bytes: Vec<u8>) {
+        let packet = RecordedPacket {
+            timestamp: self.start.elapsed(),
+            direction,
+            bytes,
+        };
+
+        self.byte_writer.clear();
+
+        // NOTE: Recording is a debugging tool, so we don't want to interrupt the
+        // session if writing fails.
+        if packet.to_bytes(&mut self.byte_writer).is_ok() {
+            let _ = self.writer.write_all(self.byte_writer.as_slice());
+            let _ = self.writer.flush();
+        }
+    }
+}
+
+/// Packet callback that writes all packets sent to and received from the
+/// servers to a [`PacketRecording`].
+///
+/// Every packet is written as soon as it is sent or received, so the recording
+/// is usable even if the client crashes.
+#[derive(Clone)]
+pub struct RecordingCallback {
+    recorder: Arc<Mutex<Recorder>>,
+}
+
+impl RecordingCallback {
+    /// Start a new recording in `writer`.
+    pub fn new(mut writer: impl Write + Send + 'static) -> std::io::Result<Self> {
+        let mut byte_writer = ByteWriter::new();
+        write_header(&mut byte_writer).expect("failed to write recording header");
+        writer.write_all(byte_writer.as_slice())?;
+        byte_writer.clear();
+
+        let recorder = Recorder {
+            writer: Box::new(writer),
+            start: Instant::now(),
+            byte_writer,
+        };
+
+        Ok(Self {
+            recorder: Arc::new(Mutex::new(recorder)),
+        })
+    }
+
+    /// Start a new recording in the file at `path`, replacing it if it
+    /// already exists.
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+
+    fn record_bytes(&self, direction: PacketDirection, bytes: &[u8]) {
+        if let Ok(mut recorder) = self.recorder.lock() {
+            recorder.record(direction, bytes.to_vec());
+        }
+    }
+}
+
+impl PacketCallback for RecordingCallback {
+    fn incoming_bytes(&self, bytes: &[u8]) {
+        self.record_bytes(PacketDirection::Incoming, bytes);
+    }
+
+    fn outgoing_bytes(&self, bytes: &[u8]) {
+        self.record_bytes(PacketDirection::Outgoing, bytes);
+    }
+}
+
+#[cfg(test)]
+mod format {
+    use std::time::Duration;
+
+    use super::{PacketDirection, PacketRecording, RecordedPacket};
+
+    fn recording() -> PacketRecording {
+        PacketRecording {
+            packets: vec![
+                RecordedPacket {
+                    timestamp: Duration::from_millis(5),
+                    direction: PacketDirection::Outgoing,
+                    bytes: vec![0x64, 0x00, 0xAA],
+                },
+                RecordedPacket {
+                    timestamp: Duration::from_millis(20),
+                    direction: PacketDirection::Incoming,
+                    bytes: vec![0xC4, 0x0A, 0xBB, 0xCC],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let recording = recording();
+        let bytes = recording.to_bytes().unwrap();
+
+        assert_eq!(PacketRecording::from_bytes(&bytes).unwrap(), recording);
+    }
+
+    #[test]
+    fn cut_off() {
+        let recording = recording();
+        let bytes = recording.to_bytes().unwrap();
+        let loaded = PacketRecording::from_bytes(&bytes[..bytes.len() - 1]).unwrap();
+
+        assert_eq!(loaded.packets, recording.packets[..1]);
+    }
+
+    #[test]
+    fn invalid_magic() {
+        assert!(PacketRecording::from_bytes(b"PCAP\x01\x00").is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use ragnarok_packets::{CharacterServerLoginPacket, LoginServerLoginPacket, MapServerLoginPacket, Packet, PacketHeader};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+
+use crate::recording::{PacketDirection, PacketRecording};
+
+/// Size of the in-memory buffer between the client and the replay.
+const BUFFER_SIZE: usize = 8192;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReplayServer {
+    Login,
+    Character,
+    Map,
+}
+
+impl ReplayServer {
+    fn from_login_header(header: PacketHeader) -> Option<Self> {
+        match header {
+            LoginServerLoginPacket::HEADER => Some(Self::Login),
+            CharacterServerLoginPacket::HEADER => Some(Self::Character),
+            MapServerLoginPacket::HEADER => Some(Self::Map),
+            _ => None,
+        }
+    }
+}
+
+/// Everything a server sent during a single connection, relative to the time
+/// the client sent the login packet.
+struct RecordedConnection {
+    server: ReplayServer,
+    incoming: Vec<(Duration, Vec<u8>)>,
+}
+
+/// Replaces the TCP connections of the networking thread with connections
+/// that play back a [`PacketRecording`].
+///
+/// The recording doesn't know which packet belongs to which server, so it is
+/// split into connections at every login packet sent by the client. Every
+/// connection attempt then replays the next recorded connection to the same
+/// type of server.
+pub(crate) struct ReplaySource {
+    connections: VecDeque<RecordedConnection>,
+}
+
+impl ReplaySource {
+    pub fn new(recording: &PacketRecording) -> Self {
+        let mut connections = VecDeque::new();
+        let mut current_connection: Option<(Duration, RecordedConnection)> = None;
+
+        for packet in &recording.packets {
+            let header = packet
+                .bytes
+                .first_chunk::<2>()
+                .map(|bytes| PacketHeader(u16::from_le_bytes(*bytes)));
+
+            match packet.direction {
+                PacketDirection::Outgoing => {
+                    let Some(server) = header.and_then(ReplayServer::from_login_header) else {
+                        continue;
+                    };
+
+                    let connection = RecordedConnection {
+                        server,
+                        incoming: Vec::new(),
+                    };
+
+                    connections.extend(
+                        current_connection
+                            .replace((packet.timestamp, connection))
+                            .map(|(_, connection)| connection),
+                    );
+                }
+                PacketDirection::Incoming => {
+                    if let Some((start, connection)) = &mut current_connection {
+                        let offset = packet.timestamp.saturating_sub(*start);
+                        connection.incoming.push((offset, packet.bytes.clone()));
+                    }
+                }
+            }
+        }
+
+        connections.extend(current_connection.map(|(_, connection)| connection));
+
+        Self { connections }
+    }
+
+    /// Connect to the next recorded `server`. Returns `None` if the recording
+    /// doesn't contain any more connections to that server.
+    ///
+    /// Must be called from inside a [`LocalSet`](tokio::task::LocalSet).
+    pub fn connect(&mut self, server: ReplayServer) -> Option<DuplexStream> {
+        let index = self.connections.iter().position(|connection| connection.server == server)?;
+        let connection = self.connections.remove(index)?;
+        let (client_stream, server_stream) = tokio::io::duplex(BUFFER_SIZE);
+
+        tokio::task::spawn_local(Self::replay_connection(server_stream, connection));
+
+        Some(client_stream)
+    }
+
+    async fn replay_connection(mut stream: DuplexStream, connection: RecordedConnection) {
+        let start = tokio::time::Instant::now();
+        let mut incoming = connection.incoming.into_iter().peekable();
+        let mut buffer = [0u8; BUFFER_SIZE];
+
+        loop {
+            let next_timestamp = incoming.peek().map(|(offset, _)| start + *offset);
+
+            tokio::select! {
+                // Discard everything sent by the client. The connection stays open after all
+                // packets have been replayed until the client closes it.
+                received_bytes = stream.read(&mut buffer) => {
+                    if !matches!(received_bytes, Ok(count) if count > 0) {
+                        break;
+                    }
+                }
+                _ = tokio::time::sleep_until(next_timestamp.unwrap_or(start)), if next_timestamp.is_some() => {
+                    let (_, bytes) = incoming.next().unwrap();
+
+                    if stream.write_all(&bytes).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::net::{IpAddr, SocketAddr};
 
+use ragnarok_packets::obfuscation::PacketObfuscationKeys;
 use ragnarok_packets::{AccountId, CharacterId, Sex};
+use tokio::io::DuplexStream;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
 use crate::SupportedPacketVersion;
@@ -61,9 +63,17 @@ pub(crate) enum ServerConnectCommand {
         action_receiver: UnboundedReceiver<Vec<u8>>,
         event_sender: UnboundedSender<NetworkEvent>,
         packet_version: SupportedPacketVersion,
+        packet_obfuscation_keys: Option<PacketObfuscationKeys>,
     },
 }
 
+pub(crate) enum ConnectionTarget {
+    Address(SocketAddr),
+    /// Replayed connection, or `None` if the recording doesn't contain any
+    /// more connections to the server.
+    Replay(Option<DuplexStream>),
+}
+
 #[derive(Debug)]
 pub(crate) enum NetworkTaskError {
     FailedToConnect,
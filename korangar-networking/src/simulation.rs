@@ -0,0 +1,273 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use crate::ServerStream;
+
+/// Size of the in-memory buffer between the client and the simulated network.
+const BUFFER_SIZE: usize = 8192;
+/// Delay added when a chunk of data is lost. TCP resends lost segments, so
+/// losing data only ever delays it.
+const RETRANSMISSION_DELAY: Duration = Duration::from_millis(200);
+
+/// Network conditions to simulate on all server connections. They apply to
+/// sending and receiving separately, so the latency is added to the round
+/// trip time twice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetworkConditions {
+    /// Delay added to all data.
+    pub latency: Duration,
+    /// Maximum random delay added on top of the latency. The order of the
+    /// data is preserved, like it would be with TCP.
+    pub jitter: Duration,
+    /// Maximum number of bytes per second, or `None` for no limit.
+    pub bandwidth: Option<u32>,
+    /// Probability between 0 and 1 that a chunk of data has to be resent.
+    pub packet_loss: f32,
+}
+
+impl NetworkConditions {
+    /// No simulated delays.
+    pub const PERFECT: Self = Self {
+        latency: Duration::ZERO,
+        jitter: Duration::ZERO,
+        bandwidth: None,
+        packet_loss: 0.0,
+    };
+}
+
+impl Default for NetworkConditions {
+    fn default() -> Self {
+        Self::PERFECT
+    }
+}
+
+/// Small xorshift generator, since the simulation doesn't need good random
+/// numbers.
+struct Random(u64);
+
+impl Random {
+    fn from_time() -> Self {
+        let nanoseconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.subsec_nanos())
+            .unwrap_or_default();
+
+        // The state must never be zero.
+        Self(u64::from(nanoseconds) | 1)
+    }
+
+    /// Random number in the range `0.0..1.0`.
+    fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Timing of one direction of a simulated connection.
+struct Link {
+    random: Random,
+    transfer_end: Instant,
+    last_delivery: Instant,
+}
+
+impl Link {
+    fn new(random: Random) -> Self {
+        let now = Instant::now();
+
+        Self {
+            random,
+            transfer_end: now,
+            last_delivery: now,
+        }
+    }
+
+    /// Returns the time at which `length` bytes sent at `now` arrive.
+    fn schedule(&mut self, now: Instant, length: usize, conditions: &NetworkConditions) -> Instant {
+        // With limited bandwidth, data can only be sent once the previous data
+        // is transferred.
+        let transfer_start = now.max(self.transfer_end);
+        let transfer_duration = conditions
+            .bandwidth
+            .map(|bandwidth| Duration::from_secs_f64(length as f64 / f64::from(bandwidth.max(1))))
+            .unwrap_or_default();
+        self.transfer_end = transfer_start + transfer_duration;
+
+        let mut delivery = self.transfer_end + conditions.latency + conditions.jitter.mul_f64(self.random.next_f64());
+
+        if self.random.next_f64() < f64::from(conditions.packet_loss) {
+            delivery += RETRANSMISSION_DELAY;
+        }
+
+        // Data can't overtake data that was sent earlier.
+        delivery = delivery.max(self.last_delivery);
+        self.last_delivery = delivery;
+
+        delivery
+    }
+}
+
+/// Shared between the networking system and the networking thread, so that
+/// the conditions can be changed while connected.
+#[derive(Clone)]
+pub(crate) struct NetworkSimulation {
+    conditions: Arc<Mutex<NetworkConditions>>,
+    disconnect_sender: Arc<watch::Sender<u64>>,
+}
+
+impl Default for NetworkSimulation {
+    fn default() -> Self {
+        Self {
+            conditions: Arc::default(),
+            disconnect_sender: Arc::new(watch::channel(0).0),
+        }
+    }
+}
+
+impl NetworkSimulation {
+    pub fn conditions(&self) -> NetworkConditions {
+        *self.conditions.lock().unwrap()
+    }
+
+    pub fn set_conditions(&self, conditions: NetworkConditions) {
+        *self.conditions.lock().unwrap() = conditions;
+    }
+
+    /// Close all connections that are currently open.
+    pub fn disconnect_all(&self) {
+        self.disconnect_sender.send_modify(|generation| *generation += 1);
+    }
+
+    /// Route all data of the `stream` through the simulated network.
+    ///
+    /// Must be called from inside a [`LocalSet`](tokio::task::LocalSet).
+    pub fn wrap(&self, stream: Box<dyn ServerStream>) -> Box<dyn ServerStream> {
+        let (client_stream, simulation_stream) = tokio::io::duplex(BUFFER_SIZE);
+        let (client_reader, client_writer) = tokio::io::split(simulation_stream);
+        let (server_reader, server_writer) = tokio::io::split(stream);
+        let mut disconnect_receiver = self.disconnect_sender.subscribe();
+        let conditions = Arc::clone(&self.conditions);
+
+        tokio::task::spawn_local(async move {
+            // Dropping both streams closes the connection on either side.
+            tokio::select! {
+                _ = Self::forward(client_reader, server_writer, &conditions) => {}
+                _ = Self::forward(server_reader, client_writer, &conditions) => {}
+                _ = disconnect_receiver.changed() => {}
+            }
+        });
+
+        Box::new(client_stream)
+    }
+
+    /// Forward all data from `reader` to `writer` until `reader` is closed.
+    async fn forward(mut reader: impl AsyncRead + Unpin, mut writer: impl AsyncWrite + Unpin, conditions: &Mutex<NetworkConditions>) {
+        let (chunk_sender, mut chunk_receiver) = tokio::sync::mpsc::unbounded_channel::<(Instant, Vec<u8>)>();
+        let mut link = Link::new(Random::from_time());
+
+        let read = async move {
+            let mut buffer = [0u8; BUFFER_SIZE];
+
+            while let Ok(received_bytes) = reader.read(&mut buffer).await
+                && received_bytes > 0
+            {
+                let conditions = *conditions.lock().unwrap();
+                let delivery = link.schedule(Instant::now(), received_bytes, &conditions);
+
+                if chunk_sender.send((delivery, buffer[..received_bytes].to_vec())).is_err() {
+                    break;
+                }
+            }
+        };
+
+        let write = async {
+            while let Some((delivery, bytes)) = chunk_receiver.recv().await {
+                tokio::time::sleep_until(delivery).await;
+
+                if writer.write_all(&bytes).await.is_err() {
+                    break;
+                }
+            }
+        };
+
+        tokio::join!(read, write);
+    }
+}
+
+#[cfg(test)]
+mod timing {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::{Link, NetworkConditions, Random};
+
+    fn link() -> Link {
+        Link::new(Random(0x2545F4914F6CDD1D))
+    }
+
+    #[test]
+    fn latency() {
+        let conditions = NetworkConditions {
+            latency: Duration::from_millis(100),
+            ..NetworkConditions::PERFECT
+        };
+        let mut link = link();
+        let now = Instant::now();
+
+        assert_eq!(link.schedule(now, 10, &conditions), now + Duration::from_millis(100));
+    }
+
+    #[test]
+    fn jitter_keeps_order() {
+        let conditions = NetworkConditions {
+            latency: Duration::from_millis(100),
+            jitter: Duration::from_millis(100),
+            ..NetworkConditions::PERFECT
+        };
+        let mut link = link();
+        let now = Instant::now();
+        let mut previous_delivery = now;
+
+        for offset in 0..100 {
+            let sent = now + Duration::from_millis(offset);
+            let delivery = link.schedule(sent, 10, &conditions);
+
+            assert!(delivery >= sent + Duration::from_millis(100));
+            assert!(delivery >= previous_delivery);
+
+            previous_delivery = delivery;
+        }
+    }
+
+    #[test]
+    fn bandwidth() {
+        let conditions = NetworkConditions {
+            bandwidth: Some(1000),
+            ..NetworkConditions::PERFECT
+        };
+        let mut link = link();
+        let now = Instant::now();
+
+        assert_eq!(link.schedule(now, 500, &conditions), now + Duration::from_millis(500));
+        // The second chunk has to wait for the first one.
+        assert_eq!(link.schedule(now, 500, &conditions), now + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn packet_loss() {
+        let conditions = NetworkConditions {
+            packet_loss: 1.0,
+            ..NetworkConditions::PERFECT
+        };
+        let mut link = link();
+        let now = Instant::now();
+
+        assert_eq!(link.schedule(now, 10, &conditions), now + super::RETRANSMISSION_DELAY);
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ragnarok_packets::PacketHeader;
+
+/// Number of samples kept for each time series.
+pub const HISTORY_LENGTH: usize = 60;
+/// Duration of a single traffic sample.
+const SAMPLE_DURATION: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerType {
+    Login,
+    Character,
+    Map,
+}
+
+/// Traffic during a single second.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrafficSample {
+    pub packets: u32,
+    pub bytes: u32,
+}
+
+/// Traffic in one direction of a server connection.
+#[derive(Debug, Clone, Default)]
+pub struct TrafficStatistics {
+    /// Traffic of the last [`HISTORY_LENGTH`] seconds, oldest first.
+    pub history: VecDeque<TrafficSample>,
+    /// Total number of packets for every packet header.
+    pub packet_counts: BTreeMap<PacketHeader, u32>,
+    current_sample: TrafficSample,
+}
+
+impl TrafficStatistics {
+    fn record(&mut self, header: Option<PacketHeader>, length: usize) {
+        self.current_sample.packets += 1;
+        self.current_sample.bytes += length as u32;
+
+        if let Some(header) = header {
+            *self.packet_counts.entry(header).or_default() += 1;
+        }
+    }
+
+    fn finish_sample(&mut self) {
+        if self.history.len() == HISTORY_LENGTH {
+            self.history.pop_front();
+        }
+
+        self.history.push_back(std::mem::take(&mut self.current_sample));
+    }
+
+    /// Traffic during the last full second.
+    pub fn last_second(&self) -> TrafficSample {
+        self.history.back().copied().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ServerStatistics {
+    pub incoming: TrafficStatistics,
+    pub outgoing: TrafficStatistics,
+}
+
+/// Aggregated traffic of all server connections.
+#[derive(Debug, Clone)]
+pub struct NetworkStatistics {
+    pub login_server: ServerStatistics,
+    pub character_server: ServerStatistics,
+    pub map_server: ServerStatistics,
+    /// Round trip times of the time synchronization with the map server,
+    /// oldest first.
+    pub round_trip_times: VecDeque<Duration>,
+    /// Difference between the client tick received from the map server and
+    /// the client tick predicted from the previous synchronization, in
+    /// milliseconds. Oldest first.
+    pub clock_drifts: VecDeque<f64>,
+    sample_start: Instant,
+}
+
+impl Default for NetworkStatistics {
+    fn default() -> Self {
+        Self {
+            login_server: ServerStatistics::default(),
+            character_server: ServerStatistics::default(),
+            map_server: ServerStatistics::default(),
+            round_trip_times: VecDeque::new(),
+            clock_drifts: VecDeque::new(),
+            sample_start: Instant::now(),
+        }
+    }
+}
+
+impl NetworkStatistics {
+    pub fn server(&self, server_type: ServerType) -> &ServerStatistics {
+        match server_type {
+            ServerType::Login => &self.login_server,
+            ServerType::Character => &self.character_server,
+            ServerType::Map => &self.map_server,
+        }
+    }
+
+    fn server_mut(&mut self, server_type: ServerType) -> &mut ServerStatistics {
+        match server_type {
+            ServerType::Login => &mut self.login_server,
+            ServerType::Character => &mut self.character_server,
+            ServerType::Map => &mut self.map_server,
+        }
+    }
+
+    /// Finish all samples that are older than a second.
+    fn update(&mut self, now: Instant) {
+        let mut finished_samples = 0;
+
+        while now.duration_since(self.sample_start) >= SAMPLE_DURATION {
+            self.sample_start += SAMPLE_DURATION;
+
+            // After a long time without any update, the older samples would be
+            // pushed out of the history anyway.
+            if finished_samples < HISTORY_LENGTH {
+                for server in [&mut self.login_server, &mut self.character_server, &mut self.map_server] {
+                    server.incoming.finish_sample();
+                    server.outgoing.finish_sample();
+                }
+
+                finished_samples += 1;
+            }
+        }
+    }
+}
+
+fn push_limited<T>(history: &mut VecDeque<T>, value: T) {
+    if history.len() == HISTORY_LENGTH {
+        history.pop_front();
+    }
+
+    history.push_back(value);
+}
+
+/// Shared between the networking system and the networking thread.
+#[derive(Clone, Default)]
+pub(crate) struct StatisticsRecorder {
+    statistics: Arc<Mutex<NetworkStatistics>>,
+}
+
+impl StatisticsRecorder {
+    /// Record a packet received from the server. `bytes` has to start with
+    /// the packet header.
+    pub fn incoming_packet(&self, server_type: ServerType, bytes: &[u8]) {
+        self.record(server_type, bytes, |server| &mut server.incoming);
+    }
+
+    /// Record a packet sent to the server. `bytes` has to start with the
+    /// packet header.
+    pub fn outgoing_packet(&self, server_type: ServerType, bytes: &[u8]) {
+        self.record(server_type, bytes, |server| &mut server.outgoing);
+    }
+
+    fn record(&self, server_type: ServerType, bytes: &[u8], direction: impl FnOnce(&mut ServerStatistics) -> &mut TrafficStatistics) {
+        let header = bytes.first_chunk::<2>().map(|bytes| PacketHeader(u16::from_le_bytes(*bytes)));
+
+        if let Ok(mut statistics) = self.statistics.lock() {
+            statistics.update(Instant::now());
+            direction(statistics.server_mut(server_type)).record(header, bytes.len());
+        }
+    }
+
+    /// Record the result of a time synchronization with the map server.
+    pub fn time_synchronization(&self, round_trip_time: Duration, clock_drift: f64) {
+        if let Ok(mut statistics) = self.statistics.lock() {
+            // The first synchronization has nothing to compare against.
+            if !statistics.round_trip_times.is_empty() {
+                push_limited(&mut statistics.clock_drifts, clock_drift);
+            }
+
+            push_limited(&mut statistics.round_trip_times, round_trip_time);
+        }
+    }
+
+    pub fn snapshot(&self) -> NetworkStatistics {
+        let mut statistics = self.statistics.lock().unwrap();
+        statistics.update(Instant::now());
+        statistics.clone()
+    }
+}
+
+#[cfg(test)]
+mod samples {
+    use std::time::Duration;
+
+    use ragnarok_packets::PacketHeader;
+
+    use super::{HISTORY_LENGTH, NetworkStatistics, TrafficSample};
+
+    #[test]
+    fn finish_after_one_second() {
+        let mut statistics = NetworkStatistics::default();
+        let start = statistics.sample_start;
+
+        statistics.map_server.incoming.record(Some(PacketHeader(0x007F)), 6);
+        statistics.map_server.incoming.record(Some(PacketHeader(0x007F)), 6);
+        statistics.update(start + Duration::from_millis(500));
+
+        assert!(statistics.map_server.incoming.history.is_empty());
+
+        statistics.update(start + Duration::from_millis(1500));
+
+        assert_eq!(statistics.map_server.incoming.last_second(), TrafficSample {
+            packets: 2,
+            bytes: 12
+        });
+        assert_eq!(statistics.map_server.incoming.packet_counts[&PacketHeader(0x007F)], 2);
+        assert_eq!(statistics.login_server.incoming.history.len(), 1);
+    }
+
+    #[test]
+    fn empty_seconds() {
+        let mut statistics = NetworkStatistics::default();
+        let start = statistics.sample_start;
+
+        statistics.map_server.outgoing.record(None, 10);
+        statistics.update(start + Duration::from_millis(3500));
+
+        let history: Vec<_> = statistics.map_server.outgoing.history.iter().map(|sample| sample.bytes).collect();
+
+        assert_eq!(history, [10, 0, 0]);
+    }
+
+    #[test]
+    fn limited_history() {
+        let mut statistics = NetworkStatistics::default();
+        let start = statistics.sample_start;
+
+        statistics.update(start + Duration::from_secs(HISTORY_LENGTH as u64 * 3));
+
+        assert_eq!(statistics.map_server.incoming.history.len(), HISTORY_LENGTH);
+        assert!(statistics.sample_start + Duration::from_secs(1) > start + Duration::from_secs(HISTORY_LENGTH as u64 * 3));
+    }
+}
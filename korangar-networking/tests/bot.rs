@@ -0,0 +1,245 @@
+//! Tests of the [`Bot`] against mock servers.
+
+use std::time::Duration;
+
+use korangar_networking::{Bot, BotError, BotLogin, CharacterSelection, NetworkEvent, SupportedPacketVersion};
+use korangar_pathing::Traversable;
+use ragnarok_mock_server::presets::{self, MockAccount};
+use ragnarok_mock_server::{MockServer, Responder, Script};
+use ragnarok_packets::*;
+
+const PACKET_VERSION: SupportedPacketVersion = SupportedPacketVersion::_20220406;
+const TIMEOUT: Duration = Duration::from_secs(5);
+const CHARACTER_NAME: &str = "Poring";
+const MAP_NAME: &str = "prontera";
+const SPAWN_POSITION: TilePosition = TilePosition { x: 150, y: 150 };
+const NPC_ID: EntityId = EntityId(110000);
+
+/// A map without any obstacles.
+struct OpenMap;
+
+impl Traversable for OpenMap {
+    fn is_walkable(&self, position: TilePosition) -> bool {
+        position.x < 300 && position.y < 300
+    }
+
+    fn is_snipeable(&self, position: TilePosition) -> bool {
+        self.is_walkable(position)
+    }
+}
+
+struct MockServers {
+    account: MockAccount,
+    login_server: MockServer,
+    _character_server: MockServer,
+    map_server: MockServer,
+}
+
+impl MockServers {
+    fn spawn(customize: impl FnOnce(Script) -> Script) -> Self {
+        let account = MockAccount::default();
+        let mut character = presets::mock_character(CharacterId(150000), 2, CHARACTER_NAME, MAP_NAME);
+        // Walk fast to keep the tests short.
+        character.movement_speed = 5;
+
+        let position = WorldPosition::new(SPAWN_POSITION.x, SPAWN_POSITION.y, Direction::South);
+        let map_server = MockServer::spawn(customize(presets::map_server(account.clone(), position))).unwrap();
+        let character_server = MockServer::spawn(presets::character_server(
+            account.clone(),
+            vec![character],
+            map_server.address(),
+        ))
+        .unwrap();
+        let login_server = MockServer::spawn(presets::login_server(account.clone(), character_server.address())).unwrap();
+
+        Self {
+            account,
+            login_server,
+            _character_server: character_server,
+            map_server,
+        }
+    }
+
+    fn login(&self, character: CharacterSelection) -> BotLogin {
+        BotLogin {
+            packet_version: PACKET_VERSION,
+            login_server: self.login_server.address(),
+            username: self.account.username.clone(),
+            password: self.account.password.clone(),
+            character,
+        }
+    }
+}
+
+/// Answer movement requests like a server would, without checking the path.
+fn walking(script: Script) -> Script {
+    let mut position = SPAWN_POSITION;
+
+    script.on(move |packet: RequestPlayerMovePacket, responder: &mut Responder| {
+        let destination = packet.position.tile_position();
+
+        responder.send(PlayerMovePacket::new(
+            ClientTick(0),
+            WorldPosition2::new(position.x, position.y, destination.x, destination.y),
+        ));
+
+        position = destination;
+    })
+}
+
+#[tokio::test]
+async fn log_in_by_name() {
+    let servers = MockServers::spawn(|script| script);
+    let bot = Bot::log_in(servers.login(CharacterSelection::Name(CHARACTER_NAME.to_owned())))
+        .await
+        .unwrap();
+
+    assert_eq!(bot.state().account_id, servers.account.account_id);
+    assert_eq!(bot.state().character.name, CHARACTER_NAME);
+    assert_eq!(bot.state().map_name, MAP_NAME);
+    assert_eq!(bot.state().position, SPAWN_POSITION);
+    assert!(servers.map_server.wait_for_packet::<MapLoadedPacket>(TIMEOUT).is_some());
+}
+
+#[tokio::test]
+async fn log_in_by_slot() {
+    let servers = MockServers::spawn(|script| script);
+    let bot = Bot::log_in(servers.login(CharacterSelection::Slot(2))).await.unwrap();
+
+    assert_eq!(bot.state().character.name, CHARACTER_NAME);
+}
+
+#[tokio::test]
+async fn unknown_character() {
+    let servers = MockServers::spawn(|script| script);
+    let result = Bot::log_in(servers.login(CharacterSelection::Name("Lunatic".to_owned()))).await;
+
+    assert!(matches!(result, Err(BotError::CharacterNotFound)));
+}
+
+#[tokio::test]
+async fn walk_to() {
+    let servers = MockServers::spawn(walking);
+    let mut bot = Bot::log_in(servers.login(CharacterSelection::Slot(2))).await.unwrap();
+    let destination = TilePosition {
+        x: SPAWN_POSITION.x + 40,
+        y: SPAWN_POSITION.y + 5,
+    };
+
+    bot.walk_to(&OpenMap, destination).await.unwrap();
+
+    let requests = servers.map_server.received::<RequestPlayerMovePacket>();
+
+    // The path is too long for the server, so it has to be walked in two steps.
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].position.tile_position(), destination);
+    assert_eq!(bot.state().position, destination);
+}
+
+#[tokio::test]
+async fn walk_to_rejected() {
+    let servers = MockServers::spawn(|script| {
+        script.on(|_: RequestPlayerMovePacket, responder: &mut Responder| {
+            responder.send(PlayerMovePacket::new(
+                ClientTick(0),
+                WorldPosition2::new(SPAWN_POSITION.x, SPAWN_POSITION.y, SPAWN_POSITION.x, SPAWN_POSITION.y),
+            ));
+        })
+    });
+    let mut bot = Bot::log_in(servers.login(CharacterSelection::Slot(2))).await.unwrap();
+    let destination = TilePosition {
+        x: SPAWN_POSITION.x + 3,
+        y: SPAWN_POSITION.y,
+    };
+
+    let result = bot.walk_to(&OpenMap, destination).await;
+
+    assert!(matches!(result, Err(BotError::MovementRejected { position }) if position == SPAWN_POSITION));
+}
+
+#[tokio::test]
+async fn talk_to_npc() {
+    let servers = MockServers::spawn(|script| {
+        script
+            .on(|packet: StartDialogPacket, responder: &mut Responder| {
+                responder.send(NpcDialogPacket::new(packet.npc_id, "Welcome!".to_owned()));
+                responder.send(NextButtonPacket::new(packet.npc_id));
+            })
+            .on(|packet: NextDialogPacket, responder: &mut Responder| {
+                responder.send(DialogMenuPacket::new(packet.npc_id, "Heal:Buff:Cancel".to_owned()));
+            })
+            .on(|packet: ChooseDialogOptionPacket, responder: &mut Responder| {
+                let text = match packet.option {
+                    1 => "You have been healed.",
+                    _ => "You have been buffed.",
+                };
+
+                responder.send(NpcDialogPacket::new(packet.npc_id, text.to_owned()));
+                responder.send(CloseButtonPacket::new(packet.npc_id));
+            })
+            .ignore::<CloseDialogPacket>()
+    });
+    let mut bot = Bot::log_in(servers.login(CharacterSelection::Slot(2))).await.unwrap();
+
+    let text = bot
+        .talk_to_npc(NPC_ID, |choices| choices.iter().position(|choice| choice == "Buff"))
+        .await
+        .unwrap();
+
+    assert_eq!(text, ["Welcome!", "You have been buffed."]);
+
+    let option = servers
+        .map_server
+        .wait_for_packet::<ChooseDialogOptionPacket>(TIMEOUT)
+        .unwrap()
+        .option;
+
+    assert_eq!(option, 2);
+    assert!(servers.map_server.wait_for_packet::<CloseDialogPacket>(TIMEOUT).is_some());
+}
+
+#[tokio::test]
+async fn wait_for_chat_message() {
+    let servers = MockServers::spawn(|script| script);
+    let mut bot = Bot::log_in(servers.login(CharacterSelection::Slot(2))).await.unwrap();
+
+    servers.map_server.send(ServerMessagePacket::new("Welcome".to_owned()));
+
+    let text = bot
+        .wait_for_event(|event| match event {
+            NetworkEvent::ChatMessage { text, .. } => Some(text.clone()),
+            _ => None,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(text, "Welcome");
+}
+
+#[tokio::test]
+async fn disconnect_while_waiting() {
+    let servers = MockServers::spawn(|script| script);
+    let mut bot = Bot::log_in(servers.login(CharacterSelection::Slot(2))).await.unwrap();
+
+    assert!(servers.map_server.wait_for_connection_count(1, TIMEOUT));
+    servers.map_server.disconnect_all();
+
+    let result = bot.wait_for_event(|_| None::<()>).await;
+
+    assert!(matches!(result, Err(BotError::Disconnected)));
+}
+
+#[tokio::test]
+async fn dialog_option_out_of_range() {
+    let servers = MockServers::spawn(|script| {
+        script.on(|packet: StartDialogPacket, responder: &mut Responder| {
+            let choices = (0..200).map(|index| index.to_string()).collect::<Vec<_>>().join(":");
+            responder.send(DialogMenuPacket::new(packet.npc_id, choices));
+        })
+    });
+    let mut bot = Bot::log_in(servers.login(CharacterSelection::Slot(2))).await.unwrap();
+
+    let result = bot.talk_to_npc(NPC_ID, |choices| Some(choices.len() - 1)).await;
+
+    assert!(matches!(result, Err(BotError::InvalidDialogOption { index: 199 })));
+}
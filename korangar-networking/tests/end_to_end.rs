@@ -0,0 +1,695 @@
+//! End-to-end tests of the [`NetworkingSystem`] against mock servers.
+
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use korangar_networking::{
+    DisconnectReason, ItemQuantity, LoginServerLoginData, NetworkEvent, NetworkEventBuffer, NetworkingSystem, PacketDirection,
+    PacketRecording, ReconnectPolicy, RecordingCallback, ShopItem, SupportedPacketVersion, UnifiedCharacterSelectionFailedReason,
+    UnifiedLoginFailedReason,
+};
+use ragnarok_mock_server::presets::{self, MockAccount};
+use ragnarok_mock_server::{MockServer, Responder, Script};
+use ragnarok_packets::handler::{NoPacketCallback, PacketCallback};
+use ragnarok_packets::obfuscation::{PacketObfuscation, PacketObfuscationKeys};
+use ragnarok_packets::*;
+
+const PACKET_VERSION: SupportedPacketVersion = SupportedPacketVersion::_20220406;
+const TIMEOUT: Duration = Duration::from_secs(5);
+const CHARACTER_ID: CharacterId = CharacterId(150000);
+const CHARACTER_NAME: &str = "Poring";
+const MAP_NAME: &str = "prontera";
+const FAST_RECONNECT: ReconnectPolicy = ReconnectPolicy {
+    maximum_attempts: 3,
+    initial_delay: Duration::from_millis(10),
+    maximum_delay: Duration::from_millis(40),
+};
+
+/// Wait for the first event that matches the pattern and evaluate the
+/// expression.
+macro_rules! wait_for_event {
+    ($client:expr, $pattern:pat => $result:expr) => {
+        $client.wait_for(stringify!($pattern), |event| match event {
+            $pattern => Some($result),
+            _ => None,
+        })
+    };
+    ($client:expr, $pattern:pat) => {
+        wait_for_event!($client, $pattern => ())
+    };
+}
+
+struct MockServers {
+    account: MockAccount,
+    login_server: MockServer,
+    character_server: MockServer,
+    map_server: MockServer,
+}
+
+impl MockServers {
+    fn spawn() -> Self {
+        Self::spawn_with_map_script(|script| script)
+    }
+
+    fn spawn_with_map_script(customize: impl FnOnce(Script) -> Script) -> Self {
+        let account = MockAccount::default();
+        let characters = vec![presets::mock_character(CHARACTER_ID, 0, CHARACTER_NAME, MAP_NAME)];
+        let position = WorldPosition::new(150, 150, Direction::South);
+
+        let map_server = MockServer::spawn(customize(presets::map_server(account.clone(), position))).unwrap();
+        let character_server = MockServer::spawn(presets::character_server(account.clone(), characters, map_server.address())).unwrap();
+        let login_server = MockServer::spawn(presets::login_server(account.clone(), character_server.address())).unwrap();
+
+        Self {
+            account,
+            login_server,
+            character_server,
+            map_server,
+        }
+    }
+}
+
+struct TestClient<Callback = NoPacketCallback> {
+    networking_system: NetworkingSystem<Callback>,
+    event_buffer: NetworkEventBuffer,
+    pending_events: VecDeque<NetworkEvent>,
+}
+
+impl TestClient {
+    fn new() -> Self {
+        Self::from_spawned(NetworkingSystem::spawn())
+    }
+}
+
+impl<Callback> TestClient<Callback>
+where
+    Callback: PacketCallback + Send,
+{
+    fn from_spawned((networking_system, event_buffer): (NetworkingSystem<Callback>, NetworkEventBuffer)) -> Self {
+        Self {
+            networking_system,
+            event_buffer,
+            pending_events: VecDeque::new(),
+        }
+    }
+
+    /// Wait for the first event accepted by `matcher`, discarding all events
+    /// before it.
+    fn wait_for<T>(&mut self, description: &str, mut matcher: impl FnMut(NetworkEvent) -> Option<T>) -> T {
+        let deadline = Instant::now() + TIMEOUT;
+
+        loop {
+            while let Some(event) = self.pending_events.pop_front() {
+                if let Some(result) = matcher(event) {
+                    return result;
+                }
+            }
+
+            assert!(Instant::now() < deadline, "timed out waiting for {description}");
+
+            std::thread::sleep(Duration::from_millis(5));
+
+            self.networking_system.get_events(&mut self.event_buffer);
+            self.pending_events.extend(self.event_buffer.drain());
+        }
+    }
+
+    fn log_in(&mut self, servers: &MockServers) -> LoginServerLoginData {
+        self.log_in_to(servers.login_server.address(), &servers.account)
+    }
+
+    fn log_in_to(&mut self, login_server: SocketAddr, account: &MockAccount) -> LoginServerLoginData {
+        self.networking_system
+            .connect_to_login_server(PACKET_VERSION, login_server, account.username.clone(), account.password.clone());
+
+        let (character_servers, login_data) = wait_for_event!(self, NetworkEvent::LoginServerConnected {
+            character_servers,
+            login_data,
+        } => (character_servers, login_data));
+
+        self.networking_system.disconnect_from_login_server();
+        self.networking_system
+            .connect_to_character_server(PACKET_VERSION, &login_data, character_servers[0].clone());
+
+        wait_for_event!(self, NetworkEvent::CharacterServerConnected { .. });
+
+        login_data
+    }
+
+    fn enter_map(&mut self, servers: &MockServers) {
+        self.enter_map_from(servers.login_server.address(), &servers.account);
+    }
+
+    fn enter_map_from(&mut self, login_server: SocketAddr, account: &MockAccount) {
+        let login_data = self.log_in_to(login_server, account);
+
+        self.networking_system.select_character(0).unwrap();
+
+        let character_login_data = wait_for_event!(self, NetworkEvent::CharacterSelected { login_data } => login_data);
+
+        self.networking_system.disconnect_from_character_server();
+        self.networking_system
+            .connect_to_map_server(PACKET_VERSION, &login_data, character_login_data);
+
+        wait_for_event!(self, NetworkEvent::UpdateClientTick { .. });
+
+        self.networking_system.map_loaded().unwrap();
+    }
+}
+
+#[test]
+fn login_with_incorrect_password() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client
+        .networking_system
+        .connect_to_login_server(PACKET_VERSION, servers.login_server.address(), "mock", "wrong");
+
+    let reason = wait_for_event!(client, NetworkEvent::LoginServerConnectionFailed { reason, .. } => reason);
+
+    assert!(matches!(reason, UnifiedLoginFailedReason::IncorrectPassword));
+}
+
+#[test]
+fn login_and_character_list() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    let login_data = client.log_in(&servers);
+
+    assert_eq!(login_data.account_id, servers.account.account_id);
+    assert_eq!(login_data.login_id1, servers.account.login_id1);
+
+    let account_id = servers
+        .character_server
+        .wait_for_packet::<CharacterServerLoginPacket>(TIMEOUT)
+        .unwrap()
+        .account_id;
+
+    assert_eq!(account_id, servers.account.account_id);
+
+    client.networking_system.request_character_list().unwrap();
+
+    let characters = wait_for_event!(client, NetworkEvent::CharacterList { characters } => characters);
+
+    assert_eq!(characters.len(), 1);
+    assert_eq!(characters[0].character_id, CHARACTER_ID);
+    assert_eq!(characters[0].name, CHARACTER_NAME);
+}
+
+#[test]
+fn select_character() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+
+    let login_packet = servers.map_server.wait_for_packet::<MapServerLoginPacket>(TIMEOUT).unwrap();
+
+    assert_eq!(login_packet.account_id, servers.account.account_id);
+    assert_eq!(login_packet.character_id, CHARACTER_ID);
+    assert!(servers.map_server.wait_for_packet::<MapLoadedPacket>(TIMEOUT).is_some());
+    assert!(servers.map_server.unhandled_packets().is_empty());
+}
+
+#[test]
+fn select_character_in_empty_slot() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.log_in(&servers);
+    client.networking_system.select_character(3).unwrap();
+
+    let reason = wait_for_event!(client, NetworkEvent::CharacterSelectionFailed { reason, .. } => reason);
+
+    assert!(matches!(reason, UnifiedCharacterSelectionFailedReason::RejectedFromServer));
+}
+
+#[test]
+fn change_map() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+    client
+        .networking_system
+        .warp_to_map("geffen".to_owned(), TilePosition { x: 119, y: 59 })
+        .unwrap();
+
+    let (map_name, position) = wait_for_event!(client, NetworkEvent::ChangeMap { map_name, position } => (map_name, position));
+
+    assert_eq!(map_name, "geffen");
+    assert_eq!(position, TilePosition { x: 119, y: 59 });
+}
+
+#[test]
+fn chat() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+    client.networking_system.send_chat_message(CHARACTER_NAME, "Hello").unwrap();
+
+    let text = wait_for_event!(client, NetworkEvent::ChatMessage { text, .. } => text);
+
+    assert_eq!(text, "Poring : Hello");
+
+    servers.map_server.send(ServerMessagePacket::new("Welcome".to_owned()));
+
+    let text = wait_for_event!(client, NetworkEvent::ChatMessage { text, .. } => text);
+
+    assert_eq!(text, "Welcome");
+}
+
+#[test]
+fn buy_from_shop() {
+    const SHOP_ID: ShopId = ShopId(110000);
+    const ITEM_ID: ItemId = ItemId(501);
+
+    let servers = MockServers::spawn_with_map_script(|script| {
+        script
+            .on(|_: SelectBuyOrSellPacket, responder: &mut Responder| {
+                responder.send(ShopItemListPacket::new(vec![ShopItemInformation {
+                    item_id: ITEM_ID,
+                    price: Price(50),
+                    discount_price: Price(50),
+                    item_type: 0,
+                    view_sprite: 0,
+                    location: 0,
+                }]));
+            })
+            .on(|packet: BuyShopItemsPacket, responder: &mut Responder| {
+                let purchased_items = packet
+                    .items
+                    .into_iter()
+                    .map(|item| BoughtShopItemInformation {
+                        item_id: item.item_id,
+                        amount: item.amount as u16,
+                        price: Price(50),
+                    })
+                    .collect();
+
+                responder.send(BuyShopItemsResultPacket::new(BuyShopItemsResult::Success, purchased_items));
+            })
+            .ignore::<CloseShopPacket>()
+    });
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+    servers.map_server.send(BuyOrSellPacket::new(SHOP_ID));
+
+    let shop_id = wait_for_event!(client, NetworkEvent::AskBuyOrSell { shop_id } => shop_id);
+
+    assert_eq!(shop_id, SHOP_ID);
+
+    client.networking_system.select_buy_or_sell(shop_id, BuyOrSellOption::Buy).unwrap();
+
+    let items = wait_for_event!(client, NetworkEvent::OpenShop { items } => items);
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].item_id, ITEM_ID);
+    assert_eq!(items[0].price, Price(50));
+
+    client
+        .networking_system
+        .purchase_items(vec![ShopItem {
+            metadata: 3,
+            item_id: ITEM_ID,
+            item_type: 0,
+            price: Price(50),
+            quantity: ItemQuantity::Infinite,
+            weight: 0,
+            location: 0,
+        }])
+        .unwrap();
+
+    let result = wait_for_event!(client, NetworkEvent::BuyingCompleted { result } => result);
+
+    assert!(matches!(result, BuyShopItemsResult::Success));
+
+    let purchase = servers.map_server.wait_for_packet::<BuyShopItemsPacket>(TIMEOUT).unwrap();
+
+    assert_eq!(purchase.items.len(), 1);
+    assert_eq!(purchase.items[0].item_id, ITEM_ID);
+    assert_eq!(purchase.items[0].amount, 3);
+
+    client.networking_system.close_shop().unwrap();
+
+    assert!(servers.map_server.wait_for_packet::<CloseShopPacket>(TIMEOUT).is_some());
+}
+
+#[test]
+fn log_out() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+    client.networking_system.log_out().unwrap();
+
+    wait_for_event!(client, NetworkEvent::LoggedOut);
+}
+
+#[test]
+fn disconnect_by_server() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+    servers.map_server.disconnect_all();
+
+    let reason = wait_for_event!(client, NetworkEvent::MapServerDisconnected { reason } => reason);
+
+    assert_eq!(reason, DisconnectReason::ConnectionError);
+    assert!(!client.networking_system.is_map_server_connected());
+}
+
+#[test]
+fn disconnect_by_client() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+    client.networking_system.disconnect_from_map_server();
+
+    let reason = wait_for_event!(client, NetworkEvent::MapServerDisconnected { reason } => reason);
+
+    assert_eq!(reason, DisconnectReason::ClosedByClient);
+    assert!(servers.map_server.wait_for_connection_count(0, TIMEOUT));
+}
+
+#[test]
+fn map_server_session_is_not_resumed() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.networking_system.set_reconnect_policy(Some(FAST_RECONNECT));
+    client.enter_map(&servers);
+    assert!(servers.map_server.wait_for_connection_count(1, TIMEOUT));
+    servers.map_server.disconnect_all();
+
+    let reason = wait_for_event!(client, NetworkEvent::MapServerDisconnected { reason } => reason);
+
+    assert_eq!(reason, DisconnectReason::ConnectionError);
+    assert!(!client.networking_system.is_reconnecting());
+    assert_eq!(servers.map_server.received::<MapServerLoginPacket>().len(), 1);
+}
+
+#[test]
+fn reconnect_to_character_server() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.networking_system.set_reconnect_policy(Some(FAST_RECONNECT));
+    client.log_in(&servers);
+    assert!(servers.character_server.wait_for_connection_count(1, TIMEOUT));
+    servers.character_server.disconnect_all();
+
+    wait_for_event!(client, NetworkEvent::CharacterServerReconnecting { .. });
+    wait_for_event!(client, NetworkEvent::CharacterServerReconnected { .. });
+
+    client.networking_system.request_character_list().unwrap();
+
+    let characters = wait_for_event!(client, NetworkEvent::CharacterList { characters } => characters);
+
+    assert_eq!(characters[0].character_id, CHARACTER_ID);
+}
+
+#[test]
+fn stop_reconnecting_after_maximum_attempts() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.networking_system.set_reconnect_policy(Some(FAST_RECONNECT));
+    client.log_in(&servers);
+    assert!(servers.character_server.wait_for_connection_count(1, TIMEOUT));
+
+    // Shut down the character server, so that every attempt fails.
+    drop(servers.character_server);
+
+    let mut attempts = Vec::new();
+    let reason = client.wait_for("CharacterServerDisconnected", |event| match event {
+        NetworkEvent::CharacterServerReconnecting { attempt, .. } => {
+            attempts.push(attempt);
+            None
+        }
+        NetworkEvent::CharacterServerDisconnected { reason } => Some(reason),
+        _ => None,
+    });
+
+    assert_eq!(attempts, [1, 2, 3]);
+    assert_eq!(reason, DisconnectReason::ConnectionError);
+    assert!(!client.networking_system.is_reconnecting());
+}
+
+#[test]
+fn no_reconnect_after_disconnect_by_client() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.networking_system.set_reconnect_policy(Some(FAST_RECONNECT));
+    client.enter_map(&servers);
+    client.networking_system.disconnect_from_map_server();
+
+    let reason = wait_for_event!(client, NetworkEvent::MapServerDisconnected { reason } => reason);
+
+    assert_eq!(reason, DisconnectReason::ClosedByClient);
+    assert!(!client.networking_system.is_reconnecting());
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn simulated_latency() {
+    use korangar_networking::NetworkConditions;
+
+    const LATENCY: Duration = Duration::from_millis(100);
+
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+    client.networking_system.set_network_conditions(NetworkConditions {
+        latency: LATENCY,
+        ..NetworkConditions::PERFECT
+    });
+
+    let start = Instant::now();
+    client.networking_system.send_chat_message(CHARACTER_NAME, "Hello").unwrap();
+    wait_for_event!(client, NetworkEvent::ChatMessage { .. });
+
+    // The latency applies to both directions.
+    assert!(start.elapsed() >= LATENCY * 2);
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn simulated_disconnect() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+    client.networking_system.simulate_disconnect();
+
+    let reason = wait_for_event!(client, NetworkEvent::MapServerDisconnected { reason } => reason);
+
+    assert_eq!(reason, DisconnectReason::ConnectionError);
+    assert!(servers.map_server.wait_for_connection_count(0, TIMEOUT));
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn reconnect_after_simulated_disconnect() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.networking_system.set_reconnect_policy(Some(FAST_RECONNECT));
+    client.log_in(&servers);
+    assert!(servers.character_server.wait_for_connection_count(1, TIMEOUT));
+    client.networking_system.simulate_disconnect();
+
+    wait_for_event!(client, NetworkEvent::CharacterServerReconnected { .. });
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn traffic_statistics() {
+    let servers = MockServers::spawn();
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+    client.networking_system.send_chat_message(CHARACTER_NAME, "Hello").unwrap();
+    wait_for_event!(client, NetworkEvent::ChatMessage { .. });
+
+    let statistics = client.networking_system.statistics();
+
+    assert_eq!(statistics.map_server.outgoing.packet_counts[&GlobalMessagePacket::HEADER], 1);
+    assert!(!statistics.map_server.incoming.packet_counts.is_empty());
+    assert!(!statistics.login_server.outgoing.packet_counts.is_empty());
+    assert!(!statistics.character_server.incoming.packet_counts.is_empty());
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn traffic_statistics_include_unhandled_packets() {
+    const UNKNOWN_HEADER: PacketHeader = PacketHeader(0x7FFF);
+
+    let servers = MockServers::spawn_with_map_script(|script| {
+        script.on(|_: StartDialogPacket, responder: &mut Responder| {
+            responder.send_raw(&[0xFF, 0x7F, 0x01, 0x02, 0x03]);
+        })
+    });
+    let mut client = TestClient::new();
+
+    client.enter_map(&servers);
+    client.networking_system.start_dialog(EntityId(110000)).unwrap();
+
+    let deadline = Instant::now() + TIMEOUT;
+
+    loop {
+        let statistics = client.networking_system.statistics();
+
+        if let Some(count) = statistics.map_server.incoming.packet_counts.get(&UNKNOWN_HEADER) {
+            assert_eq!(*count, 1);
+            break;
+        }
+
+        assert!(Instant::now() < deadline, "timed out waiting for the unhandled packet");
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn map_server_packet_headers_are_obfuscated() {
+    // The keys of the packet version are all zero, which would leave the headers
+    // unchanged.
+    const KEYS: PacketObfuscationKeys = PacketObfuscationKeys {
+        key1: 0x4C17382A,
+        key2: 0x7ED174C9,
+        key3: 0x29FF6B75,
+    };
+
+    let servers = MockServers::spawn_with_map_script(|script| script.with_packet_obfuscation(KEYS));
+    let mut client = TestClient::new();
+
+    client.networking_system.set_packet_obfuscation_keys(Some(KEYS));
+    client.enter_map(&servers);
+
+    // Longer than the read buffer of the mock server, so the server receives the
+    // packet in multiple parts and has to restore the header only once.
+    let long_message = "a".repeat(10000);
+
+    for message in [long_message.as_str(), "Hello"] {
+        client.networking_system.send_chat_message(CHARACTER_NAME, message).unwrap();
+        client.wait_for("ChatMessage", |event| match event {
+            NetworkEvent::ChatMessage { text, .. } if text.ends_with(message) => Some(()),
+            _ => None,
+        });
+    }
+
+    let received_packets = servers.map_server.received_packets();
+    let mut packet_obfuscation = PacketObfuscation::new(KEYS);
+
+    assert_eq!(received_packets[0].header, MapServerLoginPacket::HEADER);
+    assert_eq!(
+        received_packets
+            .iter()
+            .filter(|packet| packet.header == GlobalMessagePacket::HEADER)
+            .count(),
+        2
+    );
+    assert!(received_packets.iter().any(|packet| packet.wire_header != packet.header));
+
+    for packet in received_packets {
+        assert_eq!(packet.wire_header, packet_obfuscation.apply_to_header(packet.header));
+    }
+}
+
+#[test]
+fn record_and_replay() {
+    let recording_path = std::env::temp_dir().join(format!("korangar-replay-{}.krpr", std::process::id()));
+
+    let servers = MockServers::spawn();
+    let login_server = servers.login_server.address();
+    let account = servers.account.clone();
+
+    {
+        let mut client = TestClient::from_spawned(NetworkingSystem::spawn_with_callback(
+            RecordingCallback::create(&recording_path).unwrap(),
+        ));
+
+        client.enter_map(&servers);
+        client.networking_system.send_chat_message(CHARACTER_NAME, "Hello").unwrap();
+
+        wait_for_event!(client, NetworkEvent::ChatMessage { .. });
+    }
+
+    drop(servers);
+
+    let recording = PacketRecording::load(&recording_path).unwrap();
+    let _ = std::fs::remove_file(&recording_path);
+
+    let login_packets = recording
+        .packets
+        .iter()
+        .filter(|packet| packet.direction == PacketDirection::Outgoing)
+        .filter(|packet| packet.bytes.starts_with(&LoginServerLoginPacket::HEADER.0.to_le_bytes()))
+        .count();
+
+    assert_eq!(login_packets, 1);
+
+    // Replay the session without any servers running.
+    let mut client = TestClient::from_spawned(NetworkingSystem::spawn_replay(&recording));
+
+    client.enter_map_from(login_server, &account);
+    client.networking_system.send_chat_message(CHARACTER_NAME, "Hello").unwrap();
+
+    let text = wait_for_event!(client, NetworkEvent::ChatMessage { text, .. } => text);
+
+    assert_eq!(text, "Poring : Hello");
+}
+
+#[test]
+fn recording_contains_raw_frames() {
+    const UNKNOWN_PACKET: [u8; 5] = [0xFF, 0x7F, 0x01, 0x02, 0x03];
+
+    let recording_path = std::env::temp_dir().join(format!("korangar-raw-frames-{}.krpr", std::process::id()));
+
+    let servers = MockServers::spawn_with_map_script(|script| {
+        script.on(|_: StartDialogPacket, responder: &mut Responder| {
+            responder.send_raw(&UNKNOWN_PACKET);
+        })
+    });
+    let mut client = TestClient::from_spawned(NetworkingSystem::spawn_with_callback(
+        RecordingCallback::create(&recording_path).unwrap(),
+    ));
+
+    client.enter_map(&servers);
+    client.networking_system.start_dialog(EntityId(110000)).unwrap();
+
+    let deadline = Instant::now() + TIMEOUT;
+
+    let recording = loop {
+        let recording = PacketRecording::load(&recording_path).unwrap();
+
+        if recording.packets.iter().any(|packet| packet.bytes == UNKNOWN_PACKET) {
+            break recording;
+        }
+
+        assert!(Instant::now() < deadline, "timed out waiting for the unhandled packet");
+        std::thread::sleep(Duration::from_millis(5));
+    };
+
+    drop(client);
+    let _ = std::fs::remove_file(&recording_path);
+
+    let account_id = servers.account.account_id.0.to_le_bytes();
+
+    // The character server sends the account id without a packet header.
+    assert!(
+        recording
+            .packets
+            .iter()
+            .any(|packet| packet.direction == PacketDirection::Incoming && packet.bytes == account_id)
+    );
+}
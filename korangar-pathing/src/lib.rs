@@ -69,6 +69,25 @@ impl PathFinder {
         start: TilePosition,
         goal: TilePosition,
         attack_range: AttackRange,
+    ) -> Option<&[TilePosition]> {
+        self.find_path(map, start, goal, attack_range, MAX_WALK_PATH_SIZE)
+    }
+
+    /// Returns the shortest walkable path between start and goal without
+    /// limiting its size to [`MAX_WALK_PATH_SIZE`]. The server will only
+    /// accept paths up to that size, so longer paths have to be walked in
+    /// multiple steps.
+    pub fn find_long_walkable_path(&mut self, map: &impl Traversable, start: TilePosition, goal: TilePosition) -> Option<&[TilePosition]> {
+        self.find_path(map, start, goal, AttackRange(0), usize::MAX)
+    }
+
+    fn find_path(
+        &mut self,
+        map: &impl Traversable,
+        start: TilePosition,
+        goal: TilePosition,
+        attack_range: AttackRange,
+        maximum_path_size: usize,
     ) -> Option<&[TilePosition]> {
         self.open_set.clear();
         self.closed_set.clear();
@@ -85,7 +104,7 @@ impl PathFinder {
 
         while let Some(current) = self.open_set.pop() {
             if current.position.x.abs_diff(goal.x).max(current.position.y.abs_diff(goal.y)) <= attack_range.0 {
-                return match self.reconstruct_path(start, current.position) {
+                return match self.reconstruct_path(start, current.position, maximum_path_size) {
                     true => Some(&self.path),
                     false => None,
                 };
@@ -236,14 +255,14 @@ impl PathFinder {
         }
     }
 
-    fn reconstruct_path(&mut self, start: TilePosition, goal: TilePosition) -> bool {
+    fn reconstruct_path(&mut self, start: TilePosition, goal: TilePosition, maximum_path_size: usize) -> bool {
         let mut current = goal;
 
         while current != start {
             self.path.push(current);
             current = *self.came_from.get(&current).unwrap();
 
-            if self.path.len() >= MAX_WALK_PATH_SIZE {
+            if self.path.len() >= maximum_path_size {
                 return false;
             }
         }
@@ -376,6 +395,22 @@ mod tests {
         assert!(pathfinder.find_walkable_path(&map, start, goal).is_none());
     }
 
+    #[test]
+    fn test_long_path() {
+        let map = TestMap::new(100, 1);
+        let mut pathfinder = PathFinder::default();
+
+        let start = TilePosition { x: 0, y: 0 };
+        let goal = TilePosition { x: 99, y: 0 };
+
+        assert!(pathfinder.find_walkable_path(&map, start, goal).is_none());
+
+        let path = pathfinder.find_long_walkable_path(&map, start, goal).unwrap();
+        assert_eq!(path.len(), 100);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+    }
+
     #[test]
     fn test_shoot_path_straight() {
         let map = TestMap::new(10, 10);
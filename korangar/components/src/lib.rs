@@ -16,6 +16,7 @@ pub fn skill_box(token_stream: TokenStream) -> TokenStream {
     create_component_macro!(crate::interface::components::skill_box::SkillBox, {
         learnable_skill_path: !,
         learned_skill_path: !,
+        cooldown_path: !,
         source: !,
     });
 
@@ -143,6 +143,19 @@ impl Color {
     }
 }
 
+impl korangar_interface::application::Color for Color {
+    fn multiply_alpha(&self, factor: f32) -> Self {
+        Self {
+            alpha: self.alpha * factor,
+            ..*self
+        }
+    }
+
+    fn interpolate(&self, other: Self, amount: f32) -> Self {
+        *self + (other - *self) * amount
+    }
+}
+
 impl From<Color> for cosmic_text::Color {
     fn from(value: Color) -> Self {
         Self::rgba(value.red_as_u8(), value.green_as_u8(), value.blue_as_u8(), value.alpha_as_u8())
@@ -122,7 +122,6 @@ struct EngineContext {
     debug_circle_drawer: DebugCircleDrawer,
     #[cfg(feature = "debug")]
     debug_rectangle_drawer: DebugRectangleDrawer,
-    #[cfg(feature = "debug")]
     picker_marker_drawer: PickerMarkerDrawer,
 }
 
@@ -423,7 +422,6 @@ impl GraphicsEngine {
                             &global_context,
                             &screen_blit_pass_context,
                         );
-                        #[cfg(feature = "debug")]
                         let picker_marker_drawer = PickerMarkerDrawer::new(
                             &self.capabilities,
                             &self.device,
@@ -479,7 +477,6 @@ impl GraphicsEngine {
                         debug_circle_drawer,
                         #[cfg(feature = "debug")]
                         debug_rectangle_drawer,
-                        #[cfg(feature = "debug")]
                         picker_marker_drawer,
                     })
                 }
@@ -978,7 +975,6 @@ impl GraphicsEngine {
             });
             #[cfg(feature = "debug")]
             scope.spawn(|_| {
-                context.picker_marker_drawer.prepare(&self.device, instruction);
                 context.debug_aabb_drawer.prepare(&self.device, instruction);
             });
             #[cfg(feature = "debug")]
@@ -992,6 +988,7 @@ impl GraphicsEngine {
                 context.directional_shadow_pass_context.prepare(&self.device, instruction);
                 context.point_shadow_pass_context.prepare(&self.device, instruction);
                 context.picker_entity_drawer.prepare(&self.device, instruction);
+                context.picker_marker_drawer.prepare(&self.device, instruction);
             });
         });
 
@@ -1009,6 +1006,7 @@ impl GraphicsEngine {
         visitor.upload(&mut context.global_context);
         visitor.upload(&mut context.interface_rectangle_drawer);
         visitor.upload(&mut context.picker_entity_drawer);
+        visitor.upload(&mut context.picker_marker_drawer);
         visitor.upload(&mut context.point_shadow_entity_drawer);
         visitor.upload(&mut context.point_shadow_model_drawer);
         visitor.upload(&mut context.point_shadow_pass_context);
@@ -1024,7 +1022,6 @@ impl GraphicsEngine {
             visitor.upload(&mut context.debug_rectangle_drawer);
             visitor.upload(&mut context.debug_buffer_drawer);
             visitor.upload(&mut context.debug_circle_drawer);
-            visitor.upload(&mut context.picker_marker_drawer);
         }
 
         encoder.finish()
@@ -1077,10 +1074,7 @@ impl GraphicsEngine {
                 }
 
                 engine_context.picker_entity_drawer.draw(&mut render_pass, instruction.entities);
-                #[cfg(feature = "debug")]
-                {
-                    engine_context.picker_marker_drawer.draw(&mut render_pass, None);
-                }
+                engine_context.picker_marker_drawer.draw(&mut render_pass, None);
 
                 drop(render_pass);
 
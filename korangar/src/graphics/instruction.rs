@@ -9,7 +9,7 @@ use super::color::Color;
 use super::settings::RenderOptions;
 use super::vertices::ModelVertex;
 use super::{Buffer, ShadowDetail, ShadowMethod, Texture, TextureSet, TileVertex, WaterVertex};
-use crate::graphics::{CornerDiameter, ScreenClip, ScreenPosition, ScreenSize, ShadowPadding};
+use crate::graphics::{CornerDiameter, PickerTarget, ScreenClip, ScreenPosition, ScreenSize, ShadowPadding};
 #[cfg(feature = "debug")]
 use crate::world::MarkerIdentifier;
 
@@ -43,6 +43,9 @@ pub struct RenderInstruction<'a> {
     pub map_picker_tile_vertex_buffer: Option<&'a Buffer<TileVertex>>,
     pub map_picker_tile_index_buffer: Option<&'a Buffer<u32>>,
     pub font_map_texture: Option<&'a Texture>,
+    /// Screen space rectangles that can be clicked, such as chat room
+    /// balloons.
+    pub picker_rectangles: &'a [PickerRectangleInstruction],
     #[cfg(feature = "debug")]
     pub render_options: RenderOptions,
     #[cfg(feature = "debug")]
@@ -240,6 +243,13 @@ pub enum InterfaceRectangleInstruction {
     },
 }
 
+#[derive(Clone, Debug)]
+pub struct PickerRectangleInstruction {
+    pub screen_position: ScreenPosition,
+    pub screen_size: ScreenSize,
+    pub picker_target: PickerTarget,
+}
+
 #[cfg(feature = "debug")]
 #[derive(Clone, Debug)]
 pub struct MarkerInstruction {
@@ -9,11 +9,13 @@ use wgpu::{
     RenderPipeline, RenderPipelineDescriptor, ShaderStages, VertexState,
 };
 
+#[cfg(feature = "debug")]
+use crate::graphics::PickerTarget;
 use crate::graphics::passes::{
     BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, PickerRenderPassContext, RenderPassContext,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{Buffer, Capabilities, GlobalContext, PickerTarget, Prepare, RenderInstruction};
+use crate::graphics::{Buffer, Capabilities, GlobalContext, Prepare, RenderInstruction};
 
 const DRAWER_NAME: &str = "picker marker";
 const INITIAL_INSTRUCTION_SIZE: usize = 128;
@@ -38,7 +40,12 @@ pub(crate) struct PickerMarkerDrawer {
 
 impl Prepare for PickerMarkerDrawer {
     fn prepare(&mut self, _device: &Device, instructions: &RenderInstruction) {
-        self.draw_count = instructions.marker.len();
+        self.draw_count = instructions.picker_rectangles.len();
+
+        #[cfg(feature = "debug")]
+        {
+            self.draw_count += instructions.marker.len();
+        }
 
         if self.draw_count == 0 {
             return;
@@ -46,6 +53,18 @@ impl Prepare for PickerMarkerDrawer {
 
         self.instance_data.clear();
 
+        for instruction in instructions.picker_rectangles.iter() {
+            let (identifier_high, identifier_low) = instruction.picker_target.into();
+
+            self.instance_data.push(InstanceData {
+                screen_position: instruction.screen_position.into(),
+                screen_size: instruction.screen_size.into(),
+                identifier_high,
+                identifier_low,
+            });
+        }
+
+        #[cfg(feature = "debug")]
         for instruction in instructions.marker.iter() {
             let picker_target = PickerTarget::Marker(instruction.identifier);
             let (identifier_high, identifier_low) = picker_target.into();
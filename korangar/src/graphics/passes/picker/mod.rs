@@ -1,10 +1,8 @@
 mod entity;
-#[cfg(feature = "debug")]
 mod marker;
 mod tile;
 
 pub(crate) use entity::PickerEntityDrawer;
-#[cfg(feature = "debug")]
 pub(crate) use marker::PickerMarkerDrawer;
 pub(crate) use tile::{PickerTileDrawData, PickerTileDrawer};
 use wgpu::{
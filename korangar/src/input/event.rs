@@ -3,10 +3,12 @@ use cgmath::Vector2;
 #[cfg(feature = "debug")]
 use korangar_debug::profiling::FrameMeasurement;
 use korangar_interface::event::{ClickHandler, Event, EventQueue};
+#[cfg(feature = "debug")]
+use korangar_networking::NetworkConditions;
 use korangar_networking::{InventoryItem, ShopItem};
 use ragnarok_packets::{
-    AccountId, BuyOrSellOption, CharacterId, CharacterServerInformation, EntityId, HotbarSlot, ShopId, SkillId, SoldItemInformation,
-    StatUpType, TilePosition,
+    AccountId, BuyOrSellOption, CharacterId, CharacterServerInformation, EntityId, HomunculusCommand, HotbarSlot, InventoryIndex, ItemId,
+    MailId, MailOpenType, PetCommand, ShopId, SkillId, SoldItemInformation, StatUpType, TilePosition,
 };
 use rust_state::State;
 
@@ -61,6 +63,9 @@ pub enum InputEvent {
     ToggleInventoryWindow,
     /// Open or close the equipment window. Only works while playing.
     ToggleEquipmentWindow,
+    /// Open or close the cart window. Only works while playing and if the
+    /// player has a cart.
+    ToggleCartWindow,
     /// Open or close the skill tree window. Only works while playing.
     ToggleSkillTreeWindow,
     /// Open or close the stats window. Only works while playing.
@@ -69,12 +74,30 @@ pub enum InputEvent {
     ToggleGameSettingsWindow,
     /// Open or close the interface settings window.
     ToggleInterfaceSettingsWindow,
+    /// Save the current window layout under a new name and switch to it.
+    SaveWindowLayout {
+        /// Name of the new window layout.
+        name: String,
+    },
+    /// Move all windows of the current window layout back to their default
+    /// position.
+    ResetWindowLayout,
     /// Open or close the graphics settings window.
     ToggleGraphicsSettingsWindow,
     /// Open or close the audio settings window.
     ToggleAudioSettingsWindow,
     /// Open or close the friend list window. Only works while playing.
     ToggleFriendListWindow,
+    /// Open or close the mailbox window. Only works while playing.
+    ToggleMailboxWindow,
+    /// Open or close the pet window. Only works while playing.
+    TogglePetWindow,
+    /// Open or close the homunculus window. Only works while playing.
+    ToggleHomunculusWindow,
+    /// Open or close the chat room window. If the player is not in a chat
+    /// room, the window to create a new room is opened instead. Only works
+    /// while playing.
+    ToggleChatRoomWindow,
     /// Close the most recently opened or clicked closable window.
     CloseTopWindow,
     /// Toggle if the user interface should be rendered or not.
@@ -146,6 +169,12 @@ pub enum InputEvent {
         /// Id of the option.
         option: i8,
     },
+    /// Open the tooltip window of an item, e.g. when clicking an item link in
+    /// the chat.
+    OpenItemTooltip {
+        /// Id of the item.
+        item_id: ItemId,
+    },
     /// Move an item in the user interface.
     MoveItem {
         /// Source of the move.
@@ -155,6 +184,20 @@ pub enum InputEvent {
         /// Item to move.
         item: InventoryItem<ResourceMetadata>,
     },
+    /// Use an item from the inventory.
+    UseItem {
+        /// Index of the item in the inventory.
+        index: InventoryIndex,
+    },
+    /// Open a window to select how many items to drop.
+    OpenDropItemWindow {
+        /// Index of the item in the inventory.
+        index: InventoryIndex,
+        /// Number of items in the inventory.
+        amount: u16,
+    },
+    /// Drop the item selected in the drop item window.
+    ConfirmDropItem,
     /// Move a skill in the user interface.
     MoveSkill {
         /// Source of the move.
@@ -179,6 +222,16 @@ pub enum InputEvent {
         /// Name of the character to befriend.
         character_name: String,
     },
+    /// Request a trade with another player.
+    RequestTrade {
+        /// Entity id of the player.
+        entity_id: EntityId,
+    },
+    /// Invite another player to the party.
+    InviteToParty {
+        /// Name of the character to invite.
+        character_name: String,
+    },
     /// Remove a current friend.
     RemoveFriend {
         /// Account id of the friend.
@@ -200,6 +253,94 @@ pub enum InputEvent {
         /// Character id of the requestor.
         character_id: CharacterId,
     },
+    /// Request the next page of mails from the server.
+    RequestNextMailPage,
+    /// Read a mail from the mailbox.
+    ReadMail {
+        /// Mailbox that the mail is stored in.
+        open_type: MailOpenType,
+        /// Id of the mail to read.
+        mail_id: MailId,
+    },
+    /// Delete a mail from the mailbox.
+    DeleteMail {
+        /// Mailbox that the mail is stored in.
+        open_type: MailOpenType,
+        /// Id of the mail to delete.
+        mail_id: MailId,
+    },
+    /// Move the zeny attached to a mail to the player.
+    ClaimMailZeny {
+        /// Id of the mail.
+        mail_id: MailId,
+    },
+    /// Move the items attached to a mail to the player inventory.
+    ClaimMailItems {
+        /// Id of the mail.
+        mail_id: MailId,
+    },
+    /// Start writing a new mail.
+    WriteMail {
+        /// Name of the character to send the mail to. May be empty.
+        recipient_name: String,
+    },
+    /// Ask the server if the recipient of the mail that is currently being
+    /// written exists.
+    CheckMailRecipient,
+    /// Send the mail that is currently being written.
+    SendMail,
+    /// Discard the mail that is currently being written.
+    CancelWriteMail,
+    /// Hatch a pet from an egg in the inventory.
+    HatchPet {
+        /// Inventory index of the egg.
+        index: InventoryIndex,
+    },
+    /// Send a command to the pet of the player.
+    PetCommand {
+        /// Command to send.
+        command: PetCommand,
+    },
+    /// Send a command to the homunculus of the player.
+    HomunculusCommand {
+        /// Command to send.
+        command: HomunculusCommand,
+    },
+    /// Make the homunculus follow the player.
+    HomunculusFollow,
+    /// Make the homunculus stay where it currently is.
+    HomunculusStay,
+    /// Select the next entity that is clicked as the attack target of the
+    /// homunculus.
+    HomunculusSelectAttackTarget,
+    /// Level up a skill of the homunculus.
+    LevelUpHomunculusSkill {
+        /// Id of the skill.
+        skill_id: SkillId,
+    },
+    /// Create a chat room with the settings from the chat room settings
+    /// window.
+    CreateChatRoom,
+    /// Open the chat room settings window to change the current chat room.
+    OpenChangeChatRoomWindow,
+    /// Change the current chat room to the settings from the chat room
+    /// settings window.
+    ChangeChatRoom,
+    /// Join the chat room with the password from the chat room password
+    /// window.
+    JoinChatRoom,
+    /// Make another member the owner of the current chat room.
+    ChangeChatRoomOwner {
+        /// Name of the member.
+        name: String,
+    },
+    /// Kick a member from the current chat room.
+    KickChatRoomMember {
+        /// Name of the member.
+        name: String,
+    },
+    /// Leave the current chat room.
+    LeaveChatRoom,
     /// Buy items from a shop.
     BuyItems {
         /// Items to buy.
@@ -271,6 +412,15 @@ pub enum InputEvent {
     /// Open or close the commands window. Only works while playing.
     #[cfg(feature = "debug")]
     ToggleCommandsWindow,
+    /// Simulate bad network conditions on all server connections.
+    #[cfg(feature = "debug")]
+    SetNetworkConditions {
+        /// Conditions to simulate.
+        conditions: NetworkConditions,
+    },
+    /// Close all server connections as if the network failed.
+    #[cfg(feature = "debug")]
+    SimulateDisconnect,
     /// Open the theme inspector window.
     #[cfg(feature = "debug")]
     ToggleThemeInspectorWindow,
@@ -283,6 +433,9 @@ pub enum InputEvent {
     /// Open the cache statistics window.
     #[cfg(feature = "debug")]
     ToggleCacheStatisticsWindow,
+    /// Open or close the network statistics window.
+    #[cfg(feature = "debug")]
+    ToggleNetworkStatisticsWindow,
     /// Move the view direction of the debug camera.
     #[cfg(feature = "debug")]
     CameraLookAround {
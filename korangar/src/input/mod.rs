@@ -6,9 +6,10 @@ use std::mem::variant_count;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use korangar_interface::event::{CompositionEvent, EditKey, FocusDirection, KeyInput, NavigationInput};
 use ragnarok_packets::{ClientTick, HotbarSlot};
 use winit::dpi::PhysicalPosition;
-use winit::event::{ElementState, MouseButton, MouseScrollDelta};
+use winit::event::{ElementState, Ime, MouseButton, MouseScrollDelta};
 use winit::keyboard::KeyCode;
 
 pub use self::event::InputEvent;
@@ -36,6 +37,9 @@ pub struct InputReport {
     pub scroll: Option<f32>,
     pub drag: Option<ScreenSize>,
     pub characters: Vec<char>,
+    pub keys: Vec<KeyInput>,
+    pub navigation: Vec<NavigationInput>,
+    pub composition: Vec<CompositionEvent>,
     pub mouse_target: PickerTarget,
 }
 
@@ -50,6 +54,9 @@ pub struct InputSystem {
     right_mouse_button: Key,
     keys: [Key; KEY_COUNT],
     input_buffer: Vec<char>,
+    key_buffer: Vec<KeyInput>,
+    navigation_buffer: Vec<NavigationInput>,
+    composition_buffer: Vec<CompositionEvent>,
     picker_value: Arc<AtomicU64>,
     previous_mouse_button: Option<PreviousMouseButton>,
 }
@@ -69,6 +76,9 @@ impl InputSystem {
         let keys = [Key::default(); KEY_COUNT];
 
         let input_buffer = Vec::new();
+        let key_buffer = Vec::new();
+        let navigation_buffer = Vec::new();
+        let composition_buffer = Vec::new();
         let previous_mouse_button = None;
 
         Self {
@@ -82,6 +92,9 @@ impl InputSystem {
             right_mouse_button,
             keys,
             input_buffer,
+            key_buffer,
+            navigation_buffer,
+            composition_buffer,
             picker_value,
             previous_mouse_button,
         }
@@ -126,6 +139,79 @@ impl InputSystem {
         self.input_buffer.push(character);
     }
 
+    /// Buffer a key press used for editing text. Needs to be called after
+    /// [`update_keyboard`](Self::update_keyboard) so the modifiers are up to
+    /// date.
+    pub fn buffer_key(&mut self, key_code: KeyCode) {
+        let shift = self.get_key(KeyCode::ShiftLeft).down() || self.get_key(KeyCode::ShiftRight).down();
+        let control = self.get_key(KeyCode::ControlLeft).down() || self.get_key(KeyCode::ControlRight).down();
+
+        let key = match key_code {
+            KeyCode::ArrowLeft => EditKey::Left,
+            KeyCode::ArrowRight => EditKey::Right,
+            KeyCode::ArrowUp => EditKey::Up,
+            KeyCode::ArrowDown => EditKey::Down,
+            KeyCode::Home => EditKey::Home,
+            KeyCode::End => EditKey::End,
+            KeyCode::Delete => EditKey::Delete,
+            KeyCode::KeyA if control => EditKey::SelectAll,
+            KeyCode::KeyC if control => EditKey::Copy,
+            KeyCode::KeyX if control => EditKey::Cut,
+            KeyCode::KeyV if control => EditKey::Paste,
+            KeyCode::KeyZ if control && shift => EditKey::Redo,
+            KeyCode::KeyZ if control => EditKey::Undo,
+            KeyCode::KeyY if control => EditKey::Redo,
+            _ => return,
+        };
+
+        self.key_buffer.push(KeyInput { key, shift, control });
+    }
+
+    /// Buffer a key press used for navigating the interface without a mouse.
+    /// Needs to be called after [`update_keyboard`](Self::update_keyboard) so
+    /// the modifiers are up to date.
+    pub fn buffer_navigation_key(&mut self, key_code: KeyCode) {
+        let shift = self.get_key(KeyCode::ShiftLeft).down() || self.get_key(KeyCode::ShiftRight).down();
+
+        let input = match key_code {
+            KeyCode::Tab if shift => NavigationInput::Move(FocusDirection::Previous),
+            KeyCode::Tab => NavigationInput::Move(FocusDirection::Next),
+            KeyCode::ArrowUp => NavigationInput::Move(FocusDirection::Up),
+            KeyCode::ArrowDown => NavigationInput::Move(FocusDirection::Down),
+            KeyCode::ArrowLeft => NavigationInput::Move(FocusDirection::Left),
+            KeyCode::ArrowRight => NavigationInput::Move(FocusDirection::Right),
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space => NavigationInput::Activate,
+            KeyCode::Escape => NavigationInput::Cancel,
+            _ => return,
+        };
+
+        self.navigation_buffer.push(input);
+    }
+
+    /// Buffer navigation input from sources other than the keyboard, such as
+    /// the directional pad of a gamepad.
+    pub fn buffer_navigation(&mut self, input: NavigationInput) {
+        self.navigation_buffer.push(input);
+    }
+
+    pub fn buffer_ime(&mut self, ime: Ime) {
+        let event = match ime {
+            Ime::Enabled => return,
+            Ime::Preedit(text, cursor) => CompositionEvent::Preedit {
+                text,
+                cursor: cursor.map(|(start, end)| start..end),
+            },
+            Ime::Commit(text) => CompositionEvent::Commit { text },
+            // Disabling the input method discards anything that was not committed.
+            Ime::Disabled => CompositionEvent::Preedit {
+                text: String::new(),
+                cursor: None,
+            },
+        };
+
+        self.composition_buffer.push(event);
+    }
+
     #[cfg_attr(feature = "debug", korangar_debug::profile("update input system"))]
     pub fn update_delta(&mut self, client_tick: ClientTick) -> InputReport {
         self.mouse_delta = self.new_mouse_position - self.previous_mouse_position;
@@ -188,6 +274,9 @@ impl InputSystem {
             scroll: (self.scroll_delta != 0.0).then_some(self.scroll_delta),
             drag: self.left_mouse_button.down().then_some(self.mouse_delta),
             characters: self.input_buffer.drain(..).collect(),
+            keys: self.key_buffer.drain(..).collect(),
+            navigation: self.navigation_buffer.drain(..).collect(),
+            composition: self.composition_buffer.drain(..).collect(),
             mouse_target,
         }
     }
@@ -230,6 +319,26 @@ impl InputSystem {
             events.push(InputEvent::ToggleEquipmentWindow);
         }
 
+        if alt_down && self.get_key(KeyCode::KeyW).pressed() {
+            events.push(InputEvent::ToggleCartWindow);
+        }
+
+        if alt_down && self.get_key(KeyCode::KeyM).pressed() {
+            events.push(InputEvent::ToggleMailboxWindow);
+        }
+
+        if alt_down && self.get_key(KeyCode::KeyP).pressed() {
+            events.push(InputEvent::TogglePetWindow);
+        }
+
+        if alt_down && self.get_key(KeyCode::KeyR).pressed() {
+            events.push(InputEvent::ToggleHomunculusWindow);
+        }
+
+        if alt_down && self.get_key(KeyCode::KeyC).pressed() {
+            events.push(InputEvent::ToggleChatRoomWindow);
+        }
+
         if control_down && self.get_key(KeyCode::KeyS).pressed() {
             events.push(InputEvent::ToggleGameSettingsWindow);
         }
@@ -0,0 +1,22 @@
+/// Clipboard that only shares text inside the client.
+///
+/// The clipboard of the operating system is not used, so text can't be copied
+/// between the client and other applications.
+#[derive(Default)]
+pub struct Clipboard {
+    text: Option<String>,
+}
+
+impl korangar_interface::application::Clipboard for Clipboard {
+    fn create() -> Self {
+        Self::default()
+    }
+
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = Some(text);
+    }
+}
@@ -0,0 +1,99 @@
+use korangar_interface::components::context_menu::ContextMenu;
+use korangar_interface::element::id::FocusIdExt;
+use korangar_interface::event::{Event, EventQueue};
+use ragnarok_packets::{EntityId, Friend};
+use rust_state::State;
+
+use crate::input::InputEvent;
+use crate::interface::windows::{ChatTextBox, ChatWindowStatePathExt, whisper_prefix};
+use crate::state::localization::LocalizationPathExt;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+/// Create the context menu of another player in the world.
+pub fn player_context_menu(state: &State<ClientState>, entity_id: EntityId, character_name: String) -> ContextMenu<ClientState> {
+    let localization = client_state().localization();
+    let is_friend = state
+        .get(&client_state().friend_list())
+        .iter()
+        .any(|friend| friend.name == character_name);
+
+    let whisper_name = character_name.clone();
+    let menu = ContextMenu::new()
+        .item(
+            state.get(&localization.whisper_button_text()).clone(),
+            move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                state.update_value(client_state().chat_window().current_text(), whisper_prefix(&whisper_name));
+                queue.queue(Event::FocusElement {
+                    focus_id: ChatTextBox.focus_id(),
+                });
+            },
+        )
+        .item(
+            state.get(&localization.trade_button_text()).clone(),
+            move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                queue.queue(InputEvent::RequestTrade { entity_id });
+            },
+        )
+        .separator();
+
+    let menu = match is_friend {
+        true => menu.disabled_item(state.get(&localization.add_friend_button_text()).clone()),
+        false => {
+            let character_name = character_name.clone();
+
+            menu.item(
+                state.get(&localization.add_friend_button_text()).clone(),
+                move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                    queue.queue(InputEvent::AddFriend {
+                        character_name: character_name.clone(),
+                    });
+                },
+            )
+        }
+    };
+
+    menu.item(
+        state.get(&localization.invite_to_party_button_text()).clone(),
+        move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+            queue.queue(InputEvent::InviteToParty {
+                character_name: character_name.clone(),
+            });
+        },
+    )
+}
+
+/// Create the context menu of a player in the friend list.
+pub fn friend_context_menu(state: &State<ClientState>, friend: &Friend) -> ContextMenu<ClientState> {
+    let localization = client_state().localization();
+    let account_id = friend.account_id;
+    let character_id = friend.character_id;
+
+    let whisper_name = friend.name.clone();
+    let recipient_name = friend.name.clone();
+
+    ContextMenu::new()
+        .item(
+            state.get(&localization.whisper_button_text()).clone(),
+            move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                state.update_value(client_state().chat_window().current_text(), whisper_prefix(&whisper_name));
+                queue.queue(Event::FocusElement {
+                    focus_id: ChatTextBox.focus_id(),
+                });
+            },
+        )
+        .item(
+            state.get(&localization.write_mail_button_text()).clone(),
+            move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                queue.queue(InputEvent::WriteMail {
+                    recipient_name: recipient_name.clone(),
+                });
+            },
+        )
+        .separator()
+        .item(
+            state.get(&localization.remove_button_text()).clone(),
+            move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                queue.queue(InputEvent::RemoveFriend { account_id, character_id });
+            },
+        )
+}
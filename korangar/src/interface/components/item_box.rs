@@ -1,4 +1,5 @@
 use korangar_interface::MouseMode;
+use korangar_interface::components::context_menu::ContextMenu;
 use korangar_interface::element::store::{ElementStore, ElementStoreMut};
 use korangar_interface::element::{BaseLayoutInfo, Element};
 use korangar_interface::event::{ClickHandler, DropHandler, Event, EventQueue};
@@ -13,7 +14,8 @@ use crate::input::{InputEvent, MouseInputMode};
 use crate::interface::resource::ItemSource;
 use crate::loaders::{FontSize, OverflowBehavior};
 use crate::renderer::LayoutExt;
-use crate::state::ClientState;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
 use crate::world::ResourceMetadata;
 
 #[derive(Default)]
@@ -77,9 +79,97 @@ where
     }
 }
 
+/// Opens the context menu of the item.
+struct ItemMenuHandler<P> {
+    item_path: P,
+    source: ItemSource,
+}
+
+impl<P> ItemMenuHandler<P> {
+    fn new(item_path: P, source: ItemSource) -> Self {
+        Self { item_path, source }
+    }
+}
+
+impl<P> ClickHandler<ClientState> for ItemMenuHandler<P>
+where
+    P: Path<ClientState, InventoryItem<ResourceMetadata>, false>,
+{
+    fn handle_click(&self, state: &State<ClientState>, queue: &mut EventQueue<ClientState>) {
+        // Unwrapping here is fine since we only register the handler if the slot has a
+        // item.
+        let item = state.try_get(&self.item_path).unwrap().clone();
+        let localization = client_state().localization();
+
+        let menu = match self.source {
+            ItemSource::Inventory => {
+                let mut menu = ContextMenu::new();
+
+                let inventory_amount = match item.details {
+                    InventoryItemDetails::Regular { amount, .. } => {
+                        let index = item.index;
+                        let text = state.get(&localization.use_item_button_text()).clone();
+
+                        menu = match item.is_usable() {
+                            true => menu.item(text, move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                queue.queue(InputEvent::UseItem { index });
+                            }),
+                            false => menu.disabled_item(text),
+                        };
+
+                        amount
+                    }
+                    InventoryItemDetails::Equippable { equip_position, .. } => {
+                        let item = item.clone();
+
+                        menu = menu.item(
+                            state.get(&localization.equip_item_button_text()).clone(),
+                            move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                queue.queue(InputEvent::MoveItem {
+                                    source: ItemSource::Inventory,
+                                    destination: ItemSource::Equipment { position: equip_position },
+                                    item: item.clone(),
+                                });
+                            },
+                        );
+
+                        1
+                    }
+                };
+
+                let index = item.index;
+
+                menu.separator().item(
+                    state.get(&localization.drop_item_button_text()).clone(),
+                    move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                        queue.queue(InputEvent::OpenDropItemWindow {
+                            index,
+                            amount: inventory_amount,
+                        });
+                    },
+                )
+            }
+            ItemSource::Equipment { position } => ContextMenu::new().item(
+                state.get(&localization.unequip_item_button_text()).clone(),
+                move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                    queue.queue(InputEvent::MoveItem {
+                        source: ItemSource::Equipment { position },
+                        destination: ItemSource::Inventory,
+                        item: item.clone(),
+                    });
+                },
+            ),
+            _ => return,
+        };
+
+        queue.queue(Event::OpenContextMenu { menu });
+    }
+}
+
 pub struct ItemBox<A> {
     item_path: A,
     handler: ItemBoxHandler<A>,
+    menu_handler: ItemMenuHandler<A>,
     amount_display: AmountDisplay,
 }
 
@@ -94,6 +184,7 @@ where
         Self {
             item_path,
             handler: ItemBoxHandler::new(item_path, source),
+            menu_handler: ItemMenuHandler::new(item_path, source),
             amount_display: AmountDisplay::default(),
         }
     }
@@ -178,6 +269,7 @@ where
 
             if is_hovered {
                 layout.register_click_handler(MouseButton::Left, &self.handler);
+                layout.register_click_handler(MouseButton::Right, &self.menu_handler);
             }
 
             if matches!(item.details, InventoryItemDetails::Regular { .. }) {
@@ -0,0 +1,22 @@
+use korangar_interface::components::rich_text::MarkupHandler;
+use korangar_interface::event::EventQueue;
+use korangar_interface::markup::{Link, LinkKind};
+use ragnarok_packets::ItemId;
+use rust_state::State;
+
+use crate::input::InputEvent;
+use crate::state::ClientState;
+
+/// Opens the item tooltip when clicking an `<ITEM>` link. Other links are
+/// ignored for now.
+pub struct ItemLinkHandler;
+
+impl MarkupHandler<ClientState> for ItemLinkHandler {
+    fn handle_link(&self, _: &State<ClientState>, queue: &mut EventQueue<ClientState>, link: Link<'_>) {
+        if link.kind == LinkKind::Item
+            && let Ok(item_id) = link.target.parse()
+        {
+            queue.queue(InputEvent::OpenItemTooltip { item_id: ItemId(item_id) });
+        }
+    }
+}
@@ -1,2 +1,4 @@
+pub mod entity_menu;
 pub mod item_box;
+pub mod item_link;
 pub mod skill_box;
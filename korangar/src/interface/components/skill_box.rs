@@ -2,6 +2,7 @@ use korangar_interface::MouseMode;
 use korangar_interface::element::store::{ElementStore, ElementStoreMut};
 use korangar_interface::element::{BaseLayoutInfo, Element};
 use korangar_interface::event::{ClickHandler, DropHandler, Event, EventQueue};
+use korangar_interface::layout::area::Area;
 use korangar_interface::layout::tooltip::TooltipExt;
 use korangar_interface::layout::{MouseButton, Resolvers, WindowLayout, with_single_resolver};
 use ragnarok_packets::SkillLevel;
@@ -12,7 +13,7 @@ use crate::input::{InputEvent, MouseInputMode};
 use crate::interface::resource::SkillSource;
 use crate::loaders::{FontSize, OverflowBehavior};
 use crate::renderer::LayoutExt;
-use crate::state::skills::{LearnableSkill, LearnedSkill};
+use crate::state::skills::{LearnableSkill, LearnedSkill, SkillCooldown};
 use crate::state::theme::{GlobalThemePathExt, InterfaceThemePathExt, SkillTreeThemePathExt};
 use crate::state::{ClientState, client_theme};
 
@@ -39,6 +40,44 @@ impl LevelDisplay {
     }
 }
 
+/// Cover the part of the skill area that corresponds to the remaining cooldown
+/// and display the remaining seconds on top.
+pub fn lay_out_cooldown<'a>(
+    state: &'a State<ClientState>,
+    cooldown: &'a SkillCooldown,
+    area: Area,
+    corner_diameter: CornerDiameter,
+    layout: &mut WindowLayout<'a, ClientState>,
+) {
+    use korangar_interface::prelude::*;
+
+    let covered_height = area.height * cooldown.remaining;
+    let covered_area = Area {
+        top: area.top + area.height - covered_height,
+        height: covered_height,
+        ..area
+    };
+
+    layout.add_rectangle(
+        covered_area,
+        corner_diameter,
+        *state.get(&client_theme().skill_tree().cooldown_color()),
+        Color::TRANSPARENT,
+        ShadowPadding::default(),
+    );
+
+    layout.add_text(
+        area,
+        &cooldown.remaining_text,
+        *state.get(&client_theme().skill_tree().cooldown_font_size()),
+        *state.get(&client_theme().skill_tree().cooldown_text_color()),
+        *state.get(&client_theme().skill_tree().highlight_color()),
+        HorizontalAlignment::Center { offset: 0.0, border: 0.0 },
+        VerticalAlignment::Center { offset: 0.0 },
+        OverflowBehavior::Shrink,
+    );
+}
+
 struct SkillBoxHandler<A> {
     skill_path: A,
     source: SkillSource,
@@ -88,26 +127,29 @@ where
     }
 }
 
-pub struct SkillBox<A, B> {
+pub struct SkillBox<A, B, C> {
     learnable_skill_path: A,
     learned_skill_path: B,
+    cooldown_path: C,
     handler: SkillBoxHandler<A>,
     level_display: LevelDisplay,
     source: SkillSource,
 }
 
-impl<A, B> SkillBox<A, B>
+impl<A, B, C> SkillBox<A, B, C>
 where
     A: Copy,
     B: Copy,
+    C: Copy,
 {
     /// This function is supposed to be called from a component macro
     /// and not intended to be called manually.
     #[inline(always)]
-    pub fn component_new(learnable_skill_path: A, learned_skill_path: B, source: SkillSource) -> Self {
+    pub fn component_new(learnable_skill_path: A, learned_skill_path: B, cooldown_path: C, source: SkillSource) -> Self {
         Self {
             learnable_skill_path,
             learned_skill_path,
+            cooldown_path,
             handler: SkillBoxHandler::new(learnable_skill_path, source),
             level_display: LevelDisplay::default(),
             source,
@@ -115,10 +157,11 @@ where
     }
 }
 
-impl<A, B> Element<ClientState> for SkillBox<A, B>
+impl<A, B, C> Element<ClientState> for SkillBox<A, B, C>
 where
     A: Path<ClientState, LearnableSkill, false>,
     B: Path<ClientState, LearnedSkill, false>,
+    C: Path<ClientState, SkillCooldown, false>,
 {
     type LayoutInfo = BaseLayoutInfo;
 
@@ -211,6 +254,10 @@ where
                 layout.add_sprite(layout_info.area, actions, sprite, &learnable_skill.animation_state, color, 1.0);
             }
 
+            if let Some(cooldown) = state.try_get(&self.cooldown_path) {
+                lay_out_cooldown(state, cooldown, layout_info.area, CornerDiameter::uniform(20.0), layout);
+            }
+
             if is_hovered {
                 layout.register_click_handler(MouseButton::Left, &self.handler);
 
@@ -1,3 +1,4 @@
+pub mod clipboard;
 pub mod components;
 pub mod cursor;
 pub mod resource;
@@ -3,6 +3,9 @@ use ragnarok_packets::{EquipPosition, HotbarSlot};
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ItemSource {
     Inventory,
+    Cart,
+    MailAttachment,
+    MailDraft,
     Equipment { position: EquipPosition },
 }
 
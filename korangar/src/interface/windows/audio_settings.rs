@@ -40,6 +40,27 @@ where
                     state: self.audio_settings_path.mute_on_focus_loss(),
                     event: Toggle(self.audio_settings_path.mute_on_focus_loss()),
                 },
+                slider! {
+                    text: client_state().localization().background_music_volume_slider_text(),
+                    state: self.audio_settings_path.background_music_volume(),
+                    minimum: 0.0,
+                    maximum: 1.0,
+                    step: 0.05,
+                },
+                slider! {
+                    text: client_state().localization().sound_effect_volume_slider_text(),
+                    state: self.audio_settings_path.sound_effect_volume(),
+                    minimum: 0.0,
+                    maximum: 1.0,
+                    step: 0.05,
+                },
+                slider! {
+                    text: client_state().localization().spatial_sound_effect_volume_slider_text(),
+                    state: self.audio_settings_path.spatial_sound_effect_volume(),
+                    minimum: 0.0,
+                    maximum: 1.0,
+                    step: 0.05,
+                },
             ),
         }
     }
@@ -10,21 +10,45 @@ use super::WindowClass;
 use crate::graphics::ScreenSize;
 use crate::state::ClientState;
 
-#[derive(Serialize, Deserialize)]
+/// Name of the window layout that is used if no other layout was selected.
+pub const DEFAULT_WINDOW_LAYOUT_NAME: &str = "Default";
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct WindowState {
     pub anchor: Anchor<ClientState>,
     pub size: ScreenSize,
+    /// Class of the window that this window is docked with.
+    #[serde(default)]
+    pub docked_with: Option<WindowClass>,
 }
 
 impl WindowState {
     pub fn new(anchor: Anchor<ClientState>, size: ScreenSize) -> Self {
-        Self { anchor, size }
+        Self {
+            anchor,
+            size,
+            docked_with: None,
+        }
     }
 }
 
-#[derive(Default, Serialize, Deserialize)]
+/// Positions and sizes of all windows that were opened with a given layout.
+type WindowLayout = HashMap<WindowClass, WindowState>;
+
+/// Stores the position and size of windows for multiple named window layouts,
+/// for example one per character or per resolution.
 pub struct WindowCache {
-    entries: HashMap<WindowClass, WindowState>,
+    active_layout: String,
+    layouts: HashMap<String, WindowLayout>,
+}
+
+impl Default for WindowCache {
+    fn default() -> Self {
+        Self {
+            active_layout: DEFAULT_WINDOW_LAYOUT_NAME.to_string(),
+            layouts: HashMap::new(),
+        }
+    }
 }
 
 impl WindowCache {
@@ -41,19 +65,72 @@ impl WindowCache {
         #[cfg(feature = "debug")]
         print_debug!("loading window cache from {}", Self::FILE_NAME.magenta());
 
-        std::fs::read_to_string(Self::FILE_NAME)
-            .ok()
-            .and_then(|data| ron::from_str(&data).ok())
-            .map(|entries| Self { entries })
+        let data = std::fs::read_to_string(Self::FILE_NAME).ok()?;
+
+        // Older versions only stored a single layout, so we use that as the
+        // default layout.
+        let layouts = ron::from_str(&data).ok().or_else(|| {
+            ron::from_str::<WindowLayout>(&data)
+                .ok()
+                .map(|entries| HashMap::from([(DEFAULT_WINDOW_LAYOUT_NAME.to_string(), entries)]))
+        })?;
+
+        Some(Self {
+            layouts,
+            ..Default::default()
+        })
     }
 
     fn save(&self) {
         #[cfg(feature = "debug")]
         print_debug!("saving window cache to {}", Self::FILE_NAME.magenta());
 
-        let data = ron::ser::to_string_pretty(&self.entries, PrettyConfig::new()).unwrap();
+        let data = ron::ser::to_string_pretty(&self.layouts, PrettyConfig::new()).unwrap();
         std::fs::write(Self::FILE_NAME, data).expect("unable to write file");
     }
+
+    fn entries(&self) -> Option<&WindowLayout> {
+        self.layouts.get(&self.active_layout)
+    }
+
+    fn entries_mut(&mut self) -> &mut WindowLayout {
+        self.layouts.entry(self.active_layout.clone()).or_default()
+    }
+
+    /// Names of all saved window layouts, starting with the default layout.
+    pub fn layout_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .layouts
+            .keys()
+            .filter(|name| name.as_str() != DEFAULT_WINDOW_LAYOUT_NAME)
+            .cloned()
+            .collect();
+
+        names.sort_unstable();
+        names.insert(0, DEFAULT_WINDOW_LAYOUT_NAME.to_string());
+        names
+    }
+
+    /// Switch to a different window layout. Windows that are not part of the
+    /// layout yet will be opened at their default position.
+    pub fn switch_layout(&mut self, name: &str) {
+        #[cfg(feature = "debug")]
+        print_debug!("switching to window layout {}", name.magenta());
+
+        self.active_layout = name.to_string();
+    }
+
+    /// Save the current window layout under a new name, overwriting any layout
+    /// with the same name.
+    pub fn save_layout_as(&mut self, name: &str) {
+        let entries = self.entries().cloned().unwrap_or_default();
+        self.layouts.insert(name.to_string(), entries);
+    }
+
+    /// Forget the positions and sizes of all windows in the current layout.
+    pub fn reset_layout(&mut self) {
+        self.entries_mut().clear();
+    }
 }
 
 impl korangar_interface::application::WindowCache<ClientState> for WindowCache {
@@ -70,30 +147,46 @@ impl korangar_interface::application::WindowCache<ClientState> for WindowCache {
     }
 
     fn get_window_state(&self, class: WindowClass) -> Option<(Anchor<ClientState>, ScreenSize)> {
-        self.entries.get(&class).map(|entry| (entry.anchor, entry.size))
+        self.entries()
+            .and_then(|entries| entries.get(&class))
+            .map(|entry| (entry.anchor, entry.size))
     }
 
     fn register_window(&mut self, class: WindowClass, anchor: Anchor<ClientState>, size: ScreenSize) {
-        if let Some(entry) = self.entries.get_mut(&class) {
+        let entries = self.entries_mut();
+
+        if let Some(entry) = entries.get_mut(&class) {
             entry.anchor = anchor;
             entry.size = size;
         } else {
             let entry = WindowState::new(anchor, size);
-            self.entries.insert(class, entry);
+            entries.insert(class, entry);
         }
     }
 
     fn update_anchor(&mut self, class: WindowClass, anchor: Anchor<ClientState>) {
-        if let Some(entry) = self.entries.get_mut(&class) {
+        if let Some(entry) = self.entries_mut().get_mut(&class) {
             entry.anchor = anchor;
         }
     }
 
     fn update_size(&mut self, class: WindowClass, size: ScreenSize) {
-        if let Some(entry) = self.entries.get_mut(&class) {
+        if let Some(entry) = self.entries_mut().get_mut(&class) {
             entry.size = size;
         }
     }
+
+    fn get_tab_group(&self, class: WindowClass) -> Option<WindowClass> {
+        self.entries()
+            .and_then(|entries| entries.get(&class))
+            .and_then(|entry| entry.docked_with)
+    }
+
+    fn update_tab_group(&mut self, class: WindowClass, docked_with: Option<WindowClass>) {
+        if let Some(entry) = self.entries_mut().get_mut(&class) {
+            entry.docked_with = docked_with;
+        }
+    }
 }
 
 impl Drop for WindowCache {
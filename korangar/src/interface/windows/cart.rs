@@ -0,0 +1,133 @@
+use std::cell::UnsafeCell;
+use std::fmt::Display;
+
+use korangar_components::item_box;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::{Path, PathExt, Selector, VecIndexExt};
+
+use crate::ItemSource;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::cart::{Cart, CartPathExt};
+use crate::state::inventory::InventoryPathExt;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+pub(super) struct CapacityTextSelector<A, B, T> {
+    current_path: A,
+    maximum_path: B,
+    last_values: UnsafeCell<Option<(T, T)>>,
+    text: UnsafeCell<String>,
+}
+
+impl<A, B, T> CapacityTextSelector<A, B, T> {
+    pub fn new(current_path: A, maximum_path: B) -> Self {
+        Self {
+            current_path,
+            maximum_path,
+            last_values: UnsafeCell::default(),
+            text: UnsafeCell::default(),
+        }
+    }
+}
+
+impl<A, B, T> Selector<ClientState, String> for CapacityTextSelector<A, B, T>
+where
+    A: Path<ClientState, T>,
+    B: Path<ClientState, T>,
+    T: Copy + PartialEq + Display + 'static,
+{
+    fn select<'a>(&'a self, state: &'a ClientState) -> Option<&'a String> {
+        let current = *self.current_path.follow_safe(state);
+        let maximum = *self.maximum_path.follow_safe(state);
+
+        unsafe {
+            let last_values = &mut *self.last_values.get();
+
+            if last_values.is_none_or(|last| last != (current, maximum)) {
+                *self.text.get() = format!("{current} / {maximum}");
+                *last_values = Some((current, maximum));
+            }
+        }
+
+        unsafe { Some(self.text.as_ref_unchecked()) }
+    }
+}
+
+pub struct CartWindow<P> {
+    cart_path: P,
+}
+
+impl<P> CartWindow<P> {
+    pub fn new(cart_path: P) -> Self {
+        Self { cart_path }
+    }
+}
+
+impl<P> CustomWindow<ClientState> for CartWindow<P>
+where
+    P: Path<ClientState, Cart>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::Cart)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        // The server allows a maximum of 100 items in the cart.
+        const CART_ROWS: usize = 10;
+        const CART_COLUMNS: usize = 10;
+
+        let items_path = self.cart_path.inventory().items();
+
+        window! {
+            title: client_state().localization().cart_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().cart_items_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        text! {
+                            text: CapacityTextSelector::new(self.cart_path.item_count(), self.cart_path.maximum_item_count()),
+                            horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                    ),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().cart_weight_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        text! {
+                            text: CapacityTextSelector::new(self.cart_path.weight(), self.cart_path.maximum_weight()),
+                            horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                    ),
+                },
+                std::array::from_fn::<_, CART_ROWS, _>(|row| {
+                    split! {
+                        gaps: theme().window().gaps(),
+                        children: std::array::from_fn::<_, CART_COLUMNS, _>(|column| {
+                            let path = items_path.index(row * CART_COLUMNS + column);
+
+                            item_box! {
+                                item_path: path,
+                                source: ItemSource::Cart,
+                            }
+                        }),
+                    }
+                }),
+            ),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use korangar_interface::window::{CustomWindow, Window};
-use rust_state::Path;
+use rust_state::{Path, PathExt, Selector};
 
 use crate::graphics::Color;
 use crate::input::InputEvent;
@@ -9,27 +9,48 @@ use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::InterfaceThemeType;
 use crate::state::{ClientState, ClientStatePathExt, client_state};
 
-pub struct CharacterOverviewWindow<A, B, C> {
+/// Selects the text of the mailbox button, which indicates if there is new
+/// mail.
+struct MailboxButtonTextSelector<P> {
+    has_new_mail_path: P,
+}
+
+impl<P> Selector<ClientState, String> for MailboxButtonTextSelector<P>
+where
+    P: Path<ClientState, bool>,
+{
+    fn select<'a>(&'a self, state: &'a ClientState) -> Option<&'a String> {
+        match *self.has_new_mail_path.follow_safe(state) {
+            true => client_state().localization().mailbox_new_mail_button_text().follow(state),
+            false => client_state().localization().mailbox_button_text().follow(state),
+        }
+    }
+}
+
+pub struct CharacterOverviewWindow<A, B, C, D> {
     player_name_path: A,
     base_level_path: B,
     job_level_path: C,
+    has_new_mail_path: D,
 }
 
-impl<A, B, C> CharacterOverviewWindow<A, B, C> {
-    pub fn new(player_name_path: A, base_level_path: B, job_level_path: C) -> Self {
+impl<A, B, C, D> CharacterOverviewWindow<A, B, C, D> {
+    pub fn new(player_name_path: A, base_level_path: B, job_level_path: C, has_new_mail_path: D) -> Self {
         Self {
             player_name_path,
             base_level_path,
             job_level_path,
+            has_new_mail_path,
         }
     }
 }
 
-impl<A, B, C> CustomWindow<ClientState> for CharacterOverviewWindow<A, B, C>
+impl<A, B, C, D> CustomWindow<ClientState> for CharacterOverviewWindow<A, B, C, D>
 where
     A: Path<ClientState, String>,
     B: Path<ClientState, usize>,
     C: Path<ClientState, usize>,
+    D: Path<ClientState, bool>,
 {
     fn window_class() -> Option<WindowClass> {
         Some(WindowClass::CharacterOverview)
@@ -112,6 +133,12 @@ where
                     text: client_state().localization().friend_list_button_text(),
                     event: InputEvent::ToggleFriendListWindow,
                 },
+                button! {
+                    text: MailboxButtonTextSelector {
+                        has_new_mail_path: self.has_new_mail_path,
+                    },
+                    event: InputEvent::ToggleMailboxWindow,
+                },
                 button! {
                     text: client_state().localization().menu_button_text(),
                     event: InputEvent::ToggleMenuWindow,
@@ -1,18 +1,26 @@
+use std::cell::Cell;
+
 use korangar_interface::application::Size;
+use korangar_interface::components::rich_text::{MarkupHandler, lay_out_inline, resolve_inline_areas};
 use korangar_interface::components::text_box::DefaultHandler;
 use korangar_interface::element::store::{ElementStore, ElementStoreMut};
 use korangar_interface::element::{Element, StateElement};
+use korangar_interface::event::{ClickHandler, EventQueue};
 use korangar_interface::layout::area::Area;
-use korangar_interface::layout::{Resolvers, WindowLayout, with_single_resolver};
+use korangar_interface::layout::{MouseButton, Resolvers, WindowLayout, with_single_resolver};
+use korangar_interface::markup::{InlineArea, Link, MarkupSpan, inline_span};
 use korangar_interface::prelude::{HorizontalAlignment, VerticalAlignment};
 use korangar_interface::window::{CustomWindow, Window};
 use korangar_networking::MessageColor;
+use ragnarok_packets::ItemId;
 use rust_state::{Path, RustState, State};
 
 use super::WindowClass;
 use crate::graphics::Color;
 use crate::input::InputEvent;
+use crate::interface::components::item_link::ItemLinkHandler;
 use crate::loaders::{FontSize, OverflowBehavior};
+use crate::renderer::LayoutExt;
 use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::{ChatThemePathExt, InterfaceThemePathExt, InterfaceThemeType};
 use crate::state::{ChatMessage, ClientState, ClientStatePathExt, client_state, client_theme};
@@ -20,22 +28,107 @@ use crate::state::{ChatMessage, ClientState, ClientStatePathExt, client_state, c
 const MAXIMUM_CHAT_MESSAGE_LENGTH: usize = 80;
 
 /// ZST for getting the focus id of the chat text box. This is only needed to
-/// focus the chat when pressing enter.
+/// focus the chat from outside of the chat window, e.g. when pressing enter.
 pub struct ChatTextBox;
 
+/// Prefix of chat messages that are whispered to another player, e.g.
+/// `/w "Name" Hello`.
+const WHISPER_PREFIX: &str = "/w \"";
+
+/// Start of a chat message that is whispered to the given player.
+pub fn whisper_prefix(recipient_name: &str) -> String {
+    format!("{WHISPER_PREFIX}{recipient_name}\" ")
+}
+
+/// Split a chat message into the name of the recipient and the text if it is
+/// a whisper.
+pub fn parse_whisper(text: &str) -> Option<(&str, &str)> {
+    let (recipient_name, text) = text.strip_prefix(WHISPER_PREFIX)?.split_once('"')?;
+    Some((recipient_name, text.trim_start()))
+}
+
+// TODO: Theme this.
+const MESSAGE_FONT_SIZE: FontSize = FontSize(14.0);
+// TODO: Theme this.
+const MESSAGE_SPACING: f32 = 5.0;
+const MESSAGE_HORIZONTAL_ALIGNMENT: HorizontalAlignment = HorizontalAlignment::Left { offset: 5.0, border: 3.0 };
+const MESSAGE_VERTICAL_ALIGNMENT: VerticalAlignment = VerticalAlignment::Center { offset: 0.0 };
+const LINK_COLOR: Color = Color::rgb_u8(255, 160, 60);
+
+fn message_color(chat_message: &ChatMessage) -> Color {
+    match chat_message.color {
+        MessageColor::Rgb { red, green, blue } => Color::rgb_u8(red, green, blue),
+        // TODO: Make the color right.
+        MessageColor::Broadcast => Color::monochrome_u8(255),
+        // TODO: Make the color right.
+        MessageColor::Server => Color::monochrome_u8(255),
+        // TODO: Make the color right.
+        MessageColor::Error => Color::monochrome_u8(255),
+        // TODO: Make the color right.
+        MessageColor::Information => Color::monochrome_u8(255),
+    }
+}
+
+impl MarkupHandler<ClientState> for ChatMessage {
+    fn handle_link(&self, state: &State<ClientState>, queue: &mut EventQueue<ClientState>, link: Link<'_>) {
+        ItemLinkHandler.handle_link(state, queue, link);
+    }
+
+    fn lay_out_icon<'a>(&'a self, _: &'a State<ClientState>, source: &str, area: Area, layout: &mut WindowLayout<'a, ClientState>) {
+        let Ok(item_id) = source.parse().map(ItemId) else {
+            return;
+        };
+
+        if let Some(texture) = self
+            .icons
+            .iter()
+            .find(|icon| icon.item_id == item_id)
+            .and_then(|icon| icon.texture.as_ref())
+        {
+            layout.add_texture(area, texture.clone(), Color::WHITE, false);
+        }
+    }
+}
+
 struct ChatLayoutInfo {
     area: Area,
     // TODO: Don't allocate this every frame.
-    message_heights: Vec<f32>,
+    message_areas: Vec<Area>,
+    inline_areas: Vec<Vec<InlineArea>>,
 }
 
 struct ChatElement<A> {
     chat_messages_path: A,
+    /// Index of the message and the link that was hovered during the last
+    /// layout.
+    hovered_link: Cell<Option<(usize, usize)>>,
 }
 
 impl<A> ChatElement<A> {
     fn new(chat_messages_path: A) -> Self {
-        Self { chat_messages_path }
+        Self {
+            chat_messages_path,
+            hovered_link: Cell::new(None),
+        }
+    }
+}
+
+impl<A> ClickHandler<ClientState> for ChatElement<A>
+where
+    A: Path<ClientState, Vec<ChatMessage>>,
+{
+    fn handle_click(&self, state: &State<ClientState>, queue: &mut EventQueue<ClientState>) {
+        let Some((message_index, link_index)) = self.hovered_link.get() else {
+            return;
+        };
+
+        let Some(chat_message) = state.get(&self.chat_messages_path).get(message_index) else {
+            return;
+        };
+
+        if let Some(MarkupSpan::Text { link: Some(link), .. }) = inline_span(&chat_message.text, link_index) {
+            chat_message.handle_link(state, queue, link);
+        }
     }
 }
 
@@ -53,48 +146,66 @@ where
     ) -> Self::LayoutInfo {
         with_single_resolver(resolvers, |resolver| {
             let chat_messages = state.get(&self.chat_messages_path);
-            // TODO: Theme this.
-            let message_spacing = 5.0;
 
             let mut total_height = 0.0;
-            let message_heights = chat_messages
+            let message_offsets: Vec<(f32, f32)> = chat_messages
                 .iter()
                 .map(|chat_message| {
-                    let color = match chat_message.color {
-                        MessageColor::Rgb { red, green, blue } => Color::rgb_u8(red, green, blue),
-                        // TODO: Make the color right.
-                        MessageColor::Broadcast => Color::monochrome_u8(255),
-                        // TODO: Make the color right.
-                        MessageColor::Server => Color::monochrome_u8(255),
-                        // TODO: Make the color right.
-                        MessageColor::Error => Color::monochrome_u8(255),
-                        // TODO: Make the color right.
-                        MessageColor::Information => Color::monochrome_u8(255),
-                    };
-
                     let (size, _) = resolver.get_text_dimensions(
                         &chat_message.text,
-                        color,
-                        Color::rgb_u8(255, 160, 60),
-                        // TODO: Theme this.
-                        FontSize(14.0),
-                        HorizontalAlignment::Left { offset: 5.0, border: 3.0 },
+                        message_color(chat_message),
+                        LINK_COLOR,
+                        MESSAGE_FONT_SIZE,
+                        MESSAGE_HORIZONTAL_ALIGNMENT,
                         OverflowBehavior::LineBreak,
                     );
 
                     if total_height != 0.0 {
-                        total_height += message_spacing;
+                        total_height += MESSAGE_SPACING;
                     }
 
+                    let offset = total_height;
                     total_height += size.height();
 
-                    size.height()
+                    (offset, size.height())
                 })
                 .collect();
 
             let area = resolver.with_height(total_height);
 
-            Self::LayoutInfo { area, message_heights }
+            let message_areas: Vec<Area> = message_offsets
+                .into_iter()
+                .map(|(offset, height)| Area {
+                    left: area.left,
+                    top: area.top + offset,
+                    width: area.width,
+                    height,
+                })
+                .collect();
+
+            let inline_areas = chat_messages
+                .iter()
+                .zip(message_areas.iter())
+                .map(|(chat_message, message_area)| {
+                    resolve_inline_areas::<ClientState>(
+                        resolver.get_text_layouter(),
+                        *message_area,
+                        &chat_message.text,
+                        message_color(chat_message),
+                        LINK_COLOR,
+                        MESSAGE_FONT_SIZE,
+                        MESSAGE_HORIZONTAL_ALIGNMENT,
+                        MESSAGE_VERTICAL_ALIGNMENT,
+                        OverflowBehavior::LineBreak,
+                    )
+                })
+                .collect();
+
+            Self::LayoutInfo {
+                area,
+                message_areas,
+                inline_areas,
+            }
         })
     }
 
@@ -106,51 +217,35 @@ where
         layout: &mut WindowLayout<'a, ClientState>,
     ) {
         let chat_messages = state.get(&self.chat_messages_path);
-        // TODO: Theme this.
-        let message_spacing = 5.0;
+        let mut hovered_link = None;
 
-        let mut offset = 0.0;
-        chat_messages
+        for (message_index, ((chat_message, message_area), inline_areas)) in chat_messages
             .iter()
-            .zip(layout_info.message_heights.iter())
-            .for_each(|(chat_message, message_height)| {
-                let color = match chat_message.color {
-                    MessageColor::Rgb { red, green, blue } => Color::rgb_u8(red, green, blue),
-                    // TODO: Make the color right.
-                    MessageColor::Broadcast => Color::monochrome_u8(255),
-                    // TODO: Make the color right.
-                    MessageColor::Server => Color::monochrome_u8(255),
-                    // TODO: Make the color right.
-                    MessageColor::Error => Color::monochrome_u8(255),
-                    // TODO: Make the color right.
-                    MessageColor::Information => Color::monochrome_u8(255),
-                };
-
-                if offset != 0.0 {
-                    offset += message_spacing;
-                }
-
-                let text_area = Area {
-                    left: layout_info.area.left,
-                    top: layout_info.area.top + offset,
-                    width: layout_info.area.width,
-                    height: *message_height,
-                };
-
-                layout.add_text(
-                    text_area,
-                    &chat_message.text,
-                    // TODO: Theme this.
-                    FontSize(14.0),
-                    color,
-                    Color::rgb_u8(255, 160, 60),
-                    HorizontalAlignment::Left { offset: 5.0, border: 3.0 },
-                    VerticalAlignment::Center { offset: 0.0 },
-                    OverflowBehavior::LineBreak,
-                );
-
-                offset += message_height;
-            });
+            .zip(layout_info.message_areas.iter())
+            .zip(layout_info.inline_areas.iter())
+            .enumerate()
+        {
+            layout.add_text(
+                *message_area,
+                &chat_message.text,
+                MESSAGE_FONT_SIZE,
+                message_color(chat_message),
+                LINK_COLOR,
+                MESSAGE_HORIZONTAL_ALIGNMENT,
+                MESSAGE_VERTICAL_ALIGNMENT,
+                OverflowBehavior::LineBreak,
+            );
+
+            if let Some(link_index) = lay_out_inline(state, &chat_message.text, inline_areas, chat_message, layout) {
+                hovered_link = Some((message_index, link_index));
+            }
+        }
+
+        self.hovered_link.set(hovered_link);
+
+        if hovered_link.is_some() {
+            layout.register_click_handler(MouseButton::Left, self);
+        }
     }
 }
 
@@ -226,3 +321,22 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::interface::windows::chat::{parse_whisper, whisper_prefix};
+
+    #[test]
+    fn parse_whisper_prefix() {
+        let text = format!("{}Hello there", whisper_prefix("Some Player"));
+
+        assert_eq!(parse_whisper(&text), Some(("Some Player", "Hello there")));
+    }
+
+    #[test]
+    fn parse_regular_message() {
+        assert_eq!(parse_whisper("Hello there"), None);
+        assert_eq!(parse_whisper("/w Some Player Hello"), None);
+        assert_eq!(parse_whisper("/w \"Unterminated"), None);
+    }
+}
@@ -0,0 +1,167 @@
+use std::cmp::Ordering;
+
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, ElementBox};
+use korangar_interface::layout::{Resolvers, WindowLayout, with_single_resolver};
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::{ChatRoomMember, ChatRoomMemberPathExt, ChatRoomRole};
+use rust_state::{ManuallyAssertExt, Path, PathExt, State, VecIndexExt};
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::chat_room::{ChatRoom, ChatRoomPathExt};
+use crate::state::localization::LocalizationPathExt;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+/// Returns `true` if the player owns the chat room and the member is someone
+/// else, so the player is allowed to kick them or pass on the ownership.
+fn can_manage_member(state: &ClientState, member: &ChatRoomMember) -> bool {
+    let player_name = client_state().player_name().follow_safe(state);
+
+    member.name != *player_name && client_state().chat_rooms().follow_safe(state).is_owner(player_name)
+}
+
+struct ChatRoomMemberList<A> {
+    members_path: A,
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl<A> ChatRoomMemberList<A> {
+    fn new(members_path: A) -> Self {
+        Self {
+            members_path,
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl<A> Element<ClientState> for ChatRoomMemberList<A>
+where
+    A: Path<ClientState, Vec<ChatRoomMember>>,
+{
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &State<ClientState>,
+        mut store: ElementStoreMut,
+        resolvers: &mut dyn Resolvers<ClientState>,
+    ) -> Self::LayoutInfo {
+        with_single_resolver(resolvers, |resolver| {
+            use korangar_interface::prelude::*;
+
+            let members = state.get(&self.members_path);
+
+            match members.len().cmp(&self.elements.len()) {
+                Ordering::Less => {
+                    self.elements.truncate(members.len());
+                }
+                Ordering::Equal => {}
+                Ordering::Greater => {
+                    for index in self.elements.len()..members.len() {
+                        let member_path = self.members_path.index(index).manually_asserted();
+
+                        self.elements.push(ErasedElement::new(split! {
+                            gaps: theme().window().gaps(),
+                            children: (
+                                text! {
+                                    text: member_path.name(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                button! {
+                                    text: client_state().localization().make_owner_button_text(),
+                                    disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                                        let member = member_path.follow_safe(state);
+                                        member.role == ChatRoomRole::Owner || !can_manage_member(state, member)
+                                    }),
+                                    event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                        let name = state.get(&member_path).name.clone();
+                                        queue.queue(InputEvent::ChangeChatRoomOwner { name });
+                                    },
+                                },
+                                button! {
+                                    text: client_state().localization().kick_button_text(),
+                                    disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                                        !can_manage_member(state, member_path.follow_safe(state))
+                                    }),
+                                    event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                        let name = state.get(&member_path).name.clone();
+                                        queue.queue(InputEvent::KickChatRoomMember { name });
+                                    },
+                                },
+                            ),
+                        }));
+                    }
+                }
+            }
+
+            self.elements.iter_mut().enumerate().for_each(|(index, element)| {
+                element.create_layout_info(state, store.child_store(index as u64), resolver);
+            });
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        self.elements.iter().enumerate().for_each(|(index, element)| {
+            element.lay_out(state, store.child_store(index as u64), &(), layout);
+        });
+    }
+}
+
+pub struct ChatRoomWindow<A> {
+    chat_room_path: A,
+}
+
+impl<A> ChatRoomWindow<A> {
+    pub fn new(chat_room_path: A) -> Self {
+        Self { chat_room_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for ChatRoomWindow<A>
+where
+    A: Path<ClientState, ChatRoom>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::ChatRoom)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: self.chat_room_path.title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                ChatRoomMemberList::new(self.chat_room_path.members()),
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: client_state().localization().change_chat_room_button_text(),
+                            disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                                let player_name = client_state().player_name().follow_safe(state);
+                                !client_state().chat_rooms().follow_safe(state).is_owner(player_name)
+                            }),
+                            event: InputEvent::OpenChangeChatRoomWindow,
+                        },
+                        button! {
+                            text: client_state().localization().leave_chat_room_button_text(),
+                            event: InputEvent::LeaveChatRoom,
+                        },
+                    ),
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::{Path, PathExt, State};
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+const MAXIMUM_PASSWORD_LENGTH: usize = 8;
+
+/// Window for entering the password of a private chat room.
+pub struct ChatRoomPasswordWindow<A> {
+    password_path: A,
+}
+
+impl<A> ChatRoomPasswordWindow<A> {
+    pub fn new(password_path: A) -> Self {
+        Self { password_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for ChatRoomPasswordWindow<A>
+where
+    A: Path<ClientState, String>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::ChatRoomPassword)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        struct PasswordTextBox;
+
+        let password_path = self.password_path;
+
+        let password_action = move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+            if !state.get(&password_path).is_empty() {
+                queue.queue(InputEvent::JoinChatRoom);
+            }
+        };
+
+        window! {
+            title: client_state().localization().chat_room_password_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                text_box! {
+                    ghost_text: client_state().localization().chat_room_password_text_box_message(),
+                    state: password_path,
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_PASSWORD_LENGTH>::new(password_path, password_action),
+                    focus_id: PasswordTextBox,
+                    hidable: true,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                button! {
+                    text: client_state().localization().join_chat_room_button_text(),
+                    disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                        password_path.follow_safe(state).is_empty()
+                    }),
+                    event: InputEvent::JoinChatRoom,
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::element::id::FocusIdExt;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::{Path, PathExt, State};
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::chat_room::{ChatRoomDraft, ChatRoomDraftPathExt, ChatRoomsPathExt};
+use crate::state::localization::LocalizationPathExt;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+const MAXIMUM_TITLE_LENGTH: usize = 36;
+/// Enough digits for the maximum number of members.
+const MAXIMUM_LIMIT_LENGTH: usize = 2;
+const MAXIMUM_PASSWORD_LENGTH: usize = 8;
+
+/// Window for creating a new chat room or changing the settings of the
+/// current one if the player is already in a room.
+pub struct ChatRoomSettingsWindow<A> {
+    draft_path: A,
+}
+
+impl<A> ChatRoomSettingsWindow<A> {
+    pub fn new(draft_path: A) -> Self {
+        Self { draft_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for ChatRoomSettingsWindow<A>
+where
+    A: Path<ClientState, ChatRoomDraft>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::ChatRoomSettings)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        struct TitleTextBox;
+        struct LimitTextBox;
+        struct PasswordTextBox;
+
+        let title_path = self.draft_path.title();
+        let limit_path = self.draft_path.limit();
+        let password_path = self.draft_path.password();
+        let is_public_path = self.draft_path.is_public();
+
+        let title_action = move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+            if !state.get(&title_path).is_empty() {
+                queue.queue(Event::FocusElement {
+                    focus_id: LimitTextBox.focus_id(),
+                });
+            }
+        };
+
+        let limit_action = move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| match *state.get(&is_public_path) {
+            true => queue.queue(Event::Unfocus),
+            false => queue.queue(Event::FocusElement {
+                focus_id: PasswordTextBox.focus_id(),
+            }),
+        };
+
+        let password_action = move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+            queue.queue(Event::Unfocus);
+        };
+
+        let is_incomplete = move |state: &ClientState| {
+            title_path.follow_safe(state).is_empty() || (!*is_public_path.follow_safe(state) && password_path.follow_safe(state).is_empty())
+        };
+
+        window! {
+            title: client_state().localization().chat_room_settings_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                text_box! {
+                    ghost_text: client_state().localization().chat_room_title_text_box_message(),
+                    state: title_path,
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_TITLE_LENGTH>::new(title_path, title_action),
+                    focus_id: TitleTextBox,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                text_box! {
+                    ghost_text: client_state().localization().chat_room_limit_text_box_message(),
+                    state: limit_path,
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_LIMIT_LENGTH>::new(limit_path, limit_action),
+                    focus_id: LimitTextBox,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                state_button! {
+                    text: client_state().localization().public_chat_room_button_text(),
+                    state: is_public_path,
+                    event: Toggle(is_public_path),
+                },
+                text_box! {
+                    ghost_text: client_state().localization().chat_room_password_text_box_message(),
+                    state: password_path,
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_PASSWORD_LENGTH>::new(password_path, password_action),
+                    focus_id: PasswordTextBox,
+                    hidable: true,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                either! {
+                    selector: client_state().chat_rooms().is_joined(),
+                    on_true: button! {
+                        text: client_state().localization().change_chat_room_button_text(),
+                        disabled: ComputedSelector::new_default(is_incomplete),
+                        event: InputEvent::ChangeChatRoom,
+                    },
+                    on_false: button! {
+                        text: client_state().localization().create_chat_room_button_text(),
+                        disabled: ComputedSelector::new_default(is_incomplete),
+                        event: InputEvent::CreateChatRoom,
+                    },
+                },
+            ),
+        }
+    }
+}
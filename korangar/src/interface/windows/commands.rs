@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use korangar_interface::window::{CustomWindow, Window};
+use korangar_networking::NetworkConditions;
 
 use crate::input::InputEvent;
 use crate::interface::windows::WindowClass;
@@ -209,6 +212,58 @@ impl CustomWindow<ClientState> for CommandsWindow {
                     tooltip: "Resurrect the player [^000001@alive^000000]",
                     event: InputEvent::SendMessage { text: "@alive".to_string() },
                 },
+                text! {
+                    text: "Network",
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "Perfect",
+                            tooltip: "Stop simulating bad network conditions",
+                            event: InputEvent::SetNetworkConditions { conditions: NetworkConditions::PERFECT },
+                        },
+                        button! {
+                            text: "Slow",
+                            tooltip: "Add 150ms of latency in each direction",
+                            event: InputEvent::SetNetworkConditions {
+                                conditions: NetworkConditions {
+                                    latency: Duration::from_millis(150),
+                                    ..NetworkConditions::PERFECT
+                                },
+                            },
+                        },
+                        button! {
+                            text: "Unstable",
+                            tooltip: "Add 100ms of latency, up to 200ms of jitter, and 5% packet loss",
+                            event: InputEvent::SetNetworkConditions {
+                                conditions: NetworkConditions {
+                                    latency: Duration::from_millis(100),
+                                    jitter: Duration::from_millis(200),
+                                    packet_loss: 0.05,
+                                    ..NetworkConditions::PERFECT
+                                },
+                            },
+                        },
+                        button! {
+                            text: "Dial-up",
+                            tooltip: "Limit the bandwidth to 7 KB/s and add 150ms of latency",
+                            event: InputEvent::SetNetworkConditions {
+                                conditions: NetworkConditions {
+                                    latency: Duration::from_millis(150),
+                                    bandwidth: Some(7_000),
+                                    ..NetworkConditions::PERFECT
+                                },
+                            },
+                        },
+                    ),
+                },
+                button! {
+                    text: "Disconnect",
+                    tooltip: "Close all server connections as if the network failed",
+                    event: InputEvent::SimulateDisconnect,
+                },
             ),
         }
     }
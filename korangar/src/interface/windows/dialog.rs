@@ -9,6 +9,7 @@ use rust_state::{Path, RustState, State};
 
 use super::WindowClass;
 use crate::input::InputEvent;
+use crate::interface::components::item_link::ItemLinkHandler;
 use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::InterfaceThemeType;
 use crate::state::{ClientState, ClientStatePathExt, client_state};
@@ -72,8 +73,9 @@ impl DialogWindowState {
         }
 
         self.elements.push(DialogElement::new(
-            text! {
+            rich_text! {
                 text: text,
+                handler: ItemLinkHandler,
             },
             false,
         ));
@@ -0,0 +1,53 @@
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::Path;
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::state::inventory::{ItemDrop, ItemDropPathExt};
+use crate::state::localization::LocalizationPathExt;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+/// Window for selecting how many items to drop from the inventory.
+pub struct DropItemWindow<A> {
+    item_drop_path: A,
+}
+
+impl<A> DropItemWindow<A> {
+    pub fn new(item_drop_path: A) -> Self {
+        Self { item_drop_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for DropItemWindow<A>
+where
+    A: Path<ClientState, ItemDrop>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::DropItem)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: client_state().localization().drop_item_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                number_input! {
+                    text: client_state().localization().drop_amount_text(),
+                    state: self.item_drop_path.amount(),
+                    minimum: 1,
+                    maximum: self.item_drop_path.maximum_amount(),
+                    step: 1,
+                },
+                button! {
+                    text: client_state().localization().drop_item_button_text(),
+                    event: InputEvent::ConfirmDropItem,
+                },
+            ),
+        }
+    }
+}
@@ -9,6 +9,7 @@ use ragnarok_packets::{Friend, FriendPathExt};
 use rust_state::{ManuallyAssertExt, Path, RustState, State, VecIndexExt};
 
 use crate::input::InputEvent;
+use crate::interface::components::entity_menu::friend_context_menu;
 use crate::interface::windows::WindowClass;
 use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::InterfaceThemeType;
@@ -60,17 +61,11 @@ where
                         let friend_path = self.friend_list_path.index(index).manually_asserted();
                         let name_path = friend_path.name();
 
-                        self.elements.push(ErasedElement::new(collapsible! {
+                        self.elements.push(ErasedElement::new(button! {
                             text: name_path,
-                            children: button! {
-                                text: client_state().localization().remove_button_text(),
-                                event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
-                                    let &Friend { account_id, character_id, .. } = state.get(&friend_path);
-
-                                    queue.queue(
-                                        InputEvent::RemoveFriend { account_id, character_id }
-                                    );
-                                },
+                            event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                let menu = friend_context_menu(state, state.get(&friend_path));
+                                queue.queue(Event::OpenContextMenu { menu });
                             },
                         }));
                     }
@@ -0,0 +1,265 @@
+use std::cell::UnsafeCell;
+use std::cmp::Ordering;
+
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, ElementBox};
+use korangar_interface::layout::{Resolvers, WindowLayout, with_single_resolver};
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::{HomunculusCommand, HomunculusStatsPathExt, SkillLevel};
+use rust_state::{ManuallyAssertExt, Path, PathExt, Selector, State, VecIndexExt};
+
+use super::cart::CapacityTextSelector;
+use super::pet::NeedTextSelector;
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::homunculus::{Homunculus, HomunculusPathExt, MAXIMUM_HOMUNCULUS_HUNGER, MAXIMUM_HOMUNCULUS_INTIMACY};
+use crate::state::localization::LocalizationPathExt;
+use crate::state::skills::{LearnedSkill, LearnedSkillPathExt};
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+struct SkillLevelTextSelector<A> {
+    skill_level_path: A,
+    last_value: UnsafeCell<Option<SkillLevel>>,
+    text: UnsafeCell<String>,
+}
+
+impl<A> SkillLevelTextSelector<A> {
+    pub fn new(skill_level_path: A) -> Self {
+        Self {
+            skill_level_path,
+            last_value: UnsafeCell::default(),
+            text: UnsafeCell::default(),
+        }
+    }
+}
+
+impl<A> Selector<ClientState, String> for SkillLevelTextSelector<A>
+where
+    A: Path<ClientState, SkillLevel>,
+{
+    fn select<'a>(&'a self, state: &'a ClientState) -> Option<&'a String> {
+        let skill_level = *self.skill_level_path.follow_safe(state);
+
+        unsafe {
+            let last_value = &mut *self.last_value.get();
+
+            if last_value.is_none_or(|last| last != skill_level) {
+                *self.text.get() = format!("Lv. {}", skill_level.0);
+                *last_value = Some(skill_level);
+            }
+        }
+
+        unsafe { Some(self.text.as_ref_unchecked()) }
+    }
+}
+
+struct HomunculusSkillList<A, B> {
+    skills_path: A,
+    skill_points_path: B,
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl<A, B> HomunculusSkillList<A, B> {
+    fn new(skills_path: A, skill_points_path: B) -> Self {
+        Self {
+            skills_path,
+            skill_points_path,
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl<A, B> Element<ClientState> for HomunculusSkillList<A, B>
+where
+    A: Path<ClientState, Vec<LearnedSkill>>,
+    B: Path<ClientState, u16>,
+{
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &State<ClientState>,
+        mut store: ElementStoreMut,
+        resolvers: &mut dyn Resolvers<ClientState>,
+    ) -> Self::LayoutInfo {
+        with_single_resolver(resolvers, |resolver| {
+            use korangar_interface::prelude::*;
+
+            let skills = state.get(&self.skills_path);
+
+            match skills.len().cmp(&self.elements.len()) {
+                Ordering::Less => {
+                    self.elements.truncate(skills.len());
+                }
+                Ordering::Equal => {}
+                Ordering::Greater => {
+                    for index in self.elements.len()..skills.len() {
+                        let skill_path = self.skills_path.index(index).manually_asserted();
+                        let skill_points_path = self.skill_points_path;
+
+                        self.elements.push(ErasedElement::new(split! {
+                            children: (
+                                text! {
+                                    text: skill_path.skill_name(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                text! {
+                                    text: SkillLevelTextSelector::new(skill_path.skill_level()),
+                                    horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                button! {
+                                    text: client_state().localization().level_up_button_text(),
+                                    disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                                        !*skill_path.upgradable().follow_safe(state) || *skill_points_path.follow_safe(state) == 0
+                                    }),
+                                    event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                        let skill_id = state.get(&skill_path).skill_id;
+                                        queue.queue(InputEvent::LevelUpHomunculusSkill { skill_id });
+                                    },
+                                },
+                            ),
+                        }));
+                    }
+                }
+            }
+
+            self.elements.iter_mut().zip(skills.iter()).for_each(|(element, skill)| {
+                element.create_layout_info(state, store.child_store(skill.skill_id.0 as u64), resolver);
+            });
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        let skills = state.get(&self.skills_path);
+
+        self.elements.iter().zip(skills.iter()).for_each(|(element, skill)| {
+            element.lay_out(state, store.child_store(skill.skill_id.0 as u64), &(), layout);
+        });
+    }
+}
+
+pub struct HomunculusWindow<A> {
+    homunculus_path: A,
+}
+
+impl<A> HomunculusWindow<A> {
+    pub fn new(homunculus_path: A) -> Self {
+        Self { homunculus_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for HomunculusWindow<A>
+where
+    A: Path<ClientState, Homunculus>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::Homunculus)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        let stats_path = self.homunculus_path.stats();
+
+        macro_rules! value_row {
+            ($text_name:ident, $selector:expr) => {
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().$text_name(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        text! {
+                            text: $selector,
+                            horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                    ),
+                }
+            };
+        }
+
+        window! {
+            title: client_state().localization().homunculus_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                value_row!(name_text, self.homunculus_path.name()),
+                value_row!(level_text, PartialEqDisplaySelector::new(self.homunculus_path.level())),
+                value_row!(
+                    health_points_text,
+                    CapacityTextSelector::new(stats_path.health_points(), stats_path.maximum_health_points())
+                ),
+                value_row!(
+                    spell_points_text,
+                    CapacityTextSelector::new(stats_path.spell_points(), stats_path.maximum_spell_points())
+                ),
+                value_row!(
+                    experience_text,
+                    CapacityTextSelector::new(stats_path.experience(), stats_path.next_level_experience())
+                ),
+                value_row!(hunger_text, NeedTextSelector::new(self.homunculus_path.hunger(), MAXIMUM_HOMUNCULUS_HUNGER)),
+                value_row!(
+                    intimacy_text,
+                    NeedTextSelector::new(self.homunculus_path.intimacy(), MAXIMUM_HOMUNCULUS_INTIMACY)
+                ),
+                collapsible! {
+                    text: client_state().localization().stats_window_title(),
+                    children: (
+                        value_row!(attack_text, PartialEqDisplaySelector::new(stats_path.attack())),
+                        value_row!(magic_attack_text, PartialEqDisplaySelector::new(stats_path.magic_attack())),
+                        value_row!(hit_text, PartialEqDisplaySelector::new(stats_path.hit())),
+                        value_row!(critical_text, PartialEqDisplaySelector::new(stats_path.critical())),
+                        value_row!(defense_text, PartialEqDisplaySelector::new(stats_path.defense())),
+                        value_row!(magic_defense_text, PartialEqDisplaySelector::new(stats_path.magic_defense())),
+                        value_row!(flee_text, PartialEqDisplaySelector::new(stats_path.flee())),
+                        value_row!(attack_speed_text, PartialEqDisplaySelector::new(stats_path.attack_speed())),
+                    ),
+                },
+                collapsible! {
+                    text: client_state().localization().skill_tree_window_title(),
+                    children: (
+                        value_row!(available_skill_points_text, PartialEqDisplaySelector::new(stats_path.skill_points())),
+                        HomunculusSkillList::new(self.homunculus_path.skills(), stats_path.skill_points()),
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: client_state().localization().feed_button_text(),
+                            event: InputEvent::HomunculusCommand { command: HomunculusCommand::Feed },
+                        },
+                        button! {
+                            text: client_state().localization().attack_button_text(),
+                            event: InputEvent::HomunculusSelectAttackTarget,
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: client_state().localization().follow_button_text(),
+                            event: InputEvent::HomunculusFollow,
+                        },
+                        button! {
+                            text: client_state().localization().stay_button_text(),
+                            event: InputEvent::HomunculusStay,
+                        },
+                    ),
+                },
+            ),
+        }
+    }
+}
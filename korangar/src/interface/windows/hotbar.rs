@@ -6,7 +6,7 @@ use rust_state::{ArrayLookupExt, OptionExt, Path};
 use crate::interface::resource::SkillSource;
 use crate::interface::windows::WindowClass;
 use crate::state::localization::LocalizationPathExt;
-use crate::state::skills::{LearnableSkill, LearnedSkill, LearnedSkillPath};
+use crate::state::skills::{LearnableSkill, LearnedSkill, LearnedSkillPath, SkillCooldownPath, SkillTreePathExt};
 use crate::state::theme::InterfaceThemeType;
 use crate::state::{ClientState, ClientStatePathExt, client_state};
 
@@ -43,10 +43,12 @@ where
                     children: std::array::from_fn::<_, N, _>(|slot| {
                         let learnable_skill_path = self.hotbar_path.array_index(slot).unwrapped();
                         let learned_skill_path = LearnedSkillPath::new(learnable_skill_path, self.skills_path);
+                        let cooldown_path = SkillCooldownPath::new(learnable_skill_path, client_state().skill_tree().cooldowns());
 
                         skill_box! {
                             learnable_skill_path,
                             learned_skill_path,
+                            cooldown_path,
                             source: SkillSource::Hotbar { slot: HotbarSlot(slot as u16) },
                         }
                     }),
@@ -1,6 +1,9 @@
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::element::StateElement;
 use korangar_interface::window::{CustomWindow, Window};
-use rust_state::Path;
+use rust_state::{Path, RustState, State};
 
+use crate::input::InputEvent;
 use crate::interface::windows::WindowClass;
 use crate::loaders::OverflowBehavior;
 use crate::settings::{InterfaceSettings, InterfaceSettingsCapabilities, InterfaceSettingsCapabilitiesPathExt, InterfaceSettingsPathExt};
@@ -8,24 +11,35 @@ use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::InterfaceThemeType;
 use crate::state::{ClientState, ClientStatePathExt, client_state};
 
-pub struct InterfaceSettingsWindow<A, B> {
-    settings_path: A,
-    capabilities_path: B,
+const MAXIMUM_LAYOUT_NAME_LENGTH: usize = 24;
+
+/// Internal state of the interface settings window.
+#[derive(Default, RustState, StateElement)]
+pub struct InterfaceSettingsWindowState {
+    layout_name: String,
+}
+
+pub struct InterfaceSettingsWindow<A, B, C> {
+    window_state_path: A,
+    settings_path: B,
+    capabilities_path: C,
 }
 
-impl<A, B> InterfaceSettingsWindow<A, B> {
-    pub fn new(settings_path: A, capabilities_path: B) -> Self {
+impl<A, B, C> InterfaceSettingsWindow<A, B, C> {
+    pub fn new(window_state_path: A, settings_path: B, capabilities_path: C) -> Self {
         Self {
+            window_state_path,
             settings_path,
             capabilities_path,
         }
     }
 }
 
-impl<A, B> CustomWindow<ClientState> for InterfaceSettingsWindow<A, B>
+impl<A, B, C> CustomWindow<ClientState> for InterfaceSettingsWindow<A, B, C>
 where
-    A: Path<ClientState, InterfaceSettings>,
-    B: Path<ClientState, InterfaceSettingsCapabilities>,
+    A: Path<ClientState, InterfaceSettingsWindowState>,
+    B: Path<ClientState, InterfaceSettings>,
+    C: Path<ClientState, InterfaceSettingsCapabilities>,
 {
     fn window_class() -> Option<WindowClass> {
         Some(WindowClass::InterfaceSettings)
@@ -34,6 +48,18 @@ where
     fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
         use korangar_interface::prelude::*;
 
+        struct LayoutNameTextBox;
+
+        let save_layout_action = move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+            let name = state.get(&self.window_state_path.layout_name()).trim().to_string();
+
+            if !name.is_empty() {
+                state.update_value_with(self.window_state_path.layout_name(), |input| input.clear());
+                queue.queue(InputEvent::SaveWindowLayout { name });
+                queue.queue(Event::Unfocus);
+            }
+        };
+
         let elements = (
             split! {
                 children: (
@@ -95,6 +121,41 @@ where
                     }
                 )
             },
+            state_button! {
+                text: client_state().localization().reduce_motion_button_text(),
+                state: self.settings_path.reduce_motion(),
+                event: Toggle(self.settings_path.reduce_motion()),
+            },
+            split! {
+                children: (
+                    text! {
+                        text: client_state().localization().window_layout_text(),
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.window_layout(),
+                        options: self.capabilities_path.window_layouts(),
+                    }
+                )
+            },
+            text_box! {
+                ghost_text: client_state().localization().window_layout_name_text_box_message(),
+                state: self.window_state_path.layout_name(),
+                input_handler: DefaultHandler::<_, _, MAXIMUM_LAYOUT_NAME_LENGTH>::new(self.window_state_path.layout_name(), save_layout_action),
+                focus_id: LayoutNameTextBox,
+            },
+            split! {
+                children: (
+                    button! {
+                        text: client_state().localization().save_window_layout_button_text(),
+                        event: save_layout_action,
+                    },
+                    button! {
+                        text: client_state().localization().reset_window_layout_button_text(),
+                        event: InputEvent::ResetWindowLayout,
+                    },
+                )
+            },
         );
 
         window! {
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use korangar_interface::components::rich_text::MarkupHandler;
+use korangar_interface::layout::WindowLayout;
+use korangar_interface::layout::area::Area;
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::ItemId;
+use rust_state::State;
+
+use super::WindowClass;
+use crate::graphics::{Color, Texture};
+use crate::renderer::LayoutExt;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+/// Draws the icon of the item. The texture might still be loading when the
+/// window is opened, in which case the space stays empty.
+struct ItemIcon {
+    texture: Option<Arc<Texture>>,
+}
+
+impl MarkupHandler<ClientState> for ItemIcon {
+    fn lay_out_icon<'a>(&'a self, _: &'a State<ClientState>, _: &str, area: Area, layout: &mut WindowLayout<'a, ClientState>) {
+        if let Some(texture) = &self.texture {
+            layout.add_texture(area, texture.clone(), Color::WHITE, false);
+        }
+    }
+}
+
+/// Window that shows an item, e.g. after clicking an item link in the chat.
+pub struct ItemTooltipWindow {
+    text: String,
+    texture: Option<Arc<Texture>>,
+}
+
+impl ItemTooltipWindow {
+    pub fn new(item_id: ItemId, name: String, texture: Option<Arc<Texture>>) -> Self {
+        let text = format!("<ICON>{}</ICON> <B>{name}</B> ^000001#{}^000000", item_id.0, item_id.0);
+
+        Self { text, texture }
+    }
+}
+
+impl CustomWindow<ClientState> for ItemTooltipWindow {
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: client_state().localization().item_tooltip_window_title(),
+            class: Some(WindowClass::ItemTooltip),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                rich_text! {
+                    text: self.text,
+                    handler: ItemIcon { texture: self.texture },
+                },
+            ),
+        }
+    }
+}
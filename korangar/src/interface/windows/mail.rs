@@ -0,0 +1,121 @@
+use korangar_components::item_box;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::{Path, PathExt, State, VecIndexExt};
+
+use crate::ItemSource;
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::mail::{MAXIMUM_MAIL_ITEMS, OpenedMail, OpenedMailPathExt};
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+pub struct MailWindow<A> {
+    mail_path: A,
+}
+
+impl<A> MailWindow<A> {
+    pub fn new(mail_path: A) -> Self {
+        Self { mail_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for MailWindow<A>
+where
+    A: Path<ClientState, OpenedMail>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::Mail)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        let items_path = self.mail_path.items();
+
+        window! {
+            title: client_state().localization().mail_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().mail_sender_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        text! {
+                            text: self.mail_path.sender_name(),
+                            horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                    ),
+                },
+                text! {
+                    text: self.mail_path.title(),
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                text! {
+                    text: self.mail_path.text(),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().mail_zeny_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        text! {
+                            text: PartialEqDisplaySelector::new(self.mail_path.zeny()),
+                            horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: std::array::from_fn::<_, MAXIMUM_MAIL_ITEMS, _>(|index| {
+                        item_box! {
+                            item_path: items_path.index(index),
+                            source: ItemSource::MailAttachment,
+                        }
+                    }),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: client_state().localization().claim_zeny_button_text(),
+                            disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                                *self.mail_path.zeny().follow_safe(state) == 0
+                            }),
+                            event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                let mail_id = *state.get(&self.mail_path.mail_id());
+                                queue.queue(InputEvent::ClaimMailZeny { mail_id });
+                            },
+                        },
+                        button! {
+                            text: client_state().localization().claim_items_button_text(),
+                            disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                                items_path.follow_safe(state).is_empty()
+                            }),
+                            event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                let mail_id = *state.get(&self.mail_path.mail_id());
+                                queue.queue(InputEvent::ClaimMailItems { mail_id });
+                            },
+                        },
+                    ),
+                },
+                button! {
+                    text: client_state().localization().delete_mail_button_text(),
+                    event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                        let open_type = *state.get(&self.mail_path.open_type());
+                        let mail_id = *state.get(&self.mail_path.mail_id());
+                        queue.queue(InputEvent::DeleteMail { open_type, mail_id });
+                    },
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::cmp::Ordering;
+
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, ElementBox};
+use korangar_interface::layout::{Resolvers, WindowLayout, with_single_resolver};
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::{MailInformation, MailInformationPathExt};
+use rust_state::{ManuallyAssertExt, Path, PathExt, State, VecIndexExt};
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::mail::{Mailbox, MailboxPathExt};
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+struct MailList<A> {
+    mails_path: A,
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl<A> MailList<A> {
+    fn new(mails_path: A) -> Self {
+        Self {
+            mails_path,
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl<A> Element<ClientState> for MailList<A>
+where
+    A: Path<ClientState, Vec<MailInformation>>,
+{
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &State<ClientState>,
+        mut store: ElementStoreMut,
+        resolvers: &mut dyn Resolvers<ClientState>,
+    ) -> Self::LayoutInfo {
+        with_single_resolver(resolvers, |resolver| {
+            use korangar_interface::prelude::*;
+
+            let mails = state.get(&self.mails_path);
+
+            match mails.len().cmp(&self.elements.len()) {
+                Ordering::Less => {
+                    self.elements.truncate(mails.len());
+                }
+                Ordering::Equal => {}
+                Ordering::Greater => {
+                    for index in self.elements.len()..mails.len() {
+                        let mail_path = self.mails_path.index(index).manually_asserted();
+
+                        self.elements.push(ErasedElement::new(collapsible! {
+                            text: mail_path.title(),
+                            children: (
+                                split! {
+                                    children: (
+                                        text! {
+                                            text: client_state().localization().mail_sender_text(),
+                                            overflow_behavior: OverflowBehavior::Shrink,
+                                        },
+                                        text! {
+                                            text: mail_path.sender_name(),
+                                            horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                                            overflow_behavior: OverflowBehavior::Shrink,
+                                        },
+                                    ),
+                                },
+                                split! {
+                                    gaps: theme().window().gaps(),
+                                    children: (
+                                        button! {
+                                            text: client_state().localization().read_mail_button_text(),
+                                            event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                                let mail = state.get(&mail_path);
+                                                queue.queue(InputEvent::ReadMail {
+                                                    open_type: mail.open_type,
+                                                    mail_id: mail.mail_id,
+                                                });
+                                            },
+                                        },
+                                        button! {
+                                            text: client_state().localization().delete_mail_button_text(),
+                                            event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                                let mail = state.get(&mail_path);
+                                                queue.queue(InputEvent::DeleteMail {
+                                                    open_type: mail.open_type,
+                                                    mail_id: mail.mail_id,
+                                                });
+                                            },
+                                        },
+                                    ),
+                                },
+                            ),
+                        }));
+                    }
+                }
+            }
+
+            self.elements.iter_mut().zip(mails.iter()).for_each(|(element, mail)| {
+                element.create_layout_info(state, store.child_store(mail.mail_id.0), resolver);
+            });
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        let mails = state.get(&self.mails_path);
+
+        self.elements.iter().zip(mails.iter()).for_each(|(element, mail)| {
+            element.lay_out(state, store.child_store(mail.mail_id.0), &(), layout);
+        });
+    }
+}
+
+pub struct MailboxWindow<A> {
+    mailbox_path: A,
+}
+
+impl<A> MailboxWindow<A> {
+    pub fn new(mailbox_path: A) -> Self {
+        Self { mailbox_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for MailboxWindow<A>
+where
+    A: Path<ClientState, Mailbox>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::Mailbox)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: client_state().localization().mailbox_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: client_state().localization().write_mail_button_text(),
+                            event: InputEvent::WriteMail { recipient_name: String::new() },
+                        },
+                        button! {
+                            text: client_state().localization().next_page_button_text(),
+                            disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                                *self.mailbox_path.is_last_page().follow_safe(state)
+                            }),
+                            event: InputEvent::RequestNextMailPage,
+                        },
+                    ),
+                },
+                MailList::new(self.mailbox_path.mails()),
+            ),
+        }
+    }
+}
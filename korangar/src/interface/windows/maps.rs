@@ -1,3 +1,8 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+use korangar_interface::components::table::TableRow;
+use korangar_interface::event::ClickHandler;
 use korangar_interface::window::{CustomWindow, Window};
 use ragnarok_packets::TilePosition;
 
@@ -6,6 +11,34 @@ use crate::interface::windows::WindowClass;
 use crate::state::ClientState;
 use crate::state::theme::InterfaceThemeType;
 
+struct MapRow {
+    name: &'static str,
+    position: TilePosition,
+    event: InputEvent,
+}
+
+impl TableRow<ClientState> for MapRow {
+    fn cell(&self, column: usize) -> Cow<'_, str> {
+        match column {
+            0 => Cow::Borrowed(self.name),
+            1 => Cow::Owned(self.position.x.to_string()),
+            _ => Cow::Owned(self.position.y.to_string()),
+        }
+    }
+
+    fn compare(&self, other: &Self, column: usize) -> Ordering {
+        match column {
+            0 => self.name.cmp(other.name),
+            1 => self.position.x.cmp(&other.position.x),
+            _ => self.position.y.cmp(&other.position.y),
+        }
+    }
+
+    fn click_handler(&self) -> Option<&dyn ClickHandler<ClientState>> {
+        Some(&self.event)
+    }
+}
+
 pub struct MapsWindow;
 
 impl CustomWindow<ClientState> for MapsWindow {
@@ -16,8 +49,7 @@ impl CustomWindow<ClientState> for MapsWindow {
     fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
         use korangar_interface::prelude::*;
 
-        const MAP_COUNT: usize = 23;
-        const MAP_WARPS: [(&str, TilePosition); MAP_COUNT] = [
+        const MAP_WARPS: [(&str, TilePosition); 23] = [
             ("geffen", TilePosition { x: 119, y: 59 }),
             ("alberta", TilePosition { x: 28, y: 234 }),
             ("aldebaran", TilePosition { x: 140, y: 131 }),
@@ -48,18 +80,26 @@ impl CustomWindow<ClientState> for MapsWindow {
             class: Self::window_class(),
             theme: InterfaceThemeType::InGame,
             closable: true,
-            elements: std::array::from_fn::<_, MAP_COUNT, _>(|index| {
-                let warp = MAP_WARPS[index];
-
-                button! {
-                    text: warp.0,
-                    tooltip: format!("Map: {}\nCoordinates: {}, {}", warp.0, warp.1.x, warp.1.y),
-                    event: InputEvent::WarpToMap {
-                        map_name: format!("{}.gat", warp.0),
-                        position: warp.1,
-                    },
-                }
-            }),
+            elements: (
+                table! {
+                    rows: MAP_WARPS
+                        .iter()
+                        .map(|&(name, position)| MapRow {
+                            name,
+                            position,
+                            event: InputEvent::WarpToMap {
+                                map_name: format!("{name}.gat"),
+                                position,
+                            },
+                        })
+                        .collect::<Vec<_>>(),
+                    columns: vec![
+                        TableColumn::new("Map", 150.0),
+                        TableColumn::new("X", 60.0),
+                        TableColumn::new("Y", 60.0),
+                    ],
+                },
+            ),
         }
     }
 }
@@ -108,6 +108,14 @@ impl CustomWindow<ClientState> for MenuWindow {
                     hovered_background_color: client_theme().debug_button().hovered_background_color(),
                 },
                 #[cfg(feature = "debug")]
+                button! {
+                    text: "Network statistics",
+                    tooltip: "Shows traffic and latency of the server connections (^000001only available in debug mode^000000)",
+                    event: InputEvent::ToggleNetworkStatisticsWindow,
+                    foreground_color: client_theme().debug_button().foreground_color(),
+                    hovered_background_color: client_theme().debug_button().hovered_background_color(),
+                },
+                #[cfg(feature = "debug")]
                 button! {
                     text: "Cache statistics",
                     tooltip: "Shows statistics of the caches used by the client (^000001only available in debug mode^000000)",
@@ -3,13 +3,18 @@ mod buy;
 mod buy_cart;
 mod buy_or_sell;
 mod cache;
+mod cart;
 mod character_creation;
 mod character_overview;
 mod character_selection;
 mod chat;
+mod chat_room;
+mod chat_room_password;
+mod chat_room_settings;
 #[cfg(feature = "debug")]
 mod commands;
 mod dialog;
+mod drop_item;
 mod equipment;
 mod error;
 #[cfg(feature = "debug")]
@@ -18,15 +23,23 @@ mod friend_list;
 mod friend_request;
 mod game_settings;
 mod graphics_settings;
+mod homunculus;
 mod hotbar;
 mod interface_settings;
 mod inventory;
+mod item_tooltip;
 mod login;
+mod mail;
+mod mailbox;
 #[cfg(feature = "debug")]
 mod maps;
 mod menu;
 #[cfg(feature = "debug")]
+mod network_statistics;
+#[cfg(feature = "debug")]
 mod packet_inspector;
+mod pet;
+mod pet_eggs;
 #[cfg(feature = "debug")]
 mod profiler;
 #[cfg(feature = "debug")]
@@ -39,6 +52,7 @@ mod skill_tree;
 mod stats;
 #[cfg(feature = "debug")]
 mod theme_inspector;
+mod write_mail;
 
 use serde::{Deserialize, Serialize};
 
@@ -46,14 +60,19 @@ pub use self::audio_settings::AudioSettingsWindow;
 pub use self::buy::BuyWindow;
 pub use self::buy_cart::BuyCartWindow;
 pub use self::buy_or_sell::BuyOrSellWindow;
-pub use self::cache::WindowCache;
+pub use self::cache::{DEFAULT_WINDOW_LAYOUT_NAME, WindowCache};
+pub use self::cart::CartWindow;
 pub use self::character_creation::CharacterCreationWindow;
 pub use self::character_overview::CharacterOverviewWindow;
 pub use self::character_selection::CharacterSelectionWindow;
-pub use self::chat::{ChatTextBox, ChatWindow, ChatWindowState};
+pub use self::chat::{ChatTextBox, ChatWindow, ChatWindowState, ChatWindowStatePathExt, parse_whisper, whisper_prefix};
+pub use self::chat_room::ChatRoomWindow;
+pub use self::chat_room_password::ChatRoomPasswordWindow;
+pub use self::chat_room_settings::ChatRoomSettingsWindow;
 #[cfg(feature = "debug")]
 pub use self::commands::CommandsWindow;
 pub use self::dialog::{DialogWindow, DialogWindowState};
+pub use self::drop_item::DropItemWindow;
 pub use self::equipment::EquipmentWindow;
 pub use self::error::ErrorWindow;
 #[cfg(feature = "debug")]
@@ -62,15 +81,23 @@ pub use self::friend_list::{FriendListWindow, FriendListWindowState};
 pub use self::friend_request::FriendRequestWindow;
 pub use self::game_settings::GameSettingsWindow;
 pub use self::graphics_settings::GraphicsSettingsWindow;
+pub use self::homunculus::HomunculusWindow;
 pub use self::hotbar::HotbarWindow;
-pub use self::interface_settings::InterfaceSettingsWindow;
+pub use self::interface_settings::{InterfaceSettingsWindow, InterfaceSettingsWindowState};
 pub use self::inventory::InventoryWindow;
+pub use self::item_tooltip::ItemTooltipWindow;
 pub use self::login::{LoginWindow, LoginWindowState, LoginWindowStatePathExt};
+pub use self::mail::MailWindow;
+pub use self::mailbox::MailboxWindow;
 #[cfg(feature = "debug")]
 pub use self::maps::MapsWindow;
 pub use self::menu::MenuWindow;
 #[cfg(feature = "debug")]
+pub use self::network_statistics::{NetworkStatisticsWindow, NetworkStatisticsWindowState};
+#[cfg(feature = "debug")]
 pub use self::packet_inspector::PacketInspectorWindow;
+pub use self::pet::PetWindow;
+pub use self::pet_eggs::PetEggWindow;
 #[cfg(feature = "debug")]
 pub use self::profiler::{ProfilerWindow, ProfilerWindowState};
 #[cfg(feature = "debug")]
@@ -83,6 +110,7 @@ pub use self::skill_tree::{SkillTreeWindow, SkillTreeWindowState, SkillTreeWindo
 pub use self::stats::StatsWindow;
 #[cfg(feature = "debug")]
 pub use self::theme_inspector::{ThemeInspectorWindow, ThemeInspectorWindowState};
+pub use self::write_mail::WriteMailWindow;
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WindowClass {
@@ -90,27 +118,39 @@ pub enum WindowClass {
     Buy,
     BuyCart,
     BuyOrSell,
+    Cart,
     Chat,
+    ChatRoom,
+    ChatRoomPassword,
+    ChatRoomSettings,
     CharacterCreation,
     CharacterOverview,
     CharacterSelection,
     Dialog,
+    DropItem,
     GameSettings,
     InterfaceSettings,
     GraphicsSettings,
+    Homunculus,
     Hotbar,
     Inventory,
+    ItemTooltip,
     Equipment,
     SkillTree,
     Stats,
     FriendList,
     FriendRequest,
     Login,
+    Mail,
+    Mailbox,
     Menu,
+    Pet,
+    PetEgg,
     Respawn,
     SelectServer,
     Sell,
     SellCart,
+    WriteMail,
     #[cfg(feature = "debug")]
     Maps,
     #[cfg(feature = "debug")]
@@ -127,4 +167,6 @@ pub enum WindowClass {
     Profiler,
     #[cfg(feature = "debug")]
     CacheStatistics,
+    #[cfg(feature = "debug")]
+    NetworkStatistics,
 }
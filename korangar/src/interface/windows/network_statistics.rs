@@ -0,0 +1,409 @@
+use korangar_interface::components::drop_down::DropDownItem;
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{BaseLayoutInfo, Element, StateElement};
+use korangar_interface::layout::area::Area;
+use korangar_interface::layout::{Resolvers, WindowLayout, with_single_resolver};
+use korangar_interface::theme::theme;
+use korangar_interface::window::{CustomWindow, Window};
+use korangar_networking::{NetworkStatistics, ServerStatistics, ServerType, TrafficStatistics};
+use ragnarok_packets::PacketHeader;
+use rust_state::{Path, PathExt, RustState, State};
+
+use crate::graphics::{Color, CornerDiameter, ShadowPadding};
+use crate::interface::windows::WindowClass;
+use crate::state::ClientState;
+use crate::state::theme::InterfaceThemeType;
+
+/// Number of packet headers listed in each histogram.
+const HISTOGRAM_ROWS: usize = 10;
+
+struct ServerOption {
+    server_type: ServerType,
+}
+
+impl DropDownItem<ServerType> for ServerOption {
+    fn text(&self) -> &str {
+        match self.server_type {
+            ServerType::Login => "Login server",
+            ServerType::Character => "Character server",
+            ServerType::Map => "Map server",
+        }
+    }
+
+    fn value(&self) -> ServerType {
+        self.server_type
+    }
+}
+
+fn format_bytes(bytes: u32) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1048576 => format!("{:.1} KiB", bytes as f32 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f32 / 1048576.0),
+    }
+}
+
+/// Bar graph of a time series. Negative values are drawn below a center
+/// line.
+struct Graph<A> {
+    window_state_path: A,
+    values: fn(&NetworkStatisticsWindowState) -> Vec<f32>,
+    color: Color,
+}
+
+impl<A> Graph<A> {
+    fn new(window_state_path: A, values: fn(&NetworkStatisticsWindowState) -> Vec<f32>, color: Color) -> Self {
+        Self {
+            window_state_path,
+            values,
+            color,
+        }
+    }
+}
+
+impl<A> Element<ClientState> for Graph<A>
+where
+    A: Path<ClientState, NetworkStatisticsWindowState>,
+{
+    type LayoutInfo = BaseLayoutInfo;
+
+    fn create_layout_info(
+        &mut self,
+        _: &State<ClientState>,
+        _: ElementStoreMut,
+        resolvers: &mut dyn Resolvers<ClientState>,
+    ) -> Self::LayoutInfo {
+        with_single_resolver(resolvers, |resolver| {
+            let area = resolver.with_height(80.0);
+            Self::LayoutInfo { area }
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<ClientState>,
+        _: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        let values = (self.values)(state.get(&self.window_state_path));
+
+        layout.add_rectangle(
+            layout_info.area,
+            CornerDiameter::uniform(2.0),
+            Color::monochrome_u8(40),
+            Color::rgba_u8(0, 0, 0, 100),
+            ShadowPadding::diagonal(2.0, 5.0),
+        );
+
+        let maximum = values.iter().fold(0.0f32, |maximum, value| maximum.max(value.abs()));
+
+        if maximum == 0.0 {
+            return;
+        }
+
+        let has_negative_values = values.iter().any(|value| *value < 0.0);
+        let baseline = match has_negative_values {
+            true => layout_info.area.top + layout_info.area.height / 2.0,
+            false => layout_info.area.top + layout_info.area.height,
+        };
+        let height_unit = (baseline - layout_info.area.top) / maximum;
+        let bar_width = layout_info.area.width / korangar_networking::HISTORY_LENGTH as f32;
+
+        // Align the newest value to the right edge, so the graph scrolls to the
+        // left over time.
+        let mut x_position = layout_info.area.left + layout_info.area.width - bar_width * values.len() as f32;
+
+        for value in values {
+            let bar_height = height_unit * value.abs();
+            let top = match value < 0.0 {
+                true => baseline,
+                false => baseline - bar_height,
+            };
+
+            let bar_area = Area {
+                left: x_position,
+                top,
+                width: bar_width,
+                height: bar_height,
+            };
+
+            layout.add_rectangle(
+                bar_area,
+                CornerDiameter::default(),
+                self.color,
+                Color::TRANSPARENT,
+                ShadowPadding::uniform(0.0),
+            );
+
+            x_position += bar_width;
+        }
+    }
+}
+
+/// Lists the most frequent packets of one direction of the visible server.
+struct PacketHistogram<A> {
+    window_state_path: A,
+    direction: fn(&ServerStatistics) -> &TrafficStatistics,
+    color: Color,
+    rows: Vec<(PacketHeader, u32)>,
+    texts: Vec<String>,
+}
+
+impl<A> PacketHistogram<A> {
+    fn new(window_state_path: A, direction: fn(&ServerStatistics) -> &TrafficStatistics, color: Color) -> Self {
+        Self {
+            window_state_path,
+            direction,
+            color,
+            rows: Vec::new(),
+            texts: Vec::new(),
+        }
+    }
+}
+
+impl<A> Element<ClientState> for PacketHistogram<A>
+where
+    A: Path<ClientState, NetworkStatisticsWindowState>,
+{
+    fn create_layout_info(
+        &mut self,
+        state: &State<ClientState>,
+        _: ElementStoreMut,
+        resolvers: &mut dyn Resolvers<ClientState>,
+    ) -> Self::LayoutInfo {
+        let window_state = state.get(&self.window_state_path);
+        let traffic = (self.direction)(window_state.statistics.server(window_state.visible_server));
+
+        let mut rows: Vec<_> = traffic.packet_counts.iter().map(|(header, count)| (*header, *count)).collect();
+        rows.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(&right.0)));
+        rows.truncate(HISTOGRAM_ROWS);
+
+        if rows != self.rows {
+            self.texts = rows
+                .iter()
+                .map(|(header, count)| format!("0x{:0>4x}  ^000001{count}^000000", header.0))
+                .collect();
+            self.rows = rows;
+        }
+
+        with_single_resolver(resolvers, |resolver| {
+            let row_height = *state.get(&theme().text().height());
+            let area = resolver.with_height(row_height * self.rows.len().max(1) as f32);
+            let font_size = *state.get(&theme().text().font_size());
+
+            Self::LayoutInfo { area, font_size }
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<ClientState>,
+        _: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        let Some(maximum) = self.rows.first().map(|(_, count)| *count as f32) else {
+            return;
+        };
+        let row_height = layout_info.area.height / self.rows.len() as f32;
+
+        for (index, ((_, count), text)) in self.rows.iter().zip(&self.texts).enumerate() {
+            let row_area = Area {
+                left: layout_info.area.left,
+                top: layout_info.area.top + row_height * index as f32,
+                width: layout_info.area.width,
+                height: row_height,
+            };
+
+            let bar_area = Area {
+                width: row_area.width * *count as f32 / maximum,
+                ..row_area
+            };
+
+            // Keep the text readable on top of the bar.
+            layout.add_rectangle(
+                bar_area,
+                CornerDiameter::default(),
+                self.color.multiply_alpha(0.4),
+                Color::TRANSPARENT,
+                ShadowPadding::uniform(0.0),
+            );
+
+            layout.add_text(
+                row_area,
+                text,
+                layout_info.font_size,
+                *state.get(&theme().text().color()),
+                *state.get(&theme().text().highlight_color()),
+                *state.get(&theme().text().horizontal_alignment()),
+                *state.get(&theme().text().vertical_alignment()),
+                *state.get(&theme().text().overflow_behavior()),
+            );
+        }
+    }
+}
+
+/// Internal state of the network statistics window.
+#[derive(RustState, StateElement)]
+pub struct NetworkStatisticsWindowState {
+    #[hidden_element]
+    visible_server: ServerType,
+    #[hidden_element]
+    statistics: NetworkStatistics,
+}
+
+impl Default for NetworkStatisticsWindowState {
+    fn default() -> Self {
+        Self {
+            visible_server: ServerType::Map,
+            statistics: NetworkStatistics::default(),
+        }
+    }
+}
+
+impl NetworkStatisticsWindowState {
+    pub fn update(&mut self, statistics: NetworkStatistics) {
+        self.statistics = statistics;
+    }
+
+    fn visible_traffic(&self) -> &ServerStatistics {
+        self.statistics.server(self.visible_server)
+    }
+}
+
+pub struct NetworkStatisticsWindow<A> {
+    window_state_path: A,
+}
+
+impl<A> NetworkStatisticsWindow<A> {
+    pub fn new(window_state_path: A) -> Self {
+        Self { window_state_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for NetworkStatisticsWindow<A>
+where
+    A: Path<ClientState, NetworkStatisticsWindowState>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::NetworkStatistics)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        const INCOMING_COLOR: Color = Color::rgb_u8(80, 170, 240);
+        const OUTGOING_COLOR: Color = Color::rgb_u8(240, 160, 60);
+        const ROUND_TRIP_COLOR: Color = Color::rgb_u8(120, 220, 120);
+        const CLOCK_DRIFT_COLOR: Color = Color::rgb_u8(220, 110, 200);
+
+        let server_options = vec![
+            ServerOption {
+                server_type: ServerType::Login,
+            },
+            ServerOption {
+                server_type: ServerType::Character,
+            },
+            ServerOption {
+                server_type: ServerType::Map,
+            },
+        ];
+
+        let window_state_path = self.window_state_path;
+        let traffic_summary = ComputedSelector::new_default(move |state: &ClientState| {
+            let traffic = window_state_path.follow_safe(state).visible_traffic();
+            let incoming = traffic.incoming.last_second();
+            let outgoing = traffic.outgoing.last_second();
+
+            format!(
+                "In: ^000001{}^000000 packets/s, ^000001{}^000000/s  Out: ^000001{}^000000 packets/s, ^000001{}^000000/s",
+                incoming.packets,
+                format_bytes(incoming.bytes),
+                outgoing.packets,
+                format_bytes(outgoing.bytes),
+            )
+        });
+        let synchronization_summary = ComputedSelector::new_default(move |state: &ClientState| {
+            let statistics = &window_state_path.follow_safe(state).statistics;
+            let round_trip_time = statistics
+                .round_trip_times
+                .back()
+                .map(|round_trip_time| format!("{} ms", round_trip_time.as_millis()))
+                .unwrap_or_else(|| "-".to_owned());
+            let clock_drift = statistics
+                .clock_drifts
+                .back()
+                .map(|clock_drift| format!("{clock_drift:+.1} ms"))
+                .unwrap_or_else(|| "-".to_owned());
+
+            format!("Round trip time: ^000001{round_trip_time}^000000  Clock drift: ^000001{clock_drift}^000000")
+        });
+
+        window! {
+            title: "Network Statistics",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                drop_down! {
+                    selected: self.window_state_path.visible_server(),
+                    options: server_options,
+                },
+                text! {
+                    text: traffic_summary,
+                },
+                text! {
+                    text: "Incoming bytes per second",
+                },
+                Graph::new(
+                    self.window_state_path,
+                    |window_state| window_state.visible_traffic().incoming.history.iter().map(|sample| sample.bytes as f32).collect(),
+                    INCOMING_COLOR,
+                ),
+                text! {
+                    text: "Outgoing bytes per second",
+                },
+                Graph::new(
+                    self.window_state_path,
+                    |window_state| window_state.visible_traffic().outgoing.history.iter().map(|sample| sample.bytes as f32).collect(),
+                    OUTGOING_COLOR,
+                ),
+                text! {
+                    text: synchronization_summary,
+                },
+                text! {
+                    text: "Round trip time",
+                },
+                Graph::new(
+                    self.window_state_path,
+                    |window_state| {
+                        window_state
+                            .statistics
+                            .round_trip_times
+                            .iter()
+                            .map(|round_trip_time| round_trip_time.as_secs_f32() * 1000.0)
+                            .collect()
+                    },
+                    ROUND_TRIP_COLOR,
+                ),
+                text! {
+                    text: "Clock drift",
+                },
+                Graph::new(
+                    self.window_state_path,
+                    |window_state| window_state.statistics.clock_drifts.iter().map(|clock_drift| *clock_drift as f32).collect(),
+                    CLOCK_DRIFT_COLOR,
+                ),
+                collapsible! {
+                    text: "Incoming packets",
+                    children: PacketHistogram::new(self.window_state_path, |server| &server.incoming, INCOMING_COLOR),
+                },
+                collapsible! {
+                    text: "Outgoing packets",
+                    children: PacketHistogram::new(self.window_state_path, |server| &server.outgoing, OUTGOING_COLOR),
+                },
+            ),
+        }
+    }
+}
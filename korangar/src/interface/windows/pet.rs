@@ -0,0 +1,166 @@
+use std::cell::UnsafeCell;
+
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::PetCommand;
+use rust_state::{Path, PathExt, Selector};
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::pet::{MAXIMUM_PET_HUNGER, MAXIMUM_PET_INTIMACY, Pet, PetPathExt};
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+/// Displays a value together with its fixed maximum, e.g. the hunger of a
+/// pet or homunculus.
+pub(super) struct NeedTextSelector<A> {
+    value_path: A,
+    maximum: u32,
+    last_value: UnsafeCell<Option<u32>>,
+    text: UnsafeCell<String>,
+}
+
+impl<A> NeedTextSelector<A> {
+    pub fn new(value_path: A, maximum: u32) -> Self {
+        Self {
+            value_path,
+            maximum,
+            last_value: UnsafeCell::default(),
+            text: UnsafeCell::default(),
+        }
+    }
+}
+
+impl<A> Selector<ClientState, String> for NeedTextSelector<A>
+where
+    A: Path<ClientState, u32>,
+{
+    fn select<'a>(&'a self, state: &'a ClientState) -> Option<&'a String> {
+        let value = *self.value_path.follow_safe(state);
+
+        unsafe {
+            let last_value = &mut *self.last_value.get();
+
+            if last_value.is_none_or(|last| last != value) {
+                *self.text.get() = format!("{value} / {}", self.maximum);
+                *last_value = Some(value);
+            }
+        }
+
+        unsafe { Some(self.text.as_ref_unchecked()) }
+    }
+}
+
+pub struct PetWindow<A> {
+    pet_path: A,
+}
+
+impl<A> PetWindow<A> {
+    pub fn new(pet_path: A) -> Self {
+        Self { pet_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for PetWindow<A>
+where
+    A: Path<ClientState, Pet>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::Pet)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: client_state().localization().pet_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().name_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        text! {
+                            text: self.pet_path.name(),
+                            horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                    ),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().level_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        text! {
+                            text: PartialEqDisplaySelector::new(self.pet_path.level()),
+                            horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                    ),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().hunger_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        text! {
+                            text: NeedTextSelector::new(self.pet_path.hunger(), MAXIMUM_PET_HUNGER),
+                            horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                    ),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().intimacy_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        text! {
+                            text: NeedTextSelector::new(self.pet_path.intimacy(), MAXIMUM_PET_INTIMACY),
+                            horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: client_state().localization().feed_button_text(),
+                            event: InputEvent::PetCommand { command: PetCommand::Feed },
+                        },
+                        button! {
+                            text: client_state().localization().performance_button_text(),
+                            event: InputEvent::PetCommand { command: PetCommand::Performance },
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: client_state().localization().unequip_accessory_button_text(),
+                            disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                                !*self.pet_path.has_accessory().follow_safe(state)
+                            }),
+                            event: InputEvent::PetCommand { command: PetCommand::UnequipAccessory },
+                        },
+                        button! {
+                            text: client_state().localization().return_to_egg_button_text(),
+                            event: InputEvent::PetCommand { command: PetCommand::ReturnToEgg },
+                        },
+                    ),
+                },
+            ),
+        }
+    }
+}
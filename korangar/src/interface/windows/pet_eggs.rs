@@ -0,0 +1,119 @@
+use std::cmp::Ordering;
+
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, ElementBox};
+use korangar_interface::layout::{Resolvers, WindowLayout, with_single_resolver};
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::{ManuallyAssertExt, Path, State, VecIndexExt};
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::pet::{PetEgg, PetEggPathExt};
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+struct PetEggList<A> {
+    eggs_path: A,
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl<A> PetEggList<A> {
+    fn new(eggs_path: A) -> Self {
+        Self {
+            eggs_path,
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl<A> Element<ClientState> for PetEggList<A>
+where
+    A: Path<ClientState, Vec<PetEgg>>,
+{
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &State<ClientState>,
+        mut store: ElementStoreMut,
+        resolvers: &mut dyn Resolvers<ClientState>,
+    ) -> Self::LayoutInfo {
+        with_single_resolver(resolvers, |resolver| {
+            use korangar_interface::prelude::*;
+
+            let eggs = state.get(&self.eggs_path);
+
+            match eggs.len().cmp(&self.elements.len()) {
+                Ordering::Less => {
+                    self.elements.truncate(eggs.len());
+                }
+                Ordering::Equal => {}
+                Ordering::Greater => {
+                    for index in self.elements.len()..eggs.len() {
+                        let egg_path = self.eggs_path.index(index).manually_asserted();
+
+                        self.elements.push(ErasedElement::new(button! {
+                            text: egg_path.name(),
+                            event: move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+                                let index = state.get(&egg_path).index;
+                                queue.queue(InputEvent::HatchPet { index });
+                            },
+                        }));
+                    }
+                }
+            }
+
+            self.elements.iter_mut().zip(eggs.iter()).for_each(|(element, egg)| {
+                element.create_layout_info(state, store.child_store(egg.index.0 as u64), resolver);
+            });
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a State<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        let eggs = state.get(&self.eggs_path);
+
+        self.elements.iter().zip(eggs.iter()).for_each(|(element, egg)| {
+            element.lay_out(state, store.child_store(egg.index.0 as u64), &(), layout);
+        });
+    }
+}
+
+pub struct PetEggWindow<A> {
+    eggs_path: A,
+}
+
+impl<A> PetEggWindow<A> {
+    pub fn new(eggs_path: A) -> Self {
+        Self { eggs_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for PetEggWindow<A>
+where
+    A: Path<ClientState, Vec<PetEgg>>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::PetEgg)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: client_state().localization().pet_eggs_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                PetEggList::new(self.eggs_path),
+            ),
+        }
+    }
+}
@@ -11,10 +11,11 @@ use rust_state::{Path, State};
 use super::{SkillTreeWindowState, SkillTreeWindowStatePathExt};
 use crate::graphics::{Color, ShadowPadding};
 use crate::input::{InputEvent, MouseInputMode};
+use crate::interface::components::skill_box::lay_out_cooldown;
 use crate::interface::resource::SkillSource;
 use crate::loaders::OverflowBehavior;
 use crate::renderer::LayoutExt;
-use crate::state::skills::{LearnableSkill, LearnedSkill, SkillAcquisition};
+use crate::state::skills::{LearnableSkill, LearnedSkill, SkillAcquisition, SkillCooldown};
 use crate::state::theme::{InterfaceThemePathExt, SkillTreeThemePathExt};
 use crate::state::{ClientState, client_theme};
 
@@ -196,26 +197,28 @@ where
     }
 }
 
-pub struct SkillSlot<A, B, C> {
+pub struct SkillSlot<A, B, C, D> {
     learnable_skill_path: A,
     learned_skill_path: B,
     window_state_path: C,
+    cooldown_path: D,
     click_handler: SkillSlotClickHandler<A, B, C>,
     choose_lower_handler: ChooseLowerClickHandler<B, C>,
     choose_higher_handler: ChooseHigherClickHandler<B, C>,
     level_display: LevelDisplay,
 }
 
-impl<A, B, C> SkillSlot<A, B, C>
+impl<A, B, C, D> SkillSlot<A, B, C, D>
 where
     A: Copy,
     B: Copy,
     C: Copy,
 {
-    pub fn new(learnable_skill_path: A, learned_skill_path: B, window_state_path: C, source: SkillSource) -> Self {
+    pub fn new(learnable_skill_path: A, learned_skill_path: B, cooldown_path: D, window_state_path: C, source: SkillSource) -> Self {
         Self {
             learnable_skill_path,
             learned_skill_path,
+            cooldown_path,
             window_state_path,
             click_handler: SkillSlotClickHandler::new(learnable_skill_path, learned_skill_path, window_state_path, source),
             choose_lower_handler: ChooseLowerClickHandler::new(learned_skill_path, window_state_path),
@@ -225,11 +228,12 @@ where
     }
 }
 
-impl<A, B, C> Element<ClientState> for SkillSlot<A, B, C>
+impl<A, B, C, D> Element<ClientState> for SkillSlot<A, B, C, D>
 where
     A: Path<ClientState, LearnableSkill, false>,
     B: Path<ClientState, LearnedSkill, false>,
     C: Path<ClientState, SkillTreeWindowState>,
+    D: Path<ClientState, SkillCooldown, false>,
 {
     type LayoutInfo = BaseLayoutInfo;
 
@@ -448,6 +452,16 @@ where
                 layout.add_sprite(sprite_area, actions, sprite, &skill.animation_state, color, 1.3);
             }
 
+            if let Some(cooldown) = state.try_get(&self.cooldown_path) {
+                lay_out_cooldown(
+                    state,
+                    cooldown,
+                    sprite_area,
+                    *state.get(&client_theme().skill_tree().slot_corner_diameter()),
+                    layout,
+                );
+            }
+
             if is_hovered {
                 layout.register_click_handler(MouseButton::Left, &self.click_handler);
 
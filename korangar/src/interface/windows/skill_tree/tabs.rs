@@ -16,10 +16,11 @@ use crate::interface::windows::SkillTreeWindowState;
 use crate::interface::windows::skill_tree::slot::SkillSlot;
 use crate::interface::windows::skill_tree::state::LearnableSkillPath;
 use crate::state::skills::{
-    LearnableSkill, LearnedSkill, LearnedSkillPath, SkillTabLayoutPathExt, SkillTreeLayout, SkillTreeLayoutPathExt,
+    LearnableSkill, LearnedSkill, LearnedSkillPath, SkillCooldownPath, SkillTabLayoutPathExt, SkillTreeLayout, SkillTreeLayoutPathExt,
+    SkillTreePathExt,
 };
 use crate::state::theme::{GlobalThemePathExt, InterfaceThemePathExt};
-use crate::state::{ClientState, client_theme};
+use crate::state::{ClientState, ClientStatePathExt, client_state, client_theme};
 
 const SKILL_TREE_COLUMNS: usize = 7;
 
@@ -150,10 +151,13 @@ where
                                     row * SKILL_TREE_COLUMNS + column,
                                 );
                                 let learned_skill_path = LearnedSkillPath::new(learnable_skill_path, self.skills_path);
+                                let cooldown_path =
+                                    SkillCooldownPath::new(learnable_skill_path, client_state().skill_tree().cooldowns());
 
                                 SkillSlot::new(
                                     learnable_skill_path,
                                     learned_skill_path,
+                                    cooldown_path,
                                     self.window_state_path,
                                     SkillSource::SkillTree,
                                 )
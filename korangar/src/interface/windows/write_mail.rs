@@ -0,0 +1,154 @@
+use korangar_components::item_box;
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::element::id::FocusIdExt;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::{Path, PathExt, State, VecIndexExt};
+
+use crate::ItemSource;
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::mail::{MAXIMUM_MAIL_ITEMS, MailDraft, MailDraftPathExt};
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+// TODO: These constants are duplicated troughout the code base. Unify this
+// somewhere, maybe a `consts.rs` would be a good idea at this point?
+const MAXIMUM_NAME_LENGTH: usize = 24;
+const MAXIMUM_TITLE_LENGTH: usize = 40;
+const MAXIMUM_TEXT_LENGTH: usize = 500;
+/// Enough digits for the maximum amount of zeny a character can hold.
+const MAXIMUM_ZENY_LENGTH: usize = 10;
+
+pub struct WriteMailWindow<A> {
+    draft_path: A,
+}
+
+impl<A> WriteMailWindow<A> {
+    pub fn new(draft_path: A) -> Self {
+        Self { draft_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for WriteMailWindow<A>
+where
+    A: Path<ClientState, MailDraft>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::WriteMail)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        struct RecipientTextBox;
+        struct TitleTextBox;
+        struct TextTextBox;
+        struct ZenyTextBox;
+
+        let draft_path = self.draft_path;
+        let recipient_name_path = self.draft_path.recipient_name();
+        let title_path = self.draft_path.title();
+        let text_path = self.draft_path.text();
+        let zeny_path = self.draft_path.zeny();
+        let items_path = self.draft_path.items();
+
+        let recipient_action = move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+            if !state.get(&recipient_name_path).is_empty() {
+                queue.queue(InputEvent::CheckMailRecipient);
+                queue.queue(Event::FocusElement {
+                    focus_id: TitleTextBox.focus_id(),
+                });
+            }
+        };
+
+        let title_action = move |state: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+            if !state.get(&title_path).is_empty() {
+                queue.queue(Event::FocusElement {
+                    focus_id: TextTextBox.focus_id(),
+                });
+            }
+        };
+
+        let text_action = move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+            queue.queue(Event::FocusElement {
+                focus_id: ZenyTextBox.focus_id(),
+            });
+        };
+
+        let zeny_action = move |_: &State<ClientState>, queue: &mut EventQueue<ClientState>| {
+            queue.queue(Event::Unfocus);
+        };
+
+        window! {
+            title: client_state().localization().write_mail_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                text_box! {
+                    ghost_text: client_state().localization().mail_recipient_text_box_message(),
+                    state: recipient_name_path,
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_NAME_LENGTH>::new(recipient_name_path, recipient_action),
+                    focus_id: RecipientTextBox,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                button! {
+                    text: client_state().localization().check_mail_recipient_button_text(),
+                    disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                        let draft = draft_path.follow_safe(state);
+                        draft.recipient_name().is_empty() || draft.recipient_character_id().is_some()
+                    }),
+                    event: InputEvent::CheckMailRecipient,
+                },
+                text_box! {
+                    ghost_text: client_state().localization().mail_title_text_box_message(),
+                    state: title_path,
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_TITLE_LENGTH>::new(title_path, title_action),
+                    focus_id: TitleTextBox,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                text_box! {
+                    ghost_text: client_state().localization().mail_text_text_box_message(),
+                    state: text_path,
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_TEXT_LENGTH>::new(text_path, text_action),
+                    focus_id: TextTextBox,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                text_box! {
+                    ghost_text: client_state().localization().mail_zeny_text_box_message(),
+                    state: zeny_path,
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_ZENY_LENGTH>::new(zeny_path, zeny_action),
+                    focus_id: ZenyTextBox,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: std::array::from_fn::<_, MAXIMUM_MAIL_ITEMS, _>(|index| {
+                        item_box! {
+                            item_path: items_path.index(index),
+                            source: ItemSource::MailDraft,
+                        }
+                    }),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: client_state().localization().cancel_mail_button_text(),
+                            event: InputEvent::CancelWriteMail,
+                        },
+                        button! {
+                            text: client_state().localization().send_mail_button_text(),
+                            disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                                draft_path.follow_safe(state).recipient_character_id().is_none() || title_path.follow_safe(state).is_empty()
+                            }),
+                            event: InputEvent::SendMail,
+                        },
+                    ),
+                },
+            ),
+        }
+    }
+}
@@ -13,7 +13,7 @@ use blake3::Hasher;
 use flate2::bufread::ZlibDecoder;
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, Timer, print_debug};
-use ragnarok_bytes::{ByteReader, FixedByteSize, FromBytes};
+use ragnarok_bytes::{ByteReader, FixedByteSize, FromBytes, StreamReader};
 use ragnarok_formats::archive::{AssetTable, FileTableRow, Header};
 
 pub use self::builder::NativeArchiveBuilder;
@@ -57,17 +57,16 @@ impl Archive for NativeArchive {
         let mut compressed_file_table_buffer = vec![0u8; file_table.compressed_size as usize];
         file.read_exact(&mut compressed_file_table_buffer).unwrap();
 
-        let mut decoder = ZlibDecoder::new(compressed_file_table_buffer.as_slice());
-        let mut decompressed = Vec::with_capacity(file_table.uncompressed_size as usize);
-        decoder.read_to_end(&mut decompressed).expect("can't decompress file table");
+        // The file table is parsed while decompressing it, so the decompressed table
+        // never has to be held in memory as a whole.
+        let decoder = ZlibDecoder::new(compressed_file_table_buffer.as_slice());
+        let mut file_table_reader = StreamReader::without_metadata(decoder);
 
         let file_count = file_header.get_file_count();
-
-        let mut file_table_byte_reader = ByteReader::without_metadata(&decompressed);
         let mut assets = HashMap::with_capacity(file_count);
 
         for _index in 0..file_count {
-            let file_information = FileTableRow::from_bytes(&mut file_table_byte_reader).unwrap();
+            let file_information = file_table_reader.read::<FileTableRow>().expect("can't read file table row");
             let file_name = file_information.file_name.to_lowercase();
 
             assets.insert(file_name, file_information);
@@ -222,13 +222,20 @@ impl AsyncLoader {
         InventoryItem { metadata, ..item }
     }
 
-    pub fn request_shop_item_metadata_load(&self, item: ShopItem<NoMetadata>) -> ShopItem<ResourceMetadata> {
+    /// Request the icon of an identified item, e.g. for item links and icons
+    /// in chat messages.
+    #[must_use]
+    pub fn request_item_icon_load(&self, item_id: ItemId) -> Option<Arc<Texture>> {
         let resource_name = self.library.get::<ItemResource>(ItemResourceKey {
-            item_id: item.item_id,
+            item_id,
             is_identified: true,
         });
         let full_path = format!("유저인터페이스\\item\\{resource_name}.bmp");
-        let texture = self.request_item_sprite_load(item.item_id, &full_path, ImageType::Color);
+        self.request_item_sprite_load(item_id, &full_path, ImageType::Color)
+    }
+
+    pub fn request_shop_item_metadata_load(&self, item: ShopItem<NoMetadata>) -> ShopItem<ResourceMetadata> {
+        let texture = self.request_item_icon_load(item.item_id);
         let name = self
             .library
             .get::<ItemName>(ItemNameKey {
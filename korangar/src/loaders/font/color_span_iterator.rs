@@ -23,6 +23,11 @@ impl<'r, 's> ColorSpanIterator<'r, 's> {
             position: 0,
         }
     }
+
+    /// The color of the text after the last color code that was consumed.
+    pub(crate) fn color(&self) -> Option<cosmic_text::Color> {
+        self.attributes.color_opt
+    }
 }
 
 impl<'r, 's> Iterator for ColorSpanIterator<'r, 's> {
@@ -0,0 +1,149 @@
+use cosmic_text::{Attrs, Style, Weight};
+use korangar_interface::markup::{MarkupIterator, MarkupSpan, TextStyle};
+
+use super::color_span_iterator::ColorSpanIterator;
+use crate::graphics::Color;
+
+/// Text that reserves the space for an inline icon. An em space is exactly as
+/// wide as the font size, so icons are square.
+const ICON_PLACEHOLDER: &str = "\u{2003}";
+
+/// Information about a span that is needed after laying out the text. The
+/// index of the span info is stored in the metadata of the [`Attrs`], so it
+/// can be retrieved from the laid out glyphs.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct SpanInfo {
+    /// The text is bold but there is no bold font, so the glyphs are rendered
+    /// a second time with a small offset.
+    pub(crate) synthetic_bold: bool,
+    /// Index of the link or icon, see
+    /// [`InlineArea`](korangar_interface::markup::InlineArea).
+    pub(crate) inline_index: Option<usize>,
+}
+
+/// Which styles are supported by the loaded fonts.
+#[derive(Clone, Copy)]
+pub(crate) struct FontStyles {
+    pub(crate) bold: bool,
+    pub(crate) italic: bool,
+}
+
+/// Split a text with markup and color codes into spans for laying it out.
+/// Returns the spans together with the [`SpanInfo`] of every markup span.
+///
+/// Links are always drawn in the highlight color. Color codes inside of a link
+/// apply to the link only.
+pub(crate) fn markup_spans<'r, 's>(
+    text: &'s str,
+    default_color: Color,
+    highlight_color: Color,
+    attributes: Attrs<'r>,
+    font_styles: FontStyles,
+) -> (Vec<(&'s str, Attrs<'r>)>, Vec<SpanInfo>) {
+    let mut spans = Vec::new();
+    let mut span_infos = Vec::new();
+    let mut color = attributes.color_opt;
+    let mut inline_index = 0;
+
+    for markup_span in MarkupIterator::new(text) {
+        let (text, style, is_link) = match markup_span {
+            MarkupSpan::Text { text, style, link } => (text, style, link.is_some()),
+            MarkupSpan::Icon { .. } => (ICON_PLACEHOLDER, TextStyle::default(), false),
+        };
+
+        let mut span_attributes = attributes.clone().metadata(span_infos.len());
+
+        if style.bold && font_styles.bold {
+            span_attributes = span_attributes.weight(Weight::BOLD);
+        }
+
+        // Without an italic font cosmic-text would not find a matching font,
+        // so the text is displayed upright instead.
+        if style.italic && font_styles.italic {
+            span_attributes = span_attributes.style(Style::Italic);
+        }
+
+        span_infos.push(SpanInfo {
+            synthetic_bold: style.bold && !font_styles.bold,
+            inline_index: markup_span.is_inline().then_some(inline_index),
+        });
+
+        if markup_span.is_inline() {
+            inline_index += 1;
+        }
+
+        match is_link {
+            true => {
+                span_attributes.color_opt = Some(highlight_color.into());
+                spans.extend(ColorSpanIterator::new(text, default_color, highlight_color, span_attributes));
+            }
+            false => {
+                span_attributes.color_opt = color;
+                let mut iterator = ColorSpanIterator::new(text, default_color, highlight_color, span_attributes);
+                spans.extend(&mut iterator);
+                color = iterator.color();
+            }
+        }
+    }
+
+    (spans, span_infos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STYLES: FontStyles = FontStyles { bold: true, italic: true };
+    const NO_STYLES: FontStyles = FontStyles {
+        bold: false,
+        italic: false,
+    };
+
+    #[test]
+    fn test_color_continues_after_tags() {
+        let text = "^FF0000Red <B>still red</B> red";
+        let (spans, _) = markup_spans(text, Color::BLACK, Color::WHITE, Attrs::new(), ALL_STYLES);
+
+        assert_eq!(spans.len(), 3);
+        assert!(
+            spans
+                .iter()
+                .all(|(_, attributes)| attributes.color_opt == Some(Color::rgb_hex("FF0000").into()))
+        );
+        assert_eq!(spans[1].1.weight, Weight::BOLD);
+    }
+
+    #[test]
+    fn test_link_is_highlighted() {
+        let text = "^FF0000<ITEM>[Apple]<INFO>512</INFO></ITEM> red";
+        let (spans, span_infos) = markup_spans(text, Color::BLACK, Color::WHITE, Attrs::new(), ALL_STYLES);
+
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].0, "[Apple]");
+        assert_eq!(spans[0].1.color_opt, Some(Color::WHITE.into()));
+        assert_eq!(spans[1].1.color_opt, Some(Color::rgb_hex("FF0000").into()));
+        assert_eq!(span_infos[spans[0].1.metadata].inline_index, Some(0));
+        assert_eq!(span_infos[spans[1].1.metadata].inline_index, None);
+    }
+
+    #[test]
+    fn test_icon_reserves_space() {
+        let text = "<ICON>512</ICON><ICON>513</ICON>";
+        let (spans, span_infos) = markup_spans(text, Color::BLACK, Color::WHITE, Attrs::new(), ALL_STYLES);
+
+        assert_eq!(spans.len(), 2);
+        assert!(spans.iter().all(|(text, _)| *text == ICON_PLACEHOLDER));
+        assert_eq!(span_infos[spans[1].1.metadata].inline_index, Some(1));
+    }
+
+    #[test]
+    fn test_missing_styles_fall_back() {
+        let text = "<B><I>text</I></B>";
+        let (spans, span_infos) = markup_spans(text, Color::BLACK, Color::WHITE, Attrs::new(), NO_STYLES);
+
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].1.weight, Weight::NORMAL);
+        assert_eq!(spans[0].1.style, Style::Normal);
+        assert!(span_infos[spans[0].1.metadata].synthetic_bold);
+    }
+}
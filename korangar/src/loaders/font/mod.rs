@@ -2,6 +2,7 @@ mod color_span_iterator;
 mod font_file;
 mod font_map_descriptor;
 mod layout_key;
+mod markup_spans;
 
 use std::hash::Hash;
 use std::num::{NonZeroU32, NonZeroUsize};
@@ -9,7 +10,7 @@ use std::sync::{Arc, Mutex};
 
 use cgmath::{Point2, Vector2};
 use cosmic_text::fontdb::ID;
-use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping, Weight, fontdb};
+use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping, Style, Weight, fontdb};
 use hashbrown::HashMap;
 use image::{ImageBuffer, Rgba, RgbaImage, imageops};
 #[cfg(feature = "debug")]
@@ -22,10 +23,12 @@ use korangar_debug::logging::print_debug;
 use korangar_interface::application::TextLayouter;
 use korangar_interface::components::drop_down::DropDownItem;
 use korangar_interface::element::{ElementDisplay, StateElement};
+use korangar_interface::layout::area::Area;
+use korangar_interface::markup::InlineArea;
 use rust_state::RustState;
 use serde::{Deserialize, Serialize};
 
-use self::color_span_iterator::ColorSpanIterator;
+use self::markup_spans::{FontStyles, SpanInfo, markup_spans};
 use super::{GameFileLoader, TextureLoader};
 use crate::graphics::{Color, MAX_TEXTURE_SIZE, ScreenSize, Texture};
 use crate::loaders::font::font_file::FontFile;
@@ -35,9 +38,13 @@ use crate::state::ClientState;
 
 const MAX_CACHE_COUNT: u32 = 2048;
 const MAX_CACHE_SIZE: usize = 32 << 20;
+/// Horizontal offset of the second copy of a glyph when emulating bold text,
+/// relative to the font size.
+const SYNTHETIC_BOLD_OFFSET: f32 = 0.04;
 
 struct CachedLayout {
     glyphs: Vec<GlyphInstruction>,
+    inline_areas: Vec<InlineArea>,
     size: Vector2<f32>,
 }
 
@@ -45,6 +52,7 @@ impl Cacheable for CachedLayout {
     fn size(&self) -> usize {
         size_of::<Vector2<f32>>() +
         size_of::<GlyphInstruction>() * self.glyphs.len() +
+        size_of::<InlineArea>() * self.inline_areas.len() +
         // Approximation for the text that is saved as a key in the lookup table and the FIFO.
         2 * self.glyphs.len()
     }
@@ -150,6 +158,7 @@ pub(crate) struct GlyphCoordinate {
 pub struct FontLoader {
     font_system: Mutex<FontSystem>,
     primary_font_family: String,
+    font_styles: FontStyles,
     font_map: Arc<Texture>,
     glyph_cache: HashMap<ID, Arc<HashMap<u16, GlyphCoordinate>>>,
     layout_cache: Mutex<SimpleCache<LayoutKey, CachedLayout>>,
@@ -168,6 +177,7 @@ impl FontLoader {
             .collect();
 
         let primary_font_family = Self::extract_primary_font_family(&font_system, &fonts);
+        let font_styles = Self::supported_font_styles(&font_system, &primary_font_family);
         let font_map_image_data = Self::merge_font_maps(&mut glyph_cache, &mut font_system, fonts);
 
         let font_map = texture_loader.create_msdf("font map", font_map_image_data);
@@ -180,6 +190,7 @@ impl FontLoader {
         Self {
             font_system: Mutex::new(font_system),
             primary_font_family,
+            font_styles,
             font_map,
             glyph_cache,
             layout_cache: Mutex::new(layout_cache),
@@ -213,6 +224,22 @@ impl FontLoader {
             .expect("primary font has no family name")
     }
 
+    /// Bold and italic text can only be rendered if the primary font family
+    /// has a bold or italic face.
+    fn supported_font_styles(font_system: &FontSystem, primary_font_family: &str) -> FontStyles {
+        let primary_faces = || {
+            font_system
+                .db()
+                .faces()
+                .filter(|face| face.families.iter().any(|(family, _)| family == primary_font_family))
+        };
+
+        FontStyles {
+            bold: primary_faces().any(|face| face.weight.0 >= Weight::SEMIBOLD.0),
+            italic: primary_faces().any(|face| face.style == Style::Italic),
+        }
+    }
+
     fn merge_font_maps(
         glyph_cache: &mut HashMap<ID, Arc<HashMap<u16, GlyphCoordinate>>>,
         font_system: &mut FontSystem,
@@ -326,6 +353,41 @@ impl FontLoader {
         )
     }
 
+    /// Get the areas of the links and icons in a text with markup, relative
+    /// to the top left corner of the text.
+    pub fn get_inline_areas(
+        &self,
+        text: &str,
+        default_color: Color,
+        highlight_color: Color,
+        font_size: FontSize,
+        line_height_scale: f32,
+        available_width: f32,
+        overflow_behavior: OverflowBehavior,
+    ) -> Vec<InlineArea> {
+        // The text is rendered with the font size after shrinking it, so we
+        // need to use the same font size here.
+        let (_, font_size) = self.get_text_dimensions(
+            text,
+            default_color,
+            highlight_color,
+            font_size,
+            line_height_scale,
+            available_width,
+            overflow_behavior,
+        );
+
+        self.with_layout(
+            text,
+            default_color,
+            highlight_color,
+            font_size,
+            line_height_scale,
+            Some(available_width),
+            |layout| layout.inline_areas.clone(),
+        )
+    }
+
     /// Writes the text layout for the given text into the `glyphs` buffer and
     /// returns the size of the text in pixels.
     ///
@@ -340,6 +402,35 @@ impl FontLoader {
         available_width: Option<f32>,
         glyphs: Option<&mut Vec<GlyphInstruction>>,
     ) -> Vector2<f32> {
+        self.with_layout(
+            text,
+            default_color,
+            highlight_color,
+            font_size,
+            line_height_scale,
+            available_width,
+            |layout| {
+                if let Some(glyphs) = glyphs {
+                    glyphs.extend(layout.glyphs.iter().copied());
+                }
+
+                layout.size
+            },
+        )
+    }
+
+    /// Get the layout of the text from the cache or lay out the text if it
+    /// is not cached yet.
+    fn with_layout<T>(
+        &self,
+        text: &str,
+        default_color: Color,
+        highlight_color: Color,
+        font_size: FontSize,
+        line_height_scale: f32,
+        available_width: Option<f32>,
+        f: impl FnOnce(&CachedLayout) -> T,
+    ) -> T {
         // TODO: NHA We currently call get_text_dimensions() with different
         //       "available_width" and "overflow_behavior" then when we call
         //       layout_text() for with the resulting available_width.
@@ -354,13 +445,10 @@ impl FontLoader {
         };
 
         if let Some(layout) = self.layout_cache.lock().unwrap().get_with(&key, |k| k == &key) {
-            if let Some(glyphs) = glyphs {
-                glyphs.extend(layout.glyphs.iter().copied())
-            }
-            return layout.size;
+            return f(layout);
         }
 
-        let (size, rendered_glyphs) = self.render_layout(
+        let layout = self.render_layout(
             text,
             default_color,
             highlight_color,
@@ -369,14 +457,9 @@ impl FontLoader {
             available_width,
         );
 
-        if let Some(glyphs) = glyphs {
-            glyphs.extend(rendered_glyphs.iter().copied());
-        }
+        let result = f(&layout);
 
-        let _result = self.layout_cache.lock().unwrap().insert(key.to_owned(), CachedLayout {
-            glyphs: rendered_glyphs,
-            size,
-        });
+        let _result = self.layout_cache.lock().unwrap().insert(key.to_owned(), layout);
 
         #[cfg(feature = "debug")]
         if let Err(error) = _result {
@@ -388,7 +471,7 @@ impl FontLoader {
             );
         }
 
-        size
+        result
     }
 
     // TODO: NHA cosmic_text could help us to render text in boxes.
@@ -403,12 +486,13 @@ impl FontLoader {
         font_size: FontSize,
         line_height_scale: f32,
         available_width: Option<f32>,
-    ) -> (Vector2<f32>, Vec<GlyphInstruction>) {
+    ) -> CachedLayout {
         let mut text_width = 0f32;
         let mut text_height = 0f32;
 
         let metrics = Metrics::relative(font_size.0, line_height_scale);
         let attributes = Attrs::new().family(Family::Name(&self.primary_font_family));
+        let (spans, span_infos) = markup_spans(text, default_color, highlight_color, attributes.clone(), self.font_styles);
 
         // We try to hold the mutex lock as short as possible.
         let buffer = {
@@ -416,24 +500,45 @@ impl FontLoader {
             let mut buffer = Buffer::new(&mut font_system, metrics);
 
             buffer.set_size(&mut font_system, available_width, None);
-            buffer.set_rich_text(
-                &mut font_system,
-                ColorSpanIterator::new(text, default_color, highlight_color, attributes.clone()),
-                &attributes,
-                Shaping::Advanced,
-                None,
-            );
+            buffer.set_rich_text(&mut font_system, spans, &attributes, Shaping::Advanced, None);
 
             buffer
         };
 
         let mut rendered_glyphs = Vec::new();
+        let mut inline_areas: Vec<InlineArea> = Vec::new();
 
         for run in buffer.layout_runs() {
             text_width = text_width.max(run.line_w);
             text_height += run.line_height;
 
             for layout_glyph in run.glyphs.iter() {
+                let span_info = span_infos.get(layout_glyph.metadata).copied().unwrap_or_default();
+
+                // Links and icons might be split into multiple glyphs and lines, so we
+                // extend the last area as long as we are on the same line.
+                if let Some(index) = span_info.inline_index {
+                    let left = layout_glyph.x;
+                    let right = layout_glyph.x + layout_glyph.w;
+
+                    match inline_areas.last_mut() {
+                        Some(InlineArea { index: last_index, area }) if *last_index == index && area.top == run.line_top => {
+                            let new_left = area.left.min(left);
+                            area.width = (area.left + area.width).max(right) - new_left;
+                            area.left = new_left;
+                        }
+                        _ => inline_areas.push(InlineArea {
+                            index,
+                            area: Area {
+                                left,
+                                top: run.line_top,
+                                width: right - left,
+                                height: run.line_height,
+                            },
+                        }),
+                    }
+                }
+
                 let physical_glyph = layout_glyph.physical((0.0, 0.0), 1.0);
 
                 let Some(glyph_coordinate) = self.glyph_cache.get(&layout_glyph.font_id).and_then(|font| {
@@ -461,10 +566,28 @@ impl FontLoader {
                     texture_coordinate: glyph_coordinate.texture_coordinate,
                     color,
                 });
+
+                if span_info.synthetic_bold {
+                    let offset = font_size.0 * SYNTHETIC_BOLD_OFFSET;
+                    let position = Rectangle::new(
+                        Point2::new(position.min.x + offset, position.min.y),
+                        Point2::new(position.max.x + offset, position.max.y),
+                    );
+
+                    rendered_glyphs.push(GlyphInstruction {
+                        position,
+                        texture_coordinate: glyph_coordinate.texture_coordinate,
+                        color,
+                    });
+                }
             }
         }
 
-        (Vector2::new(text_width, text_height), rendered_glyphs)
+        CachedLayout {
+            glyphs: rendered_glyphs,
+            inline_areas,
+            size: Vector2::new(text_width, text_height),
+        }
     }
 
     /// The texture of the static font map.
@@ -495,4 +618,24 @@ impl TextLayouter<ClientState> for Arc<FontLoader> {
 
         (size, font_size)
     }
+
+    fn get_inline_areas(
+        &self,
+        text: &str,
+        default_color: Color,
+        highlight_color: Color,
+        font_size: FontSize,
+        available_width: f32,
+        overflow_behavior: OverflowBehavior,
+    ) -> Vec<InlineArea> {
+        self.as_ref().get_inline_areas(
+            text,
+            default_color,
+            highlight_color,
+            font_size,
+            1.0,
+            available_width,
+            overflow_behavior,
+        )
+    }
 }
@@ -62,16 +62,17 @@ use korangar_debug::profiling::Profiler;
 use korangar_interface::Interface;
 use korangar_interface::layout::MouseButton;
 use korangar_networking::{
-    DisconnectReason, HotkeyState, LoginServerLoginData, MessageColor, NetworkEvent, NetworkEventBuffer, NetworkingSystem, SellItem,
-    SupportedPacketVersion,
+    DisconnectReason, HotkeyState, InventoryItemDetails, LoginServerLoginData, MessageColor, NetworkEvent, NetworkEventBuffer,
+    NetworkingSystem, ReconnectPolicy, SellItem, SupportedPacketVersion,
 };
 #[cfg(feature = "debug")]
 use networking::{PacketHistory, PacketHistoryCallback};
 #[cfg(not(feature = "debug"))]
 use ragnarok_packets::handler::NoPacketCallback;
 use ragnarok_packets::{
-    AttackRange, BuyShopItemsResult, CharacterServerInformation, Direction, DisappearanceReason, HotbarSlot, SellItemsResult, SkillId,
-    SkillLevel, SkillType, TilePosition, UnitId, WorldPosition,
+    AddMailItemResult, AttackRange, BuyShopItemsResult, CharacterServerInformation, ClaimMailAttachmentResult, CreateChatRoomResult,
+    Direction, DisappearanceReason, HomunculusCommand, HotbarSlot, JoinChatRoomFailedReason, MailOpenType, MoveItemToCartResult,
+    PetCommand, SellItemsResult, SendMailResult, SkillId, SkillLevel, SkillType, TilePosition, UnitId, WorldPosition,
 };
 use renderer::InterfaceRenderer;
 use rust_state::{ManuallyAssertExt, State};
@@ -80,12 +81,15 @@ use rust_state::{VecIndexExt, VecLookupExt};
 use settings::{
     AudioSettings, AudioSettingsPathExt, GraphicsSettingsCapabilities, GraphicsSettingsPathExt, InterfaceSettings, InterfaceSettingsPathExt,
 };
+use state::chat_room::{ChatRoomsPathExt, MAXIMUM_CHAT_ROOM_LIMIT};
 use state::hotbar::HotbarPathExt;
 use state::inventory::InventoryPathExt;
 use state::localization::Localization;
+use state::mail::MailboxPathExt;
+use state::pet::{PetEgg, PetPathExt};
 use state::skills::SkillTreePathExt;
 use state::theme::{CursorThemePathExt, IndicatorThemePathExt, InterfaceThemePathExt, WorldThemePathExt};
-use state::{ChatMessage, ClientState, ClientStatePathExt, client_state, this_entity, this_player};
+use state::{ChatIcon, ChatMessage, ClientState, ClientStatePathExt, client_state, this_entity, this_player};
 #[cfg(feature = "debug")]
 use wgpu::Device;
 use wgpu::util::initialize_adapter_from_env_or_default;
@@ -96,7 +100,7 @@ use wgpu::{
     MemoryHints, NoopBackendOptions, Queue, Trace,
 };
 use winit::application::ApplicationHandler;
-use winit::dpi::{LogicalSize, PhysicalSize};
+use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
 use winit::event::WindowEvent;
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
 use winit::keyboard::PhysicalKey;
@@ -104,6 +108,7 @@ use winit::window::{Icon, Window, WindowId};
 
 use crate::graphics::*;
 use crate::input::{InputEvent, InputSystem};
+use crate::interface::components::entity_menu::player_context_menu;
 use crate::interface::cursor::{MouseCursor, MouseCursorState};
 use crate::interface::resource::{ItemSource, SkillSource};
 use crate::interface::windows::*;
@@ -151,6 +156,7 @@ pub static SHUTDOWN_SIGNAL: LazyLock<AtomicBool> = LazyLock::new(|| AtomicBool::
 const DEBUG_WINDOWS: &[WindowClass] = &[
     WindowClass::CacheStatistics,
     WindowClass::ClientStateInspector,
+    WindowClass::NetworkStatistics,
     WindowClass::PacketInspector,
     WindowClass::Profiler,
     WindowClass::RenderOptions,
@@ -268,6 +274,7 @@ struct Client {
     point_shadow_entity_instructions: Vec<EntityInstruction>,
     point_light_with_shadow_instructions: Vec<PointLightWithShadowInstruction>,
     point_light_instructions: Vec<PointLightInstruction>,
+    picker_rectangle_instructions: Vec<PickerRectangleInstruction>,
 
     input_system: InputSystem,
 
@@ -325,11 +332,14 @@ struct Client {
     audio_engine: Arc<AudioEngine<GameFileLoader>>,
     active_interface_settings: InterfaceSettings,
     active_graphics_settings: GraphicsSettings,
+    active_audio_settings: AudioSettings,
     graphics_engine: GraphicsEngine,
     queue: Queue,
     #[cfg(feature = "debug")]
     device: Device,
     window: Option<Arc<Window>>,
+    ime_allowed: bool,
+    ime_cursor_area: Option<(ScreenPosition, ScreenSize)>,
 
     map: Option<Arc<Map>>,
     client_state: State<ClientState>,
@@ -421,8 +431,11 @@ impl Client {
         });
 
         time_phase!("create audio engine", {
+            let audio_settings = AudioSettings::new();
             let audio_engine = Arc::new(AudioEngine::new(game_file_loader.clone()));
-            audio_engine.set_background_music_volume(0.1);
+            audio_engine.set_background_music_volume(audio_settings.background_music_volume);
+            audio_engine.set_sound_effect_volume(audio_settings.sound_effect_volume);
+            audio_engine.set_spatial_sound_effect_volume(audio_settings.spatial_sound_effect_volume);
         });
 
         time_phase!("create resource managers", {
@@ -528,6 +541,7 @@ impl Client {
             let point_shadow_entity_instructions = Vec::default();
             let point_light_with_shadow_instructions = Vec::default();
             let point_light_instructions = Vec::default();
+            let picker_rectangle_instructions = Vec::default();
         });
 
         time_phase!("create graphics engine", {
@@ -574,12 +588,14 @@ impl Client {
 
         time_phase!("initialize networking", {
             #[cfg(not(feature = "debug"))]
-            let (networking_system, network_event_buffer) = NetworkingSystem::spawn();
+            let (mut networking_system, network_event_buffer) = NetworkingSystem::spawn();
 
             #[cfg(feature = "debug")]
             let (packet_history, packet_history_callback) = PacketHistory::new();
             #[cfg(feature = "debug")]
-            let (networking_system, network_event_buffer) = NetworkingSystem::spawn_with_callback(packet_history_callback);
+            let (mut networking_system, network_event_buffer) = NetworkingSystem::spawn_with_callback(packet_history_callback);
+
+            networking_system.set_reconnect_policy(Some(ReconnectPolicy::default()));
         });
 
         time_phase!("create resources", {
@@ -640,9 +656,10 @@ impl Client {
         });
 
         time_phase!("create client state", {
-            let client_state = State::new(ClientState::new(
+            let mut client_state = State::new(ClientState::new(
                 &game_file_loader,
                 graphics_settings.clone(),
+                audio_settings.clone(),
                 #[cfg(feature = "debug")]
                 packet_history,
             ));
@@ -650,6 +667,16 @@ impl Client {
 
         let active_interface_settings = client_state.follow(crate::client_state().interface_settings()).clone();
 
+        let window_cache = interface.get_window_cache_mut();
+        window_cache.switch_layout(&active_interface_settings.window_layout);
+        let window_layouts = window_cache.layout_names();
+
+        client_state
+            .follow_mut(crate::client_state().interface_settings_capabilities())
+            .set_window_layouts(window_layouts);
+
+        interface.set_reduce_motion(active_interface_settings.reduce_motion);
+
         interface.open_window(LoginWindow::new(
             crate::client_state().login_window(),
             crate::client_state().login_settings(),
@@ -695,6 +722,7 @@ impl Client {
             point_shadow_entity_instructions,
             point_light_with_shadow_instructions,
             point_light_instructions,
+            picker_rectangle_instructions,
             input_system,
             interface,
             mouse_cursor,
@@ -734,11 +762,14 @@ impl Client {
             audio_engine,
             active_interface_settings,
             active_graphics_settings: graphics_settings,
+            active_audio_settings: audio_settings,
             graphics_engine,
             queue,
             #[cfg(feature = "debug")]
             device,
             window: None,
+            ime_allowed: false,
+            ime_cursor_area: None,
 
             map: Some(map),
             client_state,
@@ -788,6 +819,7 @@ impl Client {
         self.point_shadow_entity_instructions.clear();
         self.point_light_with_shadow_instructions.clear();
         self.point_light_instructions.clear();
+        self.picker_rectangle_instructions.clear();
 
         #[cfg(feature = "debug")]
         clear_measurement.stop();
@@ -900,7 +932,8 @@ impl Client {
                         );
                     }
                 }
-                NetworkEvent::CharacterServerConnected { normal_slot_count } => {
+                NetworkEvent::CharacterServerConnected { normal_slot_count }
+                | NetworkEvent::CharacterServerReconnected { normal_slot_count } => {
                     self.client_state
                         .follow_mut(client_state().character_slots())
                         .set_slot_count(normal_slot_count);
@@ -912,16 +945,7 @@ impl Client {
                     self.interface.open_window(ErrorWindow::new(message.to_owned()));
                 }
                 NetworkEvent::CharacterServerDisconnected { reason } => {
-                    if reason != DisconnectReason::ClosedByClient {
-                        // TODO: Make this an on-screen popup.
-                        #[cfg(feature = "debug")]
-                        print_debug!("Disconnection from the character server with error");
-
-                        let login_data = self.saved_login_data.as_ref().unwrap();
-                        let server = self.saved_character_server.clone().unwrap();
-                        self.networking_system
-                            .connect_to_character_server(self.saved_packet_version, login_data, server);
-                    } else if !self.networking_system.is_map_server_connected() {
+                    if !self.networking_system.is_map_server_connected() {
                         #[cfg(not(feature = "debug"))]
                         self.interface.close_all_windows();
 
@@ -933,8 +957,43 @@ impl Client {
                             client_state().login_settings(),
                             client_state().client_info(),
                         ));
+
+                        // The networking system already failed to resume the session, so we fall back
+                        // to the login screen.
+                        if reason == DisconnectReason::ConnectionError {
+                            #[cfg(feature = "debug")]
+                            print_debug!("Disconnection from the character server with error");
+
+                            self.interface
+                                .open_window(ErrorWindow::new("Lost the connection to the character server".to_owned()));
+                        }
                     }
                 }
+                NetworkEvent::CharacterServerReconnecting {
+                    attempt,
+                    maximum_attempts,
+                    delay,
+                } => {
+                    #[cfg(feature = "debug")]
+                    print_debug!("Reconnecting in {:?} ({}/{})", delay, attempt, maximum_attempts);
+
+                    let message = format!(
+                        "Connection lost. Reconnecting in {} seconds... ({attempt}/{maximum_attempts})",
+                        delay.as_secs()
+                    );
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new(message, MessageColor::Error));
+                }
+                NetworkEvent::MapServerConnectionFailed { .. } => {
+                    #[cfg(feature = "debug")]
+                    print_debug!("The map server refused the login");
+
+                    // Disconnecting brings the player back to the character selection, so the
+                    // character server can authenticate the session again.
+                    self.networking_system.disconnect_from_map_server();
+                }
                 NetworkEvent::MapServerDisconnected { reason } => {
                     if reason != DisconnectReason::ClosedByClient {
                         // TODO: Make this an on-screen popup.
@@ -959,6 +1018,11 @@ impl Client {
                     self.client_state.follow_mut(client_state().ground_items()).clear();
                     *self.client_state.follow_mut(client_state().buffered_action()) = None;
 
+                    self.client_state.follow_mut(client_state().pet()).clear();
+                    self.client_state.follow_mut(client_state().homunculus()).clear();
+                    self.client_state.follow_mut(client_state().chat_rooms()).clear();
+                    self.client_state.follow_mut(client_state().skill_tree()).clear_cooldowns();
+
                     self.audio_engine.play_background_music_track(None);
 
                     #[cfg(not(feature = "debug"))]
@@ -1008,6 +1072,7 @@ impl Client {
                     }
                 }
                 NetworkEvent::AccountId { .. } => {}
+                NetworkEvent::MapServerConnected { .. } => {}
                 NetworkEvent::CharacterList { characters } => {
                     self.audio_engine.play_sound_effect(self.main_menu_click_sound_effect);
 
@@ -1099,6 +1164,7 @@ impl Client {
                         // TODO: Check that manually asserting is fine. Technically this window should only
                         // be open while the player is selected.
                         this_player().manually_asserted().job_level(),
+                        client_state().mailbox().has_new_mail(),
                     ));
                     self.interface
                         .open_window(ChatWindow::new(client_state().chat_window(), client_state().chat_messages()));
@@ -1180,7 +1246,7 @@ impl Client {
                         {
                             let entity_type = entity.get_entity_type();
 
-                            if entity_type == EntityType::Monster {
+                            if matches!(entity_type, EntityType::Monster | EntityType::Homunculus) {
                                 let mut entity = entity.clone();
                                 entity.set_dead(client_tick);
                                 entity.stop_movement();
@@ -1331,8 +1397,13 @@ impl Client {
                     self.client_state.follow_mut(client_state().ground_items()).clear();
                     *self.client_state.follow_mut(client_state().buffered_action()) = None;
 
+                    // Chat rooms are closed by the server when changing maps.
+                    self.client_state.follow_mut(client_state().chat_rooms()).clear();
+
                     // Close any remaining dialogs.
                     self.interface.close_window_with_class(WindowClass::Dialog);
+                    self.interface.close_window_with_class(WindowClass::ChatRoom);
+                    self.interface.close_window_with_class(WindowClass::ChatRoomPassword);
 
                     self.async_loader.request_map_load(map_name, Some(position));
                 }
@@ -1340,9 +1411,17 @@ impl Client {
                     self.game_timer.set_client_tick(client_tick, received_at);
                 }
                 NetworkEvent::ChatMessage { text, color } => {
-                    self.client_state
-                        .follow_mut(client_state().chat_messages())
-                        .push(ChatMessage::new(text, color));
+                    let mut message = ChatMessage::new(text, color);
+                    let icons = message
+                        .icon_item_ids()
+                        .map(|item_id| ChatIcon {
+                            item_id,
+                            texture: self.async_loader.request_item_icon_load(item_id),
+                        })
+                        .collect();
+                    message.icons = icons;
+
+                    self.client_state.follow_mut(client_state().chat_messages()).push(message);
                 }
                 NetworkEvent::UpdateEntityDetails { entity_id, name } => {
                     let entity = self
@@ -1601,6 +1680,25 @@ impl Client {
                         entity.set_animation_data(animation_data);
                     }
                 }
+                NetworkEvent::UpdateEntityCart { entity_id, cart_type } => {
+                    if let Some(entity) = self
+                        .client_state
+                        .follow_mut(client_state().entities())
+                        .iter_mut()
+                        .find(|entity| entity.get_entity_id() == entity_id)
+                        && entity.get_cart_type() != cart_type
+                    {
+                        entity.set_cart_type(cart_type);
+
+                        if let Some(animation_data) = self.async_loader.request_animation_data_load(
+                            entity.get_entity_id(),
+                            entity.get_entity_type(),
+                            entity.get_entity_part_files(&self.library),
+                        ) {
+                            entity.set_animation_data(animation_data);
+                        }
+                    }
+                }
                 NetworkEvent::LoggedOut => {
                     self.networking_system.disconnect_from_map_server();
                 }
@@ -1873,6 +1971,462 @@ impl Client {
                         .follow_mut(client_state().skill_tree_window().chosen_skill_level())
                         .remove(&skill_id);
                 }
+                NetworkEvent::SkillCooldown { skill_id, duration } => {
+                    self.client_state
+                        .follow_mut(client_state().skill_tree())
+                        .start_cooldown(skill_id, duration, client_tick);
+                }
+                NetworkEvent::SkillCastStarted {
+                    source_entity_id,
+                    target_position,
+                    cast_time,
+                    ..
+                } => {
+                    let entities = self.client_state.follow_mut(client_state().entities());
+                    let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == source_entity_id);
+
+                    // Skills without a cast time are used instantly, so there is nothing to
+                    // display.
+                    if cast_time > 0
+                        && let Some(entity) = entity
+                        && let Some(map) = &self.map
+                    {
+                        entity.start_casting(map, cast_time, target_position, client_tick);
+                    }
+                }
+                NetworkEvent::SkillCastCancelled { entity_id } => {
+                    if let Some(entity) = self
+                        .client_state
+                        .follow_mut(client_state().entities())
+                        .iter_mut()
+                        .find(|entity| entity.get_entity_id() == entity_id)
+                    {
+                        entity.stop_casting();
+                    }
+                }
+                NetworkEvent::SetCart { items } => {
+                    self.client_state.follow_mut(client_state().cart()).fill(&self.async_loader, items);
+                }
+                NetworkEvent::CartItemAdded { item } => {
+                    self.client_state
+                        .follow_mut(client_state().cart())
+                        .add_item(&self.async_loader, item);
+                }
+                NetworkEvent::CartItemRemoved { index, amount } => {
+                    self.client_state.follow_mut(client_state().cart()).remove_item(index, amount);
+                }
+                NetworkEvent::UpdateCartInformation {
+                    item_count,
+                    maximum_item_count,
+                    weight,
+                    maximum_weight,
+                } => {
+                    self.client_state.follow_mut(client_state().cart()).update_information(
+                        item_count,
+                        maximum_item_count,
+                        weight,
+                        maximum_weight,
+                    );
+                }
+                NetworkEvent::MoveItemToCartFailed { result } => {
+                    let message = match result {
+                        MoveItemToCartResult::Overweight => "The cart is too heavy.",
+                        MoveItemToCartResult::TooManyItems => "The cart is full.",
+                        MoveItemToCartResult::NotAllowed => "This item can't be put in the cart.",
+                    };
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new(message.to_owned(), MessageColor::Error));
+                }
+                NetworkEvent::CartRemoved => {
+                    self.client_state.follow_mut(client_state().cart()).clear();
+                    self.interface.close_window_with_class(WindowClass::Cart);
+                }
+                NetworkEvent::NewMailStatus { has_new_mail } => {
+                    self.client_state
+                        .follow_mut(client_state().mailbox())
+                        .set_has_new_mail(has_new_mail);
+
+                    if has_new_mail {
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new("You have new mail.".to_owned(), MessageColor::Information));
+                    }
+                }
+                NetworkEvent::MailList { mails, is_last_page } => {
+                    self.client_state
+                        .follow_mut(client_state().mailbox())
+                        .add_mails(mails, is_last_page);
+                }
+                NetworkEvent::MailContent {
+                    open_type,
+                    mail_id,
+                    text,
+                    zeny,
+                    items,
+                } => {
+                    self.client_state.follow_mut(client_state().mailbox()).open_mail(
+                        &self.async_loader,
+                        open_type,
+                        mail_id,
+                        text,
+                        zeny,
+                        items,
+                    );
+
+                    if !self.interface.is_window_with_class_open(WindowClass::Mail) {
+                        self.interface.open_window(MailWindow::new(client_state().mailbox().opened_mail()));
+                    }
+                }
+                NetworkEvent::MailDeleted { mail_id } => {
+                    if self.client_state.follow_mut(client_state().mailbox()).remove_mail(mail_id) {
+                        self.interface.close_window_with_class(WindowClass::Mail);
+                    }
+                }
+                NetworkEvent::MailZenyClaimed { mail_id, result } => match result {
+                    ClaimMailAttachmentResult::Success => {
+                        self.client_state.follow_mut(client_state().mailbox()).zeny_claimed(mail_id);
+                    }
+                    ClaimMailAttachmentResult::Failed | ClaimMailAttachmentResult::Overweight => {
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new("Failed to claim the zeny.".to_owned(), MessageColor::Error));
+                    }
+                },
+                NetworkEvent::MailItemsClaimed { mail_id, result } => {
+                    let message = match result {
+                        ClaimMailAttachmentResult::Success => {
+                            self.client_state.follow_mut(client_state().mailbox()).items_claimed(mail_id);
+                            continue;
+                        }
+                        ClaimMailAttachmentResult::Failed => "Failed to claim the items.",
+                        ClaimMailAttachmentResult::Overweight => "You can't carry the attached items.",
+                    };
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new(message.to_owned(), MessageColor::Error));
+                }
+                NetworkEvent::WriteMailOpened { recipient_name, success } => match success {
+                    true => {
+                        let check_recipient = !recipient_name.is_empty();
+                        self.client_state.follow_mut(client_state().mailbox()).start_draft(recipient_name);
+
+                        if check_recipient {
+                            let recipient_name = self.client_state.follow_mut(client_state().mailbox()).check_recipient();
+                            let _ = self.networking_system.check_mail_recipient(recipient_name);
+                        }
+
+                        if !self.interface.is_window_with_class_open(WindowClass::WriteMail) {
+                            self.interface.open_window(WriteMailWindow::new(client_state().mailbox().draft()));
+                        }
+                    }
+                    false => {
+                        self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                            "You can't write a mail right now.".to_owned(),
+                            MessageColor::Error,
+                        ));
+                    }
+                },
+                NetworkEvent::MailRecipientChecked { character_id } => {
+                    self.client_state
+                        .follow_mut(client_state().mailbox())
+                        .recipient_checked(character_id);
+
+                    if character_id.is_none() {
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new("The recipient doesn't exist.".to_owned(), MessageColor::Error));
+                    }
+                }
+                NetworkEvent::MailItemAttached { result, index, amount } => {
+                    let message = match result {
+                        AddMailItemResult::Success => {
+                            let item = self
+                                .client_state
+                                .follow(client_state().inventory().items())
+                                .iter()
+                                .find(|item| item.index == index)
+                                .cloned();
+
+                            if let Some(item) = item {
+                                self.client_state.follow_mut(client_state().mailbox()).attach_item(item, amount);
+                            }
+
+                            continue;
+                        }
+                        AddMailItemResult::Overweight => "The mail is too heavy.",
+                        AddMailItemResult::Failed => "Failed to attach the item.",
+                        AddMailItemResult::TooManyItems => "You can't attach any more items.",
+                        AddMailItemResult::NotTradable => "This item can't be sent.",
+                    };
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new(message.to_owned(), MessageColor::Error));
+                }
+                NetworkEvent::MailItemDetached { success, index, amount } => {
+                    if success {
+                        self.client_state.follow_mut(client_state().mailbox()).detach_item(index, amount);
+                    }
+                }
+                NetworkEvent::MailSent { result } => {
+                    let message = match result {
+                        SendMailResult::Success => {
+                            self.client_state.follow_mut(client_state().mailbox()).clear_draft();
+                            self.interface.close_window_with_class(WindowClass::WriteMail);
+
+                            self.client_state
+                                .follow_mut(client_state().chat_messages())
+                                .push(ChatMessage::new("The mail was sent.".to_owned(), MessageColor::Information));
+
+                            continue;
+                        }
+                        SendMailResult::Failed => "Failed to send the mail.",
+                        SendMailResult::TooManyMailsSent => "You sent too many mails today.",
+                        SendMailResult::ItemFailure => "Failed to attach the items.",
+                        SendMailResult::CharacterNameCheckFailed => "The recipient doesn't exist.",
+                        SendMailResult::WhisperBlocked => "The recipient is blocking your messages.",
+                    };
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new(message.to_owned(), MessageColor::Error));
+                }
+                NetworkEvent::StartPetCapture => {
+                    self.client_state.follow_mut(client_state().pet()).set_capturing(true);
+
+                    self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                        "Select a monster to capture.".to_owned(),
+                        MessageColor::Information,
+                    ));
+                }
+                NetworkEvent::PetCaptureResult { success } => {
+                    let (message, color) = match success {
+                        true => ("The monster was captured.", MessageColor::Information),
+                        false => ("Failed to capture the monster.", MessageColor::Error),
+                    };
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new(message.to_owned(), color));
+                }
+                NetworkEvent::PetEggList { indices } => {
+                    let eggs = self
+                        .client_state
+                        .follow(client_state().inventory().items())
+                        .iter()
+                        .filter(|item| indices.contains(&item.index))
+                        .map(|item| PetEgg {
+                            index: item.index,
+                            name: self
+                                .library
+                                .get::<ItemName>(ItemNameKey {
+                                    item_id: item.item_id,
+                                    is_identified: item.is_identified(),
+                                })
+                                .to_string(),
+                        })
+                        .collect();
+
+                    self.client_state.follow_mut(client_state().pet()).set_eggs(eggs);
+
+                    if !self.interface.is_window_with_class_open(WindowClass::PetEgg) {
+                        self.interface.open_window(PetEggWindow::new(client_state().pet().eggs()));
+                    }
+                }
+                NetworkEvent::UpdatePetInformation {
+                    name,
+                    level,
+                    hunger,
+                    intimacy,
+                    accessory_id,
+                    ..
+                } => {
+                    self.client_state
+                        .follow_mut(client_state().pet())
+                        .update_information(name, level, hunger, intimacy, accessory_id);
+                }
+                NetworkEvent::UpdatePetState {
+                    entity_id,
+                    state_type,
+                    value,
+                } => {
+                    self.client_state
+                        .follow_mut(client_state().pet())
+                        .update_state(entity_id, state_type, value);
+                }
+                NetworkEvent::PetFed { success, item_id } => {
+                    if !success {
+                        let name = self
+                            .library
+                            .get::<ItemName>(ItemNameKey {
+                                item_id,
+                                is_identified: true,
+                            })
+                            .to_string();
+
+                        self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                            format!("You need {name} to feed your pet."),
+                            MessageColor::Error,
+                        ));
+                    }
+                }
+                NetworkEvent::UpdateHomunculusInformation {
+                    name,
+                    level,
+                    hunger,
+                    intimacy,
+                    stats,
+                } => {
+                    self.client_state
+                        .follow_mut(client_state().homunculus())
+                        .update_information(name, level, hunger, intimacy, stats);
+                }
+                NetworkEvent::UpdateHomunculusState {
+                    entity_id,
+                    state_type,
+                    value,
+                } => {
+                    self.client_state
+                        .follow_mut(client_state().homunculus())
+                        .update_state(entity_id, state_type, value);
+                }
+                NetworkEvent::HomunculusSkillTree { skill_information } => {
+                    self.client_state
+                        .follow_mut(client_state().homunculus())
+                        .set_skills(skill_information.into_iter().map(LearnedSkill::new).collect());
+                }
+                NetworkEvent::HomunculusFed { success, item_id } => {
+                    if !success {
+                        let name = self
+                            .library
+                            .get::<ItemName>(ItemNameKey {
+                                item_id,
+                                is_identified: true,
+                            })
+                            .to_string();
+
+                        self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                            format!("You need {name} to feed your homunculus."),
+                            MessageColor::Error,
+                        ));
+                    }
+                }
+                NetworkEvent::ChatRoomCreated { result } => {
+                    let message = match result {
+                        CreateChatRoomResult::Success => {
+                            let player_name = self.client_state.follow(client_state().player_name()).clone();
+                            self.client_state.follow_mut(client_state().chat_rooms()).enter_created(player_name);
+
+                            self.interface.close_window_with_class(WindowClass::ChatRoomSettings);
+                            self.interface
+                                .open_window(ChatRoomWindow::new(client_state().chat_rooms().current()));
+                            continue;
+                        }
+                        CreateChatRoomResult::LimitExceeded => "Too many chat rooms were created.",
+                        CreateChatRoomResult::AlreadyExists => "You are already in a chat room.",
+                    };
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new(message.to_owned(), MessageColor::Error));
+                }
+                NetworkEvent::UpdateChatRoom {
+                    owner_entity_id,
+                    chat_room_id,
+                    limit,
+                    user_count,
+                    room_type,
+                    title,
+                } => {
+                    let player_entity_id = self.client_state.try_follow(this_entity()).map(Entity::get_entity_id);
+
+                    self.client_state.follow_mut(client_state().chat_rooms()).update_balloon(
+                        owner_entity_id,
+                        chat_room_id,
+                        limit,
+                        user_count,
+                        room_type,
+                        title,
+                        player_entity_id,
+                    );
+                }
+                NetworkEvent::ChatRoomDestroyed { chat_room_id } => {
+                    self.client_state
+                        .follow_mut(client_state().chat_rooms())
+                        .remove_balloon(chat_room_id);
+                }
+                NetworkEvent::ChatRoomJoined { chat_room_id, members } => {
+                    self.client_state
+                        .follow_mut(client_state().chat_rooms())
+                        .enter_joined(chat_room_id, members);
+
+                    self.interface.close_window_with_class(WindowClass::ChatRoomPassword);
+                    self.interface
+                        .open_window(ChatRoomWindow::new(client_state().chat_rooms().current()));
+                }
+                NetworkEvent::JoinChatRoomFailed { reason } => {
+                    let message = match reason {
+                        JoinChatRoomFailedReason::RoomFull => "The chat room is full.",
+                        JoinChatRoomFailedReason::WrongPassword => "Wrong password.",
+                        JoinChatRoomFailedReason::Kicked => "You were kicked from this chat room.",
+                        JoinChatRoomFailedReason::Success => continue,
+                        JoinChatRoomFailedReason::NotEnoughZeny => "You don't have enough zeny to join this chat room.",
+                        JoinChatRoomFailedReason::LevelTooLow => "Your level is too low to join this chat room.",
+                        JoinChatRoomFailedReason::LevelTooHigh => "Your level is too high to join this chat room.",
+                        JoinChatRoomFailedReason::UnsuitableJob => "Your job can't join this chat room.",
+                    };
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new(message.to_owned(), MessageColor::Error));
+                }
+                NetworkEvent::ChatRoomMemberJoined { name } => {
+                    self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                        format!("{name} joined the chat room."),
+                        MessageColor::Information,
+                    ));
+
+                    self.client_state.follow_mut(client_state().chat_rooms()).add_member(name);
+                }
+                NetworkEvent::ChatRoomMemberLeft { name, was_kicked } => {
+                    let player_name = self.client_state.follow(client_state().player_name());
+
+                    // The server also notifies us when we left the room ourselves.
+                    if name == *player_name {
+                        let message = match was_kicked {
+                            true => "You were kicked from the chat room.",
+                            false => "You left the chat room.",
+                        };
+
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new(message.to_owned(), MessageColor::Information));
+
+                        self.client_state.follow_mut(client_state().chat_rooms()).leave();
+                        self.interface.close_window_with_class(WindowClass::ChatRoom);
+                        self.interface.close_window_with_class(WindowClass::ChatRoomSettings);
+                        continue;
+                    }
+
+                    let message = match was_kicked {
+                        true => format!("{name} was kicked from the chat room."),
+                        false => format!("{name} left the chat room."),
+                    };
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new(message, MessageColor::Information));
+
+                    self.client_state.follow_mut(client_state().chat_rooms()).remove_member(&name);
+                }
+                NetworkEvent::ChatRoomRoleChanged { name, role } => {
+                    self.client_state
+                        .follow_mut(client_state().chat_rooms())
+                        .set_member_role(&name, role);
+                }
             }
         }
 
@@ -1885,6 +2439,15 @@ impl Client {
         self.interface.process_events(&mut self.input_event_buffer);
         let interface_has_focus = self.interface.has_focus();
 
+        // Only allow the input method while the interface is focused, otherwise it
+        // would intercept the key presses used to control the game.
+        if interface_has_focus != self.ime_allowed
+            && let Some(window) = self.window.as_ref()
+        {
+            window.set_ime_allowed(interface_has_focus);
+            self.ime_allowed = interface_has_focus;
+        }
+
         if self.interface.get_mouse_mode().is_rotating_camera() {
             // TODO: Does this really need to be a InputEvent?
             let rotation = input_report.mouse_delta.width;
@@ -1994,6 +2557,16 @@ impl Client {
                         }
                     }
                 }
+                InputEvent::ToggleCartWindow => {
+                    if self.client_state.try_follow(this_entity()).is_some()
+                        && self.client_state.follow(client_state().cart()).is_available()
+                    {
+                        match self.interface.is_window_with_class_open(WindowClass::Cart) {
+                            true => self.interface.close_window_with_class(WindowClass::Cart),
+                            false => self.interface.open_window(CartWindow::new(client_state().cart())),
+                        }
+                    }
+                }
                 InputEvent::ToggleSkillTreeWindow => {
                     if self.client_state.try_follow(this_entity()).is_some() {
                         match self.interface.is_window_with_class_open(WindowClass::SkillTree) {
@@ -2023,10 +2596,25 @@ impl Client {
                 {
                     true => self.interface.close_window_with_class(WindowClass::InterfaceSettings),
                     false => self.interface.open_window(InterfaceSettingsWindow::new(
+                        client_state().interface_settings_window(),
                         client_state().interface_settings(),
                         client_state().interface_settings_capabilities(),
                     )),
                 },
+                InputEvent::SaveWindowLayout { name } => {
+                    let window_cache = self.interface.get_window_cache_mut();
+                    window_cache.save_layout_as(&name);
+                    let window_layouts = window_cache.layout_names();
+
+                    self.client_state
+                        .follow_mut(client_state().interface_settings_capabilities())
+                        .set_window_layouts(window_layouts);
+                    *self.client_state.follow_mut(client_state().interface_settings().window_layout()) = name;
+                }
+                InputEvent::ResetWindowLayout => {
+                    self.interface.get_window_cache_mut().reset_layout();
+                    self.interface.reload_window_states();
+                }
                 InputEvent::ToggleGraphicsSettingsWindow => match self.interface.is_window_with_class_open(WindowClass::GraphicsSettings) {
                     true => self.interface.close_window_with_class(WindowClass::GraphicsSettings),
                     false => self.interface.open_window(GraphicsSettingsWindow::new(
@@ -2051,6 +2639,69 @@ impl Client {
                         }
                     }
                 }
+                InputEvent::ToggleMailboxWindow => {
+                    if self.client_state.try_follow(this_entity()).is_some() {
+                        match self.interface.is_window_with_class_open(WindowClass::Mailbox) {
+                            true => {
+                                let _ = self.networking_system.close_mailbox();
+                                self.interface.close_window_with_class(WindowClass::Mailbox);
+                            }
+                            false => {
+                                self.client_state.follow_mut(client_state().mailbox()).clear_mails();
+                                let _ = self.networking_system.open_mailbox(MailOpenType::Character);
+                                self.interface.open_window(MailboxWindow::new(client_state().mailbox()));
+                            }
+                        }
+                    }
+                }
+                InputEvent::TogglePetWindow => {
+                    if self.client_state.try_follow(this_entity()).is_some() {
+                        match self.interface.is_window_with_class_open(WindowClass::Pet) {
+                            true => self.interface.close_window_with_class(WindowClass::Pet),
+                            false => {
+                                if self.client_state.follow(client_state().pet()).is_hatched() {
+                                    let _ = self.networking_system.pet_command(PetCommand::RequestInformation);
+                                }
+
+                                self.interface.open_window(PetWindow::new(client_state().pet()));
+                            }
+                        }
+                    }
+                }
+                InputEvent::ToggleHomunculusWindow => {
+                    if self.client_state.try_follow(this_entity()).is_some() {
+                        match self.interface.is_window_with_class_open(WindowClass::Homunculus) {
+                            true => self.interface.close_window_with_class(WindowClass::Homunculus),
+                            false => {
+                                if self.client_state.follow(client_state().homunculus()).is_summoned() {
+                                    let _ = self.networking_system.homunculus_command(HomunculusCommand::RequestInformation);
+                                }
+
+                                self.interface.open_window(HomunculusWindow::new(client_state().homunculus()));
+                            }
+                        }
+                    }
+                }
+                InputEvent::ToggleChatRoomWindow => {
+                    if self.client_state.try_follow(this_entity()).is_some() {
+                        match self.client_state.follow(client_state().chat_rooms()).is_joined() {
+                            true => match self.interface.is_window_with_class_open(WindowClass::ChatRoom) {
+                                true => self.interface.close_window_with_class(WindowClass::ChatRoom),
+                                false => self
+                                    .interface
+                                    .open_window(ChatRoomWindow::new(client_state().chat_rooms().current())),
+                            },
+                            false => match self.interface.is_window_with_class_open(WindowClass::ChatRoomSettings) {
+                                true => self.interface.close_window_with_class(WindowClass::ChatRoomSettings),
+                                false => {
+                                    self.client_state.follow_mut(client_state().chat_rooms()).start_draft();
+                                    self.interface
+                                        .open_window(ChatRoomSettingsWindow::new(client_state().chat_rooms().draft()));
+                                }
+                            },
+                        }
+                    }
+                }
                 InputEvent::CloseTopWindow => self.interface.close_top_window(&self.client_state),
                 InputEvent::ToggleShowInterface => self.show_interface = !self.show_interface,
                 InputEvent::SelectCharacter { slot } => {
@@ -2091,6 +2742,56 @@ impl Client {
                     *self.client_state.follow_mut(client_state().buffered_action()) = None;
                 }
                 InputEvent::PlayerInteract { entity_id } => {
+                    if self.client_state.follow(client_state().pet()).is_capturing() {
+                        self.client_state.follow_mut(client_state().pet()).set_capturing(false);
+                        let _ = self.networking_system.try_capture_pet(entity_id);
+                        continue;
+                    }
+
+                    let homunculus = self.client_state.follow_mut(client_state().homunculus());
+
+                    if homunculus.is_selecting_target() {
+                        homunculus.set_selecting_target(false);
+
+                        if let Some(homunculus_entity_id) = homunculus.entity_id()
+                            && homunculus_entity_id != entity_id
+                        {
+                            let _ = self.networking_system.homunculus_attack(homunculus_entity_id, entity_id);
+                        }
+
+                        continue;
+                    }
+
+                    let chat_rooms = self.client_state.follow_mut(client_state().chat_rooms());
+
+                    // Clicking on the owner of a chat room or the balloon above them joins the
+                    // room.
+                    if !chat_rooms.is_joined()
+                        && let Some(balloon) = chat_rooms.owned_by(entity_id)
+                    {
+                        let chat_room_id = balloon.chat_room_id;
+
+                        match balloon.requires_password() {
+                            true => {
+                                chat_rooms.start_join(chat_room_id);
+                                self.interface
+                                    .open_window(ChatRoomPasswordWindow::new(client_state().chat_rooms().join_password()));
+                            }
+                            false => {
+                                let _ = self.networking_system.join_chat_room(chat_room_id, String::new());
+                            }
+                        }
+
+                        continue;
+                    }
+
+                    // Make sure we never attack our own companions.
+                    if self.client_state.follow(client_state().pet()).is_companion(entity_id)
+                        || self.client_state.follow(client_state().homunculus()).is_companion(entity_id)
+                    {
+                        continue;
+                    }
+
                     let entity = self
                         .client_state
                         .follow_mut(client_state().entities())
@@ -2175,6 +2876,11 @@ impl Client {
                         continue;
                     }
 
+                    if let Some((recipient_name, text)) = parse_whisper(&text) {
+                        let _ = self.networking_system.send_whisper(recipient_name.to_owned(), text.to_owned());
+                        continue;
+                    }
+
                     let _ = self
                         .networking_system
                         .send_chat_message(self.client_state.follow(client_state().player_name()), &text);
@@ -2194,6 +2900,199 @@ impl Client {
                         self.interface.close_window_with_class(WindowClass::Dialog);
                     }
                 }
+                InputEvent::OpenItemTooltip { item_id } => {
+                    let name = self
+                        .library
+                        .get::<ItemName>(ItemNameKey {
+                            item_id,
+                            is_identified: true,
+                        })
+                        .to_string();
+                    let texture = self.async_loader.request_item_icon_load(item_id);
+
+                    // Only show one item at a time.
+                    self.interface.close_window_with_class(WindowClass::ItemTooltip);
+                    self.interface.open_window(ItemTooltipWindow::new(item_id, name, texture));
+                }
+                InputEvent::RequestNextMailPage => {
+                    if let Some((open_type, last_mail_id)) = self.client_state.follow(client_state().mailbox()).last_mail() {
+                        let _ = self.networking_system.request_next_mail_page(open_type, last_mail_id);
+                    }
+                }
+                InputEvent::ReadMail { open_type, mail_id } => {
+                    let _ = self.networking_system.read_mail(open_type, mail_id);
+                }
+                InputEvent::DeleteMail { open_type, mail_id } => {
+                    let _ = self.networking_system.delete_mail(open_type, mail_id);
+                }
+                InputEvent::ClaimMailZeny { mail_id } => {
+                    let open_type = self.client_state.follow(client_state().mailbox()).opened_mail_type();
+                    let _ = self.networking_system.claim_mail_zeny(open_type, mail_id);
+                }
+                InputEvent::ClaimMailItems { mail_id } => {
+                    let open_type = self.client_state.follow(client_state().mailbox()).opened_mail_type();
+                    let _ = self.networking_system.claim_mail_items(open_type, mail_id);
+                }
+                InputEvent::WriteMail { recipient_name } => {
+                    let _ = self.networking_system.open_write_mail(recipient_name);
+                }
+                InputEvent::CheckMailRecipient => {
+                    let recipient_name = self.client_state.follow_mut(client_state().mailbox()).check_recipient();
+                    let _ = self.networking_system.check_mail_recipient(recipient_name);
+                }
+                InputEvent::SendMail => {
+                    let draft = self.client_state.follow(client_state().mailbox()).draft();
+
+                    let Some(zeny) = draft.zeny() else {
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new("Invalid amount of zeny.".to_owned(), MessageColor::Error));
+                        continue;
+                    };
+
+                    let Some(recipient_character_id) = draft.recipient_character_id() else {
+                        self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                            "The recipient needs to be checked first.".to_owned(),
+                            MessageColor::Error,
+                        ));
+                        continue;
+                    };
+
+                    let recipient_name = draft.recipient_name().to_owned();
+                    let title = draft.title().to_owned();
+                    let text = draft.text().to_owned();
+                    let sender_name = self.client_state.follow(client_state().player_name()).clone();
+
+                    let _ = self
+                        .networking_system
+                        .send_mail(recipient_name, recipient_character_id, sender_name, title, text, zeny);
+                }
+                InputEvent::CancelWriteMail => {
+                    let _ = self.networking_system.cancel_write_mail();
+                    self.client_state.follow_mut(client_state().mailbox()).clear_draft();
+                    self.interface.close_window_with_class(WindowClass::WriteMail);
+                }
+                InputEvent::HatchPet { index } => {
+                    let _ = self.networking_system.select_pet_egg(index);
+                    self.client_state.follow_mut(client_state().pet()).clear_eggs();
+                    self.interface.close_window_with_class(WindowClass::PetEgg);
+                }
+                InputEvent::PetCommand { command } => {
+                    let _ = self.networking_system.pet_command(command);
+
+                    if command == PetCommand::ReturnToEgg {
+                        self.client_state.follow_mut(client_state().pet()).clear();
+                        self.interface.close_window_with_class(WindowClass::Pet);
+                    }
+                }
+                InputEvent::HomunculusCommand { command } => {
+                    let _ = self.networking_system.homunculus_command(command);
+                }
+                InputEvent::HomunculusFollow => {
+                    if let Some(entity_id) = self.client_state.follow(client_state().homunculus()).entity_id() {
+                        let _ = self.networking_system.homunculus_move_to_owner(entity_id);
+                    }
+                }
+                InputEvent::HomunculusStay => {
+                    if let Some(entity_id) = self.client_state.follow(client_state().homunculus()).entity_id()
+                        && let Some(entity) = self
+                            .client_state
+                            .follow(client_state().entities())
+                            .iter()
+                            .find(|entity| entity.get_entity_id() == entity_id)
+                    {
+                        let position = entity.get_tile_position();
+                        let _ = self.networking_system.homunculus_move(entity_id, WorldPosition {
+                            x: position.x,
+                            y: position.y,
+                            direction: Direction::North,
+                        });
+                    }
+                }
+                InputEvent::HomunculusSelectAttackTarget => {
+                    let homunculus = self.client_state.follow_mut(client_state().homunculus());
+
+                    if homunculus.is_summoned() {
+                        homunculus.set_selecting_target(true);
+
+                        self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                            "Select a target for your homunculus.".to_owned(),
+                            MessageColor::Information,
+                        ));
+                    }
+                }
+                InputEvent::LevelUpHomunculusSkill { skill_id } => {
+                    let _ = self.networking_system.level_up_homunculus_skill(skill_id);
+                }
+                InputEvent::CreateChatRoom | InputEvent::ChangeChatRoom => {
+                    let draft = self.client_state.follow(client_state().chat_rooms()).draft();
+
+                    let Some(limit) = draft.limit() else {
+                        self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                            format!("The member limit must be between 2 and {MAXIMUM_CHAT_ROOM_LIMIT}."),
+                            MessageColor::Error,
+                        ));
+                        continue;
+                    };
+
+                    let title = draft.title().to_owned();
+                    let is_public = draft.is_public();
+                    let password = match is_public {
+                        true => String::new(),
+                        false => draft.password().to_owned(),
+                    };
+
+                    match event {
+                        InputEvent::CreateChatRoom => {
+                            let _ = self.networking_system.create_chat_room(title, limit, is_public, password);
+                        }
+                        _ => {
+                            let _ = self.networking_system.change_chat_room(title, limit, is_public, password);
+                            self.interface.close_window_with_class(WindowClass::ChatRoomSettings);
+                        }
+                    }
+                }
+                InputEvent::OpenChangeChatRoomWindow => {
+                    self.client_state.follow_mut(client_state().chat_rooms()).start_change_draft();
+                    self.interface
+                        .open_window(ChatRoomSettingsWindow::new(client_state().chat_rooms().draft()));
+                }
+                InputEvent::JoinChatRoom => {
+                    let chat_rooms = self.client_state.follow(client_state().chat_rooms());
+
+                    if let Some(chat_room_id) = chat_rooms.join_chat_room_id() {
+                        let password = chat_rooms.join_password().to_owned();
+                        let _ = self.networking_system.join_chat_room(chat_room_id, password);
+                    }
+                }
+                InputEvent::ChangeChatRoomOwner { name } => {
+                    let _ = self.networking_system.change_chat_room_owner(name);
+                }
+                InputEvent::KickChatRoomMember { name } => {
+                    let _ = self.networking_system.kick_chat_room_member(name);
+                }
+                InputEvent::LeaveChatRoom => {
+                    let _ = self.networking_system.leave_chat_room();
+                }
+                InputEvent::UseItem { index } => {
+                    if let Some(login_data) = &self.saved_login_data {
+                        let _ = self.networking_system.use_item(index, login_data.account_id);
+                    }
+                }
+                InputEvent::OpenDropItemWindow { index, amount } => {
+                    self.client_state.follow_mut(client_state().item_drop()).start(index, amount);
+
+                    // Only drop one item at a time.
+                    self.interface.close_window_with_class(WindowClass::DropItem);
+                    self.interface.open_window(DropItemWindow::new(client_state().item_drop()));
+                }
+                InputEvent::ConfirmDropItem => {
+                    if let Some((index, amount)) = self.client_state.follow_mut(client_state().item_drop()).take() {
+                        let _ = self.networking_system.drop_item(index, amount);
+                    }
+
+                    self.interface.close_window_with_class(WindowClass::DropItem);
+                }
                 InputEvent::MoveItem { source, destination, item } => match (source, destination) {
                     (ItemSource::Inventory, ItemSource::Equipment { position }) => {
                         let _ = self.networking_system.request_item_equip(item.index, position);
@@ -2201,6 +3100,44 @@ impl Client {
                     (ItemSource::Equipment { .. }, ItemSource::Inventory) => {
                         let _ = self.networking_system.request_item_unequip(item.index);
                     }
+                    (ItemSource::Inventory, ItemSource::Cart) => {
+                        let amount = match &item.details {
+                            InventoryItemDetails::Regular { amount, .. } => *amount as u32,
+                            InventoryItemDetails::Equippable { .. } => 1,
+                        };
+
+                        let _ = self.networking_system.move_item_to_cart(item.index, amount);
+                    }
+                    (ItemSource::Cart, ItemSource::Inventory) => {
+                        let amount = match &item.details {
+                            InventoryItemDetails::Regular { amount, .. } => *amount as u32,
+                            InventoryItemDetails::Equippable { .. } => 1,
+                        };
+
+                        let _ = self.networking_system.move_item_from_cart(item.index, amount);
+                    }
+                    (ItemSource::Inventory, ItemSource::MailDraft) => {
+                        let amount = match &item.details {
+                            InventoryItemDetails::Regular { amount, .. } => *amount,
+                            InventoryItemDetails::Equippable { .. } => 1,
+                        };
+
+                        let _ = self.networking_system.add_mail_item(item.index, amount);
+                    }
+                    (ItemSource::MailDraft, ItemSource::Inventory) => {
+                        let amount = match &item.details {
+                            InventoryItemDetails::Regular { amount, .. } => *amount,
+                            InventoryItemDetails::Equippable { .. } => 1,
+                        };
+
+                        let _ = self.networking_system.remove_mail_item(item.index, amount);
+                    }
+                    (ItemSource::MailAttachment, ItemSource::Inventory) => {
+                        let mailbox = self.client_state.follow(client_state().mailbox());
+                        let (open_type, mail_id) = (mailbox.opened_mail_type(), mailbox.opened_mail_id());
+
+                        let _ = self.networking_system.claim_mail_items(open_type, mail_id);
+                    }
                     _ => {}
                 },
                 InputEvent::MoveSkill {
@@ -2237,6 +3174,10 @@ impl Client {
                                     learned_skill.skill_id == learnable_skill.skill_id
                                         && learned_skill.skill_level.0 >= learnable_skill.maximum_level.0
                                 })
+                        && !self
+                            .client_state
+                            .follow(client_state().skill_tree())
+                            .is_on_cooldown(learnable_skill.skill_id)
                     {
                         match learned_skill.skill_type {
                             SkillType::Passive => {}
@@ -2307,6 +3248,12 @@ impl Client {
                         let _ = self.networking_system.add_friend(character_name);
                     }
                 }
+                InputEvent::RequestTrade { entity_id } => {
+                    let _ = self.networking_system.request_trade(entity_id);
+                }
+                InputEvent::InviteToParty { character_name } => {
+                    let _ = self.networking_system.invite_to_party(character_name);
+                }
                 InputEvent::RemoveFriend { account_id, character_id } => {
                     let _ = self.networking_system.remove_friend(account_id, character_id);
                 }
@@ -2495,6 +3442,10 @@ impl Client {
                     }
                 }
                 #[cfg(feature = "debug")]
+                InputEvent::SetNetworkConditions { conditions } => self.networking_system.set_network_conditions(conditions),
+                #[cfg(feature = "debug")]
+                InputEvent::SimulateDisconnect => self.networking_system.simulate_disconnect(),
+                #[cfg(feature = "debug")]
                 InputEvent::ToggleCommandsWindow => {
                     if self.map.is_some() {
                         match self.interface.is_window_with_class_open(WindowClass::Commands) {
@@ -2531,6 +3482,14 @@ impl Client {
                     false => self.interface.open_state_window(client_state().cache_statistics()),
                 },
                 #[cfg(feature = "debug")]
+                InputEvent::ToggleNetworkStatisticsWindow => match self.interface.is_window_with_class_open(WindowClass::NetworkStatistics)
+                {
+                    true => self.interface.close_window_with_class(WindowClass::NetworkStatistics),
+                    false => self
+                        .interface
+                        .open_window(NetworkStatisticsWindow::new(client_state().network_statistics_window())),
+                },
+                #[cfg(feature = "debug")]
                 InputEvent::CameraLookAround { offset } => self.debug_camera.look_around(offset),
                 #[cfg(feature = "debug")]
                 InputEvent::CameraMoveForward => self.debug_camera.move_forward(delta_time as f32),
@@ -2592,6 +3551,19 @@ impl Client {
 
                     self.client_state
                         .follow_mut(client_state().inventory())
+                        .update_item_sprite(item_id, texture.clone());
+
+                    self.client_state
+                        .follow_mut(client_state().cart())
+                        .update_item_sprite(item_id, texture.clone());
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .iter_mut()
+                        .for_each(|message| message.update_item_sprite(item_id, texture.clone()));
+
+                    self.client_state
+                        .follow_mut(client_state().mailbox())
                         .update_item_sprite(item_id, texture);
                 }
                 (LoaderId::Map(..), LoadableResource::Map { map, position }) => {
@@ -2707,6 +3679,15 @@ impl Client {
             );
         }
 
+        #[cfg(feature = "debug")]
+        if self.interface.is_window_with_class_open(WindowClass::NetworkStatistics) {
+            profile_block!("update network statistics");
+
+            self.client_state
+                .follow_mut(client_state().network_statistics_window())
+                .update(self.networking_system.statistics());
+        }
+
         // Main map update and render loop
         if let Some(map) = self.map.as_ref() {
             #[cfg(feature = "debug")]
@@ -2771,6 +3752,10 @@ impl Client {
                     .iter_mut()
                     .for_each(|item| item.update(client_tick));
 
+                self.client_state
+                    .follow_mut(client_state().skill_tree())
+                    .update_cooldowns(client_tick);
+
                 // Remove entities that have finished fading out.
                 self.client_state
                     .follow_mut(client_state().entities())
@@ -3201,6 +4186,45 @@ impl Client {
 
                 self.effect_holder.render(&mut self.effect_renderer, current_camera);
 
+                {
+                    #[cfg(feature = "debug")]
+                    profile_block!("render chat room balloons");
+
+                    let entities = self.client_state.follow(client_state().entities());
+                    let world_theme = self.client_state.follow(client_state().world_theme());
+
+                    for balloon in self.client_state.follow(client_state().chat_rooms()).balloons() {
+                        if let Some(entity) = entities.iter().find(|entity| entity.get_entity_id() == balloon.owner_entity_id)
+                            && let Some(instruction) = entity.render_chat_room_balloon(
+                                &self.middle_interface_renderer,
+                                current_camera,
+                                world_theme,
+                                screen_size,
+                                balloon,
+                            )
+                        {
+                            self.picker_rectangle_instructions.push(instruction);
+                        }
+                    }
+                }
+
+                {
+                    #[cfg(feature = "debug")]
+                    profile_block!("render casts");
+
+                    let world_theme = self.client_state.follow(client_state().world_theme());
+
+                    for entity in self.client_state.follow(client_state().entities()) {
+                        entity.render_cast(
+                            &self.middle_interface_renderer,
+                            current_camera,
+                            world_theme,
+                            screen_size,
+                            client_tick,
+                        );
+                    }
+                }
+
                 if let Some(player) = self.client_state.try_follow(this_entity()) {
                     #[cfg(feature = "debug")]
                     profile_block!("render player status");
@@ -3245,6 +4269,10 @@ impl Client {
                                 .any(|item| item.get_entity_id() == entity_id)
                             {
                                 MouseCursorState::HoverItem
+                            } else if self.client_state.follow(client_state().pet()).is_companion(entity_id)
+                                || self.client_state.follow(client_state().homunculus()).is_companion(entity_id)
+                            {
+                                MouseCursorState::Default
                             } else {
                                 self.client_state
                                     .follow(client_state().entities())
@@ -3268,6 +4296,7 @@ impl Client {
                             interface_frame.click(&self.client_state, mouse_button);
                         } else {
                             interface_frame.unfocus();
+                            interface_frame.close_overlay();
 
                             if mouse_button == MouseButton::Left {
                                 match input_report.mouse_target {
@@ -3297,6 +4326,20 @@ impl Client {
                                         self.input_event_buffer.push(InputEvent::OpenMarkerDetails { marker_identifier })
                                     }
                                 }
+                            } else if mouse_button == MouseButton::Right
+                                && let PickerTarget::Entity(entity_id) = input_report.mouse_target
+                                && let Some(character_name) = self
+                                    .client_state
+                                    .follow(client_state().entities())
+                                    .iter()
+                                    // The first entity is always our own player.
+                                    .skip(1)
+                                    .find(|entity| entity.get_entity_id() == entity_id && entity.get_entity_type() == EntityType::Player)
+                                    .and_then(Entity::get_details)
+                                    .cloned()
+                            {
+                                let menu = player_context_menu(&self.client_state, entity_id, character_name);
+                                interface_frame.open_context_menu(&self.client_state, menu, input_report.mouse_position);
                             } else if mouse_button == MouseButton::Right && currently_playing {
                                 #[cfg_attr(feature = "debug", korangar_debug::debug_condition(!render_options.use_debug_camera))]
                                 interface_frame.set_mouse_mode(MouseInputMode::RotateCamera);
@@ -3330,10 +4373,38 @@ impl Client {
                         }
                     }
 
+                    // Navigation input that is not used by the focused element moves the focus or
+                    // activates the focused element.
+                    let mut navigation_handled = false;
+
+                    for input in &input_report.navigation {
+                        navigation_handled |= interface_frame.navigate(&self.client_state, *input);
+                    }
+
+                    if interface_has_focus {
+                        interface_frame.input_keys(&self.client_state, &input_report.keys);
+                        interface_frame.input_composition(&self.client_state, &input_report.composition);
+                    }
+
+                    let ime_cursor_area = interface_frame.get_ime_cursor_area();
+
+                    if ime_cursor_area != self.ime_cursor_area
+                        && let Some((position, size)) = ime_cursor_area
+                        && let Some(window) = self.window.as_ref()
+                    {
+                        window.set_ime_cursor_area(
+                            PhysicalPosition::new(position.left, position.top),
+                            PhysicalSize::new(size.width, size.height),
+                        );
+                    }
+
+                    self.ime_cursor_area = ime_cursor_area;
+
                     // Focus the chat if the interface is not focused, no other element is capturing
-                    // the keyboard input, enter was pressed, and the chat
-                    // window is open.
+                    // the keyboard input, enter was pressed and didn't activate an element, and the
+                    // chat window is open.
                     if (!interface_has_focus || !interface_frame.input_characters(&self.client_state, &input_report.characters))
+                        && !navigation_handled
                         && input_report.characters.contains(&'\x0d')
                         && is_chat_open
                     {
@@ -3392,8 +4463,20 @@ impl Client {
 
                                 if let Some(name) = &entity.get_details() {
                                     let name = name.split('#').next().unwrap();
-                                    self.middle_interface_renderer
-                                        .render_hover_text(name, scaling, input_report.mouse_position);
+
+                                    if self.client_state.follow(client_state().pet()).is_companion(entity_id)
+                                        || self.client_state.follow(client_state().homunculus()).is_companion(entity_id)
+                                    {
+                                        let player_name = self.client_state.follow(client_state().player_name());
+
+                                        // TODO: Don't allocate every frame
+                                        let text = format!("{name} ({player_name})");
+                                        self.middle_interface_renderer
+                                            .render_hover_text(&text, scaling, input_report.mouse_position);
+                                    } else {
+                                        self.middle_interface_renderer
+                                            .render_hover_text(name, scaling, input_report.mouse_position);
+                                    }
                                 }
                             } else if let Some(item) = self
                                 .client_state
@@ -3513,6 +4596,7 @@ impl Client {
                 map_picker_tile_vertex_buffer: Some(map.get_tile_picker_vertex_buffer()),
                 map_picker_tile_index_buffer: Some(map.get_tile_picker_index_buffer()),
                 font_map_texture: Some(self.font_loader.get_font_map()),
+                picker_rectangles: &self.picker_rectangle_instructions,
                 #[cfg(feature = "debug")]
                 render_options,
                 #[cfg(feature = "debug")]
@@ -3611,6 +4695,25 @@ impl Client {
             self.active_graphics_settings.high_quality_interface = graphics_settings.high_quality_interface;
         }
 
+        let audio_settings = self.client_state.follow(client_state().audio_settings());
+
+        if self.active_audio_settings.background_music_volume != audio_settings.background_music_volume {
+            self.audio_engine
+                .set_background_music_volume(audio_settings.background_music_volume);
+            self.active_audio_settings.background_music_volume = audio_settings.background_music_volume;
+        }
+
+        if self.active_audio_settings.sound_effect_volume != audio_settings.sound_effect_volume {
+            self.audio_engine.set_sound_effect_volume(audio_settings.sound_effect_volume);
+            self.active_audio_settings.sound_effect_volume = audio_settings.sound_effect_volume;
+        }
+
+        if self.active_audio_settings.spatial_sound_effect_volume != audio_settings.spatial_sound_effect_volume {
+            self.audio_engine
+                .set_spatial_sound_effect_volume(audio_settings.spatial_sound_effect_volume);
+            self.active_audio_settings.spatial_sound_effect_volume = audio_settings.spatial_sound_effect_volume;
+        }
+
         let language = *self.client_state.follow(client_state().interface_settings().language());
 
         if self.active_interface_settings.language != language {
@@ -3644,6 +4747,22 @@ impl Client {
             *self.client_state.follow_mut(client_state().world_theme()) = theme;
             self.active_interface_settings.world_theme = world_theme;
         }
+
+        let interface_settings = self.client_state.follow(client_state().interface_settings());
+
+        if self.active_interface_settings.window_layout != interface_settings.window_layout {
+            let window_layout = interface_settings.window_layout.clone();
+            self.interface.get_window_cache_mut().switch_layout(&window_layout);
+            self.interface.reload_window_states();
+            self.active_interface_settings.window_layout = window_layout;
+        }
+
+        let reduce_motion = *self.client_state.follow(client_state().interface_settings().reduce_motion());
+
+        if self.active_interface_settings.reduce_motion != reduce_motion {
+            self.interface.set_reduce_motion(reduce_motion);
+            self.active_interface_settings.reduce_motion = reduce_motion;
+        }
     }
 }
 
@@ -3756,9 +4875,14 @@ impl ApplicationHandler for Client {
             WindowEvent::KeyboardInput { event, .. } => {
                 if let PhysicalKey::Code(keycode) = event.physical_key {
                     self.input_system.update_keyboard(keycode, event.state);
+
+                    // Key repeats are buffered as well so holding a key keeps moving the caret.
+                    if event.state.is_pressed() {
+                        self.input_system.buffer_key(keycode);
+                        self.input_system.buffer_navigation_key(keycode);
+                    }
                 }
 
-                // TODO: NHA We should also support IME in the long term (winit::event::Ime)
                 if let Some(text) = event.text
                     && event.state.is_pressed()
                 {
@@ -3767,6 +4891,7 @@ impl ApplicationHandler for Client {
                     }
                 }
             }
+            WindowEvent::Ime(ime) => self.input_system.buffer_ime(ime),
             WindowEvent::RedrawRequested => {
                 self.render_frame(event_loop);
 
@@ -11,7 +11,7 @@ use korangar_interface::prelude::*;
 use korangar_interface::theme::theme;
 use ragnarok_bytes::{ByteReader, ByteWriter, ConversionError, ConversionResult};
 use ragnarok_packets::handler::PacketCallback;
-use ragnarok_packets::{Packet, PacketHeader};
+use ragnarok_packets::{Packet, PacketHeader, PacketLength};
 use rust_state::{DowncastExt, ManuallyAssertExt, Path, RustState, VecIndexExt};
 
 use crate::client_state;
@@ -198,6 +198,10 @@ impl Packet for UnknownPacket {
         unimplemented!()
     }
 
+    fn packet_length() -> PacketLength {
+        unimplemented!()
+    }
+
     fn to_element<App: korangar_interface::application::Application>(
         self_path: impl Path<App, Self>,
         name: String,
@@ -243,6 +247,10 @@ impl Packet for ErrorPacket {
         unimplemented!()
     }
 
+    fn packet_length() -> PacketLength {
+        unimplemented!()
+    }
+
     fn to_element<App: korangar_interface::application::Application>(
         self_path: impl Path<App, Self>,
         name: String,
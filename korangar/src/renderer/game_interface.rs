@@ -248,6 +248,53 @@ impl GameInterfaceRenderer {
         self.render_text(text, position, color, font_size, AlignHorizontal::Center);
     }
 
+    /// Renders text on a background, centered horizontally above the given
+    /// position. Returns the area covered by the balloon.
+    pub fn render_balloon(
+        &self,
+        text: &str,
+        position: ScreenPosition,
+        color: Color,
+        background_color: Color,
+        font_size: FontSize,
+        padding: ScreenSize,
+    ) -> (ScreenPosition, ScreenSize) {
+        let scaling_factor = self.scaling.get_factor();
+        let padding = padding * scaling_factor;
+        let text_size = self.font_loader.layout_text(
+            text,
+            color,
+            self.highlight_color,
+            FontSize(font_size.0 * scaling_factor),
+            1.0,
+            None,
+            None,
+        );
+
+        let balloon_size = ScreenSize {
+            width: text_size.x,
+            height: text_size.y,
+        } + padding * 2.0;
+        let balloon_position = ScreenPosition {
+            left: position.left - balloon_size.width / 2.0,
+            top: position.top - balloon_size.height,
+        };
+
+        self.render_rectangle(balloon_position, balloon_size, background_color);
+        self.render_text(
+            text,
+            ScreenPosition {
+                left: position.left,
+                top: balloon_position.top + padding.height,
+            },
+            color,
+            font_size,
+            AlignHorizontal::Center,
+        );
+
+        (balloon_position, balloon_size)
+    }
+
     pub fn render_bar(&self, position: ScreenPosition, size: ScreenSize, color: Color, maximum: f32, current: f32) {
         let bar_offset = ScreenSize::only_width(size.width / 2.0);
         let bar_size = ScreenSize {
@@ -8,11 +8,30 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Serialize, Deserialize, RustState, StateElement)]
 pub struct AudioSettings {
     pub mute_on_focus_loss: bool,
+    #[serde(default = "default_background_music_volume")]
+    pub background_music_volume: f32,
+    #[serde(default = "default_sound_effect_volume")]
+    pub sound_effect_volume: f32,
+    #[serde(default = "default_sound_effect_volume")]
+    pub spatial_sound_effect_volume: f32,
+}
+
+fn default_background_music_volume() -> f32 {
+    0.1
+}
+
+fn default_sound_effect_volume() -> f32 {
+    1.0
 }
 
 impl Default for AudioSettings {
     fn default() -> Self {
-        Self { mute_on_focus_loss: true }
+        Self {
+            mute_on_focus_loss: true,
+            background_music_volume: default_background_music_volume(),
+            sound_effect_volume: default_sound_effect_volume(),
+            spatial_sound_effect_volume: default_sound_effect_volume(),
+        }
     }
 }
 
@@ -5,6 +5,7 @@ use ron::ser::PrettyConfig;
 use rust_state::RustState;
 use serde::{Deserialize, Serialize};
 
+use crate::interface::windows::DEFAULT_WINDOW_LAYOUT_NAME;
 use crate::loaders::Scaling;
 use crate::state::localization::Language;
 
@@ -24,6 +25,16 @@ pub struct InterfaceSettings {
     pub menu_theme: String,
     pub in_game_theme: String,
     pub world_theme: String,
+    /// Name of the active window layout.
+    #[serde(default = "default_window_layout")]
+    pub window_layout: String,
+    /// Disables all interface animations.
+    #[serde(default)]
+    pub reduce_motion: bool,
+}
+
+fn default_window_layout() -> String {
+    DEFAULT_WINDOW_LAYOUT_NAME.to_string()
 }
 
 impl Default for InterfaceSettings {
@@ -34,6 +45,8 @@ impl Default for InterfaceSettings {
             menu_theme: DEFAULT_THEME_NAME.to_string(),
             in_game_theme: DEFAULT_THEME_NAME.to_string(),
             world_theme: DEFAULT_THEME_NAME.to_string(),
+            window_layout: default_window_layout(),
+            reduce_motion: false,
         }
     }
 }
@@ -89,9 +102,16 @@ pub struct InterfaceSettingsCapabilities {
     menu_themes: Vec<String>,
     in_game_themes: Vec<String>,
     world_themes: Vec<String>,
+    window_layouts: Vec<String>,
 }
 
 impl InterfaceSettingsCapabilities {
+    /// Set the names of the available window layouts. Those are stored in the
+    /// window cache and therefore not known when creating the capabilities.
+    pub fn set_window_layouts(&mut self, window_layouts: Vec<String>) {
+        self.window_layouts = window_layouts;
+    }
+
     fn load_themes(directory: &str) -> Vec<String> {
         let mut themes = vec![DEFAULT_THEME_NAME.to_string()];
 
@@ -136,6 +156,7 @@ impl Default for InterfaceSettingsCapabilities {
             menu_themes: Self::load_themes(MENU_THEMES_PATH),
             in_game_themes: Self::load_themes(IN_GAME_THEMES_PATH),
             world_themes: Self::load_themes(WORLD_THEMES_PATH),
+            window_layouts: vec![DEFAULT_WINDOW_LAYOUT_NAME.to_string()],
         }
     }
 }
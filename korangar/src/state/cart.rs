@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use korangar_interface::element::StateElement;
+use korangar_networking::{InventoryItem, NoMetadata};
+use ragnarok_packets::{InventoryIndex, ItemId};
+use rust_state::RustState;
+
+use crate::graphics::Texture;
+use crate::loaders::AsyncLoader;
+use crate::state::inventory::Inventory;
+
+/// Cart of a merchant character. The cart uses the same item logic as the
+/// player inventory.
+#[derive(Default, RustState, StateElement)]
+pub struct Cart {
+    /// Set once the server sends information about the cart and unset if the
+    /// cart gets removed.
+    is_available: bool,
+    /// Items in the cart.
+    inventory: Inventory,
+    /// Number of items currently in the cart.
+    item_count: u32,
+    /// Maximum number of items the cart can hold.
+    maximum_item_count: u32,
+    /// Current weight of the cart.
+    weight: u32,
+    /// Maximum weight of the cart.
+    maximum_weight: u32,
+}
+
+impl Cart {
+    pub fn is_available(&self) -> bool {
+        self.is_available
+    }
+
+    pub fn fill(&mut self, async_loader: &AsyncLoader, items: Vec<InventoryItem<NoMetadata>>) {
+        self.is_available = true;
+        self.inventory.fill(async_loader, items);
+    }
+
+    pub fn add_item(&mut self, async_loader: &AsyncLoader, item: InventoryItem<NoMetadata>) {
+        self.inventory.add_item(async_loader, item);
+    }
+
+    pub fn remove_item(&mut self, index: InventoryIndex, remove_amount: u32) {
+        // Stacks can't hold more than `u16::MAX` items, so removing more than that
+        // removes the entire stack.
+        let remove_amount = u16::try_from(remove_amount).unwrap_or(u16::MAX);
+        self.inventory.remove_item(index, remove_amount);
+    }
+
+    pub fn update_item_sprite(&mut self, item_id: ItemId, texture: Arc<Texture>) {
+        self.inventory.update_item_sprite(item_id, texture);
+    }
+
+    pub fn update_information(&mut self, item_count: u16, maximum_item_count: u16, weight: u32, maximum_weight: u32) {
+        self.is_available = true;
+        self.item_count = item_count as u32;
+        self.maximum_item_count = maximum_item_count as u32;
+        self.weight = weight;
+        self.maximum_weight = maximum_weight;
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use korangar_networking::{InventoryItem, InventoryItemDetails, NoMetadata};
+    use ragnarok_packets::{EquipPosition, InventoryIndex, ItemId, RegularItemFlags};
+
+    use crate::state::cart::Cart;
+    use crate::world::ResourceMetadata;
+
+    fn regular_item(index: u16, amount: u16) -> InventoryItem<NoMetadata> {
+        InventoryItem {
+            metadata: NoMetadata,
+            index: InventoryIndex(index),
+            item_id: ItemId(501),
+            item_type: 0,
+            slot: [0; 4],
+            hire_expiration_date: 0,
+            details: InventoryItemDetails::Regular {
+                amount,
+                equipped_position: EquipPosition::empty(),
+                flags: RegularItemFlags::IDENTIFIED,
+            },
+        }
+    }
+
+    fn load_metadata(item: InventoryItem<NoMetadata>) -> InventoryItem<ResourceMetadata> {
+        InventoryItem {
+            metadata: ResourceMetadata {
+                texture: None,
+                name: "Red Potion".to_owned(),
+            },
+            ..item
+        }
+    }
+
+    fn amounts(cart: &Cart) -> Vec<(u16, u16)> {
+        cart.inventory
+            .get_items()
+            .iter()
+            .map(|item| match item.details {
+                InventoryItemDetails::Regular { amount, .. } => (item.index.0, amount),
+                InventoryItemDetails::Equippable { .. } => (item.index.0, 1),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn add_item_stacks_same_index() {
+        let mut cart = Cart::default();
+
+        cart.inventory.add_item_with(regular_item(0, 5), load_metadata);
+        cart.inventory.add_item_with(regular_item(1, 2), load_metadata);
+        cart.inventory
+            .add_item_with(regular_item(0, 3), |_| panic!("stacked items should not be loaded"));
+
+        assert_eq!(amounts(&cart), [(0, 8), (1, 2)]);
+    }
+
+    #[test]
+    fn remove_part_of_stack() {
+        let mut cart = Cart::default();
+
+        cart.inventory.add_item_with(regular_item(0, 5), load_metadata);
+        cart.remove_item(InventoryIndex(0), 2);
+
+        assert_eq!(amounts(&cart), [(0, 3)]);
+    }
+
+    #[test]
+    fn remove_entire_stack() {
+        let mut cart = Cart::default();
+
+        cart.inventory.add_item_with(regular_item(0, 5), load_metadata);
+        cart.inventory.add_item_with(regular_item(1, 2), load_metadata);
+        cart.remove_item(InventoryIndex(0), 5);
+
+        assert_eq!(amounts(&cart), [(1, 2)]);
+    }
+
+    #[test]
+    fn remove_more_than_stack_limit() {
+        let mut cart = Cart::default();
+
+        cart.inventory.add_item_with(regular_item(0, u16::MAX), load_metadata);
+        // Truncating this to `u16` would only remove a single item.
+        cart.remove_item(InventoryIndex(0), u16::MAX as u32 + 2);
+
+        assert!(amounts(&cart).is_empty());
+    }
+
+    #[test]
+    fn update_information_makes_cart_available() {
+        let mut cart = Cart::default();
+
+        assert!(!cart.is_available());
+
+        cart.update_information(3, 100, 250, 8000);
+
+        assert!(cart.is_available());
+        assert_eq!(cart.item_count, 3);
+        assert_eq!(cart.maximum_item_count, 100);
+        assert_eq!(cart.weight, 250);
+        assert_eq!(cart.maximum_weight, 8000);
+
+        cart.clear();
+
+        assert!(!cart.is_available());
+    }
+}
@@ -0,0 +1,440 @@
+use korangar_interface::element::StateElement;
+use ragnarok_packets::{ChatRoomId, ChatRoomMember, ChatRoomRole, ChatRoomType, EntityId};
+use rust_state::RustState;
+
+/// The maximum number of members a chat room can have.
+pub const MAXIMUM_CHAT_ROOM_LIMIT: u16 = 20;
+
+/// Chat room that is visible on the map. The title is displayed in a balloon
+/// above the owner.
+pub struct ChatRoomBalloon {
+    pub owner_entity_id: EntityId,
+    pub chat_room_id: ChatRoomId,
+    pub limit: u16,
+    pub room_type: ChatRoomType,
+    pub title: String,
+    /// Text displayed inside the balloon. Formatted once when the room is
+    /// updated so we don't allocate every frame.
+    pub text: String,
+}
+
+impl ChatRoomBalloon {
+    pub fn requires_password(&self) -> bool {
+        matches!(self.room_type, ChatRoomType::Private | ChatRoomType::NpcPrivate)
+    }
+}
+
+/// Chat room that the player is currently in.
+#[derive(Default, RustState, StateElement)]
+pub struct ChatRoom {
+    /// Id of the chat room. When creating a room, the server only sends the
+    /// id after the room appeared on the map, so this might not be set.
+    chat_room_id: Option<ChatRoomId>,
+    /// Title of the chat room.
+    title: String,
+    /// Maximum number of members.
+    limit: u16,
+    /// Members of the chat room, including the player.
+    members: Vec<ChatRoomMember>,
+}
+
+impl ChatRoom {
+    pub fn members(&self) -> &[ChatRoomMember] {
+        &self.members
+    }
+}
+
+/// Settings of a chat room that is being created or changed.
+#[derive(Default, RustState, StateElement)]
+pub struct ChatRoomDraft {
+    /// Title of the chat room.
+    title: String,
+    /// Maximum number of members as text, so it can be edited in a text box.
+    limit: String,
+    /// Password of the chat room. Only used if the room is private.
+    password: String,
+    /// Set if anyone can join the room without a password.
+    is_public: bool,
+}
+
+impl ChatRoomDraft {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.is_public
+    }
+
+    /// Parse the member limit. Returns `None` if the limit is not a number or
+    /// out of range.
+    pub fn limit(&self) -> Option<u16> {
+        self.limit
+            .trim()
+            .parse()
+            .ok()
+            .filter(|limit| (2..=MAXIMUM_CHAT_ROOM_LIMIT).contains(limit))
+    }
+}
+
+/// Chat rooms visible to the player and the chat room the player is in.
+#[derive(Default, RustState, StateElement)]
+pub struct ChatRooms {
+    /// All chat rooms on the map that we know about.
+    #[hidden_element]
+    balloons: Vec<ChatRoomBalloon>,
+    /// Set while the player is inside a chat room.
+    is_joined: bool,
+    /// Chat room that the player is in.
+    current: ChatRoom,
+    /// Settings for creating or changing a chat room.
+    draft: ChatRoomDraft,
+    /// Chat room that the player tries to join with a password.
+    #[hidden_element]
+    join_chat_room_id: Option<ChatRoomId>,
+    /// Password entered by the player to join a private chat room.
+    join_password: String,
+}
+
+impl ChatRooms {
+    pub fn balloons(&self) -> &[ChatRoomBalloon] {
+        &self.balloons
+    }
+
+    /// Get the chat room that is owned by an entity.
+    pub fn owned_by(&self, entity_id: EntityId) -> Option<&ChatRoomBalloon> {
+        self.balloons.iter().find(|balloon| balloon.owner_entity_id == entity_id)
+    }
+
+    pub fn update_balloon(
+        &mut self,
+        owner_entity_id: EntityId,
+        chat_room_id: ChatRoomId,
+        limit: u16,
+        user_count: u16,
+        room_type: ChatRoomType,
+        title: String,
+        player_entity_id: Option<EntityId>,
+    ) {
+        let text = format!("{title} ({user_count}/{limit})");
+
+        // The server does not tell us the id of a room we created, so we
+        // take it from the first update of a room that we own.
+        if self.is_joined && self.current.chat_room_id.is_none() && player_entity_id == Some(owner_entity_id) {
+            self.current.chat_room_id = Some(chat_room_id);
+        }
+
+        if self.is_joined && self.current.chat_room_id == Some(chat_room_id) {
+            self.current.title = title.clone();
+            self.current.limit = limit;
+        }
+
+        let balloon = ChatRoomBalloon {
+            owner_entity_id,
+            chat_room_id,
+            limit,
+            room_type,
+            title,
+            text,
+        };
+
+        match self.balloons.iter_mut().find(|balloon| balloon.chat_room_id == chat_room_id) {
+            Some(known_balloon) => *known_balloon = balloon,
+            None => self.balloons.push(balloon),
+        }
+    }
+
+    pub fn remove_balloon(&mut self, chat_room_id: ChatRoomId) {
+        self.balloons.retain(|balloon| balloon.chat_room_id != chat_room_id);
+    }
+
+    pub fn clear_balloons(&mut self) {
+        self.balloons.clear();
+    }
+
+    pub fn is_joined(&self) -> bool {
+        self.is_joined
+    }
+
+    pub fn current(&self) -> &ChatRoom {
+        &self.current
+    }
+
+    /// Returns `true` if the player is the owner of the current chat room.
+    pub fn is_owner(&self, player_name: &str) -> bool {
+        self.current
+            .members
+            .iter()
+            .any(|member| member.role == ChatRoomRole::Owner && member.name == player_name)
+    }
+
+    /// Enter a chat room that was just created by the player.
+    pub fn enter_created(&mut self, player_name: String) {
+        let limit = self.draft.limit().unwrap_or(MAXIMUM_CHAT_ROOM_LIMIT);
+
+        self.is_joined = true;
+        self.current = ChatRoom {
+            chat_room_id: None,
+            title: self.draft.title.clone(),
+            limit,
+            members: vec![ChatRoomMember {
+                role: ChatRoomRole::Owner,
+                name: player_name,
+            }],
+        };
+    }
+
+    /// Enter a chat room that the player joined.
+    pub fn enter_joined(&mut self, chat_room_id: ChatRoomId, members: Vec<ChatRoomMember>) {
+        self.is_joined = true;
+        self.join_chat_room_id = None;
+        self.join_password.clear();
+
+        // The title and limit are not part of the join packet, so we take them from
+        // the balloon.
+        let (title, limit) = self
+            .balloons
+            .iter()
+            .find(|balloon| balloon.chat_room_id == chat_room_id)
+            .map(|balloon| (balloon.title.clone(), balloon.limit))
+            .unwrap_or_else(|| (String::new(), MAXIMUM_CHAT_ROOM_LIMIT));
+
+        self.current = ChatRoom {
+            chat_room_id: Some(chat_room_id),
+            title,
+            limit,
+            members,
+        };
+    }
+
+    pub fn leave(&mut self) {
+        self.is_joined = false;
+        self.current = ChatRoom::default();
+    }
+
+    pub fn add_member(&mut self, name: String) {
+        self.current.members.push(ChatRoomMember {
+            role: ChatRoomRole::Member,
+            name,
+        });
+    }
+
+    pub fn remove_member(&mut self, name: &str) {
+        self.current.members.retain(|member| member.name != name);
+    }
+
+    pub fn set_member_role(&mut self, name: &str, role: ChatRoomRole) {
+        if let Some(member) = self.current.members.iter_mut().find(|member| member.name == name) {
+            member.role = role;
+        }
+    }
+
+    pub fn draft(&self) -> &ChatRoomDraft {
+        &self.draft
+    }
+
+    /// Start creating a new chat room, discarding the previous draft.
+    pub fn start_draft(&mut self) {
+        self.draft = ChatRoomDraft {
+            limit: MAXIMUM_CHAT_ROOM_LIMIT.to_string(),
+            is_public: true,
+            ..Default::default()
+        };
+    }
+
+    /// Start changing the settings of the current chat room.
+    pub fn start_change_draft(&mut self) {
+        let is_public = self
+            .current
+            .chat_room_id
+            .and_then(|chat_room_id| self.balloons.iter().find(|balloon| balloon.chat_room_id == chat_room_id))
+            .is_none_or(|balloon| !balloon.requires_password());
+
+        self.draft = ChatRoomDraft {
+            title: self.current.title.clone(),
+            limit: self.current.limit.to_string(),
+            password: String::new(),
+            is_public,
+        };
+    }
+
+    pub fn join_chat_room_id(&self) -> Option<ChatRoomId> {
+        self.join_chat_room_id
+    }
+
+    pub fn join_password(&self) -> &str {
+        &self.join_password
+    }
+
+    /// Remember the chat room that the player wants to join while the password
+    /// is entered.
+    pub fn start_join(&mut self, chat_room_id: ChatRoomId) {
+        self.join_chat_room_id = Some(chat_room_id);
+        self.join_password.clear();
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ragnarok_packets::{ChatRoomId, ChatRoomMember, ChatRoomRole, ChatRoomType, EntityId};
+
+    use crate::state::chat_room::{ChatRoomDraft, ChatRooms, MAXIMUM_CHAT_ROOM_LIMIT};
+
+    fn draft_with_limit(limit: &str) -> ChatRoomDraft {
+        ChatRoomDraft {
+            limit: limit.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn draft_limit() {
+        assert_eq!(draft_with_limit("5").limit(), Some(5));
+        assert_eq!(draft_with_limit(" 20 ").limit(), Some(MAXIMUM_CHAT_ROOM_LIMIT));
+        assert_eq!(draft_with_limit("1").limit(), None);
+        assert_eq!(draft_with_limit("21").limit(), None);
+        assert_eq!(draft_with_limit("many").limit(), None);
+    }
+
+    #[test]
+    fn update_balloon_replaces_known_room() {
+        let mut chat_rooms = ChatRooms::default();
+
+        chat_rooms.update_balloon(
+            EntityId(1),
+            ChatRoomId(5),
+            10,
+            1,
+            ChatRoomType::Public,
+            "Trade".to_owned(),
+            None,
+        );
+        chat_rooms.update_balloon(
+            EntityId(1),
+            ChatRoomId(5),
+            10,
+            3,
+            ChatRoomType::Private,
+            "Trade".to_owned(),
+            None,
+        );
+
+        assert_eq!(chat_rooms.balloons().len(), 1);
+        assert_eq!(chat_rooms.balloons()[0].text, "Trade (3/10)");
+        assert!(chat_rooms.owned_by(EntityId(1)).unwrap().requires_password());
+
+        chat_rooms.remove_balloon(ChatRoomId(5));
+
+        assert!(chat_rooms.owned_by(EntityId(1)).is_none());
+    }
+
+    #[test]
+    fn created_room_takes_id_from_own_balloon() {
+        let mut chat_rooms = ChatRooms::default();
+
+        chat_rooms.start_draft();
+        chat_rooms.draft.title = "Trade".to_owned();
+        chat_rooms.draft.limit = "8".to_owned();
+        chat_rooms.enter_created("Alice".to_owned());
+
+        assert!(chat_rooms.is_joined());
+        assert!(chat_rooms.is_owner("Alice"));
+        assert_eq!(chat_rooms.current().limit, 8);
+
+        // Rooms of other players don't change the current room.
+        chat_rooms.update_balloon(
+            EntityId(2),
+            ChatRoomId(4),
+            20,
+            1,
+            ChatRoomType::Public,
+            "Other".to_owned(),
+            Some(EntityId(1)),
+        );
+        assert_eq!(chat_rooms.current().chat_room_id, None);
+
+        chat_rooms.update_balloon(
+            EntityId(1),
+            ChatRoomId(5),
+            8,
+            1,
+            ChatRoomType::Public,
+            "Trade".to_owned(),
+            Some(EntityId(1)),
+        );
+        assert_eq!(chat_rooms.current().chat_room_id, Some(ChatRoomId(5)));
+        assert_eq!(chat_rooms.current().title, "Trade");
+    }
+
+    #[test]
+    fn enter_joined_takes_title_from_balloon() {
+        let mut chat_rooms = ChatRooms::default();
+
+        chat_rooms.update_balloon(
+            EntityId(2),
+            ChatRoomId(4),
+            6,
+            1,
+            ChatRoomType::Private,
+            "Secret".to_owned(),
+            None,
+        );
+        chat_rooms.start_join(ChatRoomId(4));
+        chat_rooms.join_password = "1234".to_owned();
+        chat_rooms.enter_joined(ChatRoomId(4), vec![
+            ChatRoomMember {
+                role: ChatRoomRole::Owner,
+                name: "Bob".to_owned(),
+            },
+            ChatRoomMember {
+                role: ChatRoomRole::Member,
+                name: "Alice".to_owned(),
+            },
+        ]);
+
+        assert_eq!(chat_rooms.current().title, "Secret");
+        assert_eq!(chat_rooms.current().limit, 6);
+        assert_eq!(chat_rooms.join_chat_room_id(), None);
+        assert!(chat_rooms.join_password().is_empty());
+        assert!(!chat_rooms.is_owner("Alice"));
+
+        // Changing the settings keeps the room private.
+        chat_rooms.start_change_draft();
+
+        assert_eq!(chat_rooms.draft().title(), "Secret");
+        assert_eq!(chat_rooms.draft().limit(), Some(6));
+        assert!(!chat_rooms.draft().is_public());
+    }
+
+    #[test]
+    fn update_members() {
+        let mut chat_rooms = ChatRooms::default();
+
+        chat_rooms.start_draft();
+        chat_rooms.enter_created("Alice".to_owned());
+        chat_rooms.add_member("Bob".to_owned());
+        chat_rooms.set_member_role("Alice", ChatRoomRole::Member);
+        chat_rooms.set_member_role("Bob", ChatRoomRole::Owner);
+
+        assert!(chat_rooms.is_owner("Bob"));
+        assert!(!chat_rooms.is_owner("Alice"));
+
+        chat_rooms.remove_member("Alice");
+
+        let names: Vec<&str> = chat_rooms.current().members().iter().map(|member| member.name.as_str()).collect();
+        assert_eq!(names, ["Bob"]);
+
+        chat_rooms.leave();
+
+        assert!(!chat_rooms.is_joined());
+        assert!(chat_rooms.current().members().is_empty());
+    }
+}
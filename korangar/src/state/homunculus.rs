@@ -0,0 +1,185 @@
+use korangar_interface::element::StateElement;
+use ragnarok_packets::{EntityId, HomunculusStateType, HomunculusStats};
+use rust_state::RustState;
+
+use crate::state::skills::LearnedSkill;
+
+/// Maximum hunger of a homunculus.
+pub const MAXIMUM_HOMUNCULUS_HUNGER: u32 = 100;
+/// Maximum intimacy of a homunculus.
+pub const MAXIMUM_HOMUNCULUS_INTIMACY: u32 = 1000;
+
+/// Homunculus of the player.
+#[derive(RustState, StateElement)]
+pub struct Homunculus {
+    /// Entity id of the homunculus. Only set while the homunculus is
+    /// summoned.
+    entity_id: Option<EntityId>,
+    /// Name of the homunculus.
+    name: String,
+    /// Level of the homunculus.
+    level: u32,
+    /// Hunger of the homunculus, ranging from 0 (starving) to
+    /// [`MAXIMUM_HOMUNCULUS_HUNGER`].
+    hunger: u32,
+    /// Intimacy of the homunculus, ranging from 0 (hate) to
+    /// [`MAXIMUM_HOMUNCULUS_INTIMACY`].
+    intimacy: u32,
+    /// Combat stats of the homunculus.
+    stats: HomunculusStats,
+    /// Skills learned by the homunculus.
+    skills: Vec<LearnedSkill>,
+    /// Set after the player asked the homunculus to attack until a target
+    /// was selected.
+    is_selecting_target: bool,
+}
+
+impl Default for Homunculus {
+    fn default() -> Self {
+        Self {
+            entity_id: None,
+            name: String::new(),
+            level: 0,
+            hunger: 0,
+            intimacy: 0,
+            stats: HomunculusStats {
+                attack: 0,
+                magic_attack: 0,
+                hit: 0,
+                critical: 0,
+                defense: 0,
+                magic_defense: 0,
+                flee: 0,
+                attack_speed: 0,
+                health_points: 0,
+                maximum_health_points: 0,
+                spell_points: 0,
+                maximum_spell_points: 0,
+                experience: 0,
+                next_level_experience: 0,
+                skill_points: 0,
+                attack_range: 0,
+            },
+            skills: Vec::new(),
+            is_selecting_target: false,
+        }
+    }
+}
+
+impl Homunculus {
+    pub fn entity_id(&self) -> Option<EntityId> {
+        self.entity_id
+    }
+
+    pub fn is_summoned(&self) -> bool {
+        self.entity_id.is_some()
+    }
+
+    pub fn is_companion(&self, entity_id: EntityId) -> bool {
+        self.entity_id == Some(entity_id)
+    }
+
+    pub fn update_information(&mut self, name: String, level: u16, hunger: u16, intimacy: u16, stats: HomunculusStats) {
+        self.name = name;
+        self.level = level as u32;
+        self.hunger = hunger as u32;
+        self.intimacy = intimacy as u32;
+        self.stats = stats;
+    }
+
+    pub fn update_state(&mut self, entity_id: EntityId, state_type: HomunculusStateType, value: i32) {
+        match state_type {
+            HomunculusStateType::Initialize => self.entity_id = Some(entity_id),
+            HomunculusStateType::Intimacy => self.intimacy = value.max(0) as u32,
+            HomunculusStateType::Hunger => self.hunger = value.max(0) as u32,
+        }
+    }
+
+    pub fn set_skills(&mut self, skills: Vec<LearnedSkill>) {
+        self.skills = skills;
+    }
+
+    pub fn is_selecting_target(&self) -> bool {
+        self.is_selecting_target
+    }
+
+    pub fn set_selecting_target(&mut self, is_selecting_target: bool) {
+        self.is_selecting_target = is_selecting_target;
+    }
+
+    /// Called when the homunculus entity disappears from the map.
+    pub fn dismiss(&mut self) {
+        self.entity_id = None;
+        self.is_selecting_target = false;
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ragnarok_packets::{EntityId, HomunculusStateType};
+
+    use crate::state::homunculus::Homunculus;
+
+    #[test]
+    fn initialize_summons_homunculus() {
+        let mut homunculus = Homunculus::default();
+
+        assert!(!homunculus.is_summoned());
+
+        homunculus.update_state(EntityId(9), HomunculusStateType::Initialize, 0);
+
+        assert!(homunculus.is_summoned());
+        assert!(homunculus.is_companion(EntityId(9)));
+        assert!(!homunculus.is_companion(EntityId(10)));
+    }
+
+    #[test]
+    fn update_information() {
+        let mut homunculus = Homunculus::default();
+        let mut stats = Homunculus::default().stats;
+        stats.attack = 120;
+        stats.maximum_health_points = 900;
+
+        homunculus.update_information("Lif".to_owned(), 25, 60, 400, stats);
+
+        assert_eq!(homunculus.name, "Lif");
+        assert_eq!(homunculus.level, 25);
+        assert_eq!(homunculus.hunger, 60);
+        assert_eq!(homunculus.intimacy, 400);
+        assert_eq!(homunculus.stats.attack, 120);
+        assert_eq!(homunculus.stats.maximum_health_points, 900);
+    }
+
+    #[test]
+    fn update_state_clamps_negative_values() {
+        let mut homunculus = Homunculus::default();
+
+        homunculus.update_state(EntityId(9), HomunculusStateType::Hunger, -5);
+        homunculus.update_state(EntityId(9), HomunculusStateType::Intimacy, 700);
+
+        assert_eq!(homunculus.hunger, 0);
+        assert_eq!(homunculus.intimacy, 700);
+    }
+
+    #[test]
+    fn dismiss_keeps_information() {
+        let mut homunculus = Homunculus::default();
+
+        homunculus.update_state(EntityId(9), HomunculusStateType::Initialize, 0);
+        homunculus.update_state(EntityId(9), HomunculusStateType::Hunger, 50);
+        homunculus.set_selecting_target(true);
+        homunculus.dismiss();
+
+        assert!(!homunculus.is_summoned());
+        assert!(!homunculus.is_selecting_target());
+        assert_eq!(homunculus.hunger, 50);
+
+        homunculus.clear();
+
+        assert_eq!(homunculus.hunger, 0);
+    }
+}
@@ -25,6 +25,16 @@ impl Inventory {
     }
 
     pub fn add_item(&mut self, async_loader: &AsyncLoader, item: InventoryItem<NoMetadata>) {
+        self.add_item_with(item, |item| async_loader.request_inventory_item_metadata_load(item));
+    }
+
+    /// Add an item, stacking it onto an item with the same index if there is
+    /// one. The metadata is only loaded for items that are not stacked.
+    pub(super) fn add_item_with(
+        &mut self,
+        item: InventoryItem<NoMetadata>,
+        load_metadata: impl FnOnce(InventoryItem<NoMetadata>) -> InventoryItem<ResourceMetadata>,
+    ) {
         if let Some(found_item) = self.items.iter_mut().find(|inventory_item| inventory_item.index == item.index) {
             let InventoryItemDetails::Regular { amount, .. } = &mut found_item.details else {
                 panic!();
@@ -36,12 +46,14 @@ impl Inventory {
 
             *amount += added_amount;
         } else {
-            let item = async_loader.request_inventory_item_metadata_load(item);
-
-            self.items.push(item);
+            self.items.push(load_metadata(item));
         }
     }
 
+    pub fn get_items(&self) -> &[InventoryItem<ResourceMetadata>] {
+        &self.items
+    }
+
     pub fn update_item_sprite(&mut self, item_id: ItemId, texture: Arc<Texture>) {
         self.items.iter_mut().filter(|item| item.item_id == item_id).for_each(|item| {
             item.metadata.texture = Some(texture.clone());
@@ -76,3 +88,53 @@ impl Inventory {
         *equipped_position = new_equipped_position;
     }
 }
+
+/// Item that the player is about to drop while the amount is being selected.
+#[derive(Default, RustState, StateElement)]
+pub struct ItemDrop {
+    /// Inventory index of the item to drop.
+    #[hidden_element]
+    index: Option<InventoryIndex>,
+    /// Amount of the item to drop.
+    amount: u16,
+    /// Amount of the item in the inventory.
+    maximum_amount: u16,
+}
+
+impl ItemDrop {
+    /// Remember the item that the player wants to drop while the amount is
+    /// selected. Starts out with a single item so a stack is never dropped by
+    /// accident.
+    pub fn start(&mut self, index: InventoryIndex, maximum_amount: u16) {
+        self.index = Some(index);
+        self.amount = 1;
+        self.maximum_amount = maximum_amount;
+    }
+
+    /// Index and amount of the item to drop, if there is one.
+    pub fn take(&mut self) -> Option<(InventoryIndex, u16)> {
+        let index = self.index.take()?;
+        Some((index, self.amount.min(self.maximum_amount)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ragnarok_packets::InventoryIndex;
+
+    use crate::state::inventory::ItemDrop;
+
+    #[test]
+    fn item_drop() {
+        let mut item_drop = ItemDrop::default();
+
+        assert_eq!(item_drop.take(), None);
+
+        item_drop.start(InventoryIndex(3), 10);
+        assert_eq!(item_drop.amount, 1);
+
+        item_drop.amount = 25;
+        assert_eq!(item_drop.take(), Some((InventoryIndex(3), 10)));
+        assert_eq!(item_drop.take(), None);
+    }
+}
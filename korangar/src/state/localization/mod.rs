@@ -161,6 +161,9 @@ pub struct Localization {
     chat_text_box_message: String,
     audio_settings_window_title: String,
     mute_audio_on_focus_loss_button_text: String,
+    background_music_volume_slider_text: String,
+    sound_effect_volume_slider_text: String,
+    spatial_sound_effect_volume_slider_text: String,
     create_character_window_title: String,
     character_name_text: String,
     create_character_button_text: String,
@@ -172,8 +175,79 @@ pub struct Localization {
     friend_list_window_title: String,
     friend_list_text_box_message: String,
     remove_button_text: String,
+    whisper_button_text: String,
+    add_friend_button_text: String,
+    trade_button_text: String,
+    invite_to_party_button_text: String,
     hotbar_window_title: String,
     inventory_window_title: String,
+    use_item_button_text: String,
+    equip_item_button_text: String,
+    unequip_item_button_text: String,
+    drop_item_button_text: String,
+    drop_item_window_title: String,
+    drop_amount_text: String,
+    item_tooltip_window_title: String,
+    cart_window_title: String,
+    cart_items_text: String,
+    cart_weight_text: String,
+    mailbox_button_text: String,
+    mailbox_new_mail_button_text: String,
+    mailbox_window_title: String,
+    write_mail_button_text: String,
+    next_page_button_text: String,
+    read_mail_button_text: String,
+    delete_mail_button_text: String,
+    mail_window_title: String,
+    mail_sender_text: String,
+    mail_zeny_text: String,
+    claim_zeny_button_text: String,
+    claim_items_button_text: String,
+    write_mail_window_title: String,
+    mail_recipient_text_box_message: String,
+    check_mail_recipient_button_text: String,
+    mail_title_text_box_message: String,
+    mail_text_text_box_message: String,
+    mail_zeny_text_box_message: String,
+    send_mail_button_text: String,
+    cancel_mail_button_text: String,
+    pet_window_title: String,
+    level_text: String,
+    hunger_text: String,
+    intimacy_text: String,
+    feed_button_text: String,
+    performance_button_text: String,
+    return_to_egg_button_text: String,
+    unequip_accessory_button_text: String,
+    pet_eggs_window_title: String,
+    homunculus_window_title: String,
+    health_points_text: String,
+    spell_points_text: String,
+    experience_text: String,
+    attack_text: String,
+    magic_attack_text: String,
+    hit_text: String,
+    critical_text: String,
+    defense_text: String,
+    magic_defense_text: String,
+    flee_text: String,
+    attack_speed_text: String,
+    level_up_button_text: String,
+    follow_button_text: String,
+    stay_button_text: String,
+    attack_button_text: String,
+    chat_room_settings_window_title: String,
+    chat_room_title_text_box_message: String,
+    chat_room_limit_text_box_message: String,
+    chat_room_password_text_box_message: String,
+    public_chat_room_button_text: String,
+    create_chat_room_button_text: String,
+    change_chat_room_button_text: String,
+    leave_chat_room_button_text: String,
+    make_owner_button_text: String,
+    kick_button_text: String,
+    chat_room_password_window_title: String,
+    join_chat_room_button_text: String,
     respawn_window_title: String,
     respawn_button_text: String,
     disconnect_button_text: String,
@@ -187,6 +261,11 @@ pub struct Localization {
     menu_theme_text: String,
     in_game_theme_text: String,
     world_theme_text: String,
+    reduce_motion_button_text: String,
+    window_layout_text: String,
+    window_layout_name_text_box_message: String,
+    save_window_layout_button_text: String,
+    reset_window_layout_button_text: String,
     available_stat_points_text: String,
     strength_text: String,
     agility_text: String,
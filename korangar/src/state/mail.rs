@@ -0,0 +1,506 @@
+use std::sync::Arc;
+
+use korangar_interface::element::StateElement;
+use korangar_networking::{InventoryItem, InventoryItemDetails, NoMetadata};
+use ragnarok_packets::{CharacterId, InventoryIndex, ItemId, MailFlags, MailId, MailInformation, MailOpenType};
+use rust_state::RustState;
+
+use crate::graphics::Texture;
+use crate::loaders::AsyncLoader;
+use crate::world::ResourceMetadata;
+
+/// The maximum number of items that can be attached to a single mail.
+pub const MAXIMUM_MAIL_ITEMS: usize = 5;
+
+/// Mail that is currently opened in the mail window.
+#[derive(RustState, StateElement)]
+pub struct OpenedMail {
+    /// Mailbox that the mail is stored in.
+    open_type: MailOpenType,
+    /// Id of the mail.
+    mail_id: MailId,
+    /// Name of the sender.
+    sender_name: String,
+    /// Title of the mail.
+    title: String,
+    /// Body of the mail.
+    text: String,
+    /// Zeny attached to the mail.
+    zeny: u64,
+    /// Items attached to the mail.
+    // TODO: Unhide this.
+    #[hidden_element]
+    items: Vec<InventoryItem<ResourceMetadata>>,
+}
+
+impl Default for OpenedMail {
+    fn default() -> Self {
+        Self {
+            open_type: MailOpenType::Character,
+            // Arguably not very clean but avoids using an Option.
+            mail_id: MailId(0),
+            sender_name: String::new(),
+            title: String::new(),
+            text: String::new(),
+            zeny: 0,
+            items: Vec::new(),
+        }
+    }
+}
+
+/// Mail that is currently being written.
+#[derive(Default, RustState, StateElement)]
+pub struct MailDraft {
+    /// Name of the character receiving the mail.
+    recipient_name: String,
+    /// Recipient name that was last sent to the server to be checked.
+    #[hidden_element]
+    checked_recipient_name: String,
+    /// Character id of the recipient, if the server confirmed that a character
+    /// with the checked name exists.
+    #[hidden_element]
+    recipient_character_id: Option<CharacterId>,
+    /// Title of the mail.
+    title: String,
+    /// Body of the mail.
+    text: String,
+    /// Amount of zeny to send as text, so it can be edited in a text box.
+    zeny: String,
+    /// Items attached to the mail. The index of every item is the index in the
+    /// player inventory.
+    // TODO: Unhide this.
+    #[hidden_element]
+    items: Vec<InventoryItem<ResourceMetadata>>,
+}
+
+impl MailDraft {
+    pub fn recipient_name(&self) -> &str {
+        &self.recipient_name
+    }
+
+    /// Character id of the recipient. Only returns an id if the server
+    /// confirmed the current recipient name.
+    pub fn recipient_character_id(&self) -> Option<CharacterId> {
+        match self.checked_recipient_name == self.recipient_name {
+            true => self.recipient_character_id,
+            false => None,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Parse the amount of zeny to send. An empty text box is treated as zero.
+    pub fn zeny(&self) -> Option<u64> {
+        match self.zeny.trim() {
+            "" => Some(0),
+            zeny => zeny.parse().ok(),
+        }
+    }
+}
+
+/// RODEX mailbox of the player.
+#[derive(Default, RustState, StateElement)]
+pub struct Mailbox {
+    /// Set if the server notified us about unread mail.
+    has_new_mail: bool,
+    /// All mails received so far. The server sends the mails in pages, so
+    /// this list might be incomplete.
+    mails: Vec<MailInformation>,
+    /// Set once the server sent the last page of the mailbox.
+    is_last_page: bool,
+    /// Mail that is displayed in the mail window.
+    opened_mail: OpenedMail,
+    /// Mail that is currently being written.
+    draft: MailDraft,
+}
+
+impl Mailbox {
+    pub fn set_has_new_mail(&mut self, has_new_mail: bool) {
+        self.has_new_mail = has_new_mail;
+    }
+
+    pub fn is_last_page(&self) -> bool {
+        self.is_last_page
+    }
+
+    /// Mailbox and id of the oldest mail received so far. Used to request the
+    /// next page.
+    pub fn last_mail(&self) -> Option<(MailOpenType, MailId)> {
+        self.mails.last().map(|mail| (mail.open_type, mail.mail_id))
+    }
+
+    pub fn clear_mails(&mut self) {
+        self.mails.clear();
+        self.is_last_page = false;
+    }
+
+    pub fn add_mails(&mut self, mails: Vec<MailInformation>, is_last_page: bool) {
+        for mail in mails {
+            // Refreshing the mailbox might send mails that we already know about.
+            match self.mails.iter_mut().find(|known_mail| known_mail.mail_id == mail.mail_id) {
+                Some(known_mail) => *known_mail = mail,
+                None => self.mails.push(mail),
+            }
+        }
+
+        self.is_last_page = is_last_page;
+        self.has_new_mail = self.mails.iter().any(|mail| mail.is_read == 0);
+    }
+
+    pub fn open_mail(
+        &mut self,
+        async_loader: &AsyncLoader,
+        open_type: MailOpenType,
+        mail_id: MailId,
+        text: String,
+        zeny: u64,
+        items: Vec<InventoryItem<NoMetadata>>,
+    ) {
+        let (sender_name, title) = match self.mails.iter_mut().find(|mail| mail.mail_id == mail_id) {
+            Some(mail) => {
+                mail.is_read = 1;
+                (mail.sender_name.clone(), mail.title.clone())
+            }
+            None => (String::new(), String::new()),
+        };
+
+        self.opened_mail = OpenedMail {
+            open_type,
+            mail_id,
+            sender_name,
+            title,
+            text,
+            zeny,
+            items: items
+                .into_iter()
+                .map(|item| async_loader.request_inventory_item_metadata_load(item))
+                .collect(),
+        };
+
+        self.has_new_mail = self.mails.iter().any(|mail| mail.is_read == 0);
+    }
+
+    pub fn opened_mail_type(&self) -> MailOpenType {
+        self.opened_mail.open_type
+    }
+
+    pub fn opened_mail_id(&self) -> MailId {
+        self.opened_mail.mail_id
+    }
+
+    /// Remove a mail from the mailbox. Returns `true` if the removed mail was
+    /// opened.
+    pub fn remove_mail(&mut self, mail_id: MailId) -> bool {
+        self.mails.retain(|mail| mail.mail_id != mail_id);
+
+        let was_opened = self.opened_mail.mail_id == mail_id;
+
+        if was_opened {
+            self.opened_mail = OpenedMail::default();
+        }
+
+        was_opened
+    }
+
+    pub fn zeny_claimed(&mut self, mail_id: MailId) {
+        if let Some(mail) = self.mails.iter_mut().find(|mail| mail.mail_id == mail_id) {
+            mail.flags.remove(MailFlags::ZENY);
+        }
+
+        if self.opened_mail.mail_id == mail_id {
+            self.opened_mail.zeny = 0;
+        }
+    }
+
+    pub fn items_claimed(&mut self, mail_id: MailId) {
+        if let Some(mail) = self.mails.iter_mut().find(|mail| mail.mail_id == mail_id) {
+            mail.flags.remove(MailFlags::ITEM);
+        }
+
+        if self.opened_mail.mail_id == mail_id {
+            self.opened_mail.items.clear();
+        }
+    }
+
+    pub fn draft(&self) -> &MailDraft {
+        &self.draft
+    }
+
+    /// Start writing a new mail, discarding the previous draft.
+    pub fn start_draft(&mut self, recipient_name: String) {
+        self.draft = MailDraft {
+            recipient_name,
+            ..Default::default()
+        };
+    }
+
+    pub fn clear_draft(&mut self) {
+        self.draft = MailDraft::default();
+    }
+
+    /// Remember the current recipient name as the one being checked by the
+    /// server. Returns the name to send to the server.
+    pub fn check_recipient(&mut self) -> String {
+        self.draft.checked_recipient_name = self.draft.recipient_name.clone();
+        self.draft.recipient_character_id = None;
+        self.draft.checked_recipient_name.clone()
+    }
+
+    pub fn recipient_checked(&mut self, character_id: Option<CharacterId>) {
+        self.draft.recipient_character_id = character_id;
+    }
+
+    /// Attach an item from the inventory to the draft. The attached amount
+    /// never exceeds the amount of the item in the inventory.
+    pub fn attach_item(&mut self, mut item: InventoryItem<ResourceMetadata>, added_amount: u16) {
+        let inventory_amount = match item.details {
+            InventoryItemDetails::Regular { amount, .. } => amount,
+            InventoryItemDetails::Equippable { .. } => 1,
+        };
+
+        if let Some(attached_item) = self.draft.items.iter_mut().find(|attached_item| attached_item.index == item.index) {
+            if let InventoryItemDetails::Regular { amount, .. } = &mut attached_item.details {
+                *amount = amount.saturating_add(added_amount).min(inventory_amount);
+            }
+
+            return;
+        }
+
+        if let InventoryItemDetails::Regular { amount, .. } = &mut item.details {
+            *amount = added_amount.min(inventory_amount);
+        }
+
+        self.draft.items.push(item);
+    }
+
+    /// Remove an item from the draft.
+    pub fn detach_item(&mut self, index: InventoryIndex, remove_amount: u16) {
+        let Some(position) = self.draft.items.iter().position(|item| item.index == index) else {
+            return;
+        };
+
+        if let InventoryItemDetails::Regular { amount, .. } = &mut self.draft.items[position].details
+            && *amount > remove_amount
+        {
+            *amount -= remove_amount;
+            return;
+        }
+
+        self.draft.items.remove(position);
+    }
+
+    pub fn update_item_sprite(&mut self, item_id: ItemId, texture: Arc<Texture>) {
+        self.opened_mail
+            .items
+            .iter_mut()
+            .chain(self.draft.items.iter_mut())
+            .filter(|item| item.item_id == item_id)
+            .for_each(|item| {
+                item.metadata.texture = Some(texture.clone());
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use korangar_networking::{InventoryItem, InventoryItemDetails};
+    use ragnarok_packets::{
+        CharacterId, EquipPosition, InventoryIndex, ItemId, MailFlags, MailId, MailInformation, MailOpenType, RegularItemFlags,
+    };
+
+    use crate::state::mail::Mailbox;
+    use crate::world::ResourceMetadata;
+
+    fn mail(mail_id: u64, is_read: u8, flags: MailFlags) -> MailInformation {
+        MailInformation {
+            open_type: MailOpenType::Character,
+            mail_id: MailId(mail_id),
+            is_read,
+            flags,
+            sender_name: "Kafra".to_owned(),
+            expiration_time: 0,
+            title_length: 6,
+            title: "Hello".to_owned(),
+        }
+    }
+
+    fn inventory_item(index: u16, amount: u16) -> InventoryItem<ResourceMetadata> {
+        InventoryItem {
+            metadata: ResourceMetadata {
+                texture: None,
+                name: "Red Potion".to_owned(),
+            },
+            index: InventoryIndex(index),
+            item_id: ItemId(501),
+            item_type: 0,
+            slot: [0; 4],
+            hire_expiration_date: 0,
+            details: InventoryItemDetails::Regular {
+                amount,
+                equipped_position: EquipPosition::empty(),
+                flags: RegularItemFlags::IDENTIFIED,
+            },
+        }
+    }
+
+    fn attached_amounts(mailbox: &Mailbox) -> Vec<(u16, u16)> {
+        mailbox
+            .draft
+            .items
+            .iter()
+            .map(|item| match item.details {
+                InventoryItemDetails::Regular { amount, .. } => (item.index.0, amount),
+                InventoryItemDetails::Equippable { .. } => (item.index.0, 1),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn add_mails_replaces_known_mails() {
+        let mut mailbox = Mailbox::default();
+
+        mailbox.add_mails(vec![mail(2, 1, MailFlags::empty()), mail(1, 1, MailFlags::empty())], false);
+        mailbox.add_mails(vec![mail(1, 0, MailFlags::ZENY)], true);
+
+        assert_eq!(mailbox.mails.len(), 2);
+        assert_eq!(mailbox.mails[1].flags, MailFlags::ZENY);
+        assert_eq!(mailbox.last_mail(), Some((MailOpenType::Character, MailId(1))));
+        assert!(mailbox.is_last_page());
+        assert!(mailbox.has_new_mail);
+    }
+
+    #[test]
+    fn clear_mails_resets_paging() {
+        let mut mailbox = Mailbox::default();
+
+        mailbox.add_mails(vec![mail(1, 1, MailFlags::empty())], true);
+        mailbox.clear_mails();
+
+        assert_eq!(mailbox.last_mail(), None);
+        assert!(!mailbox.is_last_page());
+    }
+
+    #[test]
+    fn remove_mail() {
+        let mut mailbox = Mailbox::default();
+
+        mailbox.add_mails(vec![mail(1, 1, MailFlags::empty()), mail(2, 1, MailFlags::empty())], true);
+        mailbox.opened_mail.mail_id = MailId(2);
+
+        assert!(!mailbox.remove_mail(MailId(1)));
+        assert!(mailbox.remove_mail(MailId(2)));
+        assert!(mailbox.mails.is_empty());
+        assert_eq!(mailbox.opened_mail_id(), MailId(0));
+    }
+
+    #[test]
+    fn claim_attachments() {
+        let mut mailbox = Mailbox::default();
+
+        mailbox.add_mails(vec![mail(1, 1, MailFlags::ZENY | MailFlags::ITEM)], true);
+        mailbox.opened_mail.mail_id = MailId(1);
+        mailbox.opened_mail.zeny = 5000;
+        mailbox.opened_mail.items.push(inventory_item(0, 1));
+
+        mailbox.zeny_claimed(MailId(1));
+
+        assert_eq!(mailbox.mails[0].flags, MailFlags::ITEM);
+        assert_eq!(mailbox.opened_mail.zeny, 0);
+
+        mailbox.items_claimed(MailId(1));
+
+        assert_eq!(mailbox.mails[0].flags, MailFlags::empty());
+        assert!(mailbox.opened_mail.items.is_empty());
+    }
+
+    #[test]
+    fn draft_zeny() {
+        let mut mailbox = Mailbox::default();
+
+        mailbox.start_draft("Poring".to_owned());
+
+        assert_eq!(mailbox.draft().recipient_name(), "Poring");
+        assert_eq!(mailbox.draft().zeny(), Some(0));
+
+        mailbox.draft.zeny = " 1500 ".to_owned();
+        assert_eq!(mailbox.draft().zeny(), Some(1500));
+
+        mailbox.draft.zeny = "-5".to_owned();
+        assert_eq!(mailbox.draft().zeny(), None);
+    }
+
+    #[test]
+    fn draft_recipient_check() {
+        let mut mailbox = Mailbox::default();
+
+        mailbox.start_draft("Poring".to_owned());
+        assert_eq!(mailbox.draft().recipient_character_id(), None);
+
+        assert_eq!(mailbox.check_recipient(), "Poring");
+        mailbox.recipient_checked(Some(CharacterId(150000)));
+        assert_eq!(mailbox.draft().recipient_character_id(), Some(CharacterId(150000)));
+
+        // Changing the name invalidates the check.
+        mailbox.draft.recipient_name = "Drops".to_owned();
+        assert_eq!(mailbox.draft().recipient_character_id(), None);
+
+        mailbox.check_recipient();
+        mailbox.recipient_checked(None);
+        assert_eq!(mailbox.draft().recipient_character_id(), None);
+    }
+
+    #[test]
+    fn attach_item_stacks() {
+        let mut mailbox = Mailbox::default();
+
+        mailbox.attach_item(inventory_item(0, 10), 3);
+        mailbox.attach_item(inventory_item(1, 10), 1);
+        mailbox.attach_item(inventory_item(0, 10), 4);
+
+        assert_eq!(attached_amounts(&mailbox), [(0, 7), (1, 1)]);
+    }
+
+    #[test]
+    fn attach_item_is_limited_by_inventory() {
+        let mut mailbox = Mailbox::default();
+
+        mailbox.attach_item(inventory_item(0, 10), 15);
+        assert_eq!(attached_amounts(&mailbox), [(0, 10)]);
+
+        mailbox.attach_item(inventory_item(0, 10), 5);
+        assert_eq!(attached_amounts(&mailbox), [(0, 10)]);
+    }
+
+    #[test]
+    fn attach_item_does_not_overflow() {
+        let mut mailbox = Mailbox::default();
+
+        mailbox.attach_item(inventory_item(0, u16::MAX), u16::MAX);
+        mailbox.attach_item(inventory_item(0, u16::MAX), 1);
+
+        assert_eq!(attached_amounts(&mailbox), [(0, u16::MAX)]);
+    }
+
+    #[test]
+    fn detach_item() {
+        let mut mailbox = Mailbox::default();
+
+        mailbox.attach_item(inventory_item(0, 10), 5);
+        mailbox.attach_item(inventory_item(1, 10), 5);
+        mailbox.detach_item(InventoryIndex(0), 2);
+        mailbox.detach_item(InventoryIndex(1), 5);
+        mailbox.detach_item(InventoryIndex(2), 1);
+
+        assert_eq!(attached_amounts(&mailbox), [(0, 3)]);
+
+        mailbox.clear_draft();
+
+        assert!(attached_amounts(&mailbox).is_empty());
+    }
+}
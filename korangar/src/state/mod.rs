@@ -1,9 +1,14 @@
 #[cfg(feature = "debug")]
 pub mod cache_statistics;
+pub mod cart;
 pub mod character_slots;
+pub mod chat_room;
+pub mod homunculus;
 pub mod hotbar;
 pub mod inventory;
 pub mod localization;
+pub mod mail;
+pub mod pet;
 pub mod skills;
 pub mod theme;
 
@@ -13,20 +18,27 @@ use std::sync::Arc;
 use korangar_interface::application::Application;
 use korangar_interface::components::button::ButtonTheme;
 use korangar_interface::components::collapsible::CollapsibleTheme;
+use korangar_interface::components::context_menu::ContextMenuTheme;
 use korangar_interface::components::drop_down::DropDownTheme;
 use korangar_interface::components::field::FieldTheme;
+use korangar_interface::components::number_input::NumberInputTheme;
+use korangar_interface::components::progress_bar::ProgressBarTheme;
+use korangar_interface::components::slider::SliderTheme;
 use korangar_interface::components::state_button::StateButtonTheme;
+use korangar_interface::components::table::TableTheme;
 use korangar_interface::components::text::TextTheme;
 use korangar_interface::components::text_box::TextBoxTheme;
 use korangar_interface::element::StateElement;
 use korangar_interface::layout::tooltip::TooltipTheme;
+use korangar_interface::markup::{MarkupIterator, MarkupSpan};
 use korangar_interface::theme::ThemePathGetter;
+use korangar_interface::tween::AnimationTheme;
 use korangar_interface::window::{StateWindow, WindowTheme};
 use korangar_networking::{MessageColor, SellItem, ShopItem};
 use localization::Localization;
 #[cfg(feature = "debug")]
 use ragnarok_formats::map::{EffectSource, LightSource, MapData, SoundSource};
-use ragnarok_packets::{CharacterId, CharacterServerInformation, EntityId, Friend};
+use ragnarok_packets::{CharacterId, CharacterServerInformation, EntityId, Friend, ItemId};
 #[cfg(feature = "debug")]
 use rust_state::{ManuallyAssertExt, VecIndexExt};
 use rust_state::{Path, PathExt, RustState, Selector};
@@ -38,22 +50,28 @@ use self::cache_statistics::CacheStatistics;
 use crate::PacketHistory;
 #[cfg(feature = "debug")]
 use crate::graphics::RenderOptions;
-use crate::graphics::{Color, CornerDiameter, ScreenClip, ScreenPosition, ScreenSize, ShadowPadding};
+use crate::graphics::{Color, CornerDiameter, ScreenClip, ScreenPosition, ScreenSize, ShadowPadding, Texture};
 use crate::input::{InputEvent, MouseInputMode};
+use crate::interface::clipboard::Clipboard;
 use crate::interface::windows::{
-    ChatWindowState, DialogWindowState, FriendListWindowState, LoginWindowState, LoginWindowStatePathExt, SkillTreeWindowState,
-    WindowCache, WindowClass,
+    ChatWindowState, DialogWindowState, FriendListWindowState, InterfaceSettingsWindowState, LoginWindowState, LoginWindowStatePathExt,
+    SkillTreeWindowState, WindowCache, WindowClass,
 };
 #[cfg(feature = "debug")]
-use crate::interface::windows::{ProfilerWindowState, ThemeInspectorWindowState};
+use crate::interface::windows::{NetworkStatisticsWindowState, ProfilerWindowState, ThemeInspectorWindowState};
 use crate::loaders::{ClientInfo, FontLoader, FontSize, GameFileLoader, OverflowBehavior, load_client_info};
 use crate::renderer::InterfaceRenderer;
 use crate::settings::{
     GameSettings, GraphicsSettingsCapabilities, InterfaceSettings, InterfaceSettingsCapabilities, LoginSettings, ServiceSettings,
 };
+use crate::state::cart::Cart;
 use crate::state::character_slots::CharacterSlots;
+use crate::state::chat_room::ChatRooms;
+use crate::state::homunculus::Homunculus;
 use crate::state::hotbar::Hotbar;
-use crate::state::inventory::Inventory;
+use crate::state::inventory::{Inventory, ItemDrop};
+use crate::state::mail::Mailbox;
+use crate::state::pet::Pet;
 use crate::state::skills::SkillTree;
 use crate::state::theme::WorldTheme;
 #[cfg(feature = "debug")]
@@ -71,12 +89,41 @@ pub struct ChatMessage {
     pub text: String,
     /// Color of the message.
     pub color: MessageColor,
+    /// Item icons that are displayed inside of the message.
+    #[hidden_element]
+    pub icons: Vec<ChatIcon>,
 }
 
 impl ChatMessage {
     pub fn new(text: String, color: MessageColor) -> Self {
-        Self { text, color }
+        Self {
+            text,
+            color,
+            icons: Vec::new(),
+        }
     }
+
+    /// Get the item ids of all `<ICON>` tags in the message.
+    pub fn icon_item_ids(&self) -> impl Iterator<Item = ItemId> + '_ {
+        MarkupIterator::new(&self.text).filter_map(|span| match span {
+            MarkupSpan::Icon { source } => source.parse().ok().map(ItemId),
+            MarkupSpan::Text { .. } => None,
+        })
+    }
+
+    pub fn update_item_sprite(&mut self, item_id: ItemId, texture: Arc<Texture>) {
+        self.icons
+            .iter_mut()
+            .filter(|icon| icon.item_id == item_id)
+            .for_each(|icon| icon.texture = Some(texture.clone()));
+    }
+}
+
+/// An item icon inside of a [`ChatMessage`].
+#[derive(Debug, Clone)]
+pub struct ChatIcon {
+    pub item_id: ItemId,
+    pub texture: Option<Arc<Texture>>,
 }
 
 #[derive(Debug, Clone, Copy, RustState, StateElement)]
@@ -141,6 +188,8 @@ pub struct ClientState {
     dialog_window: DialogWindowState,
     /// Internal state of the skill tree window.
     skill_tree_window: SkillTreeWindowState,
+    /// Internal state of the interface settings window.
+    interface_settings_window: InterfaceSettingsWindowState,
 
     /// All entities on the map.
     entities: Vec<Entity>,
@@ -176,6 +225,18 @@ pub struct ClientState {
     hotbar: Hotbar,
     /// Player inventory.
     inventory: Inventory,
+    /// Item that the player is about to drop.
+    item_drop: ItemDrop,
+    /// Merchant cart.
+    cart: Cart,
+    /// RODEX mailbox.
+    mailbox: Mailbox,
+    /// Pet of the player.
+    pet: Pet,
+    /// Homunculus of the player.
+    homunculus: Homunculus,
+    /// Chat rooms on the map and the chat room the player is in.
+    chat_rooms: ChatRooms,
     /// Player skill tree.
     skill_tree: SkillTree,
 
@@ -229,6 +290,9 @@ pub struct ClientState {
     /// Internal state of the profiler window.
     #[cfg(feature = "debug")]
     profiler_window: ProfilerWindowState,
+    /// Internal state of the network statistics window.
+    #[cfg(feature = "debug")]
+    network_statistics_window: NetworkStatisticsWindowState,
     /// Internal state of the theme inspector window.
     #[cfg(feature = "debug")]
     theme_inspector_window: ThemeInspectorWindowState,
@@ -246,14 +310,15 @@ impl ClientState {
     pub fn new(
         game_file_loader: &GameFileLoader,
         graphics_settings: GraphicsSettings,
+        audio_settings: AudioSettings,
         #[cfg(feature = "debug")] packet_history: PacketHistory,
     ) -> Self {
         time_phase!("load settings", {
             let mut login_settings = LoginSettings::new();
-            let audio_settings = AudioSettings::new();
             let game_settings = GameSettings::new();
             let interface_settings = InterfaceSettings::new();
             let interface_settings_capabilities = InterfaceSettingsCapabilities::default();
+            let interface_settings_window = InterfaceSettingsWindowState::default();
         });
 
         time_phase!("load localization", {
@@ -330,6 +395,12 @@ impl ClientState {
             let player_name = String::new();
             let hotbar = Hotbar::default();
             let inventory = Inventory::default();
+            let item_drop = ItemDrop::default();
+            let cart = Cart::default();
+            let mailbox = Mailbox::default();
+            let pet = Pet::default();
+            let homunculus = Homunculus::default();
+            let chat_rooms = ChatRooms::default();
             let skill_tree = SkillTree::default();
             let skill_tree_window = SkillTreeWindowState::default();
         });
@@ -361,6 +432,8 @@ impl ClientState {
         #[cfg(feature = "debug")]
         let profiler_window = ProfilerWindowState::default();
         #[cfg(feature = "debug")]
+        let network_statistics_window = NetworkStatisticsWindowState::default();
+        #[cfg(feature = "debug")]
         let theme_inspector_window = ThemeInspectorWindowState::default();
 
         #[cfg(feature = "debug")]
@@ -387,6 +460,7 @@ impl ClientState {
             friend_list_window,
             dialog_window,
             skill_tree_window,
+            interface_settings_window,
             entities: Vec::new(),
             dead_entities: Vec::new(),
             ground_items: Vec::new(),
@@ -399,6 +473,12 @@ impl ClientState {
             player_name,
             hotbar,
             inventory,
+            item_drop,
+            cart,
+            mailbox,
+            pet,
+            homunculus,
+            chat_rooms,
             skill_tree,
             character_servers,
             character_slots,
@@ -422,6 +502,8 @@ impl ClientState {
             #[cfg(feature = "debug")]
             profiler_window,
             #[cfg(feature = "debug")]
+            network_statistics_window,
+            #[cfg(feature = "debug")]
             theme_inspector_window,
             #[cfg(feature = "debug")]
             packet_history,
@@ -503,14 +585,39 @@ impl ThemePathGetter<ClientState> for ClientThemeGetter {
         ThemePath.field()
     }
 
+    fn slider(self) -> impl Path<ClientState, SliderTheme<ClientState>> {
+        ThemePath.slider()
+    }
+
+    fn progress_bar(self) -> impl Path<ClientState, ProgressBarTheme<ClientState>> {
+        ThemePath.progress_bar()
+    }
+
+    fn number_input(self) -> impl Path<ClientState, NumberInputTheme<ClientState>> {
+        ThemePath.number_input()
+    }
+
+    fn table(self) -> impl Path<ClientState, TableTheme<ClientState>> {
+        ThemePath.table()
+    }
+
+    fn context_menu(self) -> impl Path<ClientState, ContextMenuTheme<ClientState>> {
+        ThemePath.context_menu()
+    }
+
     fn tooltip(self) -> impl Path<ClientState, TooltipTheme<ClientState>> {
         ThemePath.tooltip()
     }
+
+    fn animation(self) -> impl Path<ClientState, AnimationTheme> {
+        ThemePath.animation()
+    }
 }
 
 impl Application for ClientState {
     type Cache = WindowCache;
     type Clip = ScreenClip;
+    type Clipboard = Clipboard;
     type Color = Color;
     type CornerDiameter = CornerDiameter;
     type CustomEvent = InputEvent;
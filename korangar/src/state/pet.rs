@@ -0,0 +1,171 @@
+use korangar_interface::element::StateElement;
+use ragnarok_packets::{EntityId, InventoryIndex, ItemId, PetStateType};
+use rust_state::RustState;
+
+/// Maximum hunger of a pet.
+pub const MAXIMUM_PET_HUNGER: u32 = 100;
+/// Maximum intimacy of a pet.
+pub const MAXIMUM_PET_INTIMACY: u32 = 1000;
+
+/// Pet egg in the player inventory that can be hatched.
+#[derive(Clone, RustState, StateElement)]
+pub struct PetEgg {
+    /// Inventory index of the egg.
+    pub index: InventoryIndex,
+    /// Display name of the egg.
+    pub name: String,
+}
+
+/// Pet of the player.
+#[derive(Default, RustState, StateElement)]
+pub struct Pet {
+    /// Entity id of the pet. Only set while the pet is hatched.
+    entity_id: Option<EntityId>,
+    /// Name of the pet.
+    name: String,
+    /// Level of the pet.
+    level: u32,
+    /// Hunger of the pet, ranging from 0 (starving) to
+    /// [`MAXIMUM_PET_HUNGER`].
+    hunger: u32,
+    /// Intimacy of the pet, ranging from 0 (awkward) to
+    /// [`MAXIMUM_PET_INTIMACY`].
+    intimacy: u32,
+    /// Whether or not the pet is wearing an accessory.
+    has_accessory: bool,
+    /// Set after using a taming item until the player selected a monster to
+    /// capture.
+    is_capturing: bool,
+    /// Eggs that can be hatched after using a pet incubator.
+    eggs: Vec<PetEgg>,
+}
+
+impl Pet {
+    pub fn entity_id(&self) -> Option<EntityId> {
+        self.entity_id
+    }
+
+    pub fn is_hatched(&self) -> bool {
+        self.entity_id.is_some()
+    }
+
+    pub fn is_companion(&self, entity_id: EntityId) -> bool {
+        self.entity_id == Some(entity_id)
+    }
+
+    pub fn update_information(&mut self, name: String, level: u16, hunger: u16, intimacy: u16, accessory_id: ItemId) {
+        self.name = name;
+        self.level = level as u32;
+        self.hunger = hunger as u32;
+        self.intimacy = intimacy as u32;
+        self.has_accessory = accessory_id != ItemId(0);
+    }
+
+    pub fn update_state(&mut self, entity_id: EntityId, state_type: PetStateType, value: i32) {
+        match state_type {
+            PetStateType::Initialize => self.entity_id = Some(entity_id),
+            PetStateType::Intimacy => self.intimacy = value.max(0) as u32,
+            PetStateType::Hunger => self.hunger = value.max(0) as u32,
+            PetStateType::Accessory => self.has_accessory = value != 0,
+            // The performance and hair style are only visual and handled by the server
+            // through regular entity updates.
+            PetStateType::Performance | PetStateType::HairStyle => {}
+        }
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.is_capturing
+    }
+
+    pub fn set_capturing(&mut self, is_capturing: bool) {
+        self.is_capturing = is_capturing;
+    }
+
+    pub fn set_eggs(&mut self, eggs: Vec<PetEgg>) {
+        self.eggs = eggs;
+    }
+
+    pub fn clear_eggs(&mut self) {
+        self.eggs.clear();
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ragnarok_packets::{EntityId, InventoryIndex, ItemId, PetStateType};
+
+    use crate::state::pet::{Pet, PetEgg};
+
+    #[test]
+    fn initialize_hatches_pet() {
+        let mut pet = Pet::default();
+
+        assert!(!pet.is_hatched());
+
+        pet.update_state(EntityId(7), PetStateType::Initialize, 0);
+
+        assert!(pet.is_hatched());
+        assert!(pet.is_companion(EntityId(7)));
+        assert!(!pet.is_companion(EntityId(8)));
+    }
+
+    #[test]
+    fn update_information() {
+        let mut pet = Pet::default();
+
+        pet.update_information("Poring".to_owned(), 12, 80, 250, ItemId(10013));
+
+        assert_eq!(pet.name, "Poring");
+        assert_eq!(pet.level, 12);
+        assert_eq!(pet.hunger, 80);
+        assert_eq!(pet.intimacy, 250);
+        assert!(pet.has_accessory);
+
+        pet.update_information("Poring".to_owned(), 12, 80, 250, ItemId(0));
+
+        assert!(!pet.has_accessory);
+    }
+
+    #[test]
+    fn update_state_clamps_negative_values() {
+        let mut pet = Pet::default();
+
+        pet.update_state(EntityId(7), PetStateType::Hunger, 35);
+        pet.update_state(EntityId(7), PetStateType::Intimacy, -20);
+
+        assert_eq!(pet.hunger, 35);
+        assert_eq!(pet.intimacy, 0);
+    }
+
+    #[test]
+    fn update_state_accessory() {
+        let mut pet = Pet::default();
+
+        pet.update_state(EntityId(7), PetStateType::Accessory, 10013);
+        assert!(pet.has_accessory);
+
+        pet.update_state(EntityId(7), PetStateType::Accessory, 0);
+        assert!(!pet.has_accessory);
+    }
+
+    #[test]
+    fn clear_returns_pet_to_egg() {
+        let mut pet = Pet::default();
+
+        pet.update_state(EntityId(7), PetStateType::Initialize, 0);
+        pet.set_capturing(true);
+        pet.set_eggs(vec![PetEgg {
+            index: InventoryIndex(3),
+            name: "Poring Egg".to_owned(),
+        }]);
+        pet.clear();
+
+        assert!(!pet.is_hatched());
+        assert!(!pet.is_capturing());
+        assert!(pet.eggs.is_empty());
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use hashbrown::HashMap;
 use korangar_interface::element::StateElement;
-use ragnarok_packets::{AttackRange, JobId, SkillId, SkillInformation, SkillLevel, SkillType};
+use ragnarok_packets::{AttackRange, ClientTick, JobId, SkillId, SkillInformation, SkillLevel, SkillType};
 use rust_state::{Path, PathExt, RustState, Selector};
 
 use crate::loaders::Sprite;
@@ -77,6 +77,74 @@ where
     }
 }
 
+pub struct SkillCooldownPath<A, B> {
+    learnable_skill_path: A,
+    cooldowns_path: B,
+}
+
+impl<A, B> SkillCooldownPath<A, B> {
+    pub fn new(learnable_skill_path: A, cooldowns_path: B) -> Self {
+        Self {
+            learnable_skill_path,
+            cooldowns_path,
+        }
+    }
+}
+
+impl<A, B> Clone for SkillCooldownPath<A, B>
+where
+    A: Clone,
+    B: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            learnable_skill_path: self.learnable_skill_path.clone(),
+            cooldowns_path: self.cooldowns_path.clone(),
+        }
+    }
+}
+
+impl<A, B> Copy for SkillCooldownPath<A, B>
+where
+    A: Copy,
+    B: Copy,
+{
+}
+
+impl<A, B> Path<ClientState, SkillCooldown, false> for SkillCooldownPath<A, B>
+where
+    A: Path<ClientState, LearnableSkill, false>,
+    B: Path<ClientState, Vec<SkillCooldown>>,
+{
+    fn follow<'a>(&self, state: &'a ClientState) -> Option<&'a SkillCooldown> {
+        let learnable_skill = self.learnable_skill_path.follow(state)?;
+        let learnable_skill_id = learnable_skill.skill_id;
+
+        let cooldowns = self.cooldowns_path.follow_safe(state);
+
+        cooldowns.iter().find(|cooldown| cooldown.skill_id == learnable_skill_id)
+    }
+
+    fn follow_mut<'a>(&self, state: &'a mut ClientState) -> Option<&'a mut SkillCooldown> {
+        let learnable_skill = self.learnable_skill_path.follow(state)?;
+        let learnable_skill_id = learnable_skill.skill_id;
+
+        let cooldowns = self.cooldowns_path.follow_mut_safe(state);
+
+        cooldowns.iter_mut().find(|cooldown| cooldown.skill_id == learnable_skill_id)
+    }
+}
+
+impl<A, B> Selector<ClientState, SkillCooldown, false> for SkillCooldownPath<A, B>
+where
+    A: Path<ClientState, LearnableSkill, false>,
+    B: Path<ClientState, Vec<SkillCooldown>>,
+{
+    fn select<'a>(&'a self, state: &'a ClientState) -> Option<&'a SkillCooldown> {
+        self.follow(state)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, RustState, StateElement)]
 pub enum SkillAcquisition {
     Job,
@@ -141,6 +209,60 @@ impl LearnedSkill {
     }
 }
 
+/// Cooldown of one of the player skills.
+#[derive(Clone, Debug, RustState, StateElement)]
+pub struct SkillCooldown {
+    pub skill_id: SkillId,
+    #[hidden_element]
+    start_time: ClientTick,
+    duration_ms: u32,
+    /// Part of the cooldown that is still remaining, going from 1.0 to 0.0.
+    /// Updated once per frame.
+    pub remaining: f32,
+    /// Remaining cooldown in whole seconds, rounded up.
+    remaining_seconds: u32,
+    /// Formatted once the remaining seconds change so we don't allocate every
+    /// frame.
+    pub remaining_text: String,
+}
+
+impl SkillCooldown {
+    fn new(skill_id: SkillId, duration_ms: u32, client_tick: ClientTick) -> Self {
+        let mut cooldown = Self {
+            skill_id,
+            start_time: client_tick,
+            duration_ms,
+            remaining: 1.0,
+            remaining_seconds: 0,
+            remaining_text: String::new(),
+        };
+
+        cooldown.update(client_tick);
+        cooldown
+    }
+
+    fn update(&mut self, client_tick: ClientTick) {
+        let elapsed = client_tick.0.wrapping_sub(self.start_time.0).min(self.duration_ms);
+        let remaining_ms = self.duration_ms - elapsed;
+
+        self.remaining = match self.duration_ms {
+            0 => 0.0,
+            duration_ms => remaining_ms as f32 / duration_ms as f32,
+        };
+
+        let remaining_seconds = remaining_ms.div_ceil(1000);
+
+        if remaining_seconds != self.remaining_seconds || self.remaining_text.is_empty() {
+            self.remaining_seconds = remaining_seconds;
+            self.remaining_text = remaining_seconds.to_string();
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.remaining <= 0.0
+    }
+}
+
 #[derive(Debug, Clone, RustState, StateElement)]
 pub struct SkillTabLayout {
     pub name: String,
@@ -157,6 +279,8 @@ pub struct SkillTreeLayout {
 pub struct SkillTree {
     layout: SkillTreeLayout,
     skills: Vec<LearnedSkill>,
+    /// Skills that can't be used until their cooldown is over.
+    cooldowns: Vec<SkillCooldown>,
 }
 
 impl SkillTree {
@@ -179,6 +303,31 @@ impl SkillTree {
     pub fn remove_skill(&mut self, skill_id: SkillId) {
         self.skills.retain(|skill| skill.skill_id != skill_id);
     }
+
+    pub fn is_on_cooldown(&self, skill_id: SkillId) -> bool {
+        self.cooldowns.iter().any(|cooldown| cooldown.skill_id == skill_id)
+    }
+
+    /// Start or restart the cooldown of a skill.
+    pub fn start_cooldown(&mut self, skill_id: SkillId, duration_ms: u32, client_tick: ClientTick) {
+        let cooldown = SkillCooldown::new(skill_id, duration_ms, client_tick);
+
+        match self.cooldowns.iter_mut().find(|cooldown| cooldown.skill_id == skill_id) {
+            Some(known_cooldown) => *known_cooldown = cooldown,
+            None => self.cooldowns.push(cooldown),
+        }
+    }
+
+    /// Update the remaining time of all cooldowns and remove the ones that are
+    /// over.
+    pub fn update_cooldowns(&mut self, client_tick: ClientTick) {
+        self.cooldowns.iter_mut().for_each(|cooldown| cooldown.update(client_tick));
+        self.cooldowns.retain(|cooldown| !cooldown.is_over());
+    }
+
+    pub fn clear_cooldowns(&mut self) {
+        self.cooldowns.clear();
+    }
 }
 
 pub fn bring_skill_to_level(
@@ -231,3 +380,91 @@ pub fn bring_skill_to_level(
 
     available_skill_points.saturating_sub(total_points_required)
 }
+
+#[cfg(test)]
+mod tests {
+    use ragnarok_packets::{ClientTick, SkillId};
+
+    use crate::state::skills::{SkillCooldown, SkillTree};
+
+    #[test]
+    fn cooldown_counts_down() {
+        let mut cooldown = SkillCooldown::new(SkillId(1), 2000, ClientTick(1000));
+
+        assert_eq!(cooldown.remaining, 1.0);
+        assert_eq!(cooldown.remaining_text, "2");
+
+        cooldown.update(ClientTick(2000));
+
+        assert_eq!(cooldown.remaining, 0.5);
+        assert_eq!(cooldown.remaining_text, "1");
+        assert!(!cooldown.is_over());
+
+        // Ticks after the end of the cooldown don't make the remaining time negative.
+        cooldown.update(ClientTick(5000));
+
+        assert_eq!(cooldown.remaining, 0.0);
+        assert_eq!(cooldown.remaining_text, "0");
+        assert!(cooldown.is_over());
+    }
+
+    #[test]
+    fn cooldown_rounds_seconds_up() {
+        let mut cooldown = SkillCooldown::new(SkillId(1), 1500, ClientTick(0));
+
+        assert_eq!(cooldown.remaining_text, "2");
+
+        cooldown.update(ClientTick(1499));
+
+        assert_eq!(cooldown.remaining_text, "1");
+    }
+
+    #[test]
+    fn cooldown_handles_tick_wraparound() {
+        let mut cooldown = SkillCooldown::new(SkillId(1), 1000, ClientTick(u32::MAX - 249));
+
+        cooldown.update(ClientTick(250));
+
+        assert_eq!(cooldown.remaining, 0.5);
+        assert!(!cooldown.is_over());
+    }
+
+    #[test]
+    fn cooldown_without_duration_is_over() {
+        let cooldown = SkillCooldown::new(SkillId(1), 0, ClientTick(1000));
+
+        assert_eq!(cooldown.remaining, 0.0);
+        assert!(cooldown.is_over());
+    }
+
+    #[test]
+    fn start_cooldown_restarts_known_cooldown() {
+        let mut skill_tree = SkillTree::default();
+
+        skill_tree.start_cooldown(SkillId(1), 1000, ClientTick(0));
+        skill_tree.start_cooldown(SkillId(1), 1000, ClientTick(800));
+
+        assert_eq!(skill_tree.cooldowns.len(), 1);
+
+        // The first cooldown would be over by now.
+        skill_tree.update_cooldowns(ClientTick(1200));
+
+        assert!(skill_tree.is_on_cooldown(SkillId(1)));
+    }
+
+    #[test]
+    fn update_cooldowns_removes_finished_cooldowns() {
+        let mut skill_tree = SkillTree::default();
+
+        skill_tree.start_cooldown(SkillId(1), 1000, ClientTick(0));
+        skill_tree.start_cooldown(SkillId(2), 3000, ClientTick(0));
+        skill_tree.update_cooldowns(ClientTick(2000));
+
+        assert!(!skill_tree.is_on_cooldown(SkillId(1)));
+        assert!(skill_tree.is_on_cooldown(SkillId(2)));
+
+        skill_tree.clear_cooldowns();
+
+        assert!(!skill_tree.is_on_cooldown(SkillId(2)));
+    }
+}
@@ -2,14 +2,20 @@
 use korangar_debug::logging::{Colorize, Timer, print_debug};
 use korangar_interface::components::button::ButtonTheme;
 use korangar_interface::components::collapsible::CollapsibleTheme;
+use korangar_interface::components::context_menu::ContextMenuTheme;
 use korangar_interface::components::drop_down::DropDownTheme;
 use korangar_interface::components::field::FieldTheme;
+use korangar_interface::components::number_input::NumberInputTheme;
+use korangar_interface::components::progress_bar::ProgressBarTheme;
+use korangar_interface::components::slider::SliderTheme;
 use korangar_interface::components::state_button::StateButtonTheme;
+use korangar_interface::components::table::TableTheme;
 use korangar_interface::components::text::TextTheme;
 use korangar_interface::components::text_box::TextBoxTheme;
 use korangar_interface::element::StateElement;
 use korangar_interface::layout::tooltip::TooltipTheme;
 use korangar_interface::prelude::{HorizontalAlignment, VerticalAlignment};
+use korangar_interface::tween::{AnimationTheme, Easing};
 use korangar_interface::window::{StateWindow, WindowTheme};
 use rust_state::RustState;
 use serde::{Deserialize, Serialize};
@@ -64,6 +70,10 @@ pub struct SkillTreeTheme {
     /// Color used when the selected skill level is lower than the learned skill
     /// level.
     lower_level_color: Color,
+    /// Color of the overlay covering skills that are on cooldown.
+    cooldown_color: Color,
+    cooldown_text_color: Color,
+    cooldown_font_size: FontSize,
 }
 
 #[derive(Serialize, Deserialize, RustState, StateElement)]
@@ -101,7 +111,19 @@ pub struct InterfaceTheme {
     #[hidden_element]
     pub field: FieldTheme<ClientState>,
     #[hidden_element]
+    pub slider: SliderTheme<ClientState>,
+    #[hidden_element]
+    pub progress_bar: ProgressBarTheme<ClientState>,
+    #[hidden_element]
+    pub number_input: NumberInputTheme<ClientState>,
+    #[hidden_element]
+    pub table: TableTheme<ClientState>,
+    #[hidden_element]
+    pub context_menu: ContextMenuTheme<ClientState>,
+    #[hidden_element]
     pub tooltip: TooltipTheme<ClientState>,
+    #[hidden_element]
+    pub animation: AnimationTheme,
     pub debug_button: DebugButtonTheme,
     pub chat: ChatTheme,
     pub skill_tree: SkillTreeTheme,
@@ -191,6 +213,10 @@ impl InterfaceTheme {
                 overflow_behavior: OverflowBehavior::Shrink,
                 anchor_color: Color::rgb_u8(130, 105, 160),
                 closest_anchor_color: Color::rgb_u8(255, 175, 30),
+                focus_ring_color: Color::rgb_u8(255, 175, 30),
+                focus_ring_width: 2.0,
+                snap_distance: 15.0,
+                active_tab_color: Color::rgba_u8(255, 175, 30, 60),
             },
             text: TextTheme {
                 color: Color::monochrome_u8(220),
@@ -249,6 +275,8 @@ impl InterfaceTheme {
                 ghost_foreground_color: Color::monochrome_u8(100),
                 hide_icon_color: Color::rgb_u8(200, 180, 180),
                 hovered_hide_icon_color: Color::rgb_u8(250, 200, 200),
+                caret_color: Color::monochrome_u8(255),
+                selection_color: Color::rgba_u8(255, 160, 60, 100),
                 shadow_color: Color::rgba_u8(0, 0, 0, 100),
                 shadow_padding: ShadowPadding::diagonal(2.0, 5.0),
                 height: 30.0,
@@ -325,6 +353,104 @@ impl InterfaceTheme {
                 vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
                 overflow_behavior: OverflowBehavior::LineBreak,
             },
+            slider: SliderTheme {
+                background_color: Color::monochrome_u8(80),
+                foreground_color: Color::monochrome_u8(180),
+                highlight_color: Color::rgb_u8(255, 160, 60),
+                hovered_background_color: Color::monochrome_u8(120),
+                rail_color: Color::monochrome_u8(40),
+                filled_rail_color: Color::rgb_u8(255, 160, 60),
+                knob_color: Color::monochrome_u8(200),
+                hovered_knob_color: Color::monochrome_u8(250),
+                shadow_color: Color::rgba_u8(0, 0, 0, 100),
+                shadow_padding: ShadowPadding::diagonal(2.0, 5.0),
+                height: 30.0,
+                corner_diameter: CornerDiameter::uniform(30.0),
+                rail_height: 6.0,
+                knob_width: 14.0,
+                font_size: FontSize(16.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 15.0, border: 5.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            progress_bar: ProgressBarTheme {
+                background_color: Color::monochrome_u8(40),
+                foreground_color: Color::monochrome_u8(180),
+                highlight_color: Color::rgb_u8(255, 160, 60),
+                bar_color: Color::rgb_u8(255, 160, 60),
+                shadow_color: Color::rgba_u8(0, 0, 0, 100),
+                shadow_padding: ShadowPadding::diagonal(2.0, 5.0),
+                height: 30.0,
+                corner_diameter: CornerDiameter::uniform(30.0),
+                border: 4.0,
+                font_size: FontSize(16.0),
+                horizontal_alignment: HorizontalAlignment::Center { offset: 0.0, border: 5.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            number_input: NumberInputTheme {
+                background_color: Color::monochrome_u8(80),
+                foreground_color: Color::monochrome_u8(180),
+                highlight_color: Color::rgb_u8(255, 160, 60),
+                hovered_background_color: Color::monochrome_u8(120),
+                button_foreground_color: Color::monochrome_u8(220),
+                button_background_color: Color::monochrome_u8(100),
+                hovered_button_background_color: Color::monochrome_u8(140),
+                shadow_color: Color::rgba_u8(0, 0, 0, 100),
+                shadow_padding: ShadowPadding::diagonal(2.0, 5.0),
+                height: 30.0,
+                corner_diameter: CornerDiameter::uniform(30.0),
+                font_size: FontSize(16.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 15.0, border: 5.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            table: TableTheme {
+                background_color: Color::monochrome_u8(40),
+                foreground_color: Color::monochrome_u8(180),
+                highlight_color: Color::rgb_u8(255, 160, 60),
+                header_foreground_color: Color::monochrome_u8(220),
+                header_background_color: Color::monochrome_u8(80),
+                hovered_header_background_color: Color::monochrome_u8(120),
+                row_background_color: Color::monochrome_u8(55),
+                alternate_row_background_color: Color::monochrome_u8(65),
+                hovered_row_background_color: Color::monochrome_u8(105),
+                separator_color: Color::monochrome_u8(30),
+                icon_color: Color::monochrome_u8(220),
+                shadow_color: Color::rgba_u8(0, 0, 0, 100),
+                shadow_padding: ShadowPadding::diagonal(2.0, 5.0),
+                corner_diameter: CornerDiameter::uniform(15.0),
+                header_height: 30.0,
+                row_height: 26.0,
+                maximum_height: 500.0,
+                minimum_column_width: 40.0,
+                icon_size: 12.0,
+                font_size: FontSize(16.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 10.0, border: 5.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            context_menu: ContextMenuTheme {
+                foreground_color: Color::monochrome_u8(220),
+                disabled_foreground_color: Color::monochrome_u8(110),
+                highlight_color: Color::rgb_u8(255, 160, 60),
+                background_color: Color::monochrome_u8(40),
+                hovered_background_color: Color::monochrome_u8(90),
+                separator_color: Color::monochrome_u8(70),
+                shadow_color: Color::rgba_u8(0, 0, 0, 100),
+                shadow_padding: ShadowPadding::diagonal(5.0, 10.0),
+                corner_diameter: CornerDiameter::uniform(15.0),
+                item_corner_diameter: CornerDiameter::uniform(12.0),
+                width: 180.0,
+                border: 5.0,
+                item_height: 28.0,
+                separator_height: 9.0,
+                icon_size: 12.0,
+                font_size: FontSize(16.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 10.0, border: 5.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
             tooltip: TooltipTheme {
                 background_color: Color::rgba_u8(15, 15, 15, 200),
                 foreground_color: Color::monochrome_u8(235),
@@ -338,6 +464,14 @@ impl InterfaceTheme {
                 mouse_offset: 20.0,
                 overflow_behavior: OverflowBehavior::LineBreak,
             },
+            animation: AnimationTheme {
+                easing: Easing::EaseOut,
+                window_duration: 0.25,
+                window_offset: 20.0,
+                collapsible_duration: 0.2,
+                tooltip_duration: 0.15,
+                hover_duration: 0.1,
+            },
             debug_button: DebugButtonTheme {
                 foreground_color: Color::rgb_u8(255, 167, 89),
                 hovered_background_color: Color::rgb_u8(225, 199, 115),
@@ -363,6 +497,9 @@ impl InterfaceTheme {
                 points_font_size: FontSize(12.0),
                 arrow_color: Color::monochrome_u8(160),
                 lower_level_color: Color::rgb_u8(255, 200, 255),
+                cooldown_color: Color::rgba_u8(0, 0, 0, 160),
+                cooldown_text_color: Color::WHITE,
+                cooldown_font_size: FontSize(16.0),
             },
             global: GlobalTheme {
                 drop_area_color: Color::rgba_u8(255, 60, 200, 120),
@@ -399,6 +536,10 @@ impl InterfaceTheme {
                 overflow_behavior: OverflowBehavior::Shrink,
                 anchor_color: Color::rgb_u8(140, 105, 130),
                 closest_anchor_color: Color::rgb_u8(255, 175, 30),
+                focus_ring_color: Color::rgb_u8(255, 175, 30),
+                focus_ring_width: 1.5,
+                snap_distance: 10.0,
+                active_tab_color: Color::rgba_u8(255, 175, 30, 60),
             },
             text: TextTheme {
                 color: Color::monochrome_u8(220),
@@ -457,6 +598,8 @@ impl InterfaceTheme {
                 ghost_foreground_color: Color::monochrome_u8(130),
                 hide_icon_color: Color::monochrome_u8(180),
                 hovered_hide_icon_color: Color::rgb_u8(250, 200, 200),
+                caret_color: Color::monochrome_u8(255),
+                selection_color: Color::rgba_u8(255, 160, 60, 100),
                 shadow_color: Color::rgba_u8(0, 0, 0, 100),
                 shadow_padding: ShadowPadding::diagonal(2.0, 5.0),
                 height: 20.0,
@@ -533,6 +676,104 @@ impl InterfaceTheme {
                 vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
                 overflow_behavior: OverflowBehavior::LineBreak,
             },
+            slider: SliderTheme {
+                background_color: Color::monochrome_u8(100),
+                foreground_color: Color::monochrome_u8(220),
+                highlight_color: Color::rgb_u8(255, 160, 60),
+                hovered_background_color: Color::monochrome_u8(130),
+                rail_color: Color::monochrome_u8(60),
+                filled_rail_color: Color::rgb_u8(255, 160, 60),
+                knob_color: Color::monochrome_u8(200),
+                hovered_knob_color: Color::monochrome_u8(250),
+                shadow_color: Color::rgba_u8(0, 0, 0, 100),
+                shadow_padding: ShadowPadding::diagonal(2.0, 5.0),
+                height: 20.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                rail_height: 4.0,
+                knob_width: 10.0,
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 10.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            progress_bar: ProgressBarTheme {
+                background_color: Color::monochrome_u8(60),
+                foreground_color: Color::monochrome_u8(220),
+                highlight_color: Color::rgb_u8(255, 160, 60),
+                bar_color: Color::rgb_u8(255, 160, 60),
+                shadow_color: Color::rgba_u8(0, 0, 0, 100),
+                shadow_padding: ShadowPadding::diagonal(2.0, 5.0),
+                height: 20.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                border: 3.0,
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Center { offset: 0.0, border: 5.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            number_input: NumberInputTheme {
+                background_color: Color::monochrome_u8(100),
+                foreground_color: Color::monochrome_u8(220),
+                highlight_color: Color::rgb_u8(255, 160, 60),
+                hovered_background_color: Color::monochrome_u8(130),
+                button_foreground_color: Color::monochrome_u8(220),
+                button_background_color: Color::monochrome_u8(120),
+                hovered_button_background_color: Color::monochrome_u8(150),
+                shadow_color: Color::rgba_u8(0, 0, 0, 100),
+                shadow_padding: ShadowPadding::diagonal(2.0, 5.0),
+                height: 20.0,
+                corner_diameter: CornerDiameter::uniform(10.0),
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 10.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            table: TableTheme {
+                background_color: Color::monochrome_u8(60),
+                foreground_color: Color::monochrome_u8(220),
+                highlight_color: Color::rgb_u8(255, 160, 60),
+                header_foreground_color: Color::monochrome_u8(240),
+                header_background_color: Color::monochrome_u8(100),
+                hovered_header_background_color: Color::monochrome_u8(130),
+                row_background_color: Color::monochrome_u8(70),
+                alternate_row_background_color: Color::monochrome_u8(80),
+                hovered_row_background_color: Color::monochrome_u8(120),
+                separator_color: Color::monochrome_u8(50),
+                icon_color: Color::monochrome_u8(240),
+                shadow_color: Color::rgba_u8(0, 0, 0, 100),
+                shadow_padding: ShadowPadding::diagonal(2.0, 5.0),
+                corner_diameter: CornerDiameter::uniform(10.0),
+                header_height: 20.0,
+                row_height: 18.0,
+                maximum_height: 400.0,
+                minimum_column_width: 30.0,
+                icon_size: 10.0,
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 5.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
+            context_menu: ContextMenuTheme {
+                foreground_color: Color::monochrome_u8(220),
+                disabled_foreground_color: Color::monochrome_u8(130),
+                highlight_color: Color::rgb_u8(255, 160, 60),
+                background_color: Color::monochrome_u8(60),
+                hovered_background_color: Color::monochrome_u8(110),
+                separator_color: Color::monochrome_u8(90),
+                shadow_color: Color::rgba_u8(0, 0, 0, 100),
+                shadow_padding: ShadowPadding::diagonal(5.0, 10.0),
+                corner_diameter: CornerDiameter::uniform(10.0),
+                item_corner_diameter: CornerDiameter::uniform(8.0),
+                width: 150.0,
+                border: 4.0,
+                item_height: 20.0,
+                separator_height: 7.0,
+                icon_size: 10.0,
+                font_size: FontSize(14.0),
+                horizontal_alignment: HorizontalAlignment::Left { offset: 5.0, border: 3.0 },
+                vertical_alignment: VerticalAlignment::Center { offset: -2.0 },
+                overflow_behavior: OverflowBehavior::Shrink,
+            },
             tooltip: TooltipTheme {
                 background_color: Color::rgba_u8(15, 15, 15, 200),
                 foreground_color: Color::monochrome_u8(235),
@@ -546,6 +787,14 @@ impl InterfaceTheme {
                 mouse_offset: 16.0,
                 overflow_behavior: OverflowBehavior::LineBreak,
             },
+            animation: AnimationTheme {
+                easing: Easing::EaseOut,
+                window_duration: 0.15,
+                window_offset: 10.0,
+                collapsible_duration: 0.15,
+                tooltip_duration: 0.1,
+                hover_duration: 0.08,
+            },
             debug_button: DebugButtonTheme {
                 foreground_color: Color::rgb_u8(255, 167, 89),
                 hovered_background_color: Color::rgb_u8(225, 199, 115),
@@ -571,6 +820,9 @@ impl InterfaceTheme {
                 points_font_size: FontSize(12.0),
                 arrow_color: Color::monochrome_u8(160),
                 lower_level_color: Color::rgb_u8(255, 200, 255),
+                cooldown_color: Color::rgba_u8(0, 0, 0, 160),
+                cooldown_text_color: Color::WHITE,
+                cooldown_font_size: FontSize(16.0),
             },
             global: GlobalTheme {
                 drop_area_color: Color::rgba_u8(255, 60, 200, 120),
@@ -87,12 +87,73 @@ impl Default for CursorTheme {
     }
 }
 
+#[derive(Serialize, Deserialize, RustState, StateElement)]
+pub struct ChatRoomTheme {
+    pub background_color: Color,
+    pub public_color: Color,
+    pub private_color: Color,
+    pub font_size: FontSize,
+    pub padding: ScreenSize,
+    /// Height of the balloon above the owner in world units.
+    pub height: f32,
+}
+
+impl Default for ChatRoomTheme {
+    fn default() -> Self {
+        Self {
+            background_color: Color::rgba_u8(40, 40, 40, 200),
+            public_color: Color::monochrome_u8(255),
+            private_color: Color::rgb_u8(255, 200, 120),
+            font_size: FontSize(14.0),
+            padding: ScreenSize { width: 8.0, height: 4.0 },
+            height: 25.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, RustState, StateElement)]
+pub struct CastTheme {
+    pub background_color: Color,
+    pub bar_color: Color,
+    pub bar_size: ScreenSize,
+    pub border_size: ScreenSize,
+    /// Height of the cast bar above the caster in world units.
+    pub height: f32,
+    /// Color of the part of the ground circle that is already filled.
+    pub circle_color: Color,
+    /// Color of the part of the ground circle that is not filled yet.
+    pub circle_background_color: Color,
+    /// Radius of the ground circle in world units.
+    pub circle_radius: f32,
+    pub circle_dot_count: usize,
+    pub circle_dot_size: ScreenSize,
+}
+
+impl Default for CastTheme {
+    fn default() -> Self {
+        Self {
+            background_color: Color::monochrome_u8(40),
+            bar_color: Color::rgb_u8(90, 180, 255),
+            bar_size: ScreenSize { width: 60.0, height: 5.0 },
+            border_size: ScreenSize { width: 2.0, height: 1.0 },
+            height: 30.0,
+            circle_color: Color::rgba_u8(90, 180, 255, 220),
+            circle_background_color: Color::rgba_u8(40, 40, 40, 120),
+            circle_radius: 12.0,
+            circle_dot_count: 32,
+            circle_dot_size: ScreenSize { width: 4.0, height: 4.0 },
+        }
+    }
+}
+
 #[derive(Default, Serialize, Deserialize, RustState, StateElement)]
 pub struct WorldTheme {
     pub overlay: OverlayTheme,
     pub status_bar: StatusBarTheme,
     pub indicator: IndicatorTheme,
     pub cursor: CursorTheme,
+    pub chat_room: ChatRoomTheme,
+    pub cast: CastTheme,
 }
 
 impl WorldTheme {
@@ -2,7 +2,7 @@ use std::string::String;
 use std::sync::Arc;
 
 use arrayvec::ArrayVec;
-use cgmath::{EuclideanSpace, Point3, Vector2, VectorSpace};
+use cgmath::{EuclideanSpace, Point3, Vector2, Vector3, VectorSpace};
 use korangar_audio::{AudioEngine, SoundEffectKey};
 #[cfg(feature = "debug")]
 use korangar_debug::logging::Colorize;
@@ -23,7 +23,7 @@ use wgpu::{BufferUsages, Device, Queue};
 use crate::graphics::reduce_vertices;
 #[cfg(feature = "debug")]
 use crate::graphics::{BindlessSupport, DebugRectangleInstruction};
-use crate::graphics::{EntityInstruction, ScreenPosition, ScreenSize};
+use crate::graphics::{EntityInstruction, PickerRectangleInstruction, PickerTarget, ScreenPosition, ScreenSize};
 use crate::loaders::GameFileLoader;
 #[cfg(feature = "debug")]
 use crate::loaders::{GAT_TILE_SIZE, split_mesh_by_texture};
@@ -31,6 +31,7 @@ use crate::renderer::GameInterfaceRenderer;
 #[cfg(feature = "debug")]
 use crate::renderer::MarkerRenderer;
 use crate::state::ClientState;
+use crate::state::chat_room::ChatRoomBalloon;
 use crate::state::theme::{InterfaceThemeType, WorldTheme};
 use crate::world::{
     ActionEvent, AnimationData, AnimationState, Camera, FadeDirection, FadeState, IsBabyJob, JobIdentity, Library, MAX_WALK_PATH_SIZE, Map,
@@ -85,6 +86,30 @@ impl Movement {
     }
 }
 
+/// Skill that an entity is currently casting.
+#[derive(Clone)]
+pub struct Cast {
+    start_time: ClientTick,
+    duration_ms: u32,
+    /// Position of the target tile for skills that are cast on the ground.
+    target_position: Option<Point3<f32>>,
+}
+
+impl Cast {
+    fn progress(&self, client_tick: ClientTick) -> f32 {
+        if self.duration_ms == 0 {
+            return 1.0;
+        }
+
+        let elapsed = client_tick.0.wrapping_sub(self.start_time.0);
+        (elapsed as f32 / self.duration_ms as f32).min(1.0)
+    }
+
+    fn is_over(&self, client_tick: ClientTick) -> bool {
+        client_tick.0.wrapping_sub(self.start_time.0) >= self.duration_ms
+    }
+}
+
 #[cfg(feature = "debug")]
 #[derive(Clone)]
 pub struct Pathing {
@@ -102,6 +127,7 @@ pub struct Step {
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum EntityType {
     Hidden,
+    Homunculus,
     Monster,
     Npc,
     Player,
@@ -114,6 +140,7 @@ impl From<JobId> for EntityType {
             45 => EntityType::Warp,
             111 => EntityType::Hidden,
             0..=44 | 4000..=5999 => EntityType::Player,
+            6001..=6016 | 6048..=6052 => EntityType::Homunculus,
             46..=999 | 10000..=19999 => EntityType::Npc,
             1000..=3999 | 20000..=29999 => EntityType::Monster,
             _ => EntityType::Npc,
@@ -161,6 +188,7 @@ pub struct Common {
     pub direction: Direction,
     pub head_direction: usize,
     pub sex: Sex,
+    pub cart_type: Option<usize>,
 
     #[hidden_element]
     pub entity_type: EntityType,
@@ -178,6 +206,8 @@ pub struct Common {
     sound_state: SoundState,
     #[hidden_element]
     fade_state: FadeState,
+    #[hidden_element]
+    cast: Option<Cast>,
 }
 
 #[cfg_attr(feature = "debug", korangar_debug::profile)]
@@ -332,7 +362,14 @@ fn get_sprite_path_for_player_job(job_id: JobId) -> &'static str {
     }
 }
 
-fn get_entity_part_files(library: &Library, entity_type: EntityType, job_id: JobId, sex: Sex, head: Option<usize>) -> Vec<String> {
+fn get_entity_part_files(
+    library: &Library,
+    entity_type: EntityType,
+    job_id: JobId,
+    sex: Sex,
+    head: Option<usize>,
+    cart_type: Option<usize>,
+) -> Vec<String> {
     let sex_sprite_path = match sex == Sex::Female {
         true => "여",
         false => "남",
@@ -351,6 +388,10 @@ fn get_entity_part_files(library: &Library, entity_type: EntityType, job_id: Job
         format!("인간족\\머리통\\{}\\{}_{}", sex_sprite_path, head_id, sex_sprite_path)
     }
 
+    fn cart_path(cart_type: usize) -> String {
+        format!("이팩트\\손수레{}", cart_type)
+    }
+
     let head_id = match (sex, head) {
         (Sex::Male, Some(head)) if (0..MALE_HAIR_LOOKUP.len()).contains(&head) => MALE_HAIR_LOOKUP[head],
         (Sex::Male, Some(head)) => head,
@@ -360,12 +401,23 @@ fn get_entity_part_files(library: &Library, entity_type: EntityType, job_id: Job
     };
 
     match entity_type {
-        EntityType::Player => vec![
-            player_body_path(sex_sprite_path, job_id),
-            player_head_path(sex_sprite_path, head_id),
-        ],
+        EntityType::Player => {
+            let mut part_files = vec![
+                player_body_path(sex_sprite_path, job_id),
+                player_head_path(sex_sprite_path, head_id),
+            ];
+
+            // The cart is rendered as an additional part of the player, so it follows all
+            // the actions of the body.
+            if let Some(cart_type) = cart_type {
+                part_files.push(cart_path(cart_type));
+            }
+
+            part_files
+        }
         EntityType::Npc => vec![format!("npc\\{}", library.get::<JobIdentity>(job_id).to_string())],
         EntityType::Monster => vec![format!("몬스터\\{}", library.get::<JobIdentity>(job_id).to_string())],
+        EntityType::Homunculus => vec![format!("homun\\{}", library.get::<JobIdentity>(job_id).to_string())],
         EntityType::Warp | EntityType::Hidden => vec![format!("npc\\{}", library.get::<JobIdentity>(job_id).to_string())], // TODO: change
     }
 }
@@ -387,6 +439,7 @@ impl Common {
         let health_points = entity_data.health_points as usize;
         let maximum_health_points = entity_data.maximum_health_points as usize;
         let sex = entity_data.sex;
+        let cart_type = entity_data.cart_type;
 
         let active_movement = None;
         let entity_type = job_id.into();
@@ -406,6 +459,7 @@ impl Common {
             direction,
             head_direction,
             sex,
+            cart_type,
             active_movement,
             entity_type,
             movement_speed,
@@ -417,12 +471,13 @@ impl Common {
             stopped_moving: false,
             sound_state: SoundState::default(),
             fade_state: FadeState::new(FADE_IN_DURATION_MS, client_tick),
+            cast: None,
             scale,
         }
     }
 
     pub fn get_entity_part_files(&self, library: &Library) -> Vec<String> {
-        get_entity_part_files(library, self.entity_type, self.job_id, self.sex, None)
+        get_entity_part_files(library, self.entity_type, self.job_id, self.sex, None, self.cart_type)
     }
 
     pub fn is_dead(&self) -> bool {
@@ -448,6 +503,10 @@ impl Common {
             self.fade_state = FadeState::Opaque;
         }
 
+        if self.cast.as_ref().is_some_and(|cast| cast.is_over(client_tick)) {
+            self.cast = None;
+        }
+
         if let Some(animation_data) = self.animation_data.as_ref() {
             if animation_data.is_animation_over(&self.animation_state)
                 && (self.animation_state.is_attack() || self.animation_state.is_pickup())
@@ -1104,7 +1163,14 @@ impl Player {
 
     pub fn get_entity_part_files(&self, library: &Library) -> Vec<String> {
         let common = self.get_common();
-        get_entity_part_files(library, common.entity_type, common.job_id, common.sex, Some(self.hair_id))
+        get_entity_part_files(
+            library,
+            common.entity_type,
+            common.job_id,
+            common.sex,
+            Some(self.hair_id),
+            common.cart_type,
+        )
     }
 }
 
@@ -1161,7 +1227,7 @@ impl Npc {
     }
 
     pub fn render_status(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, theme: &WorldTheme, window_size: ScreenSize) {
-        if self.common.entity_type != EntityType::Monster {
+        if !matches!(self.common.entity_type, EntityType::Monster | EntityType::Homunculus) {
             return;
         }
 
@@ -1293,6 +1359,14 @@ impl Entity {
         }
     }
 
+    pub fn get_cart_type(&self) -> Option<usize> {
+        self.get_common().cart_type
+    }
+
+    pub fn set_cart_type(&mut self, cart_type: Option<usize>) {
+        self.get_common_mut().cart_type = cart_type;
+    }
+
     pub fn set_animation_data(&mut self, animation_data: Arc<AnimationData>) {
         self.get_common_mut().animation_data = Some(animation_data)
     }
@@ -1367,6 +1441,20 @@ impl Entity {
         self.get_common().stopped_moving
     }
 
+    pub fn start_casting(&mut self, map: &Map, cast_time: u32, target_position: Option<TilePosition>, client_tick: ClientTick) {
+        let target_position = target_position.and_then(|position| map.get_world_position(position));
+
+        self.get_common_mut().cast = Some(Cast {
+            start_time: client_tick,
+            duration_ms: cast_time,
+            target_position,
+        });
+    }
+
+    pub fn stop_casting(&mut self) {
+        self.get_common_mut().cast = None;
+    }
+
     pub fn stop_movement(&mut self) {
         self.get_common_mut().active_movement = None;
     }
@@ -1425,6 +1513,124 @@ impl Entity {
         self.get_common().render_marker(renderer, camera, marker_identifier, hovered);
     }
 
+    /// Renders the title balloon of a chat room above the entity. Returns a
+    /// picker rectangle, so the balloon can be clicked.
+    pub fn render_chat_room_balloon(
+        &self,
+        renderer: &GameInterfaceRenderer,
+        camera: &dyn Camera,
+        theme: &WorldTheme,
+        window_size: ScreenSize,
+        balloon: &ChatRoomBalloon,
+    ) -> Option<PickerRectangleInstruction> {
+        let position = self.get_position() + Vector3::new(0.0, theme.chat_room.height, 0.0);
+        let clip_space_position = camera.view_projection_matrix() * position.to_homogeneous();
+
+        // Don't render balloons behind the camera.
+        if clip_space_position.w < 0.1 {
+            return None;
+        }
+
+        let screen_position = camera.clip_to_screen_space(clip_space_position);
+        let final_position = ScreenPosition {
+            left: screen_position.x * window_size.width,
+            top: screen_position.y * window_size.height,
+        };
+
+        let color = match balloon.requires_password() {
+            true => theme.chat_room.private_color,
+            false => theme.chat_room.public_color,
+        };
+
+        let (balloon_position, balloon_size) = renderer.render_balloon(
+            &balloon.text,
+            final_position,
+            color,
+            theme.chat_room.background_color,
+            theme.chat_room.font_size,
+            theme.chat_room.padding,
+        );
+
+        Some(PickerRectangleInstruction {
+            screen_position: balloon_position / window_size,
+            screen_size: balloon_size / window_size,
+            picker_target: PickerTarget::Entity(self.get_entity_id()),
+        })
+    }
+
+    /// Render the cast bar above the entity and, for skills that are cast on
+    /// the ground, a circle around the target tile that fills up while
+    /// casting.
+    pub fn render_cast(
+        &self,
+        renderer: &GameInterfaceRenderer,
+        camera: &dyn Camera,
+        theme: &WorldTheme,
+        window_size: ScreenSize,
+        client_tick: ClientTick,
+    ) {
+        let Some(cast) = &self.get_common().cast else {
+            return;
+        };
+
+        let progress = cast.progress(client_tick);
+
+        if let Some(target_position) = cast.target_position {
+            let dot_count = theme.cast.circle_dot_count.max(1);
+            let filled_dot_count = (dot_count as f32 * progress) as usize;
+
+            for index in 0..dot_count {
+                let angle = std::f32::consts::TAU * index as f32 / dot_count as f32;
+                let position = target_position + Vector3::new(angle.sin(), 0.0, -angle.cos()) * theme.cast.circle_radius;
+                let clip_space_position = camera.view_projection_matrix() * position.to_homogeneous();
+
+                // Don't render dots behind the camera.
+                if clip_space_position.w < 0.1 {
+                    continue;
+                }
+
+                let screen_position = camera.clip_to_screen_space(clip_space_position);
+                let final_position = ScreenPosition {
+                    left: screen_position.x * window_size.width,
+                    top: screen_position.y * window_size.height,
+                };
+
+                let color = match index < filled_dot_count {
+                    true => theme.cast.circle_color,
+                    false => theme.cast.circle_background_color,
+                };
+
+                renderer.render_rectangle(
+                    final_position - theme.cast.circle_dot_size / 2.0,
+                    theme.cast.circle_dot_size,
+                    color,
+                );
+            }
+        }
+
+        let position = self.get_position() + Vector3::new(0.0, theme.cast.height, 0.0);
+        let clip_space_position = camera.view_projection_matrix() * position.to_homogeneous();
+
+        // Don't render cast bars behind the camera.
+        if clip_space_position.w < 0.1 {
+            return;
+        }
+
+        let screen_position = camera.clip_to_screen_space(clip_space_position);
+        let final_position = ScreenPosition {
+            left: screen_position.x * window_size.width,
+            top: screen_position.y * window_size.height,
+        };
+
+        renderer.render_rectangle(
+            final_position - theme.cast.border_size - ScreenSize::only_width(theme.cast.bar_size.width / 2.0),
+            theme.cast.bar_size + theme.cast.border_size * 2.0,
+            theme.cast.background_color,
+        );
+
+        renderer.render_bar(final_position, theme.cast.bar_size, theme.cast.bar_color, 1.0, progress);
+    }
+
     pub fn render_status(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, theme: &WorldTheme, window_size: ScreenSize) {
         match self {
             Self::Player(player) => player.render_status(renderer, camera, theme, window_size),
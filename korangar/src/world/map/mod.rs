@@ -11,6 +11,7 @@ use korangar_collision::{AABB, Frustum, KDTree, Sphere};
 use korangar_container::{Cacheable, SimpleKey, SimpleSlab, create_simple_key};
 #[cfg(feature = "debug")]
 use korangar_debug::logging::Colorize;
+use korangar_pathing::Traversable;
 #[cfg(feature = "debug")]
 use option_ext::OptionExt;
 #[cfg(feature = "debug")]
@@ -36,7 +37,6 @@ use crate::graphics::{EntityInstruction, IndicatorInstruction, ModelInstruction,
 use crate::loaders::GAT_TILE_SIZE;
 #[cfg(feature = "debug")]
 use crate::renderer::MarkerRenderer;
-use crate::world::pathing::Traversable;
 use crate::{Buffer, Color, GameFileLoader, ModelVertex, TileVertex};
 
 create_simple_key!(ObjectKey, "Key to an object inside the map");
@@ -11,12 +11,13 @@ mod map;
 mod model;
 mod object;
 mod particles;
-mod pathing;
 mod sound;
 mod video;
 
 use std::sync::Arc;
 
+pub use korangar_pathing::*;
+
 pub use self::action::*;
 pub use self::animation::*;
 pub use self::cameras::*;
@@ -30,7 +31,6 @@ pub use self::map::*;
 pub use self::model::*;
 pub use self::object::*;
 pub use self::particles::*;
-pub use self::pathing::*;
 pub use self::sound::*;
 pub use self::video::*;
 use crate::graphics::Texture;
@@ -5,6 +5,7 @@ mod fixed;
 mod from_bytes;
 mod metadata;
 mod reader;
+mod stream;
 mod to_bytes;
 mod writer;
 
@@ -17,6 +18,7 @@ pub use self::fixed::{FixedByteSize, FixedByteSizeCollection};
 pub use self::from_bytes::{FromBytes, FromBytesExt};
 pub use self::metadata::{CastableMetadata, Caster, DynMetadata};
 pub use self::reader::ByteReader;
+pub use self::stream::{StreamReader, StreamSavePoint};
 pub use self::to_bytes::{ToBytes, ToBytesExt};
 pub use self::writer::ByteWriter;
 
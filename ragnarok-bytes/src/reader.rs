@@ -42,17 +42,29 @@ pub struct ByteReader<'a> {
 
 impl<'a> ByteReader<'a> {
     fn new_inner(data: &'a [u8], metadata: DynMetadata) -> Self {
+        Self::from_parts(data, EUC_KR, metadata)
+    }
+
+    /// Create a new [`ByteReader`] from the state of another reader, for
+    /// example a [`StreamReader`](crate::StreamReader).
+    pub(crate) fn from_parts(data: &'a [u8], encoding: &'static Encoding, metadata: DynMetadata) -> Self {
         let limit = data.len();
 
         Self {
             data,
-            encoding: EUC_KR,
+            encoding,
             offset: 0,
             limit,
             metadata,
         }
     }
 
+    /// Take back the metadata passed to
+    /// [`from_parts`](ByteReader::from_parts).
+    pub(crate) fn into_metadata(self) -> DynMetadata {
+        self.metadata
+    }
+
     /// Create a new [`ByteReader`] without metadata.
     pub fn without_metadata(data: &'a [u8]) -> Self {
         Self::new_inner(data, DynMetadata::new(()))
@@ -0,0 +1,557 @@
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+
+use encoding_rs::{EUC_KR, Encoding};
+
+use crate::metadata::{CastableMetadata, DynMetadata};
+use crate::{ByteReader, ConversionError, ConversionErrorType, ConversionResult, FromBytes};
+
+/// Minimum number of bytes requested from the source when the buffer runs
+/// out.
+const MINIMUM_READ_SIZE: usize = 8192;
+
+/// Saved state of a [`StreamReader`] that can be restored.
+///
+/// As long as a save point exists, the reader keeps all bytes after it in
+/// memory, so it should always be passed to either
+/// [`restore_save_point`](StreamReader::restore_save_point) or
+/// [`release_save_point`](StreamReader::release_save_point).
+#[derive(Debug, PartialEq, Eq)]
+pub struct StreamSavePoint {
+    position: u64,
+}
+
+enum ReadAttempt<T> {
+    Done(ConversionResult<T>),
+    NeedMoreData,
+}
+
+/// A reader of bytes that pulls its data from a [`Read`] source (or an
+/// [`AsyncRead`](tokio::io::AsyncRead) source with the `tokio` feature) as
+/// needed. It can read any type implementing [`FromBytes`] and carries
+/// metadata just like the [`ByteReader`].
+///
+/// Values are read by running their [`FromBytes`] implementation on the
+/// buffered data. If the buffered data is too short, more data is pulled from
+/// the source and the value is read again. Since some implementations (like
+/// the one for [`Vec`]) read until the end of the data, a value that ends
+/// exactly at the end of the buffered data is also read again after pulling
+/// more data. For sources that might not have more data available right away
+/// (like sockets), this can be disabled with
+/// [`set_framed`](StreamReader::set_framed).
+///
+/// Bytes are dropped from the buffer once they are read and no
+/// [`StreamSavePoint`] needs them anymore.
+///
+/// NOTE: Just like with the [`ByteReader`], neither save points nor reading a
+/// value again restore the previous state of the metadata.
+pub struct StreamReader<R> {
+    source: R,
+    buffer: Vec<u8>,
+    /// Position of the first byte in `buffer` inside the stream.
+    buffer_position: u64,
+    /// Offset of the next byte to read inside `buffer`.
+    offset: usize,
+    end_of_stream: bool,
+    framed: bool,
+    save_points: Vec<u64>,
+    encoding: &'static Encoding,
+    // Only `None` while a value is being read.
+    metadata: Option<DynMetadata>,
+}
+
+impl<R> StreamReader<R> {
+    fn new_inner(source: R, metadata: DynMetadata) -> Self {
+        Self {
+            source,
+            buffer: Vec::new(),
+            buffer_position: 0,
+            offset: 0,
+            end_of_stream: false,
+            framed: false,
+            save_points: Vec::new(),
+            encoding: EUC_KR,
+            metadata: Some(metadata),
+        }
+    }
+
+    /// Create a new [`StreamReader`] without metadata.
+    pub fn without_metadata(source: R) -> Self {
+        Self::new_inner(source, DynMetadata::new(()))
+    }
+
+    /// Create a new [`StreamReader`] with default metadata.
+    pub fn with_default_metadata<T: CastableMetadata + Default>(source: R) -> Self {
+        let mut metadata = DynMetadata::new(T::default());
+        T::register(&mut metadata);
+
+        Self::new_inner(source, metadata)
+    }
+
+    /// Create a new [`StreamReader`] with provided metadata.
+    pub fn with_metadata<T: CastableMetadata>(source: R, metadata: T) -> Self {
+        let mut metadata = DynMetadata::new(metadata);
+        T::register(&mut metadata);
+
+        Self::new_inner(source, metadata)
+    }
+
+    /// Sets the encoding used to decode strings.
+    pub fn set_encoding(&mut self, encoding: &'static Encoding) {
+        self.encoding = encoding;
+    }
+
+    /// Framed data, like packets, never reads past the end of a value. So if
+    /// `framed` is true, a value that ends exactly at the end of the buffered
+    /// data is considered complete instead of reading it again after pulling
+    /// more data from the source.
+    pub fn set_framed(&mut self, framed: bool) {
+        self.framed = framed;
+    }
+
+    /// Position of the next byte to read inside the stream.
+    pub fn get_position(&self) -> u64 {
+        self.buffer_position + self.offset as u64
+    }
+
+    /// Number of bytes that are currently buffered but not yet read.
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len() - self.offset
+    }
+
+    pub fn create_save_point(&mut self) -> StreamSavePoint {
+        let position = self.get_position();
+        self.save_points.push(position);
+
+        StreamSavePoint { position }
+    }
+
+    /// Restore a save point. Fails if the save point was invalidated by
+    /// [`seek`](StreamReader::seek).
+    pub fn restore_save_point(&mut self, save_point: StreamSavePoint) -> ConversionResult<()> {
+        if !self.save_points.contains(&save_point.position) {
+            return Err(ConversionError::from_message("save point was invalidated by seeking"));
+        }
+
+        // The buffer is never drained past a save point, so the position is always
+        // buffered.
+        self.offset = (save_point.position - self.buffer_position) as usize;
+        self.release_save_point(save_point);
+
+        Ok(())
+    }
+
+    /// Drop a save point without restoring it.
+    pub fn release_save_point(&mut self, save_point: StreamSavePoint) {
+        if let Some(index) = self.save_points.iter().position(|position| *position == save_point.position) {
+            self.save_points.swap_remove(index);
+        }
+    }
+
+    pub fn get_metadata<Caller, As>(&self) -> ConversionResult<&As>
+    where
+        As: ?Sized + 'static,
+    {
+        self.metadata.as_ref().and_then(DynMetadata::get).ok_or_else(|| {
+            ConversionError::from_error_type(ConversionErrorType::IncorrectMetadata {
+                type_name: std::any::type_name::<Caller>(),
+            })
+        })
+    }
+
+    pub fn get_metadata_mut<Caller, As>(&mut self) -> ConversionResult<&mut As>
+    where
+        As: ?Sized + 'static,
+    {
+        self.metadata.as_mut().and_then(DynMetadata::get_mut).ok_or_else(|| {
+            ConversionError::from_error_type(ConversionErrorType::IncorrectMetadata {
+                type_name: std::any::type_name::<Caller>(),
+            })
+        })
+    }
+
+    /// Get the source back. Any bytes that are buffered but not yet read are
+    /// lost.
+    pub fn into_inner(self) -> R {
+        self.source
+    }
+
+    fn try_read<T>(&mut self, read: &mut impl FnMut(&mut ByteReader) -> ConversionResult<T>) -> ReadAttempt<T> {
+        let data = &self.buffer[self.offset..];
+        let metadata = self.metadata.take().expect("metadata is missing");
+
+        let mut byte_reader = ByteReader::from_parts(data, self.encoding, metadata);
+        let result = read(&mut byte_reader);
+        let read_bytes = byte_reader.get_offset();
+        let reached_end = byte_reader.is_empty();
+
+        self.metadata = Some(byte_reader.into_metadata());
+
+        match result {
+            Err(error) if error.is_byte_reader_too_short() && !self.end_of_stream => ReadAttempt::NeedMoreData,
+            // The value might only have stopped because there was no more data buffered.
+            Ok(_) if reached_end && !self.framed && !self.end_of_stream => ReadAttempt::NeedMoreData,
+            Ok(value) => {
+                self.offset += read_bytes;
+                ReadAttempt::Done(Ok(value))
+            }
+            Err(error) => ReadAttempt::Done(Err(error)),
+        }
+    }
+
+    /// Drop all bytes that are no longer needed and make room for at least
+    /// [`MINIMUM_READ_SIZE`] new bytes. Returns the previous length of the
+    /// buffer.
+    fn prepare_fill(&mut self) -> usize {
+        let oldest_save_point = self
+            .save_points
+            .iter()
+            .map(|position| (position - self.buffer_position) as usize)
+            .min()
+            .unwrap_or(self.offset);
+        let drain_end = oldest_save_point.min(self.offset);
+
+        self.buffer.drain(..drain_end);
+        self.buffer_position += drain_end as u64;
+        self.offset -= drain_end;
+
+        let length = self.buffer.len();
+        self.buffer.resize(length + length.max(MINIMUM_READ_SIZE), 0);
+
+        length
+    }
+
+    fn finish_fill(&mut self, previous_length: usize, read_result: std::io::Result<usize>) -> ConversionResult<()> {
+        let read_bytes = read_result.map_err(|error| {
+            self.buffer.truncate(previous_length);
+            ConversionError::from_message(format!("failed to read from source: {error}"))
+        })?;
+
+        self.buffer.truncate(previous_length + read_bytes);
+        self.end_of_stream = read_bytes == 0;
+
+        Ok(())
+    }
+}
+
+impl<R> StreamReader<R>
+where
+    R: Read,
+{
+    fn fill(&mut self) -> ConversionResult<()> {
+        let previous_length = self.prepare_fill();
+
+        let read_result = loop {
+            match self.source.read(&mut self.buffer[previous_length..]) {
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                read_result => break read_result,
+            }
+        };
+
+        self.finish_fill(previous_length, read_result)
+    }
+
+    /// Read a value of type `T`, pulling more data from the source if needed.
+    pub fn read<T: FromBytes>(&mut self) -> ConversionResult<T> {
+        self.read_with(T::from_bytes)
+    }
+
+    /// Read a value with a custom function, pulling more data from the source
+    /// if needed. `read` might be called multiple times.
+    pub fn read_with<T>(&mut self, mut read: impl FnMut(&mut ByteReader) -> ConversionResult<T>) -> ConversionResult<T> {
+        loop {
+            match self.try_read(&mut read) {
+                ReadAttempt::Done(result) => return result,
+                ReadAttempt::NeedMoreData => self.fill()?,
+            }
+        }
+    }
+
+    /// Check if the end of the stream was reached.
+    pub fn is_empty(&mut self) -> ConversionResult<bool> {
+        while self.buffered_len() == 0 && !self.end_of_stream {
+            self.fill()?;
+        }
+
+        Ok(self.buffered_len() == 0)
+    }
+}
+
+impl<R> StreamReader<R>
+where
+    R: Read + Seek,
+{
+    /// Seek to a position inside the stream. Seeking to a position outside of
+    /// the buffered data drops the buffer and invalidates all save points.
+    pub fn seek(&mut self, position: SeekFrom) -> ConversionResult<u64> {
+        let target = match position {
+            SeekFrom::Start(target) => Some(target),
+            SeekFrom::Current(delta) => self.get_position().checked_add_signed(delta),
+            SeekFrom::End(_) => None,
+        };
+
+        if let Some(target) = target
+            && target >= self.buffer_position
+            && target <= self.buffer_position + self.buffer.len() as u64
+        {
+            self.offset = (target - self.buffer_position) as usize;
+            return Ok(target);
+        }
+
+        let position = match target {
+            Some(target) => SeekFrom::Start(target),
+            None => position,
+        };
+        let new_position = self
+            .source
+            .seek(position)
+            .map_err(|error| ConversionError::from_message(format!("failed to seek in source: {error}")))?;
+
+        self.buffer.clear();
+        self.buffer_position = new_position;
+        self.offset = 0;
+        self.end_of_stream = false;
+        self.save_points.clear();
+
+        Ok(new_position)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R> StreamReader<R>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    async fn fill_async(&mut self) -> ConversionResult<()> {
+        use tokio::io::AsyncReadExt;
+
+        let previous_length = self.prepare_fill();
+        let read_result = self.source.read(&mut self.buffer[previous_length..]).await;
+
+        self.finish_fill(previous_length, read_result)
+    }
+
+    /// Read a value of type `T`, waiting for more data from the source if
+    /// needed.
+    pub async fn read_async<T: FromBytes>(&mut self) -> ConversionResult<T> {
+        self.read_with_async(T::from_bytes).await
+    }
+
+    /// Read a value with a custom function, waiting for more data from the
+    /// source if needed. `read` might be called multiple times.
+    pub async fn read_with_async<T>(&mut self, mut read: impl FnMut(&mut ByteReader) -> ConversionResult<T>) -> ConversionResult<T> {
+        loop {
+            match self.try_read(&mut read) {
+                ReadAttempt::Done(result) => return result,
+                ReadAttempt::NeedMoreData => self.fill_async().await?,
+            }
+        }
+    }
+
+    /// Check if the end of the stream was reached, waiting for more data from
+    /// the source if needed.
+    pub async fn is_empty_async(&mut self) -> ConversionResult<bool> {
+        while self.buffered_len() == 0 && !self.end_of_stream {
+            self.fill_async().await?;
+        }
+
+        Ok(self.buffered_len() == 0)
+    }
+}
+
+#[cfg(test)]
+mod streaming {
+    use std::io::{Cursor, Read, SeekFrom};
+
+    use super::StreamReader;
+    use crate::metadata::{CastableMetadata, Caster, DynMetadata};
+    use crate::{ByteReader, ConversionResult, FromBytes, FromBytesExt};
+
+    /// Source that only returns a few bytes at a time, like a socket.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        chunk_size: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            let count = self.chunk_size.min(buffer.len()).min(self.data.len());
+            buffer[..count].copy_from_slice(&self.data[..count]);
+            self.data = &self.data[count..];
+            Ok(count)
+        }
+    }
+
+    #[test]
+    fn values_across_reads() {
+        let mut stream_reader = StreamReader::without_metadata(Trickle {
+            data: &[0x11, 0x22, 0x33, 0x44, 0x55, 0x66, b'a', b'b', b'c', 0, 0x77],
+            chunk_size: 3,
+        });
+
+        assert_eq!(stream_reader.read::<u32>().unwrap(), 0x44332211);
+        assert_eq!(stream_reader.read::<u16>().unwrap(), 0x6655);
+        assert_eq!(stream_reader.read::<String>().unwrap(), "abc");
+        assert_eq!(stream_reader.read::<u8>().unwrap(), 0x77);
+        assert!(stream_reader.is_empty().unwrap());
+        assert!(stream_reader.read::<u8>().unwrap_err().is_byte_reader_too_short());
+    }
+
+    #[test]
+    fn framed() {
+        /// Source that has no more data right now, like a non-blocking socket.
+        struct WouldBlock<'a>(&'a [u8]);
+
+        impl Read for WouldBlock<'_> {
+            fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+                match self.0.is_empty() {
+                    true => Err(std::io::ErrorKind::WouldBlock.into()),
+                    false => self.0.read(buffer),
+                }
+            }
+        }
+
+        let mut stream_reader = StreamReader::without_metadata(WouldBlock(&[0x11, 0x22, 0x33, 0x44]));
+        assert!(stream_reader.read::<u32>().is_err());
+
+        let mut stream_reader = StreamReader::without_metadata(WouldBlock(&[0x11, 0x22, 0x33, 0x44]));
+        stream_reader.set_framed(true);
+        assert_eq!(stream_reader.read::<u32>().unwrap(), 0x44332211);
+    }
+
+    #[test]
+    fn read_until_end() {
+        let data: Vec<u8> = (0..20000).map(|index| index as u8).collect();
+        let mut stream_reader = StreamReader::without_metadata(Trickle {
+            data: &data,
+            chunk_size: 1000,
+        });
+
+        assert_eq!(stream_reader.read::<Vec<u8>>().unwrap(), data);
+    }
+
+    #[test]
+    fn read_with() {
+        let mut stream_reader = StreamReader::without_metadata(Trickle {
+            data: b"abcdef",
+            chunk_size: 1,
+        });
+
+        let value = stream_reader.read_with(|byte_reader| String::from_n_bytes(byte_reader, 4)).unwrap();
+
+        assert_eq!(value, "abcd");
+        assert_eq!(stream_reader.get_position(), 4);
+    }
+
+    #[test]
+    fn save_point() {
+        let data: Vec<u8> = (0..40000).map(|index| index as u8).collect();
+        let mut stream_reader = StreamReader::without_metadata(Trickle {
+            data: &data,
+            chunk_size: 5000,
+        });
+
+        stream_reader.read::<u8>().unwrap();
+        let save_point = stream_reader.create_save_point();
+
+        // Read far enough for the buffer to be refilled multiple times.
+        for _ in 0..30000 {
+            stream_reader.read::<u8>().unwrap();
+        }
+
+        stream_reader.restore_save_point(save_point).unwrap();
+
+        assert_eq!(stream_reader.get_position(), 1);
+        assert_eq!(stream_reader.read::<u8>().unwrap(), 1);
+    }
+
+    #[test]
+    fn seek_invalidates_save_points() {
+        let data: Vec<u8> = (0..40000u32).flat_map(|index| index.to_le_bytes()).collect();
+        let mut stream_reader = StreamReader::without_metadata(Cursor::new(data));
+
+        stream_reader.seek(SeekFrom::Start(4 * 30000)).unwrap();
+        let save_point = stream_reader.create_save_point();
+
+        stream_reader.seek(SeekFrom::Start(4)).unwrap();
+
+        assert!(stream_reader.restore_save_point(save_point).is_err());
+        assert_eq!(stream_reader.read::<u32>().unwrap(), 1);
+    }
+
+    #[test]
+    fn seek() {
+        let data: Vec<u8> = (0..40000u32).flat_map(|index| index.to_le_bytes()).collect();
+        let mut stream_reader = StreamReader::without_metadata(Cursor::new(data));
+
+        stream_reader.seek(SeekFrom::Start(4 * 30000)).unwrap();
+        assert_eq!(stream_reader.read::<u32>().unwrap(), 30000);
+
+        stream_reader.seek(SeekFrom::Current(4)).unwrap();
+        assert_eq!(stream_reader.read::<u32>().unwrap(), 30002);
+
+        stream_reader.seek(SeekFrom::End(-4)).unwrap();
+        assert_eq!(stream_reader.read::<u32>().unwrap(), 39999);
+
+        stream_reader.seek(SeekFrom::Start(4)).unwrap();
+        assert_eq!(stream_reader.read::<u32>().unwrap(), 1);
+    }
+
+    #[test]
+    fn metadata() {
+        struct Version(u8);
+
+        struct Versioned(u8);
+
+        impl FromBytes for Versioned {
+            fn from_bytes(byte_reader: &mut ByteReader) -> ConversionResult<Self> {
+                let version = byte_reader.get_metadata::<Self, Version>()?.0;
+                Ok(Self(u8::from_bytes(byte_reader)? + version))
+            }
+        }
+
+        impl CastableMetadata for Version {
+            fn register(metadata: &mut DynMetadata) {
+                metadata.register_caster(Caster::<Version>::new(
+                    |any| any.downcast_ref::<Version>(),
+                    |any| any.downcast_mut::<Version>(),
+                ));
+            }
+        }
+
+        let mut stream_reader = StreamReader::with_metadata(Cursor::new([1, 2]), Version(10));
+
+        assert_eq!(stream_reader.read::<Versioned>().unwrap().0, 11);
+
+        stream_reader.get_metadata_mut::<(), Version>().unwrap().0 = 20;
+
+        assert_eq!(stream_reader.read::<Versioned>().unwrap().0, 22);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn async_source() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+        runtime.block_on(async {
+            let (mut sender, receiver) = tokio::io::duplex(4);
+            let mut stream_reader = StreamReader::without_metadata(receiver);
+
+            let write = async {
+                use tokio::io::AsyncWriteExt;
+
+                sender.write_all(&[0x11, 0x22, 0x33, 0x44, b'h', b'i', 0]).await.unwrap();
+                drop(sender);
+            };
+            let read = async {
+                let number = stream_reader.read_async::<u32>().await.unwrap();
+                let string = stream_reader.read_async::<String>().await.unwrap();
+
+                assert_eq!(number, 0x44332211);
+                assert_eq!(string, "hi");
+                assert!(stream_reader.is_empty_async().await.unwrap());
+            };
+
+            tokio::join!(write, read);
+        });
+    }
+}
@@ -109,6 +109,12 @@ impl ByteWriter {
         self.data.as_slice()
     }
 
+    /// Returns a mutable slice to the inner bytes.
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        self.data.as_mut_slice()
+    }
+
     /// Clears the inner bytes.
     pub fn clear(&mut self) {
         self.data.clear();
@@ -33,10 +33,9 @@ pub fn byte_convertable_helper(data_struct: DataStruct) -> (TokenStream, Vec<Tok
             syn::Meta::Path(_) | syn::Meta::NameValue(_) => panic!("expected token stream in attribute"),
         });
         let length_remaining = get_unique_attribute(&mut field.attrs, "length_remaining").is_some();
-        let length_remaining_off_by_one = get_unique_attribute(&mut field.attrs, "length_remaining_off_by_one").is_some();
 
-        if (length.is_some() as usize) + (length_remaining as usize) + (length_remaining_off_by_one as usize) > 1 {
-            panic!("only one of `length`, `length_remaining`, or `length_remaining_off_by_one` can be used for one field at a time");
+        if length.is_some() && length_remaining {
+            panic!("only one of `length` or `length_remaining` can be used for one field at a time");
         }
 
         let from_length = match length.clone() {
@@ -47,10 +46,6 @@ pub fn byte_convertable_helper(data_struct: DataStruct) -> (TokenStream, Vec<Tok
                 byte_reader,
                 (__packet_length as usize).saturating_sub(2 + (byte_reader.get_offset() - base_offset))
             )),
-            None if length_remaining_off_by_one => quote!(ragnarok_bytes::FromBytesExt::from_n_bytes(
-                byte_reader,
-                (__packet_length as usize).saturating_sub(1 + (byte_reader.get_offset() - base_offset))
-            )),
             None => quote!(ragnarok_bytes::FromBytes::from_bytes(byte_reader)),
         };
 
@@ -136,7 +136,6 @@ pub fn derive_to_bytes(token_stream: InterfaceTokenStream) -> InterfaceTokenStre
         header,
         length,
         length_remaining,
-        length_remaining_off_by_one,
         new_default,
         new_derive,
         new_value,
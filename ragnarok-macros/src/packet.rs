@@ -1,6 +1,7 @@
 use proc_macro::TokenStream as InterfaceTokenStream;
+use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Attribute, DataStruct, Generics, Ident};
+use syn::{Attribute, DataStruct, Fields, Generics, Ident, Type};
 
 use super::helper::byte_convertable_helper;
 use crate::utils::{PacketSignature, get_unique_attribute};
@@ -21,6 +22,8 @@ pub fn derive_packet_struct(
     let is_variable_length = get_unique_attribute(&mut attributes, "variable_length").is_some();
 
     let signature = packet_signature.signature;
+    let packet_length = packet_length_implementation(&data_struct, is_variable_length);
+    let serde_implementation = packet_serde_implementation(&data_struct, &generics, &name);
     let (new_implementation, from_bytes_implementations, implemented_fields, to_bytes_implementations, delimiter) =
         byte_convertable_helper(data_struct);
 
@@ -86,6 +89,10 @@ pub fn derive_packet_struct(
                 #final_to_bytes
             }
 
+            fn packet_length() -> ragnarok_packets::PacketLength {
+                #packet_length
+            }
+
             #[cfg(feature = "packet-to-state-element")]
             fn to_element<App: korangar_interface::application::Application>(
                 self_path: impl rust_state::Path<App, Self>,
@@ -94,6 +101,130 @@ pub fn derive_packet_struct(
                 korangar_interface::element::ErasedElement::new(<Self as korangar_interface::element::StateElement<App>>::to_element(self_path, name))
             }
         }
+
+        #serde_implementation
     }
     .into()
 }
+
+/// Get the length of a packet, including the header. Packets that are not
+/// variable length must only contain fields that implement `FixedByteSize`
+/// or have a `length` attribute.
+fn packet_length_implementation(data_struct: &DataStruct, is_variable_length: bool) -> TokenStream {
+    if is_variable_length {
+        return quote!(ragnarok_packets::PacketLength::Variable);
+    }
+
+    let sizes = data_struct.fields.iter().map(|field| {
+        let field_type = &field.ty;
+
+        field
+            .attrs
+            .iter()
+            .find(|attribute| attribute.path().is_ident("length"))
+            .map(|attribute| match &attribute.meta {
+                syn::Meta::List(list) => {
+                    let length = &list.tokens;
+                    quote!((#length) as usize)
+                }
+                syn::Meta::Path(_) | syn::Meta::NameValue(_) => panic!("expected token stream in attribute"),
+            })
+            .unwrap_or(quote!(<#field_type as ragnarok_bytes::FixedByteSize>::size_in_bytes()))
+    });
+
+    quote! {
+        // The header is always two bytes long.
+        let mut total = 2;
+        #(total += #sizes;)*
+        ragnarok_packets::PacketLength::Fixed(total as u16)
+    }
+}
+
+/// Implement `Serialize` and `Deserialize` for a packet. Unlike deriving them
+/// on the struct directly, the serialized packet includes its header, which is
+/// checked when deserializing. Serializing goes through a shadow struct that
+/// borrows the fields, so the packet doesn't need to be cloned.
+fn packet_serde_implementation(data_struct: &DataStruct, generics: &Generics, name: &Ident) -> TokenStream {
+    // None of the packets are generic or tuple structs, so there is no need to
+    // support them.
+    let Fields::Named(fields) = &data_struct.fields else {
+        return quote!();
+    };
+
+    if !generics.params.is_empty() {
+        return quote!();
+    }
+
+    let name_string = name.to_string();
+    let field_identifiers: Vec<_> = fields.named.iter().map(|field| field.ident.clone().unwrap()).collect();
+    // Serde only implements its traits for arrays with up to 32 elements.
+    let is_array = |field_type: &Type| matches!(field_type, Type::Array(..));
+
+    let shadow_fields = fields.named.iter().map(|field| {
+        let identifier = &field.ident;
+        let field_type = &field.ty;
+        let with_attribute =
+            is_array(field_type).then_some(quote!(#[serde(deserialize_with = "ragnarok_packets::serde_array::deserialize")]));
+
+        quote!(#with_attribute #identifier: #field_type)
+    });
+    let shadow_reference_fields = fields.named.iter().map(|field| {
+        let identifier = &field.ident;
+        let field_type = &field.ty;
+        let with_attribute =
+            is_array(field_type).then_some(quote!(#[serde(serialize_with = "ragnarok_packets::serde_array::serialize_reference")]));
+
+        quote!(#with_attribute #identifier: &'a #field_type)
+    });
+
+    // Packets without fields don't borrow anything, and an unused lifetime is an
+    // error.
+    let lifetime = (!field_identifiers.is_empty()).then_some(quote!(<'a>));
+
+    quote! {
+        #[cfg(feature = "serde")]
+        const _: () = {
+            #[derive(serde::Deserialize)]
+            #[serde(rename = #name_string)]
+            struct SerdePacket {
+                header: ragnarok_packets::PacketHeader,
+                #(#shadow_fields),*
+            }
+
+            #[derive(serde::Serialize)]
+            #[serde(rename = #name_string)]
+            struct SerdePacketRef #lifetime {
+                header: ragnarok_packets::PacketHeader,
+                #(#shadow_reference_fields),*
+            }
+
+            impl serde::Serialize for #name {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let Self { #(#field_identifiers),* } = self;
+                    let packet = SerdePacketRef {
+                        header: <Self as ragnarok_packets::Packet>::HEADER,
+                        #(#field_identifiers),*
+                    };
+
+                    serde::Serialize::serialize(&packet, serializer)
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for #name {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let SerdePacket { header, #(#field_identifiers),* } = <SerdePacket as serde::Deserialize>::deserialize(deserializer)?;
+
+                    if header != <Self as ragnarok_packets::Packet>::HEADER {
+                        return Err(<D::Error as serde::de::Error>::custom(format!(
+                            "mismatched header: expected {:?}, found {:?}",
+                            <Self as ragnarok_packets::Packet>::HEADER,
+                            header,
+                        )));
+                    }
+
+                    Ok(Self { #(#field_identifiers),* })
+                }
+            }
+        };
+    }
+}
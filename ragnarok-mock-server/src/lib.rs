@@ -0,0 +1,355 @@
+//! Scriptable Ragnarok Online servers for testing networking code without a
+//! real server.
+
+pub mod presets;
+mod script;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener as StdTcpListener};
+use std::rc::Rc;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use ragnarok_bytes::encoding::UTF_8;
+use ragnarok_bytes::{ByteReader, ByteWriter};
+use ragnarok_packets::obfuscation::PacketObfuscation;
+use ragnarok_packets::{ClientPacket, PacketExt, PacketHeader, ServerPacket};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use self::script::ScriptResult;
+pub use self::script::{Responder, Script};
+
+/// A packet that was received and handled by a [`MockServer`].
+#[derive(Debug, Clone)]
+pub struct ReceivedPacket {
+    /// Header of the packet.
+    pub header: PacketHeader,
+    /// Header of the packet as it was sent by the client. Only differs from
+    /// `header` if the [`Script`] uses packet obfuscation.
+    pub wire_header: PacketHeader,
+    /// All bytes of the packet, *including* the (restored) header.
+    pub bytes: Vec<u8>,
+}
+
+impl ReceivedPacket {
+    /// Parse the packet as a `Packet`. Returns `None` if the header doesn't
+    /// match.
+    pub fn parse<Packet>(&self) -> Option<Packet>
+    where
+        Packet: ClientPacket,
+    {
+        if self.header != Packet::HEADER {
+            return None;
+        }
+
+        let mut byte_reader = ByteReader::without_metadata(&self.bytes);
+        byte_reader.set_encoding(UTF_8);
+
+        Packet::packet_from_bytes(&mut byte_reader).ok()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ServerCommand {
+    Send(Vec<u8>),
+    DisconnectAll,
+    Shutdown,
+}
+
+#[derive(Default)]
+struct ServerLog {
+    received_packets: Vec<ReceivedPacket>,
+    unhandled_packets: Vec<Vec<u8>>,
+    /// Number of packets of each type that were already returned by
+    /// [`MockServer::wait_for_packet`].
+    awaited_packets: HashMap<PacketHeader, usize>,
+    connection_count: usize,
+}
+
+#[derive(Default)]
+struct SharedLog {
+    log: Mutex<ServerLog>,
+    changed: Condvar,
+}
+
+impl SharedLog {
+    fn lock(&self) -> MutexGuard<'_, ServerLog> {
+        // A panic inside a script poisons the mutex, but the log is still valid.
+        self.log.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn update(&self, update: impl FnOnce(&mut ServerLog)) {
+        update(&mut self.lock());
+        self.changed.notify_all();
+    }
+
+    fn wait_for<T>(&self, timeout: Duration, mut condition: impl FnMut(&mut ServerLog) -> Option<T>) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        let mut log = self.lock();
+
+        loop {
+            if let Some(result) = condition(&mut log) {
+                return Some(result);
+            }
+
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+            log = self
+                .changed
+                .wait_timeout(log, remaining)
+                .map(|(log, _)| log)
+                .unwrap_or_else(|error| error.into_inner().0);
+        }
+    }
+}
+
+/// A Ragnarok Online server running a [`Script`] on `localhost`.
+///
+/// The server runs on its own thread and shuts down when dropped.
+pub struct MockServer {
+    address: SocketAddr,
+    command_sender: broadcast::Sender<ServerCommand>,
+    shared_log: Arc<SharedLog>,
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Start a new server on a random port.
+    pub fn spawn(script: Script) -> std::io::Result<Self> {
+        let listener = StdTcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        listener.set_nonblocking(true)?;
+
+        let address = listener.local_addr()?;
+        let (command_sender, _) = broadcast::channel(64);
+        let shared_log = Arc::new(SharedLog::default());
+
+        let thread_command_sender = command_sender.clone();
+        let thread_shared_log = Arc::clone(&shared_log);
+
+        let thread_handle = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+            let local_set = tokio::task::LocalSet::new();
+
+            local_set.block_on(&runtime, async {
+                let listener = TcpListener::from_std(listener).expect("failed to register listener");
+                let script = Rc::new(RefCell::new(script));
+                let mut command_receiver = thread_command_sender.subscribe();
+
+                loop {
+                    tokio::select! {
+                        accepted = listener.accept() => {
+                            let Ok((stream, _)) = accepted else {
+                                continue;
+                            };
+
+                            // Subscribe before updating the connection count, so that any packet sent after
+                            // waiting for the connection will reach the client.
+                            let command_receiver = thread_command_sender.subscribe();
+                            thread_shared_log.update(|log| log.connection_count += 1);
+
+                            tokio::task::spawn_local(Self::handle_connection(
+                                stream,
+                                Rc::clone(&script),
+                                command_receiver,
+                                Arc::clone(&thread_shared_log),
+                            ));
+                        }
+                        command = command_receiver.recv() => {
+                            if matches!(command, Ok(ServerCommand::Shutdown) | Err(RecvError::Closed)) {
+                                break;
+                            }
+                        }
+                    }
+                }
+            });
+        });
+
+        Ok(Self {
+            address,
+            command_sender,
+            shared_log,
+            thread_handle: Some(thread_handle),
+        })
+    }
+
+    async fn handle_connection(
+        mut stream: TcpStream,
+        script: Rc<RefCell<Script>>,
+        mut command_receiver: broadcast::Receiver<ServerCommand>,
+        shared_log: Arc<SharedLog>,
+    ) {
+        let mut buffer = [0u8; 8192];
+        let mut pending_bytes = Vec::new();
+        let mut responder = Responder::new();
+        let mut packet_obfuscation = script.borrow().packet_obfuscation_keys().map(PacketObfuscation::new);
+        let mut wire_header = None;
+
+        loop {
+            tokio::select! {
+                // Receive some packets from the client.
+                received_bytes = stream.read(&mut buffer) => {
+                    let Ok(received_bytes) = received_bytes else {
+                        break;
+                    };
+
+                    if received_bytes == 0 {
+                        // The client closed the connection.
+                        break;
+                    }
+
+                    pending_bytes.extend_from_slice(&buffer[..received_bytes]);
+
+                    let mut processed_bytes = 0;
+
+                    while processed_bytes < pending_bytes.len() {
+                        let packet_bytes = &mut pending_bytes[processed_bytes..];
+
+                        // Every packet advances the obfuscation key, so the header of a packet has to be
+                        // restored exactly once, even if the packet is cut off.
+                        if let Some(packet_obfuscation) = &mut packet_obfuscation
+                            && wire_header.is_none()
+                            && let Some(header_bytes) = packet_bytes.first_chunk::<2>()
+                        {
+                            wire_header = Some(PacketHeader(u16::from_le_bytes(*header_bytes)));
+                            packet_obfuscation.apply_to_packet(packet_bytes);
+                        }
+
+                        let mut byte_reader = ByteReader::without_metadata(packet_bytes);
+                        byte_reader.set_encoding(UTF_8);
+
+                        match script.borrow_mut().process_one(&mut byte_reader, &mut responder) {
+                            ScriptResult::Ok(header) => {
+                                let packet_length = byte_reader.get_offset();
+                                let bytes = packet_bytes[..packet_length].to_vec();
+                                let wire_header = wire_header.take().unwrap_or(header);
+
+                                shared_log.update(|log| log.received_packets.push(ReceivedPacket { header, wire_header, bytes }));
+                                processed_bytes += packet_length;
+                            }
+                            ScriptResult::PacketCutOff => break,
+                            // Without a handler we can't know the size of the packet, so we have to drop
+                            // everything that is left.
+                            ScriptResult::UnhandledPacket | ScriptResult::InternalError => {
+                                let bytes = byte_reader.remaining_bytes();
+                                shared_log.update(|log| log.unhandled_packets.push(bytes));
+                                break;
+                            }
+                        }
+                    }
+
+                    pending_bytes.drain(..processed_bytes);
+
+                    let (response, disconnect) = responder.take();
+
+                    if stream.write_all(&response).await.is_err() || disconnect {
+                        break;
+                    }
+                }
+                // Receive commands from the test.
+                command = command_receiver.recv() => match command {
+                    Ok(ServerCommand::Send(bytes)) => {
+                        if stream.write_all(&bytes).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(ServerCommand::DisconnectAll | ServerCommand::Shutdown) | Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(..)) => {}
+                }
+            }
+        }
+
+        shared_log.update(|log| log.connection_count -= 1);
+    }
+
+    /// Address that clients can connect to.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Send a packet to all connected clients.
+    pub fn send(&self, packet: impl ServerPacket) {
+        let mut byte_writer = ByteWriter::with_encoding(UTF_8);
+        packet.packet_to_bytes(&mut byte_writer).expect("failed to serialize server packet");
+
+        let _ = self.command_sender.send(ServerCommand::Send(byte_writer.into_inner()));
+    }
+
+    /// Close the connection to all connected clients.
+    pub fn disconnect_all(&self) {
+        let _ = self.command_sender.send(ServerCommand::DisconnectAll);
+    }
+
+    /// Number of clients that are currently connected.
+    pub fn connection_count(&self) -> usize {
+        self.shared_log.lock().connection_count
+    }
+
+    /// Wait until exactly `count` clients are connected. Returns `false` if
+    /// that didn't happen before the timeout.
+    pub fn wait_for_connection_count(&self, count: usize, timeout: Duration) -> bool {
+        self.shared_log
+            .wait_for(timeout, |log| (log.connection_count == count).then_some(()))
+            .is_some()
+    }
+
+    /// All packets that were handled by the script, in the order they were
+    /// received.
+    pub fn received_packets(&self) -> Vec<ReceivedPacket> {
+        self.shared_log.lock().received_packets.clone()
+    }
+
+    /// All packets of type `Packet` that were handled by the script, in the
+    /// order they were received.
+    pub fn received<Packet>(&self) -> Vec<Packet>
+    where
+        Packet: ClientPacket,
+    {
+        self.shared_log
+            .lock()
+            .received_packets
+            .iter()
+            .filter_map(ReceivedPacket::parse)
+            .collect()
+    }
+
+    /// Raw bytes of all packets that the script had no handler for or failed
+    /// to parse.
+    pub fn unhandled_packets(&self) -> Vec<Vec<u8>> {
+        self.shared_log.lock().unhandled_packets.clone()
+    }
+
+    /// Wait for the next packet of type `Packet`. Every received packet is
+    /// only returned once, so calling this function repeatedly will return
+    /// all packets of that type in the order they were received.
+    pub fn wait_for_packet<Packet>(&self, timeout: Duration) -> Option<Packet>
+    where
+        Packet: ClientPacket,
+    {
+        self.shared_log.wait_for(timeout, |log| {
+            let awaited = log.awaited_packets.get(&Packet::HEADER).copied().unwrap_or_default();
+            let packet = log
+                .received_packets
+                .iter()
+                .filter_map(ReceivedPacket::parse::<Packet>)
+                .nth(awaited)?;
+
+            log.awaited_packets.insert(Packet::HEADER, awaited + 1);
+
+            Some(packet)
+        })
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        let _ = self.command_sender.send(ServerCommand::Shutdown);
+
+        if let Some(thread_handle) = self.thread_handle.take() {
+            let _ = thread_handle.join();
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Scripts that implement the parts of the login, character, and map servers
+//! needed to log in and enter a map.
+
+use std::net::{IpAddr, SocketAddr};
+
+use ragnarok_bytes::{ByteWriter, ToBytes};
+use ragnarok_packets::*;
+
+use crate::{Responder, Script};
+
+/// Account that is accepted by the preset servers.
+#[derive(Debug, Clone)]
+pub struct MockAccount {
+    pub username: String,
+    pub password: String,
+    pub account_id: AccountId,
+    pub login_id1: u32,
+    pub login_id2: u32,
+    pub sex: Sex,
+}
+
+impl Default for MockAccount {
+    fn default() -> Self {
+        Self {
+            username: "mock".to_owned(),
+            password: "password".to_owned(),
+            account_id: AccountId(2000000),
+            login_id1: 1234,
+            login_id2: 5678,
+            sex: Sex::Male,
+        }
+    }
+}
+
+fn server_address(address: SocketAddr) -> ServerAddress {
+    match address.ip() {
+        IpAddr::V4(ip) => ServerAddress(ip.octets()),
+        IpAddr::V6(_) => panic!("only IPv4 addresses can be sent to the client"),
+    }
+}
+
+/// Create character information with sensible defaults for all fields that
+/// the client doesn't strictly need.
+pub fn mock_character(character_id: CharacterId, character_number: u8, name: &str, map_name: &str) -> CharacterInformation {
+    CharacterInformation {
+        character_id,
+        experience: 0,
+        money: 1000,
+        job_experience: 0,
+        job_level: 1,
+        body_state: 0,
+        health_state: 0,
+        effect_state: 0,
+        virtue: 0,
+        honor: 0,
+        stat_points: 48,
+        health_points: 40,
+        maximum_health_points: 40,
+        spell_points: 11,
+        maximum_spell_points: 11,
+        movement_speed: 150,
+        job_id: JobId(0),
+        head: 1,
+        body: 0,
+        weapon: 0,
+        base_level: 1,
+        sp_point: 0,
+        accessory: 0,
+        shield: 0,
+        accessory2: 0,
+        accessory3: 0,
+        head_palette: 0,
+        body_palette: 0,
+        name: name.to_owned(),
+        strength: 1,
+        agility: 1,
+        vitality: 1,
+        intelligence: 1,
+        dexterity: 1,
+        luck: 1,
+        character_number,
+        hair_color: 0,
+        b_is_changed_char: 0,
+        map_name: format!("{map_name}.gat"),
+        deletion_reverse_date: 0,
+        robe_palette: 0,
+        character_slot_change_count: 0,
+        character_name_change_count: 0,
+        sex: Sex::Male,
+    }
+}
+
+/// Login server that accepts `account` and forwards the client to the
+/// character server at `character_server`.
+pub fn login_server(account: MockAccount, character_server: SocketAddr) -> Script {
+    Script::new()
+        .on(move |packet: LoginServerLoginPacket, responder: &mut Responder| {
+            if packet.name != account.username || packet.password != account.password {
+                responder.send(LoginFailedPacket2::new(LoginFailedReason2::IncorrectPassword));
+                return;
+            }
+
+            let character_server_information = CharacterServerInformation::new(
+                server_address(character_server),
+                character_server.port(),
+                "Mock".to_owned(),
+                0,
+                0,
+                0,
+            );
+
+            responder.send(LoginServerLoginSuccessPacket::new(
+                account.login_id1,
+                account.account_id,
+                account.login_id2,
+                account.sex,
+                AuthToken([0; 17]),
+                vec![character_server_information],
+            ));
+        })
+        .ignore::<LoginServerKeepalivePacket>()
+}
+
+/// Character server that accepts `account`, lists `characters`, and forwards
+/// the client to the map server at `map_server` after selecting one of them.
+pub fn character_server(account: MockAccount, characters: Vec<CharacterInformation>, map_server: SocketAddr) -> Script {
+    Script::new()
+        .on(move |packet: CharacterServerLoginPacket, responder: &mut Responder| {
+            if packet.account_id != account.account_id || packet.login_id1 != account.login_id1 || packet.login_id2 != account.login_id2 {
+                responder.send(LoginFailedPacket::new(LoginFailedReason::AlreadyLoggedIn));
+                return;
+            }
+
+            // The character server sends the account id without a packet header first.
+            let mut byte_writer = ByteWriter::new();
+            account.account_id.to_bytes(&mut byte_writer).unwrap();
+            responder.send_raw(byte_writer.as_slice());
+
+            responder.send(CharacterServerLoginSuccessPacket::new(29, 15, 0, 0, 0, 15));
+        })
+        .on({
+            let characters = characters.clone();
+            move |_: RequestCharacterListPacket, responder: &mut Responder| {
+                responder.send(RequestCharacterListSuccessPacket::new(characters.clone()));
+            }
+        })
+        .on(move |packet: SelectCharacterPacket, responder: &mut Responder| {
+            let Some(character) = characters
+                .iter()
+                .find(|character| character.character_number == packet.selected_slot)
+            else {
+                responder.send(CharacterSelectionFailedPacket::new(
+                    CharacterSelectionFailedReason::RejectedFromServer,
+                ));
+                return;
+            };
+
+            responder.send(CharacterSelectionSuccessPacket::new(
+                character.character_id,
+                character.map_name.clone(),
+                server_address(map_server),
+                map_server.port(),
+            ));
+        })
+        .ignore::<CharacterServerKeepalivePacket>()
+}
+
+/// Map server that accepts `account` and spawns the player at `position`.
+///
+/// Besides the login it answers time synchronization requests, warps, global
+/// chat messages, and logging out. The server tick it reports is always the
+/// tick sent by the client.
+pub fn map_server(account: MockAccount, position: WorldPosition) -> Script {
+    Script::new()
+        .on(move |packet: MapServerLoginPacket, responder: &mut Responder| {
+            if packet.account_id != account.account_id || packet.login_id1 != account.login_id1 {
+                responder.disconnect();
+                return;
+            }
+
+            responder.send(MapServerLoginSuccessPacket::new(packet.client_tick, position, 0));
+        })
+        .on(|packet: RequestServerTickPacket, responder: &mut Responder| {
+            responder.send(ServerTickPacket::new(packet.client_tick));
+        })
+        .ignore::<MapLoadedPacket>()
+        .on(|packet: RequestWarpToMapPacket, responder: &mut Responder| {
+            responder.send(ChangeMapPacket::new(format!("{}.gat", packet.map_name), packet.position));
+        })
+        .on(|packet: GlobalMessagePacket, responder: &mut Responder| {
+            // The server echoes messages back to the player that sent them.
+            responder.send(ServerMessagePacket::new(packet.message));
+        })
+        .on(|packet: RestartPacket, responder: &mut Responder| {
+            if let RestartType::Disconnect = packet.restart_type {
+                responder.send(RestartResponsePacket::new(RestartResponseStatus::Ok));
+            }
+        })
+}
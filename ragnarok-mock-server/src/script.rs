@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use ragnarok_bytes::encoding::UTF_8;
+use ragnarok_bytes::{ByteReader, ByteWriter, ConversionResult, FromBytes};
+use ragnarok_packets::obfuscation::PacketObfuscationKeys;
+use ragnarok_packets::{ClientPacket, PacketExt, PacketHeader, ServerPacket};
+
+type ScriptFunction = Box<dyn FnMut(&mut ByteReader, &mut Responder) -> ConversionResult<()> + Send>;
+
+/// Possible results of [`Script::process_one`].
+pub(crate) enum ScriptResult {
+    /// Packet was successfully processed by the script.
+    Ok(PacketHeader),
+    /// The script has no handler for the incoming packet.
+    UnhandledPacket,
+    /// Packet was most likely cut-off.
+    PacketCutOff,
+    /// The packet could not be parsed.
+    InternalError,
+}
+
+/// Collects the data that a [`Script`] sends back to the client in response to
+/// a packet.
+pub struct Responder {
+    byte_writer: ByteWriter,
+    disconnect: bool,
+}
+
+impl Responder {
+    pub(crate) fn new() -> Self {
+        Self {
+            byte_writer: ByteWriter::with_encoding(UTF_8),
+            disconnect: false,
+        }
+    }
+
+    /// Send a packet to the client.
+    pub fn send(&mut self, packet: impl ServerPacket) {
+        packet
+            .packet_to_bytes(&mut self.byte_writer)
+            .expect("failed to serialize server packet");
+    }
+
+    /// Send bytes to the client as they are. This is needed for data that is
+    /// not wrapped in a packet, like the account id the character server sends
+    /// after logging in.
+    pub fn send_raw(&mut self, bytes: &[u8]) {
+        self.byte_writer.extend_from_slice(bytes);
+    }
+
+    /// Close the connection after all pending data has been sent.
+    pub fn disconnect(&mut self) {
+        self.disconnect = true;
+    }
+
+    /// Take the pending data and whether or not the connection should be
+    /// closed.
+    pub(crate) fn take(&mut self) -> (Vec<u8>, bool) {
+        let bytes = self.byte_writer.as_slice().to_vec();
+        self.byte_writer.clear();
+
+        (bytes, self.disconnect)
+    }
+}
+
+/// Describes how a mock server responds to packets from the client.
+///
+/// Registering a handler for a packet that already has one replaces the old
+/// handler, so scripts from [`presets`](crate::presets) can be adjusted for
+/// individual tests.
+#[derive(Default)]
+pub struct Script {
+    handlers: HashMap<PacketHeader, ScriptFunction>,
+    packet_obfuscation_keys: Option<PacketObfuscationKeys>,
+}
+
+impl Script {
+    /// Create an empty script that doesn't handle any packets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `handler` every time the client sends a `Packet`.
+    pub fn on<Packet>(mut self, mut handler: impl FnMut(Packet, &mut Responder) + Send + 'static) -> Self
+    where
+        Packet: ClientPacket,
+    {
+        self.handlers.insert(
+            Packet::HEADER,
+            Box::new(move |byte_reader, responder| {
+                let packet = Packet::payload_from_bytes(byte_reader)?;
+                handler(packet, responder);
+                Ok(())
+            }),
+        );
+
+        self
+    }
+
+    /// Call `handler` every time the client sends a packet with the given
+    /// header. The handler is responsible for reading the payload from the
+    /// [`ByteReader`], which is useful for packets that the derived
+    /// implementation can't read.
+    pub fn on_raw(
+        mut self,
+        header: PacketHeader,
+        handler: impl FnMut(&mut ByteReader, &mut Responder) -> ConversionResult<()> + Send + 'static,
+    ) -> Self {
+        self.handlers.insert(header, Box::new(handler));
+        self
+    }
+
+    /// Expect the client to obfuscate the headers of its packets with `keys`.
+    /// The headers are restored before the packets are passed to the
+    /// handlers.
+    pub fn with_packet_obfuscation(mut self, keys: PacketObfuscationKeys) -> Self {
+        self.packet_obfuscation_keys = Some(keys);
+        self
+    }
+
+    pub(crate) fn packet_obfuscation_keys(&self) -> Option<PacketObfuscationKeys> {
+        self.packet_obfuscation_keys
+    }
+
+    /// Accept a `Packet` from the client without responding to it.
+    pub fn ignore<Packet>(self) -> Self
+    where
+        Packet: ClientPacket,
+    {
+        self.on(|_: Packet, _| {})
+    }
+
+    /// Take a single packet from the byte stream.
+    pub(crate) fn process_one(&mut self, byte_reader: &mut ByteReader, responder: &mut Responder) -> ScriptResult {
+        let save_point = byte_reader.create_save_point();
+
+        let Ok(header) = PacketHeader::from_bytes(byte_reader) else {
+            // Packet is cut-off at the header.
+            byte_reader.restore_save_point(save_point);
+            return ScriptResult::PacketCutOff;
+        };
+
+        let Some(handler) = self.handlers.get_mut(&header) else {
+            byte_reader.restore_save_point(save_point);
+            return ScriptResult::UnhandledPacket;
+        };
+
+        match handler(byte_reader, responder) {
+            Ok(()) => ScriptResult::Ok(header),
+            // Cut-off packet (probably).
+            Err(error) if error.is_byte_reader_too_short() => {
+                byte_reader.restore_save_point(save_point);
+                ScriptResult::PacketCutOff
+            }
+            Err(..) => {
+                byte_reader.restore_save_point(save_point);
+                ScriptResult::InternalError
+            }
+        }
+    }
+}
@@ -288,6 +288,7 @@ fn main() {
         DisconnectResponsePacket,
         UseSkillSuccessPacket,
         ToUseSkillSuccessPacket,
+        CastCancelPacket,
         NotifySkillUnitPacket,
         SkillUnitDisappearPacket,
         NotifyGroundSkillPacket,
@@ -310,6 +311,40 @@ fn main() {
         SellListPacket,
         SellItemsPacket,
         SellItemsResultPacket,
+        CartInformationPacket,
+        CartItemAddedPacket,
+        CartItemRemovedPacket,
+        CartRemovedPacket,
+        MoveItemToCartFailedPacket,
+        MailListPacket,
+        MailContentPacket,
+        MailDeletedPacket,
+        ClaimMailZenyResultPacket,
+        ClaimMailItemsResultPacket,
+        OpenWriteMailResultPacket,
+        CheckMailRecipientResultPacket,
+        AddMailItemResultPacket,
+        RemoveMailItemResultPacket,
+        SendMailResultPacket,
+        StartPetCapturePacket,
+        CapturePetResultPacket,
+        PetEggListPacket,
+        PetInformationPacket,
+        PetStateChangePacket,
+        FeedPetResultPacket,
+        HomunculusStateChangePacket,
+        HomunculusInformationPacket,
+        HomunculusSkillListPacket,
+        FeedHomunculusResultPacket,
+        CreateChatRoomResultPacket,
+        ChatRoomAppearedPacket,
+        ChatRoomDestroyedPacket,
+        JoinChatRoomFailedPacket,
+        ChatRoomJoinedPacket,
+        ChatRoomMemberJoinedPacket,
+        ChatRoomMemberLeftPacket,
+        ChatRoomChangedPacket,
+        ChatRoomRoleChangedPacket,
     ]);
 
     let mut server_map_handler = create_handler!(ServerType::Map, Direction::Outgoing, [
@@ -320,6 +355,7 @@ fn main() {
         RequestDetailsPacket,
         RequestActionPacket,
         GlobalMessagePacket,
+        WhisperPacket,
         StartDialogPacket,
         NextDialogPacket,
         CloseDialogPacket,
@@ -333,12 +369,46 @@ fn main() {
         AddFriendPacket,
         RemoveFriendPacket,
         FriendRequestResponsePacket,
+        InviteToPartyPacket,
+        RequestTradePacket,
+        UseItemPacket,
+        DropItemPacket,
         SetHotkeyData2Packet,
         SelectBuyOrSellPacket,
         BuyShopItemsPacket,
         CloseShopPacket,
         SellItemsPacket,
         RequestServerTickPacket,
+        MoveItemToCartPacket,
+        MoveItemFromCartPacket,
+        RemoveCartPacket,
+        OpenMailboxPacket,
+        RequestNextMailPagePacket,
+        CloseMailboxPacket,
+        ReadMailPacket,
+        DeleteMailPacket,
+        ClaimMailZenyPacket,
+        ClaimMailItemsPacket,
+        OpenWriteMailPacket,
+        CancelWriteMailPacket,
+        AddMailItemPacket,
+        RemoveMailItemPacket,
+        CheckMailRecipientPacket,
+        SendMailPacket,
+        TryCapturePetPacket,
+        SelectPetEggPacket,
+        PetCommandPacket,
+        HomunculusCommandPacket,
+        RequestHomunculusMovePacket,
+        RequestHomunculusAttackPacket,
+        RequestHomunculusMoveToOwnerPacket,
+        LevelUpHomunculusSkillPacket,
+        CreateChatRoomPacket,
+        JoinChatRoomPacket,
+        ChangeChatRoomPacket,
+        ChangeChatRoomOwnerPacket,
+        KickChatRoomMemberPacket,
+        LeaveChatRoomPacket,
     ]);
 
     println!("{}", "Listening for packets".green());
@@ -2,7 +2,8 @@ use std::collections::HashMap;
 
 use ragnarok_bytes::{ByteReader, ConversionError, ConversionResult, FromBytes};
 
-use crate::PacketHeader;
+use crate::length_table::PacketLengthTable;
+use crate::{PacketHeader, PacketLength};
 
 /// Possible results of [`PacketHandler::process_one`].
 pub enum HandlerResult<Output> {
@@ -42,7 +43,9 @@ pub trait PacketCallback: Clone + 'static {
     }
 
     /// Called by the [`PacketHandler`] when a packet arrives that doesn't have
-    /// a handler registered.
+    /// a handler registered. If the length of the packet is known, `bytes`
+    /// contains only that packet, otherwise everything that is left in the
+    /// stream.
     fn unknown_packet(&self, bytes: Vec<u8>) {
         let _ = bytes;
     }
@@ -51,6 +54,19 @@ pub trait PacketCallback: Clone + 'static {
     fn failed_packet(&self, bytes: Vec<u8>, error: Box<ConversionError>) {
         let _ = (bytes, error);
     }
+
+    /// Called with the raw bytes received from a server, split at packet
+    /// boundaries where possible. Unlike the other callbacks, this includes
+    /// data that isn't part of any packet.
+    fn incoming_bytes(&self, bytes: &[u8]) {
+        let _ = bytes;
+    }
+
+    /// Called with the raw bytes of every packet sent to a server, before any
+    /// header obfuscation is applied.
+    fn outgoing_bytes(&self, bytes: &[u8]) {
+        let _ = bytes;
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -64,8 +80,12 @@ pub type HandlerFunction<Output> = Box<dyn Fn(&mut ByteReader) -> ConversionResu
 /// converting them to some common event type.
 ///
 /// It allows passing a packet callback to monitor incoming packets.
+///
+/// Packets without a registered handler are skipped if their length is known
+/// from the [`PacketLengthTable`].
 pub struct PacketHandler<Output, Callback> {
     handlers: HashMap<PacketHeader, HandlerFunction<Output>>,
+    packet_lengths: PacketLengthTable,
     packet_callback: Callback,
 }
 
@@ -76,6 +96,7 @@ where
     fn default() -> Self {
         Self {
             handlers: Default::default(),
+            packet_lengths: Default::default(),
             packet_callback: Default::default(),
         }
     }
@@ -90,10 +111,30 @@ where
     pub fn with_callback(packet_callback: Callback) -> Self {
         Self {
             handlers: Default::default(),
+            packet_lengths: Default::default(),
             packet_callback,
         }
     }
 
+    /// Add the lengths of packets that can be received. Packets without a
+    /// handler that are in the table will be skipped instead of stopping the
+    /// stream. The lengths of packets with a handler are always known.
+    pub fn add_packet_lengths(&mut self, packet_lengths: &PacketLengthTable) {
+        for (header, length) in packet_lengths.iter() {
+            self.packet_lengths.insert(header, length);
+        }
+    }
+
+    /// Get the callback that is notified about incoming packets.
+    pub fn packet_callback(&self) -> &Callback {
+        &self.packet_callback
+    }
+
+    /// Get the lengths of all packets known to the handler.
+    pub fn packet_length_table(&self) -> &PacketLengthTable {
+        &self.packet_lengths
+    }
+
     /// Register a new packet handler.
     pub fn register<Packet, Return>(&mut self, handler: impl Fn(Packet) -> Return + 'static) -> Result<(), DuplicateHandlerError>
     where
@@ -101,6 +142,8 @@ where
         Return: Into<Output>,
     {
         let packet_callback = self.packet_callback.clone();
+        self.packet_lengths.register::<Packet>();
+
         let old_handler = self.handlers.insert(
             Packet::HEADER,
             Box::new(move |byte_reader| {
@@ -126,6 +169,8 @@ where
         Packet: ragnarok_packets::Packet,
     {
         let packet_callback = self.packet_callback.clone();
+        self.packet_lengths.register::<Packet>();
+
         let old_handler = self.handlers.insert(
             Packet::HEADER,
             Box::new(move |byte_reader| {
@@ -157,10 +202,7 @@ where
 
         let Some(handler) = self.handlers.get(&header) else {
             byte_reader.restore_save_point(save_point);
-
-            self.packet_callback.unknown_packet(byte_reader.remaining_bytes());
-
-            return HandlerResult::UnhandledPacket;
+            return self.skip_unknown_packet(byte_reader, header);
         };
 
         match handler(byte_reader) {
@@ -179,4 +221,161 @@ where
             }
         }
     }
+
+    /// Skip a packet without a handler if its length is known. `byte_reader`
+    /// has to be positioned at the start of the packet.
+    fn skip_unknown_packet(&self, byte_reader: &mut ByteReader, header: PacketHeader) -> HandlerResult<Output> {
+        let save_point = byte_reader.create_save_point();
+
+        let packet_length = match self.packet_lengths.get(header) {
+            Some(PacketLength::Fixed(length)) => Some(length).filter(|length| *length >= 2),
+            Some(PacketLength::Variable) => {
+                let length_save_point = byte_reader.create_save_point();
+                let length = byte_reader.slice::<Self>(4).map(|bytes| u16::from_le_bytes([bytes[2], bytes[3]]));
+                byte_reader.restore_save_point(length_save_point);
+
+                match length {
+                    // A variable length packet is at least as long as its header and length, so
+                    // anything shorter means that we are out of sync.
+                    Ok(length) => Some(length).filter(|length| *length >= 4),
+                    // Packet is cut-off at the length.
+                    Err(..) => return HandlerResult::PacketCutOff,
+                }
+            }
+            None => None,
+        };
+
+        let Some(packet_length) = packet_length else {
+            self.packet_callback.unknown_packet(byte_reader.remaining_bytes());
+            return HandlerResult::UnhandledPacket;
+        };
+
+        match byte_reader.slice::<Self>(packet_length as usize) {
+            Ok(bytes) => {
+                self.packet_callback.unknown_packet(bytes.to_vec());
+                HandlerResult::Ok(Output::default())
+            }
+            Err(..) => {
+                byte_reader.restore_save_point(save_point);
+                HandlerResult::PacketCutOff
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod skipping {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use ragnarok_bytes::ByteReader;
+
+    use super::{HandlerResult, PacketCallback, PacketHandler};
+    use crate::length_table::PacketLengthTable;
+    use crate::{BroadcastMessagePacket, Packet, PacketHeader, PacketLength, ServerMessagePacket, ServerTickPacket};
+
+    #[derive(Default, Clone)]
+    struct UnknownPackets(Rc<RefCell<Vec<Vec<u8>>>>);
+
+    impl PacketCallback for UnknownPackets {
+        fn unknown_packet(&self, bytes: Vec<u8>) {
+            self.0.borrow_mut().push(bytes);
+        }
+    }
+
+    fn handler() -> (PacketHandler<Vec<u32>, UnknownPackets>, UnknownPackets) {
+        let callback = UnknownPackets::default();
+        let mut packet_handler = PacketHandler::with_callback(callback.clone());
+
+        packet_handler
+            .register(|packet: ServerTickPacket| vec![packet.client_tick.0])
+            .unwrap();
+
+        let mut packet_lengths = PacketLengthTable::new();
+        packet_lengths.register::<ServerMessagePacket>();
+        packet_lengths.register::<BroadcastMessagePacket>();
+        packet_lengths.insert(PacketHeader(0x0ABC), PacketLength::Fixed(5));
+        packet_handler.add_packet_lengths(&packet_lengths);
+
+        (packet_handler, callback)
+    }
+
+    #[test]
+    fn known_lengths() {
+        let (packet_handler, _) = handler();
+
+        assert_eq!(
+            packet_handler.packet_length_table().get(ServerTickPacket::HEADER),
+            Some(PacketLength::Fixed(6))
+        );
+        assert_eq!(
+            packet_handler.packet_length_table().get(ServerMessagePacket::HEADER),
+            Some(PacketLength::Variable)
+        );
+    }
+
+    #[test]
+    fn skip_fixed_length() {
+        let (mut packet_handler, callback) = handler();
+        let bytes = [0xBC, 0x0A, 1, 2, 3, 0x7F, 0x00, 7, 0, 0, 0];
+        let mut byte_reader = ByteReader::without_metadata(&bytes);
+
+        assert!(matches!(packet_handler.process_one(&mut byte_reader), HandlerResult::Ok(output) if output.is_empty()));
+        assert!(matches!(packet_handler.process_one(&mut byte_reader), HandlerResult::Ok(output) if output == [7]));
+        assert!(byte_reader.is_empty());
+        assert_eq!(*callback.0.borrow(), [vec![0xBC, 0x0A, 1, 2, 3]]);
+    }
+
+    #[test]
+    fn skip_variable_length() {
+        let (mut packet_handler, callback) = handler();
+        let bytes = [0x9A, 0x00, 6, 0, b'h', b'i', 0x7F, 0x00, 7, 0, 0, 0];
+        let mut byte_reader = ByteReader::without_metadata(&bytes);
+
+        assert!(matches!(packet_handler.process_one(&mut byte_reader), HandlerResult::Ok(output) if output.is_empty()));
+        assert!(matches!(packet_handler.process_one(&mut byte_reader), HandlerResult::Ok(output) if output == [7]));
+        assert_eq!(*callback.0.borrow(), [bytes[..6].to_vec()]);
+    }
+
+    #[test]
+    fn cut_off() {
+        let (mut packet_handler, callback) = handler();
+
+        for bytes in [&[0xBC, 0x0A, 1][..], &[0x8E, 0x00, 10][..], &[0x8E, 0x00, 10, 0, b'h'][..]] {
+            let mut byte_reader = ByteReader::without_metadata(bytes);
+
+            assert!(matches!(
+                packet_handler.process_one(&mut byte_reader),
+                HandlerResult::PacketCutOff
+            ));
+            assert_eq!(byte_reader.get_offset(), 0);
+        }
+
+        assert!(callback.0.borrow().is_empty());
+    }
+
+    #[test]
+    fn unknown_length() {
+        let (mut packet_handler, callback) = handler();
+        let bytes = [0x34, 0x12, 1, 2, 0x7F, 0x00, 7, 0, 0, 0];
+        let mut byte_reader = ByteReader::without_metadata(&bytes);
+
+        assert!(matches!(
+            packet_handler.process_one(&mut byte_reader),
+            HandlerResult::UnhandledPacket
+        ));
+        assert_eq!(*callback.0.borrow(), [bytes.to_vec()]);
+    }
+
+    #[test]
+    fn invalid_variable_length() {
+        let (mut packet_handler, _) = handler();
+        let bytes = [0x8E, 0x00, 2, 0];
+        let mut byte_reader = ByteReader::without_metadata(&bytes);
+
+        assert!(matches!(
+            packet_handler.process_one(&mut byte_reader),
+            HandlerResult::UnhandledPacket
+        ));
+    }
 }
@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::{PacketHeader, PacketLength};
+
+/// Lengths of all packets of a packet version.
+///
+/// Knowing the length of a packet allows skipping it even if it can't be
+/// parsed, which keeps the stream in sync with the server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PacketLengthTable {
+    lengths: BTreeMap<PacketHeader, PacketLength>,
+}
+
+impl PacketLengthTable {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the length of `Packet` to the table.
+    pub fn register<Packet>(&mut self)
+    where
+        Packet: crate::Packet,
+    {
+        self.insert(Packet::HEADER, Packet::packet_length());
+    }
+
+    /// Add the length of a packet to the table, replacing any previous
+    /// length for the same header.
+    pub fn insert(&mut self, header: PacketHeader, length: PacketLength) {
+        self.lengths.insert(header, length);
+    }
+
+    /// Get the length of the packet with the given header.
+    pub fn get(&self, header: PacketHeader) -> Option<PacketLength> {
+        self.lengths.get(&header).copied()
+    }
+
+    /// Number of packets in the table.
+    pub fn len(&self) -> usize {
+        self.lengths.len()
+    }
+
+    /// Returns `true` if the table doesn't contain any packets.
+    pub fn is_empty(&self) -> bool {
+        self.lengths.is_empty()
+    }
+
+    /// Iterate over all packets in the table, sorted by header.
+    pub fn iter(&self) -> impl Iterator<Item = (PacketHeader, PacketLength)> + '_ {
+        self.lengths.iter().map(|(header, length)| (*header, *length))
+    }
+}
+
+impl Display for PacketLengthTable {
+    /// Formats the table as one `header,length` pair per line, which is the
+    /// format used by the packet length tables of most servers.
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        for (header, length) in self.iter() {
+            writeln!(formatter, "0x{:04X},{length}", header.0)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error when parsing a [`PacketLengthTable`] from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePacketLengthTableError {
+    /// Line that could not be parsed, starting at 1.
+    pub line: usize,
+}
+
+impl FromStr for PacketLengthTable {
+    type Err = ParsePacketLengthTableError;
+
+    /// Parses the format written by [`Display`]. Empty lines and lines
+    /// starting with `//` are ignored.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut table = Self::new();
+
+        for (index, line) in string.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let error = ParsePacketLengthTableError { line: index + 1 };
+            let (header, length) = line.split_once(',').ok_or(error.clone())?;
+
+            let header = header
+                .trim()
+                .strip_prefix("0x")
+                .and_then(|header| u16::from_str_radix(header, 16).ok())
+                .ok_or(error.clone())?;
+
+            let length = match length.trim() {
+                "-1" => PacketLength::Variable,
+                length => PacketLength::Fixed(length.parse().map_err(|_| error)?),
+            };
+
+            table.insert(PacketHeader(header), length);
+        }
+
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod export {
+    use super::PacketLengthTable;
+    use crate::{PacketHeader, PacketLength};
+
+    #[test]
+    fn sorted_by_header() {
+        let mut table = PacketLengthTable::new();
+        table.insert(PacketHeader(0x0360), PacketLength::Fixed(6));
+        table.insert(PacketHeader(0x007F), PacketLength::Fixed(6));
+        table.insert(PacketHeader(0x0B72), PacketLength::Variable);
+
+        assert_eq!(table.to_string(), "0x007F,6\n0x0360,6\n0x0B72,-1\n");
+    }
+
+    #[test]
+    fn insert_replaces() {
+        let mut table = PacketLengthTable::new();
+        table.insert(PacketHeader(0x0360), PacketLength::Fixed(6));
+        table.insert(PacketHeader(0x0360), PacketLength::Variable);
+
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.get(PacketHeader(0x0360)), Some(PacketLength::Variable));
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let mut table = PacketLengthTable::new();
+        table.insert(PacketHeader(0x007F), PacketLength::Fixed(6));
+        table.insert(PacketHeader(0x0B72), PacketLength::Variable);
+
+        assert_eq!(table.to_string().parse::<PacketLengthTable>(), Ok(table));
+    }
+
+    #[test]
+    fn parse_skips_comments() {
+        let table: PacketLengthTable = "// Map server\n\n0x007F,6\n".parse().unwrap();
+
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.get(PacketHeader(0x007F)), Some(PacketLength::Fixed(6)));
+    }
+
+    #[test]
+    fn parse_reports_line() {
+        let error = "0x007F,6\n0x0360,six\n".parse::<PacketLengthTable>().unwrap_err();
+
+        assert_eq!(error.line, 2);
+    }
+}
@@ -2,7 +2,12 @@
 #![cfg_attr(feature = "interface", feature(impl_trait_in_assoc_type))]
 
 pub mod handler;
+pub mod length_table;
+pub mod obfuscation;
 mod position;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub mod serde_array;
 
 use std::net::Ipv4Addr;
 
@@ -21,8 +26,29 @@ extern crate self as ragnarok_packets;
 
 /// The header of a Ragnarok Online packet. It is always two bytes long.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketHeader(pub u16);
 
+/// The length of a Ragnarok Online packet, including the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketLength {
+    /// The packet always has the same length.
+    Fixed(u16),
+    /// The length of the packet is sent as a `u16` right after the header.
+    Variable,
+}
+
+impl std::fmt::Display for PacketLength {
+    /// Formats the length the same way as packet length tables of servers do,
+    /// with `-1` meaning variable length.
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PacketLength::Fixed(length) => write!(formatter, "{length}"),
+            PacketLength::Variable => write!(formatter, "-1"),
+        }
+    }
+}
+
 /// Base trait that all packets implement.
 /// All packets in Ragnarok online consist of a header, two bytes in size,
 /// followed by the packet data. If the packet does not have a fixed size,
@@ -44,6 +70,9 @@ pub trait Packet: std::fmt::Debug + Send + Clone + 'static {
     /// header, use [`PacketExt::packet_to_bytes`].
     fn payload_to_bytes(&self, byte_writer: &mut ByteWriter) -> ConversionResult<usize>;
 
+    /// The length of the packet, including the header.
+    fn packet_length() -> PacketLength;
+
     // Implementation detail of Korangar. Can be used to convert a packet to an
     // UI element in the packet inspector.
     #[cfg(feature = "packet-to-state-element")]
@@ -102,66 +131,82 @@ pub trait MapServerPacket: Packet {}
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClientTick(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AccountId(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterId(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PartyId(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntityId(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SkillId(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SkillLevel(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HotbarTab(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HotbarSlot(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShopId(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Price(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AttackRange(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct JobId(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ServerAddress(pub [u8; 4]);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UserId(pub [u8; 24]);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AuthToken(pub [u8; 17]);
 
 impl From<ServerAddress> for Ipv4Addr {
@@ -172,6 +217,7 @@ impl From<ServerAddress> for Ipv4Addr {
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TilePosition {
     pub x: u16,
     pub y: u16,
@@ -179,6 +225,7 @@ pub struct TilePosition {
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LargeTilePosition {
     pub x: u32,
     pub y: u32,
@@ -186,6 +233,7 @@ pub struct LargeTilePosition {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColorBGRA {
     pub blue: u8,
     pub green: u8,
@@ -193,8 +241,9 @@ pub struct ColorBGRA {
     pub alpha: u8,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColorRGBA {
     pub red: u8,
     pub green: u8,
@@ -205,6 +254,7 @@ pub struct ColorRGBA {
 /// Item index is always actual index + 2.
 #[derive(Clone, Copy, Debug, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InventoryIndex(pub u16);
 
 impl FromBytes for InventoryIndex {
@@ -221,10 +271,12 @@ impl ToBytes for InventoryIndex {
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemId(pub u32);
 
 #[derive(Copy, Debug, Clone, ByteConvertable, FixedByteSize, PartialEq)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Sex {
     Female,
     Male,
@@ -327,6 +379,7 @@ pub struct CharacterBanListPacket {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterBanInformation {
     pub character_id: CharacterId,
     #[length(20)]
@@ -351,8 +404,9 @@ pub struct Packet0b18 {
     pub unknown: u16,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum ConnectionRefusedReason {
     #[numeric_value(1)]
@@ -385,9 +439,13 @@ pub struct MapServerLoginSuccessPacket {
     pub font: u16,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LoginFailedReason {
+    /// Sent by the map server if it couldn't authenticate the session.
+    #[numeric_value(0)]
+    AuthenticationFailed,
     #[numeric_value(1)]
     ServerClosed,
     #[numeric_value(2)]
@@ -396,7 +454,7 @@ pub enum LoginFailedReason {
     AlreadyOnline,
 }
 
-#[derive(Debug, Clone, Packet, ServerPacket, LoginServer, CharacterServer)]
+#[derive(Debug, Clone, Packet, ServerPacket, LoginServer, CharacterServer, MapServer)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
 #[header(0x0081)]
 pub struct LoginFailedPacket {
@@ -412,8 +470,9 @@ pub struct MapServerUnavailablePacket {
     pub unknown: String,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LoginFailedReason2 {
     UnregisteredId,
     IncorrectPassword,
@@ -433,8 +492,9 @@ pub struct LoginFailedPacket2 {
     pub reason: LoginFailedReason2,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CharacterSelectionFailedReason {
     RejectedFromServer,
 }
@@ -465,8 +525,9 @@ pub struct CharacterSelectionSuccessPacket {
     pub unknown: [u8; 128],
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CharacterCreationFailedReason {
     CharacterNameAlreadyUsed,
     NotOldEnough,
@@ -498,6 +559,7 @@ pub struct LoginServerKeepalivePacket {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterServerInformation {
     pub server_ip: ServerAddress,
     pub server_port: u16,
@@ -507,6 +569,7 @@ pub struct CharacterServerInformation {
     pub server_type: u16, // ServerType
     pub display_new: u16, // bool16 ?
     #[new_value([0; 128])]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
     pub unknown: [u8; 128],
 }
 
@@ -569,6 +632,7 @@ pub struct CreateCharacterPacket {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterInformation {
     pub character_id: CharacterId,
     pub experience: i64,
@@ -736,8 +800,9 @@ pub struct DeleteCharacterPacket {
     pub unknown: [u8; 10],
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CharacterDeletionFailedReason {
     NotAllowed,
     CharacterNotFound,
@@ -836,8 +901,9 @@ pub struct NewMailStatusPacket {
     pub new_available: u8,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AchievementData {
     pub acheivement_id: u32,
     pub is_completed: u8,
@@ -889,8 +955,9 @@ pub struct SpriteChangePacket {
     pub value2: u32,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SpriteChangeType {
     Base,
     Hair,
@@ -928,6 +995,7 @@ pub struct InventoyEndPacket {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemOptions {
     pub index: u16,
     pub value: u16,
@@ -937,6 +1005,7 @@ pub struct ItemOptions {
 bitflags::bitflags! {
     #[derive(Debug, Clone)]
     #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct RegularItemFlags: u8 {
         const IDENTIFIED = 0b01;
         const IN_ETC_TAB = 0b10;
@@ -963,6 +1032,7 @@ impl ToBytes for RegularItemFlags {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RegularItemInformation {
     pub index: InventoryIndex,
     pub item_id: ItemId,
@@ -987,6 +1057,7 @@ pub struct RegularItemListPacket {
 bitflags::bitflags! {
     #[derive(Debug, Clone)]
     #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct EquippableItemFlags: u8 {
         const IDENTIFIED = 0b001;
         const IS_BROKEN = 0b010;
@@ -1014,6 +1085,7 @@ impl ToBytes for EquippableItemFlags {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EquippableItemInformation {
     pub index: InventoryIndex,
     pub item_id: ItemId,
@@ -1043,6 +1115,7 @@ pub struct EquippableItemListPacket {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EquippableSwitchItemInformation {
     pub index: InventoryIndex,
     pub position: u32,
@@ -1133,6 +1206,7 @@ pub struct DisplayEmotionPacket {
 /// All UpdateStatPackets do the same, they just have different sizes
 /// correlating to the space the updated value requires.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StatType {
     Weight(u32),
     MaximumWeight(u32),
@@ -1453,6 +1527,7 @@ pub struct UpdateAttackRangePacket {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StatUpType {
     Strength { amount: u8 },
     Agility { amount: u8 },
@@ -1462,6 +1537,12 @@ pub enum StatUpType {
     Luck { amount: u8 },
 }
 
+impl FixedByteSize for StatUpType {
+    fn size_in_bytes() -> usize {
+        3
+    }
+}
+
 impl FromBytes for StatUpType {
     fn from_bytes(_: &mut ByteReader) -> ConversionResult<Self> {
         todo!()
@@ -1508,8 +1589,9 @@ pub struct RequestStatUpPacket {
 
 /// rAthena seems to always return [`Success`](RequestStatUpResult::Success),
 /// even if the request fails.
-#[derive(Debug, Clone, ByteConvertable, PartialEq, Eq)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize, PartialEq, Eq)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RequestStatUpResult {
     Failure,
     Success,
@@ -1538,8 +1620,9 @@ pub struct SwitchCharacterSlotPacket {
     pub remaining_moves: u16,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Action {
     Attack,
     PickUpItem,
@@ -1567,12 +1650,43 @@ pub struct ItemPickupRequestPacket {
     pub entity_id: EntityId,
 }
 
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0363)]
+pub struct DropItemPacket {
+    pub index: InventoryIndex,
+    pub amount: u16,
+}
+
+/// Sent by the client to the map server to use an item from the inventory.
+/// The account id is the id of the player using the item.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0439)]
+pub struct UseItemPacket {
+    pub index: InventoryIndex,
+    pub account_id: AccountId,
+}
+
 #[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
 #[header(0x00F3)]
 #[variable_length]
 pub struct GlobalMessagePacket {
-    #[length_remaining_off_by_one]
+    #[length_remaining]
+    pub message: String,
+}
+
+/// Sent by the client to the map server to send a private message to another
+/// player.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0096)]
+#[variable_length]
+pub struct WhisperPacket {
+    #[length(24)]
+    pub recipient_name: String,
+    #[length_remaining]
     pub message: String,
 }
 
@@ -1595,8 +1709,9 @@ pub struct UpdateEntityHealthPointsPacket {
     pub maximum_health_points: u32,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DamageType {
     Damage,
     PickUpItem,
@@ -1664,8 +1779,9 @@ pub struct RequestServerTickPacket {
     pub client_tick: ClientTick,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, ByteConvertable)]
+#[derive(Debug, Clone, PartialEq, Eq, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum SwitchCharacterSlotResponseStatus {
     Success,
@@ -1691,8 +1807,9 @@ pub struct ChangeMapPacket {
     pub position: TilePosition,
 }
 
-#[derive(Debug, Clone, ByteConvertable, PartialEq)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize, PartialEq)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DisappearanceReason {
     OutOfSight,
     Died,
@@ -1849,6 +1966,7 @@ pub struct EntityAppear2Packet {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u32)]
 pub enum SkillType {
     #[numeric_value(0)]
@@ -1867,6 +1985,7 @@ pub enum SkillType {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SkillInformation {
     pub skill_id: SkillId,
     pub skill_type: SkillType,
@@ -1888,15 +2007,17 @@ pub struct UpdateSkillTreePacket {
     pub skill_information: Vec<SkillInformation>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, ByteConvertable)]
+#[derive(Debug, Clone, PartialEq, Eq, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HotkeyType {
     Item,
     Skill,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, ByteConvertable)]
+#[derive(Debug, Clone, PartialEq, Eq, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HotkeyData {
     pub hotkey_type: HotkeyType,
     pub item_or_skill_id: u32,
@@ -1972,8 +2093,9 @@ pub struct NavigateToMonsterPacket {
     pub target_monster_id: u16,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u32)]
 pub enum MarkerType {
     DisplayFor15Seconds,
@@ -2016,8 +2138,9 @@ pub struct DialogMenuPacket {
     pub message: String,
 }
 
-#[derive(Debug, Clone, Copy, ByteConvertable)]
+#[derive(Debug, Clone, Copy, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u32)]
 pub enum EffectId {
     Hit1,
@@ -3175,7 +3298,9 @@ pub struct DisplaySpecialEffectPacket {
 #[header(0x043D)]
 pub struct DisplaySkillCooldownPacket {
     pub skill_id: SkillId,
-    pub until: ClientTick,
+    /// Remaining cooldown in milliseconds. Despite what the name of the
+    /// packet suggests, this is not an absolute tick.
+    pub duration: u32,
 }
 
 #[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
@@ -3194,8 +3319,9 @@ pub struct DisplaySkillEffectAndDamagePacket {
     pub skill_type: u8,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum HealType {
     #[numeric_value(5)]
@@ -3235,8 +3361,9 @@ pub struct StatusChangePacket {
     pub value: [u32; 3],
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectiveDetails1 {
     pub hunt_identification: u32,
     pub objective_type: u32,
@@ -3264,6 +3391,7 @@ pub struct QuestNotificationPacket1 {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HuntingObjective {
     pub quest_id: u32,
     pub mob_id: u32,
@@ -3299,6 +3427,7 @@ pub struct QuestRemovedPacket {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuestDetails {
     pub hunt_identification: u32,
     pub objective_type: u32,
@@ -3313,6 +3442,7 @@ pub struct QuestDetails {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Quest {
     pub quest_id: u32,
     pub active: u8,
@@ -3335,8 +3465,9 @@ pub struct QuestListPacket {
     pub quests: Vec<Quest>,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u32)]
 pub enum VisualEffect {
     BaseLevelUp,
@@ -3359,8 +3490,9 @@ pub struct VisualEffectPacket {
     pub effect: VisualEffect,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum ExperienceType {
     #[numeric_value(1)]
@@ -3368,8 +3500,9 @@ pub enum ExperienceType {
     JobExperience,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum ExperienceSource {
     Regular,
@@ -3386,8 +3519,9 @@ pub struct DisplayGainedExperiencePacket {
     pub experience_source: ExperienceSource,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageLocation {
     BottomLeft,
     BottomMiddle,
@@ -3481,8 +3615,9 @@ pub struct ItemDisappearPacket {
     pub entity_id: EntityId,
 }
 
-#[derive(Debug, Clone, ByteConvertable, PartialEq, Eq)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize, PartialEq, Eq)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ItemPickupResult {
     Success,
     Invalid,
@@ -3516,8 +3651,9 @@ pub struct ItemPickupPacket {
     pub enchantment_level: u8,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum RemoveItemReason {
     Normal,
@@ -3540,8 +3676,9 @@ pub struct RemoveItemFromInventoryPacket {
 }
 
 // TODO: improve names
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum QuestEffect {
     Quest,
@@ -3559,8 +3696,9 @@ pub enum QuestEffect {
     None,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum QuestColor {
     Yellow,
@@ -3638,6 +3776,7 @@ pub struct ChooseDialogOptionPacket {
 bitflags::bitflags! {
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct EquipPosition: u32 {
         const NONE = 0;
         const HEAD_LOWER = 1;
@@ -3693,8 +3832,9 @@ pub struct RequestEquipItemPacket {
     pub equip_position: EquipPosition,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RequestEquipItemStatus {
     Success,
     Failed,
@@ -3718,8 +3858,9 @@ pub struct EquipAmmunitionPacket {
     pub inventory_index: InventoryIndex,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AmmunitionActionType {
     EquipProperAmmunitionFirst,
     WeightLimitExceeded1,
@@ -3741,8 +3882,9 @@ pub struct RequestUnequipItemPacket {
     pub inventory_index: InventoryIndex,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RequestUnequipItemStatus {
     Success,
     Failed,
@@ -3757,8 +3899,9 @@ pub struct RequestUnequipItemStatusPacket {
     pub result: RequestUnequipItemStatus,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RestartType {
     Respawn,
     Disconnect,
@@ -3781,8 +3924,9 @@ pub struct RestartPacket {
 
 // TODO: check that this can be only 1 and 0, if not ByteConvertable
 // should be implemented manually
-#[derive(Debug, Clone, ByteConvertable, PartialEq, Eq)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize, PartialEq, Eq)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RestartResponseStatus {
     Nothing,
     Ok,
@@ -3797,8 +3941,9 @@ pub struct RestartResponsePacket {
 
 // TODO: check that this can be only 1 and 0, if not Named, ByteConvertable
 // should be implemented manually
-#[derive(Debug, Clone, ByteConvertable, PartialEq, Eq)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize, PartialEq, Eq)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum DisconnectResponseStatus {
     Ok,
@@ -3872,8 +4017,18 @@ pub struct ToUseSkillSuccessPacket {
     pub cause: u8,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+/// Sent by the map server when an entity stops casting a skill before the cast
+/// time is over.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x01B9)]
+pub struct CastCancelPacket {
+    pub entity_id: EntityId,
+}
+
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u32)]
 pub enum UnitId {
     #[numeric_value(0x7E)]
@@ -4101,6 +4256,7 @@ pub struct SkillUnitDisappearPacket {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Friend {
     pub account_id: AccountId,
     pub character_id: CharacterId,
@@ -4141,8 +4297,9 @@ pub struct FriendListPacket {
     pub friend_list: Vec<Friend>,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OnlineState {
     Online,
     Offline,
@@ -4166,8 +4323,9 @@ pub struct FriendRequestPacket {
     pub requestee: Friend,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u32)]
 pub enum FriendRequestResponse {
     Reject,
@@ -4183,8 +4341,9 @@ pub struct FriendRequestResponsePacket {
     pub response: FriendRequestResponse,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, ByteConvertable)]
+#[derive(Debug, Clone, PartialEq, Eq, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum FriendRequestResult {
     Accepted,
@@ -4210,8 +4369,27 @@ pub struct PartyInvitePacket {
     pub party_name: String,
 }
 
+/// Sent by the client to the map server to invite a player to the party of
+/// the player.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x02C4)]
+pub struct InviteToPartyPacket {
+    #[length(24)]
+    pub name: String,
+}
+
+/// Sent by the client to the map server to ask another player to trade.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00E4)]
+pub struct RequestTradePacket {
+    pub entity_id: EntityId,
+}
+
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReputationEntry {
     pub reputation_type: u64,
     pub points: i64,
@@ -4229,6 +4407,7 @@ pub struct ReputationPacket {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Aliance {
     #[length(24)]
     pub name: String,
@@ -4236,6 +4415,7 @@ pub struct Aliance {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Antagonist {
     #[length(24)]
     pub name: String,
@@ -4283,6 +4463,7 @@ pub struct ChangeMapCellPacket {
 
 #[derive(Debug, Clone, FixedByteSize, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MarketItemInformation {
     pub name_id: u32,
     pub item_type: u8,
@@ -4303,6 +4484,7 @@ pub struct OpenMarketPacket {
 
 #[derive(Debug, Clone, FixedByteSize, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShopItemInformation {
     pub item_id: ItemId,
     pub price: Price,
@@ -4328,8 +4510,9 @@ pub struct BuyOrSellPacket {
     pub shop_id: ShopId,
 }
 
-#[derive(Debug, Clone, ByteConvertable)]
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BuyOrSellOption {
     Buy,
     Sell,
@@ -4345,6 +4528,7 @@ pub struct SelectBuyOrSellPacket {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BuyItemResult {
     #[numeric_value(0)]
     Successful,
@@ -4370,6 +4554,7 @@ pub enum BuyItemResult {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BuyItemInformation {
     pub amount: u16,
     pub item_id: u16,
@@ -4386,6 +4571,7 @@ pub struct BuyItemsPacket {
 
 #[derive(Debug, Clone, FixedByteSize, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BuyShopItemInformation {
     pub item_id: ItemId,
     pub amount: u32,
@@ -4401,6 +4587,7 @@ pub struct BuyShopItemsPacket {
 
 #[derive(Debug, Clone, Copy, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum BuyShopItemsResult {
     #[numeric_value(0)]
@@ -4411,6 +4598,7 @@ pub enum BuyShopItemsResult {
 
 #[derive(Debug, Clone, FixedByteSize, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoughtShopItemInformation {
     pub item_id: ItemId,
     pub amount: u16,
@@ -4434,6 +4622,7 @@ pub struct CloseShopPacket {}
 
 #[derive(Debug, Clone, FixedByteSize, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SellItemInformation {
     pub inventory_index: InventoryIndex,
     pub price: Price,
@@ -4451,6 +4640,7 @@ pub struct SellListPacket {
 
 #[derive(Debug, Clone, FixedByteSize, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SoldItemInformation {
     pub inventory_index: InventoryIndex,
     pub amount: u16,
@@ -4465,8 +4655,9 @@ pub struct SellItemsPacket {
     pub items: Vec<SoldItemInformation>,
 }
 
-#[derive(Debug, Clone, Copy, ByteConvertable)]
+#[derive(Debug, Clone, Copy, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SellItemsResult {
     Success,
     Error,
@@ -4504,3 +4695,1134 @@ pub struct UpdateSkillPacket {
 pub struct RemoveSkillPacket {
     pub skill_id: SkillId,
 }
+
+/// Sent by the map server to update the number of items and the weight of
+/// the cart.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0121)]
+pub struct CartInformationPacket {
+    pub item_count: u16,
+    pub maximum_item_count: u16,
+    pub weight: u32,
+    pub maximum_weight: u32,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A0B)]
+pub struct CartItemAddedPacket {
+    pub index: InventoryIndex,
+    pub amount: u32,
+    pub item_id: ItemId,
+    pub item_type: u8,
+    pub is_identified: u8,
+    pub is_broken: u8,
+    pub refinement_level: u8,
+    pub cards: [u32; 4],
+    pub option_data: [ItemOptions; 5], // fix count
+    pub enchantment_level: u8,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0125)]
+pub struct CartItemRemovedPacket {
+    pub index: InventoryIndex,
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0126)]
+pub struct MoveItemToCartPacket {
+    pub inventory_index: InventoryIndex,
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0127)]
+pub struct MoveItemFromCartPacket {
+    pub cart_index: InventoryIndex,
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x012A)]
+pub struct RemoveCartPacket {}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x012B)]
+pub struct CartRemovedPacket {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveItemToCartResult {
+    Overweight,
+    TooManyItems,
+    NotAllowed,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x012C)]
+pub struct MoveItemToCartFailedPacket {
+    pub result: MoveItemToCartResult,
+}
+
+#[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MailId(pub u64);
+
+/// Mailbox that a mail is stored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MailOpenType {
+    Character,
+    Account,
+    Returned,
+    Unset,
+}
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct MailFlags: u8 {
+        const ZENY = 0b0010;
+        const ITEM = 0b0100;
+        const NPC = 0b1000;
+    }
+}
+
+impl FixedByteSize for MailFlags {
+    fn size_in_bytes() -> usize {
+        <<Self as bitflags::Flags>::Bits as FixedByteSize>::size_in_bytes()
+    }
+}
+
+impl FromBytes for MailFlags {
+    fn from_bytes(byte_reader: &mut ByteReader) -> ConversionResult<Self> {
+        <Self as bitflags::Flags>::Bits::from_bytes(byte_reader).map(Self::from_bits_truncate)
+    }
+}
+
+impl ToBytes for MailFlags {
+    fn to_bytes(&self, byte_writer: &mut ByteWriter) -> ConversionResult<usize> {
+        self.bits().to_bytes(byte_writer)
+    }
+}
+
+/// Sent by the client to the map server to open the mailbox and request the
+/// first page of mails.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0AC0)]
+pub struct OpenMailboxPacket {
+    pub open_type: MailOpenType,
+    pub mail_id: MailId,
+    #[new_default]
+    pub unused: [u8; 15],
+}
+
+/// Sent by the client to the map server to request the next page of mails.
+/// The mail id is the id of the last mail of the previous page.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09EF)]
+pub struct RequestNextMailPagePacket {
+    pub open_type: MailOpenType,
+    pub mail_id: MailId,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09E9)]
+pub struct CloseMailboxPacket {}
+
+#[derive(Debug, Clone, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MailInformation {
+    pub open_type: MailOpenType,
+    pub mail_id: MailId,
+    pub is_read: u8,
+    pub flags: MailFlags,
+    #[length(24)]
+    pub sender_name: String,
+    pub expiration_time: i32,
+    pub title_length: u16,
+    #[length(title_length)]
+    pub title: String,
+}
+
+/// Sent by the map server as a response to [`OpenMailboxPacket`] or
+/// [`RequestNextMailPagePacket`].
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0AC2)]
+#[variable_length]
+pub struct MailListPacket {
+    pub is_end: u8,
+    #[length_remaining]
+    pub mails: Vec<MailInformation>,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09EA)]
+pub struct ReadMailPacket {
+    pub open_type: MailOpenType,
+    pub mail_id: MailId,
+}
+
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MailItemInformation {
+    pub amount: u16,
+    pub item_id: ItemId,
+    pub is_identified: u8,
+    pub is_broken: u8,
+    pub refinement_level: u8,
+    pub cards: [u32; 4],
+    pub equip_position: EquipPosition,
+    pub item_type: u8,
+    pub view_sprite: u16,
+    pub bind_on_equip_type: u16,
+    pub option_data: [ItemOptions; 5], // fix count
+    pub enchantment_level: u8,
+}
+
+/// Sent by the map server as a response to [`ReadMailPacket`].
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09EB)]
+#[variable_length]
+pub struct MailContentPacket {
+    pub open_type: MailOpenType,
+    pub mail_id: MailId,
+    pub text_length: u16,
+    pub zeny: u64,
+    pub item_count: u8,
+    #[length(text_length)]
+    pub text: String,
+    #[repeating(item_count)]
+    pub items: Vec<MailItemInformation>,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09F5)]
+pub struct DeleteMailPacket {
+    pub open_type: MailOpenType,
+    pub mail_id: MailId,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09F6)]
+pub struct MailDeletedPacket {
+    pub open_type: MailOpenType,
+    pub mail_id: MailId,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09F1)]
+pub struct ClaimMailZenyPacket {
+    pub mail_id: MailId,
+    pub open_type: MailOpenType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClaimMailAttachmentResult {
+    Success,
+    Failed,
+    Overweight,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09F2)]
+pub struct ClaimMailZenyResultPacket {
+    pub mail_id: MailId,
+    pub open_type: MailOpenType,
+    pub result: ClaimMailAttachmentResult,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09F3)]
+pub struct ClaimMailItemsPacket {
+    pub mail_id: MailId,
+    pub open_type: MailOpenType,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09F4)]
+pub struct ClaimMailItemsResultPacket {
+    pub mail_id: MailId,
+    pub open_type: MailOpenType,
+    pub result: ClaimMailAttachmentResult,
+}
+
+/// Sent by the client to the map server to start writing a new mail.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A08)]
+pub struct OpenWriteMailPacket {
+    #[length(24)]
+    pub recipient_name: String,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A12)]
+pub struct OpenWriteMailResultPacket {
+    #[length(24)]
+    pub recipient_name: String,
+    pub success: u8,
+}
+
+/// Sent by the client to the map server to cancel writing a mail. Any
+/// attached items are returned to the inventory.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A03)]
+pub struct CancelWriteMailPacket {}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A04)]
+pub struct AddMailItemPacket {
+    pub inventory_index: InventoryIndex,
+    pub amount: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AddMailItemResult {
+    Success,
+    Overweight,
+    Failed,
+    TooManyItems,
+    NotTradable,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A05)]
+pub struct AddMailItemResultPacket {
+    pub result: AddMailItemResult,
+    pub inventory_index: InventoryIndex,
+    pub amount: u16,
+    pub item_id: ItemId,
+    pub item_type: u8,
+    pub is_identified: u8,
+    pub is_broken: u8,
+    pub refinement_level: u8,
+    pub cards: [u32; 4],
+    pub option_data: [ItemOptions; 5], // fix count
+    pub weight: u16,
+    pub favorite: u8,
+    pub equip_position: EquipPosition,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A06)]
+pub struct RemoveMailItemPacket {
+    pub inventory_index: InventoryIndex,
+    pub amount: u16,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A07)]
+pub struct RemoveMailItemResultPacket {
+    pub success: u8,
+    pub inventory_index: InventoryIndex,
+    pub amount: u16,
+    pub weight: u16,
+}
+
+/// Sent by the client to the map server to check if a character with the
+/// given name exists before sending a mail to them.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A13)]
+pub struct CheckMailRecipientPacket {
+    #[length(24)]
+    pub name: String,
+}
+
+/// Sent by the map server in response to [`CheckMailRecipientPacket`]. If no
+/// character with the given name exists, the character id is 0.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A14)]
+pub struct CheckMailRecipientResultPacket {
+    pub character_id: CharacterId,
+    pub job_id: JobId,
+    pub base_level: u16,
+}
+
+/// Sent by the client to the map server to send a mail. The attached items
+/// need to be added with [`AddMailItemPacket`] beforehand.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A6E)]
+#[variable_length]
+pub struct SendMailPacket {
+    #[length(24)]
+    pub recipient_name: String,
+    #[length(24)]
+    pub sender_name: String,
+    pub zeny: u64,
+    pub title_length: u16,
+    pub text_length: u16,
+    pub recipient_character_id: CharacterId,
+    #[length(title_length)]
+    pub title: String,
+    #[length(text_length)]
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SendMailResult {
+    Success,
+    Failed,
+    TooManyMailsSent,
+    ItemFailure,
+    CharacterNameCheckFailed,
+    WhisperBlocked,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09ED)]
+pub struct SendMailResultPacket {
+    pub result: SendMailResult,
+}
+
+/// Sent by the map server after the player used a taming item. The client
+/// should let the player select a monster to capture.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x019E)]
+pub struct StartPetCapturePacket {}
+
+/// Sent by the client to the map server to try capturing a monster as a pet.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x019F)]
+pub struct TryCapturePetPacket {
+    pub entity_id: EntityId,
+}
+
+/// Sent by the map server as a response to [`TryCapturePetPacket`].
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x01A0)]
+pub struct CapturePetResultPacket {
+    // TODO: bool
+    pub success: u8,
+}
+
+/// Sent by the map server after the player used a pet incubator. Contains
+/// the inventory indices of all pet eggs that can be hatched.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x01A6)]
+#[variable_length]
+pub struct PetEggListPacket {
+    #[repeating_remaining]
+    pub indices: Vec<InventoryIndex>,
+}
+
+/// Sent by the client to the map server to hatch a pet egg.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x01A7)]
+pub struct SelectPetEggPacket {
+    pub index: InventoryIndex,
+}
+
+/// Sent by the map server to update the information about the pet of the
+/// player.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x01A2)]
+pub struct PetInformationPacket {
+    #[length(24)]
+    pub name: String,
+    // TODO: bool
+    pub is_renamed: u8,
+    pub level: u16,
+    pub hunger: u16,
+    pub intimacy: u16,
+    pub accessory_id: ItemId,
+    pub job_id: JobId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PetStateType {
+    Initialize,
+    Intimacy,
+    Hunger,
+    Accessory,
+    Performance,
+    HairStyle,
+}
+
+/// Sent by the map server when a single property of a pet changes.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x01A4)]
+pub struct PetStateChangePacket {
+    pub state_type: PetStateType,
+    pub entity_id: EntityId,
+    pub value: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PetCommand {
+    RequestInformation,
+    Feed,
+    Performance,
+    ReturnToEgg,
+    UnequipAccessory,
+}
+
+/// Sent by the client to the map server to command the pet of the player.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x01A1)]
+pub struct PetCommandPacket {
+    pub command: PetCommand,
+}
+
+/// Sent by the map server as a response to feeding the pet.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x01A3)]
+pub struct FeedPetResultPacket {
+    // TODO: bool
+    pub success: u8,
+    pub item_id: ItemId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HomunculusStateType {
+    Initialize,
+    Intimacy,
+    Hunger,
+}
+
+/// Sent by the map server when a single property of a homunculus changes.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0230)]
+pub struct HomunculusStateChangePacket {
+    pub companion_type: u8,
+    pub state_type: HomunculusStateType,
+    pub entity_id: EntityId,
+    pub value: i32,
+}
+
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HomunculusStats {
+    pub attack: u16,
+    pub magic_attack: u16,
+    pub hit: u16,
+    pub critical: u16,
+    pub defense: u16,
+    pub magic_defense: u16,
+    pub flee: u16,
+    pub attack_speed: u16,
+    pub health_points: u32,
+    pub maximum_health_points: u32,
+    pub spell_points: u16,
+    pub maximum_spell_points: u16,
+    pub experience: u32,
+    pub next_level_experience: u32,
+    pub skill_points: u16,
+    pub attack_range: u16,
+}
+
+/// Sent by the map server to update the information about the homunculus of
+/// the player.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09F7)]
+pub struct HomunculusInformationPacket {
+    #[length(24)]
+    pub name: String,
+    pub flags: u8,
+    pub level: u16,
+    pub hunger: u16,
+    pub intimacy: u16,
+    pub equipped_item: u16,
+    pub stats: HomunculusStats,
+}
+
+/// Sent by the map server to update the skills of the homunculus.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0235)]
+#[variable_length]
+pub struct HomunculusSkillListPacket {
+    #[repeating_remaining]
+    pub skill_information: Vec<SkillInformation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HomunculusCommand {
+    RequestInformation,
+    Feed,
+    Delete,
+}
+
+/// Sent by the client to the map server to command the homunculus of the
+/// player.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x022D)]
+pub struct HomunculusCommandPacket {
+    #[new_default]
+    pub companion_type: u16,
+    pub command: HomunculusCommand,
+}
+
+/// Sent by the map server as a response to feeding the homunculus.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x022F)]
+pub struct FeedHomunculusResultPacket {
+    // TODO: bool
+    pub success: u8,
+    pub item_id: ItemId,
+}
+
+/// Sent by the client to the map server to move the homunculus to a position.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0232)]
+pub struct RequestHomunculusMovePacket {
+    pub entity_id: EntityId,
+    pub position: WorldPosition,
+}
+
+/// Sent by the client to the map server to make the homunculus attack a
+/// target.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0233)]
+pub struct RequestHomunculusAttackPacket {
+    pub entity_id: EntityId,
+    pub target_entity_id: EntityId,
+    // TODO: bool
+    pub continuous: u8,
+}
+
+/// Sent by the client to the map server to make the homunculus follow the
+/// player.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0234)]
+pub struct RequestHomunculusMoveToOwnerPacket {
+    pub entity_id: EntityId,
+}
+
+/// Sent by the client to the map server to level up a skill of the
+/// homunculus.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0239)]
+pub struct LevelUpHomunculusSkillPacket {
+    pub skill_id: SkillId,
+}
+
+#[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChatRoomId(pub u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChatRoomType {
+    /// Chat room created by a player that requires a password.
+    Private,
+    /// Chat room created by a player that anyone can join.
+    Public,
+    /// Chat room created by an NPC that requires a password.
+    NpcPrivate,
+    /// Chat room created by an NPC that anyone can join.
+    NpcPublic,
+}
+
+/// Sent by the client to the map server to create a new chat room.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00D5)]
+#[variable_length]
+pub struct CreateChatRoomPacket {
+    pub limit: u16,
+    // TODO: bool
+    pub is_public: u8,
+    #[length(8)]
+    pub password: String,
+    #[length_remaining]
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CreateChatRoomResult {
+    Success,
+    LimitExceeded,
+    AlreadyExists,
+}
+
+/// Sent by the map server in response to [`CreateChatRoomPacket`].
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00D6)]
+pub struct CreateChatRoomResultPacket {
+    pub result: CreateChatRoomResult,
+}
+
+/// Sent by the map server when a chat room appears on the map or the player
+/// comes into range of one. The title should be displayed above the owner.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00D7)]
+#[variable_length]
+pub struct ChatRoomAppearedPacket {
+    pub owner_entity_id: EntityId,
+    pub chat_room_id: ChatRoomId,
+    pub limit: u16,
+    pub user_count: u16,
+    pub room_type: ChatRoomType,
+    #[length_remaining]
+    pub title: String,
+}
+
+/// Sent by the map server when a chat room is closed.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00D8)]
+pub struct ChatRoomDestroyedPacket {
+    pub chat_room_id: ChatRoomId,
+}
+
+/// Sent by the client to the map server to join a chat room.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00D9)]
+pub struct JoinChatRoomPacket {
+    pub chat_room_id: ChatRoomId,
+    #[length(8)]
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JoinChatRoomFailedReason {
+    RoomFull,
+    WrongPassword,
+    Kicked,
+    Success,
+    NotEnoughZeny,
+    LevelTooLow,
+    LevelTooHigh,
+    UnsuitableJob,
+}
+
+/// Sent by the map server if the player could not join a chat room.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00DA)]
+pub struct JoinChatRoomFailedPacket {
+    pub reason: JoinChatRoomFailedReason,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[numeric_type(u32)]
+pub enum ChatRoomRole {
+    #[default]
+    Owner,
+    Member,
+}
+
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChatRoomMember {
+    pub role: ChatRoomRole,
+    #[length(24)]
+    pub name: String,
+}
+
+/// Sent by the map server after the player joined or created a chat room.
+/// Contains all current members of the room.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00DB)]
+#[variable_length]
+pub struct ChatRoomJoinedPacket {
+    pub chat_room_id: ChatRoomId,
+    #[repeating_remaining]
+    pub members: Vec<ChatRoomMember>,
+}
+
+/// Sent by the map server when a new member joined the chat room that the
+/// player is in.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00DC)]
+pub struct ChatRoomMemberJoinedPacket {
+    pub user_count: u16,
+    #[length(24)]
+    pub name: String,
+}
+
+/// Sent by the map server when a member left the chat room that the player
+/// is in. This is also sent if the player left the room.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00DD)]
+pub struct ChatRoomMemberLeftPacket {
+    pub user_count: u16,
+    #[length(24)]
+    pub name: String,
+    // TODO: bool
+    pub was_kicked: u8,
+}
+
+/// Sent by the client to the map server to change the settings of the chat
+/// room. Only works for the owner of the room.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00DE)]
+#[variable_length]
+pub struct ChangeChatRoomPacket {
+    pub limit: u16,
+    // TODO: bool
+    pub is_public: u8,
+    #[length(8)]
+    pub password: String,
+    #[length_remaining]
+    pub title: String,
+}
+
+/// Sent by the map server when the settings of a chat room changed.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00DF)]
+#[variable_length]
+pub struct ChatRoomChangedPacket {
+    pub owner_entity_id: EntityId,
+    pub chat_room_id: ChatRoomId,
+    pub limit: u16,
+    pub user_count: u16,
+    pub room_type: ChatRoomType,
+    #[length_remaining]
+    pub title: String,
+}
+
+/// Sent by the client to the map server to make another member the owner of
+/// the chat room.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00E0)]
+pub struct ChangeChatRoomOwnerPacket {
+    /// Always [`ChatRoomRole::Owner`].
+    #[new_default]
+    pub role: ChatRoomRole,
+    #[length(24)]
+    pub name: String,
+}
+
+/// Sent by the map server when the role of a member in the chat room changed.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00E1)]
+pub struct ChatRoomRoleChangedPacket {
+    pub role: ChatRoomRole,
+    #[length(24)]
+    pub name: String,
+}
+
+/// Sent by the client to the map server to kick a member from the chat room.
+/// Only works for the owner of the room.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00E2)]
+pub struct KickChatRoomMemberPacket {
+    #[length(24)]
+    pub name: String,
+}
+
+/// Sent by the client to the map server to leave the current chat room.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00E3)]
+pub struct LeaveChatRoomPacket {}
+
+#[cfg(test)]
+mod variable_length {
+    use ragnarok_bytes::{ByteReader, ByteWriter};
+
+    use crate::{ClientTick, GlobalMessagePacket, MailFlags, MailId, MailListPacket, MailOpenType, PacketExt, RequestServerTickPacket};
+
+    #[test]
+    fn global_message_includes_null_terminator() {
+        let mut byte_writer = ByteWriter::new();
+        GlobalMessagePacket::new("Hello".to_owned())
+            .packet_to_bytes(&mut byte_writer)
+            .unwrap();
+        RequestServerTickPacket::new(ClientTick(100))
+            .packet_to_bytes(&mut byte_writer)
+            .unwrap();
+
+        let bytes = byte_writer.into_inner();
+
+        // Servers expect the length to include the null terminator of the message.
+        assert_eq!(&bytes[..4], &[0xF3, 0x00, 10, 0]);
+        assert_eq!(&bytes[4..10], b"Hello\0");
+
+        let mut byte_reader = ByteReader::without_metadata(&bytes);
+        let packet = GlobalMessagePacket::packet_from_bytes(&mut byte_reader).unwrap();
+
+        assert_eq!(packet.message, "Hello");
+        assert_eq!(byte_reader.get_offset(), 10);
+        assert!(RequestServerTickPacket::packet_from_bytes(&mut byte_reader).is_ok());
+    }
+
+    /// Raw mail list entry, laid out the same way as rAthena sends it.
+    fn mail_entry(open_type: u8, mail_id: u64, is_read: u8, flags: u8, sender_name: &str, expiration_time: i32, title: &str) -> Vec<u8> {
+        let mut sender_name_bytes = [0; 24];
+        sender_name_bytes[..sender_name.len()].copy_from_slice(sender_name.as_bytes());
+
+        let mut bytes = vec![open_type];
+        bytes.extend_from_slice(&mail_id.to_le_bytes());
+        bytes.extend_from_slice(&[is_read, flags]);
+        bytes.extend_from_slice(&sender_name_bytes);
+        bytes.extend_from_slice(&expiration_time.to_le_bytes());
+        // The title length includes the null terminator.
+        bytes.extend_from_slice(&(title.len() as u16 + 1).to_le_bytes());
+        bytes.extend_from_slice(title.as_bytes());
+        bytes.push(0);
+        bytes
+    }
+
+    #[test]
+    fn mail_list() {
+        let first_mail = mail_entry(0, 1001, 0, 0b0010, "Kafra", 604800, "Hello");
+        let second_mail = mail_entry(1, 1002, 1, 0b1100, "Poring", 0, "Items");
+        let packet_length = 5 + first_mail.len() + second_mail.len();
+
+        let mut bytes = vec![0xC2, 0x0A];
+        bytes.extend_from_slice(&(packet_length as u16).to_le_bytes());
+        bytes.push(1);
+        bytes.extend_from_slice(&first_mail);
+        bytes.extend_from_slice(&second_mail);
+
+        let mut byte_reader = ByteReader::without_metadata(&bytes);
+        let packet = MailListPacket::packet_from_bytes(&mut byte_reader).unwrap();
+
+        assert_eq!(packet.is_end, 1);
+        assert_eq!(packet.mails.len(), 2);
+        assert_eq!(byte_reader.get_offset(), packet_length);
+
+        assert_eq!(packet.mails[0].open_type, MailOpenType::Character);
+        assert_eq!(packet.mails[0].mail_id, MailId(1001));
+        assert_eq!(packet.mails[0].is_read, 0);
+        assert_eq!(packet.mails[0].flags, MailFlags::ZENY);
+        assert_eq!(packet.mails[0].sender_name, "Kafra");
+        assert_eq!(packet.mails[0].expiration_time, 604800);
+        assert_eq!(packet.mails[0].title, "Hello");
+
+        assert_eq!(packet.mails[1].open_type, MailOpenType::Account);
+        assert_eq!(packet.mails[1].mail_id, MailId(1002));
+        assert_eq!(packet.mails[1].is_read, 1);
+        assert_eq!(packet.mails[1].flags, MailFlags::ITEM | MailFlags::NPC);
+        assert_eq!(packet.mails[1].sender_name, "Poring");
+        assert_eq!(packet.mails[1].title, "Items");
+    }
+}
+
+#[cfg(test)]
+mod fixed_length {
+    use ragnarok_bytes::ByteReader;
+
+    use crate::{CapturePetResultPacket, CartItemAddedPacket, InventoryIndex, ItemId, PacketExt};
+
+    #[test]
+    fn cart_item_added() {
+        // ZC_ADD_ITEM_TO_CART as sent by rAthena for 120 Red Potions in cart slot 3.
+        #[rustfmt::skip]
+        let bytes = [
+            0x0B, 0x0A, // header
+            0x05, 0x00, // index
+            0x78, 0x00, 0x00, 0x00, // amount
+            0xF5, 0x01, 0x00, 0x00, // item id
+            0x00, // item type
+            0x01, // is identified
+            0x00, // is broken
+            0x00, // refinement level
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // cards
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // options
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00,
+            0x07, // enchantment level
+        ];
+
+        let mut byte_reader = ByteReader::without_metadata(&bytes);
+        let packet = CartItemAddedPacket::packet_from_bytes(&mut byte_reader).unwrap();
+
+        assert_eq!(packet.index, InventoryIndex(3));
+        assert_eq!(packet.amount, 120);
+        assert_eq!(packet.item_id, ItemId(501));
+        assert_eq!(packet.is_identified, 1);
+        assert_eq!(packet.enchantment_level, 7);
+        assert_eq!(byte_reader.get_offset(), 58);
+    }
+
+    #[test]
+    fn capture_pet_result() {
+        // ZC_TRYCAPTURE_MONSTER as sent by rAthena after a successful capture.
+        let bytes = [0xA0, 0x01, 0x01];
+
+        let mut byte_reader = ByteReader::without_metadata(&bytes);
+        let packet = CapturePetResultPacket::packet_from_bytes(&mut byte_reader).unwrap();
+
+        assert_eq!(packet.success, 1);
+        assert_eq!(byte_reader.get_offset(), 3);
+    }
+
+    #[test]
+    fn check_mail_recipient_result() {
+        let bytes = [0x14, 0x0A, 0xF0, 0x49, 0x02, 0x00, 0x0C, 0x00, 0x63, 0x00];
+
+        let mut byte_reader = ByteReader::without_metadata(&bytes);
+        let packet = CheckMailRecipientResultPacket::packet_from_bytes(&mut byte_reader).unwrap();
+
+        assert_eq!(packet.character_id, CharacterId(150000));
+        assert_eq!(packet.job_id, JobId(12));
+        assert_eq!(packet.base_level, 99);
+        assert_eq!(byte_reader.get_offset(), 10);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serialization {
+    use ragnarok_bytes::{ByteReader, ByteWriter};
+
+    use crate::handler::{HandlerResult, NoPacketCallback, PacketHandler};
+    use crate::{
+        AccountId, CancelWriteMailPacket, CharacterId, CharacterSelectionSuccessPacket, ClientTick, LoginServerLoginPacket,
+        MapServerLoginPacket, PacketExt, RequestServerTickPacket, ServerAddress, Sex,
+    };
+
+    #[test]
+    fn includes_header() {
+        let packet = RequestServerTickPacket::new(ClientTick(1234));
+        let json = serde_json::to_value(&packet).unwrap();
+
+        assert_eq!(json, serde_json::json!({ "header": 0x0360, "client_tick": 1234 }));
+    }
+
+    #[test]
+    fn round_trip() {
+        let packet = MapServerLoginPacket::new(AccountId(2000000), CharacterId(150000), 1234, ClientTick(100), Sex::Female);
+        let json = serde_json::to_string(&packet).unwrap();
+        let deserialized: MapServerLoginPacket = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.account_id, packet.account_id);
+        assert_eq!(deserialized.character_id, packet.character_id);
+        assert_eq!(deserialized.login_id1, packet.login_id1);
+        assert_eq!(deserialized.sex, packet.sex);
+    }
+
+    #[test]
+    fn large_array() {
+        let packet = CharacterSelectionSuccessPacket::new(
+            CharacterId(150000),
+            "prontera.gat".to_owned(),
+            ServerAddress([127, 0, 0, 1]),
+            5121,
+        );
+        let json = serde_json::to_string(&packet).unwrap();
+        let deserialized: CharacterSelectionSuccessPacket = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.map_name, packet.map_name);
+        assert_eq!(deserialized.unknown, packet.unknown);
+    }
+
+    #[test]
+    fn no_fields() {
+        let json = serde_json::to_value(CancelWriteMailPacket::new()).unwrap();
+
+        assert_eq!(json, serde_json::json!({ "header": 0x0A03 }));
+        assert!(serde_json::from_value::<CancelWriteMailPacket>(json).is_ok());
+    }
+
+    #[test]
+    fn mismatched_header() {
+        let json = serde_json::json!({ "header": 0x0361, "client_tick": 1234 });
+
+        assert!(serde_json::from_value::<RequestServerTickPacket>(json).is_err());
+    }
+
+    #[test]
+    fn replay() {
+        let packet = LoginServerLoginPacket::new("username".to_owned(), "password".to_owned());
+        let json = serde_json::to_string(&packet).unwrap();
+        let deserialized: LoginServerLoginPacket = serde_json::from_str(&json).unwrap();
+
+        let mut byte_writer = ByteWriter::new();
+        deserialized.packet_to_bytes(&mut byte_writer).unwrap();
+
+        let mut packet_handler = PacketHandler::<Option<String>, NoPacketCallback>::default();
+        packet_handler.register(|packet: LoginServerLoginPacket| Some(packet.name)).unwrap();
+
+        let mut byte_reader = ByteReader::without_metadata(byte_writer.as_slice());
+
+        match packet_handler.process_one(&mut byte_reader) {
+            HandlerResult::Ok(name) => assert_eq!(name.as_deref(), Some("username")),
+            _ => panic!("failed to replay packet"),
+        }
+    }
+}
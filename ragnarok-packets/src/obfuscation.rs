@@ -0,0 +1,111 @@
+use crate::PacketHeader;
+
+/// The keys used by a client date to obfuscate the headers of packets sent to
+/// the map server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacketObfuscationKeys {
+    /// Initial state of the key.
+    pub key1: u32,
+    /// Multiplier used to advance the key.
+    pub key2: u32,
+    /// Increment used to advance the key.
+    pub key3: u32,
+}
+
+/// Obfuscates packet headers using a linear congruential generator seeded by
+/// [`PacketObfuscationKeys`].
+///
+/// Before every packet the key is advanced, and bits 16 to 30 of the new key
+/// are XOR-ed onto the header. Since the same operation reverses the
+/// obfuscation, servers can use this to read obfuscated headers as well.
+///
+/// The state has to be created fresh for each connection and every packet has
+/// to pass through it in the order it is sent, otherwise client and server
+/// will be out of sync.
+#[derive(Debug, Clone)]
+pub struct PacketObfuscation {
+    keys: PacketObfuscationKeys,
+    current_key: u32,
+}
+
+impl PacketObfuscation {
+    pub fn new(keys: PacketObfuscationKeys) -> Self {
+        Self {
+            keys,
+            current_key: keys.key1,
+        }
+    }
+
+    /// Advance the key and get the mask for the next packet.
+    fn next_mask(&mut self) -> u16 {
+        self.current_key = self.current_key.wrapping_mul(self.keys.key2).wrapping_add(self.keys.key3);
+        ((self.current_key >> 16) & 0x7FFF) as u16
+    }
+
+    /// Obfuscate (or de-obfuscate) the header of the next packet.
+    pub fn apply_to_header(&mut self, header: PacketHeader) -> PacketHeader {
+        PacketHeader(header.0 ^ self.next_mask())
+    }
+
+    /// Obfuscate (or de-obfuscate) the header of the next packet in place.
+    /// `packet` has to contain exactly one packet *including* the header.
+    pub fn apply_to_packet(&mut self, packet: &mut [u8]) {
+        let Some(header_bytes) = packet.first_chunk_mut::<2>() else {
+            return;
+        };
+
+        let header = self.apply_to_header(PacketHeader(u16::from_le_bytes(*header_bytes)));
+        *header_bytes = header.0.to_le_bytes();
+    }
+}
+
+#[cfg(test)]
+mod key_sequence {
+    use super::{PacketObfuscation, PacketObfuscationKeys};
+    use crate::PacketHeader;
+
+    const KEYS: PacketObfuscationKeys = PacketObfuscationKeys {
+        key1: 0x4C17382A,
+        key2: 0x7ED14845,
+        key3: 0x5F8E3D2C,
+    };
+
+    #[test]
+    fn masks() {
+        let mut obfuscation = PacketObfuscation::new(KEYS);
+        let masks: Vec<u16> = (0..4).map(|_| obfuscation.apply_to_header(PacketHeader(0)).0).collect();
+
+        assert_eq!(masks, [0x6FE6, 0x611A, 0x5847, 0x74DD]);
+    }
+
+    #[test]
+    fn headers() {
+        let mut obfuscation = PacketObfuscation::new(KEYS);
+
+        assert_eq!(obfuscation.apply_to_header(PacketHeader(0x0436)), PacketHeader(0x6BD0));
+        assert_eq!(obfuscation.apply_to_header(PacketHeader(0x0360)), PacketHeader(0x627A));
+        assert_eq!(obfuscation.apply_to_header(PacketHeader(0x0436)), PacketHeader(0x5C71));
+        assert_eq!(obfuscation.apply_to_header(PacketHeader(0x0360)), PacketHeader(0x77BD));
+    }
+
+    #[test]
+    fn packet_in_place() {
+        let mut obfuscation = PacketObfuscation::new(KEYS);
+        let mut packet = [0x36, 0x04, 0xAA, 0xBB];
+
+        obfuscation.apply_to_packet(&mut packet);
+
+        assert_eq!(packet, [0xD0, 0x6B, 0xAA, 0xBB]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut client = PacketObfuscation::new(KEYS);
+        let mut server = PacketObfuscation::new(KEYS);
+
+        for header in [0x0436, 0x0360, 0x007D, 0x00F3, 0x0360] {
+            let obfuscated = client.apply_to_header(PacketHeader(header));
+            assert_eq!(server.apply_to_header(obfuscated), PacketHeader(header));
+        }
+    }
+}
@@ -1,9 +1,10 @@
-use ragnarok_bytes::{ByteConvertable, ByteReader, ByteWriter, ConversionResult, FromBytes, ToBytes};
+use ragnarok_bytes::{ByteConvertable, ByteReader, ByteWriter, ConversionResult, FixedByteSize, FromBytes, ToBytes};
 
 use crate::TilePosition;
 
 #[derive(Debug, Copy, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     North = 0,
     NorthEast = 1,
@@ -62,6 +63,7 @@ impl TryFrom<[isize; 2]> for Direction {
 
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WorldPosition {
     pub x: u16,
     pub y: u16,
@@ -86,6 +88,12 @@ impl WorldPosition {
     }
 }
 
+impl FixedByteSize for WorldPosition {
+    fn size_in_bytes() -> usize {
+        3
+    }
+}
+
 impl FromBytes for WorldPosition {
     fn from_bytes(byte_reader: &mut ByteReader) -> ConversionResult<Self> {
         let coordinates: Vec<u16> = byte_reader.slice::<Self>(3)?.iter().map(|byte| *byte as u16).collect();
@@ -122,6 +130,7 @@ impl ToBytes for WorldPosition {
 
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WorldPosition2 {
     pub x1: u16,
     pub y1: u16,
@@ -157,6 +166,12 @@ impl WorldPosition2 {
     }
 }
 
+impl FixedByteSize for WorldPosition2 {
+    fn size_in_bytes() -> usize {
+        6
+    }
+}
+
 impl FromBytes for WorldPosition2 {
     fn from_bytes(byte_reader: &mut ByteReader) -> ConversionResult<Self> {
         let coordinates: Vec<u16> = byte_reader.slice::<Self>(6)?.iter().map(|byte| *byte as u16).collect();
@@ -0,0 +1,68 @@
+//! Serde only implements its traits for arrays with up to 32 elements, so
+//! larger arrays are (de)serialized with these functions instead.
+
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub fn serialize<S, T, const N: usize>(array: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    let mut tuple = serializer.serialize_tuple(N)?;
+
+    for element in array {
+        tuple.serialize_element(element)?;
+    }
+
+    tuple.end()
+}
+
+/// Like [`serialize`], for fields that are borrowed by the serialized shadow
+/// struct.
+pub fn serialize_reference<S, T, const N: usize>(array: &&[T; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    serialize(*array, serializer)
+}
+
+pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+    impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+    where
+        T: Deserialize<'de>,
+    {
+        type Value = [T; N];
+
+        fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+            write!(formatter, "an array of length {N}")
+        }
+
+        fn visit_seq<A>(self, mut sequence: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut elements = Vec::with_capacity(N);
+
+            while let Some(element) = sequence.next_element()? {
+                elements.push(element);
+            }
+
+            let length = elements.len();
+            elements.try_into().map_err(|_| A::Error::invalid_length(length, &self))
+        }
+    }
+
+    deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+}